@@ -0,0 +1,136 @@
+//! 流式收集内存基准
+//!
+//! 对一个大型页面分别执行“解析为 DOM 再收集”和 `stream_collect_texts` 流式收集，
+//! 通过统计分配器记录两者各自的峰值堆内存。不传参数时生成约 50MB 的测试页面，
+//! 也可以传入本地 HTML 文件的路径。
+//!
+//! 运行方式：
+//!
+//! ```bash
+//! cargo run --release --example stream_collect_memory --features translation [-- page.html]
+//! ```
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fs::File;
+use std::io::{BufReader, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use monolith::html::html_to_dom;
+use monolith::translation::pipeline::collector::TextCollector;
+
+const PARAGRAPHS: usize = 200_000;
+
+/// 记录当前和峰值分配量的分配器
+struct CountingAllocator;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let current = CURRENT.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(current, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// 从当前分配量开始重新记录峰值
+fn reset_peak() -> usize {
+    let current = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(current, Ordering::Relaxed);
+    current
+}
+
+fn peak_since(baseline: usize) -> usize {
+    PEAK.load(Ordering::Relaxed).saturating_sub(baseline)
+}
+
+fn megabytes(bytes: usize) -> f64 {
+    bytes as f64 / (1024.0 * 1024.0)
+}
+
+/// 生成测试页面写入临时文件，返回文件路径
+fn build_large_page() -> std::io::Result<std::path::PathBuf> {
+    let path = std::env::temp_dir().join("monolith_stream_collect_memory.html");
+    let mut file = std::io::BufWriter::new(File::create(&path)?);
+    write!(file, "<html><head><title>Large page</title></head><body>")?;
+    for i in 0..PARAGRAPHS {
+        write!(
+            file,
+            "<div class=\"entry\"><h2>Section {i}</h2><p title=\"Paragraph {i}\">This is paragraph \
+             number {i} of a very large document, used to compare the memory needed to collect \
+             its text.</p><img src=\"img/{i}.png\" alt=\"Illustration {i}\"></div>"
+        )?;
+    }
+    write!(file, "</body></html>")?;
+    file.flush()?;
+    Ok(path)
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let (path, generated) = match std::env::args().nth(1) {
+        Some(path) => (path.into(), false),
+        None => (build_large_page()?, true),
+    };
+    let size = std::fs::metadata(&path)?.len() as usize;
+
+    // DOM 路径：读入整个文件，解析为 DOM 后收集
+    let baseline = reset_peak();
+    let start = Instant::now();
+    let dom_count = {
+        let data = std::fs::read(&path)?;
+        let dom = html_to_dom(&data, "utf-8".to_string());
+        drop(data);
+        let mut collector = TextCollector::default();
+        collector.collect_translatable_texts(&dom.document)?.len()
+    };
+    let dom_time = start.elapsed();
+    let dom_peak = peak_since(baseline);
+
+    // 流式路径：逐块读取文件
+    let baseline = reset_peak();
+    let start = Instant::now();
+    let stream_count = {
+        let mut collector = TextCollector::default();
+        collector
+            .stream_collect_texts(BufReader::new(File::open(&path)?))?
+            .len()
+    };
+    let stream_time = start.elapsed();
+    let stream_peak = peak_since(baseline);
+
+    println!("页面大小: {:.1} MB", megabytes(size));
+    println!(
+        "DOM 收集: 峰值 {:.1} MB，{} 条文本，耗时 {:?}",
+        megabytes(dom_peak),
+        dom_count,
+        dom_time
+    );
+    println!(
+        "流式收集: 峰值 {:.1} MB，{} 条文本，耗时 {:?}",
+        megabytes(stream_peak),
+        stream_count,
+        stream_time
+    );
+    println!(
+        "峰值内存减少: {:.1}%",
+        100.0 * (1.0 - stream_peak as f64 / (dom_peak as f64).max(1.0))
+    );
+
+    if generated {
+        std::fs::remove_file(&path)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,77 @@
+//! 翻译快速路径基准
+//!
+//! 对一个全中文页面执行“翻译到中文”，比较完整的收集+过滤+分批流程
+//! 与 `TranslationService::translate_dom` 快速路径各自的耗时。
+//!
+//! 运行方式：
+//!
+//! ```bash
+//! cargo run --release --example translation_fast_path --features translation
+//! ```
+
+use std::time::{Duration, Instant};
+
+use monolith::html::html_to_dom;
+use monolith::translation::pipeline::batch::{BatchManager, BatchManagerConfig};
+use monolith::translation::pipeline::collector::TextCollector;
+use monolith::translation::{TranslationConfig, TranslationService};
+
+const ITERATIONS: u32 = 20;
+const PARAGRAPHS: usize = 2000;
+
+fn build_chinese_page() -> Vec<u8> {
+    let mut html = String::from("<html><head><title>测试页面</title></head><body>");
+    for i in 0..PARAGRAPHS {
+        html.push_str(&format!(
+            "<p title=\"第{i}段\">这是第{i}段中文内容，用于测试翻译服务在页面已是目标语言时的快速返回。</p>"
+        ));
+    }
+    html.push_str("</body></html>");
+    html.into_bytes()
+}
+
+fn average(total: Duration) -> Duration {
+    total / ITERATIONS
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let page = build_chinese_page();
+    let config = TranslationConfig::default_with_lang("zh", None);
+
+    // 完整流程：收集、过滤并尝试分批
+    let mut full_total = Duration::ZERO;
+    for _ in 0..ITERATIONS {
+        let dom = html_to_dom(&page, "utf-8".to_string());
+        let start = Instant::now();
+        let mut collector = TextCollector::default();
+        let texts = collector.collect_translatable_texts(&dom.document)?;
+        let mut batch_manager = BatchManager::new(BatchManagerConfig::from(&config));
+        let _ = batch_manager.create_batches(texts);
+        full_total += start.elapsed();
+    }
+
+    // 快速路径：抽样发现页面已是中文后直接返回
+    let mut service = TranslationService::new(config)?;
+    let mut fast_total = Duration::ZERO;
+    for _ in 0..ITERATIONS {
+        let dom = html_to_dom(&page, "utf-8".to_string());
+        let start = Instant::now();
+        let _ = service.translate_dom(dom).await?;
+        fast_total += start.elapsed();
+        assert_eq!(service.get_stats().snapshot().skipped_empty, 1);
+    }
+
+    let full = average(full_total);
+    let fast = average(fast_total);
+    println!("段落数: {}，迭代次数: {}", PARAGRAPHS, ITERATIONS);
+    println!("完整流程平均耗时: {:?}", full);
+    println!("快速路径平均耗时: {:?}", fast);
+    println!(
+        "节省: {:?} ({:.1}%)",
+        full.saturating_sub(fast),
+        100.0 * (1.0 - fast.as_secs_f64() / full.as_secs_f64().max(f64::EPSILON))
+    );
+
+    Ok(())
+}
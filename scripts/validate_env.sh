@@ -183,6 +183,7 @@ echo -e "\n${BLUE}Web服务器配置验证:${NC}"
 check_var "MONOLITH_WEB_BIND_ADDRESS" "绑定地址" false
 check_var "MONOLITH_WEB_PORT" "监听端口" false "validate_port"
 check_var "MONOLITH_WEB_STATIC_DIR" "静态文件目录" false
+check_var "MONOLITH_WEB_LIBRARY_DIR" "文档库目录" false
 
 echo -e "\n${BLUE}MongoDB配置验证:${NC}"
 check_var "MONGODB_URL" "MongoDB连接字符串" false "validate_mongodb_url"
@@ -0,0 +1,193 @@
+//! JSON 构建器模块
+//!
+//! 为数据处理流程生成页面的机器可读摘要，而不是 HTML：
+//! 标题、字符集、社交分享元数据、嵌入的资源（URL、媒体类型、大小）、指向其他页面的链接，
+//! 以及执行过翻译时的原文→译文对照。
+//!
+//! 资源列表来自 [`Session::resources`](crate::network::Session::resources)，
+//! 链接列表由 [`collect_links_in_dom`] 从最终 DOM 中收集。
+//! 核心库不依赖 serde，因此 JSON 在这里直接手工拼接。
+
+use markup5ever_rcdom::Handle;
+
+use crate::network::EmbeddedResource;
+use crate::parsers::html::{get_social_metadata, get_title, SocialMetadata};
+use crate::parsers::link_rewriter::collect_links_in_dom;
+use crate::utils::url::Url;
+
+/// JSON 输出的文档摘要
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct JsonDocument {
+    pub title: Option<String>,
+    pub charset: String,
+    /// OpenGraph/Twitter 卡片元数据，预览图地址已按基础URL解析；为空时输出中省略该字段
+    pub social: SocialMetadata,
+    pub resources: Vec<EmbeddedResource>,
+    pub links: Vec<String>,
+    /// 原文与译文对照；为 `None` 表示未执行翻译，输出中省略该字段
+    pub translations: Option<Vec<(String, String)>>,
+    /// 内容指纹；为 `None` 时输出中省略该字段
+    pub fingerprint: Option<String>,
+}
+
+impl JsonDocument {
+    /// 从最终 DOM 和会话记录的资源创建文档摘要
+    pub fn new(
+        node: &Handle,
+        charset: &str,
+        base_url: &Url,
+        resources: &[EmbeddedResource],
+    ) -> Self {
+        Self {
+            title: get_title(node),
+            charset: charset.to_string(),
+            social: resolve_social_image(get_social_metadata(node), base_url),
+            resources: resources.to_vec(),
+            links: collect_links_in_dom(node, base_url)
+                .into_iter()
+                .map(|url| url.to_string())
+                .collect(),
+            translations: None,
+            fingerprint: None,
+        }
+    }
+
+    /// 附加翻译对照
+    pub fn with_translations(mut self, translations: Vec<(String, String)>) -> Self {
+        self.translations = Some(translations);
+        self
+    }
+
+    /// 附加内容指纹
+    pub fn with_fingerprint(mut self, fingerprint: String) -> Self {
+        self.fingerprint = Some(fingerprint);
+        self
+    }
+
+    /// 序列化为格式化的 JSON 字符串（以换行结尾）
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("{\n");
+
+        json.push_str("  \"title\": ");
+        match &self.title {
+            Some(title) => push_json_string(&mut json, title),
+            None => json.push_str("null"),
+        }
+        json.push_str(",\n  \"charset\": ");
+        push_json_string(&mut json, &self.charset);
+
+        if let Some(fingerprint) = &self.fingerprint {
+            json.push_str(",\n  \"fingerprint\": ");
+            push_json_string(&mut json, fingerprint);
+        }
+
+        if !self.social.is_empty() {
+            json.push_str(",\n  \"social\": {");
+            let fields = [
+                ("title", &self.social.title),
+                ("description", &self.social.description),
+                ("image", &self.social.image),
+                ("type", &self.social.kind),
+            ];
+            for (i, (name, value)) in fields.iter().enumerate() {
+                json.push_str(if i == 0 { "\"" } else { ", \"" });
+                json.push_str(name);
+                json.push_str("\": ");
+                match value {
+                    Some(value) => push_json_string(&mut json, value),
+                    None => json.push_str("null"),
+                }
+            }
+            json.push('}');
+        }
+
+        json.push_str(",\n  \"resources\": [");
+        for (i, resource) in self.resources.iter().enumerate() {
+            json.push_str(if i == 0 { "\n" } else { ",\n" });
+            json.push_str("    {\"url\": ");
+            push_json_string(&mut json, &resource.url);
+            json.push_str(", \"media_type\": ");
+            push_json_string(&mut json, &resource.media_type);
+            json.push_str(&format!(", \"size\": {}}}", resource.size));
+        }
+        close_array(&mut json, self.resources.is_empty());
+
+        json.push_str(",\n  \"links\": [");
+        for (i, link) in self.links.iter().enumerate() {
+            json.push_str(if i == 0 { "\n    " } else { ",\n    " });
+            push_json_string(&mut json, link);
+        }
+        close_array(&mut json, self.links.is_empty());
+
+        if let Some(translations) = &self.translations {
+            json.push_str(",\n  \"translations\": [");
+            for (i, (source, target)) in translations.iter().enumerate() {
+                json.push_str(if i == 0 { "\n" } else { ",\n" });
+                json.push_str("    {\"source\": ");
+                push_json_string(&mut json, source);
+                json.push_str(", \"target\": ");
+                push_json_string(&mut json, target);
+                json.push('}');
+            }
+            close_array(&mut json, translations.is_empty());
+        }
+
+        json.push_str("\n}\n");
+        json
+    }
+}
+
+/// 把相对的预览图地址解析为绝对地址，无法解析时保持原样
+fn resolve_social_image(mut social: SocialMetadata, base_url: &Url) -> SocialMetadata {
+    if let Some(image) = &social.image {
+        if let Ok(resolved) = base_url.join(image) {
+            social.image = Some(resolved.to_string());
+        }
+    }
+    social
+}
+
+/// 将 DOM 树及其嵌入资源转换为 JSON 摘要
+///
+/// # 示例
+///
+/// ```rust
+/// use monolith::builders::build_json;
+/// use monolith::html::html_to_dom;
+/// use monolith::url::Url;
+///
+/// let dom = html_to_dom(b"<title>Page</title><a href=\"/next\">Next</a>", "".to_string());
+/// let base_url = Url::parse("https://example.com/").unwrap();
+/// let json = build_json(&dom.document, "UTF-8", &base_url, &[]);
+/// assert!(json.contains("\"title\": \"Page\""));
+/// assert!(json.contains("\"https://example.com/next\""));
+/// ```
+pub fn build_json(
+    node: &Handle,
+    charset: &str,
+    base_url: &Url,
+    resources: &[EmbeddedResource],
+) -> String {
+    JsonDocument::new(node, charset, base_url, resources).to_json()
+}
+
+fn close_array(json: &mut String, empty: bool) {
+    json.push_str(if empty { "]" } else { "\n  ]" });
+}
+
+/// 追加带引号并按 JSON 规则转义的字符串
+fn push_json_string(json: &mut String, value: &str) {
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
@@ -4,14 +4,22 @@
 //!
 //! - HTML模板构建器
 //! - 响应格式构建器
+//! - 纯文本构建器
+//! - JSON 摘要构建器
 //!
 //! # 模块组织
 //!
 //! - `html_builder` - HTML模板构建和渲染（需要web特性）
+//! - `text_builder` - 从DOM提取可见文本，用于纯文本输出格式
+//! - `json_builder` - 生成包含标题、资源、链接和翻译对照的JSON摘要
 
 #[cfg(feature = "web")]
 pub mod html_builder;
+pub mod json_builder;
+pub mod text_builder;
 
 // Re-export commonly used items for convenience
 #[cfg(feature = "web")]
 pub use html_builder::*;
+pub use json_builder::*;
+pub use text_builder::*;
@@ -0,0 +1,155 @@
+//! 纯文本构建器模块
+//!
+//! 遍历最终的 DOM 树，输出页面上可见的文本内容，用于索引或搜索引擎收录。
+//! 块级元素之间插入换行，段落类元素之间保留一个空行，连续空白折叠为单个空格。
+//! 与翻译文本收集器一样跳过 `SKIP_ELEMENTS` 中的元素，但不做可翻译性过滤。
+//! `<canvas>` 的后备内容默认同样跳过，可用 [`TextBuilder::with_canvas_fallback`] 保留。
+
+use markup5ever_rcdom::{Handle, NodeData};
+
+use crate::parsers::html::{get_node_attr, utils::SKIP_ELEMENTS};
+
+/// 会产生换行的块级元素
+const BLOCK_ELEMENTS: &[&str] = &[
+    "address", "article", "aside", "body", "dd", "details", "dialog", "div", "dl", "dt",
+    "fieldset", "figcaption", "footer", "form", "header", "hgroup", "html", "legend", "li",
+    "main", "nav", "option", "section", "summary", "tr",
+];
+
+/// 前后各保留一个空行的段落类元素
+const PARAGRAPH_ELEMENTS: &[&str] = &[
+    "blockquote", "figure", "h1", "h2", "h3", "h4", "h5", "h6", "ol", "p", "table", "ul",
+];
+
+/// 纯文本构建器
+///
+/// 以流式方式累积文本：空白和换行都先记为“待输出”，直到遇到下一个可见字符
+/// 才真正写入，这样首尾不会出现多余的空白。
+#[derive(Default)]
+pub struct TextBuilder {
+    output: String,
+    pending_breaks: usize,
+    pending_space: bool,
+    canvas_fallback: bool,
+}
+
+impl TextBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 输出 `<canvas>` 的后备内容（不支持画布时显示的文本）
+    pub fn with_canvas_fallback(mut self) -> Self {
+        self.canvas_fallback = true;
+        self
+    }
+
+    /// 遍历节点及其子树，追加可见文本
+    pub fn walk(&mut self, node: &Handle) {
+        match node.data {
+            NodeData::Text { ref contents } => {
+                self.push_text(&contents.borrow());
+            }
+            NodeData::Element { ref name, .. } => {
+                let tag_name = name.local.as_ref();
+
+                if tag_name == "br" {
+                    self.pending_breaks = (self.pending_breaks + 1).min(2);
+                    self.pending_space = false;
+                    return;
+                }
+
+                if (SKIP_ELEMENTS.contains(&tag_name)
+                    && !(tag_name == "canvas" && self.canvas_fallback))
+                    || tag_name == "template"
+                    || get_node_attr(node, "hidden").is_some()
+                {
+                    return;
+                }
+
+                let breaks = if PARAGRAPH_ELEMENTS.contains(&tag_name) {
+                    2
+                } else if BLOCK_ELEMENTS.contains(&tag_name) {
+                    1
+                } else {
+                    0
+                };
+
+                self.request_breaks(breaks);
+                for child in node.children.borrow().iter() {
+                    self.walk(child);
+                }
+                self.request_breaks(breaks);
+
+                // 表格单元格之间用空格分隔
+                if matches!(tag_name, "td" | "th") {
+                    self.pending_space = true;
+                }
+            }
+            _ => {
+                for child in node.children.borrow().iter() {
+                    self.walk(child);
+                }
+            }
+        }
+    }
+
+    /// 结束构建，返回以换行结尾的文本
+    pub fn finish(mut self) -> String {
+        if !self.output.is_empty() {
+            self.output.push('\n');
+        }
+        self.output
+    }
+
+    fn request_breaks(&mut self, breaks: usize) {
+        if breaks > self.pending_breaks {
+            self.pending_breaks = breaks;
+        }
+        if breaks > 0 {
+            self.pending_space = false;
+        }
+    }
+
+    fn push_text(&mut self, text: &str) {
+        for c in text.chars() {
+            if c.is_whitespace() {
+                // 换行待输出时，空白没有意义
+                if self.pending_breaks == 0 {
+                    self.pending_space = true;
+                }
+                continue;
+            }
+
+            if !self.output.is_empty() {
+                if self.pending_breaks > 0 {
+                    for _ in 0..self.pending_breaks {
+                        self.output.push('\n');
+                    }
+                } else if self.pending_space {
+                    self.output.push(' ');
+                }
+            }
+            self.pending_breaks = 0;
+            self.pending_space = false;
+            self.output.push(c);
+        }
+    }
+}
+
+/// 将 DOM 树转换为纯文本
+///
+/// # 示例
+///
+/// ```rust
+/// use monolith::builders::build_text;
+/// use monolith::html::html_to_dom;
+///
+/// let dom = html_to_dom(b"<h1>Title</h1><p>Hello <b>world</b></p>", "".to_string());
+/// assert_eq!(build_text(&dom.document), "Title\n\nHello world\n");
+/// ```
+pub fn build_text(node: &Handle) -> String {
+    let mut builder = TextBuilder::new();
+    builder.walk(node);
+    builder.finish()
+}
@@ -1,18 +1,29 @@
-use chrono::{SecondsFormat, Utc};
+use chrono::{NaiveDateTime, SecondsFormat, Utc};
 use encoding_rs::Encoding;
 use markup5ever_rcdom::RcDom;
 use std::error::Error;
 use std::fmt;
 use std::fs;
-use std::path::Path;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
 use url::Url;
 
-use crate::network::session::Session;
+use crate::builders::{build_text, JsonDocument, TextBuilder};
+use crate::network::fetch_policy::FetchPolicy;
+use crate::network::session::{EmbeddedResource, Session};
 use crate::parsers::html::{
-    add_favicon, create_metadata_tag, get_base_url, get_charset, get_robots, get_title,
-    has_favicon, html_to_dom, serialize_document, set_base_url, set_charset, set_robots, walk,
+    add_favicon, add_stylesheet, content_fingerprint, create_fingerprint_tag, create_metadata_tag,
+    get_base_url, get_charset, get_meta_refresh_url, get_robots, get_title, has_favicon,
+    html_to_dom, inject_base, isolate_subtree, normalize_head, sanitize,
+    serialize_document_to_writer, set_archive_metadata, set_base_url, set_charset, set_robots,
+    set_viewport, walk, SanitizeProfile, DEFAULT_VIEWPORT,
 };
-use crate::utils::url::{create_data_url, resolve_url};
+use crate::utils::media::Viewport;
+use crate::utils::url::{create_data_url_with_encoding, resolve_url};
 
 // 文档处理器模块（内联整合到core.rs中）
 
@@ -65,6 +76,10 @@ pub enum MonolithOutputFormat {
     HTML,
     /// MHTML format for email compatibility
     MHTML,
+    /// UTF-8 plain text containing only the visible text of the page
+    Text,
+    /// JSON summary with the title, charset, embedded resources, links and translations
+    Json,
     // WARC,
     // ZIM,
     // HAR,
@@ -77,11 +92,21 @@ pub enum MonolithOutputFormat {
 #[derive(Default, Clone)]
 pub struct MonolithOptions {
     pub base_url: Option<String>,
+    /// 文档没有 `<base>` 时添加指向文档地址的 `<base>`，使未嵌入资源的归档在本地打开时
+    /// 仍按原站点解析相对地址。没有排除任何资源类型（资源全部嵌入）或设置 `base_url` 时不生效
+    pub inject_base: bool,
     pub blacklist_domains: bool,
+    /// 不抓取、也不保留引用的主机（支持 `*` 通配符），用于去除统计和跟踪资源
+    pub block_hosts: Vec<String>,
     pub domains: Option<Vec<String>>,
     pub encoding: Option<String>,
     pub ignore_errors: bool,
     pub insecure: bool,
+    /// 不验证 TLS 证书的主机（支持 `*` 通配符），其余主机仍然严格验证；`insecure` 开启时不起作用
+    pub insecure_hosts: Vec<String>,
+    /// 限制可以获取的协议和地址（见 [`FetchPolicy`]），Web 服务器默认使用 [`FetchPolicy::server`]；
+    /// 未设置时不做限制
+    pub fetch_policy: Option<FetchPolicy>,
     pub isolate: bool,
     pub no_audio: bool,
     pub no_css: bool,
@@ -89,24 +114,119 @@ pub struct MonolithOptions {
     pub no_frames: bool,
     pub no_images: bool,
     pub no_js: bool,
+    /// 只删除 `onclick`、`onload` 等内联事件处理属性，保留 `<script>`；`no_js` 已包含此操作
+    pub strip_inline_event_handlers: bool,
     pub no_metadata: bool,
     pub no_video: bool,
     pub output_format: MonolithOutputFormat,
     pub silent: bool,
     pub timeout: u64,
+    /// 主文档请求的超时（秒），未设置时使用 `timeout`
+    pub document_timeout: Option<u64>,
+    /// 单个资源请求的超时（秒），未设置时使用 `timeout`
+    pub asset_timeout: Option<u64>,
+    /// 主文档因连接错误或超时获取失败时的重试次数，HTTP 错误状态（如 404）不重试
+    pub document_fetch_retries: usize,
     pub unwrap_noscript: bool,
+    /// 压缩最终HTML（折叠空白、删除注释、去掉多余的属性引号）
+    pub minify: bool,
+    /// 压缩时保留注释
+    pub minify_preserve_comments: bool,
+    /// 在输出中记录内容指纹，用于判断重新归档的页面是否改变
+    pub fingerprint: bool,
+    /// 删除 `<link rel="preload">` 之前先嵌入其预加载的资源（字体、图片、样式等）
+    pub capture_preloads: bool,
+    /// 目标页面只是 meta refresh 跳转页时，归档跳转目标（最多跟随 `MAX_META_REFRESH_HOPS` 次）
+    pub follow_meta_refresh: bool,
+    /// `application/json` 脚本块（页面状态数据）的处理方式
+    pub json_script_policy: JsonScriptPolicy,
+    /// 嵌入资源所用 data URL 的编码方式
+    pub data_url_encoding: DataUrlEncoding,
+    /// 获取资源时 `Referer` 请求头的取值方式
+    pub referer_policy: RefererPolicy,
+    /// 目标视口：设置后，`<picture>` 中 `media` 查询在该视口下永远不会匹配的 `<source>` 被删除，
+    /// 不再下载其中的图片；作为后备的 `<img>` 总是保留
+    pub target_viewport: Option<Viewport>,
+    /// 按优先顺序排列的图片格式（MIME 类型，如 `image/avif`）：设置后，`<picture>` 中 `media` 相同的
+    /// `<source>` 只保留 `type` 排在最前的一个，其余不再下载；没有 `type` 的图源和后备的 `<img>` 总是保留
+    pub preferred_image_formats: Vec<String>,
+    /// 嵌入资源之后追加到 `<head>` 的自定义样式，不受 `no_css` 影响
+    pub extra_css: Option<String>,
+    /// 整理 `<head>`：重复的 meta 标签只保留第一个，字符编码声明只保留一个并移到最前面
+    pub normalize_head: bool,
+    /// 添加或改写 `<meta name="viewport">` 为 [`DEFAULT_VIEWPORT`]，
+    /// 使为移动端设计的页面在 iframe 中不会被缩小显示
+    pub force_viewport: bool,
+    /// 设置后限制 `<body>` 的最大宽度（CSS 长度，如 `48rem`）并水平居中，
+    /// 只在设置 `force_viewport` 时生效
+    pub max_content_width: Option<String>,
+    /// 去除 `<a>`/`<area>` 的超链接审计：删除 `ping`，设置 `referrerpolicy="no-referrer"`
+    /// 和 `rel="noopener noreferrer"`，避免离线归档在联网打开时发出请求或泄露来源
+    pub strip_hyperlink_auditing: bool,
+    /// 在 `<head>` 中添加 `monolith-archived-at` 和 `monolith-source-url` meta 标签，
+    /// 使归档文件自带来源信息；设置 `no_metadata` 时不添加
+    pub add_archive_metadata: bool,
+    /// 从所有元素上删除的属性，例如 `nonce`、`data-analytics-*`；以 `*` 结尾的条目按前缀匹配。
+    /// 前缀匹配不会删除 `src` 和 `href`，需要删除时须写出完整的属性名
+    pub strip_attributes: Vec<String>,
+    /// 按地址强制指定资源的媒体类型（模式, 媒体类型），用于纠正服务器发送的错误类型，
+    /// 例如把以 `application/octet-stream` 发送的 `.woff2` 当作 `font/woff2` 嵌入。
+    /// 模式的写法见 [`url_matches_pattern`](crate::url::url_matches_pattern)，按顺序取第一个匹配的规则
+    pub media_type_overrides: Vec<(String, String)>,
+    /// 翻译和纯文本输出同样处理 `<canvas>` 中的后备内容（不支持画布时显示的元素）；
+    /// 后备内容中的资源无论是否设置都会被嵌入
+    pub canvas_fallback: bool,
+    /// 序列化之前按规则净化页面，用于在自己的域名下提供不受信任的归档；
+    /// 与 `no_js` 不同，同时处理脚本地址、可执行的 data URL 框架和提交到外部的表单
+    pub sanitize: Option<SanitizeProfile>,
+    /// 取消 `<img>` 的懒加载：`loading` 改为 `eager`，删除 `decoding` 和 `fetchpriority`；
+    /// 图片已经内联，懒加载只会让依赖脚本的页面显示不出图片
+    pub eager_load_images: bool,
+    /// 嵌入 `<script>` 和 `<link>` 引用的资源后保留 `crossorigin` 和 `referrerpolicy`；
+    /// 默认删除，内联内容不再请求，这些属性没有意义。`integrity` 总是删除
+    pub keep_fetch_attributes: bool,
+    /// 最多嵌入的资源数量（所有类型合计），达到后其余资源保留原地址，
+    /// 跳过的数量通过 `Session::skipped_assets_count` 获取
+    pub max_embedded_assets: Option<usize>,
+    /// 框架最多嵌入的层数，主文档中的框架为第 1 层；更深的框架保留解析为绝对地址的 `src`
+    pub max_iframe_depth: Option<usize>,
+    /// 主文档中嵌入的框架合计最多的字节数（含其中嵌套的框架），超出后其余框架保留绝对地址
+    pub max_inlined_frames_size: Option<usize>,
+    /// 只归档 `<body>` 中第一个匹配该选择器的元素（支持标签名、`.class` 和 `#id`），
+    /// 其余内容在嵌入资源之前删除，`<head>` 和片段之外的样式保留
+    pub root_selector: Option<String>,
     pub user_agent: Option<String>,
     // Translation options
     #[cfg(feature = "translation")]
     pub enable_translation: bool,
     #[cfg(feature = "translation")]
     pub target_language: Option<String>,
+    /// 页面的源语言，未设置时自动检测
+    #[cfg(feature = "translation")]
+    pub source_language: Option<String>,
     #[cfg(feature = "translation")]
     pub translation_api_url: Option<String>,
     #[cfg(feature = "translation")]
     pub rewrite_links: bool,
     #[cfg(feature = "translation")]
     pub translation_base_path: Option<String>,
+    #[cfg(feature = "translation")]
+    pub concurrent_translation: bool,
+    /// 在译文上保留原文，鼠标悬停时显示
+    #[cfg(feature = "translation")]
+    pub translation_show_original: bool,
+    /// 在每个已翻译的段落之后附上原文
+    #[cfg(feature = "translation")]
+    pub translation_bilingual: bool,
+    /// 同时翻译 `<template>` 内容中的文本
+    #[cfg(feature = "translation")]
+    pub translation_templates: bool,
+    /// 同时翻译 `og:title`、`og:description` 等社交分享元数据
+    #[cfg(feature = "translation")]
+    pub translation_social_meta: bool,
+    /// 在译文的 `<head>` 中写入描述翻译来源的 JSON-LD
+    #[cfg(feature = "translation")]
+    pub translation_provenance: bool,
     
     // 智能资源过滤选项
     pub smart_filtering: bool,
@@ -116,6 +236,15 @@ pub struct MonolithOptions {
     pub exclude_decorative_images: bool,       // 排除装饰图片
     pub exclude_ad_domains: bool,              // 排除广告域名
     pub filtering_level: ResourceFilteringLevel,
+
+    // 抓取并发限制（0 表示不限制）
+    pub max_concurrent_fetches: usize,   // 全局并发连接数
+    pub max_connections_per_host: usize, // 单个主机的并发连接数
+
+    // HTTP 连接池（None 表示使用 reqwest 的默认值）
+    pub pool_max_idle_per_host: Option<usize>, // 每个主机保留的空闲连接数，0 表示不复用连接
+    pub pool_idle_timeout: Option<u64>,        // 空闲连接的保留时间(秒)
+    pub tcp_keepalive: Option<u64>,            // TCP keepalive 探测间隔(秒)
 }
 
 /// 资源过滤级别
@@ -137,6 +266,121 @@ impl Default for ResourceFilteringLevel {
     }
 }
 
+/// 改写 JSON 脚本内容的回调，参数为原始内容，返回写回文档的内容
+pub type JsonScriptRedactor = Arc<dyn Fn(&str) -> String + Send + Sync>;
+
+/// 不可执行的 JSON 脚本块的处理方式
+///
+/// 针对 `<script type="application/json">` 和 `<script id="__NEXT_DATA__">`，
+/// 单页应用常在其中放入体积很大、有时包含个人信息的页面状态。
+/// `application/ld+json` 等结构化数据不受影响。
+#[derive(Clone, Default)]
+pub enum JsonScriptPolicy {
+    /// 原样保留
+    #[default]
+    Keep,
+    /// 从文档中删除整个元素
+    Remove,
+    /// 用回调改写脚本内容，例如 [`crate::html::redact_json_strings`]
+    Redact(JsonScriptRedactor),
+}
+
+/// 嵌入资源时 data URL 的编码方式
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum DataUrlEncoding {
+    /// 分别按两种方式编码，取较短的结果
+    Auto,
+    /// base64 编码
+    #[default]
+    Base64,
+    /// 百分号编码：文本资源（CSS、SVG、脚本）通常更短，并且在输出中仍然可以直接搜索
+    PercentEncode,
+}
+
+/// 获取资源时发送的 `Referer`
+///
+/// 获取主文档时从不发送；目标页面本身不是 http(s) 地址时，只有 `Fixed` 会发送。
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub enum RefererPolicy {
+    /// 引用资源的页面或样式表的完整地址（去掉片段和用户信息），与浏览器的默认行为一致，
+    /// 防盗链的图片因此可以正常获取
+    #[default]
+    FullUrl,
+    /// 只发送引用页面的源，例如 `https://example.com/`
+    Origin,
+    /// 不发送
+    None,
+    /// 总是发送指定的地址
+    Fixed(Url),
+}
+
+/// 保存输出文件时的压缩方式
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum OutputCompression {
+    /// 不压缩
+    #[default]
+    None,
+    /// gzip，文件名以 `.gz` 结尾
+    Gzip,
+    /// Brotli，文件名以 `.br` 结尾；压缩率更高，但压缩更慢
+    Brotli,
+}
+
+impl OutputCompression {
+    /// 按文件扩展名（`.gz`、`.br`）确定压缩方式
+    pub fn from_path(path: &str) -> Self {
+        let path = path.to_ascii_lowercase();
+        if path.ends_with(".gz") {
+            OutputCompression::Gzip
+        } else if path.ends_with(".br") {
+            OutputCompression::Brotli
+        } else {
+            OutputCompression::None
+        }
+    }
+
+    /// 压缩文件的扩展名
+    pub fn extension(&self) -> Option<&'static str> {
+        match self {
+            OutputCompression::None => None,
+            OutputCompression::Gzip => Some("gz"),
+            OutputCompression::Brotli => Some("br"),
+        }
+    }
+
+    /// 压缩数据
+    pub fn compress(&self, data: &[u8]) -> std::io::Result<Vec<u8>> {
+        match self {
+            OutputCompression::None => Ok(data.to_vec()),
+            OutputCompression::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(data)?;
+                encoder.finish()
+            }
+            OutputCompression::Brotli => {
+                let mut output = Vec::new();
+                {
+                    let mut encoder = brotli::CompressorWriter::new(&mut output, 4096, 9, 22);
+                    encoder.write_all(data)?;
+                }
+                Ok(output)
+            }
+        }
+    }
+}
+
+/// 由 [`save_output`] 写入的文件
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SavedOutput {
+    /// 实际写入的路径（压缩时带有 `.gz` 或 `.br` 扩展名）
+    pub path: PathBuf,
+    /// 压缩前的字节数
+    pub uncompressed_size: usize,
+    /// 写入磁盘的字节数
+    pub written_size: usize,
+}
+
 const ANSI_COLOR_RED: &str = "\x1b[31m";
 const ANSI_COLOR_RESET: &str = "\x1b[0m";
 const FILE_SIGNATURES: [[&[u8]; 2]; 18] = [
@@ -173,6 +417,10 @@ const PLAINTEXT_MEDIA_TYPES: &[&str] = &[
     "application/vnd.mozilla.xul+xml", // .xul
     "image/svg+xml",                   // .svg
 ];
+// Maximum number of meta refresh redirects followed for a single target
+pub const MAX_META_REFRESH_HOPS: usize = 5;
+// Pages with more visible text than this are real pages, not redirect shims
+const META_REFRESH_SHIM_TEXT_LIMIT: usize = 200;
 
 /// Creates a monolithic document from raw data
 ///
@@ -250,9 +498,77 @@ pub fn create_monolithic_document(
 ) -> Result<(Vec<u8>, Option<String>), MonolithError> {
     // 清空会话中累积的URL，防止重复处理
     session.clear_urls();
+
+    let (input_data, target_url) = fetch_target(&mut session, target)?;
+
+    // 使用重构后的处理器处理文档
+    create_monolithic_document_from_data(
+        session,
+        input_data,
+        None,
+        target_url.map(|u| u.to_string()),
+    )
+}
+
+/// Archives a URL or file path, writing the result straight into `writer`
+///
+/// Does the same work as [`create_monolithic_document`] (fetching, embedding assets and,
+/// when enabled, translating), but the final document is serialized directly into `writer`
+/// instead of being collected in memory first, so huge pages never need the DOM and the
+/// complete output at the same time. Returns the document title.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::fs::File;
+/// use std::io::BufWriter;
+///
+/// use monolith::core::{archive_to_writer, MonolithOptions};
+/// use monolith::session::Session;
+///
+/// let session = Session::new(None, None, MonolithOptions::default());
+/// let mut file = BufWriter::new(File::create("example.html").unwrap());
+/// let title = archive_to_writer(session, "https://example.com", &mut file).unwrap();
+/// ```
+pub fn archive_to_writer<W: Write>(
+    mut session: Session,
+    target: &str,
+    writer: &mut W,
+) -> Result<Option<String>, MonolithError> {
+    session.clear_urls();
+    let (input_data, target_url) = fetch_target(&mut session, target)?;
+
+    DocumentProcessor::new(session).process_document_to_writer(
+        input_data,
+        None,
+        target_url.map(|u| u.to_string()),
+        writer,
+    )
+}
+
+/// 获取要归档的目标（URL、data URL 或本地文件），返回文档数据和文档地址
+fn fetch_target(
+    session: &mut Session,
+    target: &str,
+) -> Result<(Vec<u8>, Option<Url>), MonolithError> {
+    // 本地路径和作为目标的 data URL 不经过会话的地址检查
+    if let Some(policy) = &session.options.fetch_policy {
+        let scheme = if target.starts_with("data:") {
+            "data"
+        } else if target.starts_with("http://") || target.starts_with("https://") {
+            ""
+        } else {
+            "file"
+        };
+        if !scheme.is_empty() && !policy.allows_scheme(scheme) {
+            return Err(MonolithError::new(&format!(
+                "Fetching {scheme} targets is not allowed"
+            )));
+        }
+    }
     
     // 验证和解析目标URL以及获取数据
-    let (input_data, target_url) = if target.starts_with("data:") {
+    let fetched = if target.starts_with("data:") {
         // Data URL
         if let Ok(parsed_url) = Url::parse(target) {
             let (media_type, _, data) = crate::utils::url::parse_data_url(&parsed_url);
@@ -268,19 +584,45 @@ pub fn create_monolithic_document(
         }
     } else if target.starts_with("http://") || target.starts_with("https://") {
         // HTTP/HTTPS URL
-        if let Ok(parsed_url) = Url::parse(target) {
-            match session.retrieve_asset(&parsed_url, &parsed_url) {
-                Ok((data, _, media_type, _)) => {
-                    if is_plaintext_media_type(&media_type) {
-                        (data, Some(parsed_url))
-                    } else {
-                        return Err(MonolithError::new(&format!(
-                            "Unsupported media type: {media_type}"
-                        )));
-                    }
+        if let Ok(mut parsed_url) = Url::parse(target) {
+            let mut hops: usize = 0;
+            loop {
+                let (data, media_type, charset) =
+                    match session.retrieve_document(&parsed_url) {
+                        Ok((data, _, media_type, charset)) => (data, media_type, charset),
+                        Err(e) => {
+                            return Err(MonolithError::new(&format!("Failed to fetch URL: {e}")));
+                        }
+                    };
+                if !is_plaintext_media_type(&media_type) {
+                    return Err(MonolithError::new(&format!(
+                        "Unsupported media type: {media_type}"
+                    )));
                 }
-                Err(e) => {
-                    return Err(MonolithError::new(&format!("Failed to fetch URL: {e}")));
+
+                // 页面只是跳转用的中间页时，改为归档跳转目标
+                let next_url = if session.options.follow_meta_refresh && media_type == "text/html" {
+                    meta_refresh_target(&data, &charset, &parsed_url)
+                } else {
+                    None
+                };
+                match next_url {
+                    Some(next_url) => {
+                        if hops == MAX_META_REFRESH_HOPS {
+                            return Err(MonolithError::new(&format!(
+                                "Too many meta refresh redirects (more than {MAX_META_REFRESH_HOPS})"
+                            )));
+                        }
+                        hops += 1;
+                        if !session.options.silent {
+                            print_info_message(&format!(
+                                "{} -> {} (meta refresh)",
+                                parsed_url, next_url
+                            ));
+                        }
+                        parsed_url = next_url;
+                    }
+                    None => break (data, Some(parsed_url)),
                 }
             }
         } else {
@@ -309,15 +651,173 @@ pub fn create_monolithic_document(
         }
     };
 
-    // 使用重构后的处理器处理文档
-    create_monolithic_document_from_data(
-        session,
-        input_data,
-        None,
-        target_url.map(|u| u.to_string()),
+    Ok(fetched)
+}
+
+/// Called after each target processed by [`process_urls_with_options`] with the
+/// result, the number of targets finished so far and the total number of targets
+pub type BatchProgressCallback = Arc<dyn Fn(&BatchResult, usize, usize) + Send + Sync>;
+
+/// Outcome of processing one target of a batch
+#[derive(Debug)]
+pub struct BatchResult {
+    pub target: String,
+    pub result: Result<(Vec<u8>, Option<String>), MonolithError>,
+    pub duration: Duration,
+}
+
+/// Options for [`process_urls_with_options`]
+#[derive(Clone)]
+pub struct BatchOptions {
+    /// 同时处理的目标数
+    pub concurrency: usize,
+    /// 单个目标的处理时限，超时的目标记为失败
+    pub target_timeout: Option<Duration>,
+    pub progress: Option<BatchProgressCallback>,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: 4,
+            target_timeout: None,
+            progress: None,
+        }
+    }
+}
+
+/// Creates monolithic documents for many targets concurrently
+///
+/// Each target is processed with its own session forked from `session_template`,
+/// sharing its HTTP client, connection pool and asset cache. At most `concurrency`
+/// targets are processed at once. Results are returned in the order of `targets`.
+pub fn process_urls(
+    session_template: &Session,
+    targets: &[String],
+    concurrency: usize,
+) -> Vec<BatchResult> {
+    process_urls_with_options(
+        session_template,
+        targets,
+        &BatchOptions {
+            concurrency,
+            ..BatchOptions::default()
+        },
     )
 }
 
+/// Creates monolithic documents for many targets concurrently, with a progress
+/// callback and a per-target time limit
+///
+/// A target that exceeds the time limit is reported as failed right away; its
+/// worker keeps running in the background until the session's own timeouts end it.
+pub fn process_urls_with_options(
+    session_template: &Session,
+    targets: &[String],
+    options: &BatchOptions,
+) -> Vec<BatchResult> {
+    let next = AtomicUsize::new(0);
+    let finished = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<BatchResult>>> =
+        Mutex::new(targets.iter().map(|_| None).collect());
+    let workers = options.concurrency.max(1).min(targets.len());
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                let target = match targets.get(index) {
+                    Some(target) => target,
+                    None => break,
+                };
+
+                let started = Instant::now();
+                let result =
+                    process_target(session_template.fork(), target, options.target_timeout);
+                let result = BatchResult {
+                    target: target.clone(),
+                    result,
+                    duration: started.elapsed(),
+                };
+
+                let finished = finished.fetch_add(1, Ordering::SeqCst) + 1;
+                if let Some(progress) = &options.progress {
+                    progress(&result, finished, targets.len());
+                }
+                results.lock().unwrap()[index] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// 在单独的线程中处理一个目标，以便在超时后不再等待
+fn process_target(
+    session: Session,
+    target: &str,
+    timeout: Option<Duration>,
+) -> Result<(Vec<u8>, Option<String>), MonolithError> {
+    let (sender, receiver) = mpsc::channel();
+    let owned_target = target.to_string();
+    thread::spawn(move || {
+        let _ = sender.send(create_monolithic_document(session, &owned_target));
+    });
+
+    let received = match timeout {
+        Some(timeout) => receiver.recv_timeout(timeout).map_err(|e| match e {
+            mpsc::RecvTimeoutError::Timeout => {
+                MonolithError::new(&format!("{} (timed out after {:?})", target, timeout))
+            }
+            mpsc::RecvTimeoutError::Disconnected => {
+                MonolithError::new(&format!("{} (processing aborted)", target))
+            }
+        }),
+        None => receiver
+            .recv()
+            .map_err(|_| MonolithError::new(&format!("{} (processing aborted)", target))),
+    };
+    received.and_then(|result| result)
+}
+
+/// Checks whether the archive embeds every asset type
+///
+/// In that case nothing is left to resolve against the original site and a
+/// `<base>` would only redirect relative links away from the archive.
+fn inlines_all_assets(options: &MonolithOptions) -> bool {
+    !(options.no_audio
+        || options.no_css
+        || options.no_fonts
+        || options.no_frames
+        || options.no_images
+        || options.no_js
+        || options.no_video)
+}
+
+/// Returns the destination of a meta refresh redirect shim
+///
+/// A page counts as a redirect shim when it declares a refresh target and
+/// has next to no visible text of its own. Refreshes to the same page and
+/// to non-HTTP(S) locations are not followed.
+fn meta_refresh_target(data: &[u8], charset: &str, page_url: &Url) -> Option<Url> {
+    let dom = html_to_dom(data, charset.to_string());
+    let refresh_url = get_meta_refresh_url(&dom.document)?;
+    if build_text(&dom.document).chars().count() > META_REFRESH_SHIM_TEXT_LIMIT {
+        return None;
+    }
+
+    let next_url = page_url.join(&refresh_url).ok()?;
+    if !matches!(next_url.scheme(), "http" | "https") || &next_url == page_url {
+        return None;
+    }
+    Some(next_url)
+}
+
 /// Determines the media type of data based on its content signature
 pub fn detect_media_type(data: &[u8], url: &Url) -> String {
     // First try to detect by magic bytes
@@ -401,6 +901,17 @@ pub fn parse_content_type(content_type: &str) -> (String, String, bool) {
     (media_type, charset, is_base64)
 }
 
+/// Checks if the given value looks like a CSS length (e.g. `48rem`, `80%`)
+///
+/// Only ASCII letters, digits, `.` and `%` are allowed, so the value can be
+/// written into a `<style>` element without escaping.
+pub fn is_css_length(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '%')
+}
+
 /// Checks if the given media type represents plaintext content
 pub fn is_plaintext_media_type(media_type: &str) -> bool {
     media_type.starts_with("text/") || PLAINTEXT_MEDIA_TYPES.contains(&media_type)
@@ -429,6 +940,122 @@ pub fn format_output_path(path: &str, document_title: Option<&str>, is_mhtml: bo
         .replace("%ext%", if is_mhtml { "mht" } else { "htm" })
 }
 
+/// Looks for an output file produced by an earlier run that is at most `max_age` old
+///
+/// `path` is the same template given to `format_output_path`. Since `%timestamp%` gives every run
+///  a new name, files are then matched on the rest of the name (e.g. the title portion), with any
+///  timestamp in place of `%timestamp%`; this is only supported in the file name, not in directories.
+///  Returns the most recently modified matching file, or None if there is none or the output is stdout.
+pub fn find_fresh_output(
+    path: &str,
+    document_title: Option<&str>,
+    is_mhtml: bool,
+    max_age: Duration,
+) -> Option<PathBuf> {
+    if path.is_empty() || path == "-" {
+        return None;
+    }
+
+    let is_fresh = |file: &Path| {
+        fs::metadata(file)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .is_some_and(|age| age <= max_age)
+    };
+
+    if !path.contains("%timestamp%") {
+        let file = PathBuf::from(format_output_path(path, document_title, is_mhtml));
+        return if file.is_file() && is_fresh(&file) {
+            Some(file)
+        } else {
+            None
+        };
+    }
+
+    let pattern = PathBuf::from(path);
+    let directory = match pattern.parent() {
+        Some(directory) if directory.as_os_str().is_empty() => PathBuf::from("."),
+        Some(directory) if !directory.to_string_lossy().contains("%timestamp%") => PathBuf::from(
+            format_output_path(&directory.to_string_lossy(), document_title, is_mhtml),
+        ),
+        _ => return None,
+    };
+    let parts: Vec<String> = pattern
+        .file_name()?
+        .to_string_lossy()
+        .split("%timestamp%")
+        .map(|part| format_output_path(part, document_title, is_mhtml))
+        .collect();
+
+    fs::read_dir(&directory)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| matches_timestamped_name(&entry.file_name().to_string_lossy(), &parts))
+        .map(|entry| entry.path())
+        .filter(|file| file.is_file() && is_fresh(file))
+        .max_by_key(|file| {
+            fs::metadata(file)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+        })
+}
+
+/// Checks if a file name consists of the given parts separated by timestamps
+///  in the form `format_output_path` writes them (e.g. `2024-01-31T12_00_00Z`)
+fn matches_timestamped_name(name: &str, parts: &[String]) -> bool {
+    const TIMESTAMP_LENGTH: usize = "2024-01-31T12_00_00Z".len();
+
+    let mut rest = match name.strip_prefix(parts[0].as_str()) {
+        Some(rest) => rest,
+        None => return false,
+    };
+    for part in &parts[1..] {
+        let timestamp = match rest.get(..TIMESTAMP_LENGTH) {
+            Some(timestamp) => timestamp,
+            None => return false,
+        };
+        if NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%dT%H_%M_%SZ").is_err() {
+            return false;
+        }
+        rest = match rest[TIMESTAMP_LENGTH..].strip_prefix(part.as_str()) {
+            Some(rest) => rest,
+            None => return false,
+        };
+    }
+    rest.is_empty()
+}
+
+/// Writes serialized output to disk, compressing it first if requested
+///
+/// The compression's extension is appended to `path` unless it already ends with it,
+///  so `page.html` saved with gzip becomes `page.html.gz`. Use `OutputCompression::from_path`
+///  to pick the compression from a `.gz` or `.br` destination instead.
+pub fn save_output(
+    data: &[u8],
+    path: &str,
+    compression: OutputCompression,
+) -> Result<SavedOutput, MonolithError> {
+    let path = match compression.extension() {
+        Some(extension) if OutputCompression::from_path(path) != compression => {
+            PathBuf::from(format!("{path}.{extension}"))
+        }
+        _ => PathBuf::from(path),
+    };
+
+    let compressed = compression
+        .compress(data)
+        .map_err(|e| MonolithError::new(&format!("could not compress output: {e}")))?;
+    fs::write(&path, &compressed)
+        .map_err(|e| MonolithError::new(&format!("could not write {}: {e}", path.display())))?;
+
+    Ok(SavedOutput {
+        path,
+        uncompressed_size: data.len(),
+        written_size: compressed.len(),
+    })
+}
+
 /// Prints an error message to stderr
 pub fn print_error_message(msg: &str) {
     eprintln!("{ANSI_COLOR_RED}{msg}{ANSI_COLOR_RESET}");
@@ -592,31 +1219,132 @@ impl DocumentProcessor {
         input_encoding: Option<String>,
         input_target: Option<String>,
     ) -> Result<(Vec<u8>, Option<String>), MonolithError> {
-        // 1. 清空会话中累积的URL，防止重复处理
+        let (dom, document_encoding, base_url) =
+            self.parse_input(input_data, input_encoding, &input_target)?;
+
+        self.process_parsed(dom, document_encoding, base_url, &input_target)
+    }
+
+    /// 处理文档数据，把最终结果直接写入 `writer`，返回文档标题
+    ///
+    /// 与 [`process_document`](Self::process_document) 的处理相同，但输出边序列化边写出，
+    /// 不会在内存中保存完整的结果。写入失败时返回错误，`writer` 中可能已有部分输出。
+    pub fn process_document_to_writer<W: Write>(
+        mut self,
+        input_data: Vec<u8>,
+        input_encoding: Option<String>,
+        input_target: Option<String>,
+        writer: &mut W,
+    ) -> Result<Option<String>, MonolithError> {
+        let (dom, document_encoding, base_url) =
+            self.parse_input(input_data, input_encoding, &input_target)?;
+
+        self.process_parsed_to_writer(dom, document_encoding, base_url, &input_target, writer)
+    }
+
+    /// 解析文档数据，返回DOM、文档编码和基础URL
+    fn parse_input(
+        &mut self,
+        input_data: Vec<u8>,
+        input_encoding: Option<String>,
+        input_target: &Option<String>,
+    ) -> Result<(RcDom, String, Url), MonolithError> {
+        // 1. 清空会话中累积的URL和资源记录，防止重复处理
         self.session.clear_urls();
+        self.session.clear_resources();
         
         // 2. 验证配置  
         let encoding_validator = EncodingValidator::new();
         encoding_validator.validate_options(&self.session.options)?;
 
         // 2. 确定基础URL和编码
-        let url_resolver = UrlResolver::new();
-        let mut base_url = url_resolver.determine_base_url(&input_target)?;
+        let base_url = UrlResolver::new().determine_base_url(input_target)?;
 
         let encoding_processor = EncodingProcessor::new();
         let (dom, document_encoding) =
             encoding_processor.process_encoding(&input_data, input_encoding)?;
 
+        Ok((dom, document_encoding, base_url))
+    }
+
+    /// 处理已经解析好的DOM并返回最终结果
+    ///
+    /// 与 [`process_document`](Self::process_document) 执行相同的步骤（嵌入资源、翻译、
+    /// favicon、编码等），但直接使用传入的DOM，不再经过序列化和重新解析，
+    /// 调用方预先添加的节点和属性会原样保留。`document_url` 用于解析相对链接，
+    /// 输出编码取自文档中声明的字符集，没有声明时为 UTF-8。
+    pub fn process_dom(
+        mut self,
+        dom: RcDom,
+        document_url: Option<String>,
+    ) -> Result<(Vec<u8>, Option<String>), MonolithError> {
+        self.session.clear_urls();
+        self.session.clear_resources();
+
+        EncodingValidator::new().validate_options(&self.session.options)?;
+        let base_url = UrlResolver::new().determine_base_url(&document_url)?;
+        let document_encoding = get_charset(&dom.document)
+            .filter(|charset| Encoding::for_label_no_replacement(charset.as_bytes()).is_some())
+            .unwrap_or_else(|| "utf-8".to_string());
+
+        self.process_parsed(dom, document_encoding, base_url, &document_url)
+    }
+
+    fn process_parsed(
+        self,
+        dom: RcDom,
+        document_encoding: String,
+        base_url: Url,
+        input_target: &Option<String>,
+    ) -> Result<(Vec<u8>, Option<String>), MonolithError> {
+        let mut result = Vec::new();
+        let document_title = self.process_parsed_to_writer(
+            dom,
+            document_encoding,
+            base_url,
+            input_target,
+            &mut result,
+        )?;
+
+        Ok((result, document_title))
+    }
+
+    fn process_parsed_to_writer<W: Write>(
+        mut self,
+        dom: RcDom,
+        document_encoding: String,
+        mut base_url: Url,
+        input_target: &Option<String>,
+        writer: &mut W,
+    ) -> Result<Option<String>, MonolithError> {
+        let url_resolver = UrlResolver::new();
+
         // 3. 解析自定义基础URL
         base_url = url_resolver.resolve_custom_base_url(base_url, &dom, &self.session.options)?;
 
-        // 4. 遍历DOM并嵌入资源
+        // 只归档页面的一部分时，先删除其余内容，不再下载其中的资源
+        if let Some(selector) = &self.session.options.root_selector {
+            if !isolate_subtree(&dom, selector) {
+                return Err(MonolithError::new(&format!(
+                    "No element matches root selector \"{selector}\""
+                )));
+            }
+        }
+
+        // 4. 遍历DOM并嵌入资源（启用并行翻译时，翻译在后台同时进行）
+        #[cfg(feature = "translation")]
+        let translation_snapshot = self.capture_translation_snapshot(&dom);
+        #[cfg(feature = "translation")]
+        let concurrent_translation = self.start_concurrent_translation(&dom)?;
+
         let dom_processor = DomProcessor::new();
         let dom = dom_processor.process_dom(dom, &mut self.session, &base_url)?;
 
         // 5. 翻译处理（如果启用且不在iframe递归处理中）
         #[cfg(feature = "translation")]
-        let dom = if !self.session.in_iframe_processing {
+        let dom = if let Some(translation) = concurrent_translation {
+            self.finish_concurrent_translation(dom, translation)?
+        } else if !self.session.in_iframe_processing {
             self.process_translation(dom)?
         } else {
             dom
@@ -626,40 +1354,61 @@ impl DocumentProcessor {
         let dom = dom;
 
         // 6. 更新BASE元素
-        let dom = self.update_base_element(dom)?;
+        let dom = self.update_base_element(dom, &base_url)?;
 
         // 7. 处理favicon
-        let dom = self.process_favicon(dom, &base_url, &input_target)?;
+        let dom = self.process_favicon(dom, &base_url, input_target)?;
 
-        // 8. 设置robots meta标签
+        // 8. 设置robots和viewport meta标签
         let dom = self.set_robots_meta(dom);
+        let dom = self.set_viewport_meta(dom);
 
-        // 9. 处理自定义编码
+        // 9. 记录归档时间和来源地址
+        let dom = self.set_archive_metadata(dom, input_target)?;
+
+        // 10. 追加自定义样式（在资源嵌入之后，不会被改写）
+        let dom = match &self.session.options.extra_css {
+            Some(css) => add_stylesheet(dom, css),
+            None => dom,
+        };
+
+        // 11. 处理自定义编码
         let (dom, final_encoding) = self.process_custom_encoding(dom, document_encoding)?;
 
-        // 10. 序列化并格式化输出
+        // 12. 整理HEAD中的meta标签
+        let dom = if self.session.options.normalize_head {
+            normalize_head(dom, &final_encoding)
+        } else {
+            dom
+        };
+
+        // 13. 净化不受信任的页面
+        if let Some(profile) = &self.session.options.sanitize {
+            sanitize(&dom.document, &base_url, profile);
+        }
+
+        // 14. 序列化并格式化输出
         let document_title = get_title(&dom.document);
-        let output_formatter = OutputFormatter::new(&self.session.options, &input_target);
-        let result = output_formatter.format_output(dom, final_encoding)?;
+        let output_formatter = OutputFormatter::new(&self.session.options, input_target)
+            .with_resources(&base_url, self.session.resources());
+        #[cfg(feature = "translation")]
+        let output_formatter = match translation_snapshot {
+            Some(items) => output_formatter
+                .with_translations(crate::translation::collector::translation_pairs(&items)),
+            None => output_formatter,
+        };
+        output_formatter.write_output(writer, dom, final_encoding)?;
 
-        Ok((result, document_title))
+        Ok(document_title)
     }
 
     #[cfg(feature = "translation")]
     fn process_translation(&self, dom: RcDom) -> Result<RcDom, MonolithError> {
         if self.session.options.enable_translation {
-            use crate::translation::translate_dom_content_sync;
+            use crate::translation::translate_dom_with_config_sync;
 
-            let translated_dom = translate_dom_content_sync(
-                dom,
-                self.session
-                    .options
-                    .target_language
-                    .as_deref()
-                    .unwrap_or("zh"),
-                self.session.options.translation_api_url.as_deref(),
-            )
-            .map_err(|e| MonolithError::new(&format!("Translation error: {e}")))?;
+            let translated_dom = translate_dom_with_config_sync(dom, self.translation_config())
+                .map_err(|e| MonolithError::new(&format!("Translation error: {e}")))?;
 
             if !self.session.options.silent {
                 println!("Translation completed");
@@ -671,9 +1420,84 @@ impl DocumentProcessor {
         }
     }
 
-    fn update_base_element(&self, dom: RcDom) -> Result<RcDom, MonolithError> {
+    #[cfg(feature = "translation")]
+    fn translation_config(&self) -> crate::translation::TranslationConfig {
+        let options = &self.session.options;
+        let mut config = crate::translation::load_translation_config(
+            options.target_language.as_deref().unwrap_or("zh"),
+            options.translation_api_url.as_deref(),
+        );
+        if let Some(source_language) = &options.source_language {
+            config.source_lang = source_language.clone();
+        }
+        config.show_original = options.translation_show_original;
+        config.bilingual = options.translation_bilingual;
+        config.translate_templates = options.translation_templates;
+        config.translate_social_meta = options.translation_social_meta;
+        config.embed_provenance = options.translation_provenance;
+        config.translate_canvas_fallback = options.canvas_fallback;
+        config
+    }
+
+    /// JSON 输出需要原文→译文对照，因此在翻译前记录可翻译的文本项
+    #[cfg(feature = "translation")]
+    fn capture_translation_snapshot(
+        &self,
+        dom: &RcDom,
+    ) -> Option<Vec<crate::translation::TextItem>> {
+        let options = &self.session.options;
+        if options.output_format != MonolithOutputFormat::Json
+            || !options.enable_translation
+            || self.session.in_iframe_processing
+        {
+            return None;
+        }
+
+        crate::translation::TextCollector::default()
+            .collect_translatable_texts(&dom.document)
+            .ok()
+    }
+
+    #[cfg(feature = "translation")]
+    fn start_concurrent_translation(
+        &self,
+        dom: &RcDom,
+    ) -> Result<Option<crate::translation::ConcurrentTranslation>, MonolithError> {
+        let options = &self.session.options;
+        if !options.enable_translation
+            || !options.concurrent_translation
+            || self.session.in_iframe_processing
+        {
+            return Ok(None);
+        }
+
+        crate::translation::ConcurrentTranslation::start(dom, self.translation_config())
+            .map(Some)
+            .map_err(|e| MonolithError::new(&format!("Translation error: {e}")))
+    }
+
+    #[cfg(feature = "translation")]
+    fn finish_concurrent_translation(
+        &self,
+        dom: RcDom,
+        translation: crate::translation::ConcurrentTranslation,
+    ) -> Result<RcDom, MonolithError> {
+        translation
+            .finish(&dom)
+            .map_err(|e| MonolithError::new(&format!("Translation error: {e}")))?;
+
+        if !self.session.options.silent {
+            println!("Translation completed");
+        }
+
+        Ok(dom)
+    }
+
+    fn update_base_element(&self, dom: RcDom, base_url: &Url) -> Result<RcDom, MonolithError> {
         if let Some(new_base_url) = self.session.options.base_url.clone() {
             Ok(set_base_url(&dom.document, new_base_url))
+        } else if self.session.options.inject_base && !inlines_all_assets(&self.session.options) {
+            Ok(inject_base(dom, base_url))
         } else {
             Ok(dom)
         }
@@ -698,6 +1522,46 @@ impl DocumentProcessor {
         }
     }
 
+    fn set_viewport_meta(&self, dom: RcDom) -> RcDom {
+        let options = &self.session.options;
+        if !options.force_viewport {
+            return dom;
+        }
+
+        let dom = set_viewport(dom, DEFAULT_VIEWPORT);
+        match options
+            .max_content_width
+            .as_deref()
+            .filter(|width| is_css_length(width))
+        {
+            Some(width) => add_stylesheet(
+                dom,
+                &format!("body {{ max-width: {width}; margin-left: auto; margin-right: auto; }}"),
+            ),
+            None => dom,
+        }
+    }
+
+    fn set_archive_metadata(
+        &self,
+        dom: RcDom,
+        input_target: &Option<String>,
+    ) -> Result<RcDom, MonolithError> {
+        let options = &self.session.options;
+        if !options.add_archive_metadata || options.no_metadata {
+            return Ok(dom);
+        }
+        match input_target.as_ref().filter(|t| !t.is_empty()) {
+            Some(target) => {
+                let url = Url::parse(target).map_err(|e| {
+                    MonolithError::new(&format!("Failed to parse target URL: {}", e))
+                })?;
+                Ok(set_archive_metadata(dom, &url))
+            }
+            None => Ok(dom),
+        }
+    }
+
     fn process_custom_encoding(
         &self,
         dom: RcDom,
@@ -925,7 +1789,13 @@ impl FaviconProcessor {
 
         match session.retrieve_asset(base_url, &favicon_ico_url) {
             Ok((data, final_url, media_type, charset)) => {
-                let favicon_data_url = create_data_url(&media_type, &charset, &data, &final_url);
+                let favicon_data_url = create_data_url_with_encoding(
+                    &media_type,
+                    &charset,
+                    &data,
+                    &final_url,
+                    session.options.data_url_encoding,
+                );
                 Ok(add_favicon(&dom.document, favicon_data_url.to_string()))
             }
             Err(_) => {
@@ -940,6 +1810,9 @@ impl FaviconProcessor {
 pub struct OutputFormatter<'a> {
     options: &'a MonolithOptions,
     input_target: &'a Option<String>,
+    base_url: Option<&'a Url>,
+    resources: &'a [EmbeddedResource],
+    translations: Option<Vec<(String, String)>>,
 }
 
 impl<'a> OutputFormatter<'a> {
@@ -947,67 +1820,158 @@ impl<'a> OutputFormatter<'a> {
         Self {
             options,
             input_target,
+            base_url: None,
+            resources: &[],
+            translations: None,
         }
     }
 
+    /// 设置JSON输出使用的基础URL和已嵌入资源列表
+    pub fn with_resources(mut self, base_url: &'a Url, resources: &'a [EmbeddedResource]) -> Self {
+        self.base_url = Some(base_url);
+        self.resources = resources;
+        self
+    }
+
+    /// 设置JSON输出中的翻译对照
+    pub fn with_translations(mut self, translations: Vec<(String, String)>) -> Self {
+        self.translations = Some(translations);
+        self
+    }
+
     pub fn format_output(
         &self,
         dom: RcDom,
         document_encoding: String,
     ) -> Result<Vec<u8>, MonolithError> {
+        let mut result = Vec::new();
+        self.write_output(&mut result, dom, document_encoding)?;
+        Ok(result)
+    }
+
+    /// 按输出格式把文档写入 `writer`
+    ///
+    /// HTML 和 MHTML 边序列化边写出；文本和 JSON 输出需要先生成完整的结果再写出。
+    pub fn write_output<W: Write>(
+        &self,
+        writer: &mut W,
+        dom: RcDom,
+        document_encoding: String,
+    ) -> Result<(), MonolithError> {
+        let write_error =
+            |e: std::io::Error| MonolithError::new(&format!("Failed to write document: {}", e));
+
         match self.options.output_format {
-            MonolithOutputFormat::HTML => self.format_html_output(dom, document_encoding),
-            MonolithOutputFormat::MHTML => self.format_mhtml_output(dom, document_encoding),
+            MonolithOutputFormat::HTML => self.write_html_output(writer, dom, document_encoding),
+            MonolithOutputFormat::MHTML => {
+                self.write_mime_header(writer).map_err(write_error)?;
+                self.write_document(writer, dom, document_encoding)?;
+                self.write_mime_footer(writer).map_err(write_error)
+            }
+            MonolithOutputFormat::Text => writer
+                .write_all(&self.format_text_output(dom))
+                .map_err(write_error),
+            MonolithOutputFormat::Json => writer
+                .write_all(&self.format_json_output(dom, document_encoding)?)
+                .map_err(write_error),
         }
     }
 
-    fn format_html_output(
+    fn format_json_output(
         &self,
         dom: RcDom,
         document_encoding: String,
     ) -> Result<Vec<u8>, MonolithError> {
-        let mut result = serialize_document(dom, document_encoding, self.options);
+        let base_url = match self.base_url {
+            Some(base_url) => base_url.clone(),
+            None => Url::parse("data:text/html,")
+                .map_err(|e| MonolithError::new(&format!("Failed to parse base URL: {}", e)))?,
+        };
 
-        self.prepend_metadata_if_needed(&mut result)?;
-        self.ensure_trailing_newline(&mut result);
+        let mut document =
+            JsonDocument::new(&dom.document, &document_encoding, &base_url, self.resources);
+        if let Some(translations) = &self.translations {
+            document = document.with_translations(translations.clone());
+        }
+        if self.options.fingerprint {
+            document = document.with_fingerprint(content_fingerprint(&dom.document));
+        }
 
-        Ok(result)
+        Ok(document.to_json().into_bytes())
+    }
+
+    fn format_text_output(&self, dom: RcDom) -> Vec<u8> {
+        if self.options.canvas_fallback {
+            let mut builder = TextBuilder::new().with_canvas_fallback();
+            builder.walk(&dom.document);
+            builder.finish().into_bytes()
+        } else {
+            build_text(&dom.document).into_bytes()
+        }
     }
 
-    fn format_mhtml_output(
+    /// 写出文档，确保输出以换行结尾
+    fn write_html_output<W: Write>(
         &self,
+        writer: &mut W,
         dom: RcDom,
         document_encoding: String,
-    ) -> Result<Vec<u8>, MonolithError> {
-        let mut result = serialize_document(dom, document_encoding, self.options);
+    ) -> Result<(), MonolithError> {
+        let mut writer = LastByteWriter {
+            inner: writer,
+            last: None,
+        };
+        self.write_document(&mut writer, dom, document_encoding)?;
+        if writer.last != Some(b'\n') {
+            writer
+                .write_all(b"\n")
+                .map_err(|e| MonolithError::new(&format!("Failed to write document: {}", e)))?;
+        }
 
-        self.prepend_metadata_if_needed(&mut result)?;
-        self.add_mime_headers(&mut result);
+        Ok(())
+    }
 
-        Ok(result)
+    /// 依次写出元数据注释、内容指纹注释和序列化后的文档
+    ///
+    /// 文档直接序列化到缓冲区末尾，不必再把注释插入到整个文档之前
+    fn write_document<W: Write>(
+        &self,
+        writer: &mut W,
+        dom: RcDom,
+        document_encoding: String,
+    ) -> Result<(), MonolithError> {
+        let write_error =
+            |e: std::io::Error| MonolithError::new(&format!("Failed to write document: {}", e));
+
+        if let Some(metadata_comment) = self.metadata_comment()? {
+            writeln!(writer, "{}", metadata_comment).map_err(write_error)?;
+        }
+        // 在序列化之前计算内容指纹（序列化会消耗DOM）
+        if self.options.fingerprint {
+            let fingerprint = content_fingerprint(&dom.document);
+            writeln!(writer, "{}", create_fingerprint_tag(&fingerprint)).map_err(write_error)?;
+        }
+
+        serialize_document_to_writer(dom, document_encoding, self.options, writer)
+            .map_err(write_error)
     }
 
-    fn prepend_metadata_if_needed(&self, result: &mut Vec<u8>) -> Result<(), MonolithError> {
-        if !self.options.no_metadata {
-            if let Some(target) = self.input_target.as_ref().filter(|t| !t.is_empty()) {
+    fn metadata_comment(&self) -> Result<Option<String>, MonolithError> {
+        if self.options.no_metadata {
+            return Ok(None);
+        }
+        match self.input_target.as_ref().filter(|t| !t.is_empty()) {
+            Some(target) => {
                 let url = Url::parse(target).map_err(|e| {
                     MonolithError::new(&format!("Failed to parse target URL: {}", e))
                 })?;
-                let mut metadata_comment = create_metadata_tag(&url);
-                metadata_comment.push('\n');
-                result.splice(0..0, metadata_comment.as_bytes().to_vec());
+                Ok(Some(create_metadata_tag(&url)))
             }
-        }
-        Ok(())
-    }
-
-    fn ensure_trailing_newline(&self, result: &mut Vec<u8>) {
-        if result.last() != Some(&b'\n') {
-            result.extend_from_slice(b"\n");
+            None => Ok(None),
         }
     }
 
-    fn add_mime_headers(&self, result: &mut Vec<u8>) {
+    fn write_mime_header<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
         let mime_header = "MIME-Version: 1.0\r\n\
 Content-Type: multipart/related; boundary=\"----=_NextPart_000_0000\"\r\n\
 \r\n\
@@ -1016,9 +1980,31 @@ Content-Type: text/html; charset=\"utf-8\"\r\n\
 Content-Location: http://example.com/\r\n\
 \r\n";
 
-        result.splice(0..0, mime_header.as_bytes().to_vec());
+        writer.write_all(mime_header.as_bytes())
+    }
 
+    fn write_mime_footer<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
         let mime_footer = "\r\n------=_NextPart_000_0000--\r\n";
-        result.extend_from_slice(mime_footer.as_bytes());
+        writer.write_all(mime_footer.as_bytes())
+    }
+}
+
+/// 记录最后写出的字节的写入器，用于判断输出是否以换行结尾
+struct LastByteWriter<'w, W: Write> {
+    inner: &'w mut W,
+    last: Option<u8>,
+}
+
+impl<W: Write> Write for LastByteWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        if written > 0 {
+            self.last = Some(buf[written - 1]);
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
     }
 }
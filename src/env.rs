@@ -303,6 +303,51 @@ pub mod translation {
             Ok(Duration::from_secs(seconds))
         }
     }
+
+    /// 尽力而为模式（未设置时沿用配置文件）
+    pub struct BestEffort;
+    impl EnvVar<bool> for BestEffort {
+        const NAME: &'static str = "MONOLITH_TRANSLATION_BEST_EFFORT";
+        const DEFAULT: Option<bool> = None;
+        const DESCRIPTION: &'static str =
+            "Keep partial translations when some batches fail instead of failing the page";
+
+        fn parse(value: &str) -> EnvResult<bool> {
+            parse_bool(value, Self::NAME)
+        }
+    }
+
+    /// 启动时对翻译后端的自检方式
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum SelfCheckMode {
+        /// 不检查（离线环境也能正常启动）
+        Off,
+        /// 检查失败时只记录警告
+        Warn,
+        /// 检查失败时拒绝启动
+        Strict,
+    }
+
+    /// 启动自检
+    pub struct SelfCheck;
+    impl EnvVar<SelfCheckMode> for SelfCheck {
+        const NAME: &'static str = "MONOLITH_TRANSLATION_SELF_CHECK";
+        const DEFAULT: Option<SelfCheckMode> = Some(SelfCheckMode::Off);
+        const DESCRIPTION: &'static str =
+            "Probe the translation backend at startup: off, warn, strict";
+
+        fn parse(value: &str) -> EnvResult<SelfCheckMode> {
+            match value.trim().to_lowercase().as_str() {
+                "off" | "false" | "0" | "" => Ok(SelfCheckMode::Off),
+                "warn" | "true" | "1" => Ok(SelfCheckMode::Warn),
+                "strict" => Ok(SelfCheckMode::Strict),
+                _ => Err(EnvError {
+                    variable: Self::NAME.to_string(),
+                    message: format!("Invalid self-check mode '{}'. Use: off, warn, strict", value),
+                }),
+            }
+        }
+    }
 }
 
 /// 缓存相关环境变量
@@ -449,6 +494,128 @@ pub mod web {
             Ok(value.trim().to_string())
         }
     }
+    
+    /// 文档库目录（批量入库状态和文档）
+    pub struct LibraryDir;
+    impl EnvVar<String> for LibraryDir {
+        const NAME: &'static str = "MONOLITH_WEB_LIBRARY_DIR";
+        const DEFAULT: Option<String> = None;
+        
+        fn get() -> EnvResult<String> {
+            match env::var(Self::NAME) {
+                Ok(value) => Self::parse(&value),
+                Err(_) => Ok("library".to_string()),
+            }
+        }
+        const DESCRIPTION: &'static str = "Library directory for bulk ingest state and pages";
+        
+        fn parse(value: &str) -> EnvResult<String> {
+            let dir = value.trim();
+            if dir.is_empty() {
+                return Err(EnvError {
+                    variable: Self::NAME.to_string(),
+                    message: "Library directory cannot be empty".to_string(),
+                });
+            }
+            Ok(dir.to_string())
+        }
+    }
+
+    /// 已入库文档的存储方式
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum LibraryBackendKind {
+        /// 保存在文档库目录中的单个 redb 文件中
+        Redb,
+        /// 每个文档保存为文档库目录中的一个文件
+        Filesystem,
+    }
+
+    /// 文档库存储后端
+    pub struct LibraryBackend;
+    impl EnvVar<LibraryBackendKind> for LibraryBackend {
+        const NAME: &'static str = "MONOLITH_WEB_LIBRARY_BACKEND";
+        const DEFAULT: Option<LibraryBackendKind> = Some(LibraryBackendKind::Redb);
+        const DESCRIPTION: &'static str = "Storage for ingested library pages: redb, filesystem";
+
+        fn parse(value: &str) -> EnvResult<LibraryBackendKind> {
+            match value.trim().to_lowercase().as_str() {
+                "redb" | "" => Ok(LibraryBackendKind::Redb),
+                "filesystem" | "fs" => Ok(LibraryBackendKind::Filesystem),
+                _ => Err(EnvError {
+                    variable: Self::NAME.to_string(),
+                    message: format!("Invalid library backend '{}'. Use: redb, filesystem", value),
+                }),
+            }
+        }
+    }
+
+    /// 文档库统计的刷新间隔
+    pub struct LibraryStatsInterval;
+    impl EnvVar<Duration> for LibraryStatsInterval {
+        const NAME: &'static str = "MONOLITH_WEB_LIBRARY_STATS_INTERVAL";
+        const DEFAULT: Option<Duration> = Some(Duration::from_secs(300));
+        const DESCRIPTION: &'static str = "Library stats refresh interval in seconds";
+        
+        fn parse(value: &str) -> EnvResult<Duration> {
+            let seconds: u64 = value.parse().map_err(|_| EnvError {
+                variable: Self::NAME.to_string(),
+                message: "Must be a valid number of seconds".to_string(),
+            })?;
+            
+            if seconds == 0 {
+                return Err(EnvError {
+                    variable: Self::NAME.to_string(),
+                    message: "Interval must be greater than 0".to_string(),
+                });
+            }
+            
+            Ok(Duration::from_secs(seconds))
+        }
+    }
+
+    /// 翻译流的最长翻译时间，超时后不再发送剩余的批次
+    pub struct TranslationTimeout;
+    impl EnvVar<Option<Duration>> for TranslationTimeout {
+        const NAME: &'static str = "MONOLITH_WEB_TRANSLATION_TIMEOUT";
+        const DEFAULT: Option<Option<Duration>> = Some(Some(Duration::from_secs(300)));
+        const DESCRIPTION: &'static str =
+            "Translation stream time limit in seconds, 0 for no limit";
+
+        fn parse(value: &str) -> EnvResult<Option<Duration>> {
+            let seconds: u64 = value.parse().map_err(|_| EnvError {
+                variable: Self::NAME.to_string(),
+                message: "Must be a valid number of seconds".to_string(),
+            })?;
+
+            Ok((seconds > 0).then(|| Duration::from_secs(seconds)))
+        }
+    }
+
+    /// 在所有请求之间共享已获取的资源，保存在文档库目录中
+    pub struct SharedAssetCache;
+    impl EnvVar<bool> for SharedAssetCache {
+        const NAME: &'static str = "MONOLITH_WEB_SHARED_ASSET_CACHE";
+        const DEFAULT: Option<bool> = Some(false);
+        const DESCRIPTION: &'static str =
+            "Cache fetched assets on disk and share them between requests";
+
+        fn parse(value: &str) -> EnvResult<bool> {
+            parse_bool(value, Self::NAME)
+        }
+    }
+
+    /// 允许抓取内部地址（回环、私有网络等），只应在本机或可信网络中使用
+    pub struct AllowInternalFetch;
+    impl EnvVar<bool> for AllowInternalFetch {
+        const NAME: &'static str = "MONOLITH_WEB_ALLOW_INTERNAL_FETCH";
+        const DEFAULT: Option<bool> = Some(false);
+        const DESCRIPTION: &'static str =
+            "Allow fetching pages and assets from loopback and private network addresses";
+
+        fn parse(value: &str) -> EnvResult<bool> {
+            parse_bool(value, Self::NAME)
+        }
+    }
 }
 
 /// MongoDB相关环境变量
@@ -679,6 +846,7 @@ pub struct EnvConfig {
     pub translation_max_concurrent_requests: usize,
     pub translation_batch_size: usize,
     pub translation_batch_timeout: Duration,
+    pub translation_self_check: translation::SelfCheckMode,
     
     // 缓存配置
     pub cache_enabled: bool,
@@ -690,6 +858,7 @@ pub struct EnvConfig {
     pub web_bind_address: String,
     pub web_port: u16,
     pub web_static_dir: String,
+    pub web_library_dir: String,
     
     // MongoDB配置
     pub mongodb_connection_string: String,
@@ -724,6 +893,7 @@ impl EnvConfig {
             translation_max_concurrent_requests: translation::MaxConcurrentRequests::get()?,
             translation_batch_size: translation::BatchSize::get()?,
             translation_batch_timeout: translation::BatchTimeout::get()?,
+            translation_self_check: translation::SelfCheck::get()?,
             
             // 缓存配置
             cache_enabled: cache::Enabled::get()?,
@@ -735,6 +905,7 @@ impl EnvConfig {
             web_bind_address: web::BindAddress::get()?,
             web_port: web::Port::get()?,
             web_static_dir: web::StaticDir::get()?,
+            web_library_dir: web::LibraryDir::get()?,
             
             // MongoDB配置
             mongodb_connection_string: mongodb::ConnectionString::get()?,
@@ -796,6 +967,10 @@ pub fn generate_env_docs() -> String {
         translation::SourceLang::NAME, translation::SourceLang::DESCRIPTION, translation::SourceLang::DEFAULT));
     docs.push_str(&format!("- `{}`: {} (default: {:?})\n", 
         translation::ApiUrl::NAME, translation::ApiUrl::DESCRIPTION, translation::ApiUrl::DEFAULT));
+    docs.push_str(&format!("- `{}`: {} (default: {:?})\n", 
+        translation::BestEffort::NAME, translation::BestEffort::DESCRIPTION, translation::BestEffort::DEFAULT));
+    docs.push_str(&format!("- `{}`: {} (default: {:?})\n", 
+        translation::SelfCheck::NAME, translation::SelfCheck::DESCRIPTION, translation::SelfCheck::DEFAULT));
     
     // 更多部分...
     docs.push_str("\n## Cache Configuration\n\n");
@@ -857,6 +1032,45 @@ mod tests {
         assert!(translation::ApiUrl::parse("not-a-url").is_err());
     }
     
+    #[test]
+    fn test_self_check_mode_parsing() {
+        use translation::SelfCheckMode;
+
+        assert_eq!(translation::SelfCheck::parse("off").unwrap(), SelfCheckMode::Off);
+        assert_eq!(translation::SelfCheck::parse("Warn").unwrap(), SelfCheckMode::Warn);
+        assert_eq!(translation::SelfCheck::parse("strict").unwrap(), SelfCheckMode::Strict);
+        assert!(translation::SelfCheck::parse("always").is_err());
+    }
+    
+    #[test]
+    fn test_library_backend_parsing() {
+        use web::LibraryBackendKind;
+
+        assert_eq!(
+            web::LibraryBackend::parse("redb").unwrap(),
+            LibraryBackendKind::Redb
+        );
+        assert_eq!(
+            web::LibraryBackend::parse("FileSystem").unwrap(),
+            LibraryBackendKind::Filesystem
+        );
+        assert_eq!(
+            web::LibraryBackend::parse("fs").unwrap(),
+            LibraryBackendKind::Filesystem
+        );
+        assert!(web::LibraryBackend::parse("mongodb").is_err());
+    }
+
+    #[test]
+    fn test_translation_timeout_parsing() {
+        assert_eq!(
+            web::TranslationTimeout::parse("90").unwrap(),
+            Some(Duration::from_secs(90))
+        );
+        assert_eq!(web::TranslationTimeout::parse("0").unwrap(), None);
+        assert!(web::TranslationTimeout::parse("soon").is_err());
+    }
+
     #[test]
     fn test_numeric_validation() {
         // 测试数值范围
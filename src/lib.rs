@@ -12,7 +12,6 @@
 //! - `translation` - 翻译功能（可选）
 //! - `web` - Web服务器功能（可选）
 
-#[cfg(feature = "web")]
 pub mod builders;
 pub mod core;
 pub mod env;
@@ -1,17 +1,24 @@
 use std::fs;
-use std::io::{self, Error as IoError, Read, Write};
+use std::io::{self, BufWriter, Error as IoError, Read, Write};
+use std::path::Path;
 use std::process;
+use std::sync::Arc;
 
 use clap::Parser;
 use tempfile::{Builder, NamedTempFile};
 
 use monolith::core::{
-    create_monolithic_document, create_monolithic_document_from_data, format_output_path,
-    print_error_message, MonolithOptions, MonolithOutputFormat,
+    archive_to_writer, create_monolithic_document, create_monolithic_document_from_data,
+    format_output_path, is_css_length, print_error_message, print_info_message, save_output,
+    DataUrlEncoding, JsonScriptPolicy, MonolithError, MonolithOptions, MonolithOutputFormat,
+    OutputCompression, RefererPolicy,
 };
+use monolith::html::{redact_json_strings, SanitizeProfile};
 use monolith::network::cache::Cache;
 use monolith::network::cookies::{parse_cookie_file_contents, Cookie};
 use monolith::network::session::Session;
+use monolith::utils::media::Viewport;
+use monolith::utils::url::Url;
 
 const ASCII: &str = " \
  _____    _____________   __________     ___________________    ___
@@ -40,18 +47,38 @@ struct Cli {
     #[arg(short, long, value_name = "http://localhost/")]
     base_url: Option<String>,
 
+    /// Add a <base> pointing at the document's URL when some asset types are excluded
+    #[arg(long)]
+    inject_base: bool,
+
     /// Treat specified domains as blacklist
     #[arg(short = 'B', long)]
     blacklist_domains: bool,
 
+    /// Strip resources from hosts matching this pattern, e.g. *.google-analytics.com
+    #[arg(long = "block-host", value_name = "PATTERN")]
+    block_hosts: Vec<String>,
+
     /// Remove CSS
     #[arg(short = 'c', long)]
     no_css: bool,
 
+    /// Embed resources referenced only by LINK rel="preload" before dropping the hint
+    #[arg(long)]
+    capture_preloads: bool,
+
     /// Specify cookie file
     #[arg(short = 'C', long, value_name = "cookies.txt")]
     cookie_file: Option<String>,
 
+    /// Encode embedded assets as base64, percent-encoded text, or whichever is shorter (base64, percent, auto)
+    #[arg(long, value_name = "ENCODING")]
+    data_url_encoding: Option<String>,
+
+    /// Referer sent when fetching assets: the referring page (full), its origin, none, or a fixed URL
+    #[arg(long, value_name = "POLICY")]
+    referer: Option<String>,
+
     /// Specify domains to use for white/black-listing
     #[arg(short = 'd', long = "domain", value_name = "example.com")]
     domains: Vec<String>,
@@ -64,6 +91,14 @@ struct Cli {
     #[arg(short = 'E', long, value_name = "UTF-8")]
     encoding: Option<String>,
 
+    /// Append the stylesheet from this file to the document's HEAD
+    #[arg(long, value_name = "style.css")]
+    extra_css: Option<String>,
+
+    /// Archive the destination of pages that only redirect via META refresh
+    #[arg(long)]
+    follow_meta_refresh: bool,
+
     /// Remove frames and iframes
     #[arg(short = 'f', long)]
     no_frames: bool,
@@ -84,18 +119,106 @@ struct Cli {
     #[arg(short = 'j', long)]
     no_js: bool,
 
+    /// Output a JSON summary of the page instead of the document
+    #[arg(long, conflicts_with_all = ["mhtml", "text"])]
+    json: bool,
+
+    /// Keep, remove or redact JSON state blobs (keep, remove, redact)
+    #[arg(long, value_name = "POLICY")]
+    json_scripts: Option<String>,
+
     /// Allow invalid X.509 (TLS) certificates
     #[arg(short = 'k', long)]
     insecure: bool,
 
+    /// Allow invalid X.509 (TLS) certificates only for hosts matching this pattern
+    #[arg(long = "insecure-host", value_name = "PATTERN")]
+    insecure_hosts: Vec<String>,
+
     /// Use MHTML as output format
     #[arg(short = 'm', long)]
     mhtml: bool,
 
+    /// Collapse whitespace, strip comments and redundant attribute quotes
+    #[arg(long)]
+    minify: bool,
+
+    /// Keep HTML comments when minifying
+    #[arg(long, requires = "minify")]
+    minify_keep_comments: bool,
+
+    /// Record a content fingerprint for detecting changes between archives
+    #[arg(long)]
+    fingerprint: bool,
+
+    /// Drop duplicate META tags and keep a single charset declaration first in HEAD
+    #[arg(long)]
+    normalize_head: bool,
+
+    /// Set META viewport to "width=device-width, initial-scale=1" so mobile-first pages don't render tiny
+    #[arg(long)]
+    force_viewport: bool,
+
+    /// Limit the width of BODY and center it when --force-viewport is set
+    #[arg(long, value_name = "48rem")]
+    max_width: Option<String>,
+
+    /// Remove PING from links and set privacy-preserving REL and REFERRERPOLICY
+    #[arg(long)]
+    strip_hyperlink_auditing: bool,
+
+    /// Remove inline ON* event handler attributes while keeping SCRIPT elements
+    #[arg(long)]
+    strip_event_handlers: bool,
+
+    /// Remove this attribute from every element, a trailing * matches by prefix, e.g. data-analytics-*
+    #[arg(long = "strip-attribute", value_name = "NAME")]
+    strip_attributes: Vec<String>,
+
+    /// Embed resources whose URL matches PATTERN (.woff2, *.svg, cdn.example.com/fonts/*) as TYPE
+    #[arg(long = "media-type", value_name = "PATTERN=TYPE")]
+    media_types: Vec<String>,
+
+    /// Translate and output text of fallback content inside CANVAS elements
+    #[arg(long)]
+    canvas_fallback: bool,
+
+    /// Remove scripts, event handlers, script URLs, HTML data frames, plugins and external form actions
+    #[arg(long)]
+    sanitize: bool,
+
+    /// Load IMG elements eagerly, removing LOADING=lazy, DECODING and FETCHPRIORITY hints
+    #[arg(long)]
+    eager_images: bool,
+
+    /// Keep CROSSORIGIN and REFERRERPOLICY on SCRIPT and LINK elements whose resources were embedded
+    #[arg(long)]
+    keep_fetch_attributes: bool,
+
+    /// Stop embedding after this many assets and keep remaining resources as URLs
+    #[arg(long, value_name = "COUNT")]
+    max_embedded_assets: Option<usize>,
+
+    /// Inline frames nested at most this many levels deep and keep deeper ones as absolute URLs
+    #[arg(long, value_name = "DEPTH")]
+    max_iframe_depth: Option<usize>,
+
+    /// Stop inlining frames once they add up to this many bytes and keep the rest as absolute URLs
+    #[arg(long, value_name = "BYTES")]
+    max_inlined_frames_size: Option<usize>,
+
+    /// Only save the first element matching SELECTOR (tag, .class and #id) and the stylesheets it needs
+    #[arg(long, value_name = "SELECTOR")]
+    root_selector: Option<String>,
+
     /// Exclude timestamp and source information
     #[arg(short = 'M', long)]
     no_metadata: bool,
 
+    /// Record archive time and source URL in META tags inside HEAD
+    #[arg(long)]
+    archive_metadata: bool,
+
     /// Replace NOSCRIPT elements with their contents
     #[arg(short = 'n', long)]
     unwrap_noscript: bool,
@@ -104,6 +227,10 @@ struct Cli {
     #[arg(short, long, value_name = "result.html")]
     output: Option<String>,
 
+    /// Compress the output file (gzip, br), also chosen by a .gz or .br output file extension
+    #[arg(long, value_name = "COMPRESSION")]
+    compress: Option<String>,
+
     /// Suppress verbosity
     #[arg(short, long)]
     quiet: bool,
@@ -112,6 +239,22 @@ struct Cli {
     #[arg(short, long, value_name = "60")]
     timeout: Option<u64>,
 
+    /// Override the network timeout for the main document
+    #[arg(long, value_name = "60")]
+    document_timeout: Option<u64>,
+
+    /// Override the network timeout for each asset
+    #[arg(long, value_name = "10")]
+    asset_timeout: Option<u64>,
+
+    /// Retry the main document this many times on connection errors and timeouts
+    #[arg(long, value_name = "3")]
+    document_retries: Option<usize>,
+
+    /// Use plain text as output format
+    #[arg(long, conflicts_with = "mhtml")]
+    text: bool,
+
     /// Set custom User-Agent string
     #[arg(short, long, value_name = "Firefox")]
     user_agent: Option<String>,
@@ -120,6 +263,14 @@ struct Cli {
     #[arg(short = 'v', long)]
     no_video: bool,
 
+    /// Drop <picture> sources whose media query never matches this viewport
+    #[arg(long, value_name = "1280x800")]
+    viewport: Option<String>,
+
+    /// Keep only the first <picture> source in this comma-separated list of image formats
+    #[arg(long, value_name = "avif,webp")]
+    prefer_formats: Option<String>,
+
     /// Enable translation of page content
     #[cfg(feature = "translation")]
     #[arg(short = 'T', long)]
@@ -130,11 +281,41 @@ struct Cli {
     #[arg(long, value_name = "zh")]
     target_lang: Option<String>,
 
+    /// Source language of the page, skips language detection (default: auto)
+    #[cfg(feature = "translation")]
+    #[arg(long, value_name = "en", requires = "translate")]
+    source_lang: Option<String>,
+
     /// Translation API URL
     #[cfg(feature = "translation")]
     #[arg(long, value_name = "http://localhost:1188/translate")]
     translation_api: Option<String>,
 
+    /// Keep the original text on translated elements, shown on hover
+    #[cfg(feature = "translation")]
+    #[arg(long, requires = "translate")]
+    show_original: bool,
+
+    /// Follow each translated paragraph with its original text
+    #[cfg(feature = "translation")]
+    #[arg(long, requires = "translate")]
+    bilingual: bool,
+
+    /// Also translate text inside <template> elements
+    #[cfg(feature = "translation")]
+    #[arg(long, requires = "translate")]
+    translate_templates: bool,
+
+    /// Also translate OpenGraph and Twitter card titles and descriptions
+    #[cfg(feature = "translation")]
+    #[arg(long, requires = "translate")]
+    translate_social_meta: bool,
+
+    /// Embed JSON-LD describing the translation (languages, backend, time) in <head>
+    #[cfg(feature = "translation")]
+    #[arg(long, requires = "translate")]
+    translation_provenance: bool,
+
     /// Generate example translation configuration file
     #[cfg(feature = "translation")]
     #[arg(long)]
@@ -171,6 +352,11 @@ struct Cli {
 pub enum Output {
     Stdout(io::Stdout),
     File(fs::File),
+    Compressed {
+        path: String,
+        compression: OutputCompression,
+        silent: bool,
+    },
 }
 
 impl Output {
@@ -178,19 +364,53 @@ impl Output {
         destination: &str,
         document_title: &str,
         format: MonolithOutputFormat,
+        compression: OutputCompression,
+        silent: bool,
     ) -> Result<Output, IoError> {
         if destination.is_empty() || destination.eq("-") {
             Ok(Output::Stdout(io::stdout()))
         } else {
-            let final_destination = format_output_path(
-                destination,
-                Some(document_title),
-                format == MonolithOutputFormat::MHTML,
-            );
-            Ok(Output::File(fs::File::create(final_destination)?))
+            let final_destination = Output::path(destination, document_title, &format);
+            if compression == OutputCompression::None {
+                Ok(Output::File(fs::File::create(final_destination)?))
+            } else {
+                Ok(Output::Compressed {
+                    path: final_destination,
+                    compression,
+                    silent,
+                })
+            }
         }
     }
 
+    /// Resolves the output file path for the given document title
+    fn path(destination: &str, document_title: &str, format: &MonolithOutputFormat) -> String {
+        let extension = match format {
+            MonolithOutputFormat::Text => Some("txt"),
+            MonolithOutputFormat::Json => Some("json"),
+            _ => None,
+        };
+        let destination = match extension {
+            Some(extension) => destination
+                .replace("%extension%", extension)
+                .replace("%ext%", extension),
+            None => destination.to_string(),
+        };
+        format_output_path(
+            &destination,
+            Some(document_title),
+            *format == MonolithOutputFormat::MHTML,
+        )
+    }
+
+    /// Checks if the output can be opened before the document is processed,
+    /// i.e. its path does not depend on the document title and it is not compressed
+    fn can_stream(destination: &str, compression: OutputCompression) -> bool {
+        destination.is_empty()
+            || destination.eq("-")
+            || (compression == OutputCompression::None && !destination.contains("%title%"))
+    }
+
     fn write(&mut self, bytes: &[u8]) -> Result<(), IoError> {
         match self {
             Output::Stdout(stdout) => {
@@ -201,10 +421,74 @@ impl Output {
                 file.write_all(bytes)?;
                 file.flush()
             }
+            Output::Compressed {
+                path,
+                compression,
+                silent,
+            } => {
+                let saved = save_output(bytes, path, *compression)
+                    .map_err(|error| IoError::other(error.to_string()))?;
+                if !*silent {
+                    print_info_message(&format!(
+                        "{} ({} bytes, {} before compression)",
+                        saved.path.display(),
+                        saved.written_size,
+                        saved.uncompressed_size
+                    ));
+                }
+                Ok(())
+            }
         }
     }
 }
 
+fn archive_into<W: Write>(session: Session, target: &str, writer: W) -> Result<(), MonolithError> {
+    let mut writer = BufWriter::new(writer);
+    archive_to_writer(session, target, &mut writer)?;
+    writer
+        .flush()
+        .map_err(|error| MonolithError::new(&format!("could not write output: {error}")))
+}
+
+/// Archives the target into a temporary file next to the output file and moves it over the output
+/// file once archiving succeeds, so the output file is left untouched on failure and the target
+/// can be the output file itself
+fn archive_into_file(session: Session, target: &str, path: &str) -> Result<(), MonolithError> {
+    let output_error =
+        |error: IoError| MonolithError::new(&format!("could not write output: {error}"));
+    let parent = Path::new(path)
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let temp_file = NamedTempFile::new_in(parent).map_err(output_error)?;
+    archive_into(session, target, temp_file.as_file())?;
+
+    // Keep the permissions of the file being replaced, or use the usual ones for a new file
+    let permissions = match fs::metadata(path) {
+        Ok(metadata) => Some(metadata.permissions()),
+        Err(_) => default_file_permissions(),
+    };
+    if let Some(permissions) = permissions {
+        fs::set_permissions(temp_file.path(), permissions).map_err(output_error)?;
+    }
+    temp_file
+        .persist(path)
+        .map(|_| ())
+        .map_err(|error| output_error(error.error))
+}
+
+#[cfg(unix)]
+fn default_file_permissions() -> Option<fs::Permissions> {
+    use std::os::unix::fs::PermissionsExt;
+
+    Some(fs::Permissions::from_mode(0o644))
+}
+
+#[cfg(not(unix))]
+fn default_file_permissions() -> Option<fs::Permissions> {
+    None
+}
+
 pub fn read_stdin() -> Vec<u8> {
     let mut buffer: Vec<u8> = vec![];
 
@@ -220,17 +504,22 @@ fn main() {
     let mut exit_code = 0;
     let mut options: MonolithOptions = MonolithOptions::default();
     let destination;
+    let compression;
 
     // Process the command
     {
         options.base_url = cli.base_url;
+        options.inject_base = cli.inject_base;
         options.blacklist_domains = cli.blacklist_domains;
         options.encoding = cli.encoding;
         if !cli.domains.is_empty() {
             options.domains = Some(cli.domains);
         }
+        options.block_hosts = cli.block_hosts;
+        options.follow_meta_refresh = cli.follow_meta_refresh;
         options.ignore_errors = cli.ignore_errors;
         options.insecure = cli.insecure;
+        options.insecure_hosts = cli.insecure_hosts;
         options.isolate = cli.isolate;
         options.no_audio = cli.no_audio;
         options.no_css = cli.no_css;
@@ -244,10 +533,62 @@ fn main() {
             options.no_js = true;
         }
         options.no_metadata = cli.no_metadata;
+        options.add_archive_metadata = cli.archive_metadata;
+        options.fingerprint = cli.fingerprint;
         options.no_video = cli.no_video;
+        if cli.text {
+            options.output_format = MonolithOutputFormat::Text;
+            // Plain text output has no use for embedded assets
+            options.no_audio = true;
+            options.no_css = true;
+            options.no_fonts = true;
+            options.no_frames = true;
+            options.no_images = true;
+            options.no_js = true;
+            options.no_video = true;
+        }
+        if cli.json {
+            options.output_format = MonolithOutputFormat::Json;
+        }
         options.silent = cli.quiet;
         options.timeout = cli.timeout.unwrap_or(DEFAULT_NETWORK_TIMEOUT);
+        options.document_timeout = cli.document_timeout;
+        options.asset_timeout = cli.asset_timeout;
+        options.document_fetch_retries = cli.document_retries.unwrap_or(0);
         options.unwrap_noscript = cli.unwrap_noscript;
+        options.minify = cli.minify;
+        options.minify_preserve_comments = cli.minify_keep_comments;
+        options.capture_preloads = cli.capture_preloads;
+        options.normalize_head = cli.normalize_head;
+        options.force_viewport = cli.force_viewport;
+        options.strip_hyperlink_auditing = cli.strip_hyperlink_auditing;
+        options.strip_inline_event_handlers = cli.strip_event_handlers;
+        options.strip_attributes = cli.strip_attributes;
+        for rule in &cli.media_types {
+            match rule.split_once('=') {
+                Some((pattern, media_type))
+                    if !pattern.trim().is_empty() && media_type.contains('/') =>
+                {
+                    options
+                        .media_type_overrides
+                        .push((pattern.trim().to_string(), media_type.trim().to_string()));
+                }
+                _ => eprintln!(
+                    "Warning: Invalid media type rule '{}', expected PATTERN=TYPE.",
+                    rule
+                ),
+            }
+        }
+        options.canvas_fallback = cli.canvas_fallback;
+        options.eager_load_images = cli.eager_images;
+        options.keep_fetch_attributes = cli.keep_fetch_attributes;
+        options.max_embedded_assets = cli.max_embedded_assets;
+        options.max_iframe_depth = cli.max_iframe_depth;
+        options.max_inlined_frames_size = cli.max_inlined_frames_size;
+        options.root_selector = cli.root_selector;
+        if cli.sanitize {
+            options.sanitize = Some(SanitizeProfile::strict());
+        }
         if cli.user_agent.is_none() {
             options.user_agent = Some(DEFAULT_USER_AGENT.to_string());
         } else {
@@ -277,7 +618,13 @@ fn main() {
 
             options.enable_translation = cli.translate;
             options.target_language = cli.target_lang;
+            options.source_language = cli.source_lang;
             options.translation_api_url = cli.translation_api;
+            options.translation_show_original = cli.show_original;
+            options.translation_bilingual = cli.bilingual;
+            options.translation_templates = cli.translate_templates;
+            options.translation_social_meta = cli.translate_social_meta;
+            options.translation_provenance = cli.translation_provenance;
         }
 
         // 智能过滤选项
@@ -299,9 +646,98 @@ fn main() {
             }
             None => monolith::core::ResourceFilteringLevel::Moderate,
         };
+        options.json_script_policy = match cli.json_scripts.as_deref() {
+            Some("keep") | None => JsonScriptPolicy::Keep,
+            Some("remove") => JsonScriptPolicy::Remove,
+            Some("redact") => JsonScriptPolicy::Redact(Arc::new(redact_json_strings)),
+            Some(policy) => {
+                eprintln!(
+                    "Warning: Unknown JSON script policy '{}', using 'keep'.",
+                    policy
+                );
+                JsonScriptPolicy::Keep
+            }
+        };
+
+        options.data_url_encoding = match cli.data_url_encoding.as_deref() {
+            Some("base64") | None => DataUrlEncoding::Base64,
+            Some("percent") => DataUrlEncoding::PercentEncode,
+            Some("auto") => DataUrlEncoding::Auto,
+            Some(encoding) => {
+                eprintln!(
+                    "Warning: Unknown data URL encoding '{}', using 'base64'.",
+                    encoding
+                );
+                DataUrlEncoding::Base64
+            }
+        };
+
+        options.referer_policy = match cli.referer.as_deref() {
+            Some("full") | None => RefererPolicy::FullUrl,
+            Some("origin") => RefererPolicy::Origin,
+            Some("none") => RefererPolicy::None,
+            Some(referer) => match Url::parse(referer) {
+                Ok(referer) => RefererPolicy::Fixed(referer),
+                Err(_) => {
+                    eprintln!(
+                        "Warning: Unknown referer policy '{}', using 'full'.",
+                        referer
+                    );
+                    RefererPolicy::FullUrl
+                }
+            },
+        };
+
+        if let Some(viewport) = cli.viewport.as_deref() {
+            options.target_viewport = Viewport::parse(viewport);
+            if options.target_viewport.is_none() {
+                eprintln!(
+                    "Warning: Invalid viewport '{}', expected WIDTHxHEIGHT.",
+                    viewport
+                );
+            }
+        }
+
+        if let Some(max_width) = cli.max_width.as_deref() {
+            if is_css_length(max_width) {
+                options.max_content_width = Some(max_width.to_string());
+            } else {
+                eprintln!(
+                    "Warning: Invalid max width '{}', expected a CSS length.",
+                    max_width
+                );
+            }
+        }
+
+        if let Some(formats) = cli.prefer_formats.as_deref() {
+            options.preferred_image_formats = formats
+                .split(',')
+                .map(|format| format.trim().to_lowercase())
+                .filter(|format| !format.is_empty())
+                .map(|format| {
+                    if format.contains('/') {
+                        format
+                    } else {
+                        format!("image/{format}")
+                    }
+                })
+                .collect();
+        }
 
         cookie_file_path = cli.cookie_file;
         destination = cli.output.clone();
+        compression = match cli.compress.as_deref() {
+            Some("gzip") | Some("gz") => OutputCompression::Gzip,
+            Some("br") | Some("brotli") => OutputCompression::Brotli,
+            Some(compression) => {
+                eprintln!(
+                    "Warning: Unknown compression '{}', writing uncompressed output.",
+                    compression
+                );
+                OutputCompression::None
+            }
+            None => OutputCompression::from_path(cli.output.as_deref().unwrap_or_default()),
+        };
     }
 
     // Set up cache (attempt to create temporary file)
@@ -341,6 +777,21 @@ fn main() {
         }
     }
 
+    // Read custom stylesheet
+    if let Some(extra_css_file) = cli.extra_css.clone() {
+        match fs::read_to_string(&extra_css_file) {
+            Ok(css) => options.extra_css = Some(css),
+            Err(_) => {
+                if !options.silent {
+                    print_error_message(&format!(
+                        "could not read specified stylesheet \"{extra_css_file}\""
+                    ));
+                }
+                process::exit(1);
+            }
+        }
+    }
+
     // Initiate session
     let output_format = options.output_format.clone();
     let silent = options.silent;
@@ -359,6 +810,8 @@ fn main() {
                     &destination.unwrap_or(String::new()),
                     &title.unwrap_or_default(),
                     output_format,
+                    compression,
+                    silent,
                 )
                 .expect("could not prepare output");
 
@@ -373,6 +826,23 @@ fn main() {
                 exit_code = 1;
             }
         }
+    } else if Output::can_stream(destination.as_deref().unwrap_or_default(), compression) {
+        let path = destination
+            .filter(|destination| !destination.is_empty() && destination != "-")
+            .map(|destination| Output::path(&destination, "", &output_format));
+        // Write result into STDOUT or file while archiving
+        let archived = match &path {
+            Some(path) => archive_into_file(session, target, path),
+            None => archive_into(session, target, io::stdout().lock()),
+        };
+
+        if let Err(error) = archived {
+            if !silent {
+                print_error_message(&format!("Error: {error}"));
+            }
+
+            exit_code = 1;
+        }
     } else {
         match create_monolithic_document(session, &target) {
             Ok((result, title)) => {
@@ -381,6 +851,8 @@ fn main() {
                     &destination.unwrap_or(String::new()),
                     &title.unwrap_or_default(),
                     output_format,
+                    compression,
+                    silent,
                 )
                 .expect("could not prepare output");
 
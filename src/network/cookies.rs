@@ -1,7 +1,11 @@
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use chrono::DateTime;
+use url::Host;
+
 use crate::utils::url::Url;
 
+#[derive(Clone)]
 pub struct Cookie {
     pub domain: String,
     pub include_subdomains: bool,
@@ -31,6 +35,85 @@ impl Cookie {
         self.expires < since_the_epoch.as_secs()
     }
 
+    /// Parses a Set-Cookie header value received in response to a request for `url`
+    ///
+    /// Returns None for malformed headers and for cookies whose Domain attribute
+    /// doesn't cover the host that set them. Cookies deleted by the server via
+    /// a past Expires or non-positive Max-Age come back already expired.
+    pub fn from_set_cookie(header: &str, url: &Url) -> Option<Cookie> {
+        let host = url.host_str()?.to_lowercase();
+        let mut attributes = header.split(';');
+        let (name, value) = attributes.next()?.split_once('=')?;
+        let name = name.trim();
+        if name.is_empty() {
+            return None;
+        }
+
+        let mut cookie = Cookie {
+            domain: host.clone(),
+            include_subdomains: false,
+            path: default_cookie_path(url),
+            https_only: false,
+            expires: 0,
+            name: name.to_string(),
+            value: value.trim().trim_matches('"').to_string(),
+        };
+        let mut max_age: Option<i64> = None;
+
+        for attribute in attributes {
+            let (key, value) = match attribute.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => (attribute.trim(), ""),
+            };
+
+            if key.eq_ignore_ascii_case("domain") {
+                let domain = value.trim_start_matches('.').to_lowercase();
+                if domain.is_empty() || domain == host {
+                    continue;
+                }
+                // Only the host itself or one of its parent domains may be set
+                if !host.ends_with(&format!(".{}", domain))
+                    || !matches!(url.host(), Some(Host::Domain(_)))
+                {
+                    return None;
+                }
+                cookie.domain = format!(".{}", domain);
+                cookie.include_subdomains = true;
+            } else if key.eq_ignore_ascii_case("path") {
+                if value.starts_with('/') {
+                    cookie.path = value.to_string();
+                }
+            } else if key.eq_ignore_ascii_case("secure") {
+                cookie.https_only = true;
+            } else if key.eq_ignore_ascii_case("max-age") {
+                max_age = value.parse().ok();
+            } else if key.eq_ignore_ascii_case("expires") {
+                // Both "Wed, 21 Oct 2026 07:28:00 GMT" and the older "21-Oct-2026" form
+                if let Ok(date) = DateTime::parse_from_rfc2822(&value.replace('-', " ")) {
+                    cookie.expires = date.timestamp().max(1) as u64;
+                }
+            }
+        }
+
+        // Max-Age takes precedence over Expires
+        if let Some(max_age) = max_age {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("Time went backwards")
+                .as_secs();
+            cookie.expires = if max_age > 0 { now + max_age as u64 } else { 1 };
+        }
+
+        Some(cookie)
+    }
+
+    /// Whether both cookies have the same name, domain and path (i.e. one replaces the other)
+    pub fn is_same_cookie(&self, other: &Cookie) -> bool {
+        self.name == other.name
+            && self.domain.eq_ignore_ascii_case(&other.domain)
+            && self.path == other.path
+    }
+
     pub fn matches_url(&self, url: &str) -> bool {
         match Url::parse(url) {
             Ok(url) => {
@@ -52,8 +135,7 @@ impl Cookie {
                 if let Some(url_host) = url.host_str() {
                     if self.domain.starts_with(".") && self.include_subdomains {
                         if !url_host.to_lowercase().ends_with(&self.domain)
-                            && !url_host
-                                .eq_ignore_ascii_case(&self.domain[1..self.domain.len() - 1])
+                            && !url_host.eq_ignore_ascii_case(&self.domain[1..])
                         {
                             return false;
                         }
@@ -80,6 +162,14 @@ impl Cookie {
     }
 }
 
+/// Directory of the request path, used when Set-Cookie has no Path attribute
+fn default_cookie_path(url: &Url) -> String {
+    match url.path().rfind('/') {
+        Some(0) | None => "/".to_string(),
+        Some(index) => url.path()[..index].to_string(),
+    }
+}
+
 pub fn parse_cookie_file_contents(
     cookie_file_contents: &str,
 ) -> Result<Vec<Cookie>, CookieFileContentsParseError> {
@@ -0,0 +1,146 @@
+//! 抓取地址限制
+//!
+//! 公开部署的 Web 服务器会按用户提交的地址抓取页面和资源，不加限制时可以被用来读取本地文件
+//! (`file://`、本地路径) 或访问内网服务和云主机的元数据接口（SSRF）。[`FetchPolicy`] 限制可以
+//! 获取的协议，并拒绝连接到内部地址（见 [`is_internal_address`]）。
+//!
+//! 主机名在 DNS 解析之后检查：[`PublicAddressResolver`] 替代 HTTP 客户端的默认解析器，
+//! 丢弃解析结果中的内部地址，客户端只会连接到检查过的地址，DNS 重绑定无法绕过。
+//! 直接写成 IP 的地址不经过解析器，在请求之前和每次重定向时单独检查。
+//!
+//! 页面中的 data URL 本身就是页面内容，不需要获取，不受协议限制。
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs};
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use url::Host;
+
+use crate::utils::url::Url;
+
+/// Web 服务器默认允许的协议
+pub const SERVER_ALLOWED_SCHEMES: &[&str] = &["http", "https"];
+
+/// 可以抓取的地址
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FetchPolicy {
+    /// 允许获取的协议（小写）；本地路径按 `file` 处理，作为目标的 data URL 按 `data` 处理
+    pub allowed_schemes: Vec<String>,
+    /// 拒绝连接到回环、私有网络、链路本地等内部地址
+    pub deny_internal_addresses: bool,
+}
+
+impl FetchPolicy {
+    /// Web 服务器使用的限制：只允许 HTTP 和 HTTPS，拒绝内部地址
+    pub fn server() -> Self {
+        Self {
+            allowed_schemes: SERVER_ALLOWED_SCHEMES
+                .iter()
+                .map(|scheme| scheme.to_string())
+                .collect(),
+            deny_internal_addresses: true,
+        }
+    }
+
+    /// 是否允许获取该协议的地址
+    pub fn allows_scheme(&self, scheme: &str) -> bool {
+        self.allowed_schemes
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(scheme))
+    }
+
+    /// 在请求之前检查地址的协议和直接写出的 IP，不允许时返回原因
+    ///
+    /// 主机名要到解析之后才能判断，由 [`PublicAddressResolver`] 负责。
+    pub fn check_url(&self, url: &Url) -> Result<(), String> {
+        if !self.allows_scheme(url.scheme()) {
+            return Err(format!("{} URLs are not allowed", url.scheme()));
+        }
+        if self.deny_internal_addresses {
+            let ip = match url.host() {
+                Some(Host::Ipv4(ip)) => Some(IpAddr::V4(ip)),
+                Some(Host::Ipv6(ip)) => Some(IpAddr::V6(ip)),
+                _ => None,
+            };
+            if let Some(ip) = ip.filter(|ip| is_internal_address(*ip)) {
+                return Err(format!("internal address {ip} is not allowed"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 地址是否属于内部网络
+///
+/// 包括回环、未指定、私有网络（RFC 1918）、运营商级 NAT（100.64.0.0/10，部分云平台的元数据接口
+/// 位于其中）、链路本地（169.254.0.0/16，含 169.254.169.254）、保留和组播地址，IPv6 的唯一本地
+/// 和链路本地地址，以及内嵌上述 IPv4 地址的 IPv6 地址。
+pub fn is_internal_address(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_internal_ipv4(ip),
+        IpAddr::V6(ip) => is_internal_ipv6(ip),
+    }
+}
+
+fn is_internal_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+    ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || a == 0
+        || (a == 100 && (64..128).contains(&b))
+        || (a == 192 && b == 0 && c == 0)
+        || (a == 198 && (b == 18 || b == 19))
+        || a >= 240
+}
+
+fn is_internal_ipv6(ip: Ipv6Addr) -> bool {
+    let segments = ip.segments();
+    // IPv4 映射地址（::ffff:a.b.c.d）和 NAT64 地址（64:ff9b::a.b.c.d）最终连接到其中的 IPv4 地址
+    let embedded = ip.to_ipv4_mapped().or_else(|| {
+        (segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0]).then(|| {
+            let octets = ip.octets();
+            Ipv4Addr::new(octets[12], octets[13], octets[14], octets[15])
+        })
+    });
+    if let Some(ipv4) = embedded {
+        return is_internal_ipv4(ipv4);
+    }
+
+    ip.is_loopback()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        || (segments[0] & 0xfe00) == 0xfc00
+        || (segments[0] & 0xffc0) == 0xfe80
+}
+
+/// 丢弃内部地址的 DNS 解析器
+///
+/// 与 HTTP 客户端默认的解析器一样使用系统解析，阻塞的解析放在 tokio 的阻塞线程池中进行，
+/// 不占用运行时的工作线程。主机名只解析到内部地址时请求失败。
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PublicAddressResolver;
+
+impl Resolve for PublicAddressResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let lookup_host = host.clone();
+            let resolved: Vec<SocketAddr> =
+                tokio::task::spawn_blocking(move || (lookup_host.as_str(), 0).to_socket_addrs())
+                    .await??
+                    .collect();
+            let public: Vec<SocketAddr> = resolved
+                .iter()
+                .copied()
+                .filter(|addr| !is_internal_address(addr.ip()))
+                .collect();
+            if public.is_empty() && !resolved.is_empty() {
+                return Err(format!("{host} resolves to an internal address").into());
+            }
+            Ok(Box::new(public.into_iter()) as Addrs)
+        })
+    }
+}
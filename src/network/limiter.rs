@@ -0,0 +1,120 @@
+//! 资源抓取的连接数限制
+//!
+//! 并发抓取资源时需要遵守基本的爬虫礼仪：对同一个源站的并发连接数单独设限，
+//! 避免同时向一个主机发起几十个请求，同时允许不同主机之间并行抓取。
+//!
+//! [`ConnectionLimiter`] 由一个全局信号量和按主机名懒加载的信号量表组成，
+//! 两者相互独立。限制值为 0 表示不限制。一个限制器可以通过 `Arc` 在多个
+//! [`Session`](crate::network::Session) 或抓取线程之间共享。
+
+use std::collections::HashMap;
+use std::sync::{Arc, Condvar, Mutex};
+
+/// 阻塞式计数信号量
+struct Semaphore {
+    permits: Mutex<usize>,
+    available: Condvar,
+}
+
+impl Semaphore {
+    fn new(permits: usize) -> Self {
+        Self {
+            permits: Mutex::new(permits),
+            available: Condvar::new(),
+        }
+    }
+
+    fn acquire(self: &Arc<Self>) -> SemaphorePermit {
+        let mut permits = self.permits.lock().unwrap();
+        while *permits == 0 {
+            permits = self.available.wait(permits).unwrap();
+        }
+        *permits -= 1;
+
+        SemaphorePermit {
+            semaphore: Arc::clone(self),
+        }
+    }
+
+    fn release(&self) {
+        *self.permits.lock().unwrap() += 1;
+        self.available.notify_one();
+    }
+}
+
+struct SemaphorePermit {
+    semaphore: Arc<Semaphore>,
+}
+
+impl Drop for SemaphorePermit {
+    fn drop(&mut self) {
+        self.semaphore.release();
+    }
+}
+
+/// 全局和按主机的连接数限制器
+pub struct ConnectionLimiter {
+    global: Option<Arc<Semaphore>>,
+    max_connections_per_host: usize,
+    hosts: Mutex<HashMap<String, Arc<Semaphore>>>,
+}
+
+/// 连接许可，持有期间占用全局和所属主机的各一个连接名额
+///
+/// 释放（drop）时自动归还名额。
+pub struct ConnectionPermit {
+    // 先释放主机名额，再释放全局名额
+    _host: Option<SemaphorePermit>,
+    _global: Option<SemaphorePermit>,
+}
+
+impl ConnectionLimiter {
+    /// 创建限制器
+    ///
+    /// * `max_concurrent_fetches` - 全局并发连接上限，0 表示不限制
+    /// * `max_connections_per_host` - 单个主机的并发连接上限，0 表示不限制
+    pub fn new(max_concurrent_fetches: usize, max_connections_per_host: usize) -> Self {
+        Self {
+            global: (max_concurrent_fetches > 0)
+                .then(|| Arc::new(Semaphore::new(max_concurrent_fetches))),
+            max_connections_per_host,
+            hosts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 不做任何限制的限制器
+    pub fn unlimited() -> Self {
+        Self::new(0, 0)
+    }
+
+    /// 阻塞直到可以向 `host` 发起新连接
+    ///
+    /// 先获取主机名额再获取全局名额，这样等待同一主机的请求不会占用全局名额，
+    /// 其他主机的请求可以继续并行。
+    pub fn acquire(&self, host: &str) -> ConnectionPermit {
+        let host_permit = if self.max_connections_per_host > 0 {
+            let semaphore = {
+                let mut hosts = self.hosts.lock().unwrap();
+                Arc::clone(
+                    hosts
+                        .entry(host.to_lowercase())
+                        .or_insert_with(|| Arc::new(Semaphore::new(self.max_connections_per_host))),
+                )
+            };
+            Some(semaphore.acquire())
+        } else {
+            None
+        };
+
+        ConnectionPermit {
+            _host: host_permit,
+            _global: self.global.as_ref().map(|semaphore| semaphore.acquire()),
+        }
+    }
+}
+
+impl Default for ConnectionLimiter {
+    fn default() -> Self {
+        Self::unlimited()
+    }
+}
@@ -9,14 +9,20 @@
 //! # 模块组织
 //!
 //! - `session` - HTTP会话管理、请求处理、资源下载
+//! - `fetch_policy` - 允许抓取的协议和地址，防止服务器被用来访问内部网络
+//! - `limiter` - 全局和按主机的并发连接数限制
 //! - `cache` - 本地文件缓存系统
 //! - `cookies` - Cookie解析和管理
 
 pub mod cache;
 pub mod cookies;
+pub mod fetch_policy;
+pub mod limiter;
 pub mod session;
 
 // Re-export commonly used items for convenience
 pub use cache::Cache;
 pub use cookies::{parse_cookie_file_contents, Cookie};
-pub use session::Session;
+pub use fetch_policy::{is_internal_address, FetchPolicy, PublicAddressResolver};
+pub use limiter::{ConnectionLimiter, ConnectionPermit};
+pub use session::{EmbeddedResource, Session};
@@ -1,25 +1,77 @@
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::time::Duration;
-
-use reqwest::blocking::Client;
-use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE, COOKIE, REFERER, USER_AGENT};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use encoding_rs::{Encoding, UTF_8};
+use reqwest::blocking::{Client, ClientBuilder};
+use reqwest::header::{
+    HeaderMap, HeaderValue, CONTENT_TYPE, COOKIE, REFERER, SET_COOKIE, USER_AGENT,
+};
 
 use crate::core::{
-    detect_media_type, parse_content_type, print_error_message, print_info_message, MonolithOptions,
+    detect_media_type, parse_content_type, print_error_message, print_info_message,
+    MonolithOptions, RefererPolicy,
 };
 use crate::network::cache::Cache;
 use crate::network::cookies::Cookie;
-use crate::utils::url::{clean_url, domain_is_within_domain, get_referer_url, parse_data_url, Url};
+use crate::network::fetch_policy::PublicAddressResolver;
+use crate::network::limiter::ConnectionLimiter;
+use crate::utils::url::{
+    clean_url, domain_is_within_domain, get_referer_url, host_matches_pattern, parse_data_url,
+    url_matches_pattern, Url,
+};
+
+/// 主文档第一次重试前的等待时间
+const DOCUMENT_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// 跟随重定向的最大次数，与 reqwest 的默认值相同
+const MAX_REDIRECTS: usize = 10;
+
+/// 已嵌入到文档中的资源记录
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EmbeddedResource {
+    /// 资源的最终URL（跟随重定向之后）
+    pub url: String,
+    /// 资源的媒体类型
+    pub media_type: String,
+    /// 资源大小（字节）
+    pub size: usize,
+}
 
 pub struct Session {
-    cache: Option<Cache>,
+    /// 资源缓存，由 [`Session::fork`] 创建的会话共享
+    cache: Option<Arc<Mutex<Cache>>>,
     client: Client,
-    cookies: Option<Vec<Cookie>>,
+    /// 不验证证书的客户端，只用于建立它时 `insecure_hosts` 中的主机
+    insecure_client: Option<InsecureClient>,
+    /// 启动时传入的 Cookie，以及之后响应中 Set-Cookie 设置的 Cookie
+    cookies: Vec<Cookie>,
     pub options: MonolithOptions,
     urls: Vec<String>,
+    resources: Vec<EmbeddedResource>,
+    limiter: Arc<ConnectionLimiter>,
+    /// 已嵌入的资源数量，与 `max_embedded_assets` 比较
+    embedded_assets: usize,
+    /// 达到 `max_embedded_assets` 之后未获取的资源数量
+    skipped_assets: usize,
     /// 标记是否在iframe/frame递归处理中，用于防止重复翻译
     pub in_iframe_processing: bool,
+    /// 当前正在处理的框架层数，主文档为 0，与 `max_iframe_depth` 比较
+    pub(crate) frame_depth: usize,
+    /// 主文档中已嵌入的框架的字节数，与 `max_inlined_frames_size` 比较
+    pub(crate) inlined_frames_size: usize,
+}
+
+/// 不验证证书的客户端和允许使用它的主机
+///
+/// 主机列表与客户端一起保存：由 [`Session::with_options`] 创建的会话沿用基础会话的客户端，
+/// 新选项中的 `insecure_hosts` 不会放宽证书验证。
+#[derive(Clone)]
+struct InsecureClient {
+    client: Client,
+    hosts: Vec<String>,
 }
 
 impl Session {
@@ -35,33 +87,188 @@ impl Session {
                 HeaderValue::from_str(user_agent).expect("Invalid User-Agent header specified"),
             );
         }
-        let client = Client::builder()
-            .timeout(Duration::from_secs(if options.timeout > 0 {
-                options.timeout
-            } else {
-                // We have to specify something that eventually makes the program fail
-                // (prevent it from hanging forever)
-                600 // 10 minutes in seconds
-            }))
-            .danger_accept_invalid_certs(options.insecure)
-            .default_headers(header_map)
-            .build()
-            .expect("Failed to initialize HTTP client");
+        // 设置了抓取限制时，重定向到的地址同样需要检查
+        let mut builder = client_builder(&options, header_map.clone())
+            .danger_accept_invalid_certs(options.insecure);
+        if let Some(policy) = options.fetch_policy.clone() {
+            builder = builder.redirect(reqwest::redirect::Policy::custom(move |attempt| {
+                if attempt.previous().len() > MAX_REDIRECTS {
+                    attempt.error("too many redirects")
+                } else if let Err(reason) = policy.check_url(attempt.url()) {
+                    let message = format!("redirect to {}: {}", attempt.url(), reason);
+                    attempt.error(message)
+                } else {
+                    attempt.follow()
+                }
+            }));
+        }
+        let client = builder.build().expect("Failed to initialize HTTP client");
+        // 放宽验证的客户端只跟随到同样在列表中的主机的重定向，其他主机不会在未验证证书的情况下被访问
+        let insecure_client = if options.insecure || options.insecure_hosts.is_empty() {
+            None
+        } else {
+            let insecure_hosts = options.insecure_hosts.clone();
+            let fetch_policy = options.fetch_policy.clone();
+            let redirect_policy = reqwest::redirect::Policy::custom(move |attempt| {
+                let allowed = attempt.url().host_str().is_some_and(|host| {
+                    insecure_hosts
+                        .iter()
+                        .any(|pattern| host_matches_pattern(host, pattern))
+                });
+                let denied = fetch_policy
+                    .as_ref()
+                    .and_then(|policy| policy.check_url(attempt.url()).err());
+                if attempt.previous().len() > MAX_REDIRECTS {
+                    attempt.error("too many redirects")
+                } else if let Some(reason) = denied {
+                    let message = format!("redirect to {}: {}", attempt.url(), reason);
+                    attempt.error(message)
+                } else if allowed {
+                    attempt.follow()
+                } else {
+                    let message = format!(
+                        "redirect to {} which is not in insecure_hosts",
+                        attempt.url()
+                    );
+                    attempt.error(message)
+                }
+            });
+            Some(InsecureClient {
+                client: client_builder(&options, header_map)
+                    .danger_accept_invalid_certs(true)
+                    .redirect(redirect_policy)
+                    .build()
+                    .expect("Failed to initialize HTTP client"),
+                hosts: options.insecure_hosts.clone(),
+            })
+        };
+        let limiter = Arc::new(ConnectionLimiter::new(
+            options.max_concurrent_fetches,
+            options.max_connections_per_host,
+        ));
 
         Session {
-            cache,
-            cookies,
+            cache: cache.map(|cache| Arc::new(Mutex::new(cache))),
+            cookies: cookies.unwrap_or_default(),
             client,
+            insecure_client,
             options,
             urls: Vec::new(),
+            resources: Vec::new(),
+            limiter,
+            embedded_assets: 0,
+            skipped_assets: 0,
             in_iframe_processing: false,
+            frame_depth: 0,
+            inlined_frames_size: 0,
         }
     }
 
+    /// 获取页面引用的资源
+    ///
+    /// 已嵌入的资源达到 `max_embedded_assets` 后不再获取（data URL 除外），直接返回错误，
+    /// 调用方按获取失败处理，远程资源保留原地址。
     pub fn retrieve_asset(
         &mut self,
         parent_url: &Url,
         url: &Url,
+    ) -> Result<(Vec<u8>, Url, String, String), reqwest::Error> {
+        if url.scheme() != "data" {
+            if let Some(max_embedded_assets) = self.options.max_embedded_assets {
+                if self.embedded_assets >= max_embedded_assets {
+                    self.skipped_assets += 1;
+                    if !self.options.silent {
+                        print_info_message(&format!("{} (embedded asset limit reached)", url));
+                    }
+                    return Err(self.client.get("").send().unwrap_err());
+                }
+            }
+        }
+
+        let timeout = timeout_duration(self.options.asset_timeout.unwrap_or(self.options.timeout));
+        let result = self.retrieve(parent_url, url, timeout);
+        if result.is_ok() && url.scheme() != "data" {
+            self.embedded_assets += 1;
+        }
+        result
+    }
+
+    /// 获取要归档的主文档，使用 `document_timeout` 而不是资源的超时
+    ///
+    /// 连接错误和超时最多重试 `document_fetch_retries` 次，每次等待的时间翻倍；
+    /// 服务器返回的错误状态码不会因为重试而改变，直接返回。
+    pub fn retrieve_document(
+        &mut self,
+        url: &Url,
+    ) -> Result<(Vec<u8>, Url, String, String), reqwest::Error> {
+        let timeout = timeout_duration(
+            self.options
+                .document_timeout
+                .unwrap_or(self.options.timeout),
+        );
+        let mut delay = DOCUMENT_RETRY_DELAY;
+        let mut attempt = 0;
+        loop {
+            match self.retrieve(url, url, timeout) {
+                Err(error)
+                    if attempt < self.options.document_fetch_retries && is_transient(&error) =>
+                {
+                    attempt += 1;
+                    if !self.options.silent {
+                        print_info_message(&format!(
+                            "{} (retrying in {}ms, attempt {} of {})",
+                            url,
+                            delay.as_millis(),
+                            attempt,
+                            self.options.document_fetch_retries
+                        ));
+                    }
+                    thread::sleep(delay);
+                    delay *= 2;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn retrieve(
+        &mut self,
+        parent_url: &Url,
+        url: &Url,
+        timeout: Duration,
+    ) -> Result<(Vec<u8>, Url, String, String), reqwest::Error> {
+        let mut result = self.fetch_asset(parent_url, url, timeout);
+
+        // 按规则纠正服务器发送的媒体类型（data URL 自带的类型不改）
+        if let Ok((_, final_url, media_type, _)) = &mut result {
+            if url.scheme() != "data" {
+                if let Some(forced) = self.media_type_override(final_url) {
+                    *media_type = forced.to_string();
+                }
+            }
+        }
+
+        // 记录实际获取到的资源（data URL 本身已在文档中，不计入）
+        if let Ok((data, final_url, media_type, _)) = &result {
+            if url.scheme() != "data"
+                && !self.resources.iter().any(|r| r.url == final_url.as_str())
+            {
+                self.resources.push(EmbeddedResource {
+                    url: final_url.to_string(),
+                    media_type: media_type.clone(),
+                    size: data.len(),
+                });
+            }
+        }
+
+        result
+    }
+
+    fn fetch_asset(
+        &mut self,
+        parent_url: &Url,
+        url: &Url,
+        timeout: Duration,
     ) -> Result<(Vec<u8>, Url, String, String), reqwest::Error> {
         let cache_key: String = clean_url(url.clone()).as_str().to_string();
 
@@ -69,6 +276,15 @@ impl Session {
             self.urls.push(url.as_str().to_string());
         }
 
+        if url.scheme() != "data" {
+            if let Some(reason) = self.fetch_denied(url) {
+                if !self.options.silent {
+                    print_error_message(&format!("{} ({})", &cache_key, reason));
+                }
+                return Err(self.client.get("").send().unwrap_err());
+            }
+        }
+
         if url.scheme() == "data" {
             let (media_type, charset, data) = parse_data_url(url);
             Ok((data, url.clone(), media_type, charset))
@@ -115,25 +331,21 @@ impl Session {
                 // Provoke error
                 Err(self.client.get("").send().unwrap_err())
             }
-        } else if self.cache.is_some() && self.cache.as_ref().unwrap().contains_key(&cache_key) {
+        } else if let Some((data, media_type, charset)) = self.cached_asset(&cache_key) {
             // URL is in cache, we get and return it
             if !self.options.silent {
                 print_info_message(&format!("{} (from cache)", &cache_key));
             }
 
-            Ok((
-                self.cache
-                    .as_ref()
-                    .unwrap()
-                    .get(&cache_key)
-                    .unwrap()
-                    .0
-                    .to_vec(),
-                url.clone(),
-                self.cache.as_ref().unwrap().get(&cache_key).unwrap().1,
-                self.cache.as_ref().unwrap().get(&cache_key).unwrap().2,
-            ))
+            Ok((data, url.clone(), media_type, charset))
         } else {
+            if self.is_blocked_host(url) {
+                if !self.options.silent {
+                    print_info_message(&format!("{} (blocked)", &cache_key));
+                }
+                return Err(self.client.get("").send().unwrap_err());
+            }
+
             if let Some(domains) = &self.options.domains {
                 let domain_matches = domains
                     .iter()
@@ -147,25 +359,37 @@ impl Session {
 
             // URL not in cache, we retrieve the file
             let mut headers = HeaderMap::new();
-            if self.cookies.is_some() && !self.cookies.as_ref().unwrap().is_empty() {
-                for cookie in self.cookies.as_ref().unwrap() {
-                    if !cookie.is_expired() && cookie.matches_url(url.as_str()) {
-                        let cookie_header_value: String = cookie.name.clone() + "=" + &cookie.value;
-                        headers
-                            .insert(COOKIE, HeaderValue::from_str(&cookie_header_value).unwrap());
-                    }
+            let cookie_header_value: String = self
+                .cookies
+                .iter()
+                .filter(|cookie| !cookie.is_expired() && cookie.matches_url(url.as_str()))
+                .map(|cookie| cookie.name.clone() + "=" + &cookie.value)
+                .collect::<Vec<String>>()
+                .join("; ");
+            if let Ok(cookie_header) = HeaderValue::from_str(&cookie_header_value) {
+                if !cookie_header_value.is_empty() {
+                    headers.insert(COOKIE, cookie_header);
                 }
             }
             // Add referer header for page resource requests
-            if ["https", "http"].contains(&parent_url.scheme()) && parent_url != url {
-                headers.insert(
-                    REFERER,
-                    HeaderValue::from_str(get_referer_url(parent_url.clone()).as_str()).unwrap(),
-                );
+            if let Some(referer) = self.referer_url(parent_url, url) {
+                headers.insert(REFERER, HeaderValue::from_str(referer.as_str()).unwrap());
             }
-            match self.client.get(url.as_str()).headers(headers).send() {
+            // 持有连接许可直到响应体读取完毕
+            let _permit = self.limiter.acquire(url.host_str().unwrap_or_default());
+            let started = Instant::now();
+            match self
+                .client_for(url)
+                .get(url.as_str())
+                .headers(headers)
+                .timeout(timeout)
+                .send()
+            {
                 Ok(response) => {
+                    self.store_cookies(response.url(), response.headers());
+
                     if !self.options.ignore_errors && response.status() != reqwest::StatusCode::OK {
+                        record_fetch(started, false);
                         if !self.options.silent {
                             print_error_message(&format!("{} ({})", &cache_key, response.status()));
                         }
@@ -195,7 +419,9 @@ impl Session {
 
                     // Convert response into a byte array
                     let mut data: Vec<u8> = vec![];
-                    match response.bytes() {
+                    let body = response.bytes();
+                    record_fetch(started, body.is_ok());
+                    match body {
                         Ok(b) => {
                             data = b.to_vec();
                             
@@ -216,10 +442,10 @@ impl Session {
                     }
 
                     // Add retrieved resource to cache
-                    if self.cache.is_some() {
+                    if let Some(cache) = &self.cache {
                         let new_cache_key: String = clean_url(response_url.clone()).to_string();
 
-                        self.cache.as_mut().unwrap().set(
+                        cache.lock().unwrap().set(
                             &new_cache_key,
                             &data,
                             media_type.clone(),
@@ -231,25 +457,121 @@ impl Session {
                     Ok((data, response_url, media_type, charset))
                 }
                 Err(error) => {
+                    record_fetch(started, false);
                     if !self.options.silent {
                         print_error_message(&format!("{cache_key} ({error})"));
                     }
 
-                    Err(self.client.get("").send().unwrap_err())
+                    // 保留原始错误，调用方据此区分连接问题和其他失败
+                    Err(error)
                 }
             }
         }
     }
 
+    /// 从缓存中读取资源
+    fn cached_asset(&self, cache_key: &str) -> Option<(Vec<u8>, String, String)> {
+        let cache = self.cache.as_ref()?.lock().unwrap();
+        if cache.contains_key(cache_key) {
+            cache.get(cache_key).ok()
+        } else {
+            None
+        }
+    }
+
+    /// 创建一个使用相同选项的新会话，用于同时处理另一个目标
+    ///
+    /// 新会话共享 HTTP 客户端（及其连接池）、资源缓存和连接数限制器，
+    /// 复制当前的 Cookie；已检索的 URL 和资源等按文档统计的状态从零开始。
+    pub fn fork(&self) -> Session {
+        let mut session = self.with_options(self.options.clone());
+        session.limiter = Arc::clone(&self.limiter);
+        session
+    }
+
+    /// 创建一个使用 `options` 的新会话，共享 HTTP 客户端（及其连接池）和资源缓存
+    ///
+    /// 用于长期运行的服务：每个请求从同一个基础会话创建自己的会话，请求之间复用到同一来源的
+    /// 连接和已获取的资源。客户端在创建基础会话时已经建立，`options` 中的 `user_agent`、
+    /// `insecure`、`insecure_hosts`、`fetch_policy` 和连接池设置不会生效，其余选项（包括超时）按 `options` 处理。
+    /// 连接数限制器按 `options` 新建，Cookie 从基础会话复制，之后各自独立。
+    pub fn with_options(&self, options: MonolithOptions) -> Session {
+        let limiter = Arc::new(ConnectionLimiter::new(
+            options.max_concurrent_fetches,
+            options.max_connections_per_host,
+        ));
+
+        Session {
+            cache: self.cache.clone(),
+            client: self.client.clone(),
+            insecure_client: self.insecure_client.clone(),
+            cookies: self.cookies.clone(),
+            options,
+            urls: Vec::new(),
+            resources: Vec::new(),
+            limiter,
+            embedded_assets: 0,
+            skipped_assets: 0,
+            in_iframe_processing: false,
+            frame_depth: 0,
+            inlined_frames_size: 0,
+        }
+    }
+
+    /// 请求 `url` 使用的客户端：建立会话时 `insecure_hosts` 中的主机使用不验证证书的客户端
+    fn client_for(&self, url: &Url) -> &Client {
+        match (&self.insecure_client, url.host_str()) {
+            (Some(insecure_client), Some(host))
+                if insecure_client
+                    .hosts
+                    .iter()
+                    .any(|pattern| host_matches_pattern(host, pattern)) =>
+            {
+                &insecure_client.client
+            }
+            _ => &self.client,
+        }
+    }
+
     /// 清空已收集的URL列表，防止重复处理
     pub fn clear_urls(&mut self) {
         self.urls.clear();
     }
 
+    /// 获取连接数限制器，可用于在多个会话或抓取线程之间共享同一组限制
+    pub fn connection_limiter(&self) -> Arc<ConnectionLimiter> {
+        Arc::clone(&self.limiter)
+    }
+
+    /// 替换连接数限制器
+    pub fn set_connection_limiter(&mut self, limiter: Arc<ConnectionLimiter>) {
+        self.limiter = limiter;
+    }
+
+    /// 获取本次处理中已检索的资源列表
+    pub fn resources(&self) -> &[EmbeddedResource] {
+        &self.resources
+    }
+
+    /// 清空已检索的资源记录
+    pub fn clear_resources(&mut self) {
+        self.resources.clear();
+    }
+
+    /// 因达到 `max_embedded_assets` 而没有嵌入的资源数量
+    pub fn skipped_assets_count(&self) -> usize {
+        self.skipped_assets
+    }
+
     /// 重置会话状态以处理新文档
     pub fn reset_for_new_document(&mut self) {
         self.urls.clear();
+        self.resources.clear();
+        self.embedded_assets = 0;
+        self.skipped_assets = 0;
         self.in_iframe_processing = false;
+        self.frame_depth = 0;
+        self.inlined_frames_size = 0;
     }
 
     /// 获取当前收集的URL数量（用于调试）
@@ -257,6 +579,97 @@ impl Session {
         self.urls.len()
     }
 
+    /// 当前会话持有的 Cookie
+    pub fn cookies(&self) -> &[Cookie] {
+        &self.cookies
+    }
+
+    /// 记录响应中的 Set-Cookie，同名、同域且同路径的 Cookie 会被替换，过期的会被删除
+    fn store_cookies(&mut self, response_url: &Url, headers: &HeaderMap) {
+        for header in headers.get_all(SET_COOKIE) {
+            let new_cookie = match header
+                .to_str()
+                .ok()
+                .and_then(|header| Cookie::from_set_cookie(header, response_url))
+            {
+                Some(new_cookie) => new_cookie,
+                None => continue,
+            };
+            self.cookies
+                .retain(|cookie| !cookie.is_same_cookie(&new_cookie));
+            if !new_cookie.is_expired() {
+                self.cookies.push(new_cookie);
+            }
+        }
+    }
+
+    /// 按声明的字符编码解码获取到的文本资源（CSS、JavaScript）
+    ///
+    /// 编码为空或无法识别时按 UTF-8 解码。无效的字节序列替换为 U+FFFD 而不是报错，
+    /// 一个损坏的文件不会影响整个文档；发生替换时输出提示。
+    pub fn decode_text_asset(&self, data: &[u8], charset: &str, url: &Url) -> String {
+        let encoding = Encoding::for_label(charset.trim().as_bytes()).unwrap_or(UTF_8);
+        let (text, _, had_errors) = encoding.decode(data);
+
+        if had_errors && !self.options.silent {
+            print_error_message(&format!(
+                "{} (invalid {} byte sequences replaced)",
+                url,
+                encoding.name()
+            ));
+        }
+
+        text.into_owned()
+    }
+
+    /// 按 `referer_policy` 确定获取资源时发送的 `Referer`
+    fn referer_url(&self, parent_url: &Url, url: &Url) -> Option<Url> {
+        if parent_url == url {
+            return None;
+        }
+        let is_http = ["https", "http"].contains(&parent_url.scheme());
+
+        match &self.options.referer_policy {
+            RefererPolicy::FullUrl if is_http => Some(get_referer_url(parent_url.clone())),
+            RefererPolicy::Origin if is_http => {
+                Url::parse(&parent_url.origin().ascii_serialization()).ok()
+            }
+            RefererPolicy::Fixed(referer) => Some(get_referer_url(referer.clone())),
+            _ => None,
+        }
+    }
+
+    /// 地址不符合 `fetch_policy` 时返回原因
+    fn fetch_denied(&self, url: &Url) -> Option<String> {
+        self.options
+            .fetch_policy
+            .as_ref()
+            .and_then(|policy| policy.check_url(url).err())
+    }
+
+    /// 检查资源主机是否在 `block_hosts` 中
+    ///
+    /// 被屏蔽的资源不会被请求，引用它的元素属性也不保留远程地址。
+    /// `media_type_overrides` 中第一个匹配该地址的媒体类型
+    pub fn media_type_override(&self, url: &Url) -> Option<&str> {
+        self.options
+            .media_type_overrides
+            .iter()
+            .find(|(pattern, _)| url_matches_pattern(url, pattern))
+            .map(|(_, media_type)| media_type.as_str())
+    }
+
+    pub fn is_blocked_host(&self, url: &Url) -> bool {
+        match url.host_str() {
+            Some(host) => self
+                .options
+                .block_hosts
+                .iter()
+                .any(|pattern| host_matches_pattern(host, pattern)),
+            None => false,
+        }
+    }
+
     /// 智能资源过滤检查
     pub fn should_include_resource(&self, url: &Url, size: u64, content_type: &str) -> bool {
         if !self.options.smart_filtering {
@@ -395,3 +808,57 @@ impl Session {
         false
     }
 }
+
+/// 两个客户端共用的设置（证书验证除外）
+fn client_builder(options: &MonolithOptions, header_map: HeaderMap) -> ClientBuilder {
+    // 显式开启解压：发送 Accept-Encoding，并在返回数据之前解码 gzip、deflate 和 br 响应，
+    // 否则压缩后的字节会被原样嵌入文档
+    let mut builder = Client::builder()
+        .gzip(true)
+        .deflate(true)
+        .brotli(true)
+        .timeout(timeout_duration(options.timeout))
+        .default_headers(header_map);
+    // 同一来源的大量资源复用连接，避免每次都重新握手
+    if let Some(max_idle) = options.pool_max_idle_per_host {
+        builder = builder.pool_max_idle_per_host(max_idle);
+    }
+    if let Some(idle_timeout) = options.pool_idle_timeout {
+        builder = builder.pool_idle_timeout(Duration::from_secs(idle_timeout));
+    }
+    if let Some(keepalive) = options.tcp_keepalive {
+        builder = builder.tcp_keepalive(Duration::from_secs(keepalive));
+    }
+    if options
+        .fetch_policy
+        .as_ref()
+        .is_some_and(|policy| policy.deny_internal_addresses)
+    {
+        builder = builder.dns_resolver(Arc::new(PublicAddressResolver));
+    }
+    builder
+}
+
+/// 把以秒为单位的超时设置转换为请求超时
+fn timeout_duration(seconds: u64) -> Duration {
+    Duration::from_secs(if seconds > 0 {
+        seconds
+    } else {
+        // We have to specify something that eventually makes the program fail
+        // (prevent it from hanging forever)
+        600 // 10 minutes in seconds
+    })
+}
+
+/// 请求是否因为连接失败、超时等传输层问题而失败，这类错误值得重试
+fn is_transient(error: &reqwest::Error) -> bool {
+    error.is_connect() || error.is_timeout() || error.is_request()
+}
+
+/// 记录一次资源下载的耗时（仅 Web 服务器导出监控指标时需要）
+fn record_fetch(started: Instant, success: bool) {
+    #[cfg(feature = "web")]
+    crate::web::metrics::global().observe_asset_fetch(started.elapsed(), success);
+    #[cfg(not(feature = "web"))]
+    let _ = (started, success);
+}
@@ -29,7 +29,7 @@
 use cssparser::{serialize_identifier, serialize_string, ParseError, Parser, ParserInput, Token};
 
 use crate::network::session::Session;
-use crate::utils::url::{create_data_url, resolve_url, Url, EMPTY_IMAGE_DATA_URL};
+use crate::utils::url::{create_data_url_with_encoding, resolve_url, Url, EMPTY_IMAGE_DATA_URL};
 
 /// 包含图片URL的CSS属性列表
 ///
@@ -145,6 +145,35 @@ pub fn format_quoted_string(string: &str) -> String {
     res
 }
 
+/// 样式表经 [`Session::decode_text_asset`] 转为 UTF-8 后，其 data URL 应声明的编码
+///
+/// 原本没有声明编码时按 UTF-8 解码，data URL 同样不声明编码。
+pub(crate) fn transcoded_charset(charset: &str) -> &'static str {
+    if charset.trim().is_empty() {
+        ""
+    } else {
+        "utf-8"
+    }
+}
+
+/// 检查CSS函数是否为 `image-set()`（包括 `-webkit-` 前缀）
+///
+/// `image-set()` 中带引号的字符串与 `url()` 一样是图片地址，
+/// 分辨率描述符（如 `2x`）和 `type()` 按普通token原样保留。
+///
+/// # 示例
+///
+/// ```rust
+/// use monolith::css::is_image_set_function;
+///
+/// assert_eq!(is_image_set_function("-webkit-image-set"), true);
+/// assert_eq!(is_image_set_function("url"), false);
+/// ```
+pub fn is_image_set_function(func_name: &str) -> bool {
+    func_name.eq_ignore_ascii_case("image-set")
+        || func_name.eq_ignore_ascii_case("-webkit-image-set")
+}
+
 /// 检查CSS属性是否可能包含图片URL
 ///
 /// 根据预定义的CSS属性列表，判断给定的属性名称是否
@@ -418,7 +447,7 @@ pub fn process_css<'a>(
 
 /// 处理带引号的字符串token
 ///
-/// 根据当前的CSS上下文（@import规则或url()函数），
+/// 根据当前的CSS上下文（@import规则、url()或image-set()函数），
 /// 对带引号的字符串进行相应的处理。
 ///
 /// # 参数
@@ -445,6 +474,14 @@ fn process_quoted_string_token(
     } else if func_name == "url" {
         // 处理url()函数中的字符串
         process_url_quoted_string(value, session, document_url, context)
+    } else if is_image_set_function(func_name) && !value.is_empty() {
+        // 与 srcset 相同，嵌入每个候选图片；无法获取的本地图片用空图片代替，保持语法完整
+        let embedded = process_url_quoted_string(value, session, document_url, context)?;
+        if embedded.is_empty() {
+            Ok(format_quoted_string(EMPTY_IMAGE_DATA_URL))
+        } else {
+            Ok(embedded)
+        }
     } else {
         // 普通字符串，仅格式化
         Ok(format_quoted_string(value))
@@ -487,24 +524,31 @@ fn process_import_quoted_string(
     match session.retrieve_asset(document_url, &import_full_url) {
         Ok((import_contents, import_final_url, import_media_type, import_charset)) => {
             // 成功获取CSS文件，递归处理并创建data URI
-            let mut import_data_url = create_data_url(
+            let mut import_data_url = create_data_url_with_encoding(
                 &import_media_type,
-                &import_charset,
+                transcoded_charset(&import_charset),
                 embed_css(
                     session,
                     &import_final_url,
-                    &String::from_utf8_lossy(&import_contents),
+                    &session.decode_text_asset(
+                        &import_contents,
+                        &import_charset,
+                        &import_final_url,
+                    ),
                 )
                 .as_bytes(),
                 &import_final_url,
+                session.options.data_url_encoding,
             );
             // 保持URL片段（#fragment）
             import_data_url.set_fragment(import_full_url.fragment());
             Ok(format_quoted_string(import_data_url.as_ref()))
         }
         Err(_) => {
-            // 如果无法获取资源，保留远程引用
-            if import_full_url.scheme() == "http" || import_full_url.scheme() == "https" {
+            // 如果无法获取资源，保留远程引用（被屏蔽的主机除外）
+            if (import_full_url.scheme() == "http" || import_full_url.scheme() == "https")
+                && !session.is_blocked_host(&import_full_url)
+            {
                 Ok(format_quoted_string(import_full_url.as_ref()))
             } else {
                 // 本地文件无法访问时返回空字符串
@@ -552,13 +596,21 @@ fn process_url_quoted_string(
     match session.retrieve_asset(document_url, &resolved_url) {
         Ok((data, final_url, media_type, charset)) => {
             // 成功获取资源，创建data URI
-            let mut data_url = create_data_url(&media_type, &charset, &data, &final_url);
+            let mut data_url = create_data_url_with_encoding(
+                &media_type,
+                &charset,
+                &data,
+                &final_url,
+                session.options.data_url_encoding,
+            );
             data_url.set_fragment(resolved_url.fragment());
             Ok(format_quoted_string(data_url.as_ref()))
         }
         Err(_) => {
-            // 如果无法获取资源，保留远程引用
-            if resolved_url.scheme() == "http" || resolved_url.scheme() == "https" {
+            // 如果无法获取资源，保留远程引用（被屏蔽的主机除外）
+            if (resolved_url.scheme() == "http" || resolved_url.scheme() == "https")
+                && !session.is_blocked_host(&resolved_url)
+            {
                 Ok(format_quoted_string(resolved_url.as_ref()))
             } else {
                 // 本地文件无法访问时返回空字符串
@@ -649,18 +701,26 @@ fn process_import_unquoted_url(
     match session.retrieve_asset(document_url, &full_url) {
         Ok((css, final_url, media_type, charset)) => {
             // 成功获取CSS文件，递归处理并创建data URI
-            let mut data_url = create_data_url(
+            let mut data_url = create_data_url_with_encoding(
                 &media_type,
-                &charset,
-                embed_css(session, &final_url, &String::from_utf8_lossy(&css)).as_bytes(),
+                transcoded_charset(&charset),
+                embed_css(
+                    session,
+                    &final_url,
+                    &session.decode_text_asset(&css, &charset, &final_url),
+                )
+                .as_bytes(),
                 &final_url,
+                session.options.data_url_encoding,
             );
             data_url.set_fragment(full_url.fragment());
             result.push_str(&format_quoted_string(data_url.as_ref()));
         }
         Err(_) => {
-            // 如果无法获取资源，保留远程引用
-            if full_url.scheme() == "http" || full_url.scheme() == "https" {
+            // 如果无法获取资源，保留远程引用（被屏蔽的主机除外）
+            if (full_url.scheme() == "http" || full_url.scheme() == "https")
+                && !session.is_blocked_host(&full_url)
+            {
                 result.push_str(&format_quoted_string(full_url.as_ref()));
             }
             // 本地文件无法访问时不添加任何内容
@@ -695,13 +755,21 @@ fn process_regular_unquoted_url(
     match session.retrieve_asset(document_url, &full_url) {
         Ok((data, final_url, media_type, charset)) => {
             // 成功获取资源，创建data URI
-            let mut data_url = create_data_url(&media_type, &charset, &data, &final_url);
+            let mut data_url = create_data_url_with_encoding(
+                &media_type,
+                &charset,
+                &data,
+                &final_url,
+                session.options.data_url_encoding,
+            );
             data_url.set_fragment(full_url.fragment());
             result.push_str(&format_quoted_string(data_url.as_ref()));
         }
         Err(_) => {
-            // 如果无法获取资源，保留远程引用
-            if full_url.scheme() == "http" || full_url.scheme() == "https" {
+            // 如果无法获取资源，保留远程引用（被屏蔽的主机除外）
+            if (full_url.scheme() == "http" || full_url.scheme() == "https")
+                && !session.is_blocked_host(&full_url)
+            {
                 result.push_str(&format_quoted_string(full_url.as_ref()));
             }
             // 本地文件无法访问时不添加任何内容
@@ -17,18 +17,17 @@
 //! 这个模块是 Monolith 工具将网页转换为单文件 HTML 的核心组件之一。
 
 use base64::{prelude::BASE64_STANDARD, Engine};
-use encoding_rs::Encoding;
 use html5ever::interface::{Attribute, QualName};
 use html5ever::serialize::{serialize, SerializeOpts};
-use html5ever::tendril::format_tendril;
-use html5ever::tree_builder::create_element;
+use html5ever::tendril::{format_tendril, StrTendril};
+use html5ever::tree_builder::{create_element, NodeOrText, TreeSink};
 use html5ever::{namespace_url, ns, LocalName};
 use markup5ever_rcdom::{Handle, NodeData, RcDom, SerializableHandle};
 use sha2::{Digest, Sha256, Sha384, Sha512};
 
 use crate::network::session::Session;
-use crate::parsers::css::embed_css;
-use crate::utils::url::{create_data_url, resolve_url, Url, EMPTY_IMAGE_DATA_URL};
+use crate::parsers::css::{embed_css, transcoded_charset};
+use crate::utils::url::{create_data_url_with_encoding, resolve_url, Url, EMPTY_IMAGE_DATA_URL};
 
 use super::dom::get_node_attr;
 use super::dom::{find_nodes, get_node_name, html_to_dom, set_node_attr};
@@ -93,6 +92,57 @@ pub fn add_favicon(document: &Handle, favicon_data_url: String) -> RcDom {
     dom
 }
 
+/// 在 `<head>` 末尾追加一段自定义样式
+///
+/// 用于在归档结果中统一添加用户样式（例如限制正文宽度、深色模式）。
+/// 文档缺少 `<head>` 时会在 `<html>` 开头创建一个。
+///
+/// # 示例
+///
+/// ```rust
+/// use monolith::html::{add_stylesheet, html_to_dom, serialize_document};
+/// use monolith::core::MonolithOptions;
+///
+/// let dom = html_to_dom(b"<p>text</p>", "".to_string());
+/// let dom = add_stylesheet(dom, "body { max-width: 40em; }");
+/// let html = serialize_document(dom, "".to_string(), &MonolithOptions::default());
+/// assert!(String::from_utf8_lossy(&html).contains("<style>body { max-width: 40em; }</style>"));
+/// ```
+pub fn add_stylesheet(dom: RcDom, css: &str) -> RcDom {
+    let head = match find_nodes(&dom.document, vec!["html", "head"]).first() {
+        Some(head) => head.clone(),
+        None => {
+            let html = match find_nodes(&dom.document, vec!["html"]).first() {
+                Some(html) => html.clone(),
+                None => return dom,
+            };
+            let head = create_element(
+                &dom,
+                QualName::new(None, ns!(), LocalName::from("head")),
+                vec![],
+            );
+            let first_child = html.children.borrow().first().cloned();
+            match first_child {
+                Some(first_child) => {
+                    dom.append_before_sibling(&first_child, NodeOrText::AppendNode(head.clone()))
+                }
+                None => dom.append(&html, NodeOrText::AppendNode(head.clone())),
+            }
+            head
+        }
+    };
+
+    let style_node = create_element(
+        &dom,
+        QualName::new(None, ns!(), LocalName::from("style")),
+        vec![],
+    );
+    dom.append(&style_node, NodeOrText::AppendText(StrTendril::from(css)));
+    dom.append(&head, NodeOrText::AppendNode(style_node));
+
+    dom
+}
+
 /// 验证资源数据的完整性
 ///
 /// 此函数根据提供的完整性哈希值验证资源数据是否未被篡改。
@@ -185,14 +235,21 @@ pub fn embed_srcset(session: &mut Session, document_url: &Url, srcset: &str) ->
             match session.retrieve_asset(document_url, &image_full_url) {
                 Ok((data, final_url, media_type, charset)) => {
                     // 成功获取资源，创建 data URL
-                    let image_data_url: Url =
-                        create_data_url(&media_type, &charset, &data, &final_url);
+                    let image_data_url: Url = create_data_url_with_encoding(
+                        &media_type,
+                        &charset,
+                        &data,
+                        &final_url,
+                        session.options.data_url_encoding,
+                    );
 
                     result.push_str(image_data_url.as_ref());
                 }
                 Err(_) => {
-                    // 获取失败时的处理策略
-                    if image_full_url.scheme() == "http" || image_full_url.scheme() == "https" {
+                    // 获取失败时的处理策略（被屏蔽的主机不保留远程地址）
+                    if (image_full_url.scheme() == "http" || image_full_url.scheme() == "https")
+                        && !session.is_blocked_host(&image_full_url)
+                    {
                         // 保留 HTTP/HTTPS URL，让浏览器尝试加载
                         result.push_str(image_full_url.as_ref());
                     } else {
@@ -297,26 +354,33 @@ pub fn retrieve_and_embed_asset(
 
             // 只有通过完整性验证的资源才会被嵌入
             if ok_to_include {
+                // 内联之后不再发起请求，请求相关的属性只会引起浏览器警告
+                if (node_name == "link" || node_name == "script")
+                    && !session.options.keep_fetch_attributes
+                {
+                    set_node_attr(node, "crossorigin", None);
+                    set_node_attr(node, "referrerpolicy", None);
+                }
+
                 // 处理 CSS 样式表文件
                 if node_name == "link"
                     && parse_link_type(&get_node_attr(node, "rel").unwrap_or(String::from("")))
                         .contains(&LinkType::Stylesheet)
                 {
                     // 根据字符编码解码 CSS 内容
-                    let stylesheet: String;
-                    if let Some(encoding) = Encoding::for_label(charset.as_bytes()) {
-                        let (string, _, _) = encoding.decode(&data);
-                        stylesheet = string.to_string();
-                    } else {
-                        stylesheet = String::from_utf8_lossy(&data).to_string();
-                    }
+                    let stylesheet: String = session.decode_text_asset(&data, &charset, &final_url);
 
                     // CSS 样式表需要特殊处理，递归嵌入其中的资源
                     let css: String = embed_css(session, &final_url, &stylesheet);
 
-                    // 创建并设置 CSS 的 data URL
-                    let css_data_url =
-                        create_data_url(&media_type, &charset, css.as_bytes(), &final_url);
+                    // 创建并设置 CSS 的 data URL，解码后的内容已是 UTF-8
+                    let css_data_url = create_data_url_with_encoding(
+                        &media_type,
+                        transcoded_charset(&charset),
+                        css.as_bytes(),
+                        &final_url,
+                        session.options.data_url_encoding,
+                    );
                     set_node_attr(node, attr_name, Some(css_data_url.to_string()));
                 
                 // 处理 iframe 和 frame 元素
@@ -329,7 +393,9 @@ pub fn retrieve_and_embed_asset(
                     session.in_iframe_processing = true;
                     
                     // 递归处理嵌套文档中的所有资源
+                    session.frame_depth += 1;
                     walk(session, &final_url, &frame_dom.document);
+                    session.frame_depth -= 1;
                     
                     // 恢复原始标记
                     session.in_iframe_processing = original_iframe_flag;
@@ -339,9 +405,29 @@ pub fn retrieve_and_embed_asset(
                     let serializable: SerializableHandle = frame_dom.document.into();
                     serialize(&mut frame_data, &serializable, SerializeOpts::default()).unwrap();
 
+                    // 主文档中的框架合计超出大小限制时保留地址，嵌套的框架已计入外层框架
+                    if session.frame_depth == 0 {
+                        if session
+                            .options
+                            .max_inlined_frames_size
+                            .is_some_and(|max_size| {
+                                session.inlined_frames_size + frame_data.len() > max_size
+                            })
+                        {
+                            set_node_attr(node, attr_name, Some(resolved_url.to_string()));
+                            return;
+                        }
+                        session.inlined_frames_size += frame_data.len();
+                    }
+
                     // 创建并设置 iframe 的 data URL，保留原始的片段标识符
-                    let mut frame_data_url =
-                        create_data_url(&media_type, &charset, &frame_data, &final_url);
+                    let mut frame_data_url = create_data_url_with_encoding(
+                        &media_type,
+                        &charset,
+                        &frame_data,
+                        &final_url,
+                        session.options.data_url_encoding,
+                    );
                     frame_data_url.set_fragment(resolved_url.fragment());
                     set_node_attr(node, attr_name, Some(frame_data_url.to_string()));
                 
@@ -378,7 +464,8 @@ pub fn retrieve_and_embed_asset(
                                     tendril.clear();
                                     // 转义可能存在的 </script> 标签以防止 HTML 解析错误
                                     tendril.push_slice(
-                                        &String::from_utf8_lossy(&data)
+                                        &session
+                                            .decode_text_asset(&data, &charset, &final_url)
                                             .replace("</script>", "<\\/script>"),
                                     );
                                 }
@@ -390,15 +477,25 @@ pub fn retrieve_and_embed_asset(
                             }
                         } else {
                             // 对于非 JavaScript 脚本类型，使用 data URL
-                            let mut data_url =
-                                create_data_url(&script_media_type, &charset, &data, &final_url);
+                            let mut data_url = create_data_url_with_encoding(
+                                &script_media_type,
+                                &charset,
+                                &data,
+                                &final_url,
+                                session.options.data_url_encoding,
+                            );
                             data_url.set_fragment(resolved_url.fragment());
                             set_node_attr(node, attr_name, Some(data_url.to_string()));
                         }
                     } else {
                         // 对于所有其他类型的资源（图像、字体等），创建 data URL
-                        let mut data_url =
-                            create_data_url(&media_type, &charset, &data, &final_url);
+                        let mut data_url = create_data_url_with_encoding(
+                            &media_type,
+                            &charset,
+                            &data,
+                            &final_url,
+                            session.options.data_url_encoding,
+                        );
                         data_url.set_fragment(resolved_url.fragment());
                         set_node_attr(node, attr_name, Some(data_url.to_string()));
                     }
@@ -407,11 +504,13 @@ pub fn retrieve_and_embed_asset(
         }
         // 资源获取失败的错误处理
         Err(_) => {
-            if resolved_url.scheme() == "http" || resolved_url.scheme() == "https" {
+            if (resolved_url.scheme() == "http" || resolved_url.scheme() == "https")
+                && !session.is_blocked_host(&resolved_url)
+            {
                 // 如果无法检索远程资源，保留原始的远程引用让浏览器尝试加载
                 set_node_attr(node, attr_name, Some(resolved_url.to_string()));
             } else {
-                // 如果本地引用无法成功嵌入为 data URL，则移除该引用
+                // 本地引用无法成功嵌入为 data URL，或主机已被屏蔽时，移除该引用
                 set_node_attr(node, attr_name, None);
             }
         }
@@ -21,6 +21,8 @@
 //! - 安全地处理空值和无效值
 //! - 支持data URL嵌入以实现完整的单文件HTML
 
+use std::rc::Rc;
+
 use html5ever::interface::QualName;
 use html5ever::parse_document;
 use html5ever::tendril::TendrilSink;
@@ -30,13 +32,13 @@ use markup5ever_rcdom::{Handle, NodeData, RcDom};
 
 use crate::network::session::Session;
 use crate::utils::url::{
-    create_data_url, is_url_and_has_protocol, resolve_url, Url, EMPTY_IMAGE_DATA_URL,
+    create_data_url_with_encoding, is_url_and_has_protocol, resolve_url, Url, EMPTY_IMAGE_DATA_URL,
 };
 
 use super::assets::{embed_srcset, retrieve_and_embed_asset};
 use super::dom::{
     find_nodes, get_child_node_by_name, get_node_attr, get_node_name, get_parent_node, html_to_dom,
-    set_node_attr,
+    remove_node, set_node_attr,
 };
 use super::element_handlers::ElementHandler;
 
@@ -187,24 +189,19 @@ impl ElementHandler for ImageElementHandler {
     /// * `document_url` - 当前文档的URL，用于解析相对路径
     /// * `node` - 要处理的image DOM节点
     fn handle(&self, session: &mut Session, document_url: &Url, node: &Handle) {
-        // SVG image元素支持的href属性名称（包括已弃用的xlink:href）
-        let attr_names: [&str; 2] = ["href", "xlink:href"];
-
-        for attr_name in attr_names.into_iter() {
-            if let Some(image_attr_href_value) = get_node_attr(node, attr_name) {
-                if session.options.no_images {
-                    // 如果禁用图像，移除href属性
-                    set_node_attr(node, attr_name, None);
-                } else {
-                    // 检索并嵌入图像资源
-                    retrieve_and_embed_asset(
-                        session,
-                        document_url,
-                        node,
-                        attr_name,
-                        &image_attr_href_value,
-                    );
-                }
+        if let Some(image_attr_href_value) = get_svg_href(node) {
+            if session.options.no_images {
+                // 如果禁用图像，移除href和xlink:href属性
+                set_node_attr(node, "href", None);
+            } else {
+                // 检索并嵌入图像资源（同时写入href和xlink:href）
+                retrieve_and_embed_asset(
+                    session,
+                    document_url,
+                    node,
+                    "href",
+                    &image_attr_href_value,
+                );
             }
         }
     }
@@ -257,29 +254,35 @@ impl ElementHandler for UseElementHandler {
     /// * `document_url` - 当前文档的URL，用于解析相对路径
     /// * `node` - 要处理的use DOM节点
     fn handle(&self, session: &mut Session, document_url: &Url, node: &Handle) {
-        // SVG use元素支持的href属性名称（包括已弃用的xlink:href）
-        let attr_names: [&str; 2] = ["href", "xlink:href"];
-
-        for attr_name in attr_names.into_iter() {
-            if let Some(use_attr_href_value) = get_node_attr(node, attr_name) {
-                if session.options.no_images {
-                    // 如果禁用图像，移除href属性
-                    set_node_attr(node, attr_name, None);
-                } else {
-                    // 处理SVG use元素的复杂逻辑
-                    self.handle_svg_use(
-                        session,
-                        document_url,
-                        node,
-                        attr_name,
-                        &use_attr_href_value,
-                    );
-                }
+        if let Some(use_attr_href_value) = get_svg_href(node) {
+            if session.options.no_images {
+                // 如果禁用图像，移除href和xlink:href属性
+                set_node_attr(node, "href", None);
+            } else if !use_attr_href_value.starts_with('#') {
+                // 处理SVG use元素的复杂逻辑（同一文档内的片段引用无需处理）
+                self.handle_svg_use(session, document_url, node, "href", &use_attr_href_value);
             }
         }
     }
 }
 
+/// 获取SVG元素引用的资源地址
+///
+/// 解析器把 `xlink:href` 存为 XLink 命名空间下名为 `href` 的属性，因此按本地名称
+/// `href` 读取时两者都会匹配。同时存在时与浏览器一致，优先使用 SVG 2 的 `href`。
+/// 由于 [`set_node_attr`] 按本地名称修改属性，写回 `href` 会同时更新两者。
+fn get_svg_href(node: &Handle) -> Option<String> {
+    match &node.data {
+        NodeData::Element { attrs, .. } => attrs
+            .borrow()
+            .iter()
+            .filter(|attr| &*attr.name.local == "href")
+            .min_by_key(|attr| attr.name.ns == ns!(xlink))
+            .map(|attr| attr.value.to_string()),
+        _ => None,
+    }
+}
+
 impl UseElementHandler {
     /// 处理SVG use元素的具体逻辑
     /// 
@@ -311,14 +314,23 @@ impl UseElementHandler {
                     self.handle_svg_asset(session, node, attr_name, &image_asset_url, &data);
                 } else {
                     // 处理位图图像，嵌入为data URL
-                    let image_asset_data: Url =
-                        create_data_url(&media_type, &charset, &data, &final_url);
+                    let image_asset_data: Url = create_data_url_with_encoding(
+                        &media_type,
+                        &charset,
+                        &data,
+                        &final_url,
+                        session.options.data_url_encoding,
+                    );
                     set_node_attr(node, attr_name, Some(image_asset_data.to_string()));
                 }
             }
             Err(_) => {
-                // 资源检索失败，保持原始URL
-                set_node_attr(node, attr_name, Some(image_asset_url.to_string()));
+                // 资源检索失败，保持原始URL（被屏蔽的主机直接移除引用）
+                if session.is_blocked_host(&image_asset_url) {
+                    set_node_attr(node, attr_name, None);
+                } else {
+                    set_node_attr(node, attr_name, Some(image_asset_url.to_string()));
+                }
             }
         }
     }
@@ -489,6 +501,15 @@ impl ElementHandler for SourceElementHandler {
         let parent_node = get_parent_node(node);
         let parent_node_name: &str = get_node_name(&parent_node).unwrap_or_default();
 
+        // 目标视口下永远不会被选中的艺术指导图源，以及有更优先格式可选的图源直接删除
+        if parent_node_name == "picture"
+            && (!self.matches_target_viewport(session, node)
+                || !self.is_preferred_format(session, &parent_node, node))
+        {
+            remove_node(node);
+            return;
+        }
+
         // 处理src属性（针对audio和video父元素）
         self.handle_source_src(session, document_url, node, parent_node_name);
         // 处理srcset属性（针对picture父元素）
@@ -497,6 +518,58 @@ impl ElementHandler for SourceElementHandler {
 }
 
 impl SourceElementHandler {
+    /// 检查source元素的media查询在目标视口下是否可能匹配
+    ///
+    /// 未设置目标视口或没有media属性时总是返回true。
+    fn matches_target_viewport(&self, session: &Session, node: &Handle) -> bool {
+        match (
+            session.options.target_viewport,
+            get_node_attr(node, "media"),
+        ) {
+            (Some(viewport), Some(media)) => viewport.matches_media(&media),
+            _ => true,
+        }
+    }
+
+    /// 检查source元素是否是同组图源中最优先的图片格式
+    ///
+    /// 同一个picture中`media`相同的图源为一组。设置了`preferred_image_formats`时，
+    /// 组内带`type`的图源只保留格式排在最前的第一个；组内没有列出的格式，
+    /// 或者图源没有`type`属性时总是保留。
+    fn is_preferred_format(&self, session: &Session, picture: &Handle, node: &Handle) -> bool {
+        let preferred = &session.options.preferred_image_formats;
+        if preferred.is_empty() {
+            return true;
+        }
+        let rank = |source: &Handle| {
+            let source_type = get_node_attr(source, "type")?;
+            let mime_type = source_type.split(';').next().unwrap_or("").trim();
+            preferred
+                .iter()
+                .position(|format| format.eq_ignore_ascii_case(mime_type))
+        };
+        let media = |source: &Handle| get_node_attr(source, "media").unwrap_or_default();
+
+        if get_node_attr(node, "type").is_none() {
+            return true;
+        }
+        let node_media = media(node);
+        let best = picture
+            .children
+            .borrow()
+            .iter()
+            .filter(|child| get_node_name(child) == Some("source"))
+            .filter(|child| media(child).trim() == node_media.trim())
+            .filter_map(|child| rank(child).map(|rank| (rank, child.clone())))
+            // 格式相同时取最先出现的图源
+            .min_by_key(|(rank, _)| *rank);
+
+        match best {
+            Some((_, best_node)) => Rc::ptr_eq(&best_node, node),
+            None => true,
+        }
+    }
+
     /// 处理source元素的src属性
     /// 
     /// 根据父元素类型（audio或video）处理src属性中的媒体资源。
@@ -696,7 +769,18 @@ impl ElementHandler for FrameElementHandler {
                 set_node_attr(node, "src", Some("".to_string()));
             } else {
                 // 忽略空源的框架（它们会导致无限循环）
-                if !frame_attr_src_value.trim().is_empty() {
+                if frame_attr_src_value.trim().is_empty() {
+                    return;
+                }
+                if session
+                    .options
+                    .max_iframe_depth
+                    .is_some_and(|max_iframe_depth| session.frame_depth >= max_iframe_depth)
+                {
+                    // 超过嵌入层数的框架保留地址，解析为绝对地址后脱离原文档仍然有效
+                    let frame_full_url: Url = resolve_url(document_url, &frame_attr_src_value);
+                    set_node_attr(node, "src", Some(frame_full_url.to_string()));
+                } else {
                     retrieve_and_embed_asset(
                         session,
                         document_url,
@@ -884,6 +968,7 @@ impl VideoElementHandler {
 /// - 对于已有协议的绝对URL：保持原样
 /// - 对于相对URL：转换为绝对URL
 /// - 特殊协议链接（如mailto:）：保持原样
+/// - 启用 `strip_hyperlink_auditing` 时：删除 `ping`，并设置 `referrerpolicy` 和 `rel`
 /// 
 /// # 示例
 /// 
@@ -943,7 +1028,32 @@ impl ElementHandler for AnchorElementHandler {
                 }
             }
         }
+
+        if session.options.strip_hyperlink_auditing {
+            strip_hyperlink_auditing(node);
+        }
+    }
+}
+
+/// 去除超链接审计
+///
+/// 删除 `ping` 属性（点击链接时向其中的地址发送 POST 请求），将 `referrerpolicy`
+/// 设为 `no-referrer`，并在 `rel` 中补充 `noopener` 和 `noreferrer`，保留已有的其他值。
+fn strip_hyperlink_auditing(node: &Handle) {
+    set_node_attr(node, "ping", None);
+    set_node_attr(node, "referrerpolicy", Some("no-referrer".to_string()));
+
+    let rel = get_node_attr(node, "rel").unwrap_or_default();
+    let mut rel_values: Vec<&str> = rel.split_whitespace().collect();
+    for value in ["noopener", "noreferrer"] {
+        if !rel_values
+            .iter()
+            .any(|existing| existing.eq_ignore_ascii_case(value))
+        {
+            rel_values.push(value);
+        }
     }
+    set_node_attr(node, "rel", Some(rel_values.join(" ")));
 }
 
 /// NOSCRIPT元素处理器
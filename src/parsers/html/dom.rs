@@ -12,6 +12,7 @@
 use encoding_rs::Encoding;
 use html5ever::parse_document;
 use html5ever::tendril::TendrilSink;
+use html5ever::tree_builder::{NodeOrText, TreeSink};
 use html5ever::{namespace_url, ns};
 use markup5ever_rcdom::{Handle, NodeData, RcDom};
 
@@ -231,8 +232,173 @@ pub fn get_node_name(node: &Handle) -> Option<&'_ str> {
 /// # 注意
 /// 使用此函数前请确保节点确实有父节点，否则会导致程序panic
 pub fn get_parent_node(child: &Handle) -> Handle {
-    let parent = child.parent.take().clone();
-    parent.and_then(|node| node.upgrade()).unwrap()
+    parent_of(child).unwrap()
+}
+
+/// 读取节点的父节点，不改动节点自身的父指针
+///
+/// 文档根节点、`<template>` 内容片段的根节点以及已从树中移除的节点返回 `None`。
+pub fn parent_of(node: &Handle) -> Option<Handle> {
+    let parent = node.parent.take();
+    node.parent.set(parent.clone());
+    parent.and_then(|parent| parent.upgrade())
+}
+
+/// 从节点自身开始逐级向上直到根节点的祖先链
+///
+/// 用于由祖先组成的节点路径，例如流式翻译中的子节点下标路径和归档差异中的元素路径。
+pub fn ancestors(node: &Handle) -> impl Iterator<Item = Handle> {
+    std::iter::successors(Some(node.clone()), parent_of)
+}
+
+/// 获取 `<template>` 元素的内容片段
+///
+/// html5ever 把 `<template>` 的子节点放进独立的文档片段，而不是元素自身的 `children`，
+/// 因此普通的子节点遍历看不到它们。对非 `<template>` 节点返回 `None`。
+pub fn get_template_contents(node: &Handle) -> Option<Handle> {
+    match &node.data {
+        NodeData::Element {
+            template_contents, ..
+        } => template_contents.borrow().clone(),
+        _ => None,
+    }
+}
+
+/// 把所有 `<template>` 内容片段中的节点移到元素自身的子节点下
+///
+/// rcdom 序列化时只输出 `children`，模板内容会整个丢失。移动之后序列化结果与原始标记一致，
+/// 浏览器解析时会重新把这些节点放回模板内容中。嵌套的 `<template>` 同样处理。
+pub fn inline_template_contents(node: &Handle) {
+    for child in node.children.borrow().iter() {
+        inline_template_contents(child);
+    }
+
+    if let Some(fragment) = get_template_contents(node) {
+        inline_template_contents(&fragment);
+        let moved: Vec<Handle> = fragment.children.borrow_mut().drain(..).collect();
+        for child in &moved {
+            child.parent.set(Some(std::rc::Rc::downgrade(node)));
+        }
+        node.children.borrow_mut().extend(moved);
+    }
+}
+
+/// 判断元素是否匹配简单选择器
+///
+/// 选择器由可选的标签名和任意个 `.class`、`#id` 组成，所有部分都必须满足；
+/// 标签名不区分大小写，`*` 匹配任意元素。非元素节点总是不匹配。
+pub fn matches_selector(node: &Handle, selector: &str) -> bool {
+    let tag_name = match get_node_name(node) {
+        Some(tag_name) => tag_name,
+        None => return false,
+    };
+    let selector = selector.trim();
+    if selector.is_empty() {
+        return false;
+    }
+
+    let tag_end = selector.find(['.', '#']).unwrap_or(selector.len());
+    let tag = &selector[..tag_end];
+    if !tag.is_empty() && tag != "*" && !tag.eq_ignore_ascii_case(tag_name) {
+        return false;
+    }
+
+    let classes = get_node_attr(node, "class").unwrap_or_default();
+    let id = get_node_attr(node, "id");
+    let mut rest = &selector[tag_end..];
+    while !rest.is_empty() {
+        let prefix = rest.as_bytes()[0];
+        let end = rest[1..].find(['.', '#']).map_or(rest.len(), |i| i + 1);
+        let name = &rest[1..end];
+        let matched = match prefix {
+            b'.' => classes.split_whitespace().any(|class| class == name),
+            _ => id.as_deref() == Some(name),
+        };
+        if name.is_empty() || !matched {
+            return false;
+        }
+        rest = &rest[end..];
+    }
+
+    true
+}
+
+/// 按文档顺序查找第一个匹配选择器的元素（包括 `node` 自身）
+pub fn find_by_selector(node: &Handle, selector: &str) -> Option<Handle> {
+    if matches_selector(node, selector) {
+        return Some(node.clone());
+    }
+    node.children
+        .borrow()
+        .iter()
+        .find_map(|child| find_by_selector(child, selector))
+}
+
+/// 只保留 `<body>` 中第一个匹配选择器的元素，用于把页面的一部分归档为独立的片段
+///
+/// `<head>` 保持不变；`<body>` 中位于该元素之外的 `<style>` 和 `<link rel="stylesheet">`
+/// 移到该元素之前，片段因此仍带有所需的样式，其余节点全部删除。选择器的写法见
+/// [`matches_selector`]。没有匹配的元素时不修改文档并返回 `false`。
+pub fn isolate_subtree(dom: &RcDom, selector: &str) -> bool {
+    let body = match find_nodes(&dom.document, vec!["html", "body"]).first() {
+        Some(body) => body.clone(),
+        None => return false,
+    };
+    let target = match find_by_selector(&body, selector) {
+        Some(target) => target,
+        None => return false,
+    };
+    if std::rc::Rc::ptr_eq(&target, &body) {
+        return true;
+    }
+
+    let mut stylesheets = Vec::new();
+    collect_stylesheets(&body, &target, &mut stylesheets);
+    for node in stylesheets.iter().chain(std::iter::once(&target)) {
+        remove_node(node);
+    }
+    for child in body.children.borrow_mut().drain(..) {
+        child.parent.set(None);
+    }
+    for node in stylesheets.into_iter().chain(std::iter::once(target)) {
+        dom.append(&body, NodeOrText::AppendNode(node));
+    }
+
+    true
+}
+
+/// 收集 `node` 之下、`excluded` 之外的样式元素
+fn collect_stylesheets(node: &Handle, excluded: &Handle, stylesheets: &mut Vec<Handle>) {
+    for child in node.children.borrow().iter() {
+        if std::rc::Rc::ptr_eq(child, excluded) {
+            continue;
+        }
+        let is_stylesheet = match get_node_name(child) {
+            Some("style") => true,
+            Some("link") => get_node_attr(child, "rel").is_some_and(|rel| {
+                rel.split_whitespace()
+                    .any(|value| value.eq_ignore_ascii_case("stylesheet"))
+            }),
+            _ => false,
+        };
+        if is_stylesheet {
+            stylesheets.push(child.clone());
+        } else {
+            collect_stylesheets(child, excluded, stylesheets);
+        }
+    }
+}
+
+/// 把节点从其父节点的子节点列表中移除
+///
+/// 节点的父指针同时被清空；没有父节点时不做任何操作。
+pub fn remove_node(node: &Handle) {
+    if let Some(parent) = node.parent.take().and_then(|parent| parent.upgrade()) {
+        parent
+            .children
+            .borrow_mut()
+            .retain(|child| !std::rc::Rc::ptr_eq(child, node));
+    }
 }
 
 /// 设置或删除节点属性
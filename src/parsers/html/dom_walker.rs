@@ -9,6 +9,7 @@
 //! - **元素处理**：为不同类型的HTML元素调用相应的处理器
 //! - **样式处理**：处理元素的内联样式属性，嵌入外部CSS资源
 //! - **JavaScript处理**：根据配置选择性移除JavaScript事件处理器
+//! - **属性删除**：删除配置中列出的属性（如 `nonce`、`data-analytics-*`）
 //! - **资源嵌入**：协调各种资源（图片、样式表、脚本等）的嵌入操作
 //!
 //! # 工作流程
@@ -41,7 +42,7 @@ use crate::parsers::js::attr_is_event_handler;
 use crate::utils::url::Url;
 
 use super::complex_element_handlers::*;
-use super::dom::{get_node_attr, set_node_attr};
+use super::dom::{get_node_attr, get_template_contents, set_node_attr};
 use super::element_handlers::ElementHandlerRegistry;
 
 /// DOM遍历器
@@ -215,8 +216,16 @@ impl DomWalker {
                 // 处理JavaScript事件属性
                 self.process_js_attributes(session, attrs);
 
+                // 删除配置中列出的属性
+                self.process_stripped_attributes(session, attrs);
+
                 // 递归处理子节点
                 self.walk_children(session, document_url, node);
+
+                // <template> 的内容位于独立的文档片段中，同样需要嵌入其中的资源
+                if let Some(fragment) = get_template_contents(node) {
+                    self.walk(session, document_url, &fragment);
+                }
             }
             _ => {
                 // 其他节点类型（如注释节点、文本节点）不需要特殊处理
@@ -240,11 +249,12 @@ impl DomWalker {
     ///
     /// # 实现细节
     ///
-    /// 该方法通过借用父节点的子节点列表，然后对每个子节点递归调用`walk`方法。
-    /// 使用`borrow()`来安全地访问RefCell包装的子节点集合。
+    /// 该方法先复制父节点的子节点列表，然后对每个子节点递归调用`walk`方法。
+    /// 处理器可能会移除当前节点（例如 JSON 脚本块），因此遍历时不能持有列表的借用。
     fn walk_children(&self, session: &mut Session, document_url: &Url, node: &Handle) {
         // 遍历当前节点的所有直接子节点
-        for child_node in node.children.borrow().iter() {
+        let children = node.children.borrow().clone();
+        for child_node in children.iter() {
             // 递归处理每个子节点
             self.walk(session, document_url, child_node);
         }
@@ -318,7 +328,7 @@ impl DomWalker {
     ///
     /// # 处理流程
     ///
-    /// 1. 检查`session.options.no_js`配置
+    /// 1. 检查`session.options.no_js`和`session.options.strip_inline_event_handlers`配置
     /// 2. 如果需要移除JavaScript：
     ///    - 遍历所有属性，识别事件处理属性
     ///    - 收集需要删除的属性索引
@@ -335,22 +345,23 @@ impl DomWalker {
     /// <!-- 处理前 -->
     /// <button onclick="alert('hello')" onmouseover="highlight(this)">Click me</button>
     ///
-    /// <!-- 处理后（如果no_js=true） -->
+    /// <!-- 处理后（如果no_js=true或strip_inline_event_handlers=true） -->
     /// <button>Click me</button>
     /// ```
     ///
     /// # 注意事项
     ///
     /// - 该方法直接修改传入的属性列表
-    /// - 只有在`session.options.no_js`为true时才会执行移除操作
+    /// - 只有在`session.options.no_js`或`session.options.strip_inline_event_handlers`为true时才会执行移除操作，
+    ///   后者只移除事件属性，`<script>`元素保持不变
     /// - 属性识别通过`attr_is_event_handler`函数完成
     fn process_js_attributes(
         &self,
         session: &mut Session,
         attrs: &std::cell::RefCell<Vec<html5ever::interface::Attribute>>,
     ) {
-        // 根据配置决定是否从文档中剥离所有JavaScript
-        if session.options.no_js {
+        // 根据配置决定是否从文档中剥离所有JavaScript，或者只剥离内联事件处理器
+        if session.options.no_js || session.options.strip_inline_event_handlers {
             let attrs_mut = &mut attrs.borrow_mut();
 
             // 收集需要删除的JavaScript事件属性索引
@@ -369,6 +380,45 @@ impl DomWalker {
             }
         }
     }
+
+    /// 删除配置中列出的属性
+    ///
+    /// `session.options.strip_attributes` 中的条目不区分大小写，以 `*` 结尾时按前缀匹配，
+    /// 例如 `data-analytics-*` 删除所有以 `data-analytics-` 开头的属性。
+    ///
+    /// 前缀匹配不会删除 `src` 和 `href`：资源已经嵌入到这两个属性中，`*` 这样宽泛的条目
+    /// 也不应让页面失去内容。只有写出完整属性名时才会删除它们。
+    fn process_stripped_attributes(
+        &self,
+        session: &mut Session,
+        attrs: &std::cell::RefCell<Vec<html5ever::interface::Attribute>>,
+    ) {
+        if session.options.strip_attributes.is_empty() {
+            return;
+        }
+
+        attrs.borrow_mut().retain(|attr| {
+            !session
+                .options
+                .strip_attributes
+                .iter()
+                .any(|pattern| attr_matches_pattern(&attr.name.local, pattern))
+        });
+    }
+}
+
+/// 属性名是否匹配 `strip_attributes` 中的一个条目
+fn attr_matches_pattern(attr_name: &str, pattern: &str) -> bool {
+    match pattern.trim().strip_suffix('*') {
+        Some(prefix) => {
+            !attr_name.eq_ignore_ascii_case("src")
+                && !attr_name.eq_ignore_ascii_case("href")
+                && attr_name.len() >= prefix.len()
+                && attr_name.is_char_boundary(prefix.len())
+                && attr_name[..prefix.len()].eq_ignore_ascii_case(prefix)
+        }
+        None => attr_name.eq_ignore_ascii_case(pattern.trim()),
+    }
 }
 
 /// 为DomWalker实现Default trait
@@ -29,12 +29,13 @@
 
 use markup5ever_rcdom::{Handle, NodeData};
 
+use crate::core::{JsonScriptPolicy, JsonScriptRedactor};
 use crate::network::session::Session;
 use crate::parsers::css::embed_css;
 use crate::utils::url::{resolve_url, Url, EMPTY_IMAGE_DATA_URL};
 
 use super::assets::{embed_srcset, retrieve_and_embed_asset};
-use super::dom::{get_node_attr, set_node_attr};
+use super::dom::{get_node_attr, remove_node, set_node_attr};
 use super::parser::{parse_link_type, LinkType};
 
 /// HTML 元素处理器特征
@@ -119,7 +120,8 @@ impl ElementHandler for MetaElementHandler {
 ///
 /// - **Favicon/AppleTouchIcon**: 网站图标，会被嵌入为data URL
 /// - **Stylesheet**: CSS样式表，会被下载并嵌入到文档中
-/// - **Preload/DnsPrefetch**: 预加载提示，由于资源已嵌入，这些提示会被移除
+/// - **Preload/DnsPrefetch**: 预加载提示，由于资源已嵌入，这些提示会被移除；启用
+///   `capture_preloads` 时先嵌入预加载的资源（见 [`LinkElementHandler::capture_preload_link`]）
 /// - **其他类型**: 确保href属性为绝对URL
 ///
 /// # 处理策略
@@ -146,6 +148,9 @@ impl ElementHandler for LinkElementHandler {
         } else if link_node_types.contains(&LinkType::Preload)
             || link_node_types.contains(&LinkType::DnsPrefetch)
         {
+            if session.options.capture_preloads && link_node_types.contains(&LinkType::Preload) {
+                self.capture_preload_link(session, document_url, node);
+            }
             // 由于所有资源都作为data URL嵌入，预加载和DNS预取不再必要
             set_node_attr(node, "rel", None);
         } else {
@@ -213,6 +218,41 @@ impl LinkElementHandler {
         }
     }
 
+    /// 嵌入 `<link rel="preload">` 预加载的资源
+    ///
+    /// 有些字体和图片只通过预加载声明、之后由脚本使用，去掉预加载提示后归档中就没有这些资源。
+    /// 这里按 `as` 属性判断资源类型，在对应类型没有被禁用时把 `href` 嵌入为 data URL；
+    /// 调用方随后删除 `rel`，元素本身不再触发加载，只是保存资源。
+    /// 未知的 `as` 类型（如 `fetch`、`document`）保持不变。
+    fn capture_preload_link(&self, session: &mut Session, document_url: &Url, node: &Handle) {
+        let options = &session.options;
+        let capture = match get_node_attr(node, "as")
+            .unwrap_or_default()
+            .to_lowercase()
+            .as_str()
+        {
+            "font" => !options.no_fonts,
+            "image" => !options.no_images,
+            "style" => !options.no_css,
+            "script" => !options.no_js,
+            "audio" => !options.no_audio,
+            "video" => !options.no_video,
+            _ => false,
+        };
+
+        if let Some(link_attr_href_value) = get_node_attr(node, "href") {
+            if capture && !link_attr_href_value.is_empty() {
+                retrieve_and_embed_asset(
+                    session,
+                    document_url,
+                    node,
+                    "href",
+                    &link_attr_href_value,
+                );
+            }
+        }
+    }
+
     /// 处理其他类型的链接
     ///
     /// # 参数
@@ -361,6 +401,10 @@ impl ElementHandler for ImgElementHandler {
 
         // 处理srcset属性
         self.handle_srcset(session, document_url, node);
+
+        if session.options.eager_load_images {
+            self.handle_eager_loading(node);
+        }
     }
 }
 
@@ -448,6 +492,24 @@ impl ImgElementHandler {
         retrieve_and_embed_asset(session, document_url, node, "src", &img_full_url);
     }
 
+    /// 取消懒加载
+    ///
+    /// # 参数
+    ///
+    /// * `node` - img元素节点
+    ///
+    /// # 处理逻辑
+    ///
+    /// 图片已经嵌入文档，懒加载没有意义，还可能因为依赖脚本判断视口而始终不显示。
+    /// 把`loading`设置为`eager`，并删除`decoding`和`fetchpriority`提示。
+    fn handle_eager_loading(&self, node: &Handle) {
+        if get_node_attr(node, "loading").is_some() {
+            set_node_attr(node, "loading", Some("eager".to_string()));
+        }
+        set_node_attr(node, "decoding", None);
+        set_node_attr(node, "fetchpriority", None);
+    }
+
     /// 处理响应式图片的srcset属性
     ///
     /// # 参数
@@ -482,6 +544,7 @@ impl ImgElementHandler {
 /// - **条件清理**: 根据用户配置选择性移除JavaScript代码
 /// - **属性清理**: 移除不再需要的src和integrity属性
 /// - **内容清理**: 清空内联脚本内容（当禁用JS时）
+/// - **状态数据处理**: 按 `json_script_policy` 保留、删除或改写 JSON 脚本块
 ///
 /// # 安全考虑
 ///
@@ -510,12 +573,54 @@ impl ElementHandler for ScriptElementHandler {
                 // 清除integrity属性
                 set_node_attr(node, "integrity", None);
             }
+        } else if is_json_script(node) {
+            match &session.options.json_script_policy {
+                JsonScriptPolicy::Keep => {}
+                JsonScriptPolicy::Remove => remove_node(node),
+                JsonScriptPolicy::Redact(redactor) => redact_script_text(node, redactor),
+            }
         } else if !script_attr_src.is_empty() {
             retrieve_and_embed_asset(session, document_url, node, "src", &script_attr_src);
         }
     }
 }
 
+/// 判断是否为存放页面状态的 JSON 脚本块
+///
+/// 匹配 `type="application/json"`（忽略大小写和参数）以及 Next.js 的 `__NEXT_DATA__`，
+/// `application/ld+json` 这类结构化数据不在此列。
+fn is_json_script(node: &Handle) -> bool {
+    if get_node_attr(node, "id").as_deref() == Some("__NEXT_DATA__") {
+        return true;
+    }
+
+    get_node_attr(node, "type").is_some_and(|script_type| {
+        let media_type = script_type.split(';').next().unwrap_or_default();
+        media_type.trim().eq_ignore_ascii_case("application/json")
+    })
+}
+
+/// 用回调结果替换脚本的文本内容
+fn redact_script_text(node: &Handle, redactor: &JsonScriptRedactor) {
+    let mut text = String::new();
+    for child_node in node.children.borrow().iter() {
+        if let NodeData::Text { ref contents } = child_node.data {
+            text.push_str(&contents.borrow());
+        }
+    }
+
+    let mut children = node.children.borrow_mut();
+    children.retain(|child_node| matches!(child_node.data, NodeData::Text { .. }));
+    children.truncate(1);
+    if let Some(child_node) = children.first() {
+        if let NodeData::Text { ref contents } = child_node.data {
+            let mut tendril = contents.borrow_mut();
+            tendril.clear();
+            tendril.push_slice(&redactor(&text));
+        }
+    }
+}
+
 /// STYLE 元素处理器
 ///
 /// 处理HTML中的`<style>`元素，负责内联CSS样式的处理和资源嵌入。
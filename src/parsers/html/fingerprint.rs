@@ -0,0 +1,114 @@
+//! 页面内容指纹
+//!
+//! 同一页面多次归档时，输出里总有与内容无关的变化：元数据注释中的时间戳、
+//! 每次请求都不同的 `nonce`、CSRF 令牌，以及内联资源的 base64 数据。
+//! 直接比较归档文件无法判断页面是否真正改变，逐字节比较数 MB 的 base64 也很慢。
+//!
+//! [`content_fingerprint`] 只根据文档结构和文本计算 SHA-256：
+//!
+//! - 元素按标签名和排序后的属性参与计算，易变属性被忽略；
+//! - `data:` URL 只保留媒体类型，不计入载荷本身；
+//! - 文本中的连续空白折叠为一个空格，纯空白文本和注释不参与计算；
+//! - `<script>` 的内容以及内容安全策略、CSRF 令牌等 `<meta>` 元素被忽略；
+//! - `<template>` 的内容片段同样参与计算。
+
+use std::sync::OnceLock;
+
+use markup5ever_rcdom::{Handle, NodeData};
+use regex::Regex;
+use sha2::{Digest, Sha256};
+
+use super::dom::{get_node_attr, get_template_contents};
+
+/// 每次请求都可能不同的属性
+const VOLATILE_ATTRS: &[&str] = &[
+    "nonce",
+    "integrity",
+    "data-csrf",
+    "data-nonce",
+    "data-timestamp",
+];
+
+/// 由 monolith 或服务端按请求生成、与页面内容无关的 `<meta>`
+const VOLATILE_META: &[&str] = &["content-security-policy", "csrf-param", "csrf-token"];
+
+/// 计算文档的内容指纹（SHA-256 的十六进制表示）
+pub fn content_fingerprint(node: &Handle) -> String {
+    let mut hasher = Sha256::new();
+    hash_node(node, &mut hasher);
+    format!("{:x}", hasher.finalize())
+}
+
+fn hash_node(node: &Handle, hasher: &mut Sha256) {
+    match node.data {
+        NodeData::Document => hash_children(node, hasher),
+        NodeData::Element {
+            ref name,
+            ref attrs,
+            ..
+        } => {
+            let tag = name.local.as_ref();
+            if tag == "meta" && is_volatile_meta(node) {
+                return;
+            }
+
+            let mut attrs: Vec<(String, String)> = attrs
+                .borrow()
+                .iter()
+                .map(|attr| (attr.name.local.to_string(), attr.value.to_string()))
+                .filter(|(name, _)| !VOLATILE_ATTRS.contains(&name.as_str()))
+                .collect();
+            attrs.sort();
+
+            hasher.update(b"<");
+            hasher.update(tag.as_bytes());
+            for (name, value) in &attrs {
+                hasher.update(b" ");
+                hasher.update(name.as_bytes());
+                hasher.update(b"=");
+                hasher.update(strip_data_payloads(value).as_bytes());
+            }
+            hasher.update(b">");
+
+            if tag != "script" {
+                hash_children(node, hasher);
+                if let Some(fragment) = get_template_contents(node) {
+                    hash_children(&fragment, hasher);
+                }
+            }
+
+            hasher.update(b"</>");
+        }
+        NodeData::Text { ref contents } => {
+            let contents = contents.borrow();
+            let text = contents.split_whitespace().collect::<Vec<_>>().join(" ");
+            if !text.is_empty() {
+                hasher.update(b"\"");
+                hasher.update(strip_data_payloads(&text).as_bytes());
+                hasher.update(b"\"");
+            }
+        }
+        _ => {}
+    }
+}
+
+fn hash_children(node: &Handle, hasher: &mut Sha256) {
+    for child in node.children.borrow().iter() {
+        hash_node(child, hasher);
+    }
+}
+
+fn is_volatile_meta(node: &Handle) -> bool {
+    ["name", "http-equiv"].iter().any(|attr| {
+        get_node_attr(node, attr)
+            .is_some_and(|value| VOLATILE_META.contains(&value.to_lowercase().as_str()))
+    })
+}
+
+/// 把 `data:` URL 替换为只含媒体类型的形式（属性值和 CSS 中的 `url(...)` 都适用）
+fn strip_data_payloads(value: &str) -> std::borrow::Cow<'_, str> {
+    static DATA_URL: OnceLock<Regex> = OnceLock::new();
+    let regex = DATA_URL
+        .get_or_init(|| Regex::new(r#"data:([^,;"'()\s]*)[^,"'()\s]*,[^"'()\s]*"#).unwrap());
+    regex.replace_all(value, "data:$1,")
+}
@@ -1,12 +1,15 @@
 //! HTML 文档元数据处理模块
 //!
 //! 此模块提供对 HTML 文档元数据的完整处理功能，包括：
-//! - 获取和设置 base URL
+//! - 获取和设置 base URL，按文档地址的源添加 base 标签
 //! - 处理字符编码声明
-//! - 管理 robots meta 标签
+//! - 整理 `<head>` 中重复的 meta 标签
+//! - 管理 robots 和 viewport meta 标签
 //! - 提取和操作文档标题
+//! - 提取 OpenGraph 和 Twitter 卡片元数据
 //! - 检测 favicon 存在性
 //! - 生成保存元数据标签
+//! - 在 `<head>` 中记录归档时间和来源地址
 //!
 //! 这些功能主要用于 monolith 工具在打包 HTML 文档时保持正确的元数据信息，
 //! 确保生成的单文件 HTML 能够正确显示和被搜索引擎正确解析。
@@ -135,6 +138,59 @@ pub fn get_robots(handle: &Handle) -> Option<String> {
     None
 }
 
+/// 获取 refresh meta 标签指向的地址
+///
+/// 解析 `<meta http-equiv="refresh" content="0; url=...">` 的 `content` 属性，
+/// 支持 `;` 或 `,` 分隔、`URL=` 前缀大小写不敏感以及带引号的地址。
+/// 只刷新当前页面（没有指定地址）的标签不会返回结果。
+///
+/// # 示例
+///
+/// ```rust
+/// use monolith::html::{get_meta_refresh_url, html_to_dom};
+///
+/// let html = b"<meta http-equiv=\"Refresh\" content=\"0; URL='/next.html'\">";
+/// let dom = html_to_dom(&html.to_vec(), "".to_string());
+/// assert_eq!(get_meta_refresh_url(&dom.document), Some("/next.html".to_string()));
+/// ```
+pub fn get_meta_refresh_url(handle: &Handle) -> Option<String> {
+    for meta_node in find_nodes(handle, vec!["html", "head", "meta"]).iter() {
+        if get_node_attr(meta_node, "http-equiv")
+            .unwrap_or_default()
+            .eq_ignore_ascii_case("refresh")
+        {
+            return get_node_attr(meta_node, "content")
+                .and_then(|content| parse_meta_refresh_url(&content));
+        }
+    }
+
+    None
+}
+
+fn parse_meta_refresh_url(content: &str) -> Option<String> {
+    let (delay, rest) = content.trim().split_once([';', ','])?;
+    if delay.trim().is_empty() || !delay.trim().chars().all(|c| c.is_ascii_digit() || c == '.') {
+        return None;
+    }
+
+    let rest = rest.trim_start();
+    let url = match rest.get(..3) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("url") => {
+            match rest[3..].trim_start().strip_prefix('=') {
+                Some(url) => url.trim(),
+                None => rest.trim(),
+            }
+        }
+        _ => rest.trim(),
+    };
+    let url = match url.chars().next() {
+        Some(quote @ ('\'' | '"')) => url[1..].split(quote).next().unwrap_or_default(),
+        _ => url,
+    };
+
+    Some(url.trim().to_string()).filter(|url| !url.is_empty())
+}
+
 /// 获取文档标题
 ///
 /// 从 HTML 文档的 `<title>` 标签中提取文本内容。
@@ -168,6 +224,104 @@ pub fn get_title(node: &Handle) -> Option<String> {
     None
 }
 
+/// 页面的社交分享元数据（OpenGraph 和 Twitter 卡片）
+///
+/// 同一字段优先使用 `og:*`，没有时使用对应的 `twitter:*`。
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SocialMetadata {
+    /// `og:title` 或 `twitter:title`
+    pub title: Option<String>,
+    /// `og:description` 或 `twitter:description`
+    pub description: Option<String>,
+    /// 预览图地址：`og:image` 或 `twitter:image`，保持页面中的原始写法
+    pub image: Option<String>,
+    /// 内容类型：`og:type`（如 `article`），没有时为 `twitter:card`（如 `summary`）
+    pub kind: Option<String>,
+}
+
+impl SocialMetadata {
+    /// 页面没有任何社交分享元数据
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none()
+            && self.description.is_none()
+            && self.image.is_none()
+            && self.kind.is_none()
+    }
+}
+
+/// 可以翻译的社交分享元数据：标题和描述
+pub const SOCIAL_TEXT_META: &[&str] = &[
+    "og:title",
+    "og:description",
+    "twitter:title",
+    "twitter:description",
+];
+
+/// 提取 `<head>` 中的 OpenGraph 和 Twitter 卡片元数据
+///
+/// OpenGraph 标签按规范使用 `property` 属性，Twitter 标签使用 `name`，
+/// 实际页面中两种写法都很常见，因此两个属性都会检查。同名标签以第一个为准，空值忽略。
+///
+/// # 示例
+///
+/// ```rust
+/// use monolith::html::{get_social_metadata, html_to_dom};
+///
+/// let dom = html_to_dom(
+///     b"<meta property=\"og:title\" content=\"Launch\"><meta name=\"twitter:card\" content=\"summary\">",
+///     "".to_string(),
+/// );
+/// let metadata = get_social_metadata(&dom.document);
+/// assert_eq!(metadata.title.as_deref(), Some("Launch"));
+/// assert_eq!(metadata.kind.as_deref(), Some("summary"));
+/// ```
+pub fn get_social_metadata(node: &Handle) -> SocialMetadata {
+    let tags: Vec<(String, String)> = find_nodes(node, vec!["html", "head", "meta"])
+        .iter()
+        .filter_map(|meta_node| {
+            let key = social_meta_key(meta_node)?;
+            let content = get_node_attr(meta_node, "content")?.trim().to_string();
+            if content.is_empty() {
+                None
+            } else {
+                Some((key, content))
+            }
+        })
+        .collect();
+    let lookup = |keys: &[&str]| {
+        keys.iter().find_map(|key| {
+            tags.iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, content)| content.clone())
+        })
+    };
+
+    SocialMetadata {
+        title: lookup(&["og:title", "twitter:title"]),
+        description: lookup(&["og:description", "twitter:description"]),
+        image: lookup(&[
+            "og:image",
+            "og:image:url",
+            "og:image:secure_url",
+            "twitter:image",
+            "twitter:image:src",
+        ]),
+        kind: lookup(&["og:type", "twitter:card"]),
+    }
+}
+
+/// `og:*` 或 `twitter:*` meta 标签的名称（小写），其他标签返回 `None`
+pub fn social_meta_key(node: &Handle) -> Option<String> {
+    ["property", "name"].iter().find_map(|attr_name| {
+        let key = get_node_attr(node, attr_name)?.trim().to_lowercase();
+        if key.starts_with("og:") || key.starts_with("twitter:") {
+            Some(key)
+        } else {
+            None
+        }
+    })
+}
+
 /// 检查是否有 favicon
 ///
 /// 检查 HTML 文档是否包含 favicon 相关的 link 标签。
@@ -266,6 +420,42 @@ pub fn set_base_url(document: &Handle, base_href_value: String) -> RcDom {
     dom
 }
 
+/// 按文档地址添加 base 标签
+///
+/// 不嵌入资源的归档中，资源和链接保留相对地址时，在本地打开的文件会按 `file://` 解析它们。
+/// 在 `<head>` 开头加入 `<base href="文档地址">`（去掉片段）后，相对地址的解析方式与
+/// 从原站点打开时相同。文档已有 `<base>` 标签或地址没有可用的源（`data:`、`file:` 等）时
+/// 不做任何改动。
+///
+/// # 示例
+///
+/// ```ignore
+/// let url = Url::parse("https://example.com/docs/page.html#intro").unwrap();
+/// let updated_dom = inject_base(dom, &url); // <base href="https://example.com/docs/page.html">
+/// ```
+pub fn inject_base(dom: RcDom, document_url: &Url) -> RcDom {
+    if !document_url.origin().is_tuple() || get_base_url(&dom.document).is_some() {
+        return dom;
+    }
+    let mut base_url = document_url.clone();
+    base_url.set_fragment(None);
+
+    if let Some(head_node) = find_nodes(&dom.document, vec!["html", "head"]).first() {
+        let base_node = create_element(
+            &dom,
+            QualName::new(None, ns!(), LocalName::from("base")),
+            vec![Attribute {
+                name: QualName::new(None, ns!(), LocalName::from("href")),
+                value: format_tendril!("{}", base_url),
+            }],
+        );
+        // 放在最前面，使其后所有元素的相对地址都按它解析
+        head_node.children.borrow_mut().insert(0, base_node);
+    }
+
+    dom
+}
+
 /// 设置字符编码
 ///
 /// 在 HTML 文档中设置或更新字符编码声明。优先尝试更新现有的 meta 标签，
@@ -337,6 +527,72 @@ pub fn set_charset(dom: RcDom, charset: String) -> RcDom {
     dom
 }
 
+/// 整理 `<head>` 中的 meta 标签
+///
+/// 重复的 meta 标签只保留第一个（与浏览器采用的一致）：字符编码声明（`<meta charset>` 与
+/// `<meta http-equiv="content-type">` 视为同一种）、相同 `name` 以及相同 `http-equiv`
+/// 的标签，名称比较不区分大小写。随后通过 [`set_charset`] 把唯一的编码声明设为 `charset`，
+/// 并移到 `<head>` 的最前面，保证它位于文档的前 1024 字节内；其余元素保持原有顺序。
+///
+/// # 示例
+///
+/// ```ignore
+/// let dom = normalize_head(dom, "utf-8");
+/// ```
+pub fn normalize_head(dom: RcDom, charset: &str) -> RcDom {
+    let head_node = match find_nodes(&dom.document, vec!["html", "head"]).first() {
+        Some(head_node) => head_node.clone(),
+        None => return dom,
+    };
+
+    // 删除重复的 meta 标签
+    let mut seen_keys: Vec<String> = Vec::new();
+    head_node
+        .children
+        .borrow_mut()
+        .retain(|child| match meta_dedupe_key(child) {
+            Some(key) if seen_keys.contains(&key) => false,
+            Some(key) => {
+                seen_keys.push(key);
+                true
+            }
+            None => true,
+        });
+
+    let dom = set_charset(dom, charset.to_string());
+
+    // 将编码声明移到 HEAD 的最前面
+    {
+        let mut children = head_node.children.borrow_mut();
+        if let Some(position) = children
+            .iter()
+            .position(|child| meta_dedupe_key(child).as_deref() == Some("charset"))
+        {
+            let charset_node = children.remove(position);
+            children.insert(0, charset_node);
+        }
+    }
+
+    dom
+}
+
+/// 判断 meta 标签是否重复时使用的键，不是 meta 标签或无法比较时返回 `None`
+fn meta_dedupe_key(node: &Handle) -> Option<String> {
+    match node.data {
+        NodeData::Element { ref name, .. } if name.local.as_ref() == "meta" => {}
+        _ => return None,
+    }
+
+    let http_equiv = get_node_attr(node, "http-equiv").map(|value| value.to_lowercase());
+    if get_node_attr(node, "charset").is_some() || http_equiv.as_deref() == Some("content-type") {
+        return Some("charset".to_string());
+    }
+    if let Some(name) = get_node_attr(node, "name") {
+        return Some(format!("name:{}", name.trim().to_lowercase()));
+    }
+    http_equiv.map(|http_equiv| format!("http-equiv:{}", http_equiv.trim()))
+}
+
 /// 设置 robots meta 标签
 ///
 /// 在 HTML 文档中设置或更新 robots meta 标签。如果文档中已经存在 robots 标签，
@@ -404,6 +660,23 @@ pub fn set_robots(dom: RcDom, content_value: &str) -> RcDom {
     dom
 }
 
+/// 默认的视口设置：按设备宽度布局，不缩放
+pub const DEFAULT_VIEWPORT: &str = "width=device-width, initial-scale=1";
+
+/// 设置 viewport meta 标签
+///
+/// 为移动端设计的页面缺少视口声明时，在 iframe 或桌面浏览器中会按 980px 左右的虚拟宽度
+/// 缩小显示。已有的 viewport 标签会被改写为 `content_value`，否则新建一个。
+///
+/// # 示例
+///
+/// ```ignore
+/// let updated_dom = set_viewport(dom, DEFAULT_VIEWPORT);
+/// ```
+pub fn set_viewport(dom: RcDom, content_value: &str) -> RcDom {
+    set_named_meta(dom, "viewport", content_value)
+}
+
 /// 创建元数据标签
 ///
 /// 生成一个 HTML 注释标签，记录文档的来源 URL、保存时间和工具信息。
@@ -430,25 +703,95 @@ pub fn set_robots(dom: RcDom, content_value: &str) -> RcDom {
 /// // 输出类似：<!-- Saved from https://example.com/page.html at 2023-12-01T10:30:45Z using monolith v2.7.0 -->
 /// ```
 pub fn create_metadata_tag(url: &Url) -> String {
-    let datetime: &str = &Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
-    let mut clean_url: Url = clean_url(url.clone());
-
-    // 防止凭据信息泄露到元数据中
-    if clean_url.scheme() == "http" || clean_url.scheme() == "https" {
-        // 只有 HTTP(S) URL 才可能包含凭据信息
-        clean_url.set_username("").unwrap();
-        clean_url.set_password(None).unwrap();
-    }
-
     format!(
         "<!-- Saved from {} at {} using {} v{} -->",
-        if clean_url.scheme() == "http" || clean_url.scheme() == "https" {
-            clean_url.as_str()
-        } else {
-            "local source"
-        },
-        datetime,
+        saved_from_url(url).as_deref().unwrap_or("local source"),
+        archived_at(),
         env!("CARGO_PKG_NAME"),
         env!("CARGO_PKG_VERSION"),
     )
 }
+
+/// 在 `<head>` 中记录归档时间和来源地址
+///
+/// 写入 `<meta name="monolith-archived-at">`（RFC 3339 时间）和
+/// `<meta name="monolith-source-url">`，取值与 [`create_metadata_tag`] 生成的注释相同，
+/// 但保存在文档内部，不会因为注释被删除（例如压缩输出时）而丢失。
+/// 已存在同名的 meta 标签时更新其内容，重新归档的页面不会出现重复的标签。
+///
+/// 与注释一样，来源地址中的用户名和密码会被移除；本地文件没有来源地址，只记录归档时间。
+pub fn set_archive_metadata(dom: RcDom, url: &Url) -> RcDom {
+    let dom = set_named_meta(dom, "monolith-archived-at", &archived_at());
+    match saved_from_url(url) {
+        Some(source_url) => set_named_meta(dom, "monolith-source-url", &source_url),
+        None => dom,
+    }
+}
+
+/// 归档时间，精确到秒
+fn archived_at() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// 去掉凭据信息之后的来源地址，只有 HTTP(S) 地址才会记录
+fn saved_from_url(url: &Url) -> Option<String> {
+    let mut clean_url: Url = clean_url(url.clone());
+
+    if clean_url.scheme() != "http" && clean_url.scheme() != "https" {
+        return None;
+    }
+
+    // 防止凭据信息泄露到元数据中
+    clean_url.set_username("").unwrap();
+    clean_url.set_password(None).unwrap();
+    Some(clean_url.to_string())
+}
+
+/// 设置 `<meta name="...">` 的内容，不存在时追加到 `<head>` 末尾
+fn set_named_meta(dom: RcDom, name: &str, content_value: &str) -> RcDom {
+    for meta_node in find_nodes(&dom.document, vec!["html", "head", "meta"]).iter() {
+        if get_node_attr(meta_node, "name")
+            .unwrap_or_default()
+            .eq_ignore_ascii_case(name)
+        {
+            set_node_attr(meta_node, "content", Some(content_value.to_string()));
+            return dom;
+        }
+    }
+
+    let meta_node: Handle = create_element(
+        &dom,
+        QualName::new(None, ns!(), LocalName::from("meta")),
+        vec![
+            Attribute {
+                name: QualName::new(None, ns!(), LocalName::from("name")),
+                value: format_tendril!("{}", name),
+            },
+            Attribute {
+                name: QualName::new(None, ns!(), LocalName::from("content")),
+                value: format_tendril!("{}", content_value),
+            },
+        ],
+    );
+    if let Some(head_node) = find_nodes(&dom.document, vec!["html", "head"]).first() {
+        head_node.children.borrow_mut().push(meta_node);
+    }
+
+    dom
+}
+
+/// 创建记录内容指纹的HTML注释
+///
+/// 指纹由 [`content_fingerprint`](super::fingerprint::content_fingerprint) 计算，
+/// 比较两次归档的这一行即可判断页面的有效内容是否改变。
+///
+/// # 示例
+///
+/// ```rust
+/// use monolith::html::create_fingerprint_tag;
+///
+/// assert_eq!(create_fingerprint_tag("ab12"), "<!-- Content fingerprint: sha256:ab12 -->");
+/// ```
+pub fn create_fingerprint_tag(fingerprint: &str) -> String {
+    format!("<!-- Content fingerprint: sha256:{} -->", fingerprint)
+}
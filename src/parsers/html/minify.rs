@@ -0,0 +1,302 @@
+//! HTML 压缩
+//!
+//! 原始页面中的缩进和换行会让归档文件明显变大。本模块在序列化前后各做一步压缩：
+//!
+//! - [`minify_dom`]：删除注释，把文本中连续的空白折叠为一个空格，
+//!   并删除块级元素之间只包含空白的文本节点；
+//! - [`unquote_attributes`]：对序列化结果去掉不需要的属性引号，空值属性只保留属性名。
+//!
+//! `<pre>`、`<textarea>` 以及 `<script>`/`<style>` 等原始文本元素的内容保持不变。
+//! 通过 CSS `white-space` 设置的空白敏感区域无法从 DOM 判断，不在保护范围内。
+
+use html5ever::tendril::StrTendril;
+use markup5ever_rcdom::{Handle, NodeData};
+
+/// 内容中的空白有意义、不做任何改动的元素
+const PRESERVE_WHITESPACE_ELEMENTS: &[&str] = &[
+    "listing",
+    "noscript",
+    "plaintext",
+    "pre",
+    "script",
+    "style",
+    "textarea",
+    "xmp",
+];
+
+/// 序列化时内容按原样输出的元素，属性去引号时跳过其内容
+const RAW_TEXT_ELEMENTS: &[&str] = &[
+    "iframe",
+    "noembed",
+    "noframes",
+    "noscript",
+    "plaintext",
+    "script",
+    "style",
+    "textarea",
+    "title",
+    "xmp",
+];
+
+/// 相邻空白不会被渲染的块级元素
+const BLOCK_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "body",
+    "caption",
+    "col",
+    "colgroup",
+    "dd",
+    "details",
+    "dialog",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "header",
+    "hgroup",
+    "hr",
+    "html",
+    "legend",
+    "li",
+    "main",
+    "menu",
+    "nav",
+    "ol",
+    "option",
+    "p",
+    "pre",
+    "section",
+    "summary",
+    "table",
+    "tbody",
+    "td",
+    "tfoot",
+    "th",
+    "thead",
+    "tr",
+    "ul",
+];
+
+/// 压缩 DOM 树中的空白和注释
+pub fn minify_dom(node: &Handle, preserve_comments: bool) {
+    let (in_head, is_block) = match node.data {
+        NodeData::Element { ref name, .. } => (
+            &*name.local == "head",
+            BLOCK_ELEMENTS.contains(&&*name.local),
+        ),
+        _ => (false, true),
+    };
+    let mut children = node.children.borrow_mut();
+
+    // 先折叠文本节点，再根据相邻元素判断纯空白节点是否可以删除
+    for child in children.iter() {
+        match child.data {
+            NodeData::Text { ref contents } => {
+                let collapsed = collapse_whitespace(&contents.borrow());
+                *contents.borrow_mut() = StrTendril::from(collapsed);
+            }
+            NodeData::Element { ref name, .. }
+                if !PRESERVE_WHITESPACE_ELEMENTS.contains(&&*name.local) =>
+            {
+                minify_dom(child, preserve_comments);
+            }
+            _ => {}
+        }
+    }
+
+    let mut i = 0;
+    while i < children.len() {
+        let removable = match children[i].data {
+            NodeData::Comment { .. } => !preserve_comments,
+            // 文本已折叠，纯空白节点只剩一个空格
+            NodeData::Text { ref contents } if contents.borrow().trim_matches(' ').is_empty() => {
+                in_head
+                    || (is_block_boundary(i.checked_sub(1).map(|j| &children[j]), is_block)
+                        && is_block_boundary(children.get(i + 1), is_block))
+            }
+            _ => false,
+        };
+
+        if removable {
+            children.remove(i);
+        } else {
+            i += 1;
+        }
+    }
+}
+
+/// 去掉序列化结果中不需要的属性引号
+///
+/// 只处理 html5ever 序列化器的输出：属性值总是使用双引号，值中的双引号已转义为 `&quot;`。
+/// 值中不含空白和 `"'=<>` 以及反引号时去掉引号；空值属性只保留属性名。
+pub fn unquote_attributes(html: &str) -> String {
+    let mut output = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(pos) = rest.find('<') {
+        output.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+
+        if rest.starts_with("<!--") {
+            let end = rest.find("-->").map(|end| end + 3).unwrap_or(rest.len());
+            output.push_str(&rest[..end]);
+            rest = &rest[end..];
+        } else if rest[1..].starts_with(|c: char| c.is_ascii_alphabetic()) {
+            let (tag, tag_name, remaining) = unquote_start_tag(rest);
+            output.push_str(&tag);
+            rest = remaining;
+
+            // 跳过原始文本元素的内容
+            if RAW_TEXT_ELEMENTS.contains(&tag_name.as_str()) {
+                let end = find_end_tag(rest, &tag_name).unwrap_or(rest.len());
+                output.push_str(&rest[..end]);
+                rest = &rest[end..];
+            }
+        } else {
+            // 结束标签和 DOCTYPE 原样保留
+            let end = rest.find('>').map(|end| end + 1).unwrap_or(rest.len());
+            output.push_str(&rest[..end]);
+            rest = &rest[end..];
+        }
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/// 处理一个开始标签，返回改写后的标签、小写标签名和剩余的输入
+fn unquote_start_tag(input: &str) -> (String, String, &str) {
+    let name_end = input[1..]
+        .find(|c: char| c.is_ascii_whitespace() || c == '/' || c == '>')
+        .map(|end| end + 1)
+        .unwrap_or(input.len());
+    let tag_name = input[1..name_end].to_ascii_lowercase();
+    let mut tag = input[..name_end].to_string();
+    let mut rest = &input[name_end..];
+
+    loop {
+        let trimmed = rest.trim_start_matches(|c: char| c.is_ascii_whitespace());
+        if trimmed.is_empty() {
+            tag.push_str(rest);
+            return (tag, tag_name, "");
+        }
+        if trimmed.starts_with('>') || trimmed.starts_with("/>") {
+            let end = rest.len() - trimmed.len() + if trimmed.starts_with('>') { 1 } else { 2 };
+            tag.push_str(&rest[..end]);
+            return (tag, tag_name, &rest[end..]);
+        }
+
+        tag.push(' ');
+        let attr_end = trimmed
+            .find(|c: char| c.is_ascii_whitespace() || c == '=' || c == '>')
+            .unwrap_or(trimmed.len());
+        tag.push_str(&trimmed[..attr_end]);
+        rest = &trimmed[attr_end..];
+
+        if let Some(value_rest) = rest.strip_prefix("=\"") {
+            let value_end = value_rest.find('"').unwrap_or(value_rest.len());
+            let value = &value_rest[..value_end];
+            if value.is_empty() {
+                // 空值属性等价于只写属性名
+            } else if value
+                .chars()
+                .all(|c| !c.is_ascii_whitespace() && !"\"'=<>`".contains(c))
+            {
+                tag.push('=');
+                tag.push_str(value);
+            } else {
+                tag.push_str("=\"");
+                tag.push_str(value);
+                tag.push('"');
+            }
+            rest = value_rest.get(value_end + 1..).unwrap_or("");
+        }
+    }
+}
+
+/// 查找 `</tag_name` 结束标签的位置（不区分大小写）
+fn find_end_tag(input: &str, tag_name: &str) -> Option<usize> {
+    let needle = format!("</{}", tag_name);
+    input
+        .as_bytes()
+        .windows(needle.len())
+        .position(|window| window.eq_ignore_ascii_case(needle.as_bytes()))
+}
+
+/// 把连续的 ASCII 空白折叠为一个空格（不间断空格等保持不变）
+fn collapse_whitespace(text: &str) -> String {
+    let mut collapsed = String::with_capacity(text.len());
+    let mut in_whitespace = false;
+
+    for c in text.chars() {
+        if matches!(c, ' ' | '\t' | '\n' | '\r' | '\x0C') {
+            if !in_whitespace {
+                collapsed.push(' ');
+            }
+            in_whitespace = true;
+        } else {
+            collapsed.push(c);
+            in_whitespace = false;
+        }
+    }
+
+    collapsed
+}
+
+/// 兄弟节点是否为块级元素，其旁边的空白不会被渲染
+///
+/// 没有兄弟节点时取决于父元素本身是否为块级元素。
+fn is_block_boundary(sibling: Option<&Handle>, parent_is_block: bool) -> bool {
+    match sibling {
+        None => parent_is_block,
+        Some(node) => match node.data {
+            NodeData::Element { ref name, .. } => BLOCK_ELEMENTS.contains(&&*name.local),
+            NodeData::Comment { .. } | NodeData::Doctype { .. } => true,
+            _ => false,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collapse_whitespace() {
+        assert_eq!(collapse_whitespace("  a \n\t b  "), " a b ");
+        assert_eq!(collapse_whitespace("a\u{a0}\u{a0}b"), "a\u{a0}\u{a0}b");
+    }
+
+    #[test]
+    fn test_unquote_attributes() {
+        assert_eq!(
+            unquote_attributes(
+                "<div class=\"a b\" id=\"main\" hidden=\"\"><a href=\"/x?a=1\" title=\"x\">y</a></div>"
+            ),
+            "<div class=\"a b\" id=main hidden><a href=\"/x?a=1\" title=x>y</a></div>"
+        );
+    }
+
+    #[test]
+    fn test_unquote_attributes_skips_raw_text_and_comments() {
+        let html = "<!-- <a href=\"x\"> --><script>var s = '<a href=\"x\">';</script><b id=\"y\">";
+        assert_eq!(
+            unquote_attributes(html),
+            "<!-- <a href=\"x\"> --><script>var s = '<a href=\"x\">';</script><b id=y>"
+        );
+    }
+}
@@ -9,11 +9,14 @@
 //! - [`parser`]: HTML解析器类型定义和核心解析功能
 //! - [`dom`]: 基础DOM操作，包括节点查找、属性操作等
 //! - [`metadata`]: 文档元数据处理，如标题、编码、robots标签等
+//! - [`minify`]: 最终文档的空白、注释和属性引号压缩
 //! - [`serializer`]: HTML文档序列化功能，将DOM转换回HTML字符串
 //! - [`assets`]: 静态资源嵌入和处理，包括图片、CSS、JS等资源的内联化
 //! - [`walker`]: DOM遍历核心逻辑，用于递归处理DOM树中的所有节点
 //! - [`dom_walker`]: DOM遍历器的实现
 //! - [`element_handlers`]: 各种HTML元素的处理器
+//! - [`fingerprint`]: 忽略易变内容的页面内容指纹，用于判断重新归档的页面是否改变
+//! - [`sanitize`]: 面向不受信任页面的净化规则，删除脚本、脚本地址和可执行的内嵌文档
 //! - [`complex_element_handlers`]: 复杂HTML元素的专门处理器
 //!
 //! # 使用场景
@@ -65,6 +68,16 @@ pub mod dom_walker;
 /// 为各种HTML元素提供处理逻辑，实现元素转换和资源嵌入
 pub mod element_handlers;
 
+/// 内容指纹模块
+///
+/// 根据文档结构和文本计算稳定的哈希，忽略时间戳、nonce 和内联资源载荷
+pub mod fingerprint;
+
+/// HTML压缩模块
+///
+/// 折叠空白、删除注释并去掉多余的属性引号，用于减小最终文档的体积
+pub mod minify;
+
 /// 文档元数据处理模块
 ///
 /// 处理HTML文档的元数据，如标题、编码、robots设置等
@@ -75,6 +88,11 @@ pub mod metadata;
 /// 提供HTML字符串解析功能和相关类型定义
 pub mod parser;
 
+/// 页面净化模块
+///
+/// 在序列化之前删除脚本、事件属性、脚本地址等可能在归档所在域名下执行的内容
+pub mod sanitize;
+
 /// HTML序列化模块
 ///
 /// 将DOM树序列化回HTML字符串的功能实现
@@ -101,6 +119,11 @@ pub mod walker;
 /// 将指定的favicon资源嵌入到HTML文档的head部分
 pub use assets::add_favicon;
 
+/// 添加自定义样式
+///
+/// 在文档的HEAD中追加一个包含指定CSS的style元素
+pub use assets::add_stylesheet;
+
 /// 检查资源的完整性
 ///
 /// 验证嵌入资源的完整性哈希值，确保资源未被篡改
@@ -145,6 +168,16 @@ pub use dom::get_node_name;
 /// 返回指定节点的父节点引用
 pub use dom::get_parent_node;
 
+/// 读取父节点
+///
+/// 不改动节点的父指针，没有父节点时返回 `None`
+pub use dom::parent_of;
+
+/// 遍历祖先节点
+///
+/// 从节点自身开始逐级向上直到根节点
+pub use dom::ancestors;
+
 /// 将HTML字符串解析为DOM树
 ///
 /// 这是核心的HTML解析函数，将HTML字符串转换为可操作的DOM结构
@@ -155,6 +188,31 @@ pub use dom::html_to_dom;
 /// 为DOM节点设置或更新指定属性的值
 pub use dom::set_node_attr;
 
+/// 获取 `<template>` 元素的内容片段
+///
+/// 模板内容不在元素的子节点中，需要单独遍历
+pub use dom::get_template_contents;
+
+/// 展开模板内容
+///
+/// 把模板内容移回 `<template>` 元素下，使序列化结果包含这些节点
+pub use dom::inline_template_contents;
+
+/// 移除节点
+///
+/// 把节点从父节点的子节点列表中摘除
+pub use dom::remove_node;
+
+/// 按简单选择器匹配和查找元素
+///
+/// 支持标签名、`.class` 和 `#id` 的组合
+pub use dom::{find_by_selector, matches_selector};
+
+/// 只保留页面的一部分
+///
+/// 删除 `<body>` 中选择器所指元素之外的内容，保留样式
+pub use dom::isolate_subtree;
+
 // ============================================================================
 // 元数据处理相关API
 // ============================================================================
@@ -164,6 +222,16 @@ pub use dom::set_node_attr;
 /// 生成HTML meta标签，用于设置文档元数据
 pub use metadata::create_metadata_tag;
 
+/// 创建内容指纹注释
+///
+/// 生成记录内容指纹的HTML注释，与元数据注释一起放在文档开头
+pub use metadata::create_fingerprint_tag;
+
+/// 计算内容指纹
+///
+/// 根据最终DOM的结构和文本计算稳定的SHA-256哈希
+pub use fingerprint::content_fingerprint;
+
 /// 获取文档基础URL
 ///
 /// 从HTML文档中提取base标签的href属性值
@@ -179,11 +247,21 @@ pub use metadata::get_charset;
 /// 读取meta robots标签的内容，用于搜索引擎爬虫指导
 pub use metadata::get_robots;
 
+/// 获取refresh跳转地址
+///
+/// 读取meta refresh标签中的跳转URL
+pub use metadata::get_meta_refresh_url;
+
 /// 获取文档标题
 ///
 /// 从title标签中提取文档标题文本
 pub use metadata::get_title;
 
+/// 获取社交分享元数据
+///
+/// 提取OpenGraph和Twitter卡片的标题、描述、预览图和类型
+pub use metadata::{get_social_metadata, social_meta_key, SocialMetadata, SOCIAL_TEXT_META};
+
 /// 检查是否存在favicon
 ///
 /// 判断HTML文档中是否已经包含favicon相关的link标签
@@ -194,16 +272,36 @@ pub use metadata::has_favicon;
 /// 添加或更新HTML文档的base标签
 pub use metadata::set_base_url;
 
+/// 添加指向文档源的base标签
+///
+/// 文档没有base标签时，在HEAD开头加入指向文档地址源的base标签
+pub use metadata::inject_base;
+
 /// 设置文档字符编码
 ///
 /// 添加或更新文档的charset meta标签
 pub use metadata::set_charset;
 
+/// 整理文档头部
+///
+/// 删除重复的meta标签，并保证只有一个位于最前面的charset声明
+pub use metadata::normalize_head;
+
 /// 设置robots元数据
 ///
 /// 添加或更新meta robots标签
 pub use metadata::set_robots;
 
+/// 设置视口元数据
+///
+/// 添加或改写meta viewport标签
+pub use metadata::{set_viewport, DEFAULT_VIEWPORT};
+
+/// 记录归档信息
+///
+/// 在HEAD中添加或更新记录归档时间和来源地址的meta标签
+pub use metadata::set_archive_metadata;
+
 // ============================================================================
 // HTML解析器相关API
 // ============================================================================
@@ -228,6 +326,15 @@ pub use parser::LinkType;
 /// 表示srcset属性中的单个图片源，包含URL和描述符
 pub use parser::SrcSetItem;
 
+// ============================================================================
+// 净化相关API
+// ============================================================================
+
+/// 净化DOM树
+///
+/// 按净化规则删除节点和属性，规则说明见 [`sanitize`] 模块
+pub use sanitize::{sanitize, SanitizeProfile};
+
 // ============================================================================
 // 序列化相关API
 // ============================================================================
@@ -237,6 +344,11 @@ pub use parser::SrcSetItem;
 /// 将处理后的DOM树转换回HTML字符串形式
 pub use serializer::serialize_document;
 
+/// 将DOM文档直接序列化到写入器
+///
+/// 边序列化边写出，避免在内存中拼出整个文档
+pub use serializer::serialize_document_to_writer;
+
 // ============================================================================
 // 工具函数相关API
 // ============================================================================
@@ -251,6 +363,11 @@ pub use utils::compose_csp;
 /// 检查给定的链接或文件名是否为favicon图标
 pub use utils::is_favicon;
 
+/// 清空JSON中的字符串值
+///
+/// 保留键名和结构，用于脱敏页面状态数据
+pub use utils::redact_json_strings;
+
 /// favicon文件名常量集合
 ///
 /// 包含常见的favicon文件名的字符串数组
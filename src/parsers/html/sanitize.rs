@@ -0,0 +1,245 @@
+//! 不受信任页面的净化
+//!
+//! 在自己的域名下提供第三方页面的归档时，仅靠 `no_js` 不足以防止页面中的脚本运行。
+//! 净化在序列化之前对最终 DOM（包括 `<template>` 内容）执行一遍，[`SanitizeProfile`]
+//! 中的每一项规则都可以单独关闭：
+//!
+//! | 规则 | 处理 |
+//! |------|------|
+//! | `remove_scripts` | 删除 `<script>` 元素 |
+//! | `remove_event_handlers` | 删除所有以 `on` 开头的属性，不限于已知的事件名 |
+//! | `remove_script_urls` | 删除值为 `javascript:` 或 `vbscript:` 地址的 URL 属性（见 [`URL_ATTRIBUTES`]），包含脚本的 `style` 属性，以及跳转到脚本或 data 地址的 `<meta http-equiv="refresh">` |
+//! | `remove_html_frames` | 删除 `<iframe>`、`<frame>`、`<object>`、`<embed>` 以 HTML、XML 或 SVG data URL 加载的文档，以及 `srcdoc` |
+//! | `remove_external_form_actions` | 删除提交到其他主机的 `action` 和 `formaction` |
+//! | `remove_plugins` | 删除 `<object>`、`<embed>` 和 `<applet>` 元素 |
+//!
+//! 判断地址的协议时按浏览器的方式处理：忽略开头的空白和控制字符以及任意位置的制表符和换行，
+//! 不区分大小写。字符引用在解析时已经解码，`&#106;avascript:` 同样会被识别。
+
+use markup5ever_rcdom::{Handle, NodeData};
+
+use crate::utils::url::Url;
+
+use super::dom::{get_node_attr, get_template_contents, remove_node};
+
+/// 可能包含地址的属性
+pub const URL_ATTRIBUTES: &[&str] = &[
+    "action",
+    "background",
+    "cite",
+    "codebase",
+    "data",
+    "dynsrc",
+    "formaction",
+    "href",
+    "longdesc",
+    "lowsrc",
+    "manifest",
+    "ping",
+    "poster",
+    "src",
+    "srcset",
+    "usemap",
+    "xlink:href",
+    // SVG 动画可以把 href 改成脚本地址
+    "from",
+    "to",
+    "values",
+];
+
+/// 以 data URL 加载时可以执行脚本的媒体类型
+const SCRIPTABLE_DATA_TYPES: &[&str] = &[
+    "text/html",
+    "application/xhtml+xml",
+    "image/svg+xml",
+    "text/xml",
+    "application/xml",
+];
+
+/// 净化规则，默认全部启用
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SanitizeProfile {
+    /// 删除 `<script>` 元素
+    pub remove_scripts: bool,
+    /// 删除所有 `on*` 属性
+    pub remove_event_handlers: bool,
+    /// 删除脚本地址和包含脚本的样式
+    pub remove_script_urls: bool,
+    /// 删除以 data URL 或 `srcdoc` 加载的框架文档
+    pub remove_html_frames: bool,
+    /// 删除提交到其他主机的表单地址
+    pub remove_external_form_actions: bool,
+    /// 删除插件元素
+    pub remove_plugins: bool,
+}
+
+impl Default for SanitizeProfile {
+    fn default() -> Self {
+        Self::strict()
+    }
+}
+
+impl SanitizeProfile {
+    /// 启用所有规则
+    pub fn strict() -> Self {
+        Self {
+            remove_scripts: true,
+            remove_event_handlers: true,
+            remove_script_urls: true,
+            remove_html_frames: true,
+            remove_external_form_actions: true,
+            remove_plugins: true,
+        }
+    }
+}
+
+/// 按规则净化节点及其子树
+///
+/// `document_url` 用于解析相对的表单地址，判断其是否提交到其他主机。
+///
+/// # 示例
+///
+/// ```rust
+/// use monolith::html::{html_to_dom, sanitize, SanitizeProfile};
+/// use monolith::url::Url;
+///
+/// let dom = html_to_dom(b"<a href=\" javascript:alert(1)\" onclick=\"x()\">Link</a>", "".to_string());
+/// let url = Url::parse("https://example.com/").unwrap();
+/// sanitize(&dom.document, &url, &SanitizeProfile::strict());
+/// ```
+pub fn sanitize(node: &Handle, document_url: &Url, profile: &SanitizeProfile) {
+    if let NodeData::Element {
+        ref name,
+        ref attrs,
+        ..
+    } = node.data
+    {
+        let tag_name = name.local.as_ref();
+
+        if (profile.remove_scripts && tag_name == "script")
+            || (profile.remove_plugins && matches!(tag_name, "object" | "embed" | "applet"))
+            || (profile.remove_script_urls && tag_name == "meta" && is_script_refresh(node))
+        {
+            remove_node(node);
+            return;
+        }
+
+        let is_frame = matches!(tag_name, "iframe" | "frame" | "object" | "embed");
+        attrs.borrow_mut().retain(|attr| {
+            let attr_name = attr.name.local.as_ref();
+            let value = &attr.value;
+
+            if profile.remove_event_handlers
+                && attr_name.len() > 2
+                && attr_name
+                    .as_bytes()
+                    .get(..2)
+                    .is_some_and(|prefix| prefix.eq_ignore_ascii_case(b"on"))
+            {
+                return false;
+            }
+            if profile.remove_script_urls {
+                if URL_ATTRIBUTES.contains(&attr_name) && is_script_url(value) {
+                    return false;
+                }
+                if attr_name == "style" && is_script_style(value) {
+                    return false;
+                }
+            }
+            if profile.remove_html_frames
+                && is_frame
+                && (attr_name == "srcdoc"
+                    || (matches!(attr_name, "src" | "data") && is_scriptable_data_url(value)))
+            {
+                return false;
+            }
+            if profile.remove_external_form_actions
+                && matches!(attr_name, "action" | "formaction")
+                && is_external(value, document_url)
+            {
+                return false;
+            }
+            true
+        });
+    }
+
+    // 子节点可能在遍历时被删除，先复制列表
+    let children = node.children.borrow().clone();
+    for child in children.iter() {
+        sanitize(child, document_url, profile);
+    }
+    if let Some(fragment) = get_template_contents(node) {
+        sanitize(&fragment, document_url, profile);
+    }
+}
+
+/// 按浏览器解析地址的方式规范化：去掉开头的空白和控制字符及所有制表符和换行，转为小写
+fn normalize_url(value: &str) -> String {
+    value
+        .trim_start_matches(|c: char| c <= ' ')
+        .chars()
+        .filter(|c| !matches!(c, '\t' | '\n' | '\r'))
+        .collect::<String>()
+        .to_lowercase()
+}
+
+fn is_script_url(value: &str) -> bool {
+    let value = normalize_url(value);
+    // srcset 和 SVG 动画的 values 中可以有多个地址
+    value.split([',', ';']).any(|candidate| {
+        let candidate = candidate.trim_start();
+        candidate.starts_with("javascript:") || candidate.starts_with("vbscript:")
+    })
+}
+
+fn is_scriptable_data_url(value: &str) -> bool {
+    let value = normalize_url(value);
+    match value.strip_prefix("data:") {
+        Some(rest) => {
+            let media_type = rest.split([';', ',']).next().unwrap_or("").trim();
+            SCRIPTABLE_DATA_TYPES.contains(&media_type)
+        }
+        None => false,
+    }
+}
+
+/// 样式中的脚本：旧版 IE 的 `expression()`、`behavior` 以及 `url(javascript:...)`
+fn is_script_style(value: &str) -> bool {
+    let value: String = normalize_url(value)
+        .chars()
+        .filter(|c| !c.is_whitespace() && !matches!(c, '"' | '\'' | '\\'))
+        .collect();
+    value.contains("expression(")
+        || value.contains("behavior:")
+        || value.contains("-moz-binding")
+        || value.contains("javascript:")
+        || value.contains("vbscript:")
+}
+
+/// 跳转到脚本或 data 地址的 `<meta http-equiv="refresh">`
+fn is_script_refresh(node: &Handle) -> bool {
+    if !get_node_attr(node, "http-equiv").is_some_and(|value| value.eq_ignore_ascii_case("refresh"))
+    {
+        return false;
+    }
+    let content: String = normalize_url(&get_node_attr(node, "content").unwrap_or_default())
+        .chars()
+        .filter(|c| !c.is_whitespace() && !matches!(c, '"' | '\''))
+        .collect();
+    content.contains("javascript:") || content.contains("vbscript:") || content.contains("data:")
+}
+
+/// 表单地址是否指向文档所在主机之外（无法解析的地址同样视为外部地址）
+fn is_external(value: &str, document_url: &Url) -> bool {
+    if value.trim().is_empty() {
+        return false;
+    }
+    match document_url.join(value.trim()) {
+        Ok(action_url) => {
+            action_url.scheme() != document_url.scheme()
+                || action_url.host_str() != document_url.host_str()
+                || action_url.port_or_known_default() != document_url.port_or_known_default()
+        }
+        Err(_) => true,
+    }
+}
@@ -8,7 +8,9 @@
 //!
 //! 序列化过程会考虑各种安全和隔离选项，确保生成的 HTML 文档符合用户的配置要求。
 
-use encoding_rs::Encoding;
+use std::io::{self, Write};
+
+use encoding_rs::{CoderResult, Encoder, Encoding, UTF_8};
 use html5ever::interface::{Attribute, QualName};
 use html5ever::serialize::{serialize, SerializeOpts};
 use html5ever::tendril::format_tendril;
@@ -17,9 +19,10 @@ use html5ever::{namespace_url, ns, LocalName};
 use markup5ever_rcdom::{RcDom, SerializableHandle};
 use regex::Regex;
 
-use crate::core::MonolithOptions;
+use crate::core::{print_info_message, MonolithOptions};
 
-use super::dom::get_child_node_by_name;
+use super::dom::{get_child_node_by_name, inline_template_contents};
+use super::minify::{minify_dom, unquote_attributes};
 use super::utils::compose_csp;
 
 /// 将 HTML DOM 树序列化为字节数组
@@ -48,6 +51,10 @@ use super::utils::compose_csp;
 /// - `no_js`: 禁用 JavaScript
 /// - `no_images`: 禁用图片
 ///
+/// ## 压缩
+/// 当 `minify` 选项启用时，折叠空白、删除注释（除非启用 `minify_preserve_comments`）
+/// 并去掉多余的属性引号，非静默模式下输出压缩前后的字节数
+///
 /// ## NOSCRIPT 处理
 /// 当 `unwrap_noscript` 选项启用时，会将 `<noscript>` 标签转换为 HTML 注释
 ///
@@ -74,6 +81,41 @@ pub fn serialize_document(
 ) -> Vec<u8> {
     // 初始化用于存储序列化结果的字节缓冲区
     let mut buf: Vec<u8> = Vec::new();
+    serialize_document_to_writer(dom, document_encoding, options, &mut buf)
+        .expect("无法将 DOM 序列化到缓冲区");
+
+    // 返回最终的序列化结果
+    buf
+}
+
+/// 将 HTML DOM 树直接序列化到写入器
+///
+/// 与 [`serialize_document`] 的处理相同，但序列化结果边生成边写出，
+/// 不会先在内存中拼出整个文档；需要转换字符编码时同样逐段编码后写出。
+/// 保存大页面时可以直接写入文件或网络连接，降低内存峰值。
+///
+/// 启用 `minify` 或 `unwrap_noscript` 时需要对完整文档做处理，仍会先缓冲整个文档。
+///
+/// # 示例
+///
+/// ```rust
+/// use monolith::core::MonolithOptions;
+/// use monolith::html::{html_to_dom, serialize_document_to_writer};
+///
+/// let dom = html_to_dom(b"<p>Hello</p>", "".to_string());
+/// let mut file: Vec<u8> = Vec::new();
+/// serialize_document_to_writer(dom, "".to_string(), &MonolithOptions::default(), &mut file)
+///     .unwrap();
+/// assert_eq!(file, b"<html><head></head><body><p>Hello</p></body></html>");
+/// ```
+pub fn serialize_document_to_writer<W: Write>(
+    dom: RcDom,
+    document_encoding: String,
+    options: &MonolithOptions,
+    writer: &mut W,
+) -> io::Result<()> {
+    // 序列化器不输出 <template> 的内容片段，先把其中的节点移到元素下
+    inline_template_contents(&dom.document);
 
     // 检查是否需要添加内容安全策略 (CSP)
     // 当启用任何资源限制选项时，都需要添加相应的 CSP 规则
@@ -84,38 +126,102 @@ pub fn serialize_document(
         || options.no_js
         || options.no_images
     {
-        // 处理内容安全策略 (CSP) 的添加
-        if let Some(html) = get_child_node_by_name(&dom.document, "html") {
-            if let Some(head) = get_child_node_by_name(&html, "head") {
-                // 创建 CSP meta 元素
-                let meta = create_element(
-                    &dom,
-                    QualName::new(None, ns!(), LocalName::from("meta")),
-                    vec![
-                        Attribute {
-                            name: QualName::new(None, ns!(), LocalName::from("http-equiv")),
-                            value: format_tendril!("Content-Security-Policy"),
-                        },
-                        Attribute {
-                            name: QualName::new(None, ns!(), LocalName::from("content")),
-                            value: format_tendril!("{}", compose_csp(options)),
-                        },
-                    ],
-                );
-                // CSP meta 标签必须被前置添加，绝不能追加到末尾
-                // 因为原始文档中可能已经定义了一个 CSP 标签，
-                // 而浏览器不允许重新定义它们（出于明显的安全原因）
-                head.children.borrow_mut().reverse();
-                head.children.borrow_mut().push(meta.clone());
-                head.children.borrow_mut().reverse();
+        add_csp_meta(&dom, options);
+    }
+
+    // 如果指定了特定的字符编码，输出需要转换为该编码格式（UTF-8 无需转换）
+    let encoding = Some(document_encoding)
+        .filter(|label| !label.is_empty())
+        .and_then(|label| Encoding::for_label(label.as_bytes()))
+        .filter(|encoding| encoding.output_encoding() != UTF_8);
+
+    if options.minify || options.unwrap_noscript {
+        let buf = serialize_with_rewrites(dom, options)?;
+        return match encoding {
+            Some(encoding) => {
+                // 使用指定编码重新编码文档内容
+                let s: &str = &String::from_utf8_lossy(&buf);
+                let (data, _, _) = encoding.encode(s);
+                writer.write_all(&data)
             }
-        }
+            None => writer.write_all(&buf),
+        };
     }
 
     // 将 DOM 转换为可序列化的句柄并执行序列化
     let serializable: SerializableHandle = dom.document.into();
-    serialize(&mut buf, &serializable, SerializeOpts::default())
-        .expect("无法将 DOM 序列化到缓冲区");
+    match encoding {
+        Some(encoding) => {
+            let mut encoding_writer = EncodingWriter::new(writer, encoding);
+            serialize(
+                &mut encoding_writer,
+                &serializable,
+                SerializeOpts::default(),
+            )?;
+            encoding_writer.finish()
+        }
+        None => serialize(writer, &serializable, SerializeOpts::default()),
+    }
+}
+
+/// 在 HEAD 开头添加 CSP meta 元素
+fn add_csp_meta(dom: &RcDom, options: &MonolithOptions) {
+    if let Some(html) = get_child_node_by_name(&dom.document, "html") {
+        if let Some(head) = get_child_node_by_name(&html, "head") {
+            // 创建 CSP meta 元素
+            let meta = create_element(
+                dom,
+                QualName::new(None, ns!(), LocalName::from("meta")),
+                vec![
+                    Attribute {
+                        name: QualName::new(None, ns!(), LocalName::from("http-equiv")),
+                        value: format_tendril!("Content-Security-Policy"),
+                    },
+                    Attribute {
+                        name: QualName::new(None, ns!(), LocalName::from("content")),
+                        value: format_tendril!("{}", compose_csp(options)),
+                    },
+                ],
+            );
+            // CSP meta 标签必须被前置添加，绝不能追加到末尾
+            // 因为原始文档中可能已经定义了一个 CSP 标签，
+            // 而浏览器不允许重新定义它们（出于明显的安全原因）
+            head.children.borrow_mut().reverse();
+            head.children.borrow_mut().push(meta.clone());
+            head.children.borrow_mut().reverse();
+        }
+    }
+}
+
+/// 序列化到内存，并执行需要完整文档的压缩和 NOSCRIPT 展开
+fn serialize_with_rewrites(dom: RcDom, options: &MonolithOptions) -> io::Result<Vec<u8>> {
+    let serializable: SerializableHandle = dom.document.clone().into();
+
+    // 压缩前的大小只用于输出信息，安静模式下不计算
+    let original_size = if options.minify && !options.silent {
+        let mut counter = ByteCounter(0);
+        serialize(&mut counter, &serializable, SerializeOpts::default())?;
+        Some(counter.0)
+    } else {
+        None
+    };
+
+    // 压缩文档：在 DOM 上折叠空白和删除注释后序列化，再去掉多余的属性引号
+    if options.minify {
+        minify_dom(&dom.document, options.minify_preserve_comments);
+    }
+    let mut buf: Vec<u8> = Vec::new();
+    serialize(&mut buf, &serializable, SerializeOpts::default())?;
+    if options.minify {
+        buf = unquote_attributes(&String::from_utf8_lossy(&buf)).into_bytes();
+    }
+    if let Some(original_size) = original_size {
+        print_info_message(&format!(
+            "Minified document: {} -> {} bytes",
+            original_size,
+            buf.len()
+        ));
+    }
 
     // 处理 NOSCRIPT 元素的展开
     // 将 <noscript> 标签转换为 HTML 注释，以便在禁用 JavaScript 的环境中显示内容
@@ -127,17 +233,82 @@ pub fn serialize_document(
         buf = noscript_re.replace_all(s, "<!--$c-->").as_bytes().to_vec();
     }
 
-    // 处理文档字符编码转换
-    // 如果指定了特定的字符编码，将输出转换为该编码格式
-    if !document_encoding.is_empty() {
-        if let Some(encoding) = Encoding::for_label(document_encoding.as_bytes()) {
-            let s: &str = &String::from_utf8_lossy(&buf);
-            // 使用指定编码重新编码文档内容
-            let (data, _, _) = encoding.encode(s);
-            buf = data.to_vec();
+    Ok(buf)
+}
+
+/// 只统计写入字节数的写入器
+struct ByteCounter(usize);
+
+impl Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// 把写入的 UTF-8 数据逐段转换为指定编码的写入器
+///
+/// 无法用目标编码表示的字符按 HTML 数字字符引用输出，与 [`Encoding::encode`] 一致。
+struct EncodingWriter<'a, W: Write> {
+    inner: &'a mut W,
+    encoder: Encoder,
+    /// 尚未凑成完整 UTF-8 字符的尾部字节
+    pending: Vec<u8>,
+}
+
+impl<'a, W: Write> EncodingWriter<'a, W> {
+    fn new(inner: &'a mut W, encoding: &'static Encoding) -> Self {
+        Self {
+            inner,
+            encoder: encoding.new_encoder(),
+            pending: Vec::new(),
         }
     }
 
-    // 返回最终的序列化结果
-    buf
+    fn encode(&mut self, input: &str, last: bool) -> io::Result<()> {
+        let mut output = [0u8; 8192];
+        let mut input = input;
+        loop {
+            let (result, read, written, _) =
+                self.encoder.encode_from_utf8(input, &mut output, last);
+            self.inner.write_all(&output[..written])?;
+            input = &input[read..];
+            if result == CoderResult::InputEmpty {
+                return Ok(());
+            }
+        }
+    }
+
+    /// 写出剩余数据
+    fn finish(mut self) -> io::Result<()> {
+        let rest = String::from_utf8_lossy(&std::mem::take(&mut self.pending)).into_owned();
+        self.encode(&rest, true)?;
+        self.inner.flush()
+    }
+}
+
+impl<W: Write> Write for EncodingWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+        let valid_up_to = match std::str::from_utf8(&self.pending) {
+            Ok(text) => text.len(),
+            Err(error) => error.valid_up_to(),
+        };
+        if valid_up_to > 0 {
+            let pending = std::mem::take(&mut self.pending);
+            // from_utf8 已确认前 valid_up_to 个字节是合法的 UTF-8
+            let text = std::str::from_utf8(&pending[..valid_up_to]).unwrap();
+            self.encode(text, false)?;
+            self.pending = pending[valid_up_to..].to_vec();
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
 }
@@ -9,6 +9,9 @@
 //! 这些工具函数被 HTML 解析器的其他模块广泛使用，用于处理网页资源的识别、
 //! 安全策略的制定以及文本格式的标准化。
 
+use base64::{prelude::BASE64_STANDARD, Engine};
+use sha2::{Digest, Sha256};
+
 use crate::core::MonolithOptions;
 
 /// Favicon 相关的属性值定义
@@ -34,6 +37,21 @@ pub const FAVICON_VALUES: &[&str] = &["icon", "shortcut icon"];
 /// - `'\r'`: 回车符
 pub const WHITESPACES: &[char] = &[' ', '\t', '\n', '\x0c', '\r'];
 
+/// 不包含可读文本的 HTML 元素列表
+/// 
+/// 这些元素通常包含代码、样式、元数据或功能性内容。翻译文本收集和
+/// 纯文本输出都会跳过这些元素及其子树。
+pub const SKIP_ELEMENTS: &[&str] = &[
+    "script", "style", "code", "pre",      // 代码和样式相关
+    "noscript", "meta", "link", "head",    // 元数据相关
+    "svg", "math",                         // 图形和数学公式
+    "canvas", "video", "audio",            // 媒体元素
+    "embed", "object", "iframe",           // 嵌入内容
+    "map", "area", "base",                 // 映射和基础元素
+    "br", "hr", "img", "input",            // 空元素和表单
+    "source", "track", "wbr",              // 媒体资源和换行
+];
+
 /// 检查给定的属性值是否表示 favicon
 /// 
 /// 此函数用于识别 HTML `<link>` 标签的 `rel` 属性值是否指向网站图标。
@@ -71,7 +89,8 @@ pub fn is_favicon(attr_value: &str) -> bool {
 /// 
 /// # 生成的 CSP 规则
 /// - `isolate`: 设置默认源为 `'unsafe-eval' 'unsafe-inline' data:`
-/// - `no_css`: 禁用样式表加载 (`style-src 'none'`)
+/// - `no_css`: 禁用样式表加载 (`style-src 'none'`)；设置了 `extra_css` 时
+///   改为只允许这段样式 (`style-src 'sha256-...'`)
 /// - `no_fonts`: 禁用字体加载 (`font-src 'none'`)
 /// - `no_frames`: 禁用框架加载 (`frame-src 'none'; child-src 'none'`)
 /// - `no_js`: 禁用 JavaScript 执行 (`script-src 'none'`)
@@ -95,6 +114,7 @@ pub fn is_favicon(attr_value: &str) -> bool {
 /// assert!(csp.contains("style-src 'none'"));
 /// ```
 pub fn compose_csp(options: &MonolithOptions) -> String {
+    let extra_css_policy;
     let mut string_list = vec![];
 
     // 如果启用隔离模式，设置宽松的默认源策略
@@ -104,7 +124,17 @@ pub fn compose_csp(options: &MonolithOptions) -> String {
 
     // 如果禁用 CSS，则完全阻止样式表加载
     if options.no_css {
-        string_list.push("style-src 'none';");
+        match &options.extra_css {
+            // 自定义样式不受 no_css 影响，用哈希单独放行
+            Some(css) => {
+                extra_css_policy = format!(
+                    "style-src 'sha256-{}';",
+                    BASE64_STANDARD.encode(Sha256::digest(css.as_bytes()))
+                );
+                string_list.push(extra_css_policy.as_str());
+            }
+            None => string_list.push("style-src 'none';"),
+        }
     }
 
     // 如果禁用字体，则阻止字体资源加载
@@ -132,3 +162,55 @@ pub fn compose_csp(options: &MonolithOptions) -> String {
     // 将所有策略规则用空格连接成完整的 CSP 字符串
     string_list.join(" ")
 }
+
+/// 清空 JSON 中所有字符串值，保留键名和结构
+///
+/// 用作 [`JsonScriptPolicy::Redact`](crate::core::JsonScriptPolicy::Redact) 的默认回调：
+/// 页面状态中的文本（用户名、邮箱、令牌等）被替换为空字符串，数字、布尔值和
+/// `null` 保持不变。不要求输入是合法的 JSON，无法识别的部分原样输出。
+///
+/// # 示例
+/// ```rust
+/// use monolith::html::redact_json_strings;
+///
+/// assert_eq!(
+///     redact_json_strings(r#"{"user": {"email": "a@b.c", "id": 7}, "tags": ["x\"y"]}"#),
+///     r#"{"user": {"email": "", "id": 7}, "tags": [""]}"#
+/// );
+/// ```
+pub fn redact_json_strings(json: &str) -> String {
+    let mut result = String::with_capacity(json.len());
+    let mut chars = json.char_indices();
+
+    while let Some((start, c)) = chars.next() {
+        if c != '"' {
+            result.push(c);
+            continue;
+        }
+
+        // 读取完整的字符串字面量（处理转义）
+        let mut end = json.len();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '\\' => {
+                    chars.next();
+                }
+                '"' => {
+                    end = i + 1;
+                    break;
+                }
+                _ => {}
+            }
+        }
+
+        // 后面紧跟冒号的是键名，保留
+        let is_key = json[end..].trim_start().starts_with(':');
+        if is_key {
+            result.push_str(&json[start..end]);
+        } else {
+            result.push_str("\"\"");
+        }
+    }
+
+    result
+}
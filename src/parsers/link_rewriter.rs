@@ -9,6 +9,8 @@
 //! - 智能跳过不需要重写的特殊链接（如锚点链接、JavaScript链接、邮件链接等）
 //! - 将相对URL转换为绝对URL，然后重写为指向翻译服务的路径
 //! - 提供URL编码功能，确保重写后的链接能够正确传递给翻译服务
+//! - 保留链接中的片段（`#section`）：片段不编码进路径，而是附加在翻译服务链接之后，
+//!   浏览器打开翻译后的页面时仍然定位到对应的位置
 //!
 //! ## 使用场景
 //!
@@ -163,6 +165,56 @@ pub fn rewrite_links_in_html(
     Ok(String::from_utf8_lossy(&serialized).to_string())
 }
 
+/// 收集DOM树中所有指向其他页面的链接
+///
+/// 与链接重写使用相同的规则：遍历 `<a>` 的 `href` 和 `<form>` 的 `action`，
+/// 跳过锚点、JavaScript、邮件等特殊链接，相对链接基于 `base_url` 解析为绝对URL。
+/// 只保留 HTTP/HTTPS 链接，按文档顺序去重后返回。
+///
+/// # 示例
+///
+/// ```rust
+/// use monolith::html::html_to_dom;
+/// use monolith::parsers::link_rewriter::collect_links_in_dom;
+/// use monolith::url::Url;
+///
+/// let dom = html_to_dom(b"<a href=\"/about\">About</a><a href=\"#top\">Top</a>", "".to_string());
+/// let base_url = Url::parse("https://example.com/index.html").unwrap();
+/// let links = collect_links_in_dom(&dom.document, &base_url);
+/// assert_eq!(links, vec![Url::parse("https://example.com/about").unwrap()]);
+/// ```
+pub fn collect_links_in_dom(node: &Handle, base_url: &Url) -> Vec<Url> {
+    let mut links = Vec::new();
+    walk_and_collect_links(node, base_url, &mut links);
+    links
+}
+
+fn walk_and_collect_links(node: &Handle, base_url: &Url, links: &mut Vec<Url>) {
+    if let NodeData::Element { ref name, .. } = node.data {
+        let link = match name.local.as_ref() {
+            "a" => get_node_attr(node, "href"),
+            "form" => get_node_attr(node, "action"),
+            _ => None,
+        };
+
+        if let Some(link) = link {
+            let trimmed_link = link.trim();
+            if !should_skip_link(trimmed_link) {
+                let absolute_url = resolve_url(base_url, trimmed_link);
+                if (absolute_url.scheme() == "http" || absolute_url.scheme() == "https")
+                    && !links.contains(&absolute_url)
+                {
+                    links.push(absolute_url);
+                }
+            }
+        }
+    }
+
+    for child_node in node.children.borrow().iter() {
+        walk_and_collect_links(child_node, base_url, links);
+    }
+}
+
 /// 递归遍历DOM树并重写链接
 ///
 /// 这是一个内部辅助函数，用于递归遍历DOM树的每个节点，识别包含链接的元素并进行重写。
@@ -342,8 +394,8 @@ fn should_skip_link(href: &str) -> bool {
 ///
 /// 1. **URL解析** - 使用基础URL将输入URL转换为绝对URL
 /// 2. **协议检查** - 只处理HTTP和HTTPS协议的URL，其他协议（如FTP）会被跳过
-/// 3. **URL编码** - 对绝对URL进行percent编码，确保能安全地作为路径参数传递
-/// 4. **路径构建** - 将编码后的URL拼接到翻译服务基础路径后面
+/// 3. **URL编码** - 对去掉片段的绝对URL（包括查询字符串）进行percent编码，确保能安全地作为路径参数传递
+/// 4. **路径构建** - 将编码后的URL拼接到翻译服务基础路径后面，原链接的片段附加在最后
 ///
 /// # 示例
 ///
@@ -351,17 +403,28 @@ fn should_skip_link(href: &str) -> bool {
 /// let base_url = "https://example.com/page".parse().unwrap();
 /// let result = rewrite_url("/relative/path", &base_url, "/translate/");
 /// // 结果: Some("/translate/https%3A%2F%2Fexample.com%2Frelative%2Fpath")
+///
+/// let result = rewrite_url("/doc?lang=en#intro", &base_url, "/translate/");
+/// // 结果: Some("/translate/https%3A%2F%2Fexample.com%2Fdoc%3Flang%3Den#intro")
 /// ```
 fn rewrite_url(url: &str, base_url: &Url, translation_base_path: &str) -> Option<String> {
     // 使用基础URL解析为绝对URL
-    let absolute_url = match resolve_url(base_url, url) {
+    let mut absolute_url = match resolve_url(base_url, url) {
         url if url.scheme() == "http" || url.scheme() == "https" => url,
         _ => return None, // 跳过非HTTP(S)协议的链接
     };
 
+    // 片段只在浏览器中使用，作为翻译服务链接自身的片段保留
+    let fragment = absolute_url
+        .fragment()
+        .filter(|fragment| !fragment.is_empty())
+        .map(|fragment| format!("#{fragment}"))
+        .unwrap_or_default();
+    absolute_url.set_fragment(None);
+
     // 对绝对URL进行percent编码并构建翻译服务链接
     let encoded_url = utf8_percent_encode(absolute_url.as_str(), URL_ENCODE_SET);
-    Some(format!("{translation_base_path}{encoded_url}"))
+    Some(format!("{translation_base_path}{encoded_url}{fragment}"))
 }
 
 #[cfg(test)]
@@ -414,12 +477,71 @@ mod tests {
         assert_eq!(result, None);
     }
 
+    /// 测试片段和查询字符串的保留
+    ///
+    /// 验证片段附加在翻译服务链接之后，打开链接时仍然定位到原来的位置
+    #[test]
+    fn test_rewrite_url_keeps_fragment() {
+        let base_url: Url = "https://example.com/page".parse().unwrap();
+        let proxy: Url = "http://localhost:7080/".parse().unwrap();
+
+        let result = rewrite_url("/doc#intro", &base_url, "/website/").unwrap();
+        assert_eq!(result, "/website/https%3A%2F%2Fexample.com%2Fdoc#intro");
+        let resolved = proxy.join(&result).unwrap();
+        assert_eq!(resolved.fragment(), Some("intro"));
+        assert_eq!(
+            percent_encoding::percent_decode_str(&resolved.path()["/website/".len()..])
+                .decode_utf8()
+                .unwrap(),
+            "https://example.com/doc"
+        );
+
+        // 查询字符串编码进路径，片段仍在最后
+        let result = rewrite_url("/search?q=a&page=2#results", &base_url, "/website/").unwrap();
+        assert_eq!(
+            result,
+            "/website/https%3A%2F%2Fexample.com%2Fsearch%3Fq%3Da%26page%3D2#results"
+        );
+
+        // 空片段不保留
+        let result = rewrite_url("/doc#", &base_url, "/website/").unwrap();
+        assert_eq!(result, "/website/https%3A%2F%2Fexample.com%2Fdoc");
+    }
+
+    /// 测试链接收集功能
+    ///
+    /// 验证相对链接被解析、特殊链接被跳过且重复链接只保留一次
+    #[test]
+    fn test_collect_links_in_dom() {
+        use crate::parsers::html::html_to_dom;
+
+        let html = "<a href=\"https://other.com/page\">A</a><a href=\"/relative\">B</a>\
+                    <a href=\"#section\">C</a><a href=\"mailto:a@b.c\">D</a>\
+                    <a href=\"https://other.com/page\">E</a><form action=\"/search\"></form>";
+        let dom = html_to_dom(html.as_bytes(), "utf-8".to_string());
+        let base_url: Url = "https://example.com/page".parse().unwrap();
+
+        let links: Vec<String> = collect_links_in_dom(&dom.document, &base_url)
+            .into_iter()
+            .map(|url| url.to_string())
+            .collect();
+
+        assert_eq!(
+            links,
+            vec![
+                "https://other.com/page",
+                "https://example.com/relative",
+                "https://example.com/search"
+            ]
+        );
+    }
+
     /// 测试HTML字符串链接重写功能
     ///
     /// 验证完整的HTML处理流程，包括解析、重写和序列化
     #[test]
     fn test_rewrite_links_in_html() {
-        let html = "<html><body><a href=\"https://example.com/page\">External Link</a><a href=\"/relative\">Relative Link</a><a href=\"#section\">Anchor Link</a><a href=\"javascript:void(0)\">JS Link</a></body></html>";
+        let html = "<html><body><a href=\"https://example.com/page\">External Link</a><a href=\"/relative\">Relative Link</a><a href=\"#section\">Anchor Link</a><a href=\"javascript:void(0)\">JS Link</a><a href=\"/doc#intro\">Deep Link</a></body></html>";
 
         let result = rewrite_links_in_html(html, "https://test.com", None).unwrap();
 
@@ -427,6 +549,8 @@ mod tests {
         assert!(result.contains("/website/https%3A%2F%2Fexample.com%2Fpage"));
         // 验证相对链接被正确重写
         assert!(result.contains("/website/https%3A%2F%2Ftest.com%2Frelative"));
+        // 验证链接的片段被保留
+        assert!(result.contains("href=\"/website/https%3A%2F%2Ftest.com%2Fdoc#intro\""));
         // 验证锚点链接保持不变
         assert!(result.contains("href=\"#section\""));
         // 验证JavaScript链接保持不变
@@ -136,6 +136,7 @@ pub use js::attr_is_event_handler;
 
 // 链接重写模块的重新导出 - 提供链接转换功能
 pub use link_rewriter::{
+    collect_links_in_dom,  // 收集DOM树中指向其他页面的链接
     rewrite_links_in_dom,  // 重写DOM树中的所有链接
     rewrite_links_in_html, // 重写HTML字符串中的所有链接
 };
@@ -39,13 +39,16 @@
 //! let zh_config = manager.create_simple_config("zh", None)?;
 //! ```
 
+use std::collections::HashMap;
 use std::path::Path;
 use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 
 use super::constants;
+use super::template::RequestTemplate;
 use crate::translation::error::{TranslationError, TranslationResult};
+use crate::translation::pipeline::collector::primary_subtag;
 
 /// 翻译功能配置结构体
 /// 
@@ -74,17 +77,88 @@ use crate::translation::error::{TranslationError, TranslationResult};
 /// # 批次配置
 /// batch_size = 20
 /// min_batch_chars = 100
+/// # preferred_batch_chars = 1000
 /// batch_timeout_secs = 30
 ///
 /// # 缓存配置
 /// cache_enabled = true
 /// cache_ttl_secs = 3600
 /// local_cache_size = 1000
+/// # 合并同时发出的相同翻译请求
+/// coalesce_requests = true
 ///
 /// # 功能开关
 /// parallel_enabled = true
 /// retry_enabled = true
 /// max_retry_attempts = 3
+/// best_effort = false
+///
+/// # 单次文档翻译最多发送的字符数（可选，包括重试）
+/// # max_chars_per_request_job = 200000
+///
+/// # 写回前检查译文，长度比例异常或语言不符的译文保留原文
+/// verify_translations = false
+/// min_length_ratio = 0.1
+/// max_length_ratio = 10.0
+///
+/// # 双语显示
+/// show_original = false
+/// bilingual = false
+///
+/// # 是否翻译 <template> 中的文本
+/// translate_templates = false
+///
+/// # 是否翻译 og:title、og:description 等社交分享元数据
+/// translate_social_meta = false
+///
+/// # 是否翻译 <canvas> 中的后备内容
+/// translate_canvas_fallback = false
+///
+/// # 是否翻译 <pre> 中的散文和代码中的注释
+/// translate_preformatted = false
+///
+/// # 翻译 <script type="application/json"> 数据块中这些路径处的字符串
+/// translate_json_paths = []
+///
+/// # 除 title、alt、placeholder 等之外还要翻译的属性，例如组件使用的 data-label
+/// translate_attributes = []
+///
+/// # 是否重新翻译带有已翻译标记的页面
+/// retranslate = false
+///
+/// # 译文写入 <html lang> 和 Content-Language 的语言，默认为目标语言的主语言子标签（zh-CN 写为 zh）
+/// # html_lang = "zh-CN"
+///
+/// # 是否在 <head> 中写入描述翻译来源的 JSON-LD
+/// embed_provenance = false
+///
+/// # 判断页面语言时抽取的最长文本段数，0 表示不判断
+/// language_sample_size = 50
+///
+/// # 自定义接口（可选，详见 `template` 模块）
+/// # [request_template]
+/// # body = '{"q": "{{text}}", "target": "{{target}}"}'
+/// # response_path = "translatedText"
+///
+/// # 依次尝试的后端（可选），设置后取代 api_url 和 request_template
+/// # [[backends]]
+/// # name = "deepl"
+/// # api_url = "http://localhost:1188/translate"
+/// #
+/// # [[backends]]
+/// # name = "libretranslate"
+/// # api_url = "http://localhost:5000/translate"
+/// # request_template = { body = '{"q": "{{text}}", "target": "{{target}}"}', response_path = "translatedText" }
+///
+/// # 多语言同时翻译时各语言的并发权重（可选）
+/// # [language_concurrency_weights]
+/// # zh = 3
+/// # ja = 1
+///
+/// # 按域名覆盖缓存有效期（秒，可选），同样适用于子域名
+/// # [cache_domain_ttl_secs]
+/// # "news.example.com" = 600
+/// # "docs.rs" = 604800
 /// ```
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct TranslationConfig {
@@ -107,6 +181,7 @@ pub struct TranslationConfig {
     /// 
     /// 使用 "auto" 表示自动检测源语言，
     /// 或者指定具体的语言代码如 "en"、"ja" 等。
+    /// 指定后不再抽样检测页面语言，源语言随请求一起发送给翻译接口。
     pub source_lang: String,
     
     /// 翻译 API 的基础 URL
@@ -150,6 +225,14 @@ pub struct TranslationConfig {
     /// 只有当待翻译文本总字符数超过此阈值时，
     /// 才会触发批处理模式。
     pub min_batch_chars: usize,
+
+    /// 首选的批次字符数
+    ///
+    /// 设置后批次在接近该大小时结束，而不是一直填充到 `batch_size`：加入下一段文本会让批次
+    /// 比现在更偏离首选大小时，先结束当前批次。适合长输入翻译质量下降的翻译服务。
+    /// `None` 表示不启用。
+    #[serde(default)]
+    pub preferred_batch_chars: Option<usize>,
     
     /// 批处理超时时间（秒）
     /// 
@@ -173,6 +256,20 @@ pub struct TranslationConfig {
     /// 导致内存泄漏。
     pub local_cache_size: usize,
 
+    /// 按域名覆盖的缓存有效期（秒）
+    ///
+    /// 新闻站点的内容变化快，文档站点很少变化。列出的主机名（及其子域名）上页面的译文
+    /// 使用这里的有效期，其余页面使用 `cache_ttl_secs`。
+    #[serde(default)]
+    pub cache_domain_ttl_secs: HashMap<String, u64>,
+
+    /// 是否合并同时发出的相同翻译请求
+    ///
+    /// 共享缓存的多个请求同时翻译相同的文本（例如多人同时打开一个尚未缓存的热门页面）时，
+    /// 只有第一个请求发送给翻译服务，其余请求等待并使用同一个结果。
+    #[serde(default = "default_coalesce_requests")]
+    pub coalesce_requests: bool,
+
     /// 是否启用并行翻译
     /// 
     /// 启用后多个翻译任务可以并行执行，提高整体翻译速度。
@@ -189,6 +286,167 @@ pub struct TranslationConfig {
     /// 翻译请求失败时的最大重试次数。只有在 `retry_enabled` 
     /// 为 `true` 时才会生效。
     pub max_retry_attempts: usize,
+
+    /// 是否以尽力而为模式翻译
+    ///
+    /// 启用后个别批次重试后仍失败时不再中止整个翻译：其余译文照常写回，
+    /// 失败的文本保持原文，数量和样本记录在服务统计中。
+    #[serde(default)]
+    pub best_effort: bool,
+
+    /// 单次文档翻译的字符预算
+    ///
+    /// 一次 `translate_dom` 发送给翻译服务的字符总数（包括重试和回退到逐个翻译的请求）
+    /// 达到上限时停止发送，已完成的译文照常写回，其余文本保持原文。`None` 表示不限制。
+    #[serde(default)]
+    pub max_chars_per_request_job: Option<usize>,
+
+    /// 是否在写回前检查译文
+    ///
+    /// 翻译接口偶尔会返回空文本、"API quota exceeded" 之类的错误信息或未翻译的原文。
+    /// 开启后长度与原文相比异常或文字不属于目标语言的译文被拒绝，保持原文并计入服务统计。
+    #[serde(default)]
+    pub verify_translations: bool,
+
+    /// 译文与原文字符数之比的下限，用于 `verify_translations`
+    #[serde(default = "default_min_length_ratio")]
+    pub min_length_ratio: f32,
+
+    /// 译文与原文字符数之比的上限，用于 `verify_translations`
+    #[serde(default = "default_max_length_ratio")]
+    pub max_length_ratio: f32,
+
+    /// 各目标语言的并发权重
+    ///
+    /// 多个目标语言共享同一个翻译服务时，按权重把 `max_concurrent_requests` 分给各语言，
+    /// 权重高的语言可以同时进行更多请求，总并发数不超过 `max_concurrent_requests`。
+    /// 未列出的语言只受总上限约束；为空时不启用（见 `LanguageConcurrency`）。
+    #[serde(default)]
+    pub language_concurrency_weights: HashMap<String, usize>,
+
+    /// 是否在译文上保留原文提示
+    ///
+    /// 启用后原文保存在元素的 `data-original` 属性中，鼠标悬停时显示。
+    #[serde(default)]
+    pub show_original: bool,
+
+    /// 是否输出双语对照段落
+    ///
+    /// 启用后每个已翻译的段落之后插入一份淡化显示的原文副本。
+    #[serde(default)]
+    pub bilingual: bool,
+
+    /// 是否翻译 `<template>` 内容中的文本
+    ///
+    /// 模板内容由脚本按需插入页面，其中常含占位符和标记片段，默认不翻译。
+    #[serde(default)]
+    pub translate_templates: bool,
+
+    /// 是否翻译社交分享元数据
+    ///
+    /// 开启后 `<head>` 中 `og:title`、`og:description` 及对应的 `twitter:*` 标签的内容一并翻译，
+    /// 分享归档页面时预览与正文使用同一种语言。
+    #[serde(default)]
+    pub translate_social_meta: bool,
+
+    /// 是否翻译 `<canvas>` 的后备内容
+    ///
+    /// 后备内容只在不支持画布时显示，默认与画布一起跳过；面向无障碍的归档可以开启，
+    /// 使屏幕阅读器读到的说明文字同样被翻译。
+    #[serde(default)]
+    pub translate_canvas_fallback: bool,
+
+    /// 是否翻译 `<pre>` 中的散文和 `<code>` 中的注释
+    ///
+    /// 默认整体跳过 `<pre>` 和 `<code>`。开启后不像代码的 `<pre>`（诗歌、文字表格）逐行翻译，
+    /// 代码只翻译注释行，缩进和换行保持不变。
+    #[serde(default)]
+    pub translate_preformatted: bool,
+
+    /// 翻译 JSON 数据块中的字符串
+    ///
+    /// Next.js、Nuxt 等框架的页面文字由 `<script type="application/json">` 中的数据在客户端渲染，
+    /// 只翻译 DOM 时保留脚本的归档仍会显示原文。列出的路径（如 `props.pageProps.title`）处的字符串
+    /// 会被翻译后写回，数据的其余部分保持不变。
+    #[serde(default)]
+    pub translate_json_paths: Vec<String>,
+
+    /// 额外翻译的属性
+    ///
+    /// 一些嵌入的组件用 `data-label`、`data-i18n` 之类的属性保存显示的文字，脚本运行时再渲染出来。
+    /// 列出的属性与内置的可翻译属性（`title`、`alt` 等）一样收集，译文写回原属性。
+    #[serde(default)]
+    pub translate_attributes: Vec<String>,
+
+    /// 是否重新翻译已经翻译过的页面
+    ///
+    /// 翻译结果带有 `<meta name="monolith-translated">` 标记，默认情况下标记语言与目标语言
+    /// 相同的页面不再翻译，避免对译文再翻译一遍。
+    #[serde(default)]
+    pub retranslate: bool,
+
+    /// 译文的语言标签
+    ///
+    /// 翻译后写入根元素的 `lang` 属性，Web 服务器返回译文时作为 `Content-Language`。
+    /// 不设置时使用目标语言的主语言子标签，见 [`document_lang`](Self::document_lang)。
+    #[serde(default)]
+    pub html_lang: Option<String>,
+
+    /// 是否在译文中写入翻译来源信息
+    ///
+    /// 开启后，翻译完成的页面在 `<head>` 中带有记录语言、后端、时间和字符数的 JSON-LD，
+    /// 见 [`provenance`](crate::translation::pipeline::provenance)。部分翻译的页面不写入。
+    #[serde(default)]
+    pub embed_provenance: bool,
+
+    /// 判断页面语言时抽取的文本段数
+    ///
+    /// 未指定源语言时，从收集到的文本中取最长的这些段按长度加权判断页面语言，
+    /// 主要语言已是目标语言时跳过翻译。设为 0 时不做判断。
+    #[serde(default = "default_language_sample_size")]
+    pub language_sample_size: usize,
+
+    /// 自定义翻译接口的请求/响应模板
+    ///
+    /// 设置后不再使用内置的 DeepLX 请求格式，而是按模板向 `api_url` 发送 JSON 请求。
+    #[serde(default)]
+    pub request_template: Option<RequestTemplate>,
+
+    /// 按顺序尝试的翻译后端
+    ///
+    /// 为空时使用 `api_url` 和 `request_template` 描述的单个后端。设置后每次请求先发给第一个
+    /// 后端，遇到网络故障、超时、服务端错误或限流时改用下一个；输入本身无效或超出字符预算时
+    /// 不再尝试其他后端。
+    #[serde(default)]
+    pub backends: Vec<BackendConfig>,
+}
+
+/// 后端链中的一个翻译后端
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct BackendConfig {
+    /// 后端名称，用于日志和统计
+    pub name: String,
+    /// 翻译接口地址
+    pub api_url: String,
+    /// 请求/响应模板，未设置时使用 DeepLX 格式
+    #[serde(default)]
+    pub request_template: Option<RequestTemplate>,
+}
+
+fn default_coalesce_requests() -> bool {
+    true
+}
+
+fn default_language_sample_size() -> usize {
+    constants::LANGUAGE_SAMPLE_ITEMS
+}
+
+fn default_min_length_ratio() -> f32 {
+    constants::DEFAULT_MIN_LENGTH_RATIO
+}
+
+fn default_max_length_ratio() -> f32 {
+    constants::DEFAULT_MAX_LENGTH_RATIO
 }
 
 impl TranslationConfig {
@@ -231,15 +489,39 @@ impl TranslationConfig {
 
             batch_size: constants::MAX_BATCH_SIZE,
             min_batch_chars: constants::DEFAULT_MIN_CHARS,
+            preferred_batch_chars: None,
             batch_timeout_secs: constants::DEFAULT_BATCH_TIMEOUT.as_secs(),
 
             cache_enabled: true,
             cache_ttl_secs: constants::DEFAULT_CACHE_TTL.as_secs(),
             local_cache_size: 1000,
+            cache_domain_ttl_secs: HashMap::new(),
+            coalesce_requests: true,
 
             parallel_enabled: true,
             retry_enabled: true,
             max_retry_attempts: 3,
+            best_effort: false,
+            max_chars_per_request_job: None,
+            verify_translations: false,
+            min_length_ratio: constants::DEFAULT_MIN_LENGTH_RATIO,
+            max_length_ratio: constants::DEFAULT_MAX_LENGTH_RATIO,
+            language_concurrency_weights: HashMap::new(),
+
+            show_original: false,
+            bilingual: false,
+            translate_templates: false,
+            translate_social_meta: false,
+            translate_canvas_fallback: false,
+            translate_preformatted: false,
+            translate_json_paths: Vec::new(),
+            translate_attributes: Vec::new(),
+            retranslate: false,
+            html_lang: None,
+            embed_provenance: false,
+            language_sample_size: constants::LANGUAGE_SAMPLE_ITEMS,
+            request_template: None,
+            backends: Vec::new(),
         }
     }
 
@@ -278,6 +560,53 @@ impl TranslationConfig {
         config
     }
 
+    /// 显式指定的源语言
+    /// 
+    /// `source_lang` 为空或 `"auto"`（不区分大小写）时返回 `None`，表示需要自动检测。
+    /// 
+    /// ## 示例
+    /// 
+    /// ```rust
+    /// use monolith::translation::TranslationConfig;
+    ///
+    /// let mut config = TranslationConfig::default();
+    /// assert_eq!(config.explicit_source_lang(), None);
+    ///
+    /// config.source_lang = "en".to_string();
+    /// assert_eq!(config.explicit_source_lang(), Some("en"));
+    /// ```
+    pub fn explicit_source_lang(&self) -> Option<&str> {
+        let source_lang = self.source_lang.trim();
+        if source_lang.is_empty() || source_lang.eq_ignore_ascii_case("auto") {
+            None
+        } else {
+            Some(source_lang)
+        }
+    }
+
+    /// 写入 `<html lang>` 和 `Content-Language` 的语言
+    ///
+    /// 使用配置的 `html_lang`，未配置时只保留目标语言的主语言子标签：目标语言 `zh-CN`
+    /// 写为 `zh`，译文未必符合地区的用语习惯。
+    ///
+    /// ## 示例
+    ///
+    /// ```rust
+    /// use monolith::translation::TranslationConfig;
+    ///
+    /// let mut config = TranslationConfig::default_with_lang("zh-CN", None);
+    /// assert_eq!(config.document_lang(), "zh");
+    ///
+    /// config.html_lang = Some("zh-Hans".to_string());
+    /// assert_eq!(config.document_lang(), "zh-Hans");
+    /// ```
+    pub fn document_lang(&self) -> String {
+        match self.html_lang.as_deref().map(str::trim) {
+            Some(lang) if !lang.is_empty() => lang.to_string(),
+            _ => primary_subtag(&self.target_lang),
+        }
+    }
+
     /// 验证配置的有效性
     /// 
     /// 检查配置中的各项参数是否合理和有效。这个方法会验证关键配置项
@@ -286,6 +615,7 @@ impl TranslationConfig {
     /// ## 验证规则
     /// 
     /// - `batch_size` 必须大于 0
+    /// - `preferred_batch_chars` 设置时必须在 1 到 `batch_size` 之间
     /// - `max_concurrent_requests` 必须大于 0
     /// - `max_requests_per_second` 必须大于 0.0
     /// - 当启用缓存时，`local_cache_size` 必须大于 0
@@ -316,6 +646,15 @@ impl TranslationConfig {
             return Err(TranslationError::ConfigError("批次大小不能为0".to_string()));
         }
 
+        if let Some(preferred) = self.preferred_batch_chars {
+            if preferred == 0 || preferred > self.batch_size {
+                return Err(TranslationError::ConfigError(format!(
+                    "首选批次字符数必须在 1 到 batch_size ({}) 之间",
+                    self.batch_size
+                )));
+            }
+        }
+
         if self.max_concurrent_requests == 0 {
             return Err(TranslationError::ConfigError(
                 "最大并发数不能为0".to_string(),
@@ -334,6 +673,28 @@ impl TranslationConfig {
             ));
         }
 
+        if let Some(template) = &self.request_template {
+            template.validate()?;
+        }
+
+        if self.min_length_ratio < 0.0 || self.max_length_ratio < self.min_length_ratio {
+            return Err(TranslationError::ConfigError(
+                "译文长度比例的下限不能为负数，也不能大于上限".to_string(),
+            ));
+        }
+
+        for backend in &self.backends {
+            if backend.api_url.trim().is_empty() {
+                return Err(TranslationError::ConfigError(format!(
+                    "后端 {} 的接口地址不能为空",
+                    backend.name
+                )));
+            }
+            if let Some(template) = &backend.request_template {
+                template.validate()?;
+            }
+        }
+
         Ok(())
     }
 
@@ -354,6 +715,7 @@ impl TranslationConfig {
     /// - `TRANSLATION_MAX_CONCURRENT_REQUESTS`: 最大并发数
     /// - `TRANSLATION_BATCH_SIZE`: 批次大小
     /// - `TRANSLATION_BATCH_TIMEOUT`: 批次超时时间
+    /// - `TRANSLATION_BEST_EFFORT`: 部分批次失败时是否保留其余译文
     /// 
     /// ### 缓存相关
     /// - `CACHE_ENABLED`: 是否启用缓存
@@ -421,6 +783,10 @@ impl TranslationConfig {
             self.batch_timeout_secs = batch_timeout.as_secs();
         }
 
+        if let Ok(best_effort) = translation::BestEffort::get() {
+            self.best_effort = best_effort;
+        }
+
         // 缓存相关环境变量
         if let Ok(cache_enabled) = cache::Enabled::get() {
             self.cache_enabled = cache_enabled;
@@ -478,6 +844,14 @@ impl TranslationConfig {
     pub fn cache_ttl(&self) -> Duration {
         Duration::from_secs(self.cache_ttl_secs)
     }
+
+    /// 按域名覆盖的缓存有效期，见 `cache_domain_ttl_secs`
+    pub fn cache_domain_ttls(&self) -> HashMap<String, Duration> {
+        self.cache_domain_ttl_secs
+            .iter()
+            .map(|(host, secs)| (host.to_lowercase(), Duration::from_secs(*secs)))
+            .collect()
+    }
 }
 
 impl Default for TranslationConfig {
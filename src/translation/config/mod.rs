@@ -31,12 +31,15 @@
 //! ```
 
 pub mod manager;
+pub mod template;
 
 /// 重新导出主要类型，提供模块的公共接口
 ///
 /// - `ConfigManager`: 配置管理器，负责加载、验证和管理配置
 /// - `TranslationConfig`: 翻译配置结构体，包含所有配置参数
-pub use manager::{ConfigManager, TranslationConfig};
+/// - `RequestTemplate`: 自定义翻译接口的请求/响应模板
+pub use manager::{BackendConfig, ConfigManager, TranslationConfig};
+pub use template::RequestTemplate;
 
 /// 翻译系统配置常量
 ///
@@ -94,6 +97,22 @@ pub mod constants {
     /// 可能跳过翻译（认为是代码、符号等不需要翻译的内容）
     pub const SPECIAL_CHAR_THRESHOLD: f32 = 0.33;
 
+    /// 判断页面语言时默认抽取的文本段数
+    /// 
+    /// 从收集到的文本中取最长的这些段，按长度加权判断页面语言，
+    /// 如果页面语言已与目标语言一致则直接跳过翻译
+    pub const LANGUAGE_SAMPLE_ITEMS: usize = 50;
+
+    /// 译文与原文字符数之比的默认下限
+    /// 
+    /// 英文译为中文时字符数通常只有原文的三分之一左右，低于此值的译文多半被截断或为空
+    pub const DEFAULT_MIN_LENGTH_RATIO: f32 = 0.1;
+
+    /// 译文与原文字符数之比的默认上限
+    /// 
+    /// 中文译为英文时字符数可达原文的数倍，超过此值的译文多半混入了多余的内容
+    pub const DEFAULT_MAX_LENGTH_RATIO: f32 = 10.0;
+
     // 默认API设置
     
     /// 默认的翻译API服务器地址
@@ -145,22 +164,13 @@ pub mod constants {
         "placeholder",     // 输入框占位符
         "aria-label",      // 无障碍标签
         "aria-description", // 无障碍描述
+        "label",           // 下拉选项分组（optgroup）标题
     ];
 
     /// 需要跳过翻译的HTML元素列表
     /// 
-    /// 这些元素通常包含代码、样式、元数据或功能性内容，
-    /// 不应该进行文本翻译处理
-    pub const SKIP_ELEMENTS: &[&str] = &[
-        "script", "style", "code", "pre",      // 代码和样式相关
-        "noscript", "meta", "link", "head",    // 元数据相关
-        "svg", "math",                         // 图形和数学公式
-        "canvas", "video", "audio",            // 媒体元素
-        "embed", "object", "iframe",           // 嵌入内容
-        "map", "area", "base",                 // 映射和基础元素
-        "br", "hr", "img", "input",            // 空元素和表单
-        "source", "track", "wbr",              // 媒体资源和换行
-    ];
+    /// 与纯文本输出共用同一份列表，定义见 `parsers::html::utils::SKIP_ELEMENTS`
+    pub use crate::parsers::html::utils::SKIP_ELEMENTS;
 
     /// 功能性词汇列表
     /// 
@@ -0,0 +1,208 @@
+//! 翻译接口请求模板
+//!
+//! 内置客户端只支持 DeepLX 的请求格式。对于 LibreTranslate 或自建服务等接口，
+//! 可以在配置中声明请求体模板和响应提取路径，而不必实现新的后端：
+//!
+//! ```toml
+//! api_url = "http://localhost:5000/translate"
+//!
+//! [request_template]
+//! body = '{"q": "{{text}}", "source": "{{source}}", "target": "{{target}}", "format": "text"}'
+//! response_path = "translatedText"
+//!
+//! [request_template.headers]
+//! Authorization = "Bearer xxx"
+//! ```
+//!
+//! 占位符按 JSON 字符串规则转义后替换，因此应写在模板的引号内。
+//! 响应路径由点号分隔的字段名和 `[n]` 数组下标组成，可带 `$.` 前缀，
+//! 例如 `data.translations[0].translatedText`。
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::translation::error::{TranslationError, TranslationResult};
+
+/// 请求/响应模板
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RequestTemplate {
+    /// 请求体 JSON 模板，支持 `{{text}}`、`{{target}}`、`{{source}}` 占位符
+    pub body: String,
+    /// 从响应 JSON 中提取译文的路径
+    pub response_path: String,
+    /// 附加的请求头（例如认证信息）
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+}
+
+/// 响应路径中的一段
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+impl RequestTemplate {
+    /// 检查模板能否渲染为合法的 JSON，以及响应路径能否解析
+    pub fn validate(&self) -> TranslationResult<()> {
+        if !self.body.contains("{{text}}") {
+            return Err(TranslationError::ConfigError(
+                "请求模板缺少 {{text}} 占位符".to_string(),
+            ));
+        }
+
+        let sample = self.render("sample \"text\"\n", "zh", "auto");
+        serde_json::from_str::<Value>(&sample).map_err(|e| {
+            TranslationError::ConfigError(format!("请求模板不是合法的 JSON: {}", e))
+        })?;
+
+        parse_path(&self.response_path)?;
+        Ok(())
+    }
+
+    /// 渲染请求体
+    pub fn render(&self, text: &str, target_lang: &str, source_lang: &str) -> String {
+        self.body
+            .replace("{{target}}", &escape_json(target_lang))
+            .replace("{{source}}", &escape_json(source_lang))
+            .replace("{{text}}", &escape_json(text))
+    }
+
+    /// 从响应中提取译文
+    pub fn extract(&self, response: &str) -> TranslationResult<String> {
+        let value: Value = serde_json::from_str(response)
+            .map_err(|e| TranslationError::ParseError(format!("翻译响应不是合法的 JSON: {}", e)))?;
+
+        let mut current = &value;
+        for segment in parse_path(&self.response_path)? {
+            let next = match &segment {
+                PathSegment::Key(key) => current.get(key),
+                PathSegment::Index(index) => current.get(index),
+            };
+            current = next.ok_or_else(|| {
+                TranslationError::ParseError(format!("翻译响应中不存在路径 {}", self.response_path))
+            })?;
+        }
+
+        match current {
+            Value::String(text) => Ok(text.clone()),
+            other => Err(TranslationError::ParseError(format!(
+                "路径 {} 指向的不是字符串: {}",
+                self.response_path, other
+            ))),
+        }
+    }
+}
+
+/// 解析响应路径，例如 `$.data.translations[0].text`
+fn parse_path(path: &str) -> TranslationResult<Vec<PathSegment>> {
+    let invalid = || TranslationError::ConfigError(format!("无效的响应路径: {:?}", path));
+    let path = path.trim();
+    let path = path.strip_prefix("$.").unwrap_or(path);
+    if path.is_empty() {
+        return Err(invalid());
+    }
+
+    let mut segments = Vec::new();
+    for part in path.split('.') {
+        let (key, mut indices) = match part.find('[') {
+            Some(pos) => (&part[..pos], &part[pos..]),
+            None => (part, ""),
+        };
+        if key.is_empty() && indices.is_empty() {
+            return Err(invalid());
+        }
+        if !key.is_empty() {
+            segments.push(PathSegment::Key(key.to_string()));
+        }
+
+        while !indices.is_empty() {
+            let end = indices.find(']').ok_or_else(invalid)?;
+            let index = indices
+                .get(1..end)
+                .and_then(|index| index.parse().ok())
+                .ok_or_else(invalid)?;
+            segments.push(PathSegment::Index(index));
+            indices = &indices[end + 1..];
+            if !indices.is_empty() && !indices.starts_with('[') {
+                return Err(invalid());
+            }
+        }
+    }
+
+    Ok(segments)
+}
+
+/// 按 JSON 字符串规则转义（不含两侧引号）
+fn escape_json(value: &str) -> String {
+    let quoted = Value::String(value.to_string()).to_string();
+    quoted[1..quoted.len() - 1].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn libretranslate() -> RequestTemplate {
+        RequestTemplate {
+            body: r#"{"q": "{{text}}", "source": "{{source}}", "target": "{{target}}"}"#
+                .to_string(),
+            response_path: "translatedText".to_string(),
+            headers: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_render_escapes_placeholders() {
+        let body = libretranslate().render("say \"hi\"\n", "zh", "auto");
+        let value: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(value["q"], "say \"hi\"\n");
+        assert_eq!(value["target"], "zh");
+        assert_eq!(value["source"], "auto");
+    }
+
+    #[test]
+    fn test_extract_nested_path() {
+        let template = RequestTemplate {
+            response_path: "$.data.translations[1].text".to_string(),
+            ..libretranslate()
+        };
+        let response = r#"{"data": {"translations": [{"text": "a"}, {"text": "你好"}]}}"#;
+        assert_eq!(template.extract(response).unwrap(), "你好");
+
+        assert!(template.extract(r#"{"data": {}}"#).is_err());
+        assert_eq!(
+            libretranslate()
+                .extract(r#"{"translatedText": "你好"}"#)
+                .unwrap(),
+            "你好"
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(libretranslate().validate().is_ok());
+
+        let missing_text = RequestTemplate {
+            body: r#"{"q": "hello"}"#.to_string(),
+            ..libretranslate()
+        };
+        assert!(missing_text.validate().is_err());
+
+        let invalid_json = RequestTemplate {
+            body: r#"{"q": {{text}}}"#.to_string(),
+            ..libretranslate()
+        };
+        assert!(invalid_json.validate().is_err());
+
+        for path in ["", "a..b", "a[x]", "a[0]b", "a[0"] {
+            let template = RequestTemplate {
+                response_path: path.to_string(),
+                ..libretranslate()
+            };
+            assert!(template.validate().is_err(), "{:?}", path);
+        }
+    }
+}
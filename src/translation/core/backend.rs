@@ -0,0 +1,401 @@
+//! 翻译后端
+//!
+//! 处理器只需要"把一段文本翻译成目标语言"这一个操作。[`TranslationBackend`] 在
+//! markdown-translator 内置的 DeepLX 客户端和按 [`RequestTemplate`] 拼装请求的
+//! 通用 HTTP 客户端之间选择，由配置中是否设置了 `request_template` 决定。
+//!
+//! 配置了 `backends` 时组成后端链：每次请求依次尝试各后端，[`should_fall_back`] 根据错误类型
+//! 决定是否改用下一个，成功响应的后端会被计数，供服务统计读取。
+//!
+//! 每个后端还记录实际发出的请求数和收发的字符数（[`BackendUsage`]），重试和回退的每次尝试
+//! 都单独计数，用于按调用核算翻译费用。
+//!
+//! 首次请求的字符由调用方从 [`CharBudget`] 中扣除；后端链把同一段文本重发给下一个后端时，
+//! 每次回退都要再扣一次，预算不足时停止回退。
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use markdown_translator::TranslationService as BaseTranslationService;
+use serde::Serialize;
+
+use crate::translation::config::{RequestTemplate, TranslationConfig};
+use crate::translation::error::{TranslationError, TranslationResult};
+use crate::translation::processor::CharBudget;
+
+/// 翻译后端
+pub enum TranslationBackend {
+    /// 内置的 DeepLX 客户端
+    DeepLx(BaseTranslationService, Mutex<BackendUsage>),
+    /// 按模板发送请求的通用 JSON 接口
+    Template(TemplateBackend, Mutex<BackendUsage>),
+    /// 按顺序尝试的多个后端
+    Chain(BackendChain),
+}
+
+/// 后端发出的请求和收发的字符数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct BackendUsage {
+    /// 请求次数，包括失败的请求
+    pub requests: usize,
+    /// 发送的字符数
+    pub chars_sent: usize,
+    /// 收到的译文字符数
+    pub chars_received: usize,
+}
+
+impl BackendUsage {
+    /// 累加另一份用量
+    pub fn add(&mut self, other: &BackendUsage) {
+        self.requests += other.requests;
+        self.chars_sent += other.chars_sent;
+        self.chars_received += other.chars_received;
+    }
+}
+
+/// 按顺序尝试的后端链
+pub struct BackendChain {
+    backends: Vec<(String, TranslationBackend)>,
+    /// 各后端成功响应的请求数
+    served: Mutex<BTreeMap<String, usize>>,
+}
+
+/// 按模板发送请求的翻译后端
+pub struct TemplateBackend {
+    client: reqwest::Client,
+    api_url: String,
+    source_lang: String,
+    target_lang: String,
+    template: RequestTemplate,
+}
+
+impl TranslationBackend {
+    /// 根据配置选择后端
+    pub fn from_config(config: &TranslationConfig) -> Self {
+        if !config.backends.is_empty() {
+            let backends = config
+                .backends
+                .iter()
+                .map(|backend| {
+                    let backend_config = TranslationConfig {
+                        api_url: backend.api_url.clone(),
+                        request_template: backend.request_template.clone(),
+                        backends: Vec::new(),
+                        ..config.clone()
+                    };
+                    (backend.name.clone(), Self::from_config(&backend_config))
+                })
+                .collect();
+            return Self::Chain(BackendChain {
+                backends,
+                served: Mutex::new(BTreeMap::new()),
+            });
+        }
+
+        match &config.request_template {
+            Some(template) => Self::Template(
+                TemplateBackend::new(config, template.clone()),
+                Mutex::default(),
+            ),
+            None => {
+                let base_config = markdown_translator::TranslationConfig {
+                    enabled: true,
+                    source_lang: config.source_lang.clone(),
+                    target_lang: config.target_lang.clone(),
+                    deeplx_api_url: config.api_url.clone(),
+                    ..Default::default()
+                };
+                Self::DeepLx(BaseTranslationService::new(base_config), Mutex::default())
+            }
+        }
+    }
+
+    /// 翻译一段文本
+    pub async fn translate(&self, text: &str) -> TranslationResult<String> {
+        self.translate_charged(text, None).await
+    }
+
+    /// 翻译一段文本，后端链回退时从 `budget` 中扣除重发的字符
+    ///
+    /// 首次请求的字符已由调用方扣除；回退前预算不足时返回 `BudgetExceeded` 错误。
+    pub async fn translate_with_budget(
+        &self,
+        text: &str,
+        budget: &CharBudget,
+    ) -> TranslationResult<String> {
+        self.translate_charged(text, Some(budget)).await
+    }
+
+    async fn translate_charged(
+        &self,
+        text: &str,
+        budget: Option<&CharBudget>,
+    ) -> TranslationResult<String> {
+        let (result, usage) = match self {
+            Self::DeepLx(service, usage) => (
+                service
+                    .translate(text)
+                    .await
+                    .map_err(|e| TranslationError::TranslationServiceError(e.to_string())),
+                usage,
+            ),
+            Self::Template(backend, usage) => (backend.translate(text).await, usage),
+            Self::Chain(chain) => return Box::pin(chain.translate(text, budget)).await,
+        };
+
+        let mut usage = usage.lock().unwrap();
+        usage.requests += 1;
+        usage.chars_sent += text.chars().count();
+        if let Ok(translated) = &result {
+            usage.chars_received += translated.chars().count();
+        }
+        result
+    }
+
+    /// 取出并清零各后端的用量，按后端名称排列
+    ///
+    /// 单个后端以其类型（`deeplx` 或 `template`）为名称；没有发出请求的后端不出现在结果中。
+    pub fn take_usage(&self) -> BTreeMap<String, BackendUsage> {
+        let mut taken = BTreeMap::new();
+        match self {
+            Self::DeepLx(_, usage) => Self::take_into(&mut taken, "deeplx", usage),
+            Self::Template(_, usage) => Self::take_into(&mut taken, "template", usage),
+            Self::Chain(chain) => {
+                for (name, backend) in &chain.backends {
+                    for usage in backend.take_usage().values() {
+                        taken.entry(name.clone()).or_default().add(usage);
+                    }
+                }
+            }
+        }
+        taken
+    }
+
+    fn take_into(
+        taken: &mut BTreeMap<String, BackendUsage>,
+        name: &str,
+        usage: &Mutex<BackendUsage>,
+    ) {
+        let usage = std::mem::take(&mut *usage.lock().unwrap());
+        if usage.requests > 0 {
+            taken.insert(name.to_string(), usage);
+        }
+    }
+
+    /// 取出并清零后端链中各后端成功响应的请求数，单个后端时为空
+    pub fn take_served(&self) -> BTreeMap<String, usize> {
+        match self {
+            Self::Chain(chain) => std::mem::take(&mut *chain.served.lock().unwrap()),
+            _ => BTreeMap::new(),
+        }
+    }
+}
+
+/// 后端失败后是否改用下一个后端
+///
+/// 网络故障、超时、服务端错误（包括额度耗尽返回的错误状态）和限流都只与当前后端有关；
+/// 输入无效或超出字符预算时换一个后端也不会成功。
+pub fn should_fall_back(error: &TranslationError) -> bool {
+    error.is_retryable() || matches!(error, TranslationError::RateLimitExceeded)
+}
+
+impl BackendChain {
+    async fn translate(
+        &self,
+        text: &str,
+        budget: Option<&CharBudget>,
+    ) -> TranslationResult<String> {
+        let mut last_error = None;
+
+        for (i, (name, backend)) in self.backends.iter().enumerate() {
+            if let Some(budget) = budget.filter(|_| i > 0) {
+                budget.charge(text)?;
+            }
+            match backend.translate(text).await {
+                Ok(translated) => {
+                    *self.served.lock().unwrap().entry(name.clone()).or_insert(0) += 1;
+                    return Ok(translated);
+                }
+                Err(e) if should_fall_back(&e) => {
+                    tracing::warn!("翻译后端 {} 失败，尝试下一个后端: {}", name, e);
+                    last_error = Some(e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(last_error
+            .unwrap_or_else(|| TranslationError::ConfigError("没有可用的翻译后端".to_string())))
+    }
+}
+
+impl TemplateBackend {
+    fn new(config: &TranslationConfig, template: RequestTemplate) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.batch_timeout_secs.max(1)))
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            client,
+            api_url: config.api_url.clone(),
+            source_lang: config.source_lang.clone(),
+            target_lang: config.target_lang.clone(),
+            template,
+        }
+    }
+
+    async fn translate(&self, text: &str) -> TranslationResult<String> {
+        let mut request = self
+            .client
+            .post(&self.api_url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(
+                self.template
+                    .render(text, &self.target_lang, &self.source_lang),
+            );
+        for (name, value) in &self.template.headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| TranslationError::NetworkError(format!("翻译请求失败: {}", e)))?;
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| TranslationError::NetworkError(format!("读取翻译响应失败: {}", e)))?;
+
+        if !status.is_success() {
+            return Err(TranslationError::TranslationServiceError(format!(
+                "翻译接口返回 {}: {}",
+                status,
+                body.chars().take(200).collect::<String>()
+            )));
+        }
+
+        self.template.extract(&body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::translation::config::BackendConfig;
+    use crate::translation::test_backend::{closed_url, TestBackend};
+
+    fn template_backend(name: &str, api_url: String) -> BackendConfig {
+        BackendConfig {
+            name: name.to_string(),
+            api_url,
+            request_template: Some(RequestTemplate {
+                body: r#"{"q": "{{text}}"}"#.to_string(),
+                response_path: "translatedText".to_string(),
+                headers: Default::default(),
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_chain_falls_back_to_next_backend() {
+        let config = TranslationConfig {
+            backends: vec![
+                template_backend(
+                    "primary",
+                    TestBackend::fixed("456 Quota Exceeded", r#"{"message": "Quota exceeded"}"#)
+                        .url,
+                ),
+                template_backend(
+                    "fallback",
+                    TestBackend::fixed("200 OK", r#"{"translatedText": "译文"}"#).url,
+                ),
+            ],
+            ..TranslationConfig::default_with_lang("zh", None)
+        };
+        let backend = TranslationBackend::from_config(&config);
+
+        assert_eq!(backend.translate("Hello").await.unwrap(), "译文");
+        assert_eq!(backend.translate("World").await.unwrap(), "译文");
+        assert_eq!(
+            backend.take_served(),
+            BTreeMap::from([("fallback".to_string(), 2)])
+        );
+        assert!(backend.take_served().is_empty());
+
+        let usage = backend.take_usage();
+        assert_eq!(
+            usage["primary"],
+            BackendUsage {
+                requests: 2,
+                chars_sent: 10,
+                chars_received: 0,
+            }
+        );
+        assert_eq!(
+            usage["fallback"],
+            BackendUsage {
+                requests: 2,
+                chars_sent: 10,
+                chars_received: 4,
+            }
+        );
+        assert!(backend.take_usage().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_chain_charges_budget_for_fallback() {
+        let config = TranslationConfig {
+            backends: vec![
+                template_backend("primary", closed_url()),
+                template_backend(
+                    "fallback",
+                    TestBackend::fixed("200 OK", r#"{"translatedText": "译文"}"#).url,
+                ),
+            ],
+            ..TranslationConfig::default_with_lang("zh", None)
+        };
+        let backend = TranslationBackend::from_config(&config);
+
+        // 调用方为首次请求扣除 5 个字符，回退时再扣 5 个
+        let budget = CharBudget::new(Some(10));
+        budget.charge("Hello").unwrap();
+        assert_eq!(
+            backend
+                .translate_with_budget("Hello", &budget)
+                .await
+                .unwrap(),
+            "译文"
+        );
+        assert_eq!(budget.consumed(), 10);
+
+        // 预算不足以回退时不再发给下一个后端
+        let budget = CharBudget::new(Some(8));
+        budget.charge("World").unwrap();
+        assert!(matches!(
+            backend.translate_with_budget("World", &budget).await,
+            Err(TranslationError::BudgetExceeded {
+                limit: 8,
+                consumed: 5
+            })
+        ));
+        assert!(budget.is_exhausted());
+
+        let usage = backend.take_usage();
+        assert_eq!(usage["primary"].requests, 2);
+        assert_eq!(usage["fallback"].requests, 1);
+    }
+
+    #[test]
+    fn test_should_fall_back() {
+        assert!(should_fall_back(&TranslationError::NetworkError(
+            "connection refused".to_string()
+        )));
+        assert!(should_fall_back(&TranslationError::RateLimitExceeded));
+        assert!(!should_fall_back(&TranslationError::InvalidInput(
+            "empty".to_string()
+        )));
+    }
+}
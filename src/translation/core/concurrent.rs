@@ -0,0 +1,189 @@
+//! 与资源嵌入并行执行的翻译
+//!
+//! 资源嵌入（阻塞式HTTP请求，受源站网络限制）和翻译（异步API请求，受翻译服务限制）
+//! 原本是串行的两个阶段。本模块把翻译拆成两步，使两者可以重叠执行：
+//!
+//! 1. [`ConcurrentTranslation::start`] 把页面序列化后交给独立的线程，在那里重新解析并用
+//!    [`TranslationService::translate_dom_to_map`] 翻译，收集、缓存、标记等规则与直接翻译完全相同；
+//! 2. [`ConcurrentTranslation::finish`] 在资源嵌入结束后等待翻译完成，再按译文映射表把译文写回
+//!    原文档。
+//!
+//! ## 安全性
+//!
+//! `RcDom` 不能跨线程共享，后台线程只接触序列化后的 HTML 和它自己解析出的文档。
+//! 节点路径在开始翻译时就按原文档记录下来，资源嵌入之后删除或插入节点也不影响写回。
+//! 资源嵌入只修改URL类属性以及 `style`/`script` 等元素的内容，而翻译只修改文本节点和
+//! 可翻译属性（见 `TRANSLATABLE_ATTRS`），收集器也会跳过这些元素，两者修改的内容互不重叠。
+
+use std::thread::JoinHandle;
+
+use markup5ever_rcdom::{RcDom, SerializableHandle};
+use tokio::runtime::Runtime;
+
+use crate::html::{html_to_dom, inline_template_contents};
+use crate::translation::{
+    config::TranslationConfig,
+    core::service::TranslationService,
+    error::{TranslationError, TranslationResult},
+    pipeline::bilingual::{annotate_originals, BilingualSnapshot},
+    pipeline::collector::{CollectorConfig, TextCollector, TextItem},
+    pipeline::sidecar::{clone_dom, NodeIndex, TranslationMap},
+};
+
+/// 正在后台执行的翻译任务
+///
+/// 持有开始翻译时记录的节点路径和后台线程的句柄。
+/// 必须在创建它的线程上调用 [`finish`](Self::finish) 才会把译文写回DOM。
+pub struct ConcurrentTranslation {
+    nodes: NodeIndex,
+    worker: JoinHandle<TranslationResult<TranslationMap>>,
+    /// 开启 `show_original` 时翻译前的文本项
+    originals: Option<Vec<TextItem>>,
+    bilingual: Option<BilingualSnapshot>,
+}
+
+impl ConcurrentTranslation {
+    /// 序列化DOM并立即在后台线程开始翻译
+    ///
+    /// 调用方随后可以继续修改DOM的其他部分（例如嵌入资源）。
+    pub fn start(dom: &RcDom, config: TranslationConfig) -> TranslationResult<Self> {
+        // 双语段落和原文提示不记录在映射表中，在原文档上处理
+        let items = if config.show_original || config.bilingual {
+            let mut collector = TextCollector::new(CollectorConfig::from(&config));
+            collector.collect_translatable_texts(&dom.document)?
+        } else {
+            Vec::new()
+        };
+        let originals = config.show_original.then(|| items.clone());
+        let bilingual = config
+            .bilingual
+            .then(|| BilingualSnapshot::capture(dom, &items));
+
+        let html = serialize_copy(dom)?;
+        let nodes = NodeIndex::capture(dom);
+        let worker_config = TranslationConfig {
+            show_original: false,
+            bilingual: false,
+            ..config
+        };
+        let worker = std::thread::Builder::new()
+            .name("translation".to_string())
+            .spawn(move || translate_html(html, worker_config))
+            .map_err(|e| {
+                TranslationError::ConcurrencyError(format!("创建后台翻译线程失败: {}", e))
+            })?;
+
+        tracing::info!("后台翻译开始");
+        Ok(Self {
+            nodes,
+            worker,
+            originals,
+            bilingual,
+        })
+    }
+
+    /// 等待后台翻译完成并把译文写回DOM
+    ///
+    /// 返回写回的文本数量；翻译失败时返回错误，DOM保持原文。
+    pub fn finish(self, dom: &RcDom) -> TranslationResult<usize> {
+        let map = self.worker.join().map_err(|_| {
+            TranslationError::ConcurrencyError("后台翻译线程异常退出".to_string())
+        })??;
+
+        let stats = self.nodes.apply(dom, &map);
+        tracing::info!(
+            "后台翻译完成: 写回 {} 个文本，跳过 {} 个",
+            stats.applied,
+            stats.skipped
+        );
+
+        if let Some(originals) = self.originals {
+            annotate_originals(dom, &originals);
+        }
+        if let Some(snapshot) = self.bilingual {
+            snapshot.insert_originals(dom);
+        }
+        Ok(stats.applied)
+    }
+}
+
+/// 把文档的副本序列化为 UTF-8 HTML，模板内容一并输出
+fn serialize_copy(dom: &RcDom) -> TranslationResult<Vec<u8>> {
+    let copy = clone_dom(dom);
+    inline_template_contents(&copy.document);
+
+    let mut html = Vec::new();
+    html5ever::serialize(
+        &mut html,
+        &SerializableHandle::from(copy.document),
+        Default::default(),
+    )
+    .map_err(|e| TranslationError::InternalError(format!("序列化页面失败: {}", e)))?;
+    Ok(html)
+}
+
+/// 在后台线程上解析页面并翻译，返回译文映射表
+fn translate_html(html: Vec<u8>, config: TranslationConfig) -> TranslationResult<TranslationMap> {
+    let runtime = Runtime::new()
+        .map_err(|e| TranslationError::InternalError(format!("创建异步运行时失败: {}", e)))?;
+    let dom = html_to_dom(&html, "UTF-8".to_string());
+    let mut service = TranslationService::new(config)?;
+    runtime
+        .block_on(service.translate_dom_to_map(dom))
+        .map(|(_, map)| map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::html::get_node_attr;
+    use crate::translation::config::RequestTemplate;
+    use crate::translation::pipeline::marker::is_translated_to;
+    use crate::translation::test_backend::TestBackend;
+    use markup5ever_rcdom::NodeData;
+
+    #[test]
+    fn test_translations_survive_structural_changes() {
+        let config = TranslationConfig {
+            api_url: TestBackend::fixed("200 OK", r#"{"translatedText": "你好"}"#).url,
+            batch_timeout_secs: 5,
+            request_template: Some(RequestTemplate {
+                body: r#"{"q": "{{text}}", "target": "{{target}}"}"#.to_string(),
+                response_path: "translatedText".to_string(),
+                headers: Default::default(),
+            }),
+            ..TranslationConfig::default_with_lang("zh", None)
+        };
+        let dom = html_to_dom(
+            b"<html><head><script src=\"a.js\"></script></head><body>\
+              <div>Removed while embedding</div><p>Hello world</p>\
+              <img src=\"a.png\" alt=\"A friendly wave\"></body></html>",
+            "UTF-8".to_string(),
+        );
+        let translation = ConcurrentTranslation::start(&dom, config).unwrap();
+
+        // 模拟资源嵌入期间的结构变化：删除脚本和段落前的元素，再插入新的段落
+        let html = dom.document.children.borrow()[0].clone();
+        let head = html.children.borrow()[0].clone();
+        let body = html.children.borrow()[1].clone();
+        head.children.borrow_mut().clear();
+        body.children.borrow_mut().remove(0);
+        let other = html_to_dom(b"<p>Inserted paragraph</p>", "UTF-8".to_string());
+        let other_body = other.document.children.borrow()[0].children.borrow()[1].clone();
+        let inserted = other_body.children.borrow()[0].clone();
+        body.children.borrow_mut().insert(0, inserted.clone());
+        let paragraph = body.children.borrow()[1].clone();
+        let image = body.children.borrow()[2].clone();
+
+        let text = |node: &markup5ever_rcdom::Handle| match &node.children.borrow()[0].data {
+            NodeData::Text { contents } => contents.borrow().to_string(),
+            _ => panic!("expected a text node"),
+        };
+        // 删除的元素中的文本同样写回，但已不在文档中
+        assert_eq!(translation.finish(&dom).unwrap(), 3);
+        assert_eq!(text(&paragraph), "你好");
+        assert_eq!(text(&inserted), "Inserted paragraph");
+        assert_eq!(get_node_attr(&image, "alt").as_deref(), Some("你好"));
+        assert!(is_translated_to(&dom, "zh"));
+    }
+}
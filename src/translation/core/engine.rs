@@ -422,22 +422,16 @@ impl TranslationEngine {
                 continue;
             }
 
-            // 根据文本类型选择合适的更新方法
-            match &item.text_type {
-                // 文本内容类型：更新节点的文本内容
-                crate::translation::pipeline::collector::TextType::Content
-                | crate::translation::pipeline::collector::TextType::Title
-                | crate::translation::pipeline::collector::TextType::Link
-                | crate::translation::pipeline::collector::TextType::Button
-                | crate::translation::pipeline::collector::TextType::FormLabel
-                | crate::translation::pipeline::collector::TextType::ImageAlt
-                | crate::translation::pipeline::collector::TextType::Tooltip => {
-                    self.update_text_content(&item.node, translated)?;
-                }
-                // 属性类型：更新指定属性的值
-                crate::translation::pipeline::collector::TextType::Attribute(attr_name) => {
+            // 属性文本可能被归为提示、图片替代文本等类型，因此按来源而不是文本类型选择更新方法
+            match &item.attr_name {
+                // 属性文本：更新指定属性的值
+                Some(attr_name) => {
                     self.update_attribute_content(&item.node, attr_name, translated)?;
                 }
+                // 文本内容：更新节点的文本内容
+                None => {
+                    self.update_text_content(&item.node, translated)?;
+                }
             }
         }
 
@@ -10,6 +10,8 @@
 //!
 //! - **服务层** (`service.rs`): 提供统一的翻译服务接口，协调各个子系统
 //! - **引擎层** (`engine.rs`): 处理底层翻译逻辑，包括批次处理和DOM更新
+//! - **并行层** (`concurrent.rs`): 让翻译与资源嵌入在同一个DOM上重叠执行
+//! - **后端层** (`backend.rs`): 向 DeepLX 或按模板配置的自定义接口发送请求
 //!
 //! ## 主要特性
 //!
@@ -50,12 +52,20 @@
 //!             └── TranslationEngine (engine.rs)
 //! ```
 
+pub mod backend;
+pub mod concurrent;
 pub mod engine;
 pub mod service;
 
 // 重新导出核心类型和接口
 // 这些是外部模块最常用的类型，通过重新导出简化使用
 
+/// 翻译后端 - DeepLX 或按模板请求的自定义接口
+pub use backend::{BackendUsage, TranslationBackend};
+
+/// 后台翻译任务 - 与资源嵌入并行执行翻译
+pub use concurrent::ConcurrentTranslation;
+
 /// 翻译引擎 - 处理底层翻译逻辑
 pub use engine::TranslationEngine;
 
@@ -67,7 +77,12 @@ pub use service::TranslationService;
 /// 服务运行统计信息
 /// 
 /// 提供翻译过程中的各项性能指标和运行数据
-pub use service::ServiceStats;
+pub use service::{global_stats, ServiceStats};
+
+/// 单次翻译调用的用量
+///
+/// 记录发出的请求数和收发的字符数，用于按请求核算费用
+pub use service::UsageReport;
 
 /// 系统健康状态检查结果
 /// 
@@ -34,23 +34,34 @@
 //! ```
 
 use markup5ever_rcdom::RcDom;
+use std::collections::BTreeMap;
 use std::sync::{
     atomic::{AtomicU64, AtomicUsize, Ordering},
-    Arc,
+    Arc, Mutex, OnceLock,
 };
 use std::time::Instant;
-
-#[cfg(feature = "translation")]
-use markdown_translator::TranslationService as BaseTranslationService;
+use tokio_util::sync::CancellationToken;
 
 use crate::translation::{
     config::{ConfigManager, TranslationConfig},
-    error::TranslationResult,
-    pipeline::batch::{BatchManager, BatchManagerConfig},
-    pipeline::collector::{CollectorConfig, TextCollector},
-    processor::{ProcessorConfig, TranslationProcessor},
+    core::backend::{BackendUsage, TranslationBackend},
+    error::{TranslationError, TranslationResult},
+    pipeline::batch::{BatchManager, BatchManagerConfig, LanguageConcurrency},
+    pipeline::bilingual::{annotate_originals, BilingualSnapshot},
+    pipeline::collector::{CollectorConfig, TextCollector, TextItem},
+    pipeline::filters::{LanguageDetection, TextFilter},
+    pipeline::marker::{is_translated_to, mark_translated, set_document_lang},
+    pipeline::provenance::{embed_provenance, TranslationProvenance},
+    pipeline::sidecar::{clone_dom, MapSnapshot, TranslationMap},
+    pipeline::verify::TranslationVerifier,
+    pipeline::xliff::{export_xliff, import_xliff},
+    processor::{
+        apply_translation, CharBudget, ProcessorConfig, ProgressCallback, TranslationProcessor,
+        FAILED_SAMPLE_LIMIT,
+    },
     storage::cache::{CacheConfig, CacheManager},
 };
+use crate::utils::url::Url;
 
 /// 统一的翻译服务
 ///
@@ -82,7 +93,13 @@ pub struct TranslationService {
     /// 使用Arc包装以支持多线程访问，只在启用translation特性时可用。
     /// 这是实际执行翻译请求的底层服务。
     #[cfg(feature = "translation")]
-    base_service: Arc<BaseTranslationService>,
+    base_service: Arc<TranslationBackend>,
+
+    /// 创建服务时使用的翻译配置
+    /// 
+    /// 保存目标语言等运行参数，供翻译流程中的快速判断使用。
+    #[cfg(feature = "translation")]
+    config: TranslationConfig,
 
     /// 配置管理器，负责加载和管理翻译配置
     /// 
@@ -104,12 +121,26 @@ pub struct TranslationService {
     /// 支持内存和持久化缓存，避免重复翻译相同内容。
     cache_manager: CacheManager,
 
+    /// 被翻译页面的地址，缓存按其主机名确定有效期和统计命中率
+    document_url: Option<Url>,
+
     /// 翻译处理器，协调缓存查询和实际翻译操作
     /// 
     /// 只在启用translation特性时可用，负责执行具体的翻译流程。
     #[cfg(feature = "translation")]
     processor: TranslationProcessor,
 
+    /// 单次文档翻译的字符预算，与翻译处理器共享
+    #[cfg(feature = "translation")]
+    char_budget: Arc<CharBudget>,
+
+    /// 译文写回DOM后的回调，包括直接使用缓存译文的文本
+    #[cfg(feature = "translation")]
+    progress_callback: Option<ProgressCallback>,
+
+    /// 最近一次 `translate_dom` 调用的用量
+    last_usage: UsageReport,
+
     /// 服务统计信息，记录翻译服务的性能指标
     /// 
     /// 使用原子操作确保线程安全，提供实时的性能监控数据。
@@ -154,20 +185,14 @@ impl TranslationService {
     /// ```
     #[cfg(feature = "translation")]
     pub fn new(config: TranslationConfig) -> TranslationResult<Self> {
-        // 创建基础服务需要的配置结构，转换为底层库所需的格式
-        let base_config = markdown_translator::TranslationConfig {
-            enabled: true,
-            source_lang: config.source_lang.clone(),
-            deeplx_api_url: config.api_url.clone(),
-            ..Default::default()
-        };
-        let base_service = Arc::new(BaseTranslationService::new(base_config));
+        // 创建基础服务，负责与外部翻译API交互
+        let base_service = create_base_service(&config);
 
         // 初始化配置管理器，负责运行时配置的动态管理
         let config_manager = ConfigManager::new()?;
 
-        // 初始化文本收集器，使用默认配置进行DOM文本提取
-        let collector_config = CollectorConfig::default();
+        // 初始化文本收集器，是否收集模板内容由翻译配置决定
+        let collector_config = CollectorConfig::from(&config);
         let text_collector = TextCollector::new(collector_config);
 
         // 初始化批次管理器，根据用户配置优化批次大小
@@ -180,22 +205,39 @@ impl TranslationService {
             local_cache_size: config.local_cache_size,
             default_ttl: config.cache_ttl(),
             enable_warmup: false, // 暂时不启用缓存预热功能
+            domain_ttls: config.cache_domain_ttls(),
             ..Default::default()
         };
         let cache_manager = CacheManager::new(cache_config);
 
         // 初始化翻译处理器，连接基础服务和处理配置
-        let processor_config = ProcessorConfig::default();
+        let char_budget = Arc::new(CharBudget::new(config.max_chars_per_request_job));
+        let processor_config = ProcessorConfig {
+            enable_retry: config.retry_enabled,
+            max_retries: config.max_retry_attempts,
+            best_effort: config.best_effort,
+            char_budget: Arc::clone(&char_budget),
+            verifier: config
+                .verify_translations
+                .then(|| TranslationVerifier::from_config(&config)),
+            ..ProcessorConfig::default()
+        };
         // 使用 Arc::clone 明确表示这是引用计数的克隆，而非数据克隆
-        let processor = TranslationProcessor::new(Arc::clone(&base_service), processor_config);
+        let mut processor = TranslationProcessor::new(Arc::clone(&base_service), processor_config);
+        processor.set_coalescing(Self::coalescing(&config, &cache_manager));
 
         Ok(Self {
             base_service,
+            config,
             config_manager,
             text_collector,
             batch_manager,
             cache_manager,
+            document_url: None,
             processor,
+            char_budget,
+            progress_callback: None,
+            last_usage: UsageReport::default(),
             stats: ServiceStats::default(),
         })
     }
@@ -292,13 +334,132 @@ impl TranslationService {
     /// println!("翻译统计: {:?}", service.get_stats().snapshot());
     /// ```
     pub async fn translate_dom(&mut self, dom: RcDom) -> TranslationResult<RcDom> {
-        let start_time = Instant::now();
         self.stats.reset();
+        #[cfg(feature = "translation")]
+        self.char_budget.reset();
+        // 丢弃自检等其他调用留下的计数
+        self.base_service.take_served();
+        self.base_service.take_usage();
+        let result = self.translate_dom_inner(dom).await;
+        if result.is_err() {
+            self.stats.inc_errors();
+        }
+        let served = self.base_service.take_served();
+        self.stats.record_backends_served(&served);
+        self.last_usage = UsageReport::new(
+            self.base_service.take_usage(),
+            self.stats.cache_hits.load(Ordering::Relaxed),
+        );
+        if let Ok(dom) = &result {
+            self.embed_provenance(dom);
+        }
+        global_stats().accumulate(&self.stats.snapshot());
+        global_stats().record_backends_served(&served);
+        result
+    }
+
+    /// 翻译DOM文档内容，同时返回本次调用的用量
+    ///
+    /// 用量只包含本次调用发出的请求，可用于按请求核算翻译费用。翻译失败时，
+    /// 失败前已发出的请求仍可通过 [`last_usage`](Self::last_usage) 读取。
+    pub async fn translate_dom_with_usage(
+        &mut self,
+        dom: RcDom,
+    ) -> TranslationResult<(RcDom, UsageReport)> {
+        let dom = self.translate_dom(dom).await?;
+        Ok((dom, self.last_usage.clone()))
+    }
+
+    /// 翻译DOM文档内容，`cancellation` 被取消后不再发送剩余的批次
+    ///
+    /// 取消不是错误：返回的页面包含取消前已写回的译文，其余文本保持原文，页面不加已翻译标记。
+    /// 已经发出的请求不会被中断，取消之后最多还会完成正在进行的批次。
+    pub async fn translate_dom_with_cancellation(
+        &mut self,
+        dom: RcDom,
+        cancellation: CancellationToken,
+    ) -> TranslationResult<RcDom> {
+        self.processor.set_cancellation(Some(cancellation));
+        let result = self.translate_dom(dom).await;
+        self.processor.set_cancellation(None);
+        result
+    }
+
+    /// 在文档的副本上翻译，返回未改动的原文档和译文映射表
+    ///
+    /// 映射表可以作为独立的文件保存，之后用 [`apply_translation_map`] 写回原文档，
+    /// 得到与 [`translate_dom`](Self::translate_dom) 相同的页面，重新翻译时也不需要重新抓取。
+    /// 双语显示和原文提示不记录在映射表中。
+    ///
+    /// [`apply_translation_map`]: crate::translation::pipeline::apply_translation_map
+    pub async fn translate_dom_to_map(
+        &mut self,
+        dom: RcDom,
+    ) -> TranslationResult<(RcDom, TranslationMap)> {
+        let copy = clone_dom(&dom);
+        let texts = self
+            .text_collector
+            .collect_translatable_texts(&copy.document)?;
+        let snapshot = MapSnapshot::capture(&copy, &texts);
+
+        let translated = self.translate_dom(copy).await?;
+        let complete = is_translated_to(&translated, &self.config.target_lang);
+        let map = snapshot.finish(
+            &self.config.target_lang,
+            &self.config.document_lang(),
+            complete,
+        );
+        Ok((dom, map))
+    }
+
+    /// 开启 `embed_provenance` 时为本次完整翻译的页面写入来源信息
+    ///
+    /// 跳过翻译（没有文本、已是目标语言）和部分翻译的页面不写入。
+    fn embed_provenance(&self, dom: &RcDom) {
+        let translated = self.stats.texts_collected.load(Ordering::Relaxed) > 0
+            && self.stats.skipped_empty.load(Ordering::Relaxed) == 0;
+        if !self.config.embed_provenance
+            || !translated
+            || !is_translated_to(dom, &self.config.target_lang)
+        {
+            return;
+        }
+        embed_provenance(
+            dom,
+            &TranslationProvenance {
+                source_lang: self
+                    .config
+                    .explicit_source_lang()
+                    .unwrap_or("und")
+                    .to_string(),
+                target_lang: self.config.document_lang(),
+                backends: self.last_usage.backends.keys().cloned().collect(),
+                translated_at: chrono::Utc::now(),
+                char_count: self.last_usage.chars_sent,
+            },
+        );
+    }
+
+    /// 最近一次 `translate_dom` 调用的用量
+    pub fn last_usage(&self) -> &UsageReport {
+        &self.last_usage
+    }
+
+    async fn translate_dom_inner(&mut self, dom: RcDom) -> TranslationResult<RcDom> {
+        let start_time = Instant::now();
 
         tracing::info!("开始DOM翻译处理");
 
         #[cfg(feature = "translation")]
         {
+            // 之前的翻译输出带有标记，再次翻译只会降低质量
+            if !self.config.retranslate && is_translated_to(&dom, &self.config.target_lang) {
+                tracing::info!("页面已翻译为 {}，跳过翻译", self.config.target_lang);
+                self.stats.inc_skipped_translated();
+                self.stats.add_processing_time(start_time.elapsed());
+                return Ok(dom);
+            }
+
             // 第一步：从DOM中收集所有需要翻译的文本内容
             // 文本收集器会智能识别文本节点，排除不需要翻译的内容
             let texts = self
@@ -311,20 +472,116 @@ impl TranslationService {
             // 如果没有找到可翻译的文本，直接返回原始DOM
             if texts.is_empty() {
                 tracing::info!("没有找到需要翻译的文本");
+                self.stats.inc_skipped_empty();
+                self.stats.add_processing_time(start_time.elapsed());
                 return Ok(dom);
             }
 
-            // 第二步：将收集到的文本组织成批次
-            // 批次管理器会根据文本长度和API限制优化批次大小
-            let batches = self.batch_manager.create_batches(texts);
-            self.stats
-                .batches_created
-                .store(batches.len(), Ordering::Relaxed);
+            // 页面的主要语言已是目标语言时无需翻译。
+            // 显式指定了源语言时以配置为准，不再检测
+            if self.config.explicit_source_lang().is_none() {
+                let detection = self.detect_source_language(&texts);
+                if detection.language.matches_lang(&self.config.target_lang)
+                    && detection.confidence > 0.5
+                {
+                    tracing::info!(
+                        "页面语言 {:?}（置信度 {:.2}）已与目标语言 {} 一致，跳过翻译",
+                        detection.language,
+                        detection.confidence,
+                        self.config.target_lang
+                    );
+                    self.stats.inc_skipped_empty();
+                    self.stats.add_processing_time(start_time.elapsed());
+                    return Ok(dom);
+                }
+            }
+
+            // 保留原文提示时需要在译文写回后对照原文
+            let originals = self.config.show_original.then(|| texts.clone());
+            // 双语模式需要在翻译前复制段落
+            let bilingual = self
+                .config
+                .bilingual
+                .then(|| BilingualSnapshot::capture(&dom, &texts));
 
-            // 第三步：处理翻译批次
-            // 翻译处理器会查询缓存，对未缓存的内容执行翻译，并更新DOM
-            self.processor.process_batches(batches).await?;
+            // 已有缓存译文（包括人工修正）的文本直接写回，不再请求翻译
+            let texts = self.apply_cached_translations(texts);
+            // 预算耗尽、被取消或有文本翻译失败时只翻译了部分文本
+            let mut partial = false;
+            if texts.is_empty() {
+                tracing::info!("所有文本均命中缓存");
+            } else {
+                // 第二步：将收集到的文本组织成批次
+                // 批次管理器会根据文本长度和API限制优化批次大小
+                let text_count = texts.len();
+                self.stats
+                    .add_chars_processed(texts.iter().map(|item| item.text.chars().count()).sum());
+                let batches = self.batch_manager.create_batches(texts);
+                self.stats
+                    .batches_created
+                    .store(batches.len(), Ordering::Relaxed);
+
+                // 第三步：处理翻译批次
+                // 翻译处理器对未缓存的内容执行翻译并更新DOM，写回的译文同时写入缓存
+                let callback = self.caching_callback();
+                self.processor.set_progress_callback(callback);
+                let result = self.processor.process_batches(batches).await;
+                let processor_stats = self.processor.get_stats();
+                self.stats
+                    .chars_consumed
+                    .store(self.char_budget.consumed(), Ordering::Relaxed);
+                self.stats.record_failed_items(
+                    processor_stats.skipped_items,
+                    &processor_stats.failed_samples,
+                );
+                self.stats
+                    .translations_rejected
+                    .store(processor_stats.rejected_items, Ordering::Relaxed);
+                match result {
+                    // 预算耗尽：保留已写回的译文，未翻译的文本保持原文
+                    Err(error @ TranslationError::BudgetExceeded { .. }) => {
+                        tracing::warn!("{}，返回部分翻译的页面", error);
+                        self.stats.inc_budget_exceeded();
+                        partial = true;
+                    }
+                    // 已取消：同样返回已写回的部分译文
+                    Err(TranslationError::Cancelled) => {
+                        tracing::info!("翻译已取消，返回部分翻译的页面");
+                        self.stats
+                            .translations_cancelled
+                            .fetch_add(1, Ordering::Relaxed);
+                        partial = true;
+                    }
+                    result => result?,
+                }
+                self.stats.translations_completed.fetch_add(
+                    text_count.saturating_sub(processor_stats.skipped_items),
+                    Ordering::Relaxed,
+                );
+                // 尽力而为模式下失败或被拒绝的文本保持原文
+                if processor_stats.skipped_items > 0 {
+                    tracing::warn!(
+                        "{} 个文本未能翻译，返回部分翻译的页面",
+                        processor_stats.skipped_items
+                    );
+                    partial = true;
+                }
+            }
             let translated_dom = dom; // 实际的DOM修改应该在processor中完成
+            if !partial {
+                // 部分翻译的页面不加标记，之后仍可以重新翻译剩余的文本
+                mark_translated(&translated_dom, &self.config.target_lang);
+                set_document_lang(&translated_dom, &self.config.document_lang());
+            }
+
+            if let Some(originals) = originals {
+                let annotated = annotate_originals(&translated_dom, &originals);
+                tracing::info!("已为 {} 个元素保留原文提示", annotated);
+            }
+            if let Some(snapshot) = bilingual {
+                let inserted = snapshot.insert_originals(&translated_dom);
+                tracing::info!("已插入 {} 个原文段落", inserted);
+            }
 
             // 记录处理时间并输出日志
             self.stats.add_processing_time(start_time.elapsed());
@@ -340,6 +597,169 @@ impl TranslationService {
         }
     }
 
+    /// 判断收集到的文本的主要语言
+    ///
+    /// 按 `language_sample_size` 抽取最长的文本段，结果包含主要语言和置信度。
+    pub fn detect_source_language(&self, texts: &[TextItem]) -> LanguageDetection {
+        TextFilter::new().detect_dominant_language(
+            texts.iter().map(|item| item.text.as_str()),
+            self.config.language_sample_size,
+        )
+    }
+
+    /// 把页面中需要翻译的文本导出为 XLIFF 2.0，交给人工翻译
+    ///
+    /// 收集和过滤规则与 [`translate_dom`](Self::translate_dom) 相同。未配置源语言时
+    /// `srcLang` 写为 `und`。
+    pub fn export_xliff(&mut self, dom: &RcDom) -> TranslationResult<String> {
+        let texts = self
+            .text_collector
+            .collect_translatable_texts(&dom.document)?;
+        Ok(export_xliff(
+            &texts,
+            self.config.explicit_source_lang().unwrap_or("und"),
+            &self.config.target_lang,
+        ))
+    }
+
+    /// 把译者返回的 XLIFF 译文写回页面，返回写回的文本数量
+    ///
+    /// 页面需要与导出时相同（或只有部分改动），单元按 id 对应；写回了译文的页面会带上已翻译标记。
+    pub fn import_xliff(&mut self, dom: &RcDom, xliff: &str) -> TranslationResult<usize> {
+        let texts = self
+            .text_collector
+            .collect_translatable_texts(&dom.document)?;
+        let applied = import_xliff(&texts, xliff)?;
+        if applied > 0 {
+            mark_translated(dom, &self.config.target_lang);
+            set_document_lang(dom, &self.config.document_lang());
+        }
+        Ok(applied)
+    }
+
+    /// 使用外部提供的缓存管理器
+    ///
+    /// 多个服务实例共享同一个缓存管理器时，写入其中的人工修正对所有实例生效。
+    pub fn with_cache_manager(mut self, cache_manager: CacheManager) -> Self {
+        self.processor
+            .set_coalescing(Self::coalescing(&self.config, &cache_manager));
+        self.cache_manager = cache_manager;
+        self
+    }
+
+    /// 设置被翻译页面的地址
+    ///
+    /// 之后查询和写入缓存时使用 [`CacheManager::lookup_for_url`] 和
+    /// [`CacheManager::insert_for_url`]，新的译文按该页面的主机名确定有效期。
+    pub fn with_document_url(mut self, url: Url) -> Self {
+        self.document_url = Some(url);
+        self
+    }
+
+    /// 启用 `coalesce_requests` 时，相同的请求在共享缓存管理器的服务实例之间合并
+    fn coalescing(
+        config: &TranslationConfig,
+        cache_manager: &CacheManager,
+    ) -> Option<(CacheManager, String, String)> {
+        config.coalesce_requests.then(|| {
+            (
+                cache_manager.clone(),
+                config.source_lang.clone(),
+                config.target_lang.clone(),
+            )
+        })
+    }
+
+    /// 每写回一条译文时调用回调
+    ///
+    /// 命中缓存的文本在翻译开始前写回，其余文本在所属批次翻译完成时写回。
+    #[cfg(feature = "translation")]
+    pub fn with_progress_callback(mut self, callback: ProgressCallback) -> Self {
+        self.progress_callback = Some(callback);
+        self
+    }
+
+    /// 与其他服务实例共享按语言分配的并发额度
+    ///
+    /// 同时翻译多种语言的服务实例使用同一个 [`LanguageConcurrency`]（克隆即可共享）时，
+    /// 它们发出的请求总数不超过其全局上限，本服务的目标语言按配置的权重分得额度。
+    #[cfg(feature = "translation")]
+    pub fn with_language_concurrency(mut self, concurrency: LanguageConcurrency) -> Self {
+        self.processor
+            .set_language_concurrency(Some((concurrency, self.config.target_lang.clone())));
+        self
+    }
+
+    /// 将命中缓存的文本直接写回DOM，返回仍需翻译的文本
+    #[cfg(feature = "translation")]
+    fn apply_cached_translations(&self, texts: Vec<TextItem>) -> Vec<TextItem> {
+        let target_lang = &self.config.target_lang;
+        texts
+            .into_iter()
+            .filter(|item| {
+                let entry = match &self.document_url {
+                    Some(url) => self
+                        .cache_manager
+                        .lookup_for_url(&item.text, target_lang, url),
+                    None => self.cache_manager.lookup(&item.text, target_lang),
+                };
+                let entry = match entry {
+                    Some(entry) => entry,
+                    None => {
+                        self.stats.inc_cache_misses();
+                        return true;
+                    }
+                };
+                match apply_translation(item, &entry.translated_text) {
+                    Ok(()) => {
+                        self.stats.inc_cache_hits();
+                        if let Some(callback) = &self.progress_callback {
+                            for occurrence in item.occurrences() {
+                                callback.notify(occurrence, &entry.translated_text);
+                            }
+                        }
+                        false
+                    }
+                    Err(_) => true,
+                }
+            })
+            .collect()
+    }
+
+    /// 译文写回DOM后写入缓存，再转发给 [`with_progress_callback`](Self::with_progress_callback)
+    /// 设置的回调
+    #[cfg(feature = "translation")]
+    fn caching_callback(&self) -> Option<ProgressCallback> {
+        if !self.config.cache_enabled {
+            return self.progress_callback.clone();
+        }
+        let cache_manager = self.cache_manager.clone();
+        let source_lang = self.config.source_lang.clone();
+        let target_lang = self.config.target_lang.clone();
+        let document_url = self.document_url.clone();
+        let callback = self.progress_callback.clone();
+        Some(ProgressCallback::new(move |item, translated| {
+            match &document_url {
+                Some(url) => cache_manager.insert_for_url(
+                    item.text.clone(),
+                    translated.to_string(),
+                    source_lang.clone(),
+                    target_lang.clone(),
+                    url,
+                ),
+                None => cache_manager.insert_with_lang(
+                    item.text.clone(),
+                    translated.to_string(),
+                    source_lang.clone(),
+                    target_lang.clone(),
+                ),
+            }
+            if let Some(callback) = &callback {
+                callback.notify(item, translated);
+            }
+        }))
+    }
+
     /// 获取服务统计信息的只读引用
     ///
     /// 返回当前翻译服务的统计信息，包括处理的文本数量、缓存命中率、
@@ -448,6 +868,89 @@ impl TranslationService {
             components,
         }
     }
+
+    /// 向翻译后端发送一条探测文本，确认接口可用
+    ///
+    /// 与 [`get_health`](Self::get_health) 不同，这里会真正发起一次翻译请求，
+    /// 用于在启动时发现错误的 API 地址、缺失的认证信息或不兼容的响应格式。
+    /// 成功时返回探测文本的译文。
+    #[cfg(feature = "translation")]
+    pub async fn self_check(&self) -> TranslationResult<String> {
+        let timeout = std::time::Duration::from_secs(self.config.batch_timeout_secs.max(1));
+        let result = tokio::time::timeout(timeout, self.base_service.translate(SELF_CHECK_TEXT))
+            .await
+            .map_err(|_| TranslationError::TimeoutError(format!("{:?} 内没有响应", timeout)))
+            .and_then(|result| result);
+
+        let translated = match result {
+            Ok(translated) if !translated.trim().is_empty() => translated,
+            Ok(_) => {
+                return Err(TranslationError::TranslationServiceError(format!(
+                    "翻译服务自检失败 ({}): 返回了空译文",
+                    self.config.api_url
+                )))
+            }
+            Err(e) => {
+                return Err(TranslationError::TranslationServiceError(format!(
+                    "翻译服务自检失败 ({}): {}",
+                    self.config.api_url, e
+                )))
+            }
+        };
+
+        tracing::info!(
+            "翻译服务自检通过: {:?} -> {:?}",
+            SELF_CHECK_TEXT,
+            translated
+        );
+        Ok(translated)
+    }
+}
+
+/// 自检时发送的探测文本
+#[cfg(feature = "translation")]
+const SELF_CHECK_TEXT: &str = "Hello";
+
+/// 根据翻译配置创建底层翻译后端
+///
+/// 配置了 `request_template` 时使用自定义接口，否则使用内置的 DeepLX 客户端。
+#[cfg(feature = "translation")]
+pub(crate) fn create_base_service(config: &TranslationConfig) -> Arc<TranslationBackend> {
+    Arc::new(TranslationBackend::from_config(config))
+}
+
+/// 一次DOM翻译的用量
+///
+/// 字符数和请求数在后端发出请求时计数：重试和后端链回退的每次尝试都单独计入，
+/// 与其他服务合并的相同请求只计入实际发出请求的一方。
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct UsageReport {
+    /// 发送给翻译服务的字符数
+    pub chars_sent: usize,
+    /// 收到的译文字符数
+    pub chars_received: usize,
+    /// 发出的翻译请求数，包括失败的请求
+    pub api_requests: usize,
+    /// 直接使用缓存译文的文本数
+    pub cache_hits: usize,
+    /// 各后端的用量，按后端名称排列
+    pub backends: BTreeMap<String, BackendUsage>,
+}
+
+impl UsageReport {
+    fn new(backends: BTreeMap<String, BackendUsage>, cache_hits: usize) -> Self {
+        let mut total = BackendUsage::default();
+        for usage in backends.values() {
+            total.add(usage);
+        }
+        Self {
+            chars_sent: total.chars_sent,
+            chars_received: total.chars_received,
+            api_requests: total.requests,
+            cache_hits,
+            backends,
+        }
+    }
 }
 
 /// 翻译服务统计信息（线程安全版本）
@@ -471,6 +974,11 @@ impl TranslationService {
 /// - `processing_time`: 总处理时间（微秒），用于性能分析
 /// - `total_chars_processed`: 处理的字符总数，用于评估工作量
 /// - `errors_encountered`: 遇到的错误次数，用于质量监控
+/// - `skipped_translated`: 页面已带有已翻译标记而跳过的次数
+/// - `chars_consumed`: 实际发送给翻译服务的字符数，包括重试
+/// - `translations_rejected`: 未通过校验、保持原文的译文数量
+/// - `translations_cancelled`: 被调用方取消、提前结束的翻译次数
+/// - `backends_served`: 配置了后端链时，各后端成功响应的请求数
 #[derive(Debug, Default)]
 pub struct ServiceStats {
     /// 收集到的可翻译文本数量
@@ -496,6 +1004,33 @@ pub struct ServiceStats {
     
     /// 遇到的错误次数
     pub errors_encountered: AtomicUsize,
+
+    /// 因没有可翻译内容而直接返回的次数
+    pub skipped_empty: AtomicUsize,
+
+    /// 因页面已带有目标语言的已翻译标记而直接返回的次数
+    pub skipped_translated: AtomicUsize,
+
+    /// 翻译失败、保持原文的文本数量
+    pub items_failed: AtomicUsize,
+
+    /// 失败文本的样本（最多 [`FAILED_SAMPLE_LIMIT`] 条）
+    pub failed_samples: Mutex<Vec<String>>,
+
+    /// 实际发送给翻译服务的字符数（包括重试和回退请求）
+    pub chars_consumed: AtomicUsize,
+
+    /// 因超出字符预算而提前结束的翻译次数
+    pub budget_exceeded: AtomicUsize,
+
+    /// 未通过校验、保持原文的译文数量（同时计入 `items_failed`）
+    pub translations_rejected: AtomicUsize,
+
+    /// 被调用方取消、提前结束的翻译次数
+    pub translations_cancelled: AtomicUsize,
+
+    /// 后端链中各后端成功响应的请求数，按后端名称排列
+    pub backends_served: Mutex<BTreeMap<String, usize>>,
 }
 
 impl ServiceStats {
@@ -573,6 +1108,55 @@ impl ServiceStats {
         self.errors_encountered.fetch_add(1, Ordering::Relaxed);
     }
 
+    /// 增加空页面跳过计数
+    ///
+    /// 当页面已是目标语言或过滤后没有可翻译文本、翻译被直接跳过时调用。
+    pub fn inc_skipped_empty(&self) {
+        self.skipped_empty.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 增加已翻译页面跳过计数
+    ///
+    /// 当页面带有目标语言的已翻译标记、未开启 `retranslate` 而跳过翻译时调用。
+    pub fn inc_skipped_translated(&self) {
+        self.skipped_translated.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 增加预算耗尽计数
+    ///
+    /// 当一次文档翻译因超出 `max_chars_per_request_job` 而提前结束时调用。
+    pub fn inc_budget_exceeded(&self) {
+        self.budget_exceeded.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 记录翻译失败的文本
+    ///
+    /// 尽力而为模式下失败批次的文本保持原文，这里累计其数量并保留少量样本。
+    pub fn record_failed_items(&self, count: usize, samples: &[String]) {
+        self.items_failed.fetch_add(count, Ordering::Relaxed);
+        let mut failed_samples = self.failed_samples.lock().unwrap();
+        let remaining = FAILED_SAMPLE_LIMIT.saturating_sub(failed_samples.len());
+        failed_samples.extend(samples.iter().take(remaining).cloned());
+    }
+
+    /// 失败文本样本的副本
+    pub fn failed_samples(&self) -> Vec<String> {
+        self.failed_samples.lock().unwrap().clone()
+    }
+
+    /// 累加各后端成功响应的请求数
+    pub fn record_backends_served(&self, served: &BTreeMap<String, usize>) {
+        let mut backends_served = self.backends_served.lock().unwrap();
+        for (name, count) in served {
+            *backends_served.entry(name.clone()).or_insert(0) += count;
+        }
+    }
+
+    /// 各后端成功响应的请求数的副本
+    pub fn backends_served(&self) -> BTreeMap<String, usize> {
+        self.backends_served.lock().unwrap().clone()
+    }
+
     /// 获取统计数据的一致性快照
     ///
     /// 由于统计数据使用原子操作，在高并发情况下不同字段可能在不同时刻读取。
@@ -617,10 +1201,59 @@ impl ServiceStats {
             ),
             total_chars_processed: self.total_chars_processed.load(Ordering::Relaxed),
             errors_encountered: self.errors_encountered.load(Ordering::Relaxed),
+            skipped_empty: self.skipped_empty.load(Ordering::Relaxed),
+            skipped_translated: self.skipped_translated.load(Ordering::Relaxed),
+            items_failed: self.items_failed.load(Ordering::Relaxed),
+            chars_consumed: self.chars_consumed.load(Ordering::Relaxed),
+            budget_exceeded: self.budget_exceeded.load(Ordering::Relaxed),
+            translations_rejected: self.translations_rejected.load(Ordering::Relaxed),
+            translations_cancelled: self.translations_cancelled.load(Ordering::Relaxed),
         }
     }
 }
 
+/// 进程内所有翻译服务的累计统计
+///
+/// 每个 [`TranslationService`] 在 `translate_dom` 开始时重置自身的统计，
+/// 结束时把本次的数据累加到这里，供监控接口读取。
+pub fn global_stats() -> &'static ServiceStats {
+    static GLOBAL_STATS: OnceLock<ServiceStats> = OnceLock::new();
+    GLOBAL_STATS.get_or_init(ServiceStats::default)
+}
+
+impl ServiceStats {
+    /// 累加一次翻译的统计快照
+    pub fn accumulate(&self, snapshot: &ServiceStatsSnapshot) {
+        self.texts_collected
+            .fetch_add(snapshot.texts_collected, Ordering::Relaxed);
+        self.batches_created
+            .fetch_add(snapshot.batches_created, Ordering::Relaxed);
+        self.translations_completed
+            .fetch_add(snapshot.translations_completed, Ordering::Relaxed);
+        self.cache_hits.fetch_add(snapshot.cache_hits, Ordering::Relaxed);
+        self.cache_misses
+            .fetch_add(snapshot.cache_misses, Ordering::Relaxed);
+        self.add_processing_time(snapshot.processing_time);
+        self.add_chars_processed(snapshot.total_chars_processed);
+        self.errors_encountered
+            .fetch_add(snapshot.errors_encountered, Ordering::Relaxed);
+        self.skipped_empty
+            .fetch_add(snapshot.skipped_empty, Ordering::Relaxed);
+        self.skipped_translated
+            .fetch_add(snapshot.skipped_translated, Ordering::Relaxed);
+        self.items_failed
+            .fetch_add(snapshot.items_failed, Ordering::Relaxed);
+        self.chars_consumed
+            .fetch_add(snapshot.chars_consumed, Ordering::Relaxed);
+        self.budget_exceeded
+            .fetch_add(snapshot.budget_exceeded, Ordering::Relaxed);
+        self.translations_rejected
+            .fetch_add(snapshot.translations_rejected, Ordering::Relaxed);
+        self.translations_cancelled
+            .fetch_add(snapshot.translations_cancelled, Ordering::Relaxed);
+    }
+}
+
 /// 翻译服务统计数据的不可变快照
 ///
 /// 此结构体包含了某个时间点的所有统计数据副本，使用普通的数值类型而非原子类型。
@@ -662,6 +1295,27 @@ pub struct ServiceStatsSnapshot {
     
     /// 遇到的错误次数
     pub errors_encountered: usize,
+
+    /// 因没有可翻译内容而直接返回的次数
+    pub skipped_empty: usize,
+
+    /// 因页面已带有目标语言的已翻译标记而直接返回的次数
+    pub skipped_translated: usize,
+
+    /// 翻译失败、保持原文的文本数量
+    pub items_failed: usize,
+
+    /// 实际发送给翻译服务的字符数（包括重试和回退请求）
+    pub chars_consumed: usize,
+
+    /// 因超出字符预算而提前结束的翻译次数
+    pub budget_exceeded: usize,
+
+    /// 未通过校验、保持原文的译文数量
+    pub translations_rejected: usize,
+
+    /// 被调用方取消、提前结束的翻译次数
+    pub translations_cancelled: usize,
 }
 
 impl ServiceStats {
@@ -735,3 +1389,497 @@ pub enum HealthLevel {
     /// 不健康状态 - 组件无法正常工作
     Unhealthy,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::translation::config::RequestTemplate;
+    use crate::translation::test_backend::{closed_url, TestBackend};
+
+    fn template_config(api_url: String) -> TranslationConfig {
+        TranslationConfig {
+            api_url,
+            batch_timeout_secs: 5,
+            request_template: Some(RequestTemplate {
+                body: r#"{"q": "{{text}}", "target": "{{target}}"}"#.to_string(),
+                response_path: "translatedText".to_string(),
+                headers: Default::default(),
+            }),
+            ..TranslationConfig::default_with_lang("zh", None)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_self_check() {
+        let backend = TestBackend::fixed("200 OK", r#"{"translatedText": "你好"}"#);
+        let url = backend.url.clone();
+
+        let service = TranslationService::new(template_config(url)).unwrap();
+        assert_eq!(service.self_check().await.unwrap(), "你好");
+
+        // 没有服务监听的端口
+        let url = closed_url();
+        let service = TranslationService::new(template_config(url)).unwrap();
+        assert!(service.self_check().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_explicit_source_lang_skips_detection() {
+        let backend = TestBackend::fixed("200 OK", r#"{"translatedText": "こんにちは"}"#);
+        let url = backend.url.clone();
+
+        let mut config = template_config(url);
+        config.target_lang = "ja".to_string();
+        if let Some(template) = config.request_template.as_mut() {
+            template.body =
+                r#"{"q": "{{text}}", "source": "{{source}}", "target": "{{target}}"}"#.to_string();
+        }
+        let html = "<html><body><p>ひらがなとカタカナのページです</p></body></html>";
+
+        // 自动检测：页面已是目标语言，不发送请求
+        let mut service = TranslationService::new(config.clone()).unwrap();
+        service
+            .translate_dom(crate::html::html_to_dom(
+                html.as_bytes(),
+                "UTF-8".to_string(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(service.get_stats().snapshot().skipped_empty, 1);
+        assert!(backend.requests().is_empty());
+
+        // 显式指定源语言：跳过检测，源语言随请求发送
+        config.source_lang = "zh".to_string();
+        let mut service = TranslationService::new(config).unwrap();
+        service
+            .translate_dom(crate::html::html_to_dom(
+                html.as_bytes(),
+                "UTF-8".to_string(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(service.get_stats().snapshot().skipped_empty, 0);
+        let requests = backend.requests();
+        assert!(!requests.is_empty());
+        assert!(requests[0].contains(r#""source": "zh""#), "{}", requests[0]);
+    }
+
+    #[tokio::test]
+    async fn test_translated_marker_skips_translation() {
+        // 没有服务监听的端口：任何翻译请求都会失败
+        let url = closed_url();
+
+        let html = r#"<html><head><meta name="monolith-translated" content="zh-CN"></head><body><p>This page was already translated once.</p></body></html>"#;
+
+        let mut service = TranslationService::new(template_config(url.clone())).unwrap();
+        service
+            .translate_dom(crate::html::html_to_dom(
+                html.as_bytes(),
+                "UTF-8".to_string(),
+            ))
+            .await
+            .unwrap();
+        let stats = service.get_stats().snapshot();
+        assert_eq!(stats.skipped_translated, 1);
+        assert_eq!(stats.skipped_empty, 0);
+
+        // 强制重新翻译时照常发送请求
+        let mut config = template_config(url);
+        config.retranslate = true;
+        let mut service = TranslationService::new(config).unwrap();
+        service
+            .translate_dom(crate::html::html_to_dom(
+                html.as_bytes(),
+                "UTF-8".to_string(),
+            ))
+            .await
+            .ok();
+        assert_eq!(service.get_stats().snapshot().skipped_translated, 0);
+    }
+
+    #[tokio::test]
+    async fn test_budget_exceeded_returns_partial_page() {
+        // 没有服务监听的端口：超出预算的请求根本不会发送
+        let url = closed_url();
+
+        let mut config = template_config(url);
+        config.max_chars_per_request_job = Some(10);
+        let html = "<html><body><p>This paragraph is longer than the budget.</p></body></html>";
+
+        let mut service = TranslationService::new(config).unwrap();
+        let dom = service
+            .translate_dom(crate::html::html_to_dom(
+                html.as_bytes(),
+                "UTF-8".to_string(),
+            ))
+            .await
+            .unwrap();
+
+        let stats = service.get_stats().snapshot();
+        assert_eq!(stats.budget_exceeded, 1);
+        assert_eq!(stats.chars_consumed, 0);
+        assert_eq!(stats.errors_encountered, 0);
+        assert_eq!(stats.items_failed, 1);
+        assert!(!is_translated_to(&dom, "zh"));
+    }
+
+    #[tokio::test]
+    async fn test_rejected_translations_keep_original() {
+        let backend = TestBackend::fixed(
+            "200 OK",
+            r#"{"translatedText": "API quota exceeded, please try again later"}"#,
+        );
+        let url = backend.url.clone();
+        let html = "<html><body><p>Archived pages keep working long after the site is gone.</p></body></html>";
+        let translate = |verify_translations: bool| {
+            let url = url.clone();
+            async move {
+                let mut config = template_config(url);
+                config.verify_translations = verify_translations;
+                let mut service = TranslationService::new(config).unwrap();
+                let dom = service
+                    .translate_dom(crate::html::html_to_dom(
+                        html.as_bytes(),
+                        "UTF-8".to_string(),
+                    ))
+                    .await
+                    .unwrap();
+                let output = String::from_utf8_lossy(&crate::html::serialize_document(
+                    dom,
+                    "UTF-8".to_string(),
+                    &Default::default(),
+                ))
+                .to_string();
+                (output, service.get_stats().snapshot())
+            }
+        };
+
+        let (output, stats) = translate(true).await;
+        assert!(output.contains("Archived pages keep working"));
+        assert!(!output.contains("quota"));
+        assert_eq!(stats.translations_rejected, 1);
+        assert_eq!(stats.items_failed, 1);
+        // 有文本保持原文的页面是部分翻译，不加已翻译标记，之后仍可以重新翻译
+        assert!(!output.contains("monolith-translated"));
+
+        let (output, stats) = translate(false).await;
+        assert!(output.contains("API quota exceeded"));
+        assert_eq!(stats.translations_rejected, 0);
+        assert!(output.contains("monolith-translated"));
+    }
+
+    #[tokio::test]
+    async fn test_usage_counts_each_attempt() {
+        let primary_backend =
+            TestBackend::fixed("500 Internal Server Error", r#"{"message": "busy"}"#);
+        let fallback_backend = TestBackend::fixed("200 OK", r#"{"translatedText": "你好，世界"}"#);
+        let backend = |name: &str, api_url: String| crate::translation::BackendConfig {
+            name: name.to_string(),
+            api_url,
+            request_template: template_config(String::new()).request_template,
+        };
+        let config = TranslationConfig {
+            backends: vec![
+                backend("primary", primary_backend.url.clone()),
+                backend("fallback", fallback_backend.url.clone()),
+            ],
+            ..template_config(String::new())
+        };
+
+        let html = "<html><body><p>Hello, world</p></body></html>";
+        let cache_manager = CacheManager::new(CacheConfig::default());
+        let mut service = TranslationService::new(config)
+            .unwrap()
+            .with_cache_manager(cache_manager.clone());
+        let (_, usage) = service
+            .translate_dom_with_usage(crate::html::html_to_dom(
+                html.as_bytes(),
+                "UTF-8".to_string(),
+            ))
+            .await
+            .unwrap();
+
+        // 每次请求都先发给主后端，失败的请求同样计入
+        let sent = fallback_backend.requests().len();
+        assert!(sent >= 1);
+        assert_eq!(primary_backend.requests().len(), sent);
+        assert_eq!(usage.api_requests, 2 * sent);
+        let (primary, fallback) = (usage.backends["primary"], usage.backends["fallback"]);
+        assert_eq!(primary.requests, sent);
+        assert_eq!(primary.chars_received, 0);
+        assert_eq!(fallback.requests, sent);
+        assert_eq!(fallback.chars_sent, primary.chars_sent);
+        assert!(primary.chars_sent >= sent * "Hello, world".len());
+        assert_eq!(usage.chars_sent, 2 * primary.chars_sent);
+        assert_eq!(usage.chars_received, sent * "你好，世界".chars().count());
+        assert_eq!(usage.cache_hits, 0);
+
+        // 译文已缓存时不发送请求，之前的请求也不计入
+        cache_manager.insert_with_lang(
+            "Hello, world".to_string(),
+            "你好，世界".to_string(),
+            "auto".to_string(),
+            "zh".to_string(),
+        );
+        let (_, usage) = service
+            .translate_dom_with_usage(crate::html::html_to_dom(
+                html.as_bytes(),
+                "UTF-8".to_string(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(fallback_backend.requests().len(), sent);
+        assert_eq!(usage.api_requests, 0);
+        assert_eq!(usage.cache_hits, 1);
+        assert!(usage.backends.is_empty());
+        assert_eq!(service.last_usage(), &usage);
+    }
+
+    #[tokio::test]
+    async fn test_translations_cached_with_document_domain_ttl() {
+        use crate::html::html_to_dom;
+        use std::time::Duration;
+
+        let backend = TestBackend::fixed("200 OK", r#"{"translatedText": "你好"}"#);
+        let url = backend.url.clone();
+        let mut config = template_config(url);
+        config.cache_domain_ttl_secs = [
+            ("news.example.com".to_string(), 60),
+            ("docs.example.com".to_string(), 3600),
+        ]
+        .into_iter()
+        .collect();
+        let cache_manager = CacheManager::new(CacheConfig {
+            domain_ttls: config.cache_domain_ttls(),
+            ..CacheConfig::default()
+        });
+        let translate = |page: &'static str, text: &'static str| {
+            let mut service = TranslationService::new(config.clone())
+                .unwrap()
+                .with_cache_manager(cache_manager.clone())
+                .with_document_url(Url::parse(page).unwrap());
+            async move {
+                let html = format!("<html><body><p>{text}</p></body></html>");
+                service
+                    .translate_dom(html_to_dom(html.as_bytes(), "UTF-8".to_string()))
+                    .await
+                    .unwrap();
+                service.get_stats().snapshot()
+            }
+        };
+
+        translate("https://news.example.com/today", "Breaking story").await;
+        translate("https://docs.example.com/guide", "Install the tool").await;
+        let sent = backend.requests().len();
+        assert_eq!(sent, 2);
+
+        // 写入时按来源页面的主机名确定有效期
+        let news = cache_manager.lookup("Breaking story", "zh").unwrap();
+        assert_eq!(news.domain.as_deref(), Some("news.example.com"));
+        assert_eq!(news.ttl, Some(Duration::from_secs(60)));
+        let docs = cache_manager.lookup("Install the tool", "zh").unwrap();
+        assert_eq!(docs.domain.as_deref(), Some("docs.example.com"));
+        assert_eq!(docs.ttl, Some(Duration::from_secs(3600)));
+
+        // 再次翻译同一页面时命中缓存，命中情况计入该主机名
+        let stats = translate("https://news.example.com/today", "Breaking story").await;
+        assert_eq!(stats.cache_hits, 1);
+        assert_eq!(backend.requests().len(), sent);
+        let domains = cache_manager.get_stats().domains;
+        assert_eq!(domains["news.example.com"].hits, 1);
+        assert_eq!(domains["news.example.com"].misses, 1);
+        assert_eq!(domains["docs.example.com"].misses, 1);
+    }
+
+    #[tokio::test]
+    async fn test_translation_map_round_trip() {
+        use crate::html::html_to_dom;
+        use crate::translation::pipeline::{apply_translation_map, ApplyStats};
+
+        let backend = TestBackend::fixed("200 OK", r#"{"translatedText": "你好，世界"}"#);
+        let url = backend.url.clone();
+        let html = "<html><head><title>Greeting page</title></head><body>\
+                    <p> Hello, world </p><img src=\"a.png\" alt=\"A friendly wave\">\
+                    <template><p>Template greeting</p></template></body></html>";
+        let serialize = |dom: &RcDom| {
+            let mut data = Vec::new();
+            let document: markup5ever_rcdom::SerializableHandle = dom.document.clone().into();
+            html5ever::serialize::serialize(&mut data, &document, Default::default()).unwrap();
+            String::from_utf8(data).unwrap()
+        };
+        let mut config = template_config(url);
+        config.translate_templates = true;
+
+        let mut service = TranslationService::new(config.clone())
+            .unwrap()
+            .with_cache_manager(CacheManager::new(CacheConfig::default()));
+        let direct = service
+            .translate_dom(html_to_dom(html.as_bytes(), "UTF-8".to_string()))
+            .await
+            .unwrap();
+
+        let mut service = TranslationService::new(config)
+            .unwrap()
+            .with_cache_manager(CacheManager::new(CacheConfig::default()));
+        let original = html_to_dom(html.as_bytes(), "UTF-8".to_string());
+        let untouched = serialize(&original);
+        let (original, map) = service.translate_dom_to_map(original).await.unwrap();
+        assert_eq!(serialize(&original), untouched);
+        assert_eq!(map.units.len(), 4);
+        assert_eq!(
+            map.units["/html[1]/body[1]/p[1]/text()[1]"].source,
+            "Hello, world"
+        );
+        assert!(map.units.contains_key("/html[1]/body[1]/img[1]/@alt"));
+        assert!(map
+            .units
+            .contains_key("/html[1]/body[1]/template[1]/content()/p[1]/text()[1]"));
+
+        // 经过 JSON 保存再写回，与直接翻译的页面相同
+        let map = TranslationMap::from_json(&map.to_json().unwrap()).unwrap();
+        assert_eq!(apply_translation_map(&original, &map).applied, 4);
+        assert_eq!(serialize(&original), serialize(&direct));
+
+        // 原文已改变的文本不写回，页面也不标记为已翻译
+        let edited = html.replace(" Hello, world ", "Goodbye, world");
+        let edited = html_to_dom(edited.as_bytes(), "UTF-8".to_string());
+        assert_eq!(
+            apply_translation_map(&edited, &map),
+            ApplyStats {
+                applied: 3,
+                skipped: 1
+            }
+        );
+        assert!(serialize(&edited).contains("Goodbye, world"));
+        assert!(!is_translated_to(&edited, "zh"));
+    }
+
+    #[tokio::test]
+    async fn test_document_lang_follows_target() {
+        let backend = TestBackend::fixed("200 OK", r#"{"translatedText": "こんにちは、世界"}"#);
+        let url = backend.url.clone();
+        let html = "<html lang=\"en\"><body><p>Hello, world</p></body></html>";
+
+        for (target_lang, expected) in [("ja", "ja"), ("ja-JP", "ja")] {
+            let mut config = template_config(url.clone());
+            config.target_lang = target_lang.to_string();
+            let mut service = TranslationService::new(config)
+                .unwrap()
+                .with_cache_manager(CacheManager::new(CacheConfig::default()));
+            let dom = service
+                .translate_dom(crate::html::html_to_dom(
+                    html.as_bytes(),
+                    "UTF-8".to_string(),
+                ))
+                .await
+                .unwrap();
+
+            let root = crate::html::find_nodes(&dom.document, vec!["html"]).remove(0);
+            assert_eq!(
+                crate::html::get_node_attr(&root, "lang").as_deref(),
+                Some(expected)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_provenance_json_ld() {
+        let backend = TestBackend::fixed("200 OK", r#"{"translatedText": "你好，世界"}"#);
+        let url = backend.url.clone();
+        let html = "<html><head><title>T</title></head><body><p>Hello, world</p></body></html>";
+
+        let mut config = template_config(url);
+        config.embed_provenance = true;
+        let source_lang = config.explicit_source_lang().unwrap_or("und").to_string();
+        let mut service = TranslationService::new(config)
+            .unwrap()
+            .with_cache_manager(CacheManager::new(CacheConfig::default()));
+        let dom = service
+            .translate_dom(crate::html::html_to_dom(
+                html.as_bytes(),
+                "UTF-8".to_string(),
+            ))
+            .await
+            .unwrap();
+
+        let json_ld = crate::translation::pipeline::provenance::read_provenance(&dom).unwrap();
+        assert_eq!(json_ld["@context"], "https://schema.org");
+        assert_eq!(json_ld["inLanguage"], "zh");
+        assert_eq!(
+            json_ld["translationOfWork"]["inLanguage"],
+            source_lang.as_str()
+        );
+        assert_eq!(json_ld["translator"][0]["name"], "template");
+        assert_eq!(
+            json_ld["additionalProperty"]["value"],
+            service.last_usage().chars_sent
+        );
+        assert!(json_ld["dateCreated"].is_string());
+
+        // 来源信息本身不会被收集
+        let texts = TextCollector::default()
+            .collect_all_texts(&dom.document)
+            .into_iter()
+            .map(|item| item.text)
+            .collect::<Vec<_>>();
+        assert!(texts.iter().all(|text| !text.contains("schema.org")));
+    }
+
+    #[tokio::test]
+    async fn test_repeated_texts_share_one_translation() {
+        let backend = TestBackend::fixed("200 OK", r#"{"translatedText": "阅读更多"}"#);
+        let url = backend.url.clone();
+        let links: String = (0..50)
+            .map(|i| format!("<li><a href=\"/p{}\">Read more</a></li>", i))
+            .collect();
+        let html = format!("<html><body><ul>{}</ul></body></html>", links);
+
+        let notified = Arc::new(AtomicUsize::new(0));
+        let counter = notified.clone();
+        let cache_manager = CacheManager::new(CacheConfig::default());
+        let mut service = TranslationService::new(template_config(url))
+            .unwrap()
+            .with_cache_manager(cache_manager.clone())
+            .with_progress_callback(ProgressCallback::new(move |_, _| {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }));
+        let dom = service
+            .translate_dom(crate::html::html_to_dom(
+                html.as_bytes(),
+                "UTF-8".to_string(),
+            ))
+            .await
+            .unwrap();
+        let output = String::from_utf8_lossy(&crate::html::serialize_document(
+            dom,
+            "UTF-8".to_string(),
+            &Default::default(),
+        ))
+        .to_string();
+
+        // 相同的文本只请求一次，译文写回每一处
+        assert_eq!(backend.requests().len(), 1);
+        assert_eq!(output.matches("阅读更多").count(), 50);
+        assert!(!output.contains("Read more"));
+        assert_eq!(service.last_usage().chars_sent, "Read more".len());
+        // 每一处写回都通知回调
+        assert_eq!(notified.load(Ordering::SeqCst), 50);
+
+        // 命中缓存时同样逐处通知
+        cache_manager.insert_with_lang(
+            "Read more".to_string(),
+            "阅读更多".to_string(),
+            "auto".to_string(),
+            "zh".to_string(),
+        );
+        service
+            .translate_dom(crate::html::html_to_dom(
+                html.as_bytes(),
+                "UTF-8".to_string(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(backend.requests().len(), 1);
+        assert_eq!(notified.load(Ordering::SeqCst), 100);
+    }
+}
@@ -48,8 +48,8 @@ use crate::core::MonolithError;
 /// - **配置类**：`ConfigError` - 系统配置相关错误
 /// - **网络类**：`NetworkError`, `TimeoutError`, `RateLimitExceeded` - 网络通信错误
 /// - **数据类**：`InvalidInput`, `ParseError`, `SerializationError` - 数据处理错误
-/// - **系统类**：`CacheError`, `ConcurrencyError`, `ResourceExhausted` - 系统资源错误
-/// - **业务类**：`TranslationServiceError`, `BatchProcessingError`, `TextCollectionError` - 业务逻辑错误
+/// - **系统类**：`CacheError`, `ConcurrencyError`, `ResourceExhausted`, `BudgetExceeded` - 系统资源错误
+/// - **业务类**：`TranslationServiceError`, `BatchProcessingError`, `TextCollectionError`, `Cancelled` - 业务逻辑错误
 /// - **通用类**：`ProcessingError`, `InternalError` - 通用处理错误
 /// 
 /// # 使用示例
@@ -227,6 +227,19 @@ pub enum TranslationError {
     #[error("资源不足: {0}")]
     ResourceExhausted(String),
 
+    /// 字符预算耗尽错误
+    /// 
+    /// 当一次文档翻译发送给翻译服务的字符数（包括重试）将超过
+    /// `max_chars_per_request_job` 时触发，剩余文本不再发送。
+    #[error("翻译字符预算已用尽: 上限 {limit} 字符，已使用 {consumed} 字符")]
+    BudgetExceeded { limit: usize, consumed: usize },
+
+    /// 翻译已取消
+    ///
+    /// 调用方通过取消令牌中止了翻译（例如 Web 客户端已断开连接），剩余批次不再发送。
+    #[error("翻译已取消")]
+    Cancelled,
+
     /// 通用处理错误
     /// 
     /// 当业务逻辑处理过程中出现未分类的错误时触发。
@@ -265,6 +278,8 @@ impl TranslationError {
     /// 
     /// **不可重试的错误类型**：
     /// - `RateLimitExceeded` - 需要等待，不是立即重试
+    /// - `BudgetExceeded` - 预算在本次翻译内不会恢复
+    /// - `Cancelled` - 由调用方主动取消
     /// - `ConfigError` - 配置错误需要人工修复
     /// - `InvalidInput` - 输入错误需要修正数据
     /// - `ParseError` - 解析错误通常是数据格式问题
@@ -308,6 +323,12 @@ impl TranslationError {
             
             // 速率限制需要等待，不是立即重试
             TranslationError::RateLimitExceeded => false,
+
+            // 预算用尽后重试只会继续被拒绝
+            TranslationError::BudgetExceeded { .. } => false,
+
+            // 取消由调用方决定，不应重试
+            TranslationError::Cancelled => false,
             
             // 配置和输入错误需要人工修复
             TranslationError::ConfigError(_) => false,
@@ -349,9 +370,11 @@ impl TranslationError {
     ///   - `TimeoutError` - 超时可以增加等待时间
     ///   - `ConcurrencyError` - 并发问题可以重试
     ///   - `ResourceExhausted` - 资源不足可以等待
+    ///   - `BudgetExceeded` - 已完成部分的译文仍然可用
     /// 
     /// - **Info（信息）**：轻微问题，不影响主要功能
     ///   - `InvalidInput` - 输入错误可以提示用户修正
+    ///   - `Cancelled` - 由调用方主动取消
     /// 
     /// # 返回值
     /// 
@@ -391,9 +414,11 @@ impl TranslationError {
             TranslationError::TimeoutError(_) => ErrorSeverity::Warning,
             TranslationError::ConcurrencyError(_) => ErrorSeverity::Warning,
             TranslationError::ResourceExhausted(_) => ErrorSeverity::Warning,
+            TranslationError::BudgetExceeded { .. } => ErrorSeverity::Warning,
             
             // 信息：轻微问题
             TranslationError::InvalidInput(_) => ErrorSeverity::Info,
+            TranslationError::Cancelled => ErrorSeverity::Info,
         }
     }
 
@@ -461,6 +486,8 @@ impl TranslationError {
             TranslationError::SerializationError(_) => ErrorCategory::Serialization,
             TranslationError::ConcurrencyError(_) => ErrorCategory::Concurrency,
             TranslationError::ResourceExhausted(_) => ErrorCategory::Resource,
+            TranslationError::BudgetExceeded { .. } => ErrorCategory::Resource,
+            TranslationError::Cancelled => ErrorCategory::Processing,
             TranslationError::ProcessingError(_) => ErrorCategory::Processing,
             TranslationError::InternalError(_) => ErrorCategory::Internal,
         }
@@ -486,7 +513,7 @@ impl TranslationError {
     /// 
     /// # 特殊处理
     /// 
-    /// 对于 `RateLimitExceeded` 错误，由于它不包含自定义消息，
+    /// 对于 `RateLimitExceeded`、`BudgetExceeded` 和 `Cancelled` 错误，由于它们不包含自定义消息，
     /// 直接返回原错误而不添加上下文。
     /// 
     /// # 使用场景
@@ -534,6 +561,7 @@ impl TranslationError {
             TranslationError::RateLimitExceeded => {
                 return TranslationError::RateLimitExceeded;
             }
+            TranslationError::BudgetExceeded { .. } | TranslationError::Cancelled => return self,
         }
 
         self
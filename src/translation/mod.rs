@@ -104,6 +104,10 @@ pub mod service {
 /// **注意**: 此模块仍然存在于根目录，未来可能会重构到pipeline中
 pub mod processor;
 
+/// 测试用的本地翻译接口
+#[cfg(test)]
+pub(crate) mod test_backend;
+
 // ============================================================================
 // 核心API导出 - 主要的公共接口
 // ============================================================================
@@ -114,14 +118,20 @@ pub mod processor;
 /// - `HealthStatus`: 服务健康状态检查
 /// - `HealthLevel`: 健康等级枚举
 /// - `ServiceStats`: 服务统计信息
-pub use core::{HealthLevel, HealthStatus, ServiceStats, TranslationService};
+/// - `global_stats`: 进程内所有翻译的累计统计
+/// - `ConcurrentTranslation`: 与资源嵌入并行执行的后台翻译任务
+/// - `UsageReport`: 单次翻译调用的请求数和字符数
+pub use core::{
+    global_stats, BackendUsage, ConcurrentTranslation, HealthLevel, HealthStatus, ServiceStats,
+    TranslationService, UsageReport,
+};
 
 /// 配置管理相关组件
 /// 
 /// - `TranslationConfig`: 翻译配置结构体
 /// - `ConfigManager`: 配置管理器，处理配置文件读写
 /// - `constants`: 配置常量模块
-pub use config::{constants, ConfigManager, TranslationConfig};
+pub use config::{constants, BackendConfig, ConfigManager, TranslationConfig};
 
 /// 错误处理相关类型
 /// 
@@ -145,10 +155,11 @@ pub use error::{ErrorCategory, ErrorSeverity, TranslationError, TranslationResul
 /// - `Batch`: 表示一个翻译批次
 /// - `TextAnalysis`: 文本分析结果
 /// - `LanguageHint`: 语言提示信息
+/// - `LanguageConcurrency`: 多个目标语言共享的并发额度
 /// - 各种枚举类型：`BatchType`, `BatchPriority`, `TextType`, `TextPriority`
 pub use pipeline::{
-    Batch, BatchManager, BatchPriority, BatchType, LanguageHint, TextAnalysis, TextCollector,
-    TextFilter, TextItem, TextPriority, TextType,
+    Batch, BatchManager, BatchPriority, BatchType, LanguageConcurrency, LanguageHint,
+    TextAnalysis, TextCollector, TextFilter, TextItem, TextPriority, TextType,
 };
 
 /// 存储和缓存组件
@@ -158,7 +169,7 @@ pub use pipeline::{
 /// - `CacheConfig`: 缓存配置结构体
 /// - `CacheEntry`: 缓存条目表示
 /// - `CacheStats`: 缓存统计信息
-pub use storage::{CacheConfig, CacheEntry, CacheManager, CacheStats};
+pub use storage::{CacheConfig, CacheEntry, CacheManager, CacheStats, CorrectionStore, DomainCacheStats, EntrySource};
 
 // ============================================================================
 // 便利函数导出 - 简化常见操作的高级函数
@@ -199,6 +210,41 @@ pub async fn translate_dom_content(
     service.translate_dom(dom).await
 }
 
+/// 使用共享缓存翻译HTML DOM内容
+///
+/// 与 [`translate_dom_content`] 相同，但查询传入的缓存管理器，
+/// 其中的人工修正优先于机器翻译。传入 `concurrency` 时，请求数计入与其他翻译共享的
+/// 按语言分配的并发额度。传入 `cancellation` 时，令牌被取消后不再发送剩余的批次，
+/// 返回部分翻译的页面（见 [`TranslationService::translate_dom_with_cancellation`]）。
+/// 传入 `document_url` 时，缓存按页面的主机名确定有效期（见 [`TranslationService::with_document_url`]）。
+pub async fn translate_dom_content_with_cache(
+    dom: markup5ever_rcdom::RcDom,
+    target_lang: &str,
+    api_url: Option<&str>,
+    cache_manager: &CacheManager,
+    concurrency: Option<&LanguageConcurrency>,
+    cancellation: Option<&tokio_util::sync::CancellationToken>,
+    document_url: Option<&crate::utils::url::Url>,
+) -> TranslationResult<markup5ever_rcdom::RcDom> {
+    // 使用完整的配置加载，使配置文件中的请求模板等设置同样生效
+    let config = load_translation_config(target_lang, api_url);
+    let mut service = TranslationService::new(config)?.with_cache_manager(cache_manager.clone());
+    if let Some(document_url) = document_url {
+        service = service.with_document_url(document_url.clone());
+    }
+    if let Some(concurrency) = concurrency {
+        service = service.with_language_concurrency(concurrency.clone());
+    }
+    match cancellation {
+        Some(cancellation) => {
+            service
+                .translate_dom_with_cancellation(dom, cancellation.clone())
+                .await
+        }
+        None => service.translate_dom(dom).await,
+    }
+}
+
 /// 翻译HTML DOM内容（同步版本）
 ///
 /// 内部创建异步运行时来执行翻译，适用于非异步环境
@@ -223,6 +269,16 @@ pub fn translate_dom_content_sync(
     dom: markup5ever_rcdom::RcDom,
     target_lang: &str,
     api_url: Option<&str>,
+) -> Result<markup5ever_rcdom::RcDom, crate::core::MonolithError> {
+    translate_dom_with_config_sync(dom, TranslationConfig::default_with_lang(target_lang, api_url))
+}
+
+/// 使用完整的翻译配置翻译HTML DOM内容（同步版本）
+///
+/// 需要默认配置之外的选项（例如 `show_original`）时使用。
+pub fn translate_dom_with_config_sync(
+    dom: markup5ever_rcdom::RcDom,
+    config: TranslationConfig,
 ) -> Result<markup5ever_rcdom::RcDom, crate::core::MonolithError> {
     #[cfg(feature = "translation")]
     {
@@ -230,7 +286,10 @@ pub fn translate_dom_content_sync(
             .map_err(|e| crate::core::MonolithError::new(&format!("创建异步运行时失败: {e}")))?;
 
         rt.block_on(async {
-            translate_dom_content(dom, target_lang, api_url)
+            let mut service = TranslationService::new(config)
+                .map_err(|e| crate::core::MonolithError::new(&e.to_string()))?;
+            service
+                .translate_dom(dom)
                 .await
                 .map_err(|e| crate::core::MonolithError::new(&e.to_string()))
         })
@@ -238,20 +297,18 @@ pub fn translate_dom_content_sync(
 
     #[cfg(not(feature = "translation"))]
     {
-        let _ = (dom, target_lang, api_url);
+        let _ = (dom, config);
         Err(crate::core::MonolithError::new(
             "Translation feature not enabled",
         ))
     }
 }
 
-/// 翻译CSS内容（实验性功能）
+/// 翻译CSS生成内容中的文本
 /// 
-/// **注意**: 此功能目前处于实验阶段，暂时返回原始CSS内容不做翻译。
-/// 在未来的版本中，将支持CSS中的文本内容翻译，如：
-/// - `content` 属性中的文本
-/// - CSS变量中的文本值
-/// - 注释中的文本（可选）
+/// 只翻译 `content` 声明中最外层的带引号字符串（见 [`pipeline::css`]），
+/// `counter()`、`attr()` 等函数和关键字保持原样，编号和拼接结构不变。
+/// 字符串两端的空白在翻译后保留；不需要翻译或翻译失败的字符串保持原文。
 /// 
 /// # 参数
 /// 
@@ -261,7 +318,7 @@ pub fn translate_dom_content_sync(
 /// 
 /// # 返回值
 /// 
-/// 返回翻译后的CSS内容。当前版本返回原始内容。
+/// 返回翻译后的CSS内容，其余部分与输入完全相同。
 /// 
 /// # Examples
 /// 
@@ -269,13 +326,13 @@ pub fn translate_dom_content_sync(
 /// use monolith::translation::translate_css_content;
 /// 
 /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
-/// let css = ".button::after { content: 'Click me'; }";
+/// let css = "h2::before { content: \"Chapter \" counter(ch) \": Introduction\"; }";
 /// let translated_css = translate_css_content(
 ///     css,
 ///     "zh",
 ///     Some("http://localhost:1188/translate")
 /// ).await?;
-/// // 未来版本将返回: ".button::after { content: '点击我'; }"
+/// // 例如: "h2::before { content: \"章 \" counter(ch) \": 简介\"; }"
 /// # Ok(())
 /// # }
 /// ```
@@ -285,14 +342,40 @@ pub async fn translate_css_content(
     target_lang: &str,
     api_url: Option<&str>,
 ) -> TranslationResult<String> {
-    // TODO: 实现CSS翻译功能
-    // 1. 解析CSS，识别可翻译的文本内容
-    // 2. 提取文本并进行翻译
-    // 3. 将翻译结果替换回CSS中
-    // 
-    // 暂时返回原内容，避免破坏CSS结构
-    let _ = (target_lang, api_url);
-    Ok(css.to_string())
+    use pipeline::css::{content_literals, replace_content_literals};
+
+    let literals = content_literals(css);
+    if literals.is_empty() {
+        return Ok(css.to_string());
+    }
+
+    let config = load_translation_config(target_lang, api_url);
+    let service = core::service::create_base_service(&config);
+    let filter = pipeline::TextFilter::new();
+
+    let mut translations = Vec::with_capacity(literals.len());
+    for literal in &literals {
+        let text = literal.text.trim();
+        if !filter.should_translate(text) {
+            translations.push(None);
+            continue;
+        }
+
+        match service.translate(text).await {
+            Ok(translated) => {
+                // 保留两端的空白，例如 "Chapter " 与后面的编号之间的空格
+                let leading = &literal.text[..literal.text.len() - literal.text.trim_start().len()];
+                let trailing = &literal.text[literal.text.trim_end().len()..];
+                translations.push(Some(format!("{leading}{}{trailing}", translated.trim())));
+            }
+            Err(e) => {
+                tracing::warn!("CSS文本翻译失败: {}", e);
+                translations.push(None);
+            }
+        }
+    }
+
+    Ok(replace_content_literals(css, &literals, &translations))
 }
 
 /// 检查文本是否应该翻译（便利函数）
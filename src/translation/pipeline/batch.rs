@@ -9,6 +9,7 @@
 //! - **批次优化**: 自动合并小批次，拆分过大批次
 //! - **优先级处理**: 支持关键、高、普通、低四个优先级
 //! - **并发控制**: 线程安全的批次处理和统计
+//! - **语言并发额度**: 多个目标语言共享全局并发上限，按权重分配额度
 //! - **队列管理**: 智能的批次队列和处理状态管理
 //!
 //! ## 算法优化策略
@@ -52,6 +53,8 @@ use tokio::time::{sleep, timeout};
 use crate::translation::collector::{TextItem, TextPriority};
 use crate::translation::config::{constants, TranslationConfig};
 use crate::translation::error::{TranslationError, TranslationResult};
+#[cfg(feature = "translation")]
+use crate::translation::pipeline::collector::primary_subtag;
 
 /// 翻译批次信息
 /// 
@@ -391,6 +394,7 @@ impl Batch {
         self.items.extend(other.items);
         self.priority = self.priority.max(other.priority);
         self.estimated_chars += other.estimated_chars;
+        self.estimated_effective_size += other.estimated_effective_size;
         self.estimated_duration += other.estimated_duration;
 
         // 根据合并后的大小重新确定批次类型
@@ -507,6 +511,8 @@ pub struct BatchManagerConfig {
     pub max_batch_size: usize,
     /// 批次的最小字符数，低于此值会尝试合并
     pub min_batch_chars: usize,
+    /// 首选的批次字符数，设置后批次在接近该大小时结束，合并小批次时也不超过它
+    pub preferred_batch_chars: Option<usize>,
     /// 考虑复杂度的最大有效大小
     pub max_effective_size: f32,
     /// 考虑复杂度的最小有效大小
@@ -534,6 +540,7 @@ impl From<&TranslationConfig> for BatchManagerConfig {
         Self {
             max_batch_size: config.batch_size,
             min_batch_chars: config.min_batch_chars,
+            preferred_batch_chars: config.preferred_batch_chars,
             max_effective_size: config.batch_size as f32 * 1.5,
             min_effective_size: config.min_batch_chars as f32 * 0.8,
             batch_delay: Duration::from_millis(constants::BATCH_DELAY_MS),
@@ -553,6 +560,7 @@ impl Default for BatchManagerConfig {
         Self {
             max_batch_size: constants::MAX_BATCH_SIZE,
             min_batch_chars: constants::DEFAULT_MIN_CHARS,
+            preferred_batch_chars: None,
             max_effective_size: constants::MAX_BATCH_SIZE as f32 * 1.5, // 允许更高的有效大小
             min_effective_size: constants::DEFAULT_MIN_CHARS as f32 * 0.8,
             batch_delay: Duration::from_millis(constants::BATCH_DELAY_MS),
@@ -830,7 +838,8 @@ impl BatchManager {
                 !current_batch.is_empty()  // 当前批次不为空
                     && (current_effective_size + item_effective_size
                         > self.config.max_effective_size         // 超过有效大小限制
-                        || current_char_size + item_char_size > self.config.max_batch_size) // 或超过字符数限制
+                        || current_char_size + item_char_size > self.config.max_batch_size // 或超过字符数限制
+                        || self.overshoots_preferred(current_char_size, item_char_size)) // 或偏离首选大小
             };
 
             if should_create_new_batch {
@@ -898,7 +907,10 @@ impl BatchManager {
         for item in items {
             let item_size = item.char_count();
 
-            if !current_batch.is_empty() && current_size + item_size > self.config.max_batch_size {
+            if !current_batch.is_empty()
+                && (current_size + item_size > self.config.max_batch_size
+                    || self.overshoots_preferred(current_size, item_size))
+            {
                 // 创建批次
                 let batch_type = if current_batch.len() == 1 {
                     BatchType::Single
@@ -1151,11 +1163,60 @@ impl BatchManager {
             return batches;
         }
 
-        if self.config.enable_smart_sizing {
+        let optimized = if self.config.enable_smart_sizing {
             self.optimize_batches_smart(batches)
         } else {
             self.optimize_batches_legacy(batches)
+        };
+        self.merge_trailing_batches_across_priorities(optimized)
+    }
+
+    /// 跨优先级合并分组末尾的批次
+    ///
+    /// 每个优先级分组单独分批，分组的最后一个批次通常装不满。相邻优先级（相差 1 级）分组的
+    /// 末尾批次在大小允许时合并到前一个分组的末尾批次中，减少请求次数；合并后的批次使用较高的
+    /// 优先级，留在前一个分组的位置，高优先级的文本不会被推迟。
+    fn merge_trailing_batches_across_priorities(&mut self, batches: Vec<Batch>) -> Vec<Batch> {
+        let mut merged: Vec<Batch> = Vec::with_capacity(batches.len());
+        // 前一个分组的末尾批次在 `merged` 中的位置
+        let mut previous_trailing: Option<usize> = None;
+        let mut batches = batches.into_iter().peekable();
+
+        while let Some(batch) = batches.next() {
+            let is_trailing = batches
+                .peek()
+                .is_none_or(|next| next.priority != batch.priority);
+            if !is_trailing {
+                merged.push(batch);
+                continue;
+            }
+
+            match previous_trailing {
+                Some(index) if self.can_merge_trailing(&merged[index], &batch) => {
+                    let target = merged.remove(index);
+                    merged.insert(index, target.merge(batch));
+                    self.stats.inc_merged_batches();
+                }
+                _ => {
+                    merged.push(batch);
+                    previous_trailing = Some(merged.len() - 1);
+                }
+            }
         }
+
+        merged
+    }
+
+    /// 分组末尾的批次能否合并到前一个分组的末尾批次中
+    ///
+    /// 与 [`Batch::can_merge_with`] 一样只合并相邻优先级，但不限制批次类型：
+    /// 末尾批次常常只有一项，只要合并后不超过大小限制即可。
+    fn can_merge_trailing(&self, target: &Batch, trailing: &Batch) -> bool {
+        (target.priority as i32 - trailing.priority as i32).abs() <= 1
+            && target.estimated_chars + trailing.estimated_chars <= self.config.max_batch_size
+            && target.estimated_effective_size + trailing.estimated_effective_size
+                <= self.config.max_effective_size
+            && !self.overshoots_preferred(target.estimated_chars, trailing.estimated_chars)
     }
 
     /// 智能批次优化
@@ -1219,7 +1280,9 @@ impl BatchManager {
         while let Some(mut current) = candidates.pop_front() {
             // 尝试与后续批次合并（使用有效大小）
             while let Some(next) = candidates.front() {
-                if current.can_merge_with(next, self.config.max_effective_size) {
+                if current.can_merge_with(next, self.config.max_effective_size)
+                    && !self.overshoots_preferred(current.estimated_chars, next.estimated_chars)
+                {
                     let next_batch = candidates.pop_front().unwrap();
                     current = current.merge(next_batch);
                     self.stats.inc_merged_batches();
@@ -1254,6 +1317,19 @@ impl BatchManager {
         merged
     }
 
+    /// 已有 `current` 个字符的批次再加入 `additional` 个字符后，是否比现在更偏离首选批次大小
+    ///
+    /// 未设置首选大小时总是返回 false。
+    fn overshoots_preferred(&self, current: usize, additional: usize) -> bool {
+        match self.config.preferred_batch_chars {
+            Some(preferred) if preferred > 0 => {
+                let merged = current + additional;
+                merged > preferred && merged - preferred > preferred.saturating_sub(current)
+            }
+            _ => false,
+        }
+    }
+
     /// 获取下一个批次ID（线程安全）
     fn next_batch_id(&self) -> usize {
         self.next_batch_id.fetch_add(1, Ordering::Relaxed)
@@ -1301,6 +1377,164 @@ impl BatchManager {
     }
 }
 
+/// 按目标语言分配的翻译并发额度
+///
+/// 多个目标语言同时翻译、而翻译服务的限流是全局的时候使用。所有语言共享一个
+/// `max_concurrent_requests` 大小的全局许可池，配置了权重的语言另有按权重分得的额度，
+/// 权重高的语言可以同时进行更多请求。各语言的额度之和不超过全局上限（语言数多于上限时
+/// 每种语言至少保留一个额度），实际并发的请求总数始终受全局许可池限制。
+/// 未配置权重的语言只受全局上限约束。
+///
+/// 语言按主语言子标签区分（`zh-CN` 与 `zh` 共用额度），同一主语言的多个权重相加。
+///
+/// 克隆得到的实例共享同一组许可。
+#[cfg(feature = "translation")]
+#[derive(Debug, Clone)]
+pub struct LanguageConcurrency {
+    /// 全局许可池
+    global: Arc<Semaphore>,
+    /// 各语言的额度和许可池
+    languages: Arc<HashMap<String, (usize, Arc<Semaphore>)>>,
+}
+
+#[cfg(feature = "translation")]
+impl LanguageConcurrency {
+    /// 按全局上限和各语言权重创建并发额度，权重为 0 的语言视为未配置
+    pub fn new(max_concurrent_requests: usize, weights: &HashMap<String, usize>) -> Self {
+        let max_concurrent_requests = max_concurrent_requests.max(1);
+        let mut normalized: HashMap<String, usize> = HashMap::new();
+        for (lang, weight) in weights {
+            *normalized.entry(primary_subtag(lang)).or_default() += weight;
+        }
+        let languages = allocate_language_slots(max_concurrent_requests, &normalized)
+            .into_iter()
+            .map(|(lang, slots)| (lang, (slots, Arc::new(Semaphore::new(slots)))))
+            .collect();
+
+        Self {
+            global: Arc::new(Semaphore::new(max_concurrent_requests)),
+            languages: Arc::new(languages),
+        }
+    }
+
+    /// 由翻译配置创建，没有配置语言权重时返回 `None`
+    pub fn from_config(config: &TranslationConfig) -> Option<Self> {
+        if config
+            .language_concurrency_weights
+            .values()
+            .all(|weight| *weight == 0)
+        {
+            return None;
+        }
+        Some(Self::new(
+            config.max_concurrent_requests,
+            &config.language_concurrency_weights,
+        ))
+    }
+
+    /// 语言分得的额度，未配置权重的语言返回 `None`
+    pub fn slots(&self, target_lang: &str) -> Option<usize> {
+        self.languages
+            .get(&primary_subtag(target_lang))
+            .map(|(slots, _)| *slots)
+    }
+
+    /// 等待一个可用许可：先占用语言额度，再占用全局许可
+    ///
+    /// 返回的许可在释放前一直计入并发数。
+    pub async fn acquire(&self, target_lang: &str) -> TranslationResult<LanguagePermit> {
+        let language = match self.languages.get(&primary_subtag(target_lang)) {
+            Some((_, semaphore)) => {
+                Some(Arc::clone(semaphore).acquire_owned().await.map_err(|e| {
+                    TranslationError::ConcurrencyError(format!("获取语言并发许可失败: {}", e))
+                })?)
+            }
+            None => None,
+        };
+        let global = Arc::clone(&self.global)
+            .acquire_owned()
+            .await
+            .map_err(|e| {
+                TranslationError::ConcurrencyError(format!("获取全局并发许可失败: {}", e))
+            })?;
+
+        Ok(LanguagePermit {
+            _language: language,
+            _global: global,
+        })
+    }
+}
+
+/// [`LanguageConcurrency::acquire`] 返回的许可，释放时归还语言额度和全局许可
+#[cfg(feature = "translation")]
+#[derive(Debug)]
+pub struct LanguagePermit {
+    _language: Option<tokio::sync::OwnedSemaphorePermit>,
+    _global: tokio::sync::OwnedSemaphorePermit,
+}
+
+/// 按权重把全局并发上限分给各语言
+///
+/// 先按比例向下取整（至少为 1），再把剩余的额度依次分给小数部分最大的语言。
+/// 语言数不超过上限时，额度之和不超过 `total`。
+fn allocate_language_slots(
+    total: usize,
+    weights: &HashMap<String, usize>,
+) -> HashMap<String, usize> {
+    let mut weighted: Vec<(&String, usize)> = weights
+        .iter()
+        .filter(|(_, weight)| **weight > 0)
+        .map(|(lang, weight)| (lang, *weight))
+        .collect();
+    // 权重相同时按语言代码排序，保证分配结果稳定
+    weighted.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+
+    let weight_sum: usize = weighted.iter().map(|(_, weight)| weight).sum();
+    if weight_sum == 0 {
+        return HashMap::new();
+    }
+
+    let mut slots: Vec<usize> = weighted
+        .iter()
+        .map(|(_, weight)| (total * weight / weight_sum).max(1))
+        .collect();
+
+    // 最低额度可能使总数超出上限，从额度最多的语言中扣回
+    while slots.iter().sum::<usize>() > total {
+        match slots
+            .iter_mut()
+            .enumerate()
+            .filter(|(_, slots)| **slots > 1)
+            .max_by_key(|(i, slots)| (**slots, std::cmp::Reverse(*i)))
+        {
+            Some((_, slots)) => *slots -= 1,
+            None => break,
+        }
+    }
+
+    // 剩余额度按小数部分从大到小分配
+    let mut remainders: Vec<(usize, usize)> = weighted
+        .iter()
+        .enumerate()
+        .map(|(i, (_, weight))| (i, total * weight % weight_sum))
+        .collect();
+    remainders.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    let mut remaining = total.saturating_sub(slots.iter().sum());
+    for (i, _) in remainders {
+        if remaining == 0 {
+            break;
+        }
+        slots[i] += 1;
+        remaining -= 1;
+    }
+
+    weighted
+        .into_iter()
+        .zip(slots)
+        .map(|((lang, _), slots)| (lang.clone(), slots))
+        .collect()
+}
+
 /// 批次处理统计信息（线程安全版本）
 /// 
 /// 收集和维护批次处理过程中的各种统计数据，用于性能分析、
@@ -1673,6 +1907,7 @@ mod tests {
                 text_type: TextType::Button,
                 depth: 0,
                 parent_tag: Some("button".to_string()),
+                duplicates: Vec::new(),
             },
             TextItem {
                 text: "Title text".to_string(),
@@ -1682,6 +1917,7 @@ mod tests {
                 text_type: TextType::Title,
                 depth: 0,
                 parent_tag: Some("h1".to_string()),
+                duplicates: Vec::new(),
             },
         ]
     }
@@ -1781,4 +2017,158 @@ mod tests {
 
         assert!(queue.dequeue().is_none(), "Queue should be empty");
     }
+
+    fn paragraphs(count: usize, chars: usize) -> Vec<TextItem> {
+        let handle = create_test_text_items()[0].node.clone();
+        (0..count)
+            .map(|i| {
+                let text = format!("{i:03}{}", "a".repeat(chars - 3));
+                TextItem::content(text, handle.clone(), 0)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_batches_close_near_preferred_size() {
+        let config = BatchManagerConfig {
+            preferred_batch_chars: Some(1000),
+            ..BatchManagerConfig::default()
+        };
+        let mut manager = BatchManager::new(config);
+        let batches = manager.create_batches(paragraphs(10, 450));
+
+        // 第三段会让批次达到 1350 个字符，比停在 900 更偏离 1000
+        assert_eq!(batches.len(), 5);
+        assert!(batches.iter().all(|batch| batch.estimated_chars == 900));
+
+        let mut manager = BatchManager::default();
+        assert_eq!(manager.create_batches(paragraphs(10, 450)).len(), 1);
+    }
+
+    #[test]
+    fn test_preferred_size_allows_slight_overshoot() {
+        let config = BatchManagerConfig {
+            preferred_batch_chars: Some(1000),
+            ..BatchManagerConfig::default()
+        };
+        let mut manager = BatchManager::new(config);
+        let batches = manager.create_batches(paragraphs(6, 350));
+
+        // 1050 比 700 更接近 1000
+        assert_eq!(batches.len(), 2);
+        assert!(batches.iter().all(|batch| batch.estimated_chars == 1050));
+    }
+
+    #[test]
+    fn test_trailing_batches_merge_across_priorities() {
+        let config = BatchManagerConfig {
+            preferred_batch_chars: Some(1000),
+            ..BatchManagerConfig::default()
+        };
+        let mut high = paragraphs(3, 450);
+        for item in high.iter_mut() {
+            item.priority = TextPriority::High;
+            item.text.replace_range(..1, "h");
+        }
+        let normal = paragraphs(3, 450);
+
+        // 每个分组单独分批：900 + 450 各两个批次
+        let per_group: usize = [high.clone(), normal.clone()]
+            .into_iter()
+            .map(|items| {
+                BatchManager::new(config.clone())
+                    .create_batches(items)
+                    .len()
+            })
+            .sum();
+        assert_eq!(per_group, 4);
+
+        let mut manager = BatchManager::new(config);
+        let batches = manager.create_batches(high.into_iter().chain(normal).collect());
+        assert!(batches.len() < per_group);
+
+        let mut texts: Vec<String> = batches
+            .iter()
+            .flat_map(|batch| batch.items.iter().map(|item| item.text.clone()))
+            .collect();
+        texts.sort();
+        texts.dedup();
+        assert_eq!(texts.len(), 6);
+        // 合并后的批次保持较高的优先级，排在普通优先级之前
+        assert_eq!(batches[1].priority, BatchPriority::High);
+        assert_eq!(batches[1].estimated_chars, 900);
+    }
+
+    fn weights(entries: &[(&str, usize)]) -> HashMap<String, usize> {
+        entries
+            .iter()
+            .map(|(lang, weight)| (lang.to_string(), *weight))
+            .collect()
+    }
+
+    #[test]
+    fn test_language_slots_follow_weights() {
+        let slots = allocate_language_slots(10, &weights(&[("zh", 3), ("ja", 1), ("fr", 1)]));
+
+        assert_eq!(slots["zh"], 6);
+        assert_eq!(slots["ja"], 2);
+        assert_eq!(slots["fr"], 2);
+        assert_eq!(slots.values().sum::<usize>(), 10);
+    }
+
+    #[test]
+    fn test_language_slots_never_exceed_total() {
+        let slots = allocate_language_slots(3, &weights(&[("zh", 10), ("ja", 1), ("fr", 1)]));
+        assert_eq!(slots["zh"], 1);
+        assert_eq!(slots["ja"], 1);
+        assert_eq!(slots["fr"], 1);
+
+        let slots = allocate_language_slots(4, &weights(&[("zh", 100), ("ja", 1), ("de", 0)]));
+        assert_eq!(slots["zh"], 3);
+        assert_eq!(slots["ja"], 1);
+        assert!(!slots.contains_key("de"));
+    }
+
+    #[tokio::test]
+    async fn test_language_concurrency_respects_global_cap() {
+        let concurrency = LanguageConcurrency::new(3, &weights(&[("zh", 2), ("ja", 1)]));
+        assert_eq!(concurrency.slots("zh"), Some(2));
+        assert_eq!(concurrency.slots("ja"), Some(1));
+        assert_eq!(concurrency.slots("fr"), None);
+
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+        let tasks: Vec<_> = ["zh", "zh", "zh", "ja", "ja", "fr", "fr", "fr"]
+            .into_iter()
+            .map(|lang| {
+                let concurrency = concurrency.clone();
+                let in_flight = Arc::clone(&in_flight);
+                let max_in_flight = Arc::clone(&max_in_flight);
+                tokio::spawn(async move {
+                    let _permit = concurrency.acquire(lang).await.unwrap();
+                    let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_in_flight.fetch_max(current, Ordering::SeqCst);
+                    sleep(Duration::from_millis(10)).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        assert!(max_in_flight.load(Ordering::SeqCst) <= 3);
+    }
+
+    #[test]
+    fn test_language_concurrency_normalizes_tags() {
+        let concurrency =
+            LanguageConcurrency::new(6, &weights(&[("zh-CN", 1), ("ZH_tw", 1), ("ja", 1)]));
+
+        assert_eq!(concurrency.slots("zh"), Some(4));
+        assert_eq!(concurrency.slots("zh-CN"), Some(4));
+        assert_eq!(concurrency.slots("zh-Hant"), Some(4));
+        assert_eq!(concurrency.slots("ja-JP"), Some(2));
+        assert_eq!(concurrency.slots("fr"), None);
+    }
 }
@@ -0,0 +1,381 @@
+//! 双语显示
+//!
+//! 翻译后的页面默认只保留译文。为方便语言学习者对照，本模块在译文写回DOM之后：
+//!
+//! - [`annotate_originals`]：把原文写入文本所在元素的 `data-original` 属性，
+//!   并注入一段样式，鼠标悬停时显示原文。该属性不在可翻译属性列表中，
+//!   再次翻译时不会被收集；
+//! - [`BilingualSnapshot`]：翻译前复制文本所在的块级元素，翻译后把原文副本以淡化样式
+//!   插入在译文之后，得到译文与原文上下对照的段落。
+//!
+//! 原文副本带有 `translate="no"`，文本收集器会跳过它，因此不会被重复翻译。
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use html5ever::interface::{Attribute, QualName};
+use html5ever::tendril::StrTendril;
+use html5ever::tree_builder::{create_element, NodeOrText, TreeSink};
+use html5ever::{namespace_url, ns, LocalName};
+use markup5ever_rcdom::{Handle, Node, NodeData, RcDom};
+
+use crate::html::{find_nodes, get_node_attr, parent_of, set_node_attr};
+use crate::translation::pipeline::collector::TextItem;
+
+/// 保存原文的属性名
+pub const ORIGINAL_ATTR: &str = "data-original";
+
+/// 标记原文副本的属性名
+pub const BILINGUAL_ATTR: &str = "data-bilingual";
+
+/// 原文副本的淡化样式
+const BILINGUAL_CSS: &str = "[data-bilingual=original]{opacity:.6;font-size:.92em}";
+
+/// 在元素之后插入同名的原文副本的块级元素
+const SIBLING_BLOCKS: &[&str] = &[
+    "blockquote",
+    "dd",
+    "dt",
+    "figcaption",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "p",
+    "summary",
+];
+
+/// 父元素对子元素类型有要求的块级元素，原文副本以 `<div>` 插入在元素内部末尾
+const INNER_BLOCKS: &[&str] = &["caption", "li", "td", "th"];
+
+/// 悬停显示原文的样式；嵌套的带原文元素只显示最内层的提示
+const ORIGINAL_TOOLTIP_CSS: &str = "[data-original]{cursor:help}\
+[data-original]:hover:not(:has([data-original]:hover)){position:relative}\
+[data-original]:hover:not(:has([data-original]:hover))::after{content:attr(data-original);\
+position:absolute;left:0;top:100%;z-index:2147483647;max-width:32em;width:max-content;\
+padding:4px 8px;border-radius:4px;background:#333;color:#fff;font:13px/1.4 sans-serif;\
+white-space:pre-wrap;text-align:left;pointer-events:none}";
+
+/// 把已翻译文本的原文写入所在元素的 `data-original` 属性
+///
+/// 同一元素下的多个文本节点按文档顺序合并。只处理文本节点，属性译文不做标注；
+/// 已带有 `data-original` 的元素保持不变。至少标注一个元素时向 `<head>` 注入提示样式。
+/// 返回标注的元素数量。
+pub fn annotate_originals(dom: &RcDom, items: &[TextItem]) -> usize {
+    let mut elements: Vec<(Handle, Vec<&str>, bool)> = Vec::new();
+
+    for item in items
+        .iter()
+        .flat_map(TextItem::occurrences)
+        .filter(|item| item.attr_name.is_none() && !item.is_json_value())
+    {
+        let element = match parent_element(&item.node) {
+            Some(element) => element,
+            None => continue,
+        };
+        let translated = item
+            .current_text()
+            .map(|current| !current.split_whitespace().eq(item.text.split_whitespace()))
+            .unwrap_or(false);
+
+        match elements
+            .iter_mut()
+            .find(|(e, _, _)| Rc::ptr_eq(e, &element))
+        {
+            Some((_, texts, changed)) => {
+                texts.push(item.text.trim());
+                *changed |= translated;
+            }
+            None => elements.push((element, vec![item.text.trim()], translated)),
+        }
+    }
+
+    let mut annotated = 0;
+    for (element, texts, changed) in elements {
+        if changed && get_node_attr(&element, ORIGINAL_ATTR).is_none() {
+            set_node_attr(&element, ORIGINAL_ATTR, Some(texts.join(" ")));
+            annotated += 1;
+        }
+    }
+
+    if annotated > 0 {
+        inject_style(dom, ORIGINAL_TOOLTIP_CSS);
+    }
+    annotated
+}
+
+/// 翻译前块级元素的副本
+///
+/// 只处理 [`SIBLING_BLOCKS`] 和 [`INNER_BLOCKS`] 中离文本最近的块级元素；
+/// 直接位于 `<div>`、`<body>` 等容器中的文本只显示译文，避免复制整个容器。
+pub struct BilingualSnapshot {
+    /// (块级元素, 翻译前的副本, 块内的文本项)
+    blocks: Vec<(Handle, Handle, Vec<TextItem>)>,
+}
+
+impl BilingualSnapshot {
+    /// 在翻译前复制文本项所在的块级元素
+    pub fn capture(dom: &RcDom, items: &[TextItem]) -> Self {
+        let mut blocks: Vec<(Handle, Handle, Vec<TextItem>)> = Vec::new();
+
+        for item in items
+            .iter()
+            .flat_map(TextItem::occurrences)
+            .filter(|item| item.attr_name.is_none() && !item.is_json_value())
+        {
+            let block = match nearest_block(&item.node) {
+                Some(block) => block,
+                None => continue,
+            };
+            match blocks.iter_mut().find(|(b, _, _)| Rc::ptr_eq(b, &block)) {
+                Some((_, _, block_items)) => block_items.push(item.clone()),
+                None => {
+                    let copy = deep_clone(dom, &block);
+                    blocks.push((block, copy, vec![item.clone()]));
+                }
+            }
+        }
+
+        Self { blocks }
+    }
+
+    /// 在至少有一个文本已翻译的块级元素之后插入原文副本
+    ///
+    /// 至少插入一个副本时向 `<head>` 注入淡化样式。返回插入的副本数量。
+    pub fn insert_originals(self, dom: &RcDom) -> usize {
+        let mut inserted = 0;
+
+        for (block, copy, items) in self.blocks {
+            let translated = items.iter().any(|item| {
+                item.current_text()
+                    .map(|current| !current.split_whitespace().eq(item.text.split_whitespace()))
+                    .unwrap_or(false)
+            });
+            if !translated {
+                continue;
+            }
+
+            let original = if INNER_BLOCKS.contains(&element_name(&block).as_str()) {
+                let wrapper = create_element(
+                    dom,
+                    QualName::new(None, ns!(), LocalName::from("div")),
+                    vec![],
+                );
+                for child in copy.children.take() {
+                    child.parent.set(None);
+                    dom.append(&wrapper, NodeOrText::AppendNode(child));
+                }
+                dom.append(&block, NodeOrText::AppendNode(wrapper.clone()));
+                wrapper
+            } else {
+                insert_after(&block, &copy);
+                copy
+            };
+            set_node_attr(&original, BILINGUAL_ATTR, Some("original".to_string()));
+            set_node_attr(&original, "translate", Some("no".to_string()));
+            inserted += 1;
+        }
+
+        if inserted > 0 {
+            inject_style(dom, BILINGUAL_CSS);
+        }
+        inserted
+    }
+}
+
+/// 复制块级元素时跳过的嵌套块级内容，它们各自生成原文副本
+const NESTED_BLOCKS: &[&str] = &[
+    "article", "aside", "details", "div", "dl", "figure", "ol", "pre", "section", "table", "ul",
+];
+
+/// 深度复制节点，去掉 `id` 属性以免文档中出现重复的 id，并跳过嵌套的块级元素
+fn deep_clone(dom: &RcDom, node: &Handle) -> Handle {
+    let copy = match node.data {
+        NodeData::Element {
+            ref name,
+            ref attrs,
+            ..
+        } => {
+            let attrs: Vec<Attribute> = attrs
+                .borrow()
+                .iter()
+                .filter(|attr| &*attr.name.local != "id")
+                .cloned()
+                .collect();
+            create_element(dom, name.clone(), attrs)
+        }
+        NodeData::Text { ref contents } => Node::new(NodeData::Text {
+            contents: RefCell::new(contents.borrow().clone()),
+        }),
+        NodeData::Comment { ref contents } => Node::new(NodeData::Comment {
+            contents: contents.clone(),
+        }),
+        _ => return Node::new(NodeData::Document),
+    };
+
+    for child in node.children.borrow().iter() {
+        let name = element_name(child);
+        let nested_block = NESTED_BLOCKS.contains(&name.as_str())
+            || SIBLING_BLOCKS.contains(&name.as_str())
+            || INNER_BLOCKS.contains(&name.as_str());
+        if !nested_block
+            && matches!(
+                child.data,
+                NodeData::Element { .. } | NodeData::Text { .. } | NodeData::Comment { .. }
+            )
+        {
+            dom.append(&copy, NodeOrText::AppendNode(deep_clone(dom, child)));
+        }
+    }
+    copy
+}
+
+/// 离节点最近的、可以插入原文副本的块级元素
+fn nearest_block(node: &Handle) -> Option<Handle> {
+    let mut current = parent_element(node);
+    while let Some(element) = current {
+        let name = element_name(&element);
+        if SIBLING_BLOCKS.contains(&name.as_str()) || INNER_BLOCKS.contains(&name.as_str()) {
+            return Some(element);
+        }
+        current = parent_element(&element);
+    }
+    None
+}
+
+/// 把 `new_node` 插入为 `node` 的下一个兄弟节点
+fn insert_after(node: &Handle, new_node: &Handle) {
+    let parent = match parent_element(node) {
+        Some(parent) => parent,
+        None => return,
+    };
+    let mut children = parent.children.borrow_mut();
+    if let Some(index) = children.iter().position(|child| Rc::ptr_eq(child, node)) {
+        children.insert(index + 1, new_node.clone());
+        new_node.parent.set(Some(Rc::downgrade(&parent)));
+    }
+}
+
+fn element_name(node: &Handle) -> String {
+    match node.data {
+        NodeData::Element { ref name, .. } => name.local.to_string(),
+        _ => String::new(),
+    }
+}
+
+/// 在 `<head>` 末尾追加一个 `<style>` 元素
+fn inject_style(dom: &RcDom, css: &str) {
+    if let Some(head) = find_nodes(&dom.document, vec!["html", "head"]).first() {
+        let style = create_element(
+            dom,
+            QualName::new(None, ns!(), LocalName::from("style")),
+            vec![],
+        );
+        dom.append(&style, NodeOrText::AppendText(StrTendril::from(css)));
+        dom.append(head, NodeOrText::AppendNode(style));
+    }
+}
+
+/// 获取节点的父元素
+fn parent_element(node: &Handle) -> Option<Handle> {
+    parent_of(node).filter(|parent| matches!(parent.data, NodeData::Element { .. }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::html::html_to_dom;
+    use crate::translation::pipeline::collector::TextCollector;
+    use crate::translation::processor::apply_translation;
+
+    fn translate_items(items: &[TextItem], translated: &str) {
+        for item in items.iter().filter(|item| item.attr_name.is_none()) {
+            apply_translation(item, translated).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_bilingual_paragraph() {
+        let html = "<html><head></head><body>\
+                    <p id=\"intro\">Welcome to <a href=\"/x\">our website</a></p>\
+                    <ul><li>First item</li></ul></body></html>";
+        let dom = html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let items = TextCollector::default()
+            .collect_translatable_texts(&dom.document)
+            .unwrap();
+
+        let snapshot = BilingualSnapshot::capture(&dom, &items);
+        translate_items(&items, "译文");
+        assert_eq!(snapshot.insert_originals(&dom), 2);
+
+        // 段落：译文在前，原文副本紧随其后并保留内联结构
+        let paragraphs = find_nodes(&dom.document, vec!["html", "body", "p"]);
+        assert_eq!(paragraphs.len(), 2);
+        assert_eq!(
+            get_node_attr(&paragraphs[0], "id"),
+            Some("intro".to_string())
+        );
+        assert_eq!(get_node_attr(&paragraphs[1], "id"), None);
+        assert_eq!(
+            get_node_attr(&paragraphs[1], BILINGUAL_ATTR),
+            Some("original".to_string())
+        );
+        assert_eq!(
+            find_nodes(&paragraphs[1], vec!["a"])
+                .first()
+                .and_then(|a| get_node_attr(a, "href")),
+            Some("/x".to_string())
+        );
+
+        // 列表项：原文放在 li 内部，列表结构不变
+        let items_in_list = find_nodes(&dom.document, vec!["html", "body", "ul", "li"]);
+        assert_eq!(items_in_list.len(), 1);
+        assert_eq!(find_nodes(&items_in_list[0], vec!["div"]).len(), 1);
+
+        // 原文副本不会被再次收集
+        let recollected = TextCollector::default()
+            .collect_translatable_texts(&dom.document)
+            .unwrap();
+        assert!(recollected.iter().all(|item| item.text == "译文"));
+    }
+
+    #[test]
+    fn test_annotate_originals() {
+        let html = "<html><head></head><body><p>Hello world</p><p>Second paragraph</p>\
+                    <img alt=\"A picture\"></body></html>";
+        let dom = html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let items = TextCollector::default()
+            .collect_translatable_texts(&dom.document)
+            .unwrap();
+
+        for item in &items {
+            if item.text.contains("Hello") {
+                apply_translation(item, "你好世界").unwrap();
+            }
+            if item.attr_name.is_some() {
+                apply_translation(item, "一张图片").unwrap();
+            }
+        }
+
+        assert_eq!(annotate_originals(&dom, &items), 1);
+
+        let paragraphs = find_nodes(&dom.document, vec!["html", "body", "p"]);
+        assert_eq!(
+            get_node_attr(&paragraphs[0], ORIGINAL_ATTR),
+            Some("Hello world".to_string())
+        );
+        assert_eq!(get_node_attr(&paragraphs[1], ORIGINAL_ATTR), None);
+        assert_eq!(
+            find_nodes(&dom.document, vec!["html", "head", "style"]).len(),
+            1
+        );
+
+        // 原文属性不会被再次收集
+        let recollected = TextCollector::default()
+            .collect_translatable_texts(&dom.document)
+            .unwrap();
+        assert!(recollected.iter().all(|item| item.text != "Hello world"));
+    }
+}
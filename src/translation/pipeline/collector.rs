@@ -41,19 +41,31 @@
 //! - 智能预分配容器容量，减少重分配开销
 //! - 优化的去重算法和排序策略
 
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::io::{self, Read};
+use std::ops::Range;
+use std::rc::Rc;
 use std::sync::Arc;
 
 use html5ever::tendril;
-use markup5ever_rcdom::{Handle, NodeData};
+use html5ever::tokenizer::states::RawKind;
+use html5ever::tokenizer::{
+    BufferQueue, Tag, TagKind, Token, TokenSink, TokenSinkResult, Tokenizer, TokenizerOpts,
+};
+use markup5ever_rcdom::{Handle, Node, NodeData};
 
 // #[cfg(feature = "translation")]
 // use rayon::prelude::*;
 
-use crate::html::get_node_attr;
-use crate::translation::config::constants;
+use crate::html::{
+    find_nodes, get_child_node_by_name, get_node_attr, get_node_name, get_template_contents,
+    matches_selector, parent_of, social_meta_key, SOCIAL_TEXT_META,
+};
+use crate::translation::config::{constants, TranslationConfig};
 use crate::translation::error::TranslationResult;
 use crate::translation::filters::TextFilter;
+use crate::translation::pipeline::json::{is_json_script_type, json_string_values};
 
 /// 文本项结构体 - 存储需要翻译的文本及其元数据
 ///
@@ -68,6 +80,7 @@ use crate::translation::filters::TextFilter;
 /// - `text_type`: 文本类型分类，用于不同的翻译策略
 /// - `depth`: 在DOM树中的深度，用于排序和复杂度计算
 /// - `parent_tag`: 父元素的标签名，用于上下文分析
+/// - `duplicates`: 去重时合并进来的相同文本的其他位置
 #[derive(Debug, Clone)]
 pub struct TextItem {
     /// 文本内容
@@ -84,6 +97,49 @@ pub struct TextItem {
     pub depth: usize,
     /// 父元素标签名
     pub parent_tag: Option<String>,
+    /// 去重时合并到此项的相同文本（同一文本类型）的其他位置，
+    /// 只翻译一次，译文同样写回这些位置
+    pub duplicates: Vec<TextItem>,
+}
+
+/// 内置的属性文本类型映射，未列出的属性归为 [`TextType::Attribute`]
+pub const DEFAULT_ATTRIBUTE_TYPES: &[(&str, TextType)] = &[
+    ("title", TextType::Tooltip),
+    ("alt", TextType::ImageAlt),
+    ("aria-label", TextType::Tooltip),
+    ("aria-description", TextType::Tooltip),
+    ("placeholder", TextType::FormLabel),
+    ("label", TextType::FormLabel),
+    ("value", TextType::Button),
+];
+
+/// `<ruby>` 中的注音元素：注音文本（`rt`、`rtc`）和不支持注音时显示的括号（`rp`）
+pub const RUBY_ANNOTATIONS: &[&str] = &["rt", "rp", "rtc"];
+
+/// 内容被跳过时属性同样不收集的元素：它们不显示在页面上，`title` 等属性有其他用途
+/// （例如 `<link>` 和 `<style>` 的 `title` 是备用样式表的名称）
+pub const HIDDEN_ATTRIBUTE_ELEMENTS: &[&str] = &["head", "meta", "link", "base", "script", "style"];
+
+/// `value` 显示为按钮文字的 `<input>` 类型
+pub const BUTTON_INPUT_TYPES: &[&str] = &["submit", "button", "reset"];
+
+/// 翻译前删除的不可见字符：软连字符、零宽空格、词连接符和零宽不换行空格（BOM）。
+/// 零宽连接符和零宽非连接符（U+200C、U+200D）影响波斯语、印地语等文字和 emoji 的显示，不在其中
+pub const INVISIBLE_CHARS: &[char] = &['\u{00AD}', '\u{200B}', '\u{2060}', '\u{FEFF}'];
+
+/// 不换行空格和窄不换行空格
+const NON_BREAKING_SPACES: &[char] = &['\u{00A0}', '\u{202F}'];
+
+/// `translate_preformatted` 开启时，代码中以这些标记（去掉行首缩进后）开头的行视为注释行
+pub const COMMENT_MARKERS: &[&str] = &["/// ", "// ", "# ", "-- ", "/* ", "* "];
+
+/// `translate_preformatted` 开启时预格式化元素的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Preformatted {
+    /// 不像代码的 `<pre>`：逐行翻译
+    Prose,
+    /// 代码：只翻译注释行
+    Code,
 }
 
 /// 文本翻译优先级枚举
@@ -118,6 +174,7 @@ pub enum TextPriority {
 /// - `FormLabel`: 表单标签文本（label标签等）
 /// - `ImageAlt`: 图片替代文本（img标签的alt属性）
 /// - `Tooltip`: 提示文本（title、aria-label等属性）
+/// - `JsonValue`: JSON 数据块中的字符串，包含值的路径
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum TextType {
     /// 普通文本内容
@@ -136,6 +193,8 @@ pub enum TextType {
     ImageAlt,
     /// 提示文本
     Tooltip,
+    /// `<script type="application/json">` 中的字符串，内容为值的完整路径（见 [`json`](super::json)）
+    JsonValue(String),
 }
 
 impl TextItem {
@@ -154,12 +213,14 @@ impl TextItem {
             text_type,
             depth,
             parent_tag,
+            duplicates: Vec::new(),
         }
     }
 
     /// 创建内容文本项
     pub fn content(text: String, node: Handle, depth: usize) -> Self {
-        let priority = Self::calculate_priority(&TextType::Content, &text);
+        let text_type = Self::determine_text_type(&node, &None);
+        let priority = Self::calculate_priority(&text_type, &text);
         let parent_tag = Self::get_parent_tag(&node);
 
         Self {
@@ -167,15 +228,47 @@ impl TextItem {
             node,
             attr_name: None,
             priority,
-            text_type: TextType::Content,
+            text_type,
+            depth,
+            parent_tag,
+            duplicates: Vec::new(),
+        }
+    }
+
+    /// 创建 JSON 数据块中字符串的文本项，`node` 为 `<script>` 中的文本节点
+    pub fn json_value(text: String, node: Handle, path: String, depth: usize) -> Self {
+        let text_type = TextType::JsonValue(path);
+        let priority = Self::calculate_priority(&text_type, &text);
+        let parent_tag = Self::get_parent_tag(&node);
+
+        Self {
+            text,
+            node,
+            attr_name: None,
+            priority,
+            text_type,
             depth,
             parent_tag,
+            duplicates: Vec::new(),
         }
     }
 
     /// 创建属性文本项
     pub fn attribute(text: String, node: Handle, attr_name: String, depth: usize) -> Self {
         let text_type = TextType::Attribute(attr_name.clone());
+        Self::attribute_with_type(text, node, attr_name, text_type, depth)
+    }
+
+    /// 创建指定文本类型的属性文本项
+    ///
+    /// 例如把 `data-tooltip` 属性中的文本当作 [`TextType::Tooltip`] 处理
+    pub fn attribute_with_type(
+        text: String,
+        node: Handle,
+        attr_name: String,
+        text_type: TextType,
+        depth: usize,
+    ) -> Self {
         let priority = Self::calculate_priority(&text_type, &text);
         let parent_tag = Self::get_parent_tag(&node);
 
@@ -187,6 +280,7 @@ impl TextItem {
             text_type,
             depth,
             parent_tag,
+            duplicates: Vec::new(),
         }
     }
 
@@ -195,6 +289,38 @@ impl TextItem {
         self.attr_name.is_some()
     }
 
+    /// 是否为 JSON 数据块中的字符串
+    pub fn is_json_value(&self) -> bool {
+        matches!(self.text_type, TextType::JsonValue(_))
+    }
+
+    /// 文本出现的所有位置：此项本身和 [`duplicates`](Self::duplicates) 中的各项
+    pub fn occurrences(&self) -> impl Iterator<Item = &TextItem> {
+        std::iter::once(self).chain(self.duplicates.iter())
+    }
+
+    /// 读取节点当前的文本（翻译写回之后即为译文）
+    pub fn current_text(&self) -> Option<String> {
+        if let TextType::JsonValue(path) = &self.text_type {
+            return match self.node.data {
+                NodeData::Text { ref contents } => {
+                    json_string_values(&contents.borrow(), std::slice::from_ref(path))
+                        .into_iter()
+                        .next()
+                        .map(|value| value.text)
+                }
+                _ => None,
+            };
+        }
+        match &self.attr_name {
+            Some(attr_name) => get_node_attr(&self.node, attr_name),
+            None => match self.node.data {
+                NodeData::Text { ref contents } => Some(contents.borrow().to_string()),
+                _ => None,
+            },
+        }
+    }
+
     /// 获取文本字符数
     pub fn char_count(&self) -> usize {
         self.text.chars().count()
@@ -253,7 +379,7 @@ impl TextItem {
             TextType::ImageAlt => weight += 0.1,
             TextType::Tooltip => weight += 0.1,
             TextType::Attribute(_) => weight -= 0.2,
-            TextType::Content => {} // 保持基础权重
+            TextType::Content | TextType::JsonValue(_) => {} // 保持基础权重
         }
 
         // 6. 基于DOM深度：深层嵌套可能表示更复杂的上下文
@@ -294,26 +420,14 @@ impl TextItem {
     /// 确定文本类型
     fn determine_text_type(node: &Handle, attr_name: &Option<String>) -> TextType {
         if let Some(attr) = attr_name {
-            match attr.as_str() {
-                "title" => TextType::Tooltip,
-                "alt" => TextType::ImageAlt,
-                "aria-label" | "aria-description" => TextType::Tooltip,
-                "placeholder" => TextType::FormLabel,
-                _ => TextType::Attribute(attr.clone()),
-            }
+            DEFAULT_ATTRIBUTE_TYPES
+                .iter()
+                .find(|(name, _)| name == attr)
+                .map(|(_, text_type)| text_type.clone())
+                .unwrap_or_else(|| TextType::Attribute(attr.clone()))
         } else {
             // 如果不是属性文本，根据节点类型和父元素确定文本类型
-            let parent_tag = Self::get_parent_tag(node);
-            match parent_tag.as_deref() {
-                Some("title") => TextType::Title,
-                Some("h1") | Some("h2") | Some("h3") | Some("h4") | Some("h5") | Some("h6") => {
-                    TextType::Title
-                }
-                Some("button") | Some("input") => TextType::Button,
-                Some("a") => TextType::Link,
-                Some("label") => TextType::FormLabel,
-                _ => TextType::Content,
-            }
+            content_text_type(Self::get_parent_tag(node).as_deref())
         }
     }
 
@@ -323,7 +437,7 @@ impl TextItem {
             TextType::Title => TextPriority::Critical,
             TextType::Button | TextType::Link => TextPriority::High,
             TextType::FormLabel | TextType::Tooltip => TextPriority::High,
-            TextType::ImageAlt => TextPriority::Normal,
+            TextType::ImageAlt | TextType::JsonValue(_) => TextPriority::Normal,
             TextType::Content => {
                 // 对于内容文本，根据文本长度和内容特征确定优先级
                 if text.len() > 100 {
@@ -346,10 +460,11 @@ impl TextItem {
     }
 
     /// 获取父元素标签名
-    fn get_parent_tag(_node: &Handle) -> Option<String> {
-        // 简化实现，避免复杂的父节点访问
-        // 在实际使用中可以通过DOM遍历获取父元素信息
-        None
+    fn get_parent_tag(node: &Handle) -> Option<String> {
+        match parent_of(node)?.data {
+            NodeData::Element { ref name, .. } => Some(name.local.to_string()),
+            _ => None,
+        }
     }
 }
 
@@ -366,8 +481,38 @@ pub struct CollectorConfig {
     pub collect_attributes: Vec<String>,
     /// 最小文本长度
     pub min_text_length: usize,
+    /// 按文本类型覆盖 `min_text_length`，例如让按钮、链接中的短文本（"Go"）也参与翻译，
+    /// 同时为正文保留更高的下限。设置了下限的类型不再排除功能性短词
+    pub min_text_lengths: HashMap<TextType, usize>,
     /// 启用优先级排序
     pub enable_priority_sorting: bool,
+    /// 在过滤前把文本内部的连续空白折叠为单个空格（`<pre>` 内除外）
+    pub normalize_whitespace: bool,
+    /// 在过滤前删除不可见字符（[`INVISIBLE_CHARS`]），并把不换行空格换成普通空格，
+    /// 法语标点两侧和数字分组中的不换行空格除外，见 [`normalize_invisible_chars`]
+    pub normalize_invisible_chars: bool,
+    /// 同时收集 `<template>` 内容片段中的文本
+    pub include_templates: bool,
+    /// 同时收集 `<head>` 中社交分享元数据（[`SOCIAL_TEXT_META`]）的 `content`
+    pub include_social_meta: bool,
+    /// 翻译目标语言；设置后，`lang` 属性（含从祖先继承的）与之相同的元素不再收集。
+    /// 只比较主语言子标签，因此 `lang="en-GB"` 与目标 `en` 视为相同
+    pub target_lang: Option<String>,
+    /// 属性名到文本类型的映射，决定属性文本的优先级和复杂度权重；
+    /// 默认为 [`DEFAULT_ATTRIBUTE_TYPES`]，未列出的属性归为 [`TextType::Attribute`]
+    pub attribute_types: HashMap<String, TextType>,
+    /// 只收集匹配这些选择器的元素子树，为空时收集整个文档。
+    /// 支持标签、`.class`、`#id` 及其组合（如 `div.content`）
+    pub only_selectors: Vec<String>,
+    /// 收集 `<pre>` 和 `<code>` 中的部分文本（两者在 `skip_elements` 中时）。
+    /// 去掉注释行后整块内容不像代码（[`TextFilter::is_code_like`]）的 `<pre>` 逐行收集；
+    /// 其余 `<pre>` 和不在这类 `<pre>` 中的 `<code>` 只收集注释行（见 [`COMMENT_MARKERS`]）的内容。
+    /// 收集的文本被拆分为单独的文本节点，缩进、换行和注释标记留在原处，不参与翻译
+    pub translate_preformatted: bool,
+    /// 翻译 `<script type="application/json">` 数据块中这些路径处的字符串，为空时不处理数据块。
+    /// 路径的写法见 [`json`](super::json)，例如 `props.pageProps.title`。
+    /// 与社交分享元数据一样，设置 `only_selectors` 时不收集
+    pub translate_json_paths: Vec<String>,
 }
 
 impl Default for CollectorConfig {
@@ -375,8 +520,10 @@ impl Default for CollectorConfig {
         Self {
             enable_parallel: true,
             max_depth: 50,
+            // `<input>` 没有子节点，但占位符和按钮文字都在它的属性中，收集器不跳过它
             skip_elements: constants::SKIP_ELEMENTS
                 .iter()
+                .filter(|tag| **tag != "input")
                 .map(|s| s.to_string())
                 .collect(),
             collect_attributes: constants::TRANSLATABLE_ATTRS
@@ -384,8 +531,45 @@ impl Default for CollectorConfig {
                 .map(|s| s.to_string())
                 .collect(),
             min_text_length: constants::MIN_TEXT_LENGTH,
+            min_text_lengths: HashMap::new(),
             enable_priority_sorting: true,
+            normalize_whitespace: true,
+            normalize_invisible_chars: true,
+            include_templates: false,
+            include_social_meta: false,
+            target_lang: None,
+            attribute_types: DEFAULT_ATTRIBUTE_TYPES
+                .iter()
+                .map(|(name, text_type)| (name.to_string(), text_type.clone()))
+                .collect(),
+            only_selectors: Vec::new(),
+            translate_preformatted: false,
+            translate_json_paths: Vec::new(),
+        }
+    }
+}
+
+impl From<&TranslationConfig> for CollectorConfig {
+    fn from(config: &TranslationConfig) -> Self {
+        let mut collector_config = Self {
+            include_templates: config.translate_templates,
+            include_social_meta: config.translate_social_meta,
+            target_lang: Some(config.target_lang.clone()),
+            translate_preformatted: config.translate_preformatted,
+            translate_json_paths: config.translate_json_paths.clone(),
+            ..Self::default()
+        };
+        // 画布本身没有文本，不再跳过它即可收集其中的后备内容
+        if config.translate_canvas_fallback {
+            collector_config.skip_elements.retain(|tag| tag != "canvas");
+        }
+        for attr_name in &config.translate_attributes {
+            let attr_name = attr_name.trim().to_ascii_lowercase();
+            if !attr_name.is_empty() && !collector_config.collect_attributes.contains(&attr_name) {
+                collector_config.collect_attributes.push(attr_name);
+            }
         }
+        collector_config
     }
 }
 
@@ -394,6 +578,8 @@ pub struct TextCollector {
     config: CollectorConfig,
     filter: Arc<TextFilter>,
     stats: CollectionStats,
+    /// 收集时跳过可翻译性过滤（由 `collect_all_texts` 临时开启）
+    unfiltered: bool,
 }
 
 impl TextCollector {
@@ -403,6 +589,7 @@ impl TextCollector {
             config,
             filter: Arc::new(TextFilter::new()),
             stats: CollectionStats::default(),
+            unfiltered: false,
         }
     }
 
@@ -420,30 +607,64 @@ impl TextCollector {
 
         self.stats.reset();
 
-        // 根据编译特性和配置选择收集策略
-        #[cfg(feature = "translation")]
-        {
-            if self.config.enable_parallel {
-                // 使用并行收集（实验性功能）
-                self.collect_parallel(root, &mut texts)?;
-            } else {
-                // 使用串行递归收集
-                self.collect_recursive(root, &mut texts, 0);
+        for (root, depth, in_pre, in_target_lang) in self.collection_roots(root) {
+            // 根据编译特性和配置选择收集策略
+            #[cfg(feature = "translation")]
+            {
+                if self.config.enable_parallel {
+                    // 使用并行收集（实验性功能）
+                    self.collect_parallel(&root, &mut texts, depth, in_pre, in_target_lang)?;
+                } else {
+                    // 使用串行递归收集
+                    self.collect_recursive(&root, &mut texts, depth, in_pre, in_target_lang);
+                }
             }
-        }
 
-        #[cfg(not(feature = "translation"))]
-        {
-            // 无translation特性时只支持串行收集
-            self.collect_recursive(root, &mut texts, 0);
+            #[cfg(not(feature = "translation"))]
+            {
+                // 无translation特性时只支持串行收集
+                self.collect_recursive(&root, &mut texts, depth, in_pre, in_target_lang);
+            }
         }
+        self.collect_social_meta(root, &mut texts);
+        self.collect_json_islands(root, &mut texts, 0);
 
         // 过滤和排序
         self.filter_and_sort_texts(texts)
     }
 
+    /// 按文档顺序收集所有文本
+    ///
+    /// 与 `collect_translatable_texts` 遍历相同的节点和属性（同样受 `only_selectors` 限制），但不经过可翻译性过滤、
+    /// 长度过滤和去重，也不按优先级排序，适合用来比较两份文档的内容。
+    pub fn collect_all_texts(&mut self, root: &Handle) -> Vec<TextItem> {
+        let mut texts = Vec::new();
+
+        self.stats.reset();
+        self.unfiltered = true;
+        for (root, depth, in_pre, in_target_lang) in self.collection_roots(root) {
+            self.collect_recursive(&root, &mut texts, depth, in_pre, in_target_lang);
+        }
+        self.collect_social_meta(root, &mut texts);
+        self.collect_json_islands(root, &mut texts, 0);
+        self.unfiltered = false;
+        self.stats.final_text_count = texts.len();
+
+        texts
+    }
+
     /// 递归收集文本
-    fn collect_recursive(&mut self, node: &Handle, texts: &mut Vec<TextItem>, depth: usize) {
+    ///
+    /// `in_pre` 表示当前节点位于 `<pre>` 内，其中的空白有意义，不做折叠；
+    /// `in_target_lang` 表示当前节点的有效 `lang` 已是目标语言，其文本和属性不收集。
+    fn collect_recursive(
+        &mut self,
+        node: &Handle,
+        texts: &mut Vec<TextItem>,
+        depth: usize,
+        in_pre: bool,
+        in_target_lang: bool,
+    ) {
         if depth > self.config.max_depth {
             return;
         }
@@ -452,30 +673,231 @@ impl TextCollector {
 
         match node.data {
             NodeData::Text { ref contents } => {
-                self.collect_text_content(node, contents, texts, depth);
+                if !in_target_lang {
+                    self.collect_text_content(node, contents, texts, depth, in_pre);
+                }
             }
             NodeData::Element { ref name, .. } => {
                 let tag_name = name.local.as_ref();
 
-                if self.should_skip_element(tag_name) {
+                if self.should_skip_element(node, tag_name) {
                     self.stats.nodes_skipped += 1;
+                    for (element, depth) in
+                        self.skipped_attribute_elements(node, tag_name, depth, in_target_lang)
+                    {
+                        self.collect_element_attributes(&element, texts, depth);
+                    }
+                    if let Some(kind) = self.preformatted_kind(node, tag_name) {
+                        if !self.is_target_lang(node, in_target_lang) {
+                            for piece in split_preformatted(node, kind) {
+                                if let NodeData::Text { ref contents } = piece.data {
+                                    self.collect_text_content(
+                                        &piece,
+                                        contents,
+                                        texts,
+                                        depth + 1,
+                                        true,
+                                    );
+                                }
+                            }
+                        }
+                    }
                     return;
                 }
 
-                // 收集元素属性
-                self.collect_element_attributes(node, texts, depth);
+                let in_target_lang = self.is_target_lang(node, in_target_lang);
+                if in_target_lang {
+                    self.stats.nodes_skipped += 1;
+                } else {
+                    // 收集元素属性
+                    self.collect_element_attributes(node, texts, depth);
+                }
 
-                // 递归处理子节点
+                // 递归处理子节点（子元素可能重新声明其他语言）
+                let in_pre = in_pre || tag_name == "pre";
                 for child in node.children.borrow().iter() {
-                    self.collect_recursive(child, texts, depth + 1);
+                    self.collect_recursive(child, texts, depth + 1, in_pre, in_target_lang);
+                }
+                if let Some(fragment) = self.template_contents(node) {
+                    self.collect_recursive(&fragment, texts, depth + 1, in_pre, in_target_lang);
                 }
             }
             _ => {
                 // 处理其他类型的节点
                 for child in node.children.borrow().iter() {
-                    self.collect_recursive(child, texts, depth + 1);
+                    self.collect_recursive(child, texts, depth + 1, in_pre, in_target_lang);
+                }
+            }
+        }
+    }
+
+    /// 确定开始收集的子树
+    ///
+    /// 未设置 `only_selectors` 时为文档根节点，以及 `<head>` 被跳过时其中的 `<title>`
+    /// （页面标题同样需要翻译，见 [`Self::head_titles`]）；否则按文档顺序返回最外层的匹配元素
+    /// （匹配元素内部再次匹配的元素已包含在其子树中），以及它们所处的深度、
+    /// 是否位于 `<pre>` 内和是否继承了目标语言。位于跳过元素内的匹配不计入。
+    fn collection_roots(&self, root: &Handle) -> Vec<(Handle, usize, bool, bool)> {
+        let mut roots = Vec::new();
+        if self.config.only_selectors.is_empty() {
+            roots.push((root.clone(), 0, false, false));
+            roots.extend(self.head_titles(root));
+        } else {
+            self.find_selected_roots(root, &mut roots, 0, false, false);
+        }
+        roots
+    }
+
+    /// `<head>` 中的 `<title>` 元素
+    ///
+    /// `<head>` 在默认的跳过列表中，其中的标题因此不会被遍历到；这里把它单独作为收集的起点。
+    /// `<head>` 没有被跳过时标题已在文档树中收集，返回 `None`。
+    fn head_titles(&self, root: &Handle) -> Option<(Handle, usize, bool, bool)> {
+        if !self.config.skip_elements.iter().any(|tag| tag == "head") {
+            return None;
+        }
+
+        let html = get_child_node_by_name(root, "html")?;
+        let head = get_child_node_by_name(&html, "head")?;
+        let title = get_child_node_by_name(&head, "title")?;
+        if self.should_skip_element(&title, "title") {
+            return None;
+        }
+
+        let in_target_lang = self.is_target_lang(&head, self.is_target_lang(&html, false));
+        Some((title, 3, false, in_target_lang))
+    }
+
+    /// 收集社交分享元数据中的标题和描述
+    ///
+    /// 这些文本位于 meta 标签的 `content` 属性中，不在 `collect_attributes` 之列，
+    /// 因此单独收集（与页面标题一样，设置 `only_selectors` 时不收集）。
+    fn collect_social_meta(&mut self, root: &Handle, texts: &mut Vec<TextItem>) {
+        if !self.config.include_social_meta || !self.config.only_selectors.is_empty() {
+            return;
+        }
+
+        let in_target_lang = match get_child_node_by_name(root, "html") {
+            Some(html) => match get_child_node_by_name(&html, "head") {
+                Some(head) => self.is_target_lang(&head, self.is_target_lang(&html, false)),
+                None => return,
+            },
+            None => return,
+        };
+        if in_target_lang {
+            return;
+        }
+
+        for meta in find_nodes(root, vec!["html", "head", "meta"]) {
+            let is_text_meta =
+                social_meta_key(&meta).is_some_and(|key| SOCIAL_TEXT_META.contains(&key.as_str()));
+            if !is_text_meta {
+                continue;
+            }
+            if let Some(content) = get_node_attr(&meta, "content") {
+                self.stats.attributes_found += 1;
+                match self
+                    .prepare_text(&content, false)
+                    .map(|content| self.attribute_item(content, &meta, "content", 3))
+                    .filter(|item| self.accepts(item))
+                {
+                    Some(item) => {
+                        texts.push(item);
+                        self.stats.translatable_attributes += 1;
+                    }
+                    None => self.stats.filtered_attributes += 1,
+                }
+            }
+        }
+    }
+
+    /// 收集 JSON 数据块中 `translate_json_paths` 指定的字符串
+    ///
+    /// `<script>` 在跳过列表中，数据块因此单独遍历整个文档查找；带有 `translate="no"` 的数据块不收集。
+    /// 字符串按原样收集，不折叠空白。
+    fn collect_json_islands(&mut self, node: &Handle, texts: &mut Vec<TextItem>, depth: usize) {
+        if self.config.translate_json_paths.is_empty()
+            || !self.config.only_selectors.is_empty()
+            || depth > self.config.max_depth
+        {
+            return;
+        }
+
+        if get_node_name(node) == Some("script") {
+            let is_json = get_node_attr(node, "type").is_some_and(|t| is_json_script_type(&t));
+            if is_json && !is_translate_no(node) {
+                self.collect_json_script(node, texts, depth + 1);
+            }
+            return;
+        }
+
+        for child in node.children.borrow().iter() {
+            self.collect_json_islands(child, texts, depth + 1);
+        }
+    }
+
+    fn collect_json_script(&mut self, script: &Handle, texts: &mut Vec<TextItem>, depth: usize) {
+        let text_node = match script.children.borrow().first() {
+            Some(child) => child.clone(),
+            None => return,
+        };
+        let json = match text_node.data {
+            NodeData::Text { ref contents } => contents.borrow().to_string(),
+            _ => return,
+        };
+
+        for value in json_string_values(&json, &self.config.translate_json_paths) {
+            self.stats.text_nodes_found += 1;
+            let item = TextItem::json_value(value.text, text_node.clone(), value.path, depth);
+            if self.accepts(&item) {
+                texts.push(item);
+                self.stats.translatable_texts += 1;
+            } else {
+                self.stats.filtered_texts += 1;
+            }
+        }
+    }
+
+    fn find_selected_roots(
+        &self,
+        node: &Handle,
+        roots: &mut Vec<(Handle, usize, bool, bool)>,
+        depth: usize,
+        in_pre: bool,
+        in_target_lang: bool,
+    ) {
+        if depth > self.config.max_depth {
+            return;
+        }
+
+        let (in_pre, in_target_lang) = match node.data {
+            NodeData::Element { ref name, .. } => {
+                let tag_name = name.local.as_ref();
+                if self.should_skip_element(node, tag_name) {
+                    return;
+                }
+                if self
+                    .config
+                    .only_selectors
+                    .iter()
+                    .any(|selector| matches_selector(node, selector))
+                {
+                    roots.push((node.clone(), depth, in_pre, in_target_lang));
+                    return;
                 }
+                (
+                    in_pre || tag_name == "pre",
+                    self.is_target_lang(node, in_target_lang),
+                )
             }
+            _ => (in_pre, in_target_lang),
+        };
+
+        for child in node.children.borrow().iter() {
+            self.find_selected_roots(child, roots, depth + 1, in_pre, in_target_lang);
+        }
+        if let Some(fragment) = self.template_contents(node) {
+            self.find_selected_roots(&fragment, roots, depth + 1, in_pre, in_target_lang);
         }
     }
 
@@ -485,17 +907,20 @@ impl TextCollector {
         &mut self,
         root: &Handle,
         texts: &mut Vec<TextItem>,
+        depth: usize,
+        in_pre: bool,
+        in_target_lang: bool,
     ) -> TranslationResult<()> {
         // 首先收集所有节点
         let mut all_nodes = Vec::new();
-        self.collect_all_nodes(root, &mut all_nodes, 0);
+        self.collect_all_nodes(root, &mut all_nodes, depth, in_pre, in_target_lang);
 
         // 串行处理节点（避免并发问题）
         let mut results: Vec<Vec<TextItem>> = Vec::new();
         for chunk in all_nodes.chunks(100) {
             let mut chunk_texts = Vec::new();
-            for (node, depth) in chunk {
-                self.process_single_node(node, &mut chunk_texts, *depth);
+            for (node, depth, in_pre) in chunk {
+                self.process_single_node(node, &mut chunk_texts, *depth, *in_pre);
             }
             results.push(chunk_texts);
         }
@@ -510,48 +935,84 @@ impl TextCollector {
 
     /// 收集所有节点（用于并行处理）
     #[cfg(feature = "translation")]
-    fn collect_all_nodes(&self, node: &Handle, nodes: &mut Vec<(Handle, usize)>, depth: usize) {
+    fn collect_all_nodes(
+        &self,
+        node: &Handle,
+        nodes: &mut Vec<(Handle, usize, bool)>,
+        depth: usize,
+        in_pre: bool,
+        in_target_lang: bool,
+    ) {
         if depth > self.config.max_depth {
             return;
         }
 
-        // 使用Rc共享引用，避免昂贵的节点克隆操作
-        nodes.push((node.clone(), depth)); // TODO: 考虑使用Weak引用进一步优化内存
-
         if let NodeData::Element { ref name, .. } = node.data {
             let tag_name = name.local.as_ref();
-            if !self.should_skip_element(tag_name) {
+            if self.should_skip_element(node, tag_name) {
+                for (element, depth) in
+                    self.skipped_attribute_elements(node, tag_name, depth, in_target_lang)
+                {
+                    nodes.push((element, depth, in_pre));
+                }
+                if let Some(kind) = self.preformatted_kind(node, tag_name) {
+                    if !self.is_target_lang(node, in_target_lang) {
+                        for piece in split_preformatted(node, kind) {
+                            nodes.push((piece, depth + 1, true));
+                        }
+                    }
+                }
+            } else {
+                let in_target_lang = self.is_target_lang(node, in_target_lang);
+                if !in_target_lang {
+                    nodes.push((node.clone(), depth, in_pre));
+                }
+
+                let in_pre = in_pre || tag_name == "pre";
                 for child in node.children.borrow().iter() {
-                    self.collect_all_nodes(child, nodes, depth + 1);
+                    self.collect_all_nodes(child, nodes, depth + 1, in_pre, in_target_lang);
+                }
+                if let Some(fragment) = self.template_contents(node) {
+                    self.collect_all_nodes(&fragment, nodes, depth + 1, in_pre, in_target_lang);
                 }
             }
         } else {
+            // 使用Rc共享引用，避免昂贵的节点克隆操作
+            if !in_target_lang {
+                nodes.push((node.clone(), depth, in_pre)); // TODO: 考虑使用Weak引用进一步优化内存
+            }
             for child in node.children.borrow().iter() {
-                self.collect_all_nodes(child, nodes, depth + 1);
+                self.collect_all_nodes(child, nodes, depth + 1, in_pre, in_target_lang);
             }
         }
     }
 
     /// 处理单个节点
-    fn process_single_node(&self, node: &Handle, texts: &mut Vec<TextItem>, depth: usize) {
+    fn process_single_node(
+        &self,
+        node: &Handle,
+        texts: &mut Vec<TextItem>,
+        depth: usize,
+        in_pre: bool,
+    ) {
         match node.data {
             NodeData::Text { ref contents } => {
-                let text = contents.borrow().to_string();
-                if self.filter.should_translate(&text) {
-                    texts.push(TextItem::content(text, node.clone(), depth));
+                if let Some(text) = self.prepare_text(&contents.borrow(), in_pre) {
+                    let item = TextItem::content(text, node.clone(), depth);
+                    if self.accepts(&item) {
+                        texts.push(item);
+                    }
                 }
             }
             NodeData::Element { .. } => {
                 // 收集属性
-                for attr_name in &self.config.collect_attributes {
-                    if let Some(attr_value) = get_node_attr(node, attr_name) {
-                        if self.filter.should_translate(&attr_value) {
-                            texts.push(TextItem::attribute(
-                                attr_value,
-                                node.clone(),
-                                attr_name.clone(),
-                                depth,
-                            ));
+                for attr_name in self.attribute_names(node) {
+                    if let Some(attr_value) = get_node_attr(node, &attr_name)
+                        .and_then(|value| self.prepare_text(&value, false))
+                    {
+                        let item = self.attribute_item(attr_value, node, &attr_name, depth);
+                        if self.accepts(&item) {
+                            texts.push(item);
                         }
                     }
                 }
@@ -567,13 +1028,17 @@ impl TextCollector {
         contents: &std::cell::RefCell<tendril::StrTendril>,
         texts: &mut Vec<TextItem>,
         depth: usize,
+        in_pre: bool,
     ) {
-        let text = contents.borrow().to_string();
-
         self.stats.text_nodes_found += 1;
 
-        if self.filter.should_translate(&text) {
-            texts.push(TextItem::content(text, node.clone(), depth));
+        let text = self.prepare_text(&contents.borrow(), in_pre);
+
+        if let Some(item) = text
+            .map(|text| TextItem::content(text, node.clone(), depth))
+            .filter(|item| self.accepts(item))
+        {
+            texts.push(item);
             self.stats.translatable_texts += 1;
         } else {
             self.stats.filtered_texts += 1;
@@ -587,17 +1052,17 @@ impl TextCollector {
         texts: &mut Vec<TextItem>,
         depth: usize,
     ) {
-        for attr_name in &self.config.collect_attributes {
-            if let Some(attr_value) = get_node_attr(node, attr_name) {
+        for attr_name in self.attribute_names(node) {
+            if let Some(attr_value) = get_node_attr(node, &attr_name) {
                 self.stats.attributes_found += 1;
 
-                if self.filter.should_translate(&attr_value) {
-                    texts.push(TextItem::attribute(
-                        attr_value,
-                        node.clone(),
-                        attr_name.clone(),
-                        depth,
-                    ));
+                let item = self
+                    .prepare_text(&attr_value, false)
+                    .map(|value| self.attribute_item(value, node, &attr_name, depth))
+                    .filter(|item| self.accepts(item));
+
+                if let Some(item) = item {
+                    texts.push(item);
                     self.stats.translatable_attributes += 1;
                 } else {
                     self.stats.filtered_attributes += 1;
@@ -606,9 +1071,212 @@ impl TextCollector {
         }
     }
 
+    /// 被跳过的元素中仍需收集属性的元素及其深度
+    ///
+    /// 跳过元素只是不收集其内容，`<img>`、`<area>` 的 `alt` 和 `title` 仍然显示给读者。
+    /// `<map>` 的内容整体被跳过，其中的各个 `<area>`（可以嵌套在其他元素中）也一并返回。
+    /// 带有 `translate="no"`、语言已是目标语言或属于 [`HIDDEN_ATTRIBUTE_ELEMENTS`] 的元素不收集。
+    fn skipped_attribute_elements(
+        &self,
+        node: &Handle,
+        tag_name: &str,
+        depth: usize,
+        in_target_lang: bool,
+    ) -> Vec<(Handle, usize)> {
+        let tag_name = tag_name.to_lowercase();
+        if HIDDEN_ATTRIBUTE_ELEMENTS.contains(&tag_name.as_str())
+            || is_translate_no(node)
+            || self.is_target_lang(node, in_target_lang)
+        {
+            return Vec::new();
+        }
+
+        let mut elements = vec![(node.clone(), depth)];
+        if tag_name == "map" {
+            for child in node.children.borrow().iter() {
+                self.find_areas(child, &mut elements, depth + 1, false);
+            }
+        }
+        elements
+    }
+
+    fn find_areas(
+        &self,
+        node: &Handle,
+        areas: &mut Vec<(Handle, usize)>,
+        depth: usize,
+        in_target_lang: bool,
+    ) {
+        if depth > self.config.max_depth || is_translate_no(node) {
+            return;
+        }
+        let in_target_lang = self.is_target_lang(node, in_target_lang);
+        if !in_target_lang
+            && get_node_name(node).is_some_and(|name| name.eq_ignore_ascii_case("area"))
+        {
+            areas.push((node.clone(), depth));
+        }
+        for child in node.children.borrow().iter() {
+            self.find_areas(child, areas, depth + 1, in_target_lang);
+        }
+    }
+
+    /// 元素上需要收集的属性
+    ///
+    /// 除 `collect_attributes` 外，`type` 属于 [`BUTTON_INPUT_TYPES`] 的 `<input>` 的 `value`
+    /// 是按钮上显示的文字，同样收集；其他输入框的 `value` 是表单数据，保持原样。
+    fn attribute_names(&self, node: &Handle) -> Vec<String> {
+        let mut names = self.config.collect_attributes.clone();
+        let is_button_input = get_node_name(node) == Some("input")
+            && get_node_attr(node, "type").is_some_and(|input_type| {
+                BUTTON_INPUT_TYPES.contains(&input_type.trim().to_ascii_lowercase().as_str())
+            });
+        if is_button_input && !names.iter().any(|name| name == "value") {
+            names.push("value".to_string());
+        }
+        names
+    }
+
+    /// 按 `attribute_types` 创建属性文本项
+    fn attribute_item(
+        &self,
+        text: String,
+        node: &Handle,
+        attr_name: &str,
+        depth: usize,
+    ) -> TextItem {
+        let text_type = self
+            .config
+            .attribute_types
+            .get(attr_name)
+            .cloned()
+            .unwrap_or_else(|| TextType::Attribute(attr_name.to_string()));
+        TextItem::attribute_with_type(text, node.clone(), attr_name.to_string(), text_type, depth)
+    }
+
+    /// 文本项是否应当收集
+    ///
+    /// 文本类型在 `min_text_lengths` 中有单独的下限时，按该下限检查长度。
+    fn accepts(&self, item: &TextItem) -> bool {
+        self.accepts_text(&item.text, &item.text_type)
+    }
+
+    fn accepts_text(&self, text: &str, text_type: &TextType) -> bool {
+        if self.unfiltered {
+            return true;
+        }
+        match self.config.min_text_lengths.get(text_type) {
+            Some(min_length) => self
+                .filter
+                .should_translate_with_min_length(text, *min_length),
+            None => self.filter.should_translate(text),
+        }
+    }
+
+    /// 文本类型的最短长度（字节数）
+    fn min_text_length(&self, text_type: &TextType) -> usize {
+        self.config
+            .min_text_lengths
+            .get(text_type)
+            .copied()
+            .unwrap_or(self.config.min_text_length)
+    }
+
+    /// 过滤前预处理文本
+    ///
+    /// 纯空白文本（通常是格式化HTML中的换行和缩进）直接丢弃；
+    /// 启用 `normalize_whitespace` 时，`<pre>` 之外的文本折叠内部连续空白。
+    /// 启用 `normalize_invisible_chars` 时先删除不可见字符，之后保留的不换行空格不参与折叠。
+    fn prepare_text(&self, text: &str, in_pre: bool) -> Option<String> {
+        let text = if self.config.normalize_invisible_chars {
+            normalize_invisible_chars(text)
+        } else {
+            text.to_string()
+        };
+        if text.trim().is_empty() {
+            return None;
+        }
+
+        if !self.config.normalize_whitespace || in_pre {
+            Some(text)
+        } else if self.config.normalize_invisible_chars {
+            Some(
+                text.split(|c: char| c.is_whitespace() && !NON_BREAKING_SPACES.contains(&c))
+                    .filter(|word| !word.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            )
+        } else {
+            Some(text.split_whitespace().collect::<Vec<_>>().join(" "))
+        }
+    }
+
     /// 检查是否应该跳过元素
-    fn should_skip_element(&self, tag_name: &str) -> bool {
-        self.config.skip_elements.contains(&tag_name.to_lowercase())
+    ///
+    /// 除 `skip_elements` 中的标签外，带有 `translate="no"` 的元素（包括双语模式插入的原文）
+    /// 及其子树也不收集。`<ruby>` 的注音（[`RUBY_ANNOTATIONS`]）同样跳过：只翻译基础文本，
+    /// 注音和括号保持原样，不会与基础文本混在一起送去翻译。
+    fn should_skip_element(&self, node: &Handle, tag_name: &str) -> bool {
+        self.should_skip_tag(tag_name)
+            || get_node_attr(node, "translate").is_some_and(|value| value.eq_ignore_ascii_case("no"))
+    }
+
+    /// 标签本身是否被跳过（不考虑 `translate` 属性）
+    fn should_skip_tag(&self, tag_name: &str) -> bool {
+        let tag_name = tag_name.to_lowercase();
+        self.config.skip_elements.contains(&tag_name)
+            || RUBY_ANNOTATIONS.contains(&tag_name.as_str())
+    }
+
+    /// 开启 `translate_preformatted` 时跳过的 `<pre>`、`<code>` 的处理方式
+    ///
+    /// `<pre>` 是否为代码按去掉注释行和空白后的整块内容判断，缩进和注释不会冲淡其中的符号比例。
+    /// 带有 `translate="no"` 的元素仍然整体跳过。
+    fn preformatted_kind(&self, node: &Handle, tag_name: &str) -> Option<Preformatted> {
+        if !self.config.translate_preformatted || is_translate_no(node) {
+            return None;
+        }
+        match tag_name.to_lowercase().as_str() {
+            "pre" => {
+                let code = without_comments(&text_content(node));
+                if self.filter.is_code_like(&code) {
+                    Some(Preformatted::Code)
+                } else {
+                    Some(Preformatted::Prose)
+                }
+            }
+            "code" => Some(Preformatted::Code),
+            _ => None,
+        }
+    }
+
+    /// 计算元素的有效语言是否为目标语言
+    ///
+    /// 元素自身声明了 `lang` 时以其为准（空值表示语言未知），否则沿用父元素的结果。
+    fn is_target_lang(&self, node: &Handle, inherited: bool) -> bool {
+        self.is_target_lang_attr(get_node_attr(node, "lang").as_deref(), inherited)
+    }
+
+    /// 按 `lang` 属性的值计算有效语言是否为目标语言，见 [`Self::is_target_lang`]
+    fn is_target_lang_attr(&self, lang: Option<&str>, inherited: bool) -> bool {
+        let target = match &self.config.target_lang {
+            Some(target) => target,
+            None => return false,
+        };
+
+        match lang {
+            Some(lang) => primary_subtag(lang) == primary_subtag(target),
+            None => inherited,
+        }
+    }
+
+    /// 启用 `include_templates` 时返回 `<template>` 的内容片段
+    fn template_contents(&self, node: &Handle) -> Option<Handle> {
+        if self.config.include_templates {
+            get_template_contents(node)
+        } else {
+            None
+        }
     }
 
     /// 过滤和排序文本
@@ -617,7 +1285,7 @@ impl TextCollector {
         mut texts: Vec<TextItem>,
     ) -> TranslationResult<Vec<TextItem>> {
         // 按文本长度过滤
-        texts.retain(|item| item.text.len() >= self.config.min_text_length);
+        texts.retain(|item| item.text.len() >= self.min_text_length(&item.text_type));
 
         // 去重（基于文本内容）
         texts = self.deduplicate_texts(texts);
@@ -632,36 +1300,33 @@ impl TextCollector {
         Ok(texts)
     }
 
-    /// 去重文本（优化版，减少克隆）
+    /// 去重文本
+    ///
+    /// 文本和类型都相同的项只保留一项送去翻译，其余的移入保留项的 `duplicates`，
+    /// 写回译文时一并更新。
     fn deduplicate_texts(&mut self, texts: Vec<TextItem>) -> Vec<TextItem> {
-        // 使用字符串引用作为键，避免克隆
-        let mut seen = HashMap::new();
-        let mut unique_texts = Vec::new();
-
-        // 预分配容量以降低重分配开销，估算去重后为原来的一半
-        unique_texts.reserve(texts.len() / 2); // 估计去重后为一半
+        let mut seen: HashMap<(String, TextType), usize> = HashMap::new();
+        let mut unique_texts: Vec<TextItem> = Vec::with_capacity(texts.len() / 2);
 
         for item in texts {
-            // 为HashMap查找创建组合键，由于需要哈希和比较，这里无法避免克隆
             let key = (item.text.clone(), item.text_type.clone());
-
-            if let Some(&existing_priority) = seen.get(&key) {
-                // 如果已存在，保留优先级更高的
-                if item.priority > existing_priority {
-                    // 找到并更新现有项
-                    if let Some(pos) = unique_texts.iter().position(|t: &TextItem| {
-                        t.text == item.text && t.text_type == item.text_type
-                    }) {
-                        let item_priority = item.priority; // 在移动之前保存优先级
-                        unique_texts[pos] = item; // 使用移动语义更新现有项，无需克隆
-                        seen.insert(key, item_priority); // 更新HashMap中的优先级记录
+            match seen.get(&key) {
+                Some(&index) => {
+                    let existing = &mut unique_texts[index];
+                    // 保留优先级更高的一项，原来的一项随之写回
+                    if item.priority > existing.priority {
+                        let mut previous = std::mem::replace(existing, item);
+                        existing.duplicates.append(&mut previous.duplicates);
+                        existing.duplicates.push(previous);
+                    } else {
+                        existing.duplicates.push(item);
                     }
+                    self.stats.duplicate_texts += 1;
+                }
+                None => {
+                    seen.insert(key, unique_texts.len());
+                    unique_texts.push(item);
                 }
-                self.stats.duplicate_texts += 1;
-            } else {
-                // 新项，添加到结果中
-                seen.insert(key, item.priority); // 在HashMap中记录该文本的优先级
-                unique_texts.push(item); // 将新项添加到结果列表
             }
         }
 
@@ -723,26 +1388,207 @@ impl TextCollector {
     }
 }
 
-/// 收集统计信息
-#[derive(Debug, Clone, Default)]
-pub struct CollectionStats {
-    pub nodes_visited: usize,
-    pub nodes_skipped: usize,
-    pub text_nodes_found: usize,
-    pub attributes_found: usize,
-    pub translatable_texts: usize,
-    pub translatable_attributes: usize,
-    pub filtered_texts: usize,
-    pub filtered_attributes: usize,
-    pub duplicate_texts: usize,
-    pub final_text_count: usize,
-    pub collection_time_ms: u64,
+/// 文本节点按父元素标签确定的文本类型
+fn content_text_type(parent_tag: Option<&str>) -> TextType {
+    match parent_tag {
+        Some("title") => TextType::Title,
+        Some("h1") | Some("h2") | Some("h3") | Some("h4") | Some("h5") | Some("h6") => {
+            TextType::Title
+        }
+        Some("button") | Some("input") => TextType::Button,
+        Some("a") => TextType::Link,
+        Some("label") | Some("option") | Some("optgroup") => TextType::FormLabel,
+        _ => TextType::Content,
+    }
 }
 
-impl CollectionStats {
-    /// 重置统计
-    pub fn reset(&mut self) {
-        *self = Default::default();
+/// 删除不可见字符，并把排版上没有作用的不换行空格换成普通空格
+///
+/// 法语在 `: ; ! ? »` 之前和 `«` 之后使用（窄）不换行空格，数字分组（`10 000`）同样如此，
+/// 这些位置的不换行空格保持不变；其余的通常只是用来排版或防止折行，换成普通空格。
+pub fn normalize_invisible_chars(text: &str) -> String {
+    let chars: Vec<char> = text
+        .chars()
+        .filter(|c| !INVISIBLE_CHARS.contains(c))
+        .collect();
+
+    chars
+        .iter()
+        .enumerate()
+        .map(|(i, &c)| {
+            if !NON_BREAKING_SPACES.contains(&c) {
+                return c;
+            }
+            let previous = i.checked_sub(1).map(|i| chars[i]);
+            let next = chars.get(i + 1).copied();
+            let french_punctuation =
+                matches!(next, Some(':' | ';' | '!' | '?' | '»')) || previous == Some('«');
+            let digit_group = previous.is_some_and(|c| c.is_ascii_digit())
+                && next.is_some_and(|c| c.is_ascii_digit());
+            if french_punctuation || digit_group {
+                c
+            } else {
+                ' '
+            }
+        })
+        .collect()
+}
+
+/// 拆分预格式化元素中的文本节点，返回包含待翻译文本的新节点
+///
+/// 散文按行拆分，代码只取注释行中注释标记之后（块注释结尾的 `*/` 之前）的部分。
+/// 缩进、换行和注释标记成为单独的文本节点，序列化结果与拆分前相同。
+/// 带有 `translate="no"` 的子元素不拆分。
+fn split_preformatted(element: &Handle, kind: Preformatted) -> Vec<Handle> {
+    let mut pieces = Vec::new();
+    let mut children = Vec::new();
+    let mut changed = false;
+
+    for child in element.children.borrow().iter() {
+        match child.data {
+            NodeData::Text { ref contents } => {
+                let text = contents.borrow().to_string();
+                let ranges = match kind {
+                    Preformatted::Prose => line_ranges(&text),
+                    Preformatted::Code => comment_ranges(&text),
+                };
+                if ranges.is_empty() {
+                    children.push(child.clone());
+                    continue;
+                }
+
+                changed = true;
+                let mut last = 0;
+                for range in ranges {
+                    if range.start > last {
+                        children.push(text_node(&text[last..range.start], element));
+                    }
+                    let piece = text_node(&text[range.clone()], element);
+                    pieces.push(piece.clone());
+                    children.push(piece);
+                    last = range.end;
+                }
+                if last < text.len() {
+                    children.push(text_node(&text[last..], element));
+                }
+            }
+            NodeData::Element { .. } => {
+                if !is_translate_no(child) {
+                    pieces.extend(split_preformatted(child, kind));
+                }
+                children.push(child.clone());
+            }
+            _ => children.push(child.clone()),
+        }
+    }
+
+    if changed {
+        *element.children.borrow_mut() = children;
+    }
+    pieces
+}
+
+/// 每一行去掉首尾空白后的内容
+fn line_ranges(text: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        let start = offset + (line.len() - line.trim_start().len());
+        let end = offset + line.trim_end().len();
+        if start < end {
+            ranges.push(start..end);
+        }
+        offset += line.len();
+    }
+    ranges
+}
+
+/// 注释行中注释标记之后的内容
+fn comment_ranges(text: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        let indent = line.len() - line.trim_start().len();
+        if let Some(marker) = COMMENT_MARKERS
+            .iter()
+            .find(|marker| line[indent..].starts_with(**marker))
+        {
+            let body = &line[indent + marker.len()..];
+            let body = body.trim_end();
+            let body = body.strip_suffix("*/").unwrap_or(body).trim_end();
+            let start = offset + indent + marker.len() + (body.len() - body.trim_start().len());
+            let end = offset + indent + marker.len() + body.len();
+            if start < end {
+                ranges.push(start..end);
+            }
+        }
+        offset += line.len();
+    }
+    ranges
+}
+
+/// 去掉注释行和所有空白后的文本
+fn without_comments(text: &str) -> String {
+    text.lines()
+        .filter(|line| {
+            let line = line.trim_start();
+            !COMMENT_MARKERS
+                .iter()
+                .any(|marker| line.starts_with(marker))
+        })
+        .flat_map(|line| line.chars().filter(|c| !c.is_whitespace()))
+        .collect()
+}
+
+fn is_translate_no(node: &Handle) -> bool {
+    get_node_attr(node, "translate").is_some_and(|value| value.eq_ignore_ascii_case("no"))
+}
+
+fn text_node(text: &str, parent: &Handle) -> Handle {
+    let node = Node::new(NodeData::Text {
+        contents: RefCell::new(text.into()),
+    });
+    node.parent.set(Some(Rc::downgrade(parent)));
+    node
+}
+
+/// 元素的全部文本内容
+fn text_content(node: &Handle) -> String {
+    match node.data {
+        NodeData::Text { ref contents } => contents.borrow().to_string(),
+        _ => node.children.borrow().iter().map(text_content).collect(),
+    }
+}
+
+/// 语言标签的主语言子标签（小写），例如 `en-GB` -> `en`
+pub(crate) fn primary_subtag(lang: &str) -> String {
+    lang.trim()
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+/// 收集统计信息
+#[derive(Debug, Clone, Default)]
+pub struct CollectionStats {
+    pub nodes_visited: usize,
+    pub nodes_skipped: usize,
+    pub text_nodes_found: usize,
+    pub attributes_found: usize,
+    pub translatable_texts: usize,
+    pub translatable_attributes: usize,
+    pub filtered_texts: usize,
+    pub filtered_attributes: usize,
+    pub duplicate_texts: usize,
+    pub final_text_count: usize,
+    pub collection_time_ms: u64,
+}
+
+impl CollectionStats {
+    /// 重置统计
+    pub fn reset(&mut self) {
+        *self = Default::default();
     }
 
     /// 获取总发现的可翻译项目数
@@ -822,6 +1668,468 @@ impl AdvancedTextCollector {
     }
 }
 
+/// 流式收集每次从输入读取的字节数
+pub const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
+/// 解析器不会为其生成结束标签的空元素
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// 隐式结束 `<p>` 的块级开始标签
+const CLOSES_PARAGRAPH: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "dd",
+    "details",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "li",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "ul",
+];
+
+/// 查找隐式结束的元素时不越过的元素
+const SCOPE_BOUNDARIES: &[&str] = &[
+    "applet", "button", "caption", "html", "marquee", "object", "table", "td", "template", "th",
+];
+
+/// 开始标签隐式结束的元素，以及查找时另外不越过的元素
+fn implied_end_tags(tag_name: &str) -> Option<(&'static [&'static str], &'static [&'static str])> {
+    match tag_name {
+        "li" => Some((&["li"], &["ol", "ul"])),
+        "dt" | "dd" => Some((&["dt", "dd"], &["dl"])),
+        "tr" => Some((&["tr"], &["tbody", "tfoot", "thead"])),
+        "td" | "th" => Some((&["td", "th"], &["tr"])),
+        "option" => Some((&["option"], &["datalist", "optgroup", "select"])),
+        "optgroup" => Some((&["option", "optgroup"], &["datalist", "select"])),
+        _ => None,
+    }
+}
+
+/// 流式收集得到的文本单元
+///
+/// 与 [`TextItem`] 相同，但不持有 DOM 节点，只能用来估算和分析，不能写回译文。
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamedText {
+    /// 文本内容
+    pub text: String,
+    /// 属性名（如果是属性文本）
+    pub attr_name: Option<String>,
+    /// 文本优先级
+    pub priority: TextPriority,
+    /// 文本类型
+    pub text_type: TextType,
+    /// 在文档中的深度
+    pub depth: usize,
+    /// 父元素标签名
+    pub parent_tag: Option<String>,
+}
+
+impl StreamedText {
+    fn new(
+        text: String,
+        attr_name: Option<String>,
+        text_type: TextType,
+        depth: usize,
+        parent_tag: Option<String>,
+    ) -> Self {
+        let priority = TextItem::calculate_priority(&text_type, &text);
+        Self {
+            text,
+            attr_name,
+            priority,
+            text_type,
+            depth,
+            parent_tag,
+        }
+    }
+
+    /// 获取文本字符数
+    pub fn char_count(&self) -> usize {
+        self.text.chars().count()
+    }
+}
+
+/// 流式收集时一个尚未结束的元素
+struct OpenElement {
+    tag: String,
+    /// 元素的内容被跳过
+    skipped: bool,
+    /// 元素的有效 `lang` 是目标语言
+    in_target_lang: bool,
+    in_pre: bool,
+    /// 位于 `<svg>`、`<math>` 中，自闭合标签不产生子元素
+    foreign: bool,
+    /// 位于收集 `<area>` 属性的 `<map>` 中
+    in_map: bool,
+    /// 被跳过的 `<head>`（祖先没有被跳过），其中的 `<title>` 仍然收集
+    skipped_head: bool,
+}
+
+struct StreamState {
+    open: Vec<OpenElement>,
+    /// 当前文本节点已读入的内容
+    pending_text: String,
+    texts: Vec<StreamedText>,
+    stats: CollectionStats,
+}
+
+struct StreamSink<'a> {
+    collector: &'a TextCollector,
+    state: RefCell<StreamState>,
+}
+
+impl StreamSink<'_> {
+    fn depth(state: &StreamState) -> usize {
+        state.open.len() + 1
+    }
+
+    /// 当前位置的文本是否收集
+    fn collects_text(&self, state: &StreamState) -> bool {
+        match state.open.last() {
+            Some(element) => {
+                !element.skipped
+                    && !element.in_target_lang
+                    && Self::depth(state) <= self.collector.config.max_depth
+            }
+            None => true,
+        }
+    }
+
+    /// 结束当前文本节点
+    fn flush_text(&self, state: &mut StreamState) {
+        if state.pending_text.is_empty() {
+            return;
+        }
+        let text = std::mem::take(&mut state.pending_text);
+        let in_pre = state.open.last().is_some_and(|element| element.in_pre);
+        let parent_tag = state.open.last().map(|element| element.tag.clone());
+        let text_type = content_text_type(parent_tag.as_deref());
+
+        state.stats.text_nodes_found += 1;
+        match self.collector.prepare_text(&text, in_pre).filter(|text| {
+            self.collector.accepts_text(text, &text_type)
+                && text.len() >= self.collector.min_text_length(&text_type)
+        }) {
+            Some(text) => {
+                let depth = Self::depth(state);
+                state
+                    .texts
+                    .push(StreamedText::new(text, None, text_type, depth, parent_tag));
+                state.stats.translatable_texts += 1;
+            }
+            None => state.stats.filtered_texts += 1,
+        }
+    }
+
+    /// 像树构建一样，在开始标签前结束省略了结束标签的元素（如连续的 `<p>`、`<li>`）
+    fn close_implied(state: &mut StreamState, tag_name: &str) {
+        if state.open.last().is_some_and(|element| element.foreign) {
+            return;
+        }
+        if CLOSES_PARAGRAPH.contains(&tag_name) {
+            Self::close_in_scope(&mut state.open, &["p"], &[]);
+        }
+        if let Some((targets, boundaries)) = implied_end_tags(tag_name) {
+            Self::close_in_scope(&mut state.open, targets, boundaries);
+        }
+    }
+
+    /// 结束最近的 `targets` 元素及其中未结束的元素，先遇到边界元素时不结束
+    fn close_in_scope(open: &mut Vec<OpenElement>, targets: &[&str], boundaries: &[&str]) {
+        let position = open
+            .iter()
+            .rposition(|element| {
+                let tag = element.tag.as_str();
+                targets.contains(&tag)
+                    || SCOPE_BOUNDARIES.contains(&tag)
+                    || boundaries.contains(&tag)
+            })
+            .filter(|&position| targets.contains(&open[position].tag.as_str()));
+        if let Some(position) = position {
+            open.truncate(position);
+        }
+    }
+
+    fn start_tag(&self, state: &mut StreamState, tag: &Tag) -> TokenSinkResult<()> {
+        let tag_name = tag.name.to_string();
+        Self::close_implied(state, &tag_name);
+        let attr = |name: &str| {
+            tag.attrs
+                .iter()
+                .find(|attr| attr.name.local.as_ref() == name)
+                .map(|attr| attr.value.to_string())
+        };
+        let translate_no = attr("translate").is_some_and(|value| value.eq_ignore_ascii_case("no"));
+
+        let depth = Self::depth(state);
+        let parent = state.open.last();
+        let parent_skipped = parent.is_some_and(|parent| parent.skipped);
+        let in_head_title = tag_name == "title"
+            && parent.is_some_and(|parent| parent.skipped_head && parent.tag == "head");
+        let inherited_lang = parent.is_some_and(|parent| parent.in_target_lang);
+        let in_target_lang = self
+            .collector
+            .is_target_lang_attr(attr("lang").as_deref(), inherited_lang);
+        let skips_tag = self.collector.should_skip_tag(&tag_name)
+            || (tag_name == "template" && !self.collector.config.include_templates);
+        let skipped = (parent_skipped && !in_head_title) || translate_no || skips_tag;
+
+        // 与 DOM 收集一样，被跳过的元素本身的属性（例如 `<img>` 的 `alt`）仍然收集
+        let collects_attributes = if parent_skipped {
+            parent.is_some_and(|parent| parent.in_map) && tag_name == "area" && !translate_no
+        } else {
+            let hidden = skips_tag && HIDDEN_ATTRIBUTE_ELEMENTS.contains(&tag_name.as_str());
+            !(translate_no || hidden)
+        };
+        if collects_attributes && !in_target_lang && depth <= self.collector.config.max_depth {
+            self.collect_attributes(state, tag, &tag_name, depth);
+        }
+
+        let parent = state.open.last();
+        let element = OpenElement {
+            skipped,
+            in_target_lang,
+            in_pre: parent.is_some_and(|parent| parent.in_pre) || tag_name == "pre",
+            foreign: parent.is_some_and(|parent| parent.foreign)
+                || tag_name == "svg"
+                || tag_name == "math",
+            in_map: !translate_no
+                && (parent.is_some_and(|parent| parent.in_map)
+                    || (tag_name == "map" && collects_attributes && !in_target_lang)),
+            skipped_head: tag_name == "head" && skips_tag && !parent_skipped && !translate_no,
+            tag: tag_name,
+        };
+
+        let closes_itself =
+            VOID_ELEMENTS.contains(&element.tag.as_str()) || (tag.self_closing && element.foreign);
+        let result = if element.foreign {
+            TokenSinkResult::Continue
+        } else {
+            match element.tag.as_str() {
+                "title" | "textarea" => TokenSinkResult::RawData(RawKind::Rcdata),
+                "style" | "xmp" | "iframe" | "noembed" | "noframes" | "noscript" => {
+                    TokenSinkResult::RawData(RawKind::Rawtext)
+                }
+                "script" => TokenSinkResult::RawData(RawKind::ScriptData),
+                "plaintext" => TokenSinkResult::Plaintext,
+                _ => TokenSinkResult::Continue,
+            }
+        };
+        if !closes_itself {
+            state.stats.nodes_visited += 1;
+            if element.skipped {
+                state.stats.nodes_skipped += 1;
+            }
+            state.open.push(element);
+        }
+        result
+    }
+
+    fn collect_attributes(&self, state: &mut StreamState, tag: &Tag, tag_name: &str, depth: usize) {
+        let mut names = self.collector.config.collect_attributes.clone();
+        let is_button_input = tag_name == "input"
+            && tag.attrs.iter().any(|attr| {
+                attr.name.local.as_ref() == "type"
+                    && BUTTON_INPUT_TYPES.contains(&attr.value.trim().to_ascii_lowercase().as_str())
+            });
+        if is_button_input && !names.iter().any(|name| name == "value") {
+            names.push("value".to_string());
+        }
+
+        for attr_name in names {
+            let Some(attr) = tag
+                .attrs
+                .iter()
+                .find(|attr| attr.name.local.as_ref() == attr_name)
+            else {
+                continue;
+            };
+            state.stats.attributes_found += 1;
+
+            let text_type = self
+                .collector
+                .config
+                .attribute_types
+                .get(&attr_name)
+                .cloned()
+                .unwrap_or_else(|| TextType::Attribute(attr_name.clone()));
+            match self
+                .collector
+                .prepare_text(&attr.value, false)
+                .filter(|text| {
+                    self.collector.accepts_text(text, &text_type)
+                        && text.len() >= self.collector.min_text_length(&text_type)
+                }) {
+                Some(text) => {
+                    let parent_tag = state.open.last().map(|element| element.tag.clone());
+                    state.texts.push(StreamedText::new(
+                        text,
+                        Some(attr_name),
+                        text_type,
+                        depth,
+                        parent_tag,
+                    ));
+                    state.stats.translatable_attributes += 1;
+                }
+                None => state.stats.filtered_attributes += 1,
+            }
+        }
+    }
+
+    /// 结束标签关闭最近的同名元素及其中未结束的元素，没有同名元素时忽略
+    fn end_tag(&self, state: &mut StreamState, tag: &Tag) {
+        if let Some(position) = state
+            .open
+            .iter()
+            .rposition(|element| element.tag == tag.name.as_ref())
+        {
+            state.open.truncate(position);
+        }
+    }
+}
+
+impl TokenSink for StreamSink<'_> {
+    type Handle = ();
+
+    fn process_token(&self, token: Token, _line_number: u64) -> TokenSinkResult<()> {
+        let mut state = self.state.borrow_mut();
+        match token {
+            Token::CharacterTokens(text) => {
+                if self.collects_text(&state) {
+                    state.pending_text.push_str(&text);
+                }
+                TokenSinkResult::Continue
+            }
+            Token::TagToken(tag) => {
+                self.flush_text(&mut state);
+                match tag.kind {
+                    TagKind::StartTag => self.start_tag(&mut state, &tag),
+                    TagKind::EndTag => {
+                        self.end_tag(&mut state, &tag);
+                        TokenSinkResult::Continue
+                    }
+                }
+            }
+            Token::CommentToken(_) | Token::EOFToken => {
+                self.flush_text(&mut state);
+                TokenSinkResult::Continue
+            }
+            _ => TokenSinkResult::Continue,
+        }
+    }
+}
+
+impl TextCollector {
+    /// 不构建 DOM，逐块读取 HTML 并收集可翻译文本
+    ///
+    /// 用于估算无法完整解析为 DOM 的超大文档的翻译量：只保留尚未结束的元素、当前文本节点和
+    /// 收集结果，内存占用与文档大小基本无关。输入按 UTF-8 解码，无效字节替换为 U+FFFD。
+    ///
+    /// 跳过规则、属性收集、目标语言判断和长度过滤与 [`Self::collect_translatable_texts`] 相同，
+    /// 但有以下差别：
+    /// - 结果按文档顺序返回，不去重也不排序；
+    /// - 不做完整的树构建，只按开始标签隐式结束 `<p>`、`<li>`、`<dt>`、`<dd>`、`<tr>`、`<td>`、
+    ///   `<th>`、`<option>` 等元素，少数文本的类型和深度可能与 DOM 收集不同；
+    /// - 不支持 `only_selectors`、`include_social_meta`、`translate_preformatted`、
+    ///   `translate_json_paths`。
+    pub fn stream_collect_texts<R: Read>(
+        &mut self,
+        mut reader: R,
+    ) -> io::Result<Vec<StreamedText>> {
+        let sink = StreamSink {
+            collector: self,
+            state: RefCell::new(StreamState {
+                open: Vec::new(),
+                pending_text: String::new(),
+                texts: Vec::new(),
+                stats: CollectionStats::default(),
+            }),
+        };
+        let tokenizer = Tokenizer::new(sink, TokenizerOpts::default());
+        let input = BufferQueue::default();
+
+        let mut chunk = vec![0; STREAM_CHUNK_SIZE];
+        let mut undecoded = Vec::new();
+        loop {
+            let read = match reader.read(&mut chunk) {
+                Ok(0) => break,
+                Ok(read) => read,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            undecoded.extend_from_slice(&chunk[..read]);
+            input.push_back(decode_utf8_prefix(&mut undecoded).into());
+            let _ = tokenizer.feed(&input);
+        }
+        if !undecoded.is_empty() {
+            input.push_back(String::from_utf8_lossy(&undecoded).into_owned().into());
+            let _ = tokenizer.feed(&input);
+        }
+        tokenizer.end();
+
+        let mut state = tokenizer.sink.state.into_inner();
+        state.stats.final_text_count = state.texts.len();
+        self.stats = state.stats;
+        Ok(state.texts)
+    }
+}
+
+/// 取出 `bytes` 开头可以解码的部分，末尾不完整的字符留到下次
+fn decode_utf8_prefix(bytes: &mut Vec<u8>) -> String {
+    let mut decoded = String::new();
+    let mut start = 0;
+    loop {
+        match std::str::from_utf8(&bytes[start..]) {
+            Ok(valid) => {
+                decoded.push_str(valid);
+                start = bytes.len();
+                break;
+            }
+            Err(error) => {
+                let valid_end = start + error.valid_up_to();
+                decoded.push_str(std::str::from_utf8(&bytes[start..valid_end]).unwrap_or_default());
+                match error.error_len() {
+                    Some(invalid) => {
+                        decoded.push(char::REPLACEMENT_CHARACTER);
+                        start = valid_end + invalid;
+                    }
+                    None => {
+                        start = valid_end;
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    bytes.drain(..start);
+    decoded
+}
+
 /// 便利函数：收集可翻译文本
 pub fn collect_translatable_texts(root: &Handle) -> Vec<TextItem> {
     let mut collector = TextCollector::default();
@@ -830,6 +2138,26 @@ pub fn collect_translatable_texts(root: &Handle) -> Vec<TextItem> {
         .unwrap_or_default()
 }
 
+/// 便利函数：不构建 DOM，流式收集可翻译文本，见 [`TextCollector::stream_collect_texts`]
+pub fn stream_collect_texts<R: Read>(reader: R) -> io::Result<Vec<StreamedText>> {
+    TextCollector::default().stream_collect_texts(reader)
+}
+
+/// 便利函数：对照翻译前收集的文本项与节点当前内容，返回发生变化的（原文, 译文）对
+pub fn translation_pairs(items: &[TextItem]) -> Vec<(String, String)> {
+    items
+        .iter()
+        .filter_map(|item| {
+            let current = item.current_text()?;
+            let source = item.text.trim();
+            let target = current.trim();
+            // 收集时文本可能已折叠空白，仅空白不同的视为未翻译
+            (!source.split_whitespace().eq(target.split_whitespace()))
+                .then(|| (source.to_string(), target.to_string()))
+        })
+        .collect()
+}
+
 /// 便利函数：收集指定类型的文本
 pub fn collect_texts_by_type(root: &Handle, text_type: TextType) -> Vec<TextItem> {
     let texts = collect_translatable_texts(root);
@@ -1010,6 +2338,7 @@ mod tests {
             text_type: TextType::Content,
             depth: 0,
             parent_tag: None,
+            duplicates: Vec::new(),
         };
 
         let title_item = TextItem {
@@ -1020,6 +2349,7 @@ mod tests {
             text_type: TextType::Title,
             depth: 0,
             parent_tag: Some("h1".to_string()),
+            duplicates: Vec::new(),
         };
 
         let button_item = TextItem {
@@ -1030,6 +2360,7 @@ mod tests {
             text_type: TextType::Button,
             depth: 0,
             parent_tag: Some("button".to_string()),
+            duplicates: Vec::new(),
         };
 
         let content_weight = content_item.complexity_weight();
@@ -1150,4 +2481,729 @@ mod tests {
             TextType::Content
         );
     }
+
+    #[test]
+    fn test_head_title_is_collected() {
+        let dom = create_test_dom(
+            "<html><head><title>Quarterly report</title><meta name=\"description\" content=\"Skipped meta\"></head>\
+             <body><p>Body paragraph</p></body></html>",
+        );
+
+        for enable_parallel in [true, false] {
+            let config = CollectorConfig {
+                enable_parallel,
+                ..CollectorConfig::default()
+            };
+            let texts = TextCollector::new(config)
+                .collect_translatable_texts(&dom.document)
+                .unwrap();
+
+            let title = texts
+                .iter()
+                .find(|item| item.text == "Quarterly report")
+                .expect("title should be collected");
+            assert_eq!(title.text_type, TextType::Title);
+            assert_eq!(title.priority, TextPriority::Critical);
+            assert!(!texts.iter().any(|item| item.text == "Skipped meta"));
+        }
+
+        // 页面已是目标语言时标题同样不收集
+        let dom = create_test_dom(
+            "<html lang=\"zh\"><head><title>Quarterly report</title></head><body></body></html>",
+        );
+        let config = CollectorConfig {
+            target_lang: Some("zh".to_string()),
+            ..CollectorConfig::default()
+        };
+        let texts = TextCollector::new(config)
+            .collect_translatable_texts(&dom.document)
+            .unwrap();
+        assert!(texts.is_empty());
+    }
+
+    #[test]
+    fn test_social_meta_is_opt_in() {
+        let dom = create_test_dom(
+            "<html><head><meta property=\"og:title\" content=\"Launch announcement\">\
+             <meta name=\"twitter:description\" content=\"Everything about the launch\">\
+             <meta property=\"og:image\" content=\"https://example.com/cover.png\">\
+             <meta name=\"description\" content=\"Plain description\"></head>\
+             <body><p>Body paragraph</p></body></html>",
+        );
+
+        let texts = TextCollector::default()
+            .collect_translatable_texts(&dom.document)
+            .unwrap();
+        assert!(!texts.iter().any(|item| item.attr_name.is_some()));
+
+        let config = CollectorConfig {
+            include_social_meta: true,
+            ..CollectorConfig::default()
+        };
+        let texts = TextCollector::new(config)
+            .collect_translatable_texts(&dom.document)
+            .unwrap();
+        let mut meta_texts: Vec<&str> = texts
+            .iter()
+            .filter(|item| item.attr_name.as_deref() == Some("content"))
+            .map(|item| item.text.as_str())
+            .collect();
+        meta_texts.sort();
+        assert_eq!(
+            meta_texts,
+            vec!["Everything about the launch", "Launch announcement"]
+        );
+    }
+
+    #[test]
+    fn test_json_island_paths() {
+        let dom = create_test_dom(
+            "<html><body><div id=\"__next\"><h1>Welcome back</h1></div>\
+             <script id=\"__NEXT_DATA__\" type=\"application/json\">\
+             {\"props\":{\"pageProps\":{\"title\":\"Welcome back\",\"slug\":\"welcome-back\"}},\"page\":\"/\"}\
+             </script></body></html>",
+        );
+
+        let texts = TextCollector::default()
+            .collect_translatable_texts(&dom.document)
+            .unwrap();
+        assert!(!texts.iter().any(|item| item.is_json_value()));
+
+        let config = CollectorConfig {
+            translate_json_paths: vec!["props.pageProps.title".to_string()],
+            ..CollectorConfig::default()
+        };
+        let texts = TextCollector::new(config)
+            .collect_translatable_texts(&dom.document)
+            .unwrap();
+        let json_items: Vec<&TextItem> = texts.iter().filter(|item| item.is_json_value()).collect();
+        assert_eq!(json_items.len(), 1);
+        assert_eq!(json_items[0].text, "Welcome back");
+        assert_eq!(
+            json_items[0].text_type,
+            TextType::JsonValue("props.pageProps.title".to_string())
+        );
+
+        crate::translation::processor::apply_translation(json_items[0], "欢迎回来").unwrap();
+        assert_eq!(json_items[0].current_text().as_deref(), Some("欢迎回来"));
+        let scripts = find_nodes(&dom.document, vec!["html", "body", "script"]);
+        assert_eq!(
+            text_content(&scripts[0]),
+            "{\"props\":{\"pageProps\":{\"title\":\"欢迎回来\",\"slug\":\"welcome-back\"}},\"page\":\"/\"}"
+        );
+    }
+
+    #[test]
+    fn test_canvas_fallback_is_opt_in() {
+        let dom = create_test_dom(
+            "<body><p>Visible paragraph</p><canvas><p>Fallback paragraph</p></canvas></body>",
+        );
+
+        let mut translation_config = TranslationConfig::default();
+        let texts: Vec<String> = TextCollector::new(CollectorConfig::from(&translation_config))
+            .collect_translatable_texts(&dom.document)
+            .unwrap()
+            .into_iter()
+            .map(|item| item.text)
+            .collect();
+        assert_eq!(texts, vec!["Visible paragraph".to_string()]);
+
+        translation_config.translate_canvas_fallback = true;
+        let mut texts: Vec<String> = TextCollector::new(CollectorConfig::from(&translation_config))
+            .collect_translatable_texts(&dom.document)
+            .unwrap()
+            .into_iter()
+            .map(|item| item.text)
+            .collect();
+        texts.sort();
+        assert_eq!(
+            texts,
+            vec![
+                "Fallback paragraph".to_string(),
+                "Visible paragraph".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ruby_annotations_are_kept() {
+        let dom = create_test_dom(
+            "<body><p>Read <ruby>Tokyo Tower<rp>(</rp><rt>toukyou tawaa</rt><rp>)</rp></ruby> today</p>\
+             <p><ruby><ruby>Mount Fuji<rt>fuji san</rt></ruby><rtc>highest mountain</rtc></ruby></p></body>",
+        );
+
+        let texts: Vec<String> = TextCollector::new(CollectorConfig::default())
+            .collect_translatable_texts(&dom.document)
+            .unwrap()
+            .into_iter()
+            .map(|item| item.text)
+            .collect();
+
+        let mut sorted = texts.clone();
+        sorted.sort();
+        assert_eq!(
+            sorted,
+            vec![
+                "Mount Fuji".to_string(),
+                "Read".to_string(),
+                "Tokyo Tower".to_string(),
+                "today".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_attributes_of_skipped_elements() {
+        let dom = create_test_dom(
+            "<html><head><link rel=\"alternate stylesheet\" title=\"High contrast\" href=\"a.css\"></head>\
+             <body><img src=\"map.png\" usemap=\"#site\" alt=\"Site map\">\
+             <map name=\"site\" title=\"Navigation\">\
+             <area shape=\"rect\" coords=\"0,0,10,10\" href=\"/\" alt=\"Home\">\
+             <div><area shape=\"rect\" coords=\"10,0,20,10\" href=\"/about\" title=\"About us\"></div>\
+             <area translate=\"no\" href=\"/x\" alt=\"Brand name\">\
+             </map></body></html>",
+        );
+
+        for enable_parallel in [true, false] {
+            let items = TextCollector::new(CollectorConfig {
+                enable_parallel,
+                ..CollectorConfig::default()
+            })
+            .collect_translatable_texts(&dom.document)
+            .unwrap();
+            let mut texts: Vec<(String, Option<String>)> = items
+                .iter()
+                .map(|item| (item.text.clone(), item.attr_name.clone()))
+                .collect();
+            texts.sort();
+            assert_eq!(
+                texts,
+                vec![
+                    ("About us".to_string(), Some("title".to_string())),
+                    ("Home".to_string(), Some("alt".to_string())),
+                    ("Navigation".to_string(), Some("title".to_string())),
+                    ("Site map".to_string(), Some("alt".to_string())),
+                ]
+            );
+        }
+
+        let items = TextCollector::new(CollectorConfig::default())
+            .collect_translatable_texts(&dom.document)
+            .unwrap();
+        let home = items.iter().find(|item| item.text == "Home").unwrap();
+        crate::translation::processor::apply_translation(home, "首页").unwrap();
+        assert_eq!(get_node_attr(&home.node, "alt").as_deref(), Some("首页"));
+    }
+
+    #[test]
+    fn test_button_input_values() {
+        let dom = create_test_dom(
+            "<body><form>\
+             <input type=\"text\" name=\"q\" value=\"current search terms\" placeholder=\"Search the site\">\
+             <input type=\"hidden\" name=\"token\" value=\"hidden form value\">\
+             <input type=\"Submit\" value=\"Search now\">\
+             <input type=\"reset\" value=\"Clear the form\">\
+             </form></body>",
+        );
+
+        let items = TextCollector::new(CollectorConfig::default())
+            .collect_translatable_texts(&dom.document)
+            .unwrap();
+        let mut texts: Vec<(String, Option<String>)> = items
+            .iter()
+            .map(|item| (item.text.clone(), item.attr_name.clone()))
+            .collect();
+        texts.sort();
+
+        assert_eq!(
+            texts,
+            vec![
+                ("Clear the form".to_string(), Some("value".to_string())),
+                ("Search now".to_string(), Some("value".to_string())),
+                (
+                    "Search the site".to_string(),
+                    Some("placeholder".to_string())
+                ),
+            ]
+        );
+        assert!(items
+            .iter()
+            .filter(|item| item.attr_name.as_deref() == Some("value"))
+            .all(|item| item.text_type == TextType::Button));
+    }
+
+    #[test]
+    fn test_min_text_length_per_type() {
+        let dom =
+            create_test_dom("<body><p>Hi</p><button>Go</button><p>Longer paragraph</p></body>");
+        let collect = |config: CollectorConfig| -> Vec<String> {
+            let mut texts: Vec<String> = TextCollector::new(config)
+                .collect_translatable_texts(&dom.document)
+                .unwrap()
+                .into_iter()
+                .map(|item| item.text)
+                .collect();
+            texts.sort();
+            texts
+        };
+
+        // 默认情况下两个短单词都不收集
+        assert_eq!(
+            collect(CollectorConfig::default()),
+            vec!["Longer paragraph".to_string()]
+        );
+
+        for enable_parallel in [true, false] {
+            let config = CollectorConfig {
+                enable_parallel,
+                min_text_lengths: HashMap::from([(TextType::Button, 1), (TextType::Content, 3)]),
+                ..CollectorConfig::default()
+            };
+            assert_eq!(
+                collect(config),
+                vec!["Go".to_string(), "Longer paragraph".to_string()]
+            );
+        }
+    }
+
+    #[test]
+    fn test_template_contents_are_opt_in() {
+        let html = "<body><p>Visible paragraph</p>\
+                    <template><p>Template paragraph</p></template></body>";
+        let dom = create_test_dom(html);
+
+        let mut config = CollectorConfig::default();
+        for enable_parallel in [true, false] {
+            config.enable_parallel = enable_parallel;
+
+            config.include_templates = false;
+            let texts: Vec<String> = TextCollector::new(config.clone())
+                .collect_translatable_texts(&dom.document)
+                .unwrap()
+                .into_iter()
+                .map(|item| item.text)
+                .collect();
+            assert_eq!(texts, vec!["Visible paragraph".to_string()]);
+
+            config.include_templates = true;
+            let mut texts: Vec<String> = TextCollector::new(config.clone())
+                .collect_translatable_texts(&dom.document)
+                .unwrap()
+                .into_iter()
+                .map(|item| item.text)
+                .collect();
+            texts.sort();
+            assert_eq!(
+                texts,
+                vec![
+                    "Template paragraph".to_string(),
+                    "Visible paragraph".to_string()
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_skips_elements_declared_in_target_lang() {
+        let html = "<html lang=\"zh\"><body><p>Mixed paragraph text</p>\
+                    <blockquote lang=\"en-GB\" title=\"Quoted from the original\">An English quote \
+                    <span lang=\"fr\">Une citation</span></blockquote>\
+                    <p lang=\"\">Unknown language text</p></body></html>";
+        let dom = create_test_dom(html);
+
+        let mut config = CollectorConfig::default();
+        for enable_parallel in [true, false] {
+            config.enable_parallel = enable_parallel;
+
+            config.target_lang = None;
+            let texts = TextCollector::new(config.clone())
+                .collect_translatable_texts(&dom.document)
+                .unwrap();
+            assert_eq!(texts.len(), 5);
+
+            config.target_lang = Some("EN".to_string());
+            let mut texts: Vec<String> = TextCollector::new(config.clone())
+                .collect_translatable_texts(&dom.document)
+                .unwrap()
+                .into_iter()
+                .map(|item| item.text)
+                .collect();
+            texts.sort();
+            assert_eq!(
+                texts,
+                vec![
+                    "Mixed paragraph text".to_string(),
+                    "Une citation".to_string(),
+                    "Unknown language text".to_string(),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_normalize_whitespace_drops_indentation_and_preserves_pre() {
+        let html = "<html><body>\n    <div>\n        <p>Hello\n            world</p>\n    </div>\n    \
+                    <pre>  first line\n      indented line</pre>\n</body></html>";
+        let dom = create_test_dom(html);
+        let mut config = CollectorConfig::default();
+        config.skip_elements.retain(|tag| tag != "pre");
+
+        for enable_parallel in [true, false] {
+            config.enable_parallel = enable_parallel;
+            let mut collector = TextCollector::new(config.clone());
+            let mut texts: Vec<String> = collector
+                .collect_translatable_texts(&dom.document)
+                .unwrap()
+                .into_iter()
+                .map(|item| item.text)
+                .collect();
+            texts.sort();
+
+            assert_eq!(
+                texts,
+                vec![
+                    "  first line\n      indented line".to_string(),
+                    "Hello world".to_string(),
+                ]
+            );
+        }
+
+        // 关闭折叠时保留原始空白，但纯空白节点仍被丢弃
+        config.normalize_whitespace = false;
+        let mut collector = TextCollector::new(config);
+        let texts = collector.collect_translatable_texts(&dom.document).unwrap();
+        assert_eq!(texts.len(), 2);
+        assert!(texts.iter().all(|item| !item.text.trim().is_empty()));
+        assert!(texts.iter().any(|item| item.text == "Hello\n            world"));
+    }
+
+    #[test]
+    fn test_normalize_invisible_chars() {
+        let html = "<html><body><p>Hel\u{200B}lo wo\u{00AD}rld\u{00A0}\u{00A0}again</p>\
+                    <p>Bonjour\u{00A0}! Il\u{00A0}a\u{202F}: \u{00AB}\u{00A0}oui\u{00A0}\u{00BB} 10\u{00A0}000</p>\
+                    <p>\u{200B}\u{FEFF}</p></body></html>";
+        let dom = create_test_dom(html);
+        let mut collector = TextCollector::new(CollectorConfig::default());
+        let texts: Vec<String> = collector
+            .collect_translatable_texts(&dom.document)
+            .unwrap()
+            .into_iter()
+            .map(|item| item.text)
+            .collect();
+
+        let hello = texts.iter().find(|text| text.starts_with("Hel")).unwrap();
+        assert_eq!(hello, "Hello world again");
+        // 字符数按处理后的文本计算，不包括被删除的不可见字符
+        assert_eq!(hello.chars().count(), 17);
+        assert!(texts.contains(
+            &"Bonjour\u{00A0}! Il a\u{202F}: \u{00AB}\u{00A0}oui\u{00A0}\u{00BB} 10\u{00A0}000"
+                .to_string()
+        ));
+        assert_eq!(texts.len(), 2);
+
+        let mut config = CollectorConfig::default();
+        config.normalize_invisible_chars = false;
+        let mut collector = TextCollector::new(config);
+        let texts = collector.collect_translatable_texts(&dom.document).unwrap();
+        assert!(texts
+            .iter()
+            .any(|item| item.text == "Hel\u{200B}lo wo\u{00AD}rld again"));
+    }
+
+    #[test]
+    fn test_translate_preformatted() {
+        let html = "<html><body>\
+                    <pre>\n  Roses are red,\n    violets are blue.\n</pre>\
+                    <pre><code>fn main() {\n    // Print the greeting twice\n    let x = f(1, [2]);\n}</code></pre>\
+                    <p>Install the tools with <code>make</code></p></body></html>";
+
+        for enable_parallel in [true, false] {
+            let dom = create_test_dom(html);
+            let body = find_nodes(&dom.document, vec!["html", "body"]).remove(0);
+            let original = text_content(&body);
+
+            let config = CollectorConfig {
+                enable_parallel,
+                ..CollectorConfig::default()
+            };
+            let texts = TextCollector::new(config)
+                .collect_translatable_texts(&dom.document)
+                .unwrap();
+            assert_eq!(texts.len(), 1);
+            assert_eq!(texts[0].text, "Install the tools with");
+
+            let config = CollectorConfig {
+                enable_parallel,
+                translate_preformatted: true,
+                ..CollectorConfig::default()
+            };
+            let mut texts: Vec<String> = TextCollector::new(config)
+                .collect_translatable_texts(&dom.document)
+                .unwrap()
+                .into_iter()
+                .map(|item| item.text)
+                .collect();
+            texts.sort();
+            assert_eq!(
+                texts,
+                vec![
+                    "Install the tools with",
+                    "Print the greeting twice",
+                    "Roses are red,",
+                    "violets are blue."
+                ]
+            );
+            // 拆分文本节点不改变内容和空白
+            assert_eq!(text_content(&body), original);
+        }
+    }
+
+    #[test]
+    fn test_only_selectors_restricts_collection() {
+        let html = "<html><body><nav><a href=\"/\">Home page link</a></nav>\
+                    <article><h1>Article headline</h1><p>Article body text</p></article>\
+                    <div class=\"sidebar extra\"><p>Sidebar paragraph</p></div>\
+                    <section id=\"notes\"><p>Footnote content</p></section>\
+                    <footer>Footer copyright text</footer></body></html>";
+        let dom = create_test_dom(html);
+
+        let mut config = CollectorConfig::default();
+        config.enable_priority_sorting = false;
+        config.only_selectors = vec!["article".to_string()];
+        for enable_parallel in [true, false] {
+            config.enable_parallel = enable_parallel;
+            let texts: Vec<String> = TextCollector::new(config.clone())
+                .collect_translatable_texts(&dom.document)
+                .unwrap()
+                .into_iter()
+                .map(|item| item.text)
+                .collect();
+            assert_eq!(
+                texts,
+                vec![
+                    "Article headline".to_string(),
+                    "Article body text".to_string()
+                ]
+            );
+        }
+
+        config.only_selectors = vec![
+            "div.sidebar".to_string(),
+            "#notes".to_string(),
+            ".missing".to_string(),
+        ];
+        let texts: Vec<String> = TextCollector::new(config)
+            .collect_translatable_texts(&dom.document)
+            .unwrap()
+            .into_iter()
+            .map(|item| item.text)
+            .collect();
+        assert_eq!(
+            texts,
+            vec![
+                "Sidebar paragraph".to_string(),
+                "Footnote content".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_attribute_types_classify_custom_attributes() {
+        let dom = create_test_dom(
+            "<html><body><span data-tooltip=\"Opens the settings dialog\" \
+             title=\"Settings button\" data-note=\"Internal remark here\">Settings</span></body></html>",
+        );
+
+        let mut config = CollectorConfig::default();
+        config.enable_parallel = false;
+        config.collect_attributes = vec![
+            "title".to_string(),
+            "data-tooltip".to_string(),
+            "data-note".to_string(),
+        ];
+        config
+            .attribute_types
+            .insert("data-tooltip".to_string(), TextType::Tooltip);
+
+        let items = TextCollector::new(config)
+            .collect_translatable_texts(&dom.document)
+            .unwrap();
+        let attribute = |name: &str| {
+            items
+                .iter()
+                .find(|item| item.attr_name.as_deref() == Some(name))
+                .unwrap()
+        };
+
+        assert_eq!(attribute("data-tooltip").text_type, TextType::Tooltip);
+        assert_eq!(attribute("data-tooltip").priority, TextPriority::High);
+        assert_eq!(attribute("title").text_type, TextType::Tooltip);
+        assert_eq!(
+            attribute("data-note").text_type,
+            TextType::Attribute("data-note".to_string())
+        );
+        assert_eq!(attribute("data-note").priority, TextPriority::Low);
+    }
+
+    #[test]
+    fn test_select_options_are_form_labels() {
+        let dom = create_test_dom(
+            "<html><body><form><select name=\"country\">\
+             <optgroup label=\"Western Europe\"><option value=\"de\">Germany</option>\
+             <option value=\"fr\">France</option></optgroup></select></form></body></html>",
+        );
+
+        let mut config = CollectorConfig::default();
+        config.enable_parallel = false;
+        let items = TextCollector::new(config)
+            .collect_translatable_texts(&dom.document)
+            .unwrap();
+
+        let mut texts: Vec<&str> = items.iter().map(|item| item.text.as_str()).collect();
+        texts.sort();
+        assert_eq!(texts, vec!["France", "Germany", "Western Europe"]);
+        assert!(items.iter().all(
+            |item| item.text_type == TextType::FormLabel && item.priority == TextPriority::High
+        ));
+
+        for item in &items {
+            let translated = match item.text.as_str() {
+                "Germany" => "德国",
+                "France" => "法国",
+                _ => "西欧",
+            };
+            crate::translation::processor::apply_translation(item, translated).unwrap();
+        }
+
+        let select =
+            &crate::html::find_nodes(&dom.document, vec!["html", "body", "form", "select"])[0];
+        let mut buf: Vec<u8> = Vec::new();
+        html5ever::serialize(
+            &mut buf,
+            &markup5ever_rcdom::SerializableHandle::from(select.clone()),
+            Default::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "<optgroup label=\"西欧\"><option value=\"de\">德国</option>\
+             <option value=\"fr\">法国</option></optgroup>"
+        );
+    }
+
+    #[test]
+    fn test_translation_pairs_reports_changed_texts() {
+        let dom = create_test_dom(
+            "<html><body><p title=\"Helpful tooltip\">Hello world</p><p>Unchanged text</p></body></html>",
+        );
+        let mut collector = TextCollector::default();
+        let items = collector.collect_translatable_texts(&dom.document).unwrap();
+
+        for item in &items {
+            match (&item.attr_name, item.text.as_str()) {
+                (Some(attr_name), _) => {
+                    crate::html::set_node_attr(&item.node, attr_name, Some("提示".to_string()))
+                }
+                (None, "Hello world") => {
+                    if let NodeData::Text { ref contents } = item.node.data {
+                        *contents.borrow_mut() = "你好世界".into();
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut pairs = translation_pairs(&items);
+        pairs.sort();
+        assert_eq!(
+            pairs,
+            vec![
+                ("Hello world".to_string(), "你好世界".to_string()),
+                ("Helpful tooltip".to_string(), "提示".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stream_collect_matches_dom_collection() {
+        /// 每次只读出一个字节，多字节字符被拆在两次读取之间
+        struct ByteReader<'a>(&'a [u8]);
+
+        impl std::io::Read for ByteReader<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                match self.0.split_first() {
+                    Some((byte, rest)) if !buf.is_empty() => {
+                        buf[0] = *byte;
+                        self.0 = rest;
+                        Ok(1)
+                    }
+                    _ => Ok(0),
+                }
+            }
+        }
+
+        let html = "<!DOCTYPE html><html><head><title>Archive &amp; notes</title>\
+                    <style>p { color: red }</style><script>var s = \"<p>Not text</p>\";</script>\
+                    </head><body><h1>Streaming collection</h1>\
+                    <p>Plain paragraph with <a href=\"/x\">a link inside</a> and café prose.</p>\
+                    <img src=\"a.png\" alt=\"Picture of the harbour\">\
+                    <input type=\"submit\" value=\"Send message\"><input placeholder=\"Your name here\">\
+                    <div translate=\"no\">Brand name stays</div>\
+                    <p lang=\"zh\">已经是中文的段落</p>\
+                    <map name=\"m\"><area href=\"/y\" alt=\"Area description\"></map>\
+                    <ul><li>First item text<li>Second item text</ul>\
+                    <!-- comment --><pre>  keep   spacing  </pre></body></html>";
+        let config = CollectorConfig {
+            target_lang: Some("zh".to_string()),
+            ..CollectorConfig::default()
+        };
+
+        let dom = create_test_dom(html);
+        let mut expected: Vec<(String, TextType)> = TextCollector::new(config.clone())
+            .collect_translatable_texts(&dom.document)
+            .unwrap()
+            .into_iter()
+            .map(|item| (item.text, item.text_type))
+            .collect();
+        let mut streamed: Vec<(String, TextType)> = TextCollector::new(config)
+            .stream_collect_texts(ByteReader(html.as_bytes()))
+            .unwrap()
+            .into_iter()
+            .map(|item| (item.text, item.text_type))
+            .collect();
+
+        assert!(streamed.contains(&("Archive & notes".to_string(), TextType::Title)));
+        assert!(streamed.contains(&("Plain paragraph with".to_string(), TextType::Content)));
+        expected.sort_by(|a, b| a.0.cmp(&b.0));
+        streamed.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(streamed, expected);
+
+        // 不闭合的段落和单元格不应该越嵌越深，超过 max_depth 后丢失文本
+        let mut html = String::from("<body>");
+        for i in 0..80 {
+            html.push_str(&format!("<p>Unclosed paragraph number {i}"));
+        }
+        html.push_str(
+            "<table><tr><td>First cell text<td>Second cell text<tr><td>Third cell text</table>",
+        );
+        let config = CollectorConfig::default();
+        let dom = create_test_dom(&html);
+        let mut expected: Vec<(String, TextType)> = TextCollector::new(config.clone())
+            .collect_translatable_texts(&dom.document)
+            .unwrap()
+            .into_iter()
+            .map(|item| (item.text, item.text_type))
+            .collect();
+        let mut streamed: Vec<(String, TextType)> = TextCollector::new(config)
+            .stream_collect_texts(html.as_bytes())
+            .unwrap()
+            .into_iter()
+            .map(|item| (item.text, item.text_type))
+            .collect();
+
+        assert_eq!(streamed.len(), 83);
+        expected.sort_by(|a, b| a.0.cmp(&b.0));
+        streamed.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(streamed, expected);
+    }
 }
@@ -0,0 +1,299 @@
+//! CSS 生成内容中的文本
+//!
+//! `content` 属性的值可以把字符串与 `counter()`、`attr()` 以及 `open-quote` 等关键字拼接，
+//! 例如 `content: "Chapter " counter(ch) ": Intro"`。只有值中处于最外层的带引号字符串是
+//! 可翻译的文本；函数（包括 `counters()` 的分隔符参数和 `url()`）与关键字保持原样，
+//! 编号和拼接结构因此不受翻译影响。
+//!
+//! [`content_literals`] 找出这些字符串，[`replace_content_literals`] 把译文重新转义后写回，
+//! 其余字符（包括注释和其他属性）不做任何改动。
+
+use std::ops::Range;
+
+/// `content` 值中的一个字符串字面量
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentLiteral {
+    /// 字符串在 CSS 中的字节范围，包括两端的引号
+    pub range: Range<usize>,
+    /// 反转义之后的文本
+    pub text: String,
+    /// 使用的引号
+    pub quote: char,
+}
+
+/// 找出所有 `content` 声明中最外层的字符串字面量，按出现顺序返回
+pub fn content_literals(css: &str) -> Vec<ContentLiteral> {
+    let bytes = css.as_bytes();
+    let mut literals = Vec::new();
+    let mut depth = 0usize;
+    // 上一个有意义的字符是否为声明的开始（`{` 或 `;`）
+    let mut at_declaration_start = false;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = skip_comment(css, i);
+                continue;
+            }
+            b'"' | b'\'' => {
+                i = skip_string(css, i).end;
+                at_declaration_start = false;
+                continue;
+            }
+            b'{' => {
+                depth += 1;
+                at_declaration_start = true;
+            }
+            b'}' => {
+                depth = depth.saturating_sub(1);
+                at_declaration_start = true;
+            }
+            b';' => at_declaration_start = true,
+            byte if byte.is_ascii_whitespace() => {}
+            _ if depth > 0 && at_declaration_start => {
+                at_declaration_start = false;
+                if let Some(value_start) = content_value_start(css, i) {
+                    i = collect_value_literals(css, value_start, &mut literals);
+                    continue;
+                }
+            }
+            _ => at_declaration_start = false,
+        }
+        i += 1;
+    }
+
+    literals
+}
+
+/// 用译文替换字符串字面量，`translations` 与 `literals` 一一对应，`None` 表示保留原文
+pub fn replace_content_literals(
+    css: &str,
+    literals: &[ContentLiteral],
+    translations: &[Option<String>],
+) -> String {
+    let mut result = String::with_capacity(css.len());
+    let mut last = 0;
+
+    for (literal, translated) in literals.iter().zip(translations) {
+        if let Some(translated) = translated {
+            result.push_str(&css[last..literal.range.start]);
+            result.push_str(&quote_string(translated, literal.quote));
+            last = literal.range.end;
+        }
+    }
+    result.push_str(&css[last..]);
+
+    result
+}
+
+/// 如果 `start` 处是 `content` 属性名，返回冒号之后值的起始位置
+fn content_value_start(css: &str, start: usize) -> Option<usize> {
+    let rest = &css[start..];
+    let name_len = rest
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+        .unwrap_or(rest.len());
+    if !rest[..name_len].eq_ignore_ascii_case("content") {
+        return None;
+    }
+
+    let after_name = &rest[name_len..];
+    let colon = after_name.len() - after_name.trim_start().len();
+    if after_name[colon..].starts_with(':') {
+        Some(start + name_len + colon + 1)
+    } else {
+        None
+    }
+}
+
+/// 收集一个声明值中的字符串，返回值结束的位置（`;` 或 `}` 处）
+fn collect_value_literals(css: &str, start: usize, literals: &mut Vec<ContentLiteral>) -> usize {
+    let bytes = css.as_bytes();
+    let mut parens = 0usize;
+    let mut i = start;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = skip_comment(css, i);
+                continue;
+            }
+            b'"' | b'\'' => {
+                let range = skip_string(css, i);
+                if parens == 0 {
+                    // 未闭合的字符串没有结尾的引号
+                    let closed = range.len() >= 2 && bytes[range.end - 1] == bytes[i];
+                    let inner_end = if closed { range.end - 1 } else { range.end };
+                    literals.push(ContentLiteral {
+                        text: unescape(&css[range.start + 1..inner_end]),
+                        quote: bytes[i] as char,
+                        range: range.clone(),
+                    });
+                }
+                i = range.end;
+                continue;
+            }
+            b'(' => parens += 1,
+            b')' => parens = parens.saturating_sub(1),
+            b';' | b'}' if parens == 0 => return i,
+            _ => {}
+        }
+        i += 1;
+    }
+
+    i
+}
+
+/// 跳过注释，返回注释之后的位置
+fn skip_comment(css: &str, start: usize) -> usize {
+    match css[start + 2..].find("*/") {
+        Some(end) => start + 2 + end + 2,
+        None => css.len(),
+    }
+}
+
+/// 字符串（包括引号）的范围；未闭合的字符串延伸到行尾或文件末尾
+fn skip_string(css: &str, start: usize) -> Range<usize> {
+    let bytes = css.as_bytes();
+    let quote = bytes[start];
+    let mut i = start + 1;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'\n' => return start..i,
+            byte if byte == quote => return start..i + 1,
+            _ => i += 1,
+        }
+    }
+
+    start..bytes.len()
+}
+
+/// 处理 CSS 转义：`\` 加 1 到 6 位十六进制数（可跟一个空白）、`\` 加换行（续行）以及 `\` 加任意字符
+fn unescape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        let mut hex = String::new();
+        while hex.len() < 6 {
+            match chars.peek() {
+                Some(c) if c.is_ascii_hexdigit() => {
+                    hex.push(*c);
+                    chars.next();
+                }
+                _ => break,
+            }
+        }
+
+        if !hex.is_empty() {
+            let code = u32::from_str_radix(&hex, 16).unwrap_or(0xFFFD);
+            result.push(char::from_u32(code).unwrap_or('\u{FFFD}'));
+            if chars.peek().is_some_and(|c| c.is_ascii_whitespace()) {
+                chars.next();
+            }
+        } else {
+            match chars.next() {
+                Some('\n') | None => {}
+                Some(c) => result.push(c),
+            }
+        }
+    }
+
+    result
+}
+
+/// 把文本写成带引号的 CSS 字符串
+fn quote_string(text: &str, quote: char) -> String {
+    let mut result = String::with_capacity(text.len() + 2);
+    result.push(quote);
+    for c in text.chars() {
+        match c {
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\A "),
+            c if c == quote => {
+                result.push('\\');
+                result.push(c);
+            }
+            c => result.push(c),
+        }
+    }
+    result.push(quote);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(css: &str) -> Vec<String> {
+        content_literals(css)
+            .into_iter()
+            .map(|literal| literal.text)
+            .collect()
+    }
+
+    #[test]
+    fn test_literals_around_counter() {
+        let css =
+            "h2::before { counter-increment: ch; content: \"Chapter \" counter(ch) \": Intro\"; }";
+        assert_eq!(texts(css), vec!["Chapter ", ": Intro"]);
+
+        let literals = content_literals(css);
+        let translated = replace_content_literals(
+            css,
+            &literals,
+            &[Some("第 ".to_string()), Some("：简介".to_string())],
+        );
+        assert_eq!(
+            translated,
+            "h2::before { counter-increment: ch; content: \"第 \" counter(ch) \"：简介\"; }"
+        );
+    }
+
+    #[test]
+    fn test_function_arguments_are_kept() {
+        let css = "li::after { content: counters(item, \".\") ' ' attr(title) url(\"icon.png\") }\
+                   a::before { content: open-quote; }";
+        assert_eq!(texts(css), vec![" "]);
+    }
+
+    #[test]
+    fn test_only_content_declarations() {
+        let css = "/* content: \"comment\" */\
+                   [title=\"x\"] { font-family: \"Open Sans\"; align-content: center; }\
+                   .tip::after { color: red; CONTENT : 'Click here' }";
+        assert_eq!(texts(css), vec!["Click here"]);
+    }
+
+    #[test]
+    fn test_escapes() {
+        let css = ".q::before { content: \"It\\'s \\201C quoted\\201D \\\\ done\" }";
+        assert_eq!(texts(css), vec!["It's \u{201C}quoted\u{201D}\\ done"]);
+
+        let literals = content_literals(css);
+        let translated =
+            replace_content_literals(css, &literals, &[Some("say \"hi\"\nnow".to_string())]);
+        assert_eq!(
+            translated,
+            ".q::before { content: \"say \\\"hi\\\"\\A now\" }"
+        );
+    }
+
+    #[test]
+    fn test_untranslated_literals_are_untouched() {
+        let css = ".a::before { content: 'One' } .b::before { content: 'Two' }";
+        let literals = content_literals(css);
+        let translated = replace_content_literals(css, &literals, &[None, Some("二".to_string())]);
+        assert_eq!(
+            translated,
+            ".a::before { content: 'One' } .b::before { content: '二' }"
+        );
+    }
+}
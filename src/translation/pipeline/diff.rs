@@ -0,0 +1,387 @@
+//! 归档页面差异比较
+//!
+//! 同一 URL 的两份归档之间，[`diff_archives`] 给出结构化的差异列表，而不是逐行的文本 diff：
+//!
+//! - 文本单元是文本节点以及 `title`、`alt` 等可翻译属性中的文本。与翻译不同，
+//!   `<pre>`、`<code>`、`translate="no"` 和 `<ruby>` 注音中的文本同样参与比较，
+//!   只跳过 `<script>`、`<style>` 和 `<noscript>`；
+//! - 资源单元是嵌入资源的元素属性（`src`、`srcset`、`poster`、`<link href>`、`<object data>`），
+//!   `data:` URL 以媒体类型和内容摘要表示，避免在结果中出现大段 base64；
+//! - 每个单元带有一个由祖先元素组成的路径（例如 `html > body > div#main > p`），
+//!   不包含兄弟节点序号，因此兄弟元素调换顺序不会产生差异。
+//!
+//! 对齐分三步：路径和内容都相同的单元视为未改变；剩余单元中路径相同的按文档顺序两两配对，
+//! 记为修改；其余分别记为删除和新增。
+
+use std::collections::{HashMap, VecDeque};
+
+use markup5ever_rcdom::{Handle, NodeData};
+use sha2::{Digest, Sha256};
+
+use crate::html::{ancestors, get_node_attr, get_template_contents, html_to_dom};
+use crate::translation::config::constants;
+
+/// 不参与文本比较的元素
+const SKIP_ELEMENTS: &[&str] = &["script", "style", "noscript"];
+
+/// 引用嵌入资源的属性：(元素名, 属性名)，元素名为 `*` 时匹配任意元素
+const RESOURCE_ATTRS: &[(&str, &str)] = &[
+    ("*", "src"),
+    ("*", "srcset"),
+    ("*", "poster"),
+    ("link", "href"),
+    ("object", "data"),
+];
+
+/// 单个文本或资源单元的差异
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    /// 只出现在新归档中
+    Added { path: String, new: String },
+    /// 只出现在旧归档中
+    Removed { path: String, old: String },
+    /// 同一位置的内容被修改
+    Changed {
+        path: String,
+        old: String,
+        new: String,
+    },
+}
+
+impl Change {
+    /// 差异所在的元素路径
+    pub fn path(&self) -> &str {
+        match self {
+            Change::Added { path, .. } | Change::Removed { path, .. } => path,
+            Change::Changed { path, .. } => path,
+        }
+    }
+}
+
+/// 两份归档之间的差异
+///
+/// 删除和修改按旧文档中的顺序排列，新增按新文档中的顺序排列在其后。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArchiveDiff {
+    /// 文本差异
+    pub text: Vec<Change>,
+    /// 嵌入资源差异
+    pub resources: Vec<Change>,
+}
+
+impl ArchiveDiff {
+    /// 两份归档的文本和资源是否完全一致
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty() && self.resources.is_empty()
+    }
+}
+
+/// 参与对齐的单元：(路径, 内容)
+type Unit = (String, String);
+
+/// 比较同一页面的两份归档
+pub fn diff_archives(old_html: &[u8], new_html: &[u8]) -> ArchiveDiff {
+    let old_dom = html_to_dom(old_html, "".to_string());
+    let new_dom = html_to_dom(new_html, "".to_string());
+
+    ArchiveDiff {
+        text: align(
+            collect_text_units(&old_dom.document),
+            collect_text_units(&new_dom.document),
+        ),
+        resources: align(
+            collect_resource_units(&old_dom.document),
+            collect_resource_units(&new_dom.document),
+        ),
+    }
+}
+
+fn collect_text_units(root: &Handle) -> Vec<Unit> {
+    let mut units = Vec::new();
+    collect_texts_recursive(root, &mut units);
+    units
+}
+
+/// 按文档顺序收集文本节点和可翻译属性，空白折叠后为空的跳过
+///
+/// 不使用翻译收集器：其中 `translate="no"`、`<ruby>` 注音、目标语言内容和过短文本等
+/// 跳过规则是为翻译设计的，这些文本的改动同样需要出现在差异中。
+fn collect_texts_recursive(node: &Handle, units: &mut Vec<Unit>) {
+    match node.data {
+        NodeData::Text { ref contents } => {
+            let text = collapse_whitespace(&contents.borrow());
+            if !text.is_empty() {
+                units.push((element_path(node), text));
+            }
+        }
+        NodeData::Element { ref name, .. } => {
+            if SKIP_ELEMENTS.contains(&name.local.as_ref()) {
+                return;
+            }
+            for attr_name in constants::TRANSLATABLE_ATTRS {
+                if let Some(value) = get_node_attr(node, attr_name) {
+                    let text = collapse_whitespace(&value);
+                    if !text.is_empty() {
+                        units.push((format!("{}[{}]", element_path(node), attr_name), text));
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+
+    for child in node.children.borrow().iter() {
+        collect_texts_recursive(child, units);
+    }
+    if let Some(fragment) = get_template_contents(node) {
+        collect_texts_recursive(&fragment, units);
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn collect_resource_units(root: &Handle) -> Vec<Unit> {
+    let mut units = Vec::new();
+    collect_resources_recursive(root, &mut units);
+    units
+}
+
+fn collect_resources_recursive(node: &Handle, units: &mut Vec<Unit>) {
+    if let NodeData::Element { ref name, .. } = node.data {
+        let tag = name.local.as_ref();
+        for (element, attr_name) in RESOURCE_ATTRS {
+            if *element != "*" && *element != tag {
+                continue;
+            }
+            if let Some(value) = get_node_attr(node, attr_name).filter(|value| !value.is_empty()) {
+                units.push((
+                    format!("{}[{}]", element_path(node), attr_name),
+                    describe_resource(&value),
+                ));
+            }
+        }
+    }
+
+    for child in node.children.borrow().iter() {
+        collect_resources_recursive(child, units);
+    }
+    if let Some(fragment) = get_template_contents(node) {
+        collect_resources_recursive(&fragment, units);
+    }
+}
+
+/// `data:` URL 改写为 `data:<媒体类型>;sha256=<摘要前 16 位>`，其他 URL 原样返回
+fn describe_resource(value: &str) -> String {
+    if !value.trim_start().to_lowercase().starts_with("data:") {
+        return value.to_string();
+    }
+
+    let header = value.trim_start()[5..]
+        .split([',', ';'])
+        .next()
+        .unwrap_or("");
+    let digest = format!("{:x}", Sha256::digest(value.as_bytes()));
+    format!("data:{};sha256={}", header.trim(), &digest[..16])
+}
+
+/// 由祖先元素组成的路径，带 `id` 的元素写作 `tag#id`
+pub(crate) fn element_path(node: &Handle) -> String {
+    let mut labels: Vec<String> = ancestors(node)
+        .filter_map(|node| match node.data {
+            NodeData::Element { ref name, .. } => {
+                let mut label = name.local.to_string();
+                if let Some(id) = get_node_attr(&node, "id").filter(|id| !id.is_empty()) {
+                    label.push('#');
+                    label.push_str(&id);
+                }
+                Some(label)
+            }
+            _ => None,
+        })
+        .collect();
+
+    labels.reverse();
+    labels.join(" > ")
+}
+
+fn align(old: Vec<Unit>, new: Vec<Unit>) -> Vec<Change> {
+    let mut new_matched = vec![false; new.len()];
+    let mut old_matched = vec![false; old.len()];
+
+    // 1. 路径和内容都相同：未改变（与顺序无关）
+    let mut by_unit: HashMap<&Unit, VecDeque<usize>> = HashMap::new();
+    for (i, unit) in new.iter().enumerate() {
+        by_unit.entry(unit).or_default().push_back(i);
+    }
+    for (i, unit) in old.iter().enumerate() {
+        if let Some(j) = by_unit
+            .get_mut(unit)
+            .and_then(|indexes| indexes.pop_front())
+        {
+            old_matched[i] = true;
+            new_matched[j] = true;
+        }
+    }
+
+    // 2. 剩余单元中路径相同的按顺序配对：修改
+    let mut by_path: HashMap<&str, VecDeque<usize>> = HashMap::new();
+    for (j, (path, _)) in new.iter().enumerate() {
+        if !new_matched[j] {
+            by_path.entry(path).or_default().push_back(j);
+        }
+    }
+
+    let mut changes = Vec::new();
+    for (i, (path, old_value)) in old.iter().enumerate() {
+        if old_matched[i] {
+            continue;
+        }
+        match by_path
+            .get_mut(path.as_str())
+            .and_then(|indexes| indexes.pop_front())
+        {
+            Some(j) => {
+                new_matched[j] = true;
+                changes.push(Change::Changed {
+                    path: path.clone(),
+                    old: old_value.clone(),
+                    new: new[j].1.clone(),
+                });
+            }
+            None => changes.push(Change::Removed {
+                path: path.clone(),
+                old: old_value.clone(),
+            }),
+        }
+    }
+
+    // 3. 新文档中没有配对的单元：新增
+    for (j, (path, new_value)) in new.iter().enumerate() {
+        if !new_matched[j] {
+            changes.push(Change::Added {
+                path: path.clone(),
+                new: new_value.clone(),
+            });
+        }
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identical_archives() {
+        let html = b"<html><head><title>Page</title></head><body><h1>Title</h1><p>Text</p><img src=\"data:image/png;base64,AAAA\"></body></html>";
+        assert!(diff_archives(html, html).is_empty());
+    }
+
+    #[test]
+    fn test_changed_paragraph() {
+        let diff = diff_archives(
+            b"<body><div id=\"main\"><p>Intro</p><p>Price is 10 EUR</p></div></body>",
+            b"<body><div id=\"main\"><p>Intro</p><p>Price is 12 EUR</p></div></body>",
+        );
+
+        assert_eq!(
+            diff.text,
+            vec![Change::Changed {
+                path: "html > body > div#main > p".to_string(),
+                old: "Price is 10 EUR".to_string(),
+                new: "Price is 12 EUR".to_string(),
+            }]
+        );
+        assert!(diff.resources.is_empty());
+    }
+
+    #[test]
+    fn test_reordered_siblings() {
+        let diff = diff_archives(
+            b"<ul><li>First</li><li>Second</li><li>Third</li></ul>",
+            b"<ul><li>Third</li><li>First</li><li>Second</li></ul>",
+        );
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn test_added_removed_and_attributes() {
+        let diff = diff_archives(
+            b"<body><p>Kept</p><aside>Old note</aside><img alt=\"Logo\"></body>",
+            b"<body><p>Kept</p><img alt=\"New logo\"><footer>Contact</footer></body>",
+        );
+
+        assert_eq!(
+            diff.text,
+            vec![
+                Change::Removed {
+                    path: "html > body > aside".to_string(),
+                    old: "Old note".to_string(),
+                },
+                Change::Changed {
+                    path: "html > body > img[alt]".to_string(),
+                    old: "Logo".to_string(),
+                    new: "New logo".to_string(),
+                },
+                Change::Added {
+                    path: "html > body > footer".to_string(),
+                    new: "Contact".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_untranslatable_texts_compared() {
+        let diff = diff_archives(
+            b"<body><p translate=\"no\">ACME 1.0</p><ruby>\xe6\xbc\xa2<rt>kan</rt></ruby></body>",
+            b"<body><p translate=\"no\">ACME 2.0</p><ruby>\xe6\xbc\xa2<rt>han</rt></ruby></body>",
+        );
+
+        assert_eq!(
+            diff.text,
+            vec![
+                Change::Changed {
+                    path: "html > body > p".to_string(),
+                    old: "ACME 1.0".to_string(),
+                    new: "ACME 2.0".to_string(),
+                },
+                Change::Changed {
+                    path: "html > body > ruby > rt".to_string(),
+                    old: "kan".to_string(),
+                    new: "han".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resources() {
+        let diff = diff_archives(
+            b"<head><script src=\"data:text/javascript;base64,YQ==\"></script></head>\
+              <body><img src=\"data:image/png;base64,AAAA\"><img src=\"https://example.com/a.png\"></body>",
+            b"<head></head>\
+              <body><img src=\"data:image/png;base64,BBBB\"><img src=\"https://example.com/a.png\"></body>",
+        );
+
+        assert!(diff.text.is_empty());
+        assert_eq!(diff.resources.len(), 2);
+        assert!(matches!(
+            &diff.resources[0],
+            Change::Removed { path, old }
+                if path == "html > head > script[src]" && old.starts_with("data:text/javascript;sha256=")
+        ));
+        match &diff.resources[1] {
+            Change::Changed { path, old, new } => {
+                assert_eq!(path, "html > body > img[src]");
+                assert!(old.starts_with("data:image/png;sha256="));
+                assert!(new.starts_with("data:image/png;sha256="));
+                assert_ne!(old, new);
+            }
+            change => panic!("unexpected change: {change:?}"),
+        }
+    }
+}
@@ -205,6 +205,37 @@ impl TextFilter {
             return false;
         }
 
+        if !self.is_translatable_content(trimmed) {
+            return false;
+        }
+
+        // 对于短文本，检查是否为功能性词汇（如 "OK", "Yes" 等）
+        if trimmed.len() < constants::MIN_TRANSLATION_LENGTH && self.is_functional_text(trimmed) {
+            return false;
+        }
+
+        true
+    }
+
+    /// 使用指定的最短长度（字节数）判断文本是否需要翻译
+    ///
+    /// 与 [`Self::should_translate`] 的区别在于长度下限由调用方决定，并且 "Go"、"OK"
+    /// 这类短单词不再被当作功能性词汇或类名排除：放宽下限正是为了翻译按钮、链接中的这类文本。
+    /// URL、代码、纯符号和已是中文的文本仍然不翻译。
+    pub fn should_translate_with_min_length(&self, text: &str, min_length: usize) -> bool {
+        let trimmed = text.trim();
+        if trimmed.len() < min_length.max(1) {
+            return false;
+        }
+
+        if trimmed.chars().all(|c| c.is_alphabetic()) {
+            return !self.is_already_chinese(trimmed);
+        }
+        self.is_translatable_content(trimmed)
+    }
+
+    /// 与长度无关的检查：排除技术性内容、不含字母的文本和中文文本
+    fn is_translatable_content(&self, trimmed: &str) -> bool {
         // 检查是否为非可翻译内容（URL、邮箱、代码等）
         if self.is_non_translatable_content(trimmed) {
             return false;
@@ -220,11 +251,6 @@ impl TextFilter {
             return false;
         }
 
-        // 对于短文本，检查是否为功能性词汇（如 "OK", "Yes" 等）
-        if trimmed.len() < constants::MIN_TRANSLATION_LENGTH && self.is_functional_text(trimmed) {
-            return false;
-        }
-
         true
     }
 
@@ -370,7 +396,7 @@ impl TextFilter {
     /// 
     /// * `true` - 类似代码片段
     /// * `false` - 不像代码片段
-    fn is_code_like(&self, text: &str) -> bool {
+    pub fn is_code_like(&self, text: &str) -> bool {
         let special_chars = text
             .chars()
             .filter(|&c| {
@@ -406,6 +432,13 @@ impl TextFilter {
             return true;
         }
 
+        // 只由字母组成的单词是普通文本（例如下拉选项 "Germany"），不是类名
+        if text.chars().count() >= constants::MIN_TRANSLATION_LENGTH
+            && text.chars().all(|c| c.is_alphabetic())
+        {
+            return false;
+        }
+
         #[cfg(feature = "translation")]
         {
             let css_regex = self.regex_cache.css_selector_regex.get_or_init(|| {
@@ -690,7 +723,7 @@ impl TextFilter {
     /// # 返回值
     /// 
     /// 返回 `LanguageHint` 枚举值，表示检测到的语言类型
-    fn detect_language_hint(&self, text: &str) -> LanguageHint {
+    pub fn detect_language_hint(&self, text: &str) -> LanguageHint {
         if text.is_empty() {
             return LanguageHint::Unknown;
         }
@@ -730,6 +763,56 @@ impl TextFilter {
     }
 }
 
+impl TextFilter {
+    /// 按长度加权汇总多段文本的语言提示，判断页面的主要语言
+    ///
+    /// 只取最长的 `sample_size` 段文本：长文本的判断更可靠，也不必逐一检查大型页面上的
+    /// 每个文本节点。每段文本按字符数计入其语言提示的权重，权重最高的语言即为结果，
+    /// 其权重占比作为置信度。没有文本（或 `sample_size` 为 0）时返回置信度为 0 的
+    /// [`LanguageHint::Unknown`]。
+    pub fn detect_dominant_language<'a>(
+        &self,
+        texts: impl IntoIterator<Item = &'a str>,
+        sample_size: usize,
+    ) -> LanguageDetection {
+        let mut texts: Vec<(&str, usize)> = texts
+            .into_iter()
+            .map(|text| (text, text.trim().chars().count()))
+            .filter(|(_, length)| *length > 0)
+            .collect();
+        texts.sort_by_key(|(_, length)| std::cmp::Reverse(*length));
+        texts.truncate(sample_size);
+
+        let mut weights: Vec<(LanguageHint, usize)> = Vec::new();
+        for (text, length) in &texts {
+            let hint = self.detect_language_hint(text.trim());
+            match weights.iter_mut().find(|(known, _)| *known == hint) {
+                Some((_, weight)) => *weight += length,
+                None => weights.push((hint, *length)),
+            }
+        }
+
+        let total: usize = weights.iter().map(|(_, weight)| weight).sum();
+        // 权重相同时取先出现（即所在文本更长）的语言
+        let dominant = weights
+            .into_iter()
+            .reduce(|best, next| if next.1 > best.1 { next } else { best });
+
+        match dominant {
+            Some((language, weight)) => LanguageDetection {
+                language,
+                confidence: weight as f32 / total as f32,
+                sampled: texts.len(),
+            },
+            None => LanguageDetection {
+                language: LanguageHint::Unknown,
+                confidence: 0.0,
+                sampled: 0,
+            },
+        }
+    }
+}
+
 impl Default for TextFilter {
     fn default() -> Self {
         Self::new()
@@ -789,6 +872,43 @@ pub enum LanguageHint {
     Unknown,
 }
 
+impl LanguageHint {
+    /// 判断语言提示是否与指定的语言代码一致
+    /// 
+    /// 只对能够可靠识别的语言（中文、日语、韩语）返回 `true`，
+    /// 拉丁文涵盖了太多语言，无法据此推断具体的语言代码。
+    /// 
+    /// ```rust
+    /// use monolith::translation::pipeline::filters::LanguageHint;
+    /// 
+    /// assert!(LanguageHint::Chinese.matches_lang("zh-CN"));
+    /// assert!(!LanguageHint::Latin.matches_lang("en"));
+    /// ```
+    pub fn matches_lang(&self, lang: &str) -> bool {
+        let primary = lang
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+
+        matches!(
+            (self, primary.as_str()),
+            (LanguageHint::Chinese, "zh") | (LanguageHint::Japanese, "ja") | (LanguageHint::Korean, "ko")
+        )
+    }
+}
+
+/// 页面主要语言的判断结果，见 [`TextFilter::detect_dominant_language`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanguageDetection {
+    /// 权重最高的语言
+    pub language: LanguageHint,
+    /// 该语言的文本长度占抽样文本总长度的比例（0.0-1.0）
+    pub confidence: f32,
+    /// 参与判断的文本段数
+    pub sampled: usize,
+}
+
 /// 文本过滤统计信息
 /// 
 /// 用于追踪和统计文本过滤过程中的各种数据。
@@ -1182,6 +1302,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_should_translate_with_min_length() {
+        let filter = create_test_filter();
+
+        assert!(!filter.should_translate("Go"));
+        assert!(filter.should_translate_with_min_length("Go", 1));
+        assert!(filter.should_translate_with_min_length("X", 1));
+        assert!(!filter.should_translate_with_min_length("Go", 3));
+        assert!(!filter.should_translate_with_min_length("新", 1));
+        assert!(!filter.should_translate_with_min_length("42", 1));
+        assert!(!filter.should_translate_with_min_length("https://example.com", 1));
+    }
+
     #[test]
     fn test_should_translate_urls() {
         let filter = create_test_filter();
@@ -1327,6 +1460,14 @@ mod tests {
             !filter.should_translate("div::before"),
             "CSS pseudo-elements should not be translated"
         );
+        assert!(
+            !filter.should_translate("main-content"),
+            "Bare class names should not be translated"
+        );
+        assert!(
+            filter.should_translate("Germany"),
+            "Single words should be translated"
+        );
     }
 
     #[test]
@@ -1537,6 +1678,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_language_hint_matches_lang() {
+        assert!(LanguageHint::Chinese.matches_lang("zh"));
+        assert!(LanguageHint::Chinese.matches_lang("zh-CN"));
+        assert!(LanguageHint::Japanese.matches_lang("ja_JP"));
+        assert!(LanguageHint::Korean.matches_lang("KO"));
+
+        // 拉丁文无法对应到具体语言，不应匹配
+        assert!(!LanguageHint::Latin.matches_lang("en"));
+        assert!(!LanguageHint::Chinese.matches_lang("ja"));
+        assert!(!LanguageHint::Mixed.matches_lang("zh"));
+    }
+
+    #[test]
+    fn test_detect_dominant_language() {
+        let filter = create_test_filter();
+
+        // 大量短小的英文界面文字不应盖过正文
+        let mut texts = vec!["Home", "About", "Contact", "Login", "Search"];
+        texts.push("这是一段很长的中文正文，介绍了这个项目的背景、目标以及主要的设计思路。");
+        texts.push("第二段中文正文同样比导航链接长得多，因此在加权时占据主导地位。");
+        let detection = filter.detect_dominant_language(texts.iter().copied(), 50);
+        assert_eq!(detection.language, LanguageHint::Chinese);
+        assert!(detection.confidence > 0.5 && detection.confidence < 1.0);
+        assert_eq!(detection.sampled, 7);
+
+        // 只取最长的两段
+        let detection = filter.detect_dominant_language(texts.iter().copied(), 2);
+        assert_eq!(detection.language, LanguageHint::Chinese);
+        assert_eq!(detection.confidence, 1.0);
+        assert_eq!(detection.sampled, 2);
+
+        let detection = filter.detect_dominant_language(["   ", ""], 50);
+        assert_eq!(detection.language, LanguageHint::Unknown);
+        assert_eq!(detection.confidence, 0.0);
+    }
+
     #[test]
     fn test_filter_texts_batch() {
         let filter = create_test_filter();
@@ -0,0 +1,271 @@
+//! JSON 数据块中的文本
+//!
+//! Next.js、Nuxt 等框架把页面数据放在 `<script type="application/json">` 中，页面显示的文字在
+//! 客户端由这些数据渲染出来，DOM 中的文本会被覆盖。这里按配置的路径找出数据中的字符串并写回译文。
+//!
+//! 路径由 `.` 分隔的键组成，例如 `props.pageProps.title`；数组元素用下标（`items.0.name`）表示，
+//! `*` 匹配任意一个键或下标（`items.*.name`）。键中含有 `.` 的值无法指定。只有路径指向的
+//! 字符串会被翻译，对象、数组和其他类型的值保持原样。
+//!
+//! 写回时只替换字符串本身，其余字符（缩进、键的顺序、数字的写法）不做任何改动。
+//! 无法完整解析的 JSON 不做处理。
+
+use std::ops::Range;
+
+/// 可能包含页面数据的 `<script>` 类型
+pub const JSON_SCRIPT_TYPES: &[&str] = &["application/json"];
+
+/// JSON 中的一个字符串值
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonString {
+    /// 字符串在 JSON 中的字节范围，包括两端的引号
+    pub range: Range<usize>,
+    /// 值的完整路径，不含通配符
+    pub path: String,
+    /// 反转义之后的文本
+    pub text: String,
+}
+
+/// 找出路径匹配 `patterns` 中任一项的字符串值，按出现顺序返回
+pub fn json_string_values(json: &str, patterns: &[String]) -> Vec<JsonString> {
+    let patterns: Vec<Vec<&str>> = patterns
+        .iter()
+        .map(|pattern| pattern.split('.').collect())
+        .collect();
+
+    let mut values = Vec::new();
+    let mut visit = |path: &[String], range: Range<usize>| {
+        if patterns.iter().any(|pattern| matches_path(pattern, path)) {
+            if let Ok(text) = serde_json::from_str::<String>(&json[range.clone()]) {
+                values.push(JsonString {
+                    range,
+                    path: path.join("."),
+                    text,
+                });
+            }
+        }
+    };
+    let parsed = Scanner {
+        json,
+        bytes: json.as_bytes(),
+        path: Vec::new(),
+        visit: &mut visit,
+    }
+    .parse_document();
+
+    if parsed {
+        values
+    } else {
+        Vec::new()
+    }
+}
+
+/// 把 `path` 处的字符串替换为 `text`，路径不存在或指向的不是字符串时返回 `None`
+pub fn replace_json_string(json: &str, path: &str, text: &str) -> Option<String> {
+    let value = json_string_values(json, &[path.to_string()])
+        .into_iter()
+        .find(|value| value.path == path)?;
+
+    let mut result = String::with_capacity(json.len() + text.len());
+    result.push_str(&json[..value.range.start]);
+    result.push_str(&quote_string(text));
+    result.push_str(&json[value.range.end..]);
+    Some(result)
+}
+
+/// `<script>` 的 `type` 是否表示 JSON 数据
+pub fn is_json_script_type(script_type: &str) -> bool {
+    let essence = script_type.split(';').next().unwrap_or("").trim();
+    JSON_SCRIPT_TYPES
+        .iter()
+        .any(|json_type| essence.eq_ignore_ascii_case(json_type))
+}
+
+fn matches_path(pattern: &[&str], path: &[String]) -> bool {
+    pattern.len() == path.len()
+        && pattern
+            .iter()
+            .zip(path)
+            .all(|(expected, actual)| *expected == "*" || *expected == actual)
+}
+
+/// 把文本写成 JSON 字符串；`<` 写成转义形式，译文中的 `</script>` 不会提前结束脚本元素
+fn quote_string(text: &str) -> String {
+    serde_json::to_string(text)
+        .unwrap_or_default()
+        .replace('<', "\\u003c")
+}
+
+struct Scanner<'a, F: FnMut(&[String], Range<usize>)> {
+    json: &'a str,
+    bytes: &'a [u8],
+    path: Vec<String>,
+    visit: &'a mut F,
+}
+
+impl<F: FnMut(&[String], Range<usize>)> Scanner<'_, F> {
+    /// 解析整个文档，值之后只允许有空白
+    fn parse_document(&mut self) -> bool {
+        match self.parse_value(0) {
+            Some(end) => self.skip_whitespace(end) == self.bytes.len(),
+            None => false,
+        }
+    }
+
+    /// 解析从 `start` 开始的值，返回值结束的位置
+    fn parse_value(&mut self, start: usize) -> Option<usize> {
+        let start = self.skip_whitespace(start);
+        match *self.bytes.get(start)? {
+            b'{' => self.parse_object(start),
+            b'[' => self.parse_array(start),
+            b'"' => {
+                let end = self.string_end(start)?;
+                (self.visit)(&self.path, start..end);
+                Some(end)
+            }
+            _ => {
+                let end = self.bytes[start..]
+                    .iter()
+                    .position(|byte| {
+                        matches!(byte, b',' | b'}' | b']') || byte.is_ascii_whitespace()
+                    })
+                    .map_or(self.bytes.len(), |offset| start + offset);
+                (end > start).then_some(end)
+            }
+        }
+    }
+
+    fn parse_object(&mut self, start: usize) -> Option<usize> {
+        let mut i = self.skip_whitespace(start + 1);
+        if self.bytes.get(i) == Some(&b'}') {
+            return Some(i + 1);
+        }
+
+        loop {
+            if self.bytes.get(i) != Some(&b'"') {
+                return None;
+            }
+            let key_end = self.string_end(i)?;
+            let key = serde_json::from_str::<String>(&self.json[i..key_end]).ok()?;
+
+            i = self.skip_whitespace(key_end);
+            if self.bytes.get(i) != Some(&b':') {
+                return None;
+            }
+            self.path.push(key);
+            let value_end = self.parse_value(i + 1);
+            self.path.pop();
+
+            i = self.skip_whitespace(value_end?);
+            match self.bytes.get(i)? {
+                b',' => i = self.skip_whitespace(i + 1),
+                b'}' => return Some(i + 1),
+                _ => return None,
+            }
+        }
+    }
+
+    fn parse_array(&mut self, start: usize) -> Option<usize> {
+        let mut i = self.skip_whitespace(start + 1);
+        if self.bytes.get(i) == Some(&b']') {
+            return Some(i + 1);
+        }
+
+        let mut index = 0usize;
+        loop {
+            self.path.push(index.to_string());
+            let value_end = self.parse_value(i);
+            self.path.pop();
+
+            i = self.skip_whitespace(value_end?);
+            match self.bytes.get(i)? {
+                b',' => i += 1,
+                b']' => return Some(i + 1),
+                _ => return None,
+            }
+            index += 1;
+        }
+    }
+
+    /// 字符串结尾引号之后的位置，未闭合时返回 `None`
+    fn string_end(&self, start: usize) -> Option<usize> {
+        let mut i = start + 1;
+        while i < self.bytes.len() {
+            match self.bytes[i] {
+                b'\\' => i += 2,
+                b'"' => return Some(i + 1),
+                _ => i += 1,
+            }
+        }
+        None
+    }
+
+    fn skip_whitespace(&self, start: usize) -> usize {
+        self.bytes[start.min(self.bytes.len())..]
+            .iter()
+            .position(|byte| !byte.is_ascii_whitespace())
+            .map_or(self.bytes.len(), |offset| start + offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NEXT_DATA: &str = r#"{
+  "props": {"pageProps": {"title": "Hello world", "count": 3,
+    "items": [{"name": "First"}, {"name": "Second", "id": "a1"}]}},
+  "page": "/"
+}"#;
+
+    fn paths(json: &str, patterns: &[&str]) -> Vec<(String, String)> {
+        let patterns: Vec<String> = patterns.iter().map(|p| p.to_string()).collect();
+        json_string_values(json, &patterns)
+            .into_iter()
+            .map(|value| (value.path, value.text))
+            .collect()
+    }
+
+    #[test]
+    fn test_paths_and_wildcards() {
+        assert_eq!(
+            paths(
+                NEXT_DATA,
+                &["props.pageProps.title", "props.pageProps.count"]
+            ),
+            vec![(
+                "props.pageProps.title".to_string(),
+                "Hello world".to_string()
+            )]
+        );
+        assert_eq!(
+            paths(NEXT_DATA, &["props.pageProps.items.*.name"]),
+            vec![
+                (
+                    "props.pageProps.items.0.name".to_string(),
+                    "First".to_string()
+                ),
+                (
+                    "props.pageProps.items.1.name".to_string(),
+                    "Second".to_string()
+                ),
+            ]
+        );
+        assert!(paths("{\"a\": \"b\"", &["a"]).is_empty());
+    }
+
+    #[test]
+    fn test_replace_keeps_layout() {
+        let translated =
+            replace_json_string(NEXT_DATA, "props.pageProps.title", "你好 </script>").unwrap();
+        assert_eq!(
+            translated,
+            NEXT_DATA.replace("\"Hello world\"", "\"你好 \\u003c/script>\"")
+        );
+        assert_eq!(
+            paths(&translated, &["props.pageProps.title"])[0].1,
+            "你好 </script>"
+        );
+        assert!(replace_json_string(NEXT_DATA, "props.pageProps.count", "三").is_none());
+    }
+}
@@ -0,0 +1,100 @@
+//! 已翻译标记
+//!
+//! 翻译结束后在 `<head>` 中写入 `<meta name="monolith-translated" content="zh">`。
+//! 之后再翻译同一份输出时，标记语言与目标语言相同（只比较主语言子标签）的页面直接跳过，
+//! 重复执行翻译不会把译文再翻译一遍。需要强制重新翻译时使用配置项 `retranslate`。
+//!
+//! 同时把根元素的 `<html lang>` 改为译文的语言，浏览器和读屏软件按这个属性选择字体和发音。
+
+use html5ever::interface::{Attribute, QualName};
+use html5ever::tree_builder::{create_element, NodeOrText, TreeSink};
+use html5ever::{namespace_url, ns, LocalName};
+use markup5ever_rcdom::{Handle, RcDom};
+
+use crate::html::{find_nodes, get_node_attr, set_node_attr};
+use crate::translation::pipeline::collector::primary_subtag;
+
+/// 标记所用 `<meta>` 的名称
+pub const TRANSLATED_META_NAME: &str = "monolith-translated";
+
+/// 读取文档的已翻译标记，返回翻译时的目标语言
+pub fn translated_lang(dom: &RcDom) -> Option<String> {
+    find_marker(dom)
+        .and_then(|meta| get_node_attr(&meta, "content"))
+        .map(|lang| lang.trim().to_string())
+        .filter(|lang| !lang.is_empty())
+}
+
+/// 文档是否已经翻译为目标语言
+pub fn is_translated_to(dom: &RcDom, target_lang: &str) -> bool {
+    translated_lang(dom).is_some_and(|lang| primary_subtag(&lang) == primary_subtag(target_lang))
+}
+
+/// 写入已翻译标记，已有标记时更新其语言
+pub fn mark_translated(dom: &RcDom, lang: &str) {
+    if let Some(meta) = find_marker(dom) {
+        set_node_attr(&meta, "content", Some(lang.to_string()));
+        return;
+    }
+
+    if let Some(head) = find_nodes(&dom.document, vec!["html", "head"]).first() {
+        let attribute = |name: &str, value: &str| Attribute {
+            name: QualName::new(None, ns!(), LocalName::from(name)),
+            value: value.into(),
+        };
+        let meta = create_element(
+            dom,
+            QualName::new(None, ns!(), LocalName::from("meta")),
+            vec![
+                attribute("name", TRANSLATED_META_NAME),
+                attribute("content", lang),
+            ],
+        );
+        dom.append(head, NodeOrText::AppendNode(meta));
+    }
+}
+
+/// 把根元素的 `lang` 设为 `lang`，没有该属性时添加
+pub fn set_document_lang(dom: &RcDom, lang: &str) {
+    if let Some(html) = find_nodes(&dom.document, vec!["html"]).first() {
+        set_node_attr(html, "lang", Some(lang.to_string()));
+    }
+}
+
+fn find_marker(dom: &RcDom) -> Option<Handle> {
+    find_nodes(&dom.document, vec!["html", "head", "meta"])
+        .into_iter()
+        .find(|meta| {
+            get_node_attr(meta, "name")
+                .is_some_and(|name| name.eq_ignore_ascii_case(TRANSLATED_META_NAME))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::html::html_to_dom;
+
+    #[test]
+    fn test_mark_and_detect() {
+        let dom = html_to_dom(
+            &b"<html><head><title>Page</title></head><body></body></html>".to_vec(),
+            "".to_string(),
+        );
+        assert_eq!(translated_lang(&dom), None);
+        assert!(!is_translated_to(&dom, "zh"));
+
+        mark_translated(&dom, "zh-CN");
+        assert_eq!(translated_lang(&dom), Some("zh-CN".to_string()));
+        assert!(is_translated_to(&dom, "zh"));
+        assert!(!is_translated_to(&dom, "en"));
+
+        // 已有标记时只更新语言，不重复插入
+        mark_translated(&dom, "en");
+        assert_eq!(
+            find_nodes(&dom.document, vec!["html", "head", "meta"]).len(),
+            1
+        );
+        assert!(is_translated_to(&dom, "EN"));
+    }
+}
@@ -101,8 +101,17 @@
 //! - **调试支持**: 完整的日志记录和错误追踪
 
 pub mod batch;
+pub mod bilingual;
 pub mod collector;
+pub mod css;
+pub mod diff;
 pub mod filters;
+pub mod json;
+pub mod marker;
+pub mod provenance;
+pub mod sidecar;
+pub mod verify;
+pub mod xliff;
 
 // 重新导出主要类型，提供统一的API接口
 
@@ -110,7 +119,7 @@ pub mod filters;
 /// 
 /// 这些类型用于将文本项组织成优化的批次，提高翻译API的调用效率。
 /// 批次管理器会根据文本的优先级、大小和复杂度智能地创建批次。
-pub use batch::{Batch, BatchManager, BatchPriority, BatchType};
+pub use batch::{Batch, BatchManager, BatchPriority, BatchType, LanguageConcurrency};
 
 /// 文本收集相关类型
 /// 
@@ -118,8 +127,33 @@ pub use batch::{Batch, BatchManager, BatchPriority, BatchType};
 /// 文本收集器提供了智能的文本识别和优先级分配功能。
 pub use collector::{TextCollector, TextItem, TextPriority, TextType};
 
+/// 归档差异相关类型
+///
+/// 基于文本收集器比较同一页面的两份归档，给出新增、删除和修改的文本与资源。
+pub use diff::{diff_archives, ArchiveDiff, Change};
+
 /// 文本过滤相关类型
 /// 
 /// 这些类型用于智能判断文本是否需要翻译，过滤掉不适合翻译的内容。
 /// 过滤器支持多种过滤策略和自定义规则。
-pub use filters::{LanguageHint, TextAnalysis, TextFilter};
+pub use filters::{LanguageDetection, LanguageHint, TextAnalysis, TextFilter};
+
+/// 译文校验
+///
+/// 写回前拒绝长度异常或文字与目标语言不符的译文。
+pub use verify::{Rejection, TranslationVerifier};
+
+/// 翻译来源信息
+///
+/// 在页面中以 JSON-LD 记录语言、翻译后端和翻译时间。
+pub use provenance::{embed_provenance, TranslationProvenance};
+
+/// 译文映射表
+///
+/// 以节点路径记录译文，与未翻译的归档分开保存，之后再写回归档。
+pub use sidecar::{apply_translation_map, ApplyStats, TranslationMap, TranslationUnit};
+
+/// XLIFF 导出与导入
+///
+/// 以稳定的单元 id 导出文本项，并把译者填写的译文写回 DOM，用于人工翻译流程。
+pub use xliff::{export_xliff, import_xliff, unit_ids};
@@ -0,0 +1,169 @@
+//! 翻译来源信息
+//!
+//! 开启 `embed_provenance` 后，翻译完成的页面在 `<head>` 中带有一段 JSON-LD
+//! (`<script type="application/ld+json">`)，按 schema.org 的词汇记录原文和译文的语言、
+//! 使用的翻译后端、翻译时间和发送给翻译服务的字符数，供搜索引擎和其他读取结构化数据的工具使用。
+//!
+//! 脚本带有 `translate="no"`，再次翻译时不会被收集；重新翻译的页面更新已有的这段脚本，
+//! 不会重复插入。
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use html5ever::interface::{Attribute, QualName};
+use html5ever::tendril::StrTendril;
+use html5ever::tree_builder::{create_element, NodeOrText, TreeSink};
+use html5ever::{namespace_url, ns, LocalName};
+use markup5ever_rcdom::{Handle, NodeData, RcDom};
+use serde_json::{json, Value};
+
+use crate::html::{find_nodes, get_node_attr};
+
+/// 标记来源信息脚本的属性名
+pub const PROVENANCE_ATTR: &str = "data-monolith-provenance";
+
+/// 一次翻译的来源信息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranslationProvenance {
+    /// 原文语言，未知时为 `und`
+    pub source_lang: String,
+    /// 译文语言
+    pub target_lang: String,
+    /// 发出了请求的翻译后端，全部命中缓存时为空
+    pub backends: Vec<String>,
+    /// 翻译完成的时间
+    pub translated_at: DateTime<Utc>,
+    /// 发送给翻译服务的字符数，见 [`UsageReport`](crate::translation::UsageReport)
+    pub char_count: usize,
+}
+
+impl TranslationProvenance {
+    /// 生成 JSON-LD 对象
+    pub fn to_json_ld(&self) -> Value {
+        let translators: Vec<Value> = self
+            .backends
+            .iter()
+            .map(|backend| json!({"@type": "SoftwareApplication", "name": backend}))
+            .collect();
+        json!({
+            "@context": "https://schema.org",
+            "@type": "WebPage",
+            "inLanguage": self.target_lang,
+            "translationOfWork": {
+                "@type": "WebPage",
+                "inLanguage": self.source_lang,
+            },
+            "translator": translators,
+            "dateCreated": self.translated_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            "additionalProperty": {
+                "@type": "PropertyValue",
+                "name": "characterCount",
+                "value": self.char_count,
+            },
+        })
+    }
+}
+
+/// 在 `<head>` 末尾写入来源信息，已有来源信息时替换其内容
+pub fn embed_provenance(dom: &RcDom, provenance: &TranslationProvenance) {
+    // `<` 写成转义形式，JSON 中的 `</script>` 不会提前结束脚本元素
+    let json_ld = serde_json::to_string_pretty(&provenance.to_json_ld())
+        .unwrap_or_default()
+        .replace('<', "\\u003c");
+
+    let script = match find_provenance_script(dom) {
+        Some(script) => {
+            script.children.borrow_mut().clear();
+            script
+        }
+        None => {
+            let Some(head) = find_nodes(&dom.document, vec!["html", "head"])
+                .into_iter()
+                .next()
+            else {
+                return;
+            };
+            let attribute = |name: &str, value: &str| Attribute {
+                name: QualName::new(None, ns!(), LocalName::from(name)),
+                value: value.into(),
+            };
+            let script = create_element(
+                dom,
+                QualName::new(None, ns!(), LocalName::from("script")),
+                vec![
+                    attribute("type", "application/ld+json"),
+                    attribute("translate", "no"),
+                    attribute(PROVENANCE_ATTR, ""),
+                ],
+            );
+            dom.append(&head, NodeOrText::AppendNode(script.clone()));
+            script
+        }
+    };
+    dom.append(&script, NodeOrText::AppendText(StrTendril::from(json_ld)));
+}
+
+/// 读取文档中的来源信息，没有或无法解析时返回 `None`
+pub fn read_provenance(dom: &RcDom) -> Option<Value> {
+    let script = find_provenance_script(dom)?;
+    let json_ld: String = script
+        .children
+        .borrow()
+        .iter()
+        .filter_map(|child| match child.data {
+            NodeData::Text { ref contents } => Some(contents.borrow().to_string()),
+            _ => None,
+        })
+        .collect();
+    serde_json::from_str(&json_ld).ok()
+}
+
+fn find_provenance_script(dom: &RcDom) -> Option<Handle> {
+    find_nodes(&dom.document, vec!["html", "head", "script"])
+        .into_iter()
+        .find(|script| get_node_attr(script, PROVENANCE_ATTR).is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::html::{html_to_dom, serialize_document};
+
+    #[test]
+    fn test_embed_and_read_provenance() {
+        let dom = html_to_dom(
+            &b"<html><head><title>Page</title></head><body><p>Hi</p></body></html>".to_vec(),
+            "".to_string(),
+        );
+        let mut provenance = TranslationProvenance {
+            source_lang: "en".to_string(),
+            target_lang: "zh".to_string(),
+            backends: vec!["deeplx".to_string()],
+            translated_at: DateTime::parse_from_rfc3339("2024-05-01T08:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            char_count: 1234,
+        };
+        embed_provenance(&dom, &provenance);
+        provenance.char_count = 42;
+        embed_provenance(&dom, &provenance);
+
+        assert_eq!(
+            find_nodes(&dom.document, vec!["html", "head", "script"]).len(),
+            1
+        );
+        let json_ld = read_provenance(&dom).unwrap();
+        assert_eq!(json_ld["inLanguage"], "zh");
+        assert_eq!(json_ld["translationOfWork"]["inLanguage"], "en");
+        assert_eq!(json_ld["translator"][0]["name"], "deeplx");
+        assert_eq!(json_ld["dateCreated"], "2024-05-01T08:00:00Z");
+        assert_eq!(json_ld["additionalProperty"]["value"], 42);
+
+        // 序列化后重新解析，脚本内容保持为合法的 JSON
+        let html = serialize_document(dom, "".to_string(), &Default::default());
+        assert!(String::from_utf8_lossy(&html)
+            .contains("<script type=\"application/ld+json\" translate=\"no\""));
+        assert_eq!(
+            read_provenance(&html_to_dom(&html, "".to_string())),
+            Some(json_ld)
+        );
+    }
+}
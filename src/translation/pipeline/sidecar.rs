@@ -0,0 +1,301 @@
+//! 独立保存的译文映射表
+//!
+//! 审校译文时需要保留未翻译的归档，译文另外保存。[`TranslationMap`] 以节点路径为键记录每个
+//! 文本单元的原文和译文，可以序列化为 JSON 文件与归档放在一起；[`apply_translation_map`]
+//! 之后把译文写回同一份归档，得到与直接翻译相同的页面。重新翻译时不必重新抓取页面。
+//!
+//! 节点路径的写法类似 XPath，例如 `/html[1]/body[1]/p[2]/text()[1]`：序号从 1 开始，
+//! 在同名的兄弟节点中计数。属性写作 `/@alt`，`<template>` 的内容写作 `/content()`，
+//! JSON 数据块中的字符串在文本节点之后附加 `/json(props.pageProps.title)`。
+//! 同一份 HTML 每次解析都得到相同的路径，映射表只能用于生成它的那份归档。
+//!
+//! 映射表只记录译文本身，双语对照和原文提示不会被记录。
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::rc::Rc;
+
+use markup5ever_rcdom::{Handle, Node, NodeData, RcDom};
+use serde::{Deserialize, Serialize};
+
+use crate::html::get_template_contents;
+use crate::translation::error::{TranslationError, TranslationResult};
+use crate::translation::pipeline::collector::{normalize_invisible_chars, TextItem, TextType};
+use crate::translation::pipeline::marker::{mark_translated, set_document_lang};
+use crate::translation::processor::apply_translation;
+
+/// 一个文本单元的原文和译文
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranslationUnit {
+    /// 翻译前的文本
+    pub source: String,
+    /// 写回节点的译文，文本节点包括原有的首尾空白
+    pub translation: String,
+}
+
+/// 节点路径到译文的映射表
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranslationMap {
+    /// 目标语言
+    pub target_lang: String,
+    /// 写入 `<html lang>` 的语言
+    pub document_lang: String,
+    /// 页面是否翻译完整；部分翻译（预算耗尽或被取消）的页面应用时不加已翻译标记
+    pub complete: bool,
+    /// 节点路径 -> 文本单元，只包含翻译后发生变化的文本
+    pub units: BTreeMap<String, TranslationUnit>,
+}
+
+impl TranslationMap {
+    /// 序列化为 JSON
+    pub fn to_json(&self) -> TranslationResult<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| TranslationError::InternalError(format!("映射表序列化失败: {}", e)))
+    }
+
+    /// 从 JSON 读取映射表
+    pub fn from_json(json: &str) -> TranslationResult<Self> {
+        serde_json::from_str(json)
+            .map_err(|e| TranslationError::ParseError(format!("映射表格式错误: {}", e)))
+    }
+}
+
+/// 翻译前记录的文本单元，翻译完成后对照节点的当前内容得到映射表
+pub(crate) struct MapSnapshot {
+    units: Vec<(String, TextItem, String)>,
+}
+
+impl MapSnapshot {
+    /// 记录 `items` 的路径和当前内容，`dom` 是收集文本项的文档
+    pub(crate) fn capture(dom: &RcDom, items: &[TextItem]) -> Self {
+        let paths = node_paths(&dom.document);
+        let units = items
+            .iter()
+            .flat_map(TextItem::occurrences)
+            .filter_map(|item| {
+                let path = item_path(&paths, item)?;
+                let current = item.current_text()?;
+                Some((path, item.clone(), current))
+            })
+            .collect();
+        Self { units }
+    }
+
+    /// 生成映射表，没有变化的文本不记录
+    pub(crate) fn finish(
+        self,
+        target_lang: &str,
+        document_lang: &str,
+        complete: bool,
+    ) -> TranslationMap {
+        let units = self
+            .units
+            .into_iter()
+            .filter_map(|(path, item, before)| {
+                let translation = item.current_text().filter(|after| *after != before)?;
+                Some((
+                    path,
+                    TranslationUnit {
+                        source: item.text.trim().to_string(),
+                        translation,
+                    },
+                ))
+            })
+            .collect();
+
+        TranslationMap {
+            target_lang: target_lang.to_string(),
+            document_lang: document_lang.to_string(),
+            complete,
+            units,
+        }
+    }
+}
+
+/// 写回映射表的结果
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ApplyStats {
+    /// 写回的单元数量
+    pub applied: usize,
+    /// 跳过的单元数量：节点的当前文本与记录的原文不同，或写回失败
+    pub skipped: usize,
+}
+
+/// 把映射表中的译文写回文档
+///
+/// 找不到的路径（文档与生成映射表时不同）不计入结果；节点当前的文本与单元的原文不一致，
+/// 或者写回失败时跳过该单元，其余单元照常写回，不会有写了一半的单元。映射表完整且没有
+/// 跳过的单元时，同时写入已翻译标记和 `<html lang>`，与直接翻译的结果一致。
+pub fn apply_translation_map(dom: &RcDom, map: &TranslationMap) -> ApplyStats {
+    NodeIndex::capture(dom).apply(dom, map)
+}
+
+/// 按路径记录的文档节点
+///
+/// 在文档结构改变之前记录，之后仍按记录时的路径找到节点，用于先翻译、后改动文档的场景
+/// （例如与资源嵌入并行翻译）。
+pub(crate) struct NodeIndex(HashMap<String, Handle>);
+
+impl NodeIndex {
+    pub(crate) fn capture(dom: &RcDom) -> Self {
+        Self(node_paths(&dom.document).into_values().collect())
+    }
+
+    /// 与 [`apply_translation_map`] 相同，但路径按记录时的文档解析
+    pub(crate) fn apply(&self, dom: &RcDom, map: &TranslationMap) -> ApplyStats {
+        let mut stats = ApplyStats::default();
+        for (path, unit) in &map.units {
+            let item = match resolve_unit(&self.0, path) {
+                Some(item) => item,
+                None => continue,
+            };
+            let matches_source = item
+                .current_text()
+                .is_some_and(|current| comparable_text(&current) == comparable_text(&unit.source));
+            if !matches_source {
+                tracing::warn!("跳过原文已改变的文本: {}", path);
+                stats.skipped += 1;
+                continue;
+            }
+            match apply_translation(&item, &unit.translation) {
+                Ok(()) => stats.applied += 1,
+                Err(e) => {
+                    tracing::warn!("写回译文失败，跳过 {}: {}", path, e);
+                    stats.skipped += 1;
+                }
+            }
+        }
+
+        if map.complete && stats.applied > 0 && stats.skipped == 0 {
+            mark_translated(dom, &map.target_lang);
+            set_document_lang(dom, &map.document_lang);
+        }
+        stats
+    }
+}
+
+/// 比较原文时使用的文本：与收集文本时一样去掉不可见字符并合并空白
+fn comparable_text(text: &str) -> String {
+    normalize_invisible_chars(text)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 深度复制整个文档，用于在副本上翻译而保留原文档
+pub(crate) fn clone_dom(dom: &RcDom) -> RcDom {
+    RcDom {
+        document: clone_node(&dom.document),
+        errors: RefCell::new(dom.errors.borrow().clone()),
+        quirks_mode: dom.quirks_mode.clone(),
+    }
+}
+
+fn clone_node(node: &Handle) -> Handle {
+    let data = match node.data {
+        NodeData::Document => NodeData::Document,
+        NodeData::Doctype {
+            ref name,
+            ref public_id,
+            ref system_id,
+        } => NodeData::Doctype {
+            name: name.clone(),
+            public_id: public_id.clone(),
+            system_id: system_id.clone(),
+        },
+        NodeData::Text { ref contents } => NodeData::Text {
+            contents: RefCell::new(contents.borrow().clone()),
+        },
+        NodeData::Comment { ref contents } => NodeData::Comment {
+            contents: contents.clone(),
+        },
+        NodeData::Element {
+            ref name,
+            ref attrs,
+            ref template_contents,
+            mathml_annotation_xml_integration_point,
+        } => NodeData::Element {
+            name: name.clone(),
+            attrs: RefCell::new(attrs.borrow().clone()),
+            template_contents: RefCell::new(template_contents.borrow().as_ref().map(clone_node)),
+            mathml_annotation_xml_integration_point,
+        },
+        NodeData::ProcessingInstruction {
+            ref target,
+            ref contents,
+        } => NodeData::ProcessingInstruction {
+            target: target.clone(),
+            contents: contents.clone(),
+        },
+    };
+
+    let copy = Node::new(data);
+    for child in node.children.borrow().iter() {
+        let child = clone_node(child);
+        child.parent.set(Some(Rc::downgrade(&copy)));
+        copy.children.borrow_mut().push(child);
+    }
+    copy
+}
+
+/// 文档中每个节点的路径，以节点地址为键
+fn node_paths(root: &Handle) -> HashMap<*const Node, (String, Handle)> {
+    let mut paths = HashMap::new();
+    add_child_paths(root, "", &mut paths);
+    paths
+}
+
+fn add_child_paths(
+    parent: &Handle,
+    parent_path: &str,
+    paths: &mut HashMap<*const Node, (String, Handle)>,
+) {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for child in parent.children.borrow().iter() {
+        let step = match child.data {
+            NodeData::Element { ref name, .. } => name.local.to_string(),
+            NodeData::Text { .. } => "text()".to_string(),
+            _ => continue,
+        };
+        let count = counts.entry(step.clone()).or_insert(0);
+        *count += 1;
+        let path = format!("{}/{}[{}]", parent_path, step, count);
+
+        add_child_paths(child, &path, paths);
+        if let Some(contents) = get_template_contents(child) {
+            add_child_paths(&contents, &format!("{}/content()", path), paths);
+        }
+        paths.insert(Rc::as_ptr(child), (path, child.clone()));
+    }
+}
+
+/// 文本项的路径：节点路径，属性附加 `/@name`，JSON 字符串附加 `/json(path)`
+fn item_path(paths: &HashMap<*const Node, (String, Handle)>, item: &TextItem) -> Option<String> {
+    let (node_path, _) = paths.get(&Rc::as_ptr(&item.node))?;
+    Some(match (&item.text_type, &item.attr_name) {
+        (TextType::JsonValue(json_path), _) => format!("{}/json({})", node_path, json_path),
+        (_, Some(attr_name)) => format!("{}/@{}", node_path, attr_name),
+        _ => node_path.clone(),
+    })
+}
+
+/// 由路径找到写回译文所需的文本项
+fn resolve_unit(nodes: &HashMap<String, Handle>, path: &str) -> Option<TextItem> {
+    if let Some(node_path) = path.strip_suffix(')') {
+        if let Some((node_path, json_path)) = node_path.rsplit_once("/json(") {
+            let mut item = TextItem::new(String::new(), nodes.get(node_path)?.clone(), None);
+            item.text_type = TextType::JsonValue(json_path.to_string());
+            return Some(item);
+        }
+    }
+    if let Some((node_path, attr_name)) = path.rsplit_once("/@") {
+        let node = nodes.get(node_path)?.clone();
+        return Some(TextItem::new(
+            String::new(),
+            node,
+            Some(attr_name.to_string()),
+        ));
+    }
+    let node = nodes.get(path)?.clone();
+    Some(TextItem::new(String::new(), node, None))
+}
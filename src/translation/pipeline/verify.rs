@@ -0,0 +1,161 @@
+//! 译文校验
+//!
+//! 翻译接口偶尔会对整个批次返回空文本、错误信息（例如 "API quota exceeded"）或根本没有翻译的
+//! 原文，写回后页面上就会出现这些内容。[`TranslationVerifier`] 在写回前逐条检查译文：
+//!
+//! - 译文与原文的字符数之比必须在 `min_length_ratio` 和 `max_length_ratio` 之间；
+//! - 原文足够长（至少 [`LANGUAGE_CHECK_MIN_CHARS`] 个字符）时，译文的文字必须与目标语言相符：
+//!   目标语言是中文、日语或韩语时译文不能以拉丁字母为主，其他目标语言的译文不能以中日韩文字为主。
+//!
+//! 语言判断使用 [`TextFilter::detect_language_hint`]，只能区分文字体系，因此只拒绝明显写错
+//! 文字的译文；英文误译为法文之类的情况无法发现。
+
+use std::fmt;
+
+use crate::translation::config::TranslationConfig;
+use crate::translation::pipeline::filters::{LanguageHint, TextFilter};
+
+/// 检查译文语言所需的最少原文字符数，更短的文本（按钮、专有名词）常常不需要改变文字
+pub const LANGUAGE_CHECK_MIN_CHARS: usize = 20;
+
+/// 译文被拒绝的原因
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rejection {
+    /// 译文为空
+    Empty,
+    /// 译文与原文的字符数之比低于下限
+    TooShort(f32),
+    /// 译文与原文的字符数之比高于上限
+    TooLong(f32),
+    /// 译文的文字与目标语言不符
+    WrongLanguage(LanguageHint),
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rejection::Empty => write!(f, "译文为空"),
+            Rejection::TooShort(ratio) => write!(f, "译文过短（长度比例 {:.2}）", ratio),
+            Rejection::TooLong(ratio) => write!(f, "译文过长（长度比例 {:.2}）", ratio),
+            Rejection::WrongLanguage(hint) => write!(f, "译文语言不符（{:?}）", hint),
+        }
+    }
+}
+
+/// 写回前检查译文的校验器
+#[derive(Debug, Clone)]
+pub struct TranslationVerifier {
+    min_length_ratio: f32,
+    max_length_ratio: f32,
+    target_lang: String,
+}
+
+impl TranslationVerifier {
+    /// 按配置中的比例和目标语言创建校验器
+    pub fn from_config(config: &TranslationConfig) -> Self {
+        Self {
+            min_length_ratio: config.min_length_ratio,
+            max_length_ratio: config.max_length_ratio,
+            target_lang: config.target_lang.clone(),
+        }
+    }
+
+    /// 检查一条译文，不合格时返回原因
+    pub fn verify(&self, original: &str, translated: &str) -> Result<(), Rejection> {
+        let original = original.trim();
+        let translated = translated.trim();
+        if translated.is_empty() {
+            return Err(Rejection::Empty);
+        }
+
+        let original_len = original.chars().count();
+        if original_len > 0 {
+            let ratio = translated.chars().count() as f32 / original_len as f32;
+            if ratio < self.min_length_ratio {
+                return Err(Rejection::TooShort(ratio));
+            }
+            if ratio > self.max_length_ratio {
+                return Err(Rejection::TooLong(ratio));
+            }
+        }
+
+        if original_len >= LANGUAGE_CHECK_MIN_CHARS {
+            let hint = TextFilter::new().detect_language_hint(translated);
+            if !self.is_target_script(&hint) {
+                return Err(Rejection::WrongLanguage(hint));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn is_target_script(&self, hint: &LanguageHint) -> bool {
+        let cjk_target = [
+            LanguageHint::Chinese,
+            LanguageHint::Japanese,
+            LanguageHint::Korean,
+        ]
+        .iter()
+        .any(|cjk| cjk.matches_lang(&self.target_lang));
+
+        match hint {
+            LanguageHint::Latin => !cjk_target,
+            LanguageHint::Chinese | LanguageHint::Japanese | LanguageHint::Korean => {
+                // 日文中的汉字较多时可能被判断为中文
+                cjk_target
+            }
+            LanguageHint::Mixed | LanguageHint::Unknown => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn verifier(target_lang: &str) -> TranslationVerifier {
+        TranslationVerifier::from_config(&TranslationConfig::default_with_lang(target_lang, None))
+    }
+
+    #[test]
+    fn test_rejects_degenerate_translations() {
+        let verifier = verifier("zh");
+        let original = "Archived pages keep working long after the original site is gone.";
+
+        assert!(verifier
+            .verify(original, "原网站消失很久之后，归档的页面仍然可以正常使用。")
+            .is_ok());
+        assert_eq!(verifier.verify(original, "  "), Err(Rejection::Empty));
+        assert!(matches!(
+            verifier.verify(original, "无"),
+            Err(Rejection::TooShort(_))
+        ));
+        assert_eq!(
+            verifier.verify(original, "API quota exceeded, please try again later"),
+            Err(Rejection::WrongLanguage(LanguageHint::Latin))
+        );
+        // 短文本不检查语言
+        assert!(verifier.verify("iPhone 15 Pro", "iPhone 15 Pro").is_ok());
+    }
+
+    #[test]
+    fn test_latin_targets() {
+        let verifier = verifier("en");
+        let original = "原网站消失很久之后，归档的页面仍然可以正常使用，链接和图片都不会丢失。";
+
+        assert!(verifier
+            .verify(
+                original,
+                "Archived pages keep working long after the original site is gone."
+            )
+            .is_ok());
+        assert_eq!(
+            verifier.verify(original, original),
+            Err(Rejection::WrongLanguage(LanguageHint::Chinese))
+        );
+        assert!(matches!(
+            verifier.verify("好", &"Good ".repeat(20)),
+            Err(Rejection::TooLong(_))
+        ));
+    }
+}
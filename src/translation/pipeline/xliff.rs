@@ -0,0 +1,274 @@
+//! XLIFF 导出与导入
+//!
+//! 供人工翻译使用：[`export_xliff`] 把收集到的文本项写成 XLIFF 2.0 文件，译者在 CAT 工具中
+//! 填写 `<target>` 后，[`import_xliff`] 按单元 id 把译文写回 DOM。
+//!
+//! 单元 id 由元素路径、属性名和原文计算得到，不依赖收集顺序中的序号。同一页面重新收集时
+//! id 保持不变，页面其他部分的改动也不会让已完成的译文失效；只有原文本身改变的单元才会
+//! 得到新的 id。路径和原文都相同的文本按出现顺序追加 `-2`、`-3` 等后缀。
+
+use std::collections::HashMap;
+
+use regex::Regex;
+use sha2::{Digest, Sha256};
+
+use crate::translation::error::{TranslationError, TranslationResult};
+use crate::translation::pipeline::collector::TextItem;
+use crate::translation::pipeline::diff::element_path;
+use crate::translation::processor::apply_translation;
+
+/// XLIFF 2.0 命名空间
+const XLIFF_NAMESPACE: &str = "urn:oasis:names:tc:xliff:document:2.0";
+
+/// 为文本项计算稳定的单元 id，顺序与 `items` 一致
+pub fn unit_ids(items: &[TextItem]) -> Vec<String> {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+
+    items
+        .iter()
+        .map(|item| {
+            let mut hasher = Sha256::new();
+            hasher.update(unit_location(item).as_bytes());
+            hasher.update([0]);
+            hasher.update(item.text.trim().as_bytes());
+            let id = format!("u{}", &format!("{:x}", hasher.finalize())[..12]);
+
+            let count = seen.entry(id.clone()).or_insert(0);
+            *count += 1;
+            if *count == 1 {
+                id
+            } else {
+                format!("{}-{}", id, count)
+            }
+        })
+        .collect()
+}
+
+/// 把文本项导出为 XLIFF 2.0 文档
+///
+/// 源语言未知时传入 `und`。每个单元附带一条 `location` 备注，写明文本所在的元素路径。
+pub fn export_xliff(items: &[TextItem], source_lang: &str, target_lang: &str) -> String {
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str(&format!(
+        "<xliff xmlns=\"{}\" version=\"2.0\" srcLang=\"{}\" trgLang=\"{}\">\n",
+        XLIFF_NAMESPACE,
+        escape_xml(source_lang),
+        escape_xml(target_lang)
+    ));
+    out.push_str("  <file id=\"f1\">\n");
+
+    for (item, id) in items.iter().zip(unit_ids(items)) {
+        out.push_str(&format!("    <unit id=\"{}\">\n", id));
+        out.push_str(&format!(
+            "      <notes><note category=\"location\">{}</note></notes>\n",
+            escape_xml(&unit_location(item))
+        ));
+        out.push_str(&format!(
+            "      <segment><source>{}</source></segment>\n",
+            escape_xml(item.text.trim())
+        ));
+        out.push_str("    </unit>\n");
+    }
+
+    out.push_str("  </file>\n");
+    out.push_str("</xliff>\n");
+    out
+}
+
+/// 把 XLIFF 文档中的译文按单元 id 写回文本项对应的 DOM 节点
+///
+/// 没有 `<target>` 或译文为空的单元会被跳过，文档中找不到的 id 同样忽略。
+/// 返回实际写回的文本数量。
+pub fn import_xliff(items: &[TextItem], xliff: &str) -> TranslationResult<usize> {
+    let translations = parse_targets(xliff)?;
+    let mut applied = 0;
+
+    for (item, id) in items.iter().zip(unit_ids(items)) {
+        if let Some(translated) = translations.get(&id) {
+            apply_translation(item, translated)?;
+            applied += 1;
+        }
+    }
+
+    Ok(applied)
+}
+
+/// 读取 XLIFF 文档中各单元的译文：单元 id -> 译文
+fn parse_targets(xliff: &str) -> TranslationResult<HashMap<String, String>> {
+    if !xliff.contains("<xliff") {
+        return Err(TranslationError::ParseError(
+            "不是 XLIFF 文档：缺少 <xliff> 根元素".to_string(),
+        ));
+    }
+
+    let unit_regex = Regex::new(r#"(?s)<unit\b[^>]*?\bid="([^"]*)"[^>]*>(.*?)</unit>"#)
+        .map_err(|e| TranslationError::ParseError(format!("正则表达式编译失败: {}", e)))?;
+    let target_regex = Regex::new(r"(?s)<target\b[^>]*>(.*?)</target>")
+        .map_err(|e| TranslationError::ParseError(format!("正则表达式编译失败: {}", e)))?;
+
+    let mut translations = HashMap::new();
+    for unit in unit_regex.captures_iter(xliff) {
+        // 一个单元可以有多个片段，按顺序拼接
+        let translated: String = target_regex
+            .captures_iter(&unit[2])
+            .map(|target| unescape_xml(&target[1]))
+            .collect();
+        let translated = translated.trim();
+        if !translated.is_empty() {
+            translations.insert(unescape_xml(&unit[1]), translated.to_string());
+        }
+    }
+
+    Ok(translations)
+}
+
+/// 文本项的位置：元素路径，属性文本再附加属性名
+fn unit_location(item: &TextItem) -> String {
+    match &item.attr_name {
+        Some(attr_name) => format!("{}[{}]", element_path(&item.node), attr_name),
+        None => element_path(&item.node),
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// 还原预定义实体、数字字符引用和 CDATA 段
+fn unescape_xml(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find(['&', '<']) {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+            let end = cdata.find("]]>").unwrap_or(cdata.len());
+            out.push_str(&cdata[..end]);
+            rest = cdata.get(end + 3..).unwrap_or("");
+            continue;
+        }
+
+        let entity_end = if rest.starts_with('&') {
+            rest.find(';')
+        } else {
+            None
+        };
+        let decoded = entity_end.and_then(|end| match &rest[1..end] {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            entity => entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        });
+        match (decoded, entity_end) {
+            (Some(c), Some(end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            _ => {
+                out.push_str(&rest[..1]);
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::html::{html_to_dom, serialize_document};
+    use crate::translation::pipeline::collector::{CollectorConfig, TextCollector};
+
+    const PAGE: &str = "<html><head></head><body>\
+        <h1>Welcome to our store</h1><p>Free shipping on all orders</p>\
+        <a href=\"/gallery\" title=\"Open the product gallery\">Product gallery</a></body></html>";
+
+    fn collect(html: &str) -> (markup5ever_rcdom::RcDom, Vec<TextItem>) {
+        let dom = html_to_dom(html.as_bytes(), "UTF-8".to_string());
+        let mut config = CollectorConfig::default();
+        config.enable_parallel = false;
+        let items = TextCollector::new(config)
+            .collect_translatable_texts(&dom.document)
+            .unwrap();
+        (dom, items)
+    }
+
+    #[test]
+    fn test_unit_ids_are_stable() {
+        let (_, first) = collect(PAGE);
+        let (_, second) = collect(&PAGE.replace(
+            "<p>Free shipping",
+            "<p>Returns accepted within thirty days</p><p>Free shipping",
+        ));
+
+        let first_ids = unit_ids(&first);
+        for (item, id) in first.iter().zip(&first_ids) {
+            let position = second
+                .iter()
+                .position(|other| other.text == item.text)
+                .unwrap();
+            assert_eq!(&unit_ids(&second)[position], id, "{}", item.text);
+        }
+
+        // 路径和原文都相同的文本项按出现顺序加后缀
+        let ids = unit_ids(&[first[0].clone(), first[0].clone()]);
+        assert_eq!(ids[1], format!("{}-2", ids[0]));
+    }
+
+    #[test]
+    fn test_export_import_round_trip() {
+        let (dom, items) = collect(PAGE);
+        let exported = export_xliff(&items, "en", "zh");
+        assert!(exported.contains("srcLang=\"en\" trgLang=\"zh\""));
+        assert!(exported.contains("<source>Free shipping on all orders</source>"));
+
+        // 模拟译者在 CAT 工具中填写译文
+        let translated = [
+            ("Welcome to our store", "欢迎光临 &amp; 选购"),
+            ("Free shipping on all orders", "<![CDATA[全场包邮]]>"),
+            ("Open the product gallery", "打开&#x4EA7;品图库"),
+            ("Product gallery", "产品图库"),
+        ]
+        .iter()
+        .fold(exported, |xliff, (source, target)| {
+            xliff.replace(
+                &format!("<source>{}</source>", source),
+                &format!("<source>{}</source><target>{}</target>", source, target),
+            )
+        });
+
+        assert_eq!(import_xliff(&items, &translated).unwrap(), items.len());
+        let html = String::from_utf8(serialize_document(
+            dom,
+            "UTF-8".to_string(),
+            &Default::default(),
+        ))
+        .unwrap();
+        assert!(html.contains("<h1>欢迎光临 &amp; 选购</h1>"));
+        assert!(html.contains("<p>全场包邮</p>"));
+        assert!(html.contains("<a href=\"/gallery\" title=\"打开产品图库\">产品图库</a>"));
+    }
+
+    #[test]
+    fn test_import_skips_missing_targets() {
+        let (_, items) = collect(PAGE);
+        let xliff = export_xliff(&items, "und", "zh");
+        assert_eq!(import_xliff(&items, &xliff).unwrap(), 0);
+        assert!(import_xliff(&items, "<html></html>").is_err());
+    }
+}
@@ -13,11 +13,12 @@
 //! 
 //! ```rust
 //! use std::sync::Arc;
-//! use markdown_translator::TranslationService;
+//! use crate::translation::config::TranslationConfig;
+//! use crate::translation::core::TranslationBackend;
 //! use crate::translation::processor::{TranslationProcessor, ProcessorConfig};
 //! 
 //! async fn process_translation() -> Result<(), Box<dyn std::error::Error>> {
-//!     let service = Arc::new(TranslationService::new());
+//!     let service = Arc::new(TranslationBackend::from_config(&TranslationConfig::default()));
 //!     let config = ProcessorConfig::default();
 //!     let mut processor = TranslationProcessor::new(service, config);
 //!     
@@ -29,22 +30,31 @@
 //! ```
 
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
 
 #[cfg(feature = "translation")]
-use markdown_translator::TranslationService;
+use crate::translation::core::backend::TranslationBackend;
 #[cfg(feature = "translation")]
 use regex::Regex;
 #[cfg(feature = "translation")]
 use tokio::time::{sleep, Duration};
+#[cfg(feature = "translation")]
+use tokio_util::sync::CancellationToken;
 
 use crate::html::set_node_attr;
-use crate::translation::batch::{Batch, BatchType};
-use crate::translation::collector::TextItem;
+use crate::translation::batch::{Batch, BatchType, LanguageConcurrency, LanguagePermit};
+use crate::translation::collector::{TextItem, TextType};
 use crate::translation::error::{TranslationError, TranslationResult};
+use crate::translation::pipeline::json::replace_json_string;
+use crate::translation::pipeline::verify::TranslationVerifier;
+use crate::translation::storage::cache::{generate_cache_key, CacheManager};
 use markup5ever_rcdom::NodeData;
 
+/// 统计中保留的失败文本样本数量上限
+pub const FAILED_SAMPLE_LIMIT: usize = 5;
+
 /// 翻译处理器
 /// 
 /// 负责协调和执行文本翻译任务的核心组件。支持多种处理策略：
@@ -59,7 +69,7 @@ use markup5ever_rcdom::NodeData;
 #[cfg(feature = "translation")]
 pub struct TranslationProcessor {
     /// 翻译服务实例
-    service: Arc<TranslationService>,
+    service: Arc<TranslationBackend>,
     /// 处理统计信息
     stats: ProcessorStats,
     /// 处理器配置参数
@@ -82,11 +92,11 @@ impl TranslationProcessor {
     /// use std::sync::Arc;
     /// use crate::translation::processor::{TranslationProcessor, ProcessorConfig};
     /// 
-    /// let service = Arc::new(TranslationService::new());
+    /// let service = Arc::new(TranslationBackend::from_config(&TranslationConfig::default()));
     /// let config = ProcessorConfig::default();
     /// let processor = TranslationProcessor::new(service, config);
     /// ```
-    pub fn new(service: Arc<TranslationService>, config: ProcessorConfig) -> Self {
+    pub fn new(service: Arc<TranslationBackend>, config: ProcessorConfig) -> Self {
         Self {
             service,
             stats: ProcessorStats::default(),
@@ -176,10 +186,27 @@ impl TranslationProcessor {
                 let _retry_count = Arc::clone(&retry_count);
 
                 async move {
+                    // 失败时报告未翻译的文本，需在批次移交前记录
+                    let item_count = batch.items.len();
+                    let samples = failed_samples(&batch);
+
                     // 获取信号量许可，控制并发数
                     let _permit = semaphore.acquire().await.map_err(|e| {
-                        TranslationError::ConcurrencyError(format!("获取并发许可失败: {}", e))
+                        (
+                            TranslationError::ConcurrencyError(format!("获取并发许可失败: {}", e)),
+                            item_count,
+                            samples.clone(),
+                        )
                     })?;
+                    let _language_permit = acquire_language_permit(&config)
+                        .await
+                        .map_err(|e| (e, item_count, samples.clone()))?;
+
+                    // 等待许可期间可能已被取消
+                    if is_cancelled(&config) {
+                        failed_count.fetch_add(1, Ordering::Relaxed);
+                        return Err((TranslationError::Cancelled, item_count, samples));
+                    }
 
                     tracing::debug!(
                         "开始并发处理批次 {}: {} 项",
@@ -206,7 +233,7 @@ impl TranslationProcessor {
                         Err(e) => {
                             failed_count.fetch_add(1, Ordering::Relaxed);
                             tracing::error!("批次 {} 处理失败: {}", batch_index + 1, e);
-                            Err(e.clone())
+                            Err((e.clone(), item_count, samples))
                         }
                     }
                 }
@@ -232,10 +259,22 @@ impl TranslationProcessor {
         let mut total_translated_items = 0;
         let mut total_processing_time = Duration::from_millis(0);
 
+        let mut first_error = None;
+
         for result in results {
-            if let Ok((_, stats)) = result {
-                total_translated_items += stats.translated_items;
-                total_processing_time += stats.processing_time;
+            match result {
+                Ok((_, stats)) => {
+                    total_translated_items += stats.translated_items;
+                    total_processing_time += stats.processing_time;
+                    self.stats.rejected_items += stats.rejected_items;
+                    // 批次内逐个翻译时个别文本可能失败
+                    self.stats
+                        .record_failed_items(stats.skipped_items, stats.failed_samples);
+                }
+                Err((error, item_count, samples)) => {
+                    self.stats.record_failed_items(item_count, samples);
+                    first_error.get_or_insert(error);
+                }
             }
         }
 
@@ -263,14 +302,45 @@ impl TranslationProcessor {
             self.stats.success_rate * 100.0
         );
 
-        if failed > 0 {
-            Err(TranslationError::ProcessingError(format!(
-                "{}个批次处理失败",
-                failed
-            )))
-        } else {
-            Ok(())
+        self.finish_with_failures(first_error)
+    }
+
+    /// 根据失败情况决定处理结果
+    ///
+    /// 默认任一批次失败即返回错误；`best_effort` 模式下保留已成功批次的译文，
+    /// 失败的文本保持原文，只在统计中记录。
+    fn finish_with_failures(&self, first_error: Option<TranslationError>) -> TranslationResult<()> {
+        let failed = self.stats.failed_batches;
+        if failed == 0 {
+            return Ok(());
+        }
+
+        // 预算耗尽和取消不属于批次故障，即使尽力而为模式也要告知调用方
+        if let Some(error) = self.config.char_budget.exceeded_error() {
+            tracing::warn!("{}，{} 项保持原文", error, self.stats.skipped_items);
+            return Err(error);
+        }
+        if is_cancelled(&self.config) {
+            tracing::info!("翻译已取消，{} 项保持原文", self.stats.skipped_items);
+            return Err(TranslationError::Cancelled);
         }
+
+        if self.config.best_effort {
+            tracing::warn!(
+                "{} 个批次翻译失败，{} 项保持原文",
+                failed,
+                self.stats.skipped_items
+            );
+            return Ok(());
+        }
+
+        Err(TranslationError::ProcessingError(format!(
+            "{}个批次处理失败: {}",
+            failed,
+            first_error
+                .map(|e| e.to_string())
+                .unwrap_or_else(|| "未知错误".to_string())
+        )))
     }
 
     /// 带重试机制的批次处理
@@ -294,7 +364,7 @@ impl TranslationProcessor {
     /// - 每次重试延迟时间为：retry_delay * 2^attempt
     async fn process_batch_with_retry(
         batch: Batch,
-        service: Arc<TranslationService>,
+        service: Arc<TranslationBackend>,
         config: ProcessorConfig,
         batch_number: usize,
     ) -> TranslationResult<ProcessorStats> {
@@ -373,16 +443,18 @@ impl TranslationProcessor {
     /// - `batches`: 待处理的批次列表
     /// 
     /// # 返回值
-    /// - `Ok(())`: 处理完成（即使有部分失败）
+    /// - `Ok(())`: 处理完成（`best_effort` 模式下即使有部分失败）
+    /// - `Err(TranslationError)`: 有批次处理失败
     /// 
     /// # 特性
     /// - 每个批次间有延迟控制
     /// - 详细的进度日志
-    /// - 失败容错，不会因单个批次失败而中断
+    /// - 单个批次失败不会中断后续批次
     /// - 完整的统计信息收集
     async fn process_batches_sequentially(&mut self, batches: Vec<Batch>) -> TranslationResult<()> {
         tracing::info!("开始顺序处理 {} 个翻译批次", batches.len());
 
+        let mut first_error = None;
         for (i, batch) in batches.into_iter().enumerate() {
             tracing::debug!(
                 "处理批次 {}/{}: {}",
@@ -391,13 +463,28 @@ impl TranslationProcessor {
                 batch.summary()
             );
 
-            let result = self.process_single_batch(batch).await;
+            let item_count = batch.items.len();
+            let samples = failed_samples(&batch);
+
+            // 预算耗尽或已取消时剩余批次直接记为失败，不再等待批次间延迟
+            if self.config.char_budget.is_exhausted() || is_cancelled(&self.config) {
+                self.stats.failed_batches += 1;
+                self.stats.record_failed_items(item_count, samples);
+                continue;
+            }
+
+            let result = match acquire_language_permit(&self.config).await {
+                Ok(_permit) => self.process_single_batch(batch).await,
+                Err(e) => Err(e),
+            };
 
             match result {
                 Ok(_) => self.stats.successful_batches += 1,
                 Err(e) => {
                     self.stats.failed_batches += 1;
+                    self.stats.record_failed_items(item_count, samples);
                     tracing::error!("批次处理失败: {}", e);
+                    first_error.get_or_insert(e);
                 }
             }
 
@@ -420,7 +507,7 @@ impl TranslationProcessor {
             self.stats.success_rate * 100.0
         );
 
-        Ok(())
+        self.finish_with_failures(first_error)
     }
 
     /// 处理单个批次（支持Clone以便重试）
@@ -496,6 +583,7 @@ impl TranslationProcessor {
                 self.stats.indexed_failures += 1;
                 self.process_individual_items(&batch.items).await
             }
+            Err(e @ TranslationError::BudgetExceeded { .. }) => Err(e),
             Err(e) => {
                 tracing::warn!("索引翻译出错: {}, 回退到逐个翻译", e);
                 self.stats.indexed_failures += 1;
@@ -524,8 +612,9 @@ impl TranslationProcessor {
         let combined_text = self.combine_texts_with_indices(&batch.items);
 
         tracing::debug!("索引组合文本长度: {} 字符", combined_text.len());
+        self.config.char_budget.charge(&combined_text)?;
 
-        match self.service.translate(&combined_text).await {
+        match request_translation(&self.service, &self.config, &combined_text).await {
             Ok(translated) => {
                 let success = self.apply_indexed_translation(&batch.items, &translated)?;
                 Ok(success)
@@ -586,24 +675,8 @@ impl TranslationProcessor {
         items: &[TextItem],
         translated: &str,
     ) -> TranslationResult<bool> {
-        let index_regex = Regex::new(r"^\[(\d+)\]\s*(.*)$")
-            .map_err(|e| TranslationError::ParseError(format!("正则表达式编译失败: {}", e)))?;
-
-        let mut translations = HashMap::new();
-
         // 解析翻译结果
-        for line in translated.lines() {
-            if let Some(captures) = index_regex.captures(line.trim()) {
-                if let (Some(index_str), Some(text)) = (captures.get(1), captures.get(2)) {
-                    if let Ok(index) = index_str.as_str().parse::<usize>() {
-                        let translated_text = text.as_str().trim();
-                        if !translated_text.is_empty() {
-                            translations.insert(index, translated_text);
-                        }
-                    }
-                }
-            }
-        }
+        let translations = parse_indexed_translation(translated)?;
 
         tracing::debug!(
             "索引翻译解析: 输入 {} 项，解析到 {} 项",
@@ -613,19 +686,27 @@ impl TranslationProcessor {
 
         // 应用翻译
         let mut success_count = 0;
+        let mut missing = Vec::new();
         for (i, item) in items.iter().enumerate() {
             if let Some(translated_text) = translations.get(&i) {
+                if !self.verify_translation(item, translated_text) {
+                    continue;
+                }
                 self.apply_translation(item, translated_text)?;
                 success_count += 1;
                 self.stats.translated_items += 1;
             } else {
                 tracing::debug!("索引 {} 没有找到对应翻译", i);
-                self.stats.skipped_items += 1;
+                missing.push(item.text.clone());
             }
         }
 
         let success_rate = success_count as f32 / items.len() as f32;
         let is_success = success_rate >= self.config.min_success_rate;
+        // 回退到逐个翻译时缺失的文本还会再翻译，只有不回退时才保持原文
+        if is_success {
+            self.stats.record_failed_items(missing.len(), missing);
+        }
 
         tracing::debug!(
             "索引翻译成功率: {:.1}% ({}/{}), 判定: {}",
@@ -659,12 +740,16 @@ impl TranslationProcessor {
 
         for (i, item) in items.iter().enumerate() {
             match self.process_single_item(item).await {
-                Ok(_) => {
+                Ok(true) => {
                     self.stats.translated_items += 1;
                     tracing::debug!("项目 {}/{} 翻译成功", i + 1, items.len());
                 }
+                // 译文被拒绝，已计入统计
+                Ok(false) => {}
+                // 预算耗尽时其余项目也不会再发送，整个批次按失败处理
+                Err(e @ TranslationError::BudgetExceeded { .. }) => return Err(e),
                 Err(e) => {
-                    self.stats.skipped_items += 1;
+                    self.stats.record_failed_items(1, vec![item.text.clone()]);
                     tracing::warn!("项目 {}/{} 翻译失败: {}", i + 1, items.len(), e);
                 }
             }
@@ -687,22 +772,41 @@ impl TranslationProcessor {
     /// - `item`: 包含文本内容和DOM节点信息的文本项
     /// 
     /// # 返回值
-    /// - `Ok(())`: 翻译并应用成功
+    /// - `Ok(true)`: 翻译并应用成功
+    /// - `Ok(false)`: 译文未通过校验，保持原文
     /// - `Err(TranslationError)`: 翻译服务调用失败或应用失败
     /// 
     /// # 处理流程
     /// 1. 调用翻译服务获取翻译结果
     /// 2. 清理翻译结果（去除首尾空格）
-    /// 3. 将翻译结果应用到DOM节点
-    async fn process_single_item(&mut self, item: &TextItem) -> TranslationResult<()> {
-        let translated = self
-            .service
-            .translate(&item.text)
-            .await
-            .map_err(|e| TranslationError::TranslationServiceError(e.to_string()))?;
-
+    /// 3. 校验译文（配置了校验器时）
+    /// 4. 将翻译结果应用到DOM节点
+    async fn process_single_item(&mut self, item: &TextItem) -> TranslationResult<bool> {
+        self.config.char_budget.charge(&item.text)?;
+        let translated = request_translation(&self.service, &self.config, &item.text).await?;
+
+        if !self.verify_translation(item, translated.trim()) {
+            return Ok(false);
+        }
         self.apply_translation(item, &translated.trim())?;
-        Ok(())
+        Ok(true)
+    }
+
+    /// 按 [`ProcessorConfig::verifier`] 检查译文，被拒绝的文本保持原文并计入统计
+    fn verify_translation(&mut self, item: &TextItem, translated: &str) -> bool {
+        let verifier = match &self.config.verifier {
+            Some(verifier) => verifier,
+            None => return true,
+        };
+        match verifier.verify(&item.text, translated) {
+            Ok(()) => true,
+            Err(rejection) => {
+                tracing::warn!("拒绝译文，保留原文 '{}': {}", item.text, rejection);
+                self.stats.rejected_items += 1;
+                self.stats.record_failed_items(1, vec![item.text.clone()]);
+                false
+            }
+        }
     }
 
     /// 将翻译结果应用到DOM节点
@@ -727,32 +831,12 @@ impl TranslationProcessor {
     /// - 检测可能的翻译失败（结果与原文相同）
     /// - 验证DOM节点类型的正确性
     fn apply_translation(&self, item: &TextItem, translated: &str) -> TranslationResult<()> {
-        if translated.is_empty() {
-            return Err(TranslationError::InvalidInput("翻译结果为空".to_string()));
-        }
-
-        // 检查翻译质量
-        if translated == item.text.trim() && item.text.len() > 5 {
-            tracing::debug!("翻译结果与原文相同，可能是专有名词: '{}'", translated);
-        }
-
-        // 应用到DOM
-        if let Some(attr_name) = &item.attr_name {
-            // 属性翻译
-            set_node_attr(&item.node, attr_name, Some(translated.to_string()));
-        } else {
-            // 文本内容翻译
-            if let NodeData::Text { ref contents } = item.node.data {
-                let mut content_ref = contents.borrow_mut();
-                content_ref.clear();
-                content_ref.push_slice(translated);
-            } else {
-                return Err(TranslationError::InternalError(
-                    "节点不是文本类型".to_string(),
-                ));
+        apply_translation(item, translated)?;
+        if let Some(callback) = &self.config.progress_callback {
+            for occurrence in item.occurrences() {
+                callback.notify(occurrence, translated);
             }
         }
-
         Ok(())
     }
 
@@ -771,6 +855,168 @@ impl TranslationProcessor {
     pub fn get_stats(&self) -> &ProcessorStats {
         &self.stats
     }
+
+    /// 设置译文写回回调，见 [`ProcessorConfig::progress_callback`]
+    pub fn set_progress_callback(&mut self, callback: Option<ProgressCallback>) {
+        self.config.progress_callback = callback;
+    }
+
+    /// 设置多语言共享的并发额度，见 [`ProcessorConfig::language_concurrency`]
+    pub fn set_language_concurrency(&mut self, concurrency: Option<(LanguageConcurrency, String)>) {
+        self.config.language_concurrency = concurrency;
+    }
+
+    /// 设置合并相同请求所用的缓存管理器，见 [`ProcessorConfig::coalescing`]
+    pub fn set_coalescing(&mut self, coalescing: Option<(CacheManager, String, String)>) {
+        self.config.coalescing = coalescing;
+    }
+
+    /// 设置取消令牌，见 [`ProcessorConfig::cancellation`]
+    pub fn set_cancellation(&mut self, cancellation: Option<CancellationToken>) {
+        self.config.cancellation = cancellation;
+    }
+}
+
+/// 向翻译服务发送一段文本；配置了请求合并时，与正在进行的相同请求共享结果
+///
+/// 后端链回退到其他后端时，重发的文本同样计入 [`ProcessorConfig::char_budget`]。
+#[cfg(feature = "translation")]
+async fn request_translation(
+    service: &Arc<TranslationBackend>,
+    config: &ProcessorConfig,
+    text: &str,
+) -> TranslationResult<String> {
+    match &config.coalescing {
+        Some((cache_manager, source_lang, target_lang)) => {
+            let key = generate_cache_key(text, source_lang, target_lang);
+            let service = Arc::clone(service);
+            let budget = Arc::clone(&config.char_budget);
+            let text = text.to_string();
+            cache_manager
+                .coalesce(key, async move {
+                    service.translate_with_budget(&text, &budget).await
+                })
+                .await
+        }
+        None => {
+            service
+                .translate_with_budget(text, &config.char_budget)
+                .await
+        }
+    }
+}
+
+/// 配置的取消令牌是否已被取消
+#[cfg(feature = "translation")]
+fn is_cancelled(config: &ProcessorConfig) -> bool {
+    config
+        .cancellation
+        .as_ref()
+        .is_some_and(|token| token.is_cancelled())
+}
+
+/// 配置了多语言并发额度时，等待本次目标语言的许可
+#[cfg(feature = "translation")]
+async fn acquire_language_permit(
+    config: &ProcessorConfig,
+) -> TranslationResult<Option<LanguagePermit>> {
+    match &config.language_concurrency {
+        Some((concurrency, target_lang)) => concurrency.acquire(target_lang).await.map(Some),
+        None => Ok(None),
+    }
+}
+
+/// 解析索引标记格式的翻译结果
+/// 
+/// 按行匹配 `[索引] 翻译内容` 格式，返回索引到译文的映射。
+/// 格式不正确的行和空译文会被忽略。
+#[cfg(feature = "translation")]
+pub(crate) fn parse_indexed_translation(
+    translated: &str,
+) -> TranslationResult<HashMap<usize, String>> {
+    let index_regex = Regex::new(r"^\[(\d+)\]\s*(.*)$")
+        .map_err(|e| TranslationError::ParseError(format!("正则表达式编译失败: {}", e)))?;
+
+    let mut translations = HashMap::new();
+
+    for line in translated.lines() {
+        if let Some(captures) = index_regex.captures(line.trim()) {
+            if let (Some(index_str), Some(text)) = (captures.get(1), captures.get(2)) {
+                if let Ok(index) = index_str.as_str().parse::<usize>() {
+                    let translated_text = text.as_str().trim();
+                    if !translated_text.is_empty() {
+                        translations.insert(index, translated_text.to_string());
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(translations)
+}
+
+/// 将翻译结果写回文本项对应的DOM节点
+/// 
+/// 属性文本更新对应属性值，内容文本替换文本节点内容。
+/// 去重时合并的相同文本（[`TextItem::duplicates`]）写入同一译文。
+pub(crate) fn apply_translation(item: &TextItem, translated: &str) -> TranslationResult<()> {
+    for occurrence in item.occurrences() {
+        apply_translation_to(occurrence, translated)?;
+    }
+    Ok(())
+}
+
+fn apply_translation_to(item: &TextItem, translated: &str) -> TranslationResult<()> {
+    if translated.is_empty() {
+
+        return Err(TranslationError::InvalidInput("翻译结果为空".to_string()));
+    }
+
+    // 检查翻译质量
+    if translated == item.text.trim() && item.text.len() > 5 {
+        tracing::debug!("翻译结果与原文相同，可能是专有名词: '{}'", translated);
+    }
+
+    // 应用到DOM
+    if let TextType::JsonValue(path) = &item.text_type {
+        // JSON 数据块：只替换路径处的字符串
+        if let NodeData::Text { ref contents } = item.node.data {
+            let replaced = replace_json_string(&contents.borrow(), path, translated);
+            match replaced {
+                Some(json) => {
+                    let mut content_ref = contents.borrow_mut();
+                    content_ref.clear();
+                    content_ref.push_slice(&json);
+                }
+                None => {
+                    return Err(TranslationError::InternalError(format!(
+                        "JSON 数据中没有字符串 {}",
+                        path
+                    )))
+                }
+            }
+        } else {
+            return Err(TranslationError::InternalError(
+                "节点不是文本类型".to_string(),
+            ));
+        }
+    } else if let Some(attr_name) = &item.attr_name {
+        // 属性翻译
+        set_node_attr(&item.node, attr_name, Some(translated.to_string()));
+    } else {
+        // 文本内容翻译
+        if let NodeData::Text { ref contents } = item.node.data {
+            let mut content_ref = contents.borrow_mut();
+            content_ref.clear();
+            content_ref.push_slice(translated);
+        } else {
+            return Err(TranslationError::InternalError(
+                "节点不是文本类型".to_string(),
+            ));
+        }
+    }
+
+    Ok(())
 }
 
 /// 处理器配置
@@ -839,6 +1085,46 @@ pub struct ProcessorConfig {
     /// 每个批次处理的最大允许时间。
     /// 超过此时间的批次会被标记为超时并可能触发重试。
     pub batch_timeout: Duration,
+
+    /// 尽力而为模式
+    /// 
+    /// 为true时，重试后仍失败的批次不会使整体处理失败：
+    /// 成功批次的译文照常写回，失败批次中的文本保持原文，并记录在统计信息中。
+    pub best_effort: bool,
+
+    /// 字符预算
+    /// 
+    /// 每次向翻译服务发送请求前扣除请求文本的字符数，重试和回退请求同样计入。
+    /// 配置的副本（包括并发批次和重试使用的临时处理器）共享同一个计数器。
+    pub char_budget: Arc<CharBudget>,
+
+    /// 译文写回DOM后的回调
+    /// 
+    /// 用于在整页翻译完成前逐步推送已完成的译文。
+    pub progress_callback: Option<ProgressCallback>,
+
+    /// 译文校验器
+    /// 
+    /// 设置后每条译文写回前都要通过校验，被拒绝的文本保持原文，计入 `rejected_items`
+    /// 和失败文本统计。
+    pub verifier: Option<TranslationVerifier>,
+
+    /// 多语言共享的并发额度及本次翻译的目标语言
+    /// 
+    /// 设置后每个批次在本地并发限制之外，还需取得该语言的额度和全局许可，
+    /// 同时翻译多种语言的服务实例因此共享同一个请求上限。
+    pub language_concurrency: Option<(LanguageConcurrency, String)>,
+
+    /// 合并相同请求所用的缓存管理器及本次翻译的源语言、目标语言
+    /// 
+    /// 设置后，共享该缓存管理器的处理器同时发送相同的文本时只请求一次翻译服务。
+    pub coalescing: Option<(CacheManager, String, String)>,
+
+    /// 取消令牌
+    /// 
+    /// 令牌被取消后不再发送剩余的批次，已写回的译文保留，处理结果为 `Cancelled` 错误。
+    /// 已经发出的请求不会被中断。
+    pub cancellation: Option<CancellationToken>,
 }
 
 impl Default for ProcessorConfig {
@@ -855,6 +1141,13 @@ impl Default for ProcessorConfig {
     /// - `enable_concurrent_processing`: true - 启用并发处理
     /// - `max_concurrent_batches`: 5 - 最多5个并发批次
     /// - `batch_timeout`: 30s - 批次超时时间
+    /// - `best_effort`: false - 任一批次失败即返回错误
+    /// - `char_budget`: 不限制字符数
+    /// - `progress_callback`: 无
+    /// - `verifier`: 无，不校验译文
+    /// - `language_concurrency`: 无
+    /// - `coalescing`: 无
+    /// - `cancellation`: 无，不可取消
     fn default() -> Self {
         Self {
             small_batch_threshold: 2,
@@ -866,6 +1159,122 @@ impl Default for ProcessorConfig {
             enable_concurrent_processing: true,
             max_concurrent_batches: 5,
             batch_timeout: Duration::from_secs(30),
+            best_effort: false,
+            char_budget: Arc::new(CharBudget::new(None)),
+            progress_callback: None,
+            verifier: None,
+            language_concurrency: None,
+            coalescing: None,
+            cancellation: None,
+        }
+    }
+}
+
+/// 译文写回回调
+/// 
+/// 参数为已写回的文本项和写入的译文。去重合并的每一处都单独通知，
+/// 重试的批次可能对同一文本项多次调用。
+#[derive(Clone)]
+pub struct ProgressCallback(Arc<ProgressFn>);
+
+type ProgressFn = dyn Fn(&TextItem, &str) + Send + Sync;
+
+impl ProgressCallback {
+    /// 包装回调函数
+    pub fn new<F>(callback: F) -> Self
+    where
+        F: Fn(&TextItem, &str) + Send + Sync + 'static,
+    {
+        Self(Arc::new(callback))
+    }
+
+    /// 通知一个文本项已写回译文
+    pub fn notify(&self, item: &TextItem, translated: &str) {
+        (self.0)(item, translated)
+    }
+}
+
+impl std::fmt::Debug for ProgressCallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ProgressCallback")
+    }
+}
+
+/// 翻译字符预算
+/// 
+/// 记录已发送给翻译服务的字符数。某次请求会超出上限时拒绝该请求，
+/// 并且之后的请求一律拒绝，避免预算耗尽后仍有较短的文本零星发送出去。
+#[derive(Debug, Default)]
+pub struct CharBudget {
+    limit: Option<usize>,
+    consumed: AtomicUsize,
+    exhausted: AtomicBool,
+}
+
+impl CharBudget {
+    /// 创建字符预算，`None` 表示不限制
+    pub fn new(limit: Option<usize>) -> Self {
+        Self {
+            limit,
+            ..Self::default()
+        }
+    }
+
+    /// 为即将发送的文本扣除预算
+    /// 
+    /// 超出上限时不扣除，返回 `BudgetExceeded` 错误。
+    pub fn charge(&self, text: &str) -> TranslationResult<()> {
+        let chars = text.chars().count();
+        let limit = match self.limit {
+            Some(limit) => limit,
+            None => {
+                self.consumed.fetch_add(chars, Ordering::Relaxed);
+                return Ok(());
+            }
+        };
+
+        let mut consumed = self.consumed.load(Ordering::Relaxed);
+        loop {
+            if self.exhausted.load(Ordering::Relaxed) || consumed + chars > limit {
+                self.exhausted.store(true, Ordering::Relaxed);
+                return Err(TranslationError::BudgetExceeded { limit, consumed });
+            }
+            match self.consumed.compare_exchange_weak(
+                consumed,
+                consumed + chars,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return Ok(()),
+                Err(current) => consumed = current,
+            }
+        }
+    }
+
+    /// 已发送的字符数
+    pub fn consumed(&self) -> usize {
+        self.consumed.load(Ordering::Relaxed)
+    }
+
+    /// 是否已有请求因超出预算被拒绝
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted.load(Ordering::Relaxed)
+    }
+
+    /// 开始新的文档翻译时清零
+    pub fn reset(&self) {
+        self.consumed.store(0, Ordering::Relaxed);
+        self.exhausted.store(false, Ordering::Relaxed);
+    }
+
+    /// 预算耗尽时对应的错误
+    fn exceeded_error(&self) -> Option<TranslationError> {
+        match self.limit {
+            Some(limit) if self.is_exhausted() => Some(TranslationError::BudgetExceeded {
+                limit,
+                consumed: self.consumed(),
+            }),
+            _ => None,
         }
     }
 }
@@ -912,6 +1321,16 @@ pub struct ProcessorStats {
     /// 
     /// 由于错误或其他原因未能翻译的文本项数量。
     pub skipped_items: usize,
+
+    /// 未能翻译的文本样本
+    /// 
+    /// 最多保留 [`FAILED_SAMPLE_LIMIT`] 条，用于排查失败原因。
+    pub failed_samples: Vec<String>,
+
+    /// 未通过校验的译文数量
+    /// 
+    /// 这些文本保持原文，同时计入 `skipped_items`。
+    pub rejected_items: usize,
     
     /// 索引批处理成功次数
     /// 
@@ -937,6 +1356,14 @@ impl ProcessorStats {
         *self = Default::default();
     }
 
+    /// 记录一个失败批次中未翻译的文本项
+    pub fn record_failed_items(&mut self, count: usize, samples: Vec<String>) {
+        self.skipped_items += count;
+        let remaining = FAILED_SAMPLE_LIMIT.saturating_sub(self.failed_samples.len());
+        self.failed_samples
+            .extend(samples.into_iter().take(remaining));
+    }
+
     /// 计算索引批处理成功率
     /// 
     /// 返回索引批处理的成功率（0.0-1.0）。
@@ -959,6 +1386,17 @@ impl ProcessorStats {
     }
 }
 
+/// 取批次开头的若干文本作为失败样本
+#[cfg(feature = "translation")]
+fn failed_samples(batch: &Batch) -> Vec<String> {
+    batch
+        .items
+        .iter()
+        .take(FAILED_SAMPLE_LIMIT)
+        .map(|item| item.text.clone())
+        .collect()
+}
+
 /// 非翻译功能的占位实现
 /// 
 /// 当未启用 "translation" 功能时的简化实现。
@@ -991,3 +1429,312 @@ impl TranslationProcessor {
         Err(TranslationError::new("Translation feature not enabled"))
     }
 }
+
+#[cfg(all(test, feature = "translation"))]
+mod tests {
+    use super::*;
+    use crate::translation::config::{RequestTemplate, TranslationConfig};
+    use crate::translation::test_backend::TestBackend;
+    use markup5ever_rcdom::{Handle, Node};
+    use std::cell::RefCell;
+
+    /// 启动一个翻译接口：请求中含有 `broken` 时返回 500，否则返回固定译文
+    fn start_flaky_backend() -> String {
+        TestBackend::start(|body| {
+            if body.contains("broken") {
+                ("500 Internal Server Error", "{}".to_string())
+            } else {
+                ("200 OK", r#"{"translatedText": "译文"}"#.to_string())
+            }
+        })
+        .url
+    }
+
+    fn text_node(text: &str) -> Handle {
+        Node::new(NodeData::Text {
+            contents: RefCell::new(text.into()),
+        })
+    }
+
+    fn node_text(node: &Handle) -> String {
+        match node.data {
+            NodeData::Text { ref contents } => contents.borrow().to_string(),
+            _ => String::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_best_effort_keeps_successful_batches() {
+        let config = TranslationConfig {
+            api_url: start_flaky_backend(),
+            request_template: Some(RequestTemplate {
+                body: r#"{"q": "{{text}}"}"#.to_string(),
+                response_path: "translatedText".to_string(),
+                headers: Default::default(),
+            }),
+            ..TranslationConfig::default_with_lang("zh", None)
+        };
+        let backend = Arc::new(TranslationBackend::from_config(&config));
+
+        for (best_effort, concurrent) in
+            [(true, true), (true, false), (false, true), (false, false)]
+        {
+            let nodes: Vec<Handle> = ["First paragraph", "broken paragraph", "Last paragraph"]
+                .iter()
+                .map(|text| text_node(text))
+                .collect();
+            let batches = nodes
+                .iter()
+                .enumerate()
+                .map(|(i, node)| {
+                    let item = TextItem::content(node_text(node), node.clone(), 0);
+                    Batch::new(i, vec![item], BatchType::Single)
+                })
+                .collect();
+
+            let mut processor = TranslationProcessor::new(
+                Arc::clone(&backend),
+                ProcessorConfig {
+                    enable_retry: false,
+                    enable_concurrent_processing: concurrent,
+                    batch_delay: Duration::from_millis(0),
+                    best_effort,
+                    ..ProcessorConfig::default()
+                },
+            );
+            let result = processor.process_batches(batches).await;
+
+            assert_eq!(result.is_ok(), best_effort, "concurrent: {}", concurrent);
+            assert_eq!(node_text(&nodes[0]), "译文");
+            assert_eq!(node_text(&nodes[1]), "broken paragraph");
+            assert_eq!(node_text(&nodes[2]), "译文");
+
+            let stats = processor.get_stats();
+            assert_eq!(stats.failed_batches, 1);
+            assert_eq!(stats.skipped_items, 1);
+            assert_eq!(stats.failed_samples, vec!["broken paragraph".to_string()]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_configured_attributes_are_written_back() {
+        use crate::html::{get_node_attr, html_to_dom};
+        use crate::translation::collector::{CollectorConfig, TextCollector};
+
+        let config = TranslationConfig {
+            api_url: start_flaky_backend(),
+            request_template: Some(RequestTemplate {
+                body: r#"{"q": "{{text}}"}"#.to_string(),
+                response_path: "translatedText".to_string(),
+                headers: Default::default(),
+            }),
+            translate_attributes: vec!["data-label".to_string()],
+            ..TranslationConfig::default_with_lang("zh", None)
+        };
+        let dom = html_to_dom(
+            b"<body><div data-label=\"Subscribe to the newsletter\" data-id=\"signup form\"></div></body>",
+            "utf-8".to_string(),
+        );
+
+        let items = TextCollector::new(CollectorConfig::from(&config))
+            .collect_translatable_texts(&dom.document)
+            .unwrap();
+        assert_eq!(items.len(), 1);
+        let widget = items[0].node.clone();
+        let batches = items
+            .into_iter()
+            .enumerate()
+            .map(|(i, item)| Batch::new(i, vec![item], BatchType::Single))
+            .collect();
+
+        let mut processor = TranslationProcessor::new(
+            Arc::new(TranslationBackend::from_config(&config)),
+            ProcessorConfig {
+                enable_concurrent_processing: false,
+                batch_delay: Duration::from_millis(0),
+                ..ProcessorConfig::default()
+            },
+        );
+        processor.process_batches(batches).await.unwrap();
+
+        assert_eq!(
+            get_node_attr(&widget, "data-label").as_deref(),
+            Some("译文")
+        );
+        assert_eq!(
+            get_node_attr(&widget, "data-id").as_deref(),
+            Some("signup form")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_char_budget_stops_sending() {
+        let config = TranslationConfig {
+            api_url: start_flaky_backend(),
+            request_template: Some(RequestTemplate {
+                body: r#"{"q": "{{text}}"}"#.to_string(),
+                response_path: "translatedText".to_string(),
+                headers: Default::default(),
+            }),
+            ..TranslationConfig::default_with_lang("zh", None)
+        };
+        let backend = Arc::new(TranslationBackend::from_config(&config));
+
+        // 前两段共 31 个字符，第三段会超出 40 字符的预算
+        let nodes: Vec<Handle> = ["First paragraph", "Second paragraph", "Last paragraph"]
+            .iter()
+            .map(|text| text_node(text))
+            .collect();
+        let batches = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| {
+                let item = TextItem::content(node_text(node), node.clone(), 0);
+                Batch::new(i, vec![item], BatchType::Single)
+            })
+            .collect();
+
+        let budget = Arc::new(CharBudget::new(Some(40)));
+        let mut processor = TranslationProcessor::new(
+            Arc::clone(&backend),
+            ProcessorConfig {
+                enable_concurrent_processing: false,
+                batch_delay: Duration::from_millis(0),
+                best_effort: true,
+                char_budget: Arc::clone(&budget),
+                ..ProcessorConfig::default()
+            },
+        );
+        let result = processor.process_batches(batches).await;
+
+        assert!(matches!(
+            result,
+            Err(TranslationError::BudgetExceeded {
+                limit: 40,
+                consumed: 31
+            })
+        ));
+        assert_eq!(node_text(&nodes[0]), "译文");
+        assert_eq!(node_text(&nodes[1]), "译文");
+        assert_eq!(node_text(&nodes[2]), "Last paragraph");
+        assert_eq!(budget.consumed(), 31);
+        assert_eq!(processor.get_stats().skipped_items, 1);
+
+        // 重试的请求同样计入预算（重试只在并发处理多个批次时进行）
+        let batches = ["broken text", "Fine"]
+            .iter()
+            .enumerate()
+            .map(|(i, text)| {
+                let item = TextItem::content(text.to_string(), text_node(text), 0);
+                Batch::new(i, vec![item], BatchType::Single)
+            })
+            .collect();
+        let budget = Arc::new(CharBudget::new(Some(100)));
+        let mut processor = TranslationProcessor::new(
+            backend,
+            ProcessorConfig {
+                max_retries: 2,
+                retry_delay: Duration::from_millis(0),
+                best_effort: true,
+                char_budget: Arc::clone(&budget),
+                ..ProcessorConfig::default()
+            },
+        );
+        processor.process_batches(batches).await.unwrap();
+        assert_eq!(budget.consumed(), 3 * 11 + 4);
+    }
+
+    #[tokio::test]
+    async fn test_progress_callback_reports_written_translations() {
+        let config = TranslationConfig {
+            api_url: start_flaky_backend(),
+            request_template: Some(RequestTemplate {
+                body: r#"{"q": "{{text}}"}"#.to_string(),
+                response_path: "translatedText".to_string(),
+                headers: Default::default(),
+            }),
+            ..TranslationConfig::default_with_lang("zh", None)
+        };
+
+        let reported = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = Arc::clone(&reported);
+        let mut processor = TranslationProcessor::new(
+            Arc::new(TranslationBackend::from_config(&config)),
+            ProcessorConfig {
+                enable_retry: false,
+                enable_concurrent_processing: false,
+                batch_delay: Duration::from_millis(0),
+                best_effort: true,
+                progress_callback: Some(ProgressCallback::new(move |item, translated| {
+                    sink.lock()
+                        .unwrap()
+                        .push((item.text.clone(), translated.to_string()));
+                })),
+                ..ProcessorConfig::default()
+            },
+        );
+
+        let batches = ["First paragraph", "broken paragraph"]
+            .iter()
+            .enumerate()
+            .map(|(i, text)| {
+                let item = TextItem::content(text.to_string(), text_node(text), 0);
+                Batch::new(i, vec![item], BatchType::Single)
+            })
+            .collect();
+        processor.process_batches(batches).await.unwrap();
+
+        assert_eq!(
+            *reported.lock().unwrap(),
+            vec![("First paragraph".to_string(), "译文".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cancellation_skips_remaining_batches() {
+        let config = TranslationConfig {
+            api_url: start_flaky_backend(),
+            request_template: Some(RequestTemplate {
+                body: r#"{"q": "{{text}}"}"#.to_string(),
+                response_path: "translatedText".to_string(),
+                headers: Default::default(),
+            }),
+            ..TranslationConfig::default_with_lang("zh", None)
+        };
+
+        // 第一段写回后取消，相当于客户端在翻译途中断开
+        let token = CancellationToken::new();
+        let trigger = token.clone();
+        let mut processor = TranslationProcessor::new(
+            Arc::new(TranslationBackend::from_config(&config)),
+            ProcessorConfig {
+                enable_concurrent_processing: false,
+                batch_delay: Duration::from_millis(0),
+                best_effort: true,
+                cancellation: Some(token),
+                progress_callback: Some(ProgressCallback::new(move |_, _| trigger.cancel())),
+                ..ProcessorConfig::default()
+            },
+        );
+
+        let nodes: Vec<Handle> = ["First paragraph", "Second paragraph", "Last paragraph"]
+            .iter()
+            .map(|text| text_node(text))
+            .collect();
+        let batches = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| {
+                let item = TextItem::content(node_text(node), node.clone(), 0);
+                Batch::new(i, vec![item], BatchType::Single)
+            })
+            .collect();
+        let result = processor.process_batches(batches).await;
+
+        assert!(matches!(result, Err(TranslationError::Cancelled)));
+        assert_eq!(node_text(&nodes[0]), "译文");
+        assert_eq!(node_text(&nodes[1]), "Second paragraph");
+        assert_eq!(node_text(&nodes[2]), "Last paragraph");
+        assert_eq!(processor.get_stats().skipped_items, 2);
+    }
+}
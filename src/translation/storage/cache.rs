@@ -1,16 +1,43 @@
 //! 翻译缓存模块（简化版）
 //!
 //! 提供简单高效的翻译结果缓存功能。
+//!
+//! 除机器翻译结果外，缓存还保存人工提交的译文修正。修正按（原文, 目标语言）存放在
+//! 独立的表中，查询时优先于机器翻译结果，不会过期，也不会被 LRU 驱逐或 `clear` 清除。
+//!
+//! [`CacheManager::coalesce`] 合并同时发出的相同翻译请求：同一个键的请求在第一个请求完成前
+//! 共享同一个 future，翻译服务只收到一次请求。
+//!
+//! 不同网站的内容更新频率不同。[`CacheConfig::domain_ttls`] 按主机名覆盖默认的有效期，
+//! 通过 `insert_for_url` 写入的条目在写入时按来源页面的主机名确定有效期，之后配置的变化
+//! 不影响已有的条目。通过 `lookup_for_url` 查询时，命中和未命中同时按主机名计入
+//! [`CacheStats::domains`]。
 
 use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
+use std::future::Future;
+use std::sync::{Arc, Mutex, RwLock};
 use std::time::{Duration, Instant};
 
+use futures::future::{BoxFuture, FutureExt, Shared};
+
+use crate::translation::error::TranslationResult;
+use crate::utils::url::Url;
+
 
 // ============================================================================
 // 核心类型
 // ============================================================================
 
+/// 译文来源
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EntrySource {
+    /// 机器翻译结果
+    #[default]
+    Machine,
+    /// 人工修正
+    Human,
+}
+
 /// 缓存条目
 #[derive(Debug, Clone)]
 pub struct CacheEntry {
@@ -18,9 +45,14 @@ pub struct CacheEntry {
     pub translated_text: String,
     pub source_lang: String,
     pub target_lang: String,
+    pub source: EntrySource,
     pub created_at: Instant,
     pub access_count: u64,
     pub last_accessed: Instant,
+    /// 来源页面的主机名
+    pub domain: Option<String>,
+    /// 写入时按主机名确定的有效期，`None` 时使用缓存的默认有效期
+    pub ttl: Option<Duration>,
 }
 
 /// 缓存配置
@@ -32,6 +64,8 @@ pub struct CacheConfig {
     pub local_cache_size: usize,
     pub default_ttl: Duration,
     pub enable_warmup: bool,
+    /// 按主机名覆盖的有效期，同样适用于其子域名，最具体的主机名优先
+    pub domain_ttls: HashMap<String, Duration>,
 }
 
 impl Default for CacheConfig {
@@ -43,10 +77,18 @@ impl Default for CacheConfig {
             local_cache_size: 1000,
             default_ttl: Duration::from_secs(3600),
             enable_warmup: false,
+            domain_ttls: HashMap::new(),
         }
     }
 }
 
+impl CacheConfig {
+    /// 来自 `host` 的条目的有效期
+    pub fn ttl_for_host(&self, host: &str) -> Duration {
+        domain_ttl(&self.domain_ttls, host).unwrap_or(self.default_ttl)
+    }
+}
+
 /// 缓存统计信息
 #[derive(Debug, Default, Clone)]
 pub struct CacheStats {
@@ -55,18 +97,41 @@ pub struct CacheStats {
     pub cache_misses: u64,
     pub total_entries: usize,
     pub evictions: u64,
+    /// 按主机名统计的查询，只包含通过 `lookup_for_url` 的查询
+    pub domains: HashMap<String, DomainCacheStats>,
+}
+
+/// 单个主机名的缓存查询统计
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DomainCacheStats {
+    pub requests: u64,
+    pub hits: u64,
+    pub misses: u64,
 }
 
 /// 缓存管理器
+///
+/// 克隆得到的实例共享同一份缓存数据。
+#[derive(Clone)]
 pub struct CacheManager {
     cache: TranslationCache,
+    /// 正在进行的翻译请求，键由 [`generate_cache_key`] 生成
+    in_flight: Arc<Mutex<HashMap<String, InFlightTranslation>>>,
 }
 
+type InFlightTranslation = Shared<BoxFuture<'static, TranslationResult<String>>>;
+
 /// 翻译缓存
+///
+/// 克隆得到的实例共享同一份缓存数据。
+#[derive(Clone)]
 pub struct TranslationCache {
     entries: Arc<RwLock<HashMap<String, CacheEntry>>>,
+    /// 人工修正，键由 [`correction_key`] 生成
+    corrections: Arc<RwLock<HashMap<String, CacheEntry>>>,
     max_size: usize,
     ttl: Duration,
+    domain_ttls: HashMap<String, Duration>,
     stats: Arc<RwLock<CacheStats>>,
 }
 
@@ -88,21 +153,42 @@ impl CacheEntry {
             translated_text,
             source_lang,
             target_lang,
+            source: EntrySource::Machine,
             created_at: now,
             access_count: 0,
             last_accessed: now,
+            domain: None,
+            ttl: None,
+        }
+    }
+
+    /// 创建人工修正条目
+    pub fn human(original_text: String, corrected_text: String, target_lang: String) -> Self {
+        Self {
+            source: EntrySource::Human,
+            ..Self::new(original_text, corrected_text, "auto".to_string(), target_lang)
         }
     }
 
+    /// 是否为人工修正
+    pub fn is_human(&self) -> bool {
+        self.source == EntrySource::Human
+    }
+
     /// 更新访问信息
     pub fn access(&mut self) {
         self.access_count += 1;
         self.last_accessed = Instant::now();
     }
 
-    /// 检查条目是否过期
+    /// 检查条目是否过期（人工修正永不过期），`ttl` 为缓存的默认有效期
     pub fn is_expired(&self, ttl: Duration) -> bool {
-        self.created_at.elapsed() > ttl
+        !self.is_human() && self.created_at.elapsed() > self.effective_ttl(ttl)
+    }
+
+    /// 条目实际使用的有效期
+    pub fn effective_ttl(&self, default_ttl: Duration) -> Duration {
+        self.ttl.unwrap_or(default_ttl)
     }
 
     /// 生成缓存键
@@ -122,8 +208,48 @@ impl CacheManager {
         let ttl = config.default_ttl;
         
         Self {
-            cache: TranslationCache::with_config(max_size, ttl),
+            cache: TranslationCache::with_config(max_size, ttl)
+                .with_domain_ttls(config.domain_ttls),
+            in_flight: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// 合并相同的并发翻译请求
+    ///
+    /// 没有相同 `key` 的请求正在进行时执行 `translate`，否则等待正在进行的请求并共享其结果
+    /// （包括错误）。请求完成后即从表中移除，之后相同的请求会重新执行。
+    pub async fn coalesce<F>(&self, key: String, translate: F) -> TranslationResult<String>
+    where
+        F: Future<Output = TranslationResult<String>> + Send + 'static,
+    {
+        let future = {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            match in_flight.get(&key) {
+                Some(future) => future.clone(),
+                None => {
+                    let future = translate.boxed().shared();
+                    in_flight.insert(key.clone(), future.clone());
+                    future
+                }
+            }
+        };
+
+        let result = future.clone().await;
+
+        // 完成的请求可能已被移除并由新的请求替换
+        let mut in_flight = self.in_flight.lock().unwrap();
+        if in_flight
+            .get(&key)
+            .is_some_and(|current| current.ptr_eq(&future))
+        {
+            in_flight.remove(&key);
         }
+        result
+    }
+
+    /// 正在进行的翻译请求数
+    pub fn in_flight_count(&self) -> usize {
+        self.in_flight.lock().unwrap().len()
     }
 
     /// 获取缓存条目
@@ -147,11 +273,54 @@ impl CacheManager {
         self.cache.insert_with_lang(original, translated, source_lang, target_lang);
     }
 
+    /// 插入来自 `source_url` 页面的缓存条目，有效期按其主机名确定
+    pub fn insert_for_url(
+        &self,
+        original: String,
+        translated: String,
+        source_lang: String,
+        target_lang: String,
+        source_url: &Url,
+    ) {
+        self.cache
+            .insert_for_url(original, translated, source_lang, target_lang, source_url);
+    }
+
+    /// 写入人工修正
+    pub fn insert_correction(&self, original: String, corrected: String, target_lang: String) {
+        self.cache.insert_correction(original, corrected, target_lang);
+    }
+
+    /// 查询指定目标语言的译文，人工修正优先
+    pub fn lookup(&self, text: &str, target_lang: &str) -> Option<CacheEntry> {
+        self.cache.lookup(text, target_lang)
+    }
+
+    /// 查询 `source_url` 页面中的文本，并按其主机名记录命中情况
+    pub fn lookup_for_url(
+        &self,
+        text: &str,
+        target_lang: &str,
+        source_url: &Url,
+    ) -> Option<CacheEntry> {
+        self.cache.lookup_for_url(text, target_lang, source_url)
+    }
+
+    /// 获取所有人工修正
+    pub fn corrections(&self) -> Vec<CacheEntry> {
+        self.cache.corrections()
+    }
+
     /// 获取统计信息
     pub fn get_stats(&self) -> CacheStats {
         self.cache.get_stats()
     }
 
+    /// 重置统计信息
+    pub fn reset_stats(&self) {
+        self.cache.reset_stats();
+    }
+
     /// 清空缓存
     pub fn clear(&self) {
         self.cache.clear();
@@ -163,6 +332,15 @@ impl CacheManager {
     }
 }
 
+impl std::fmt::Debug for CacheManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CacheManager")
+            .field("entries", &self.cache.size())
+            .field("in_flight", &self.in_flight_count())
+            .finish()
+    }
+}
+
 impl TranslationCache {
     /// 创建新的翻译缓存
     pub fn new() -> Self {
@@ -173,12 +351,20 @@ impl TranslationCache {
     pub fn with_config(max_size: usize, ttl: Duration) -> Self {
         Self {
             entries: Arc::new(RwLock::new(HashMap::new())),
+            corrections: Arc::new(RwLock::new(HashMap::new())),
             max_size,
             ttl,
+            domain_ttls: HashMap::new(),
             stats: Arc::new(RwLock::new(CacheStats::default())),
         }
     }
 
+    /// 按主机名覆盖有效期，见 [`CacheConfig::domain_ttls`]
+    pub fn with_domain_ttls(mut self, domain_ttls: HashMap<String, Duration>) -> Self {
+        self.domain_ttls = domain_ttls;
+        self
+    }
+
     /// 获取缓存条目
     pub fn get(&self, text: &str) -> Option<String> {
         let mut stats = self.stats.write().unwrap();
@@ -214,6 +400,37 @@ impl TranslationCache {
         source_lang: String,
         target_lang: String,
     ) {
+        self.insert_entry(CacheEntry::new(
+            original,
+            translated,
+            source_lang,
+            target_lang,
+        ));
+    }
+
+    /// 插入来自 `source_url` 页面的缓存条目
+    ///
+    /// 有效期在写入时按主机名确定，没有匹配的覆盖项时使用默认有效期。
+    pub fn insert_for_url(
+        &self,
+        original: String,
+        translated: String,
+        source_lang: String,
+        target_lang: String,
+        source_url: &Url,
+    ) {
+        let domain = source_url.host_str().map(str::to_lowercase);
+        let ttl = domain
+            .as_deref()
+            .and_then(|host| domain_ttl(&self.domain_ttls, host));
+        self.insert_entry(CacheEntry {
+            domain,
+            ttl,
+            ..CacheEntry::new(original, translated, source_lang, target_lang)
+        });
+    }
+
+    fn insert_entry(&self, entry: CacheEntry) {
         let mut entries = self.entries.write().unwrap();
         
         // 如果达到最大容量，先清理
@@ -221,15 +438,75 @@ impl TranslationCache {
             self.evict_lru(&mut entries);
         }
 
-        let entry = CacheEntry::new(original.clone(), translated, source_lang, target_lang);
-        entries.insert(original, entry);
+        entries.insert(entry.original_text.clone(), entry);
 
         // 更新统计
         let mut stats = self.stats.write().unwrap();
         stats.total_entries = entries.len();
     }
 
-    /// 清空缓存
+    /// 写入人工修正，覆盖同一原文和目标语言之前的修正
+    pub fn insert_correction(&self, original: String, corrected: String, target_lang: String) {
+        let key = correction_key(&original, &target_lang);
+        let entry = CacheEntry::human(original, corrected, target_lang);
+        self.corrections.write().unwrap().insert(key, entry);
+    }
+
+    /// 查询指定目标语言的译文
+    ///
+    /// 先查人工修正（原文按空白折叠后比较），再查目标语言一致的机器翻译结果。
+    pub fn lookup(&self, text: &str, target_lang: &str) -> Option<CacheEntry> {
+        let correction = self
+            .corrections
+            .read()
+            .unwrap()
+            .get(&correction_key(text, target_lang))
+            .cloned();
+        if let Some(entry) = correction {
+            let mut stats = self.stats.write().unwrap();
+            stats.total_requests += 1;
+            stats.cache_hits += 1;
+            return Some(entry);
+        }
+
+        let translated = self.get(text)?;
+        let entries = self.entries.read().unwrap();
+        entries
+            .get(text)
+            .filter(|entry| entry.target_lang == target_lang)
+            .map(|entry| CacheEntry {
+                translated_text: translated,
+                ..entry.clone()
+            })
+    }
+
+    /// 查询 `source_url` 页面中的文本，命中情况同时计入该主机名的统计
+    pub fn lookup_for_url(
+        &self,
+        text: &str,
+        target_lang: &str,
+        source_url: &Url,
+    ) -> Option<CacheEntry> {
+        let entry = self.lookup(text, target_lang);
+        if let Some(host) = source_url.host_str() {
+            let mut stats = self.stats.write().unwrap();
+            let domain = stats.domains.entry(host.to_lowercase()).or_default();
+            domain.requests += 1;
+            if entry.is_some() {
+                domain.hits += 1;
+            } else {
+                domain.misses += 1;
+            }
+        }
+        entry
+    }
+
+    /// 获取所有人工修正
+    pub fn corrections(&self) -> Vec<CacheEntry> {
+        self.corrections.read().unwrap().values().cloned().collect()
+    }
+
+    /// 清空缓存（人工修正保留）
     pub fn clear(&self) {
         let mut entries = self.entries.write().unwrap();
         entries.clear();
@@ -363,6 +640,29 @@ pub fn generate_cache_key(text: &str, source_lang: &str, target_lang: &str) -> S
     format!("{}:{}:{}", source_lang, target_lang, text)
 }
 
+/// 查找主机名的有效期覆盖项，依次尝试 `host` 及其各级父域名
+fn domain_ttl(domain_ttls: &HashMap<String, Duration>, host: &str) -> Option<Duration> {
+    if domain_ttls.is_empty() {
+        return None;
+    }
+    let host = host.trim_end_matches('.').to_lowercase();
+    let mut candidate = host.as_str();
+    loop {
+        if let Some(ttl) = domain_ttls.get(candidate) {
+            return Some(*ttl);
+        }
+        candidate = candidate.split_once('.')?.1;
+    }
+}
+
+/// 生成人工修正的键
+///
+/// 原文中的连续空白折叠为一个空格，使缩进不同的同一段文本命中同一条修正。
+pub fn correction_key(text: &str, target_lang: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    format!("{}:{}", target_lang.to_lowercase(), text)
+}
+
 impl CacheStats {
     /// 计算缓存命中率
     pub fn hit_rate(&self) -> f64 {
@@ -373,6 +673,11 @@ impl CacheStats {
         }
     }
 
+    /// 指定主机名的缓存命中率，没有该主机名的查询时返回 `None`
+    pub fn domain_hit_rate(&self, host: &str) -> Option<f64> {
+        self.domains.get(host).map(DomainCacheStats::hit_rate)
+    }
+
     /// 计算缓存未命中率
     pub fn miss_rate(&self) -> f64 {
         1.0 - self.hit_rate()
@@ -389,13 +694,60 @@ impl CacheStats {
         self.cache_hits += other.cache_hits;
         self.cache_misses += other.cache_misses;
         self.evictions += other.evictions;
+        for (host, domain) in &other.domains {
+            let merged = self.domains.entry(host.clone()).or_default();
+            merged.requests += domain.requests;
+            merged.hits += domain.hits;
+            merged.misses += domain.misses;
+        }
         // total_entries使用当前值，因为它是快照
     }
 }
 
+impl DomainCacheStats {
+    /// 该主机名的缓存命中率
+    pub fn hit_rate(&self) -> f64 {
+        if self.requests == 0 {
+            0.0
+        } else {
+            self.hits as f64 / self.requests as f64
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn test_concurrent_requests_are_coalesced() {
+        let manager = CacheManager::new(CacheConfig::default());
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let translate = |calls: Arc<AtomicUsize>| async move {
+            calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            Ok("你好".to_string())
+        };
+        let key = generate_cache_key("Hello", "auto", "zh");
+        let (first, second) = tokio::join!(
+            manager.coalesce(key.clone(), translate(Arc::clone(&calls))),
+            manager.coalesce(key.clone(), translate(Arc::clone(&calls))),
+        );
+
+        assert_eq!(first.unwrap(), "你好");
+        assert_eq!(second.unwrap(), "你好");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(manager.in_flight_count(), 0);
+
+        // 完成之后的请求重新执行
+        manager
+            .coalesce(key, translate(Arc::clone(&calls)))
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
 
     #[test]
     fn test_cache_basic_operations() {
@@ -484,4 +836,88 @@ mod tests {
         assert_eq!(cache.get("2"), None); // 应该被驱逐
         assert_eq!(cache.get("3"), Some("三".to_string()));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_correction_takes_precedence() {
+        let cache = TranslationCache::with_config(1, Duration::from_millis(1));
+
+        cache.insert_with_lang(
+            "hello".to_string(),
+            "哈喽".to_string(),
+            "en".to_string(),
+            "zh".to_string(),
+        );
+        cache.insert_correction("hello".to_string(), "你好".to_string(), "zh".to_string());
+
+        let entry = cache.lookup("  hello\n", "zh").unwrap();
+        assert_eq!(entry.translated_text, "你好");
+        assert!(entry.is_human());
+        assert!(cache.lookup("hello", "ja").is_none());
+
+        // 人工修正不过期、不被驱逐，也不随缓存清空
+        std::thread::sleep(Duration::from_millis(10));
+        cache.insert("other".to_string(), "其他".to_string());
+        cache.clear();
+        assert_eq!(cache.lookup("hello", "zh").unwrap().translated_text, "你好");
+    }
+
+    #[test]
+    fn test_lookup_machine_entry_by_target_lang() {
+        let cache = TranslationCache::new();
+        cache.insert_with_lang(
+            "hello".to_string(),
+            "哈喽".to_string(),
+            "en".to_string(),
+            "zh".to_string(),
+        );
+
+        let entry = cache.lookup("hello", "zh").unwrap();
+        assert_eq!(entry.translated_text, "哈喽");
+        assert_eq!(entry.source, EntrySource::Machine);
+        assert!(cache.lookup("hello", "ja").is_none());
+    }
+
+    #[test]
+    fn test_domain_ttl_overrides() {
+        let config = CacheConfig {
+            domain_ttls: HashMap::from([
+                ("news.example.com".to_string(), Duration::from_millis(1)),
+                ("docs.example.com".to_string(), Duration::from_secs(86400)),
+            ]),
+            ..CacheConfig::default()
+        };
+        assert_eq!(
+            config.ttl_for_host("news.example.com"),
+            Duration::from_millis(1)
+        );
+        assert_eq!(
+            config.ttl_for_host("api.docs.example.com"),
+            Duration::from_secs(86400)
+        );
+        assert_eq!(config.ttl_for_host("example.com"), config.default_ttl);
+
+        let manager = CacheManager::new(config);
+        let news = Url::parse("https://news.example.com/today").unwrap();
+        let docs = Url::parse("https://docs.example.com/guide").unwrap();
+        for (text, url) in [("Breaking", &news), ("Install", &docs)] {
+            manager.insert_for_url(
+                text.to_string(),
+                "译文".to_string(),
+                "en".to_string(),
+                "zh".to_string(),
+                url,
+            );
+        }
+
+        std::thread::sleep(Duration::from_millis(10));
+        assert!(manager.lookup_for_url("Breaking", "zh", &news).is_none());
+        let entry = manager.lookup_for_url("Install", "zh", &docs).unwrap();
+        assert_eq!(entry.domain.as_deref(), Some("docs.example.com"));
+        assert_eq!(entry.ttl, Some(Duration::from_secs(86400)));
+
+        let stats = manager.get_stats();
+        assert_eq!(stats.domain_hit_rate("news.example.com"), Some(0.0));
+        assert_eq!(stats.domain_hit_rate("docs.example.com"), Some(1.0));
+        assert_eq!(stats.domain_hit_rate("example.com"), None);
+    }
+}
@@ -0,0 +1,337 @@
+//! 人工修正的持久化存储
+//!
+//! 用户提交的译文修正写入 redb 数据库，服务重启后通过 [`CorrectionStore::load_into`]
+//! 重新装入 [`CacheManager`]，使后续翻译继续使用人工修正。
+//!
+//! # 格式版本
+//!
+//! 每条记录以 JSON 保存，并带有写入时的格式版本 [`CACHE_SCHEMA_VERSION`]。之后给记录增加字段时
+//! 将版本加一，新字段必须带 `#[serde(default)]`，旧版本的记录读取时按默认值补齐。
+//!
+//! - 版本 1：表 `translation_corrections`，值只有修正后的译文。打开数据库时整表迁移到当前格式，
+//!   然后删除旧表；
+//! - 版本 2：表 `translation_corrections_v2`，值为 [`StoredCorrection`]。
+//!
+//! 无法解析或版本高于当前程序的记录（由更新的版本写入）读取时记录警告并跳过，不会导致整体加载失败。
+//!
+//! 数据库文件在保存第一条修正时才创建。
+
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use redb::{ReadableTable, TableDefinition, TableHandle};
+use serde::{Deserialize, Serialize};
+
+use crate::translation::error::{TranslationError, TranslationResult};
+use crate::translation::storage::cache::{CacheEntry, CacheManager};
+use crate::utils::storage::LazyDatabase;
+
+/// 当前写入的记录格式版本
+pub const CACHE_SCHEMA_VERSION: u32 = 2;
+
+/// 版本 1 的表：(目标语言, 原文) -> 修正后的译文
+const LEGACY_CORRECTIONS_TABLE: TableDefinition<(&str, &str), &str> =
+    TableDefinition::new("translation_corrections");
+
+/// (目标语言, 原文) -> JSON 格式的 [`StoredCorrection`]
+const CORRECTIONS_TABLE: TableDefinition<(&str, &str), &str> =
+    TableDefinition::new("translation_corrections_v2");
+
+/// 持久化的修正记录
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredCorrection {
+    /// 写入时的格式版本
+    pub version: u32,
+    /// 修正后的译文
+    pub translated_text: String,
+    /// 原文语言，版本 1 的记录没有保存，默认为 `auto`
+    #[serde(default = "default_source_lang")]
+    pub source_lang: String,
+    /// 保存时间（Unix 秒），版本 1 的记录没有保存
+    #[serde(default)]
+    pub saved_at: Option<u64>,
+}
+
+fn default_source_lang() -> String {
+    "auto".to_string()
+}
+
+impl StoredCorrection {
+    /// 以当前格式版本创建记录
+    pub fn new(translated_text: &str) -> Self {
+        Self {
+            version: CACHE_SCHEMA_VERSION,
+            translated_text: translated_text.to_string(),
+            source_lang: default_source_lang(),
+            saved_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .ok()
+                .map(|elapsed| elapsed.as_secs()),
+        }
+    }
+
+    /// 解析记录，缺少的字段按默认值补齐，版本高于当前程序时返回错误
+    pub fn decode(value: &str) -> Result<Self, String> {
+        let record: Self = serde_json::from_str(value).map_err(|e| e.to_string())?;
+        if record.version > CACHE_SCHEMA_VERSION {
+            return Err(format!(
+                "格式版本 {} 高于当前支持的版本 {}",
+                record.version, CACHE_SCHEMA_VERSION
+            ));
+        }
+        Ok(Self {
+            version: CACHE_SCHEMA_VERSION,
+            ..record
+        })
+    }
+
+    fn encode(&self) -> TranslationResult<String> {
+        serde_json::to_string(self).map_err(cache_error)
+    }
+}
+
+/// 人工修正存储
+pub struct CorrectionStore {
+    db: LazyDatabase,
+}
+
+impl CorrectionStore {
+    /// 打开修正数据库，文件不存在时等到保存第一条修正再创建
+    pub fn open(path: &Path) -> TranslationResult<Self> {
+        let db = LazyDatabase::open(path).map_err(cache_error)?;
+        let Some(existing) = db.get() else {
+            return Ok(Self { db });
+        };
+
+        let write_txn = existing.begin_write().map_err(cache_error)?;
+        {
+            let mut table = write_txn
+                .open_table(CORRECTIONS_TABLE)
+                .map_err(cache_error)?;
+            let migrated = migrate_legacy(&write_txn, &mut table)?;
+            if migrated > 0 {
+                tracing::info!("已将 {} 条版本 1 的人工修正迁移到当前格式", migrated);
+            }
+        }
+        write_txn.commit().map_err(cache_error)?;
+
+        Ok(Self { db })
+    }
+
+    /// 保存一条修正，覆盖同一原文和目标语言之前的修正
+    pub fn save(
+        &self,
+        original: &str,
+        corrected: &str,
+        target_lang: &str,
+    ) -> TranslationResult<()> {
+        let db = self.db.get_or_create().map_err(cache_error)?;
+        let write_txn = db.begin_write().map_err(cache_error)?;
+        {
+            let mut table = write_txn
+                .open_table(CORRECTIONS_TABLE)
+                .map_err(cache_error)?;
+            let record = StoredCorrection::new(corrected).encode()?;
+            table
+                .insert((target_lang, original), record.as_str())
+                .map_err(cache_error)?;
+        }
+        write_txn.commit().map_err(cache_error)
+    }
+
+    /// 读取所有修正，跳过无法解析的记录
+    pub fn all(&self) -> TranslationResult<Vec<CacheEntry>> {
+        let Some(db) = self.db.get() else {
+            return Ok(Vec::new());
+        };
+        let read_txn = db.begin_read().map_err(cache_error)?;
+        let table = read_txn
+            .open_table(CORRECTIONS_TABLE)
+            .map_err(cache_error)?;
+
+        let mut entries = Vec::new();
+        for item in table.iter().map_err(cache_error)? {
+            let (key, value) = item.map_err(cache_error)?;
+            let (target_lang, original) = key.value();
+            match StoredCorrection::decode(value.value()) {
+                Ok(record) => entries.push(CacheEntry {
+                    source_lang: record.source_lang,
+                    ..CacheEntry::human(
+                        original.to_string(),
+                        record.translated_text,
+                        target_lang.to_string(),
+                    )
+                }),
+                Err(e) => {
+                    tracing::warn!(
+                        "跳过无法读取的人工修正 ({}, {}): {}",
+                        target_lang,
+                        original,
+                        e
+                    )
+                }
+            }
+        }
+        Ok(entries)
+    }
+
+    /// 将所有修正装入缓存，返回装入的条数
+    pub fn load_into(&self, cache: &CacheManager) -> TranslationResult<usize> {
+        let entries = self.all()?;
+        let count = entries.len();
+        for entry in entries {
+            cache.insert_correction(
+                entry.original_text,
+                entry.translated_text,
+                entry.target_lang,
+            );
+        }
+        Ok(count)
+    }
+}
+
+/// 把版本 1 的表中的记录写入当前的表并删除旧表，返回迁移的条数
+///
+/// 当前的表中已有的记录不会被覆盖。
+fn migrate_legacy(
+    write_txn: &redb::WriteTransaction,
+    table: &mut redb::Table<(&str, &str), &str>,
+) -> TranslationResult<usize> {
+    let has_legacy = write_txn
+        .list_tables()
+        .map_err(cache_error)?
+        .any(|handle| handle.name() == LEGACY_CORRECTIONS_TABLE.name());
+    if !has_legacy {
+        return Ok(0);
+    }
+
+    let mut migrated = 0;
+    {
+        let legacy = write_txn
+            .open_table(LEGACY_CORRECTIONS_TABLE)
+            .map_err(cache_error)?;
+        for item in legacy.iter().map_err(cache_error)? {
+            let (key, value) = item.map_err(cache_error)?;
+            let (target_lang, original) = key.value();
+            if table
+                .get((target_lang, original))
+                .map_err(cache_error)?
+                .is_some()
+            {
+                continue;
+            }
+            let record = StoredCorrection {
+                saved_at: None,
+                ..StoredCorrection::new(value.value())
+            }
+            .encode()?;
+            table
+                .insert((target_lang, original), record.as_str())
+                .map_err(cache_error)?;
+            migrated += 1;
+        }
+    }
+    write_txn
+        .delete_table(LEGACY_CORRECTIONS_TABLE)
+        .map_err(cache_error)?;
+
+    Ok(migrated)
+}
+
+fn cache_error<E: std::fmt::Display>(error: E) -> TranslationError {
+    TranslationError::CacheError(format!("Correction storage error: {}", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::translation::storage::cache::CacheConfig;
+
+    #[test]
+    fn test_corrections_survive_reopen() {
+        let path =
+            std::env::temp_dir().join(format!("monolith-corrections-{}.redb", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        {
+            let store = CorrectionStore::open(&path).unwrap();
+            assert!(store.all().unwrap().is_empty());
+            assert!(!path.exists());
+            store.save("Hello", "哈喽", "zh").unwrap();
+            store.save("Hello", "你好", "zh").unwrap();
+            store.save("Hello", "こんにちは", "ja").unwrap();
+        }
+
+        let store = CorrectionStore::open(&path).unwrap();
+        let cache = CacheManager::new(CacheConfig::default());
+        assert_eq!(store.load_into(&cache).unwrap(), 2);
+
+        let entry = cache.lookup("Hello", "zh").unwrap();
+        assert_eq!(entry.translated_text, "你好");
+        assert!(entry.is_human());
+        assert_eq!(
+            cache.lookup("Hello", "ja").unwrap().translated_text,
+            "こんにちは"
+        );
+
+        drop(store);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_legacy_entries_are_upgraded() {
+        let path = std::env::temp_dir().join(format!(
+            "monolith-corrections-legacy-{}.redb",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        {
+            let db = redb::Database::create(&path).unwrap();
+            let write_txn = db.begin_write().unwrap();
+            {
+                let mut legacy = write_txn.open_table(LEGACY_CORRECTIONS_TABLE).unwrap();
+                legacy.insert(("zh", "Hello"), "你好").unwrap();
+                let mut table = write_txn.open_table(CORRECTIONS_TABLE).unwrap();
+                table
+                    .insert(
+                        ("ja", "Hello"),
+                        "{\"version\":99,\"translated_text\":\"?\"}",
+                    )
+                    .unwrap();
+                table.insert(("ja", "Bye"), "not json").unwrap();
+            }
+            write_txn.commit().unwrap();
+        }
+
+        let store = CorrectionStore::open(&path).unwrap();
+        let entries = store.all().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].original_text, "Hello");
+        assert_eq!(entries[0].translated_text, "你好");
+        assert_eq!(entries[0].source_lang, "auto");
+        assert!(entries[0].is_human());
+
+        let read_txn = store.db.get().unwrap().begin_read().unwrap();
+        assert!(read_txn.open_table(LEGACY_CORRECTIONS_TABLE).is_err());
+        let table = read_txn.open_table(CORRECTIONS_TABLE).unwrap();
+        let record =
+            StoredCorrection::decode(table.get(("zh", "Hello")).unwrap().unwrap().value()).unwrap();
+        assert_eq!(record.version, CACHE_SCHEMA_VERSION);
+        assert_eq!(record.saved_at, None);
+        drop(table);
+        drop(read_txn);
+
+        drop(store);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_decode_fills_defaults() {
+        let record =
+            StoredCorrection::decode("{\"version\":1,\"translated_text\":\"你好\"}").unwrap();
+        assert_eq!(record.version, CACHE_SCHEMA_VERSION);
+        assert_eq!(record.source_lang, "auto");
+        assert!(StoredCorrection::decode("{\"version\":3,\"translated_text\":\"你好\"}").is_err());
+    }
+}
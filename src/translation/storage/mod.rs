@@ -3,5 +3,7 @@
 //! 提供缓存和持久化存储功能。
 
 pub mod cache;
+pub mod corrections;
 
-pub use cache::{TranslationCache, CacheStats, CacheEntry, CacheConfig, CacheManager, create_default_cache};
\ No newline at end of file
+pub use cache::{TranslationCache, CacheStats, DomainCacheStats, CacheEntry, CacheConfig, CacheManager, EntrySource, create_default_cache};
+pub use corrections::{CorrectionStore, StoredCorrection, CACHE_SCHEMA_VERSION};
\ No newline at end of file
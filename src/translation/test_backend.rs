@@ -0,0 +1,79 @@
+//! 测试用的本地翻译接口
+//!
+//! 在随机端口上监听，逐个处理请求：读取请求体，交给测试提供的处理函数得到状态行和
+//! JSON 响应体，回复后关闭连接。收到的请求体按顺序记录下来，供测试检查。
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+
+pub(crate) struct TestBackend {
+    /// 翻译接口地址，路径为 `/translate`
+    pub url: String,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl TestBackend {
+    /// 启动接口，按请求体决定响应的状态行和响应体
+    pub fn start<H>(handler: H) -> Self
+    where
+        H: Fn(&str) -> (&'static str, String) + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/translate", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let received = Arc::clone(&requests);
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = match stream {
+                    Ok(stream) => stream,
+                    Err(_) => break,
+                };
+                let body = read_body(&mut stream);
+                let (status, response) = handler(&body);
+                received.lock().unwrap().push(body);
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nContent-Type: application/json\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+                    status,
+                    response.len(),
+                    response
+                );
+            }
+        });
+        Self { url, requests }
+    }
+
+    /// 启动接口，对所有请求返回固定的状态行和响应体
+    pub fn fixed(status: &'static str, body: &'static str) -> Self {
+        Self::start(move |_| (status, body.to_string()))
+    }
+
+    /// 到目前为止收到的请求体
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+/// 没有服务监听的地址，发往这里的请求都会失败
+pub(crate) fn closed_url() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    format!("http://{}/translate", listener.local_addr().unwrap())
+}
+
+fn read_body(stream: &mut std::net::TcpStream) -> String {
+    let mut reader = BufReader::new(stream);
+    let mut content_length = 0;
+    let mut line = String::new();
+    while reader.read_line(&mut line).unwrap_or(0) > 0 && line != "\r\n" {
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+        line.clear();
+    }
+    let mut body = vec![0; content_length];
+    let _ = reader.read_exact(&mut body);
+    String::from_utf8_lossy(&body).to_string()
+}
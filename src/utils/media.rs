@@ -0,0 +1,165 @@
+/// Assumed size of `em` and `rem` units in media queries, in pixels
+const FONT_SIZE_PX: f64 = 16.0;
+
+/// Screen size used to evaluate media queries, e.g. to drop art-directed
+///  `<picture>` sources that would never be shown
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Viewport {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Viewport {
+    /// Parses a `WIDTHxHEIGHT` string such as `1280x800`
+    pub fn parse(value: &str) -> Option<Viewport> {
+        let (width, height) = value.trim().split_once(['x', 'X'])?;
+        Some(Viewport {
+            width: width.trim().parse().ok()?,
+            height: height.trim().parse().ok()?,
+        })
+    }
+
+    /// Evaluates a media query list (the `media` attribute) against this viewport
+    ///
+    /// Understands media types, `not`/`only`, `and`, width/height features (both the `min-`/`max-`
+    ///  form and range syntax) and `orientation`. Features it can't evaluate are assumed to match,
+    ///  so only queries that provably never apply return false.
+    pub fn matches_media(&self, media: &str) -> bool {
+        let media = media.trim().to_lowercase();
+        if media.is_empty() {
+            return true;
+        }
+
+        media
+            .split(',')
+            .any(|query| self.matches_query(query.trim()))
+    }
+
+    fn matches_query(&self, query: &str) -> bool {
+        let (negated, query) = match query.strip_prefix("not ") {
+            Some(rest) => (true, rest.trim()),
+            None => (false, query.strip_prefix("only ").unwrap_or(query).trim()),
+        };
+
+        let matches = query.split(" and ").all(|part| {
+            let part = part.trim();
+            match part
+                .strip_prefix('(')
+                .and_then(|part| part.strip_suffix(')'))
+            {
+                Some(feature) => self.matches_feature(feature.trim()).unwrap_or(true),
+                None => matches!(part, "" | "all" | "screen"),
+            }
+        });
+
+        matches != negated
+    }
+
+    /// Evaluates a single media feature, returns `None` if it isn't understood
+    fn matches_feature(&self, feature: &str) -> Option<bool> {
+        if let Some((name, value)) = feature.split_once(':') {
+            let name = name.trim();
+            let value = value.trim();
+            if name == "orientation" {
+                let portrait = self.height >= self.width;
+                return match value {
+                    "portrait" => Some(portrait),
+                    "landscape" => Some(!portrait),
+                    _ => None,
+                };
+            }
+
+            let (prefix, dimension) = match name.split_once('-') {
+                Some((prefix @ ("min" | "max"), dimension)) => (prefix, dimension),
+                _ => ("", name),
+            };
+            let actual = self.dimension(dimension)?;
+            let expected = parse_length(value)?;
+            return Some(match prefix {
+                "min" => actual >= expected,
+                "max" => actual <= expected,
+                _ => actual == expected,
+            });
+        }
+
+        // Range syntax: `width >= 600px`, `600px < width` or `400px <= width <= 700px`
+        let tokens = tokenize_range(feature)?;
+        let mut matches = true;
+        for window in tokens.windows(3).step_by(2) {
+            let (left, operator, right) = (&window[0], &window[1], &window[2]);
+            let left = self.dimension(left).or_else(|| parse_length(left))?;
+            let right = self.dimension(right).or_else(|| parse_length(right))?;
+            matches &= match operator.as_str() {
+                "<" => left < right,
+                "<=" => left <= right,
+                ">" => left > right,
+                ">=" => left >= right,
+                "=" => left == right,
+                _ => return None,
+            };
+        }
+        Some(matches)
+    }
+
+    fn dimension(&self, name: &str) -> Option<f64> {
+        match name {
+            "width" => Some(self.width as f64),
+            "height" => Some(self.height as f64),
+            _ => None,
+        }
+    }
+}
+
+/// Splits a range feature into alternating operands and comparison operators
+fn tokenize_range(feature: &str) -> Option<Vec<String>> {
+    let mut tokens: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let mut chars = feature.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if matches!(c, '<' | '>' | '=') {
+            if !current.trim().is_empty() {
+                tokens.push(current.trim().to_string());
+            }
+            current.clear();
+            let mut operator = c.to_string();
+            if c != '=' && chars.peek() == Some(&'=') {
+                operator.push(chars.next().unwrap());
+            }
+            tokens.push(operator);
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.trim().is_empty() {
+        tokens.push(current.trim().to_string());
+    }
+
+    if tokens.len() == 3 || tokens.len() == 5 {
+        Some(tokens)
+    } else {
+        None
+    }
+}
+
+/// Converts a CSS length used in media queries into pixels
+fn parse_length(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let (number, factor) = if let Some(number) = value.strip_suffix("px") {
+        (number, 1.0)
+    } else if let Some(number) = value
+        .strip_suffix("rem")
+        .or_else(|| value.strip_suffix("em"))
+    {
+        (number, FONT_SIZE_PX)
+    } else {
+        (value, 1.0)
+    };
+
+    let number: f64 = number.trim().parse().ok()?;
+    if factor == 1.0 && number != 0.0 && !value.ends_with("px") {
+        // Unitless lengths other than zero aren't valid
+        return None;
+    }
+    Some(number * factor)
+}
@@ -5,15 +5,21 @@
 //! - URL处理和解析工具
 //! - 数据URL创建和解析
 //! - URL清理和规范化
+//! - 媒体查询求值
 //!
 //! # 模块组织
 //!
 //! - `url` - URL处理、数据URL、域名验证等工具函数
+//! - `media` - 按目标视口判断 `media` 属性中的媒体查询是否可能匹配
+//! - `storage` - 按需创建的 redb 数据库
 
+pub mod media;
+pub mod storage;
 pub mod url;
 
 // Re-export commonly used items for convenience
 pub use url::{
-    clean_url, create_data_url, domain_is_within_domain, get_referer_url, is_url_and_has_protocol,
-    parse_data_url, resolve_url, Url, EMPTY_IMAGE_DATA_URL,
+    clean_url, create_data_url, domain_is_within_domain, get_referer_url, host_matches_pattern,
+    is_url_and_has_protocol, parse_data_url, resolve_url, Url, EMPTY_IMAGE_DATA_URL,
 };
+pub use media::Viewport;
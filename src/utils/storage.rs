@@ -0,0 +1,58 @@
+//! 按需创建的 redb 数据库
+//!
+//! Web 服务把批量入库状态、人工修正等保存在文档库目录中。只读取的请求不应在磁盘上留下空的
+//! 数据库文件，因此文件已存在时直接打开，不存在时等到第一次写入才创建文件及其所在目录。
+
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use redb::{Database, DatabaseError, StorageError};
+
+pub struct LazyDatabase {
+    path: PathBuf,
+    db: OnceLock<Database>,
+    /// 保证同时写入时只创建一次
+    creating: Mutex<()>,
+}
+
+impl LazyDatabase {
+    /// 文件已存在时立即打开，否则不访问磁盘
+    pub fn open(path: &Path) -> Result<Self, DatabaseError> {
+        let lazy = Self {
+            path: path.to_path_buf(),
+            db: OnceLock::new(),
+            creating: Mutex::new(()),
+        };
+        if path.exists() {
+            let _ = lazy.db.set(Database::create(path)?);
+        }
+        Ok(lazy)
+    }
+
+    /// 已打开的数据库，文件尚未创建时返回 `None`
+    pub fn get(&self) -> Option<&Database> {
+        self.db.get()
+    }
+
+    /// 打开数据库，文件不存在时连同所在目录一起创建
+    pub fn get_or_create(&self) -> Result<&Database, DatabaseError> {
+        if let Some(db) = self.db.get() {
+            return Ok(db);
+        }
+
+        let _creating = self.creating.lock().unwrap();
+        if let Some(db) = self.db.get() {
+            return Ok(db);
+        }
+        if let Some(parent) = self
+            .path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| DatabaseError::Storage(StorageError::Io(e)))?;
+        }
+        let db = Database::create(&self.path)?;
+        Ok(self.db.get_or_init(|| db))
+    }
+}
@@ -1,8 +1,8 @@
 use base64::{prelude::BASE64_STANDARD, Engine};
-use percent_encoding::percent_decode_str;
+use percent_encoding::{percent_decode_str, percent_encode, AsciiSet, CONTROLS};
 pub use url::Url;
 
-use crate::core::{detect_media_type, parse_content_type};
+use crate::core::{detect_media_type, parse_content_type, DataUrlEncoding};
 
 pub const EMPTY_IMAGE_DATA_URL: &str = "data:image/png,\
 %89PNG%0D%0A%1A%0A%00%00%00%0DIHDR%00%00%00%0D%00%00%00%0D%08%04%00%00%00%D8%E2%2C%F7%00%00%00%11IDATx%DAcd%C0%09%18G%A5%28%96%02%00%0A%F8%00%0E%CB%8A%EB%16%00%00%00%00IEND%AEB%60%82";
@@ -17,6 +17,37 @@ pub fn clean_url(url: Url) -> Url {
 }
 
 pub fn create_data_url(media_type: &str, charset: &str, data: &[u8], final_asset_url: &Url) -> Url {
+    create_data_url_with_encoding(
+        media_type,
+        charset,
+        data,
+        final_asset_url,
+        DataUrlEncoding::Base64,
+    )
+}
+
+/// Bytes percent-encoded in data URLs; everything else (including `{`, `:` and `;`) stays literal
+///  so that embedded CSS or SVG can still be read and searched
+const DATA_URL_ENCODE_SET: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'\'')
+    .add(b'(')
+    .add(b')')
+    .add(b'<')
+    .add(b'>')
+    .add(b'\\')
+    .add(b'`');
+
+pub fn create_data_url_with_encoding(
+    media_type: &str,
+    charset: &str,
+    data: &[u8],
+    final_asset_url: &Url,
+    encoding: DataUrlEncoding,
+) -> Url {
     // TODO: move this block out of this function
     let media_type: String = if media_type.is_empty() {
         detect_media_type(data, final_asset_url)
@@ -33,15 +64,22 @@ pub fn create_data_url(media_type: &str, charset: &str, data: &[u8], final_asset
             "".to_string()
         };
 
-    data_url.set_path(
-        format!(
-            "{}{};base64,{}",
-            media_type,
-            c,
-            BASE64_STANDARD.encode(data)
-        )
-        .as_str(),
-    );
+    let base64 = || format!(";base64,{}", BASE64_STANDARD.encode(data));
+    let percent_encoded = || format!(",{}", percent_encode(data, DATA_URL_ENCODE_SET));
+    let payload: String = match encoding {
+        DataUrlEncoding::Base64 => base64(),
+        DataUrlEncoding::PercentEncode => percent_encoded(),
+        DataUrlEncoding::Auto => {
+            let (base64, percent_encoded) = (base64(), percent_encoded());
+            if percent_encoded.len() < base64.len() {
+                percent_encoded
+            } else {
+                base64
+            }
+        }
+    };
+
+    data_url.set_path(format!("{}{}{}", media_type, c, payload).as_str());
 
     data_url
 }
@@ -102,6 +140,82 @@ pub fn domain_is_within_domain(domain: &str, domain_to_match_against: &str) -> b
     ok
 }
 
+/// Checks a host against a block-list pattern
+///
+/// A plain pattern matches the host itself and all of its subdomains, while `*` matches any
+///  sequence of characters, e.g. `*.doubleclick.net` or `ads.*`
+pub fn host_matches_pattern(host: &str, pattern: &str) -> bool {
+    let host = host.trim_end_matches('.').to_lowercase();
+    let pattern = pattern.trim().trim_end_matches('.').to_lowercase();
+
+    if pattern.is_empty() {
+        return false;
+    }
+
+    if !pattern.contains('*') {
+        return host == pattern || host.ends_with(&format!(".{}", pattern));
+    }
+
+    glob_matches(&host, &pattern)
+}
+
+/// Checks a URL against a resource pattern
+///
+/// A pattern starting with `.` matches by file extension (`.woff2`), a pattern with a scheme is
+///  matched against the whole URL and any other pattern against the host followed by the path
+///  (`cdn.example.com/fonts/*`); `*` matches any sequence of characters, including `/`.
+///  Query strings and fragments are ignored, and matching is case-insensitive.
+pub fn url_matches_pattern(url: &Url, pattern: &str) -> bool {
+    let pattern = pattern.trim().to_lowercase();
+    if pattern.is_empty() {
+        return false;
+    }
+
+    let path = url.path().to_lowercase();
+    if pattern.starts_with('.') {
+        return path.ends_with(&pattern);
+    }
+
+    let target = if pattern.contains("://") {
+        let mut url = url.clone();
+        url.set_query(None);
+        url.set_fragment(None);
+        url.as_str().to_lowercase()
+    } else {
+        format!(
+            "{}{}",
+            url.host_str().unwrap_or_default().to_lowercase(),
+            path
+        )
+    };
+    glob_matches(&target, &pattern)
+}
+
+/// Glob match where `*` matches any sequence of characters, backtracking to the most recent `*`
+fn glob_matches(text: &str, pattern: &str) -> bool {
+    let text: Vec<char> = text.chars().collect();
+    let pattern: Vec<char> = pattern.chars().collect();
+    let (mut t, mut p) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == text[t] {
+            t += 1;
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
 pub fn is_url_and_has_protocol(input: &str) -> bool {
     match Url::parse(input) {
         Ok(parsed_url) => !parsed_url.scheme().is_empty(),
@@ -2,7 +2,8 @@
 //! 
 //! 使用类型安全的环境变量系统进行配置管理
 
-use crate::env::{EnvResult, EnvError, EnvVar};
+use crate::env::web::LibraryBackendKind;
+use crate::env::{translation::SelfCheckMode, EnvResult, EnvError, EnvVar};
 
 // MongoDB 配置已移除 - 轻量化版本不再使用数据库
 
@@ -15,6 +16,18 @@ pub struct WebConfig {
     pub port: u16,
     /// 静态文件目录
     pub static_dir: Option<String>,
+    /// 文档库目录，保存批量入库的状态和文档
+    pub library_dir: String,
+    /// 已入库文档的存储方式
+    pub library_backend: LibraryBackendKind,
+    /// 文档库统计的后台刷新间隔
+    pub library_stats_interval: std::time::Duration,
+    /// 启动时是否对翻译后端做一次实际请求的自检
+    pub translation_self_check: SelfCheckMode,
+    /// 是否在请求之间共享已获取的资源（保存在文档库目录中）
+    pub shared_asset_cache: bool,
+    /// 翻译流的最长翻译时间，`None` 时不限制
+    pub translation_timeout: Option<std::time::Duration>,
 }
 
 impl WebConfig {
@@ -30,11 +43,23 @@ impl WebConfig {
         } else { 
             Some(static_dir_str) 
         };
+        let library_dir = web::LibraryDir::get()?;
+        let library_backend = web::LibraryBackend::get()?;
+        let library_stats_interval = web::LibraryStatsInterval::get()?;
+        let translation_self_check = crate::env::translation::SelfCheck::get()?;
+        let shared_asset_cache = web::SharedAssetCache::get()?;
+        let translation_timeout = web::TranslationTimeout::get()?;
         
         Ok(Self {
             bind_addr,
             port,
             static_dir,
+            library_dir,
+            library_backend,
+            library_stats_interval,
+            translation_self_check,
+            shared_asset_cache,
+            translation_timeout,
         })
     }
     
@@ -91,6 +116,12 @@ impl Default for WebConfig {
                 bind_addr: "127.0.0.1".to_string(),
                 port: 7080,
                 static_dir: Some("static".to_string()),
+                library_dir: "library".to_string(),
+                library_backend: LibraryBackendKind::Redb,
+                library_stats_interval: std::time::Duration::from_secs(300),
+                translation_self_check: SelfCheckMode::Off,
+                shared_asset_cache: false,
+                translation_timeout: Some(std::time::Duration::from_secs(300)),
             }
         })
     }
@@ -26,16 +26,24 @@ pub async fn get_cache_stats(
 /// 清理缓存 - 简化版本
 #[cfg(feature = "web")]
 pub async fn clear_cache(
-    State(_state): State<Arc<AppState>>,
-    Json(_request): Json<CacheClearRequest>,
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<CacheClearRequest>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
-    // 轻量化版本：由于不使用数据库缓存，直接返回成功
-    tracing::info!("缓存清理请求 - 轻量化版本无需处理");
+    // 轻量化版本不使用数据库缓存，只需清理内存中的原文页面
+    let cleared_count = match request.url {
+        Some(ref url) => usize::from(state.page_cache.remove(url)),
+        None => {
+            let count = state.page_cache.len();
+            state.page_cache.clear();
+            count
+        }
+    };
+    tracing::info!("缓存清理请求: 清理了 {} 个原文页面", cleared_count);
 
     Ok(Json(serde_json::json!({
         "success": true,
         "message": "轻量化版本不使用持久化缓存",
-        "cleared_count": 0
+        "cleared_count": cleared_count
     })))
 }
 
@@ -0,0 +1,315 @@
+//! 流水线自检
+//!
+//! 与只确认依赖可用的检查不同，这里在内存中的固定页面上完整运行一次归档流程：
+//! 解析 → 嵌入内联资源 → 翻译一句短文本 → 序列化，并检查输出中同时包含译文和重新编码后的
+//! data URL。依赖都在线但流程本身出错（例如资源不再被嵌入、译文没有写回）时也能发现。
+//!
+//! 一次自检会真正调用翻译服务，因此结果缓存 [`MIN_INTERVAL`]，间隔内的请求直接返回上次的结果，
+//! 不适合作为存活探针的目标。需要时用 `?fresh=true` 强制重新运行，但距上次运行不足
+//! [`MIN_FRESH_INTERVAL`] 时仍返回缓存的结果；同一时间最多只有一次自检在运行。
+
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "web")]
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use markup5ever_rcdom::{Handle, NodeData, RcDom};
+use tokio::sync::Mutex;
+use url::Url;
+
+use crate::core::MonolithOptions;
+use crate::parsers::html::{get_node_attr, html_to_dom, serialize_document, walk};
+use crate::session::Session;
+use crate::web::types::{AppState, PipelineHealthQuery, PipelineHealthReport, PipelineStageReport};
+
+/// 两次自检之间的最短间隔
+pub const MIN_INTERVAL: Duration = Duration::from_secs(30);
+
+/// 用 `?fresh=true` 强制重新运行时，两次自检之间的最短间隔
+pub const MIN_FRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// 自检使用的页面：一段待翻译的文本和一张百分号编码的内联图片
+const FIXTURE: &str = "<!DOCTYPE html><html><head><title>Pipeline health</title></head><body>\
+    <p id=\"health-phrase\">Hello world</p>\
+    <img src=\"data:image/svg+xml,%3Csvg%20xmlns%3D%22http%3A%2F%2Fwww.w3.org%2F2000%2Fsvg%22%2F%3E\">\
+    </body></html>";
+
+/// 自检页面的地址，只用于解析相对链接，不会被请求
+const FIXTURE_URL: &str = "https://pipeline-health.invalid/";
+
+/// 嵌入阶段把内联图片重新编码后应得到的前缀
+const EMBEDDED_PREFIX: &str = "data:image/svg+xml;base64,";
+
+/// 待翻译文本所在元素的 `id`
+const PHRASE_ID: &str = "health-phrase";
+
+/// 自检翻译的目标语言
+const TARGET_LANG: &str = "zh";
+
+/// 最近一次自检的结果
+///
+/// 自检运行期间一直持有锁，同时到达的请求等待这次自检完成后直接使用它的结果。
+fn last_report() -> &'static Mutex<Option<(Instant, PipelineHealthReport)>> {
+    static LAST_REPORT: OnceLock<Mutex<Option<(Instant, PipelineHealthReport)>>> = OnceLock::new();
+    LAST_REPORT.get_or_init(|| Mutex::new(None))
+}
+
+/// 运行流水线自检，任一阶段失败时返回 503
+#[cfg(feature = "web")]
+pub async fn get_pipeline_health(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<PipelineHealthQuery>,
+) -> (StatusCode, Json<PipelineHealthReport>) {
+    let min_interval = if query.fresh.unwrap_or(false) {
+        MIN_FRESH_INTERVAL
+    } else {
+        MIN_INTERVAL
+    };
+    let mut last = last_report().lock().await;
+    if let Some((checked, report)) = last.as_ref() {
+        if checked.elapsed() < min_interval {
+            let mut report = report.clone();
+            report.cached = true;
+            return (status_of(&report), Json(report));
+        }
+    }
+
+    let options = state.monolith_options.clone();
+    let report = tokio::task::spawn_blocking(move || run_pipeline(options))
+        .await
+        .unwrap_or_else(|e| PipelineHealthReport {
+            healthy: false,
+            stages: vec![PipelineStageReport {
+                name: "pipeline",
+                ok: false,
+                skipped: false,
+                duration_ms: 0,
+                error: Some(e.to_string()),
+            }],
+            total_ms: 0,
+            checked_at: chrono::Utc::now().to_rfc3339(),
+            cached: false,
+        });
+
+    if !report.healthy {
+        tracing::warn!(
+            "流水线自检失败: {:?}",
+            report
+                .stages
+                .iter()
+                .filter_map(|stage| stage.error.as_deref())
+                .collect::<Vec<_>>()
+        );
+    }
+    *last = Some((Instant::now(), report.clone()));
+    (status_of(&report), Json(report))
+}
+
+#[cfg(feature = "web")]
+fn status_of(report: &PipelineHealthReport) -> StatusCode {
+    if report.healthy {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    }
+}
+
+/// 在固定页面上依次运行各阶段，遇到失败的阶段即停止
+///
+/// 翻译阶段在当前 tokio 运行时上阻塞等待，需要在 `spawn_blocking` 中调用。
+pub fn run_pipeline(mut options: MonolithOptions) -> PipelineHealthReport {
+    let started = Instant::now();
+    let mut stages = Vec::new();
+    // 自检关心的是流程本身，不受部署时关闭图片或静默输出等选项影响
+    options.no_images = false;
+    options.silent = true;
+
+    let healthy = run_stages(options, &mut stages).is_some();
+
+    PipelineHealthReport {
+        healthy,
+        stages,
+        total_ms: started.elapsed().as_millis() as u64,
+        checked_at: chrono::Utc::now().to_rfc3339(),
+        cached: false,
+    }
+}
+
+fn run_stages(options: MonolithOptions, stages: &mut Vec<PipelineStageReport>) -> Option<()> {
+    let document_url = Url::parse(FIXTURE_URL).expect("自检页面地址无效");
+
+    let (dom, phrase) = run_stage(stages, "parse", || {
+        let dom = html_to_dom(FIXTURE.as_bytes(), "utf-8".to_string());
+        match find_by_id(&dom.document, PHRASE_ID) {
+            Some(node) => {
+                let phrase = text_content(&node);
+                Ok((dom, phrase))
+            }
+            None => Err("解析结果中缺少待翻译的段落".to_string()),
+        }
+    })?;
+
+    let dom = run_stage(stages, "embed", || {
+        let mut session = Session::new(None, None, options.clone());
+        walk(&mut session, &document_url, &dom.document);
+        Ok(dom)
+    })?;
+
+    let (dom, translated) = translate_stage(stages, dom, &phrase)?;
+
+    run_stage(stages, "serialize", || {
+        let html = String::from_utf8_lossy(&serialize_document(dom, "utf-8".to_string(), &options))
+            .to_string();
+        if let Some(translated) = &translated {
+            if !html.contains(translated.as_str()) {
+                return Err(format!("输出中缺少译文 \"{translated}\""));
+            }
+        }
+        if !html.contains(EMBEDDED_PREFIX) {
+            return Err(format!("输出中缺少嵌入的资源 ({EMBEDDED_PREFIX}...)"));
+        }
+        Ok(())
+    })
+}
+
+/// 翻译待翻译的段落，返回 DOM 和写回后的译文
+#[cfg(feature = "translation")]
+fn translate_stage(
+    stages: &mut Vec<PipelineStageReport>,
+    dom: RcDom,
+    phrase: &str,
+) -> Option<(RcDom, Option<String>)> {
+    use crate::translation::{load_translation_config, TranslationService};
+
+    run_stage(stages, "translate", || {
+        let mut config = load_translation_config(TARGET_LANG, None);
+        // 缓存的译文会掩盖翻译服务的故障
+        config.cache_enabled = false;
+        config.bilingual = false;
+
+        let mut service = TranslationService::new(config).map_err(|e| e.to_string())?;
+        let dom = tokio::runtime::Handle::current()
+            .block_on(service.translate_dom(dom))
+            .map_err(|e| e.to_string())?;
+
+        let translated = find_by_id(&dom.document, PHRASE_ID)
+            .map(|node| text_content(&node).trim().to_string())
+            .unwrap_or_default();
+        if translated.is_empty() || translated == phrase.trim() {
+            return Err(format!("段落没有被翻译: \"{translated}\""));
+        }
+        Ok((dom, Some(translated)))
+    })
+}
+
+/// 未启用翻译功能时跳过翻译阶段
+#[cfg(not(feature = "translation"))]
+fn translate_stage(
+    stages: &mut Vec<PipelineStageReport>,
+    dom: RcDom,
+    _phrase: &str,
+) -> Option<(RcDom, Option<String>)> {
+    stages.push(PipelineStageReport {
+        name: "translate",
+        ok: true,
+        skipped: true,
+        duration_ms: 0,
+        error: None,
+    });
+    Some((dom, None))
+}
+
+/// 运行并记录一个阶段，失败时返回 `None`
+fn run_stage<T>(
+    stages: &mut Vec<PipelineStageReport>,
+    name: &'static str,
+    stage: impl FnOnce() -> Result<T, String>,
+) -> Option<T> {
+    let started = Instant::now();
+    let result = stage();
+    stages.push(PipelineStageReport {
+        name,
+        ok: result.is_ok(),
+        skipped: false,
+        duration_ms: started.elapsed().as_millis() as u64,
+        error: result.as_ref().err().cloned(),
+    });
+    result.ok()
+}
+
+fn find_by_id(node: &Handle, id: &str) -> Option<Handle> {
+    if let NodeData::Element { .. } = node.data {
+        if get_node_attr(node, "id").as_deref() == Some(id) {
+            return Some(node.clone());
+        }
+    }
+    node.children
+        .borrow()
+        .iter()
+        .find_map(|child| find_by_id(child, id))
+}
+
+fn text_content(node: &Handle) -> String {
+    match node.data {
+        NodeData::Text { ref contents } => contents.borrow().to_string(),
+        _ => node.children.borrow().iter().map(text_content).collect(),
+    }
+}
+
+#[cfg(all(test, feature = "web"))]
+mod tests {
+    use super::*;
+    use crate::web::test_state;
+
+    async fn check(state: &Arc<AppState>, fresh: bool) -> PipelineHealthReport {
+        let query = PipelineHealthQuery { fresh: Some(fresh) };
+        let (_, Json(report)) = get_pipeline_health(State(Arc::clone(state)), Query(query)).await;
+        report
+    }
+
+    /// 把上次自检的结果设置为指定时间之前的一份报告
+    async fn set_last_report(age: Duration) -> PipelineHealthReport {
+        let report = PipelineHealthReport {
+            healthy: true,
+            stages: Vec::new(),
+            total_ms: 0,
+            checked_at: "2026-01-01T00:00:00+00:00".to_string(),
+            cached: false,
+        };
+        *last_report().lock().await = Some((Instant::now() - age, report.clone()));
+        report
+    }
+
+    #[test]
+    fn test_cached_and_fresh_checks() {
+        // 会话持有阻塞式 HTTP 客户端，不能在异步上下文中释放
+        let state = test_state("health");
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            // 间隔内的请求返回上次的结果，强制重新运行也同样受最短间隔限制
+            let last = set_last_report(Duration::ZERO).await;
+            for fresh in [false, true] {
+                let report = check(&state, fresh).await;
+                assert!(report.cached);
+                assert_eq!(report.checked_at, last.checked_at);
+            }
+
+            // 超过强制运行的最短间隔后，只有 `fresh=true` 会重新运行
+            set_last_report(MIN_FRESH_INTERVAL).await;
+            assert!(check(&state, false).await.cached);
+            let fresh = check(&state, true).await;
+            assert!(!fresh.cached);
+            assert_ne!(fresh.checked_at, last.checked_at);
+            assert_eq!(fresh.stages[0].name, "parse");
+
+            // 重新运行的结果同样被缓存
+            let cached = check(&state, true).await;
+            assert!(cached.cached);
+            assert_eq!(cached.checked_at, fresh.checked_at);
+        });
+    }
+}
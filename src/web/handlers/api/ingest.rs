@@ -0,0 +1,98 @@
+//! 批量入库API处理器
+
+use std::sync::Arc;
+
+#[cfg(feature = "web")]
+use axum::{
+    extract::{Json as ExtractJson, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+
+use crate::web::ingest::{IngestEntry, IngestProgress};
+use crate::web::library::LibraryStats;
+use crate::web::types::{AppState, IngestRequest, IngestResponse, LibraryStatsQuery};
+
+/// 将URL列表加入入库队列并启动后台处理
+///
+/// 重复提交是安全的：已完成的URL被跳过，失败的URL重新排队。
+#[cfg(feature = "web")]
+pub async fn enqueue_ingest(
+    State(state): State<Arc<AppState>>,
+    ExtractJson(request): ExtractJson<IngestRequest>,
+) -> Result<Json<IngestResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let ingest = Arc::clone(&state.ingest);
+    let summary = tokio::task::spawn_blocking(move || ingest.enqueue(&request.urls))
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|result| result.map_err(|e| e.to_string()))
+        .map_err(|e| {
+            tracing::error!("入库队列更新失败: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({
+                    "status": "error",
+                    "error": format!("入库队列更新失败: {}", e)
+                })),
+            )
+        })?;
+
+    tracing::info!(
+        "入库请求: 新增 {}, 重试 {}, 跳过 {}, 无效 {}",
+        summary.added,
+        summary.retried,
+        summary.skipped,
+        summary.invalid.len()
+    );
+
+    state.ingest.start_worker(state.monolith_options.clone());
+
+    Ok(Json(IngestResponse {
+        summary,
+        progress: state.ingest.progress(),
+    }))
+}
+
+/// 获取入库进度
+#[cfg(feature = "web")]
+pub async fn get_ingest_progress(State(state): State<Arc<AppState>>) -> Json<IngestProgress> {
+    Json(state.ingest.progress())
+}
+
+/// 获取失败的URL报告
+#[cfg(feature = "web")]
+pub async fn get_ingest_failed(State(state): State<Arc<AppState>>) -> Json<Vec<IngestEntry>> {
+    Json(state.ingest.failed())
+}
+
+/// 获取文档库统计
+///
+/// 默认返回后台任务最近一次计算的结果，`?fresh=true` 时立即重新计算。
+#[cfg(feature = "web")]
+pub async fn get_library_stats(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<LibraryStatsQuery>,
+) -> Result<Json<LibraryStats>, (StatusCode, Json<serde_json::Value>)> {
+    if !query.fresh.unwrap_or(false) {
+        if let Some(stats) = state.library.cached_stats() {
+            return Ok(Json(stats));
+        }
+    }
+
+    let library = Arc::clone(&state.library);
+    tokio::task::spawn_blocking(move || library.refresh_stats())
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|result| result.map_err(|e| e.to_string()))
+        .map(Json)
+        .map_err(|e| {
+            tracing::error!("文档库统计失败: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({
+                    "status": "error",
+                    "error": format!("文档库统计失败: {}", e)
+                })),
+            )
+        })
+}
@@ -0,0 +1,29 @@
+//! 监控指标API处理器
+
+use std::sync::Arc;
+
+#[cfg(feature = "web")]
+use axum::{extract::State, http::header, response::IntoResponse};
+
+use crate::web::metrics;
+use crate::web::types::AppState;
+
+/// 以 Prometheus 文本格式导出监控指标
+#[cfg(feature = "web")]
+pub async fn get_metrics(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let mut body = String::new();
+    metrics::global().render(&mut body);
+
+    #[cfg(feature = "translation")]
+    metrics::render_translation(&mut body, &state.translation_cache);
+    #[cfg(not(feature = "translation"))]
+    let _ = state;
+
+    (
+        [(
+            header::CONTENT_TYPE,
+            "text/plain; version=0.0.4; charset=utf-8",
+        )],
+        body,
+    )
+}
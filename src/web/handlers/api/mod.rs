@@ -3,13 +3,21 @@
 pub mod bookmarklet;
 pub mod cache;
 pub mod content;
+pub mod health;
+pub mod ingest;
+pub mod metrics;
 pub mod process;
+pub mod stream;
 pub mod theme;
 pub mod translation;
 
 pub use bookmarklet::*;
 pub use cache::*;
 pub use content::*;
+pub use health::*;
+pub use ingest::*;
+pub use metrics::*;
 pub use process::*;
+pub use stream::*;
 pub use theme::*;
 pub use translation::*;
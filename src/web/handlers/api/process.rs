@@ -5,7 +5,7 @@ use std::sync::Arc;
 #[cfg(feature = "web")]
 use axum::{
     extract::{Json as ExtractJson, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     response::Json,
 };
 
@@ -13,20 +13,25 @@ use axum::{
 use tokio::task;
 
 use crate::core::{create_monolithic_document, MonolithError};
-use crate::session::Session;
 use crate::web::types::{AppState, ProcessRequest, ProcessResponse};
 
 /// 统一的URL处理端点 - 轻量化版本
 /// 
-/// 这是书签脚本的核心API，整合了资源提取和翻译功能
+/// 这是书签脚本的核心API，整合了资源提取和翻译功能。
+/// 返回译文时 `Content-Language` 为译文的语言，与页面的 `<html lang>` 相同
 #[cfg(feature = "web")]
 pub async fn process_url(
     State(state): State<Arc<AppState>>,
     ExtractJson(request): ExtractJson<ProcessRequest>,
-) -> Result<Json<ProcessResponse>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<(HeaderMap, Json<ProcessResponse>), (StatusCode, Json<serde_json::Value>)> {
     let url = request.url.clone();
     let translate = request.translate.unwrap_or(false);
     let target_lang = request.target_lang.clone().unwrap_or_else(|| "zh".to_string());
+    let concurrent_translation = request
+        .options
+        .as_ref()
+        .and_then(|opts| opts.concurrent_translation)
+        .unwrap_or(false);
 
     tracing::info!("处理URL请求: {} (翻译: {})", url, translate);
 
@@ -53,29 +58,62 @@ pub async fn process_url(
     let original_future = {
         let url = url.clone();
         let options = options_original;
+        let base_session = state.session.clone();
         task::spawn_blocking(move || {
-            let session = Session::new(None, None, options);
+            let session = base_session.with_options(options);
             create_monolithic_document(session, &url)
         })
     };
 
+    // 客户端断开连接时处理器的 future 被丢弃，守卫随之取消令牌，翻译不再发送剩余的批次
+    #[cfg(feature = "translation")]
+    let translation_cancel = tokio_util::sync::CancellationToken::new();
+    #[cfg(feature = "translation")]
+    let _cancel_on_disconnect = translation_cancel.clone().drop_guard();
+
     // 根据请求决定是否处理译文
     let translated_future = if translate {
         let url = url.clone();
         let options = options_translated;
+        let base_session = state.session.clone();
         let _target_lang_clone = target_lang.clone();
-        
+        #[cfg(feature = "translation")]
+        let translation_cache = state.translation_cache.clone();
+        #[cfg(feature = "translation")]
+        let translation_concurrency = state.translation_concurrency.clone();
+        #[cfg(feature = "translation")]
+        let cancellation = translation_cancel.clone();
+
         Some(task::spawn_blocking(
             move || -> Result<(Vec<u8>, Option<String>), MonolithError> {
+                // 并行模式：在嵌入资源的同时翻译文本，一次处理即得到译文
+                #[cfg(feature = "translation")]
+                if concurrent_translation {
+                    use crate::translation::load_translation_config;
+
+                    let translation_config = load_translation_config(&_target_lang_clone, None);
+                    let mut options = options.clone();
+                    options.enable_translation = true;
+                    options.concurrent_translation = true;
+                    options.target_language = Some(_target_lang_clone.clone());
+                    options.translation_api_url = Some(translation_config.api_url);
+
+                    let session = base_session.with_options(options);
+                    return create_monolithic_document(session, &url);
+                }
+
                 // 先获取原始内容
-                let session = Session::new(None, None, options.clone());
+                let session = base_session.with_options(options.clone());
                 let original_result = create_monolithic_document(session, &url)?;
 
                 // 如果启用了翻译功能，进行翻译
                 #[cfg(feature = "translation")]
                 {
                     use crate::parsers::html::{html_to_dom, serialize_document};
-                    use crate::translation::{load_translation_config, translate_dom_content};
+                    use crate::translation::{
+                        load_translation_config, translate_dom_content_with_cache,
+                    };
+                    use crate::utils::url::Url;
 
                     let (original_data, title) = original_result;
                     let dom = html_to_dom(&original_data, url.clone());
@@ -85,10 +123,14 @@ pub async fn process_url(
 
                     // 翻译DOM内容
                     let translated_dom = tokio::runtime::Handle::current()
-                        .block_on(translate_dom_content(
+                        .block_on(translate_dom_content_with_cache(
                             dom,
                             &_target_lang_clone,
                             Some(&translation_config.api_url),
+                            &translation_cache,
+                            translation_concurrency.as_ref(),
+                            Some(&cancellation),
+                            Url::parse(&url).ok().as_ref(),
                         ))
                         .map_err(|e| MonolithError::new(&format!("翻译错误: {}", e)))?;
 
@@ -98,6 +140,7 @@ pub async fn process_url(
 
                 #[cfg(not(feature = "translation"))]
                 {
+                    let _ = concurrent_translation;
                     tracing::warn!("翻译功能未启用，返回原文内容");
                     Ok(original_result)
                 }
@@ -137,6 +180,9 @@ pub async fn process_url(
     };
 
     let original_html = String::from_utf8_lossy(&original_data).to_string();
+    state
+        .page_cache
+        .insert_original(&url, original_title.clone(), original_html.clone());
     
     // 处理译文（如果需要）
     let translated_html = if let Some(future) = translated_future {
@@ -194,6 +240,15 @@ pub async fn process_url(
         None
     };
 
+    let mut headers = HeaderMap::new();
+    #[cfg(feature = "translation")]
+    if translated_html.is_some() {
+        let lang = crate::translation::load_translation_config(&target_lang, None).document_lang();
+        if let Ok(value) = axum::http::HeaderValue::from_str(&lang) {
+            headers.insert(axum::http::header::CONTENT_LANGUAGE, value);
+        }
+    }
+
     // 生成预览URL
     let preview_url = format!("/preview/{}", urlencoding::encode(&url));
 
@@ -209,7 +264,7 @@ pub async fn process_url(
     };
 
     tracing::info!("URL处理完成: {}", url);
-    Ok(Json(response))
+    Ok((headers, Json(response)))
 }
 
 /// 非 web feature 的占位函数
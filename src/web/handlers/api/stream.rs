@@ -0,0 +1,329 @@
+//! 逐步推送译文的翻译流
+//!
+//! 客户端建立 WebSocket 连接后发送一条 JSON 文本消息 `{"url": ..., "target_lang": ...}`，
+//! 服务器随后依次发送以 `type` 字段区分的 JSON 消息：
+//!
+//! 1. `skeleton`：嵌入资源后的原文页面；
+//! 2. `update`：每写回一条译文发送一次，`node_path` 为从文档根节点开始逐级的
+//!    `childNodes` 下标（以 `/` 分隔），`attr` 不为空时表示更新该元素的属性；
+//! 3. `done` 或 `error`：翻译结束，服务器随即关闭连接。
+//!
+//! 翻译所用的 DOM 由发送给客户端的页面原样解析得到，客户端解析同一份 HTML 后按相同的下标
+//! 即可找到对应节点。由于会改变页面结构，双语段落和 `<template>` 翻译在流中不启用。
+//!
+//! 客户端断开连接或超过 `translation_timeout` 时翻译被取消，不再发送剩余的批次；
+//! 超时的流仍以 `done` 结束，`cancelled` 为 `true`。
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[cfg(feature = "web")]
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        State,
+    },
+    response::Response,
+};
+#[cfg(feature = "web")]
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+
+use markup5ever_rcdom::Handle;
+
+use crate::core::create_monolithic_document;
+use crate::parsers::html::ancestors;
+use crate::web::types::{AppState, TranslationStreamMessage, TranslationStreamRequest};
+
+/// 逐步推送译文的 WebSocket 接口
+#[cfg(feature = "web")]
+pub async fn stream_translation(
+    State(state): State<Arc<AppState>>,
+    upgrade: WebSocketUpgrade,
+) -> Response {
+    upgrade.on_upgrade(move |socket| serve_stream(socket, state))
+}
+
+/// 读取客户端的请求并转发翻译线程产生的消息，直到翻译结束或客户端断开连接
+#[cfg(feature = "web")]
+async fn serve_stream(mut socket: WebSocket, state: Arc<AppState>) {
+    let request = match read_request(&mut socket).await {
+        Some(Ok(request)) => request,
+        Some(Err(error)) => {
+            send_message(&mut socket, &TranslationStreamMessage::Error { error }).await;
+            let _ = socket.send(Message::Close(None)).await;
+            return;
+        }
+        None => return,
+    };
+    tracing::info!(
+        "翻译流请求: {} ({})",
+        request.url,
+        request.target_lang.as_deref().unwrap_or("zh")
+    );
+
+    let (sender, mut receiver) = unbounded_channel();
+    // 连接结束时守卫取消令牌，翻译不再发送剩余的批次
+    #[cfg(feature = "translation")]
+    let cancellation = tokio_util::sync::CancellationToken::new();
+    #[cfg(feature = "translation")]
+    let _cancel_on_disconnect = cancellation.clone().drop_guard();
+    tokio::task::spawn_blocking(move || {
+        translate_to_stream(
+            &state,
+            request,
+            &sender,
+            #[cfg(feature = "translation")]
+            cancellation,
+        )
+    });
+
+    loop {
+        tokio::select! {
+            message = receiver.recv() => match message {
+                Some(message) => {
+                    if !send_message(&mut socket, &message).await {
+                        return;
+                    }
+                }
+                None => break,
+            },
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+    let _ = socket.send(Message::Close(None)).await;
+}
+
+/// 等待客户端发送的第一条文本消息；连接在此之前关闭时返回 `None`
+#[cfg(feature = "web")]
+async fn read_request(socket: &mut WebSocket) -> Option<Result<TranslationStreamRequest, String>> {
+    loop {
+        match socket.recv().await? {
+            Ok(Message::Text(text)) => {
+                return Some(
+                    serde_json::from_str(&text).map_err(|e| format!("无效的翻译流请求: {}", e)),
+                )
+            }
+            Ok(Message::Close(_)) | Err(_) => return None,
+            Ok(_) => {}
+        }
+    }
+}
+
+/// 以 JSON 文本消息发送，返回客户端是否仍在接收
+#[cfg(feature = "web")]
+async fn send_message(socket: &mut WebSocket, message: &TranslationStreamMessage) -> bool {
+    match serde_json::to_string(message) {
+        Ok(text) => socket.send(Message::Text(text)).await.is_ok(),
+        Err(_) => true,
+    }
+}
+
+/// 抓取页面并翻译，把产生的消息依次交给 `sender`
+///
+/// 在阻塞线程上运行，需要处在 tokio 运行时的上下文中。
+#[cfg(feature = "web")]
+fn translate_to_stream(
+    state: &AppState,
+    request: TranslationStreamRequest,
+    sender: &UnboundedSender<TranslationStreamMessage>,
+    #[cfg(feature = "translation")] cancellation: tokio_util::sync::CancellationToken,
+) {
+    let url = request.url;
+    let target_lang = request.target_lang.unwrap_or_else(|| "zh".to_string());
+    let session = state.session.with_options(state.monolith_options.clone());
+    let (data, title) = match create_monolithic_document(session, &url) {
+        Ok(result) => result,
+        Err(e) => {
+            let _ = sender.send(TranslationStreamMessage::Error {
+                error: e.to_string(),
+            });
+            return;
+        }
+    };
+    let _ = sender.send(TranslationStreamMessage::Skeleton {
+        html: String::from_utf8_lossy(&data).into_owned(),
+        title,
+    });
+
+    #[cfg(feature = "translation")]
+    {
+        use crate::parsers::html::html_to_dom;
+        use crate::translation::{
+            load_translation_config, processor::ProgressCallback, TranslationService,
+        };
+        use crate::web::types::TranslationStreamUpdate;
+
+        let mut config = load_translation_config(&target_lang, None);
+        config.bilingual = false;
+        config.translate_templates = false;
+
+        let updates = sender.clone();
+        let cancel_on_send_failure = cancellation.clone();
+        let callback = ProgressCallback::new(move |item, translated| {
+            let update = TranslationStreamUpdate {
+                node_path: node_path(&item.node),
+                attr: item.attr_name.clone(),
+                translated_text: translated.to_string(),
+            };
+            if updates
+                .send(TranslationStreamMessage::Update(update))
+                .is_err()
+            {
+                cancel_on_send_failure.cancel();
+            }
+        });
+
+        let dom = html_to_dom(&data, url.clone());
+        let result = TranslationService::new(config).and_then(|service| {
+            let mut service = service
+                .with_cache_manager(state.translation_cache.clone())
+                .with_progress_callback(callback);
+            if let Some(concurrency) = state.translation_concurrency.clone() {
+                service = service.with_language_concurrency(concurrency);
+            }
+            let runtime = tokio::runtime::Handle::current();
+            let timer = state.translation_timeout.map(|timeout| {
+                let cancellation = cancellation.clone();
+                runtime.spawn(async move {
+                    tokio::time::sleep(timeout).await;
+                    cancellation.cancel();
+                })
+            });
+            let result = runtime
+                .block_on(service.translate_dom_with_cancellation(dom, cancellation.clone()))
+                .map(|_| service.get_stats().snapshot());
+            if let Some(timer) = timer {
+                timer.abort();
+            }
+            result
+        });
+
+        let message = match result {
+            Ok(stats) => TranslationStreamMessage::Done {
+                translations_completed: stats.translations_completed,
+                items_failed: stats.items_failed,
+                cancelled: stats.translations_cancelled > 0,
+            },
+            Err(e) => {
+                tracing::warn!("翻译流失败: {} ({})", url, e);
+                TranslationStreamMessage::Error {
+                    error: e.to_string(),
+                }
+            }
+        };
+        let _ = sender.send(message);
+    }
+
+    #[cfg(not(feature = "translation"))]
+    {
+        let _ = target_lang;
+        let _ = sender.send(TranslationStreamMessage::Done {
+            translations_completed: 0,
+            items_failed: 0,
+            cancelled: false,
+        });
+    }
+}
+
+/// 节点在文档中的位置：从根节点开始逐级的子节点下标，以 `/` 分隔
+pub fn node_path(node: &Handle) -> String {
+    let chain: Vec<Handle> = ancestors(node).collect();
+    let mut indexes: Vec<String> = chain
+        .windows(2)
+        .map(|pair| {
+            pair[1]
+                .children
+                .borrow()
+                .iter()
+                .position(|child| Rc::ptr_eq(child, &pair[0]))
+                .unwrap_or(0)
+                .to_string()
+        })
+        .collect();
+
+    indexes.reverse();
+    indexes.join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::html::html_to_dom;
+
+    #[test]
+    fn test_node_path() {
+        let dom = html_to_dom(
+            b"<!DOCTYPE html><html><head></head><body><p>One</p><p>Two <b>three</b></p></body></html>",
+            "".to_string(),
+        );
+        let html = dom.document.children.borrow()[1].clone();
+        let body = html.children.borrow()[1].clone();
+        let second = body.children.borrow()[1].clone();
+        let bold_text = second.children.borrow()[1].children.borrow()[0].clone();
+
+        assert_eq!(node_path(&bold_text), "1/1/1/1/0");
+        assert_eq!(node_path(&dom.document), "");
+        // 父指针保持不变
+        assert_eq!(node_path(&bold_text), "1/1/1/1/0");
+    }
+
+    #[cfg(feature = "web")]
+    #[test]
+    fn test_translate_to_stream() {
+        let state = crate::web::test_state("translation-stream");
+        // 所有文本都有人工修正，翻译时不请求翻译服务
+        #[cfg(feature = "translation")]
+        state.translation_cache.insert_correction(
+            "Hello".to_string(),
+            "你好".to_string(),
+            "zh".to_string(),
+        );
+
+        let (sender, mut receiver) = unbounded_channel();
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _context = runtime.enter();
+        let request = TranslationStreamRequest {
+            url: "data:text/html,<p>Hello</p>".to_string(),
+            target_lang: None,
+        };
+        translate_to_stream(
+            &state,
+            request,
+            &sender,
+            #[cfg(feature = "translation")]
+            tokio_util::sync::CancellationToken::new(),
+        );
+        drop(sender);
+        let messages: Vec<serde_json::Value> = std::iter::from_fn(|| receiver.try_recv().ok())
+            .map(|message| serde_json::to_value(message).unwrap())
+            .collect();
+
+        assert_eq!(messages[0]["type"], "skeleton");
+        let skeleton = messages[0]["html"].as_str().unwrap();
+        assert!(skeleton.contains("<p>Hello</p>"));
+        #[cfg(feature = "translation")]
+        {
+            // 按更新中的路径可以在客户端解析的骨架页面中找到原文节点
+            let dom = html_to_dom(skeleton.as_bytes(), "".to_string());
+            let path = messages[1]["node_path"].as_str().unwrap();
+            let node = path.split('/').fold(dom.document.clone(), |node, index| {
+                let child = node.children.borrow()[index.parse::<usize>().unwrap()].clone();
+                child
+            });
+            match node.data {
+                markup5ever_rcdom::NodeData::Text { ref contents } => {
+                    assert_eq!(contents.borrow().as_ref(), "Hello")
+                }
+                _ => panic!("node_path does not point to a text node"),
+            }
+
+            assert_eq!(messages[1]["type"], "update");
+            assert_eq!(messages[1]["translated_text"], "你好");
+            assert_eq!(messages.len(), 3);
+        }
+        assert_eq!(messages.last().unwrap()["type"], "done");
+    }
+}
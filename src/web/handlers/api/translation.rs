@@ -5,7 +5,7 @@ use std::sync::Arc;
 #[cfg(feature = "web")]
 use axum::{
     extract::{Json as ExtractJson, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     response::Json,
 };
 
@@ -13,8 +13,10 @@ use axum::{
 use tokio::task;
 
 use crate::core::{create_monolithic_document, MonolithError};
-use crate::session::Session;
-use crate::web::types::{AppState, TranslateRequest, TranslateResponse};
+use crate::web::types::{
+    AppState, RetranslateRequest, TranslateRequest, TranslateResponse,
+    TranslationCorrectionRequest, TranslationCorrectionResponse,
+};
 
 /// 翻译 URL 处理器
 #[cfg(feature = "web")]
@@ -44,27 +46,44 @@ pub async fn translate_url(
     let original_future = {
         let url = url.clone();
         let options = options_original;
+        let base_session = state.session.clone();
         task::spawn_blocking(move || {
-            let session = Session::new(None, None, options);
+            let session = base_session.with_options(options);
             create_monolithic_document(session, &url)
         })
     };
 
+    // 客户端断开连接时处理器的 future 被丢弃，守卫随之取消令牌，翻译不再发送剩余的批次
+    #[cfg(feature = "translation")]
+    let translation_cancel = tokio_util::sync::CancellationToken::new();
+    #[cfg(feature = "translation")]
+    let _cancel_on_disconnect = translation_cancel.clone().drop_guard();
+
     let translated_future = {
         let url = url.clone();
         let options = options_translated;
+        let base_session = state.session.clone();
         let _target_lang_clone = target_lang.clone();
+        #[cfg(feature = "translation")]
+        let translation_cache = state.translation_cache.clone();
+        #[cfg(feature = "translation")]
+        let translation_concurrency = state.translation_concurrency.clone();
+        #[cfg(feature = "translation")]
+        let cancellation = translation_cancel.clone();
         task::spawn_blocking(
             move || -> Result<(Vec<u8>, Option<String>), MonolithError> {
                 // 先获取原始内容
-                let session = Session::new(None, None, options.clone());
+                let session = base_session.with_options(options.clone());
                 let original_result = create_monolithic_document(session, &url)?;
 
                 // 如果启用了翻译功能，进行翻译
                 #[cfg(feature = "translation")]
                 {
                     use crate::html::{html_to_dom, serialize_document};
-                    use crate::translation::{load_translation_config, translate_dom_content};
+                    use crate::translation::{
+                        load_translation_config, translate_dom_content_with_cache,
+                    };
+                    use crate::utils::url::Url;
 
                     let (original_data, title) = original_result;
                     let dom = html_to_dom(&original_data, url.clone());
@@ -74,10 +93,14 @@ pub async fn translate_url(
 
                     // 翻译需要在异步上下文中运行，这里简化处理
                     let translated_dom = tokio::runtime::Handle::current()
-                        .block_on(translate_dom_content(
+                        .block_on(translate_dom_content_with_cache(
                             dom,
                             &_target_lang_clone,
                             Some(&translation_config.api_url),
+                            &translation_cache,
+                            translation_concurrency.as_ref(),
+                            Some(&cancellation),
+                            Url::parse(&url).ok().as_ref(),
                         ))
                         .map_err(|e| MonolithError::new(&format!("Translation error: {}", e)))?;
 
@@ -183,6 +206,216 @@ pub async fn translate_url(
     Ok(Json(response))
 }
 
+/// 提交译文修正
+///
+/// 修正写入持久化存储和共享缓存，之后的翻译请求优先使用人工修正。
+#[cfg(feature = "web")]
+pub async fn submit_translation_correction(
+    State(state): State<Arc<AppState>>,
+    ExtractJson(request): ExtractJson<TranslationCorrectionRequest>,
+) -> Result<Json<TranslationCorrectionResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let source_text = request.source_text.trim().to_string();
+    let target_lang = request.target_lang.trim().to_lowercase();
+    let corrected_text = request.corrected_text.trim().to_string();
+
+    if source_text.is_empty() || target_lang.is_empty() || corrected_text.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({
+                "error": true,
+                "message": "source_text, target_lang and corrected_text must not be empty"
+            })),
+        ));
+    }
+
+    #[cfg(feature = "translation")]
+    {
+        let store = Arc::clone(&state.translation_corrections);
+        let (original, corrected, lang) = (
+            source_text.clone(),
+            corrected_text.clone(),
+            target_lang.clone(),
+        );
+        let saved = tokio::task::spawn_blocking(move || store.save(&original, &corrected, &lang))
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|result| result.map_err(|e| e.to_string()));
+        if let Err(e) = saved {
+            tracing::error!("保存译文修正失败: {}", e);
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({
+                    "error": true,
+                    "message": format!("Failed to save correction: {}", e)
+                })),
+            ));
+        }
+        state.translation_cache.insert_correction(
+            source_text.clone(),
+            corrected_text.clone(),
+            target_lang.clone(),
+        );
+        tracing::info!("已保存译文修正 ({}): {}", target_lang, source_text);
+
+        Ok(Json(TranslationCorrectionResponse {
+            status: "saved".to_string(),
+            source_text,
+            target_lang,
+            corrected_text,
+        }))
+    }
+
+    #[cfg(not(feature = "translation"))]
+    {
+        let _ = state;
+        Err((
+            StatusCode::NOT_IMPLEMENTED,
+            Json(serde_json::json!({
+                "error": true,
+                "message": "Translation feature not enabled"
+            })),
+        ))
+    }
+}
+
+/// 把已缓存的原文页面翻译成新的语言
+///
+/// 原文由 `/api/process` 处理时保存，这里不重新抓取页面和资源；页面未缓存时返回 404，
+/// 客户端应改用 `/api/process`。`Content-Language` 为译文的语言。
+#[cfg(feature = "web")]
+pub async fn retranslate_cached_page(
+    State(state): State<Arc<AppState>>,
+    ExtractJson(request): ExtractJson<RetranslateRequest>,
+) -> Result<(HeaderMap, Json<TranslateResponse>), (StatusCode, Json<serde_json::Value>)> {
+    let target_lang = request.target_lang.trim().to_lowercase();
+    if target_lang.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({
+                "error": true,
+                "message": "target_lang must not be empty"
+            })),
+        ));
+    }
+
+    let cached = state.page_cache.get(&request.url);
+    let original_html = match cached.as_ref().and_then(|page| page.original_html.clone()) {
+        Some(html) => html,
+        None => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({
+                    "error": true,
+                    "message": "Page is not cached, use /api/process"
+                })),
+            ));
+        }
+    };
+    let title = cached.and_then(|page| page.title);
+    tracing::info!("重新翻译已缓存的页面: {} ({})", request.url, target_lang);
+
+    #[cfg(feature = "translation")]
+    {
+        let url = request.url.clone();
+        let html = original_html.clone();
+        let options = state.monolith_options.clone();
+        let translation_cache = state.translation_cache.clone();
+        let translation_concurrency = state.translation_concurrency.clone();
+        // 客户端断开连接时取消翻译
+        let cancellation = tokio_util::sync::CancellationToken::new();
+        let _cancel_on_disconnect = cancellation.clone().drop_guard();
+        let translation_config = crate::translation::load_translation_config(&target_lang, None);
+        let content_language = translation_config.document_lang();
+
+        let translated = task::spawn_blocking(move || -> Result<String, MonolithError> {
+            use crate::parsers::html::{html_to_dom, serialize_document};
+            use crate::translation::translate_dom_content_with_cache;
+            use crate::utils::url::Url;
+
+            let document_url = Url::parse(&url).ok();
+            let dom = html_to_dom(html.as_bytes(), url);
+            let translated_dom = tokio::runtime::Handle::current()
+                .block_on(translate_dom_content_with_cache(
+                    dom,
+                    &target_lang,
+                    Some(&translation_config.api_url),
+                    &translation_cache,
+                    translation_concurrency.as_ref(),
+                    Some(&cancellation),
+                    document_url.as_ref(),
+                ))
+                .map_err(|e| MonolithError::new(&format!("Translation error: {}", e)))?;
+            let data = serialize_document(translated_dom, "UTF-8".to_string(), &options);
+            Ok(String::from_utf8_lossy(&data).to_string())
+        })
+        .await;
+
+        let mut translated_html = match translated {
+            Ok(Ok(html)) => html,
+            Ok(Err(e)) => {
+                tracing::error!("重新翻译失败: {}", e);
+                return Err((
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(serde_json::json!({
+                        "error": true,
+                        "message": format!("Failed to process translation: {}", e)
+                    })),
+                ));
+            }
+            Err(e) => {
+                return Err((
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(serde_json::json!({
+                        "error": true,
+                        "message": format!("Task execution error: {}", e)
+                    })),
+                ));
+            }
+        };
+
+        if state.monolith_options.rewrite_links {
+            use crate::parsers::link_rewriter::rewrite_links_in_html;
+
+            let translation_base_path = state
+                .monolith_options
+                .translation_base_path
+                .as_deref()
+                .unwrap_or("/preview/");
+            match rewrite_links_in_html(&translated_html, &request.url, Some(translation_base_path))
+            {
+                Ok(rewritten_html) => translated_html = rewritten_html,
+                Err(e) => tracing::warn!("链接重写失败: {}, 使用原始翻译结果", e),
+            }
+        }
+
+        let mut headers = HeaderMap::new();
+        if let Ok(value) = axum::http::HeaderValue::from_str(&content_language) {
+            headers.insert(axum::http::header::CONTENT_LANGUAGE, value);
+        }
+        Ok((
+            headers,
+            Json(TranslateResponse {
+                original_html: original_html.to_string(),
+                translated_html,
+                title,
+                url: request.url,
+            }),
+        ))
+    }
+
+    #[cfg(not(feature = "translation"))]
+    {
+        let _ = (original_html, title);
+        Err((
+            StatusCode::NOT_IMPLEMENTED,
+            Json(serde_json::json!({
+                "error": true,
+                "message": "Translation feature not enabled"
+            })),
+        ))
+    }
+}
+
 /// 向后兼容的翻译处理器（已弃用）
 #[cfg(feature = "web")]
 pub async fn translate_url_deprecated(
@@ -205,12 +438,13 @@ pub async fn translate_url_deprecated(
             no_images: Some(false),
             no_fonts: Some(false),
             timeout: Some(30),
+            concurrent_translation: None,
         }),
     };
     
     // 调用新的统一处理器
     match super::process::process_url(State(state), ExtractJson(process_request)).await {
-        Ok(Json(process_response)) => {
+        Ok((_, Json(process_response))) => {
             let translate_response = TranslateResponse {
                 original_html: process_response.original_html.unwrap_or_default(),
                 translated_html: process_response.translated_html.unwrap_or_default(),
@@ -229,8 +463,89 @@ pub async fn translate_url() -> Result<String, String> {
     Err("Web feature not enabled".to_string())
 }
 
+/// 非 web feature 的占位函数
+#[cfg(not(feature = "web"))]
+pub async fn submit_translation_correction() -> Result<String, String> {
+    Err("Web feature not enabled".to_string())
+}
+
+/// 非 web feature 的占位函数
+#[cfg(not(feature = "web"))]
+pub async fn retranslate_cached_page() -> Result<String, String> {
+    Err("Web feature not enabled".to_string())
+}
+
 /// 非 web feature 的占位函数
 #[cfg(not(feature = "web"))]
 pub async fn translate_url_deprecated() -> Result<String, String> {
     Err("Web feature not enabled".to_string())
-}
\ No newline at end of file
+}
+#[cfg(all(test, feature = "web"))]
+mod tests {
+    use super::*;
+    use crate::web::test_state;
+
+    fn retranslate(
+        state: &Arc<AppState>,
+        url: &str,
+    ) -> Result<(HeaderMap, Json<TranslateResponse>), (StatusCode, Json<serde_json::Value>)> {
+        let request = RetranslateRequest {
+            url: url.to_string(),
+            target_lang: "ZH".to_string(),
+        };
+        tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(retranslate_cached_page(
+                State(Arc::clone(state)),
+                ExtractJson(request),
+            ))
+    }
+
+    #[test]
+    fn test_retranslate_uncached_page() {
+        let state = test_state("retranslate-uncached");
+        let (status, Json(body)) = match retranslate(&state, "https://example.com/missing") {
+            Ok(_) => panic!("uncached page was retranslated"),
+            Err(error) => error,
+        };
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["message"], "Page is not cached, use /api/process");
+    }
+
+    #[cfg(feature = "translation")]
+    #[test]
+    fn test_retranslate_cached_page() {
+        let state = test_state("retranslate-cached");
+        let url = "https://example.com/page";
+        state.page_cache.insert_original(
+            url,
+            Some("Greeting".to_string()),
+            "<html><head></head><body><p>Hello</p></body></html>".to_string(),
+        );
+        // 所有文本都有人工修正，翻译时不请求翻译服务
+        state.translation_cache.insert_correction(
+            "Hello".to_string(),
+            "你好".to_string(),
+            "zh".to_string(),
+        );
+
+        let (headers, Json(response)) = retranslate(&state, url).unwrap();
+        assert_eq!(response.url, url);
+        assert_eq!(response.title.as_deref(), Some("Greeting"));
+        assert!(response.original_html.contains("<p>Hello</p>"));
+        assert!(response.translated_html.contains("你好"));
+        assert!(!response.translated_html.contains(">Hello<"));
+        assert_eq!(
+            headers
+                .get(axum::http::header::CONTENT_LANGUAGE)
+                .and_then(|value| value.to_str().ok()),
+            Some("zh")
+        );
+        // 缓存的原文保持不变
+        assert!(state
+            .page_cache
+            .get(url)
+            .and_then(|page| page.original_html)
+            .is_some_and(|html| html.contains("<p>Hello</p>")));
+    }
+}
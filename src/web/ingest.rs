@@ -0,0 +1,530 @@
+//! 批量入库
+//!
+//! 对成千上万个URL构建文档库时，进程崩溃不应丢失进度。本模块把每个URL的处理状态
+//! （待处理/处理中/已完成/失败）保存在 redb 数据库中，处理完的文档保存在 [`LibraryServiceV2`] 中：
+//!
+//! - 重复入队是幂等的：已完成或已在队列中的URL会被跳过，失败的URL重新排队重试；
+//! - 至少处理一次：文档先写入文档库，之后才标记为已完成。重新打开数据库时，上次中断时仍在
+//!   处理中的URL回到待处理状态，重新处理时覆盖文档库中已保存的文档；
+//! - 失败的URL连同错误信息和尝试次数可以单独导出。
+//!
+//! 数据库文件在第一次入队时才创建，见 [`LazyDatabase`]。读写数据库的方法会阻塞，
+//! 异步环境中应放在阻塞线程中调用。
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use redb::{ReadableTable, TableDefinition};
+use serde::{Deserialize, Serialize};
+
+use crate::core::{create_monolithic_document, MonolithError, MonolithOptions};
+use crate::parsers::html::{content_fingerprint, html_to_dom};
+use crate::session::Session;
+use crate::utils::storage::LazyDatabase;
+use crate::utils::url::Url;
+use crate::web::library::LibraryServiceV2;
+
+/// URL -> 序列化的 [`IngestEntry`]
+const ENTRIES_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("ingest_entries");
+
+/// URL 的处理状态
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IngestStatus {
+    Pending,
+    InProgress,
+    Done,
+    Failed,
+}
+
+/// 单个URL的入库记录
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct IngestEntry {
+    pub url: String,
+    pub status: IngestStatus,
+    /// 已尝试处理的次数
+    pub attempts: u32,
+    /// 最近一次失败的错误信息
+    pub error: Option<String>,
+    pub title: Option<String>,
+    /// 已入库文档的内容指纹，见 [`content_fingerprint`]
+    #[serde(default)]
+    pub fingerprint: Option<String>,
+    pub updated_at: DateTime<Utc>,
+    /// 入队顺序，待处理的URL按此顺序处理
+    seq: u64,
+}
+
+/// 一次入队操作的结果
+#[derive(Serialize, Debug, Default, PartialEq, Eq)]
+pub struct EnqueueSummary {
+    /// 新加入队列的URL数量
+    pub added: usize,
+    /// 之前失败、重新排队的URL数量
+    pub retried: usize,
+    /// 已完成或已在队列中而跳过的URL数量
+    pub skipped: usize,
+    /// 无法解析为 HTTP/HTTPS URL 的输入
+    pub invalid: Vec<String>,
+}
+
+/// 入库进度
+#[derive(Serialize, Debug, Default, PartialEq, Eq)]
+pub struct IngestProgress {
+    pub total: usize,
+    pub pending: usize,
+    pub in_progress: usize,
+    pub done: usize,
+    pub failed: usize,
+    /// 后台处理任务是否正在运行
+    pub running: bool,
+}
+
+/// 批量入库队列
+pub struct BulkIngest {
+    db: LazyDatabase,
+    entries: Mutex<HashMap<String, IngestEntry>>,
+    worker_running: AtomicBool,
+    /// 保存处理完的文档
+    library: Arc<LibraryServiceV2>,
+}
+
+impl BulkIngest {
+    /// 打开入库状态数据库，文件不存在时等到第一次入队再创建
+    ///
+    /// 上次中断时处于处理中的URL会被重置为待处理。
+    pub fn open(path: &Path, library: Arc<LibraryServiceV2>) -> Result<Self, MonolithError> {
+        let db = LazyDatabase::open(path).map_err(storage_error)?;
+        let entries = match db.get() {
+            Some(db) => Self::restore_entries(db)?,
+            None => HashMap::new(),
+        };
+
+        Ok(Self {
+            db,
+            entries: Mutex::new(entries),
+            worker_running: AtomicBool::new(false),
+            library,
+        })
+    }
+
+    /// 读取所有入库记录，把处理中的URL重置为待处理
+    fn restore_entries(db: &redb::Database) -> Result<HashMap<String, IngestEntry>, MonolithError> {
+        let mut entries = HashMap::new();
+        let write_txn = db.begin_write().map_err(storage_error)?;
+        {
+            let mut table = write_txn.open_table(ENTRIES_TABLE).map_err(storage_error)?;
+
+            for item in table.iter().map_err(storage_error)? {
+                let (_, value) = item.map_err(storage_error)?;
+                let entry: IngestEntry =
+                    serde_json::from_slice(value.value()).map_err(storage_error)?;
+                entries.insert(entry.url.clone(), entry);
+            }
+
+            for entry in entries.values_mut() {
+                if entry.status == IngestStatus::InProgress {
+                    entry.status = IngestStatus::Pending;
+                    table
+                        .insert(entry.url.as_str(), encode_entry(entry)?.as_slice())
+                        .map_err(storage_error)?;
+                }
+            }
+        }
+        write_txn.commit().map_err(storage_error)?;
+
+        Ok(entries)
+    }
+
+    /// 将URL列表加入队列
+    pub fn enqueue<S: AsRef<str>>(&self, urls: &[S]) -> Result<EnqueueSummary, MonolithError> {
+        let mut summary = EnqueueSummary::default();
+        let mut entries = self.entries.lock().unwrap();
+        let mut changed = Vec::new();
+        let mut next_seq = entries.values().map(|e| e.seq + 1).max().unwrap_or(0);
+
+        for url in urls {
+            let url = url.as_ref().trim();
+            let parsed = match Url::parse(url) {
+                Ok(parsed) if parsed.scheme() == "http" || parsed.scheme() == "https" => parsed,
+                _ => {
+                    summary.invalid.push(url.to_string());
+                    continue;
+                }
+            };
+            let key = parsed.to_string();
+
+            match entries.get_mut(&key) {
+                Some(entry) if entry.status == IngestStatus::Failed => {
+                    entry.status = IngestStatus::Pending;
+                    entry.seq = next_seq;
+                    entry.updated_at = Utc::now();
+                    summary.retried += 1;
+                }
+                Some(_) => {
+                    summary.skipped += 1;
+                    continue;
+                }
+                None => {
+                    entries.insert(
+                        key.clone(),
+                        IngestEntry {
+                            url: key.clone(),
+                            status: IngestStatus::Pending,
+                            attempts: 0,
+                            error: None,
+                            title: None,
+                            fingerprint: None,
+                            updated_at: Utc::now(),
+                            seq: next_seq,
+                        },
+                    );
+                    summary.added += 1;
+                }
+            }
+            next_seq += 1;
+            changed.push(entries[&key].clone());
+        }
+
+        self.persist(&changed)?;
+        Ok(summary)
+    }
+
+    /// 取出下一个待处理的URL并标记为处理中
+    pub fn take_next(&self) -> Result<Option<String>, MonolithError> {
+        let mut entries = self.entries.lock().unwrap();
+        let next = entries
+            .values_mut()
+            .filter(|entry| entry.status == IngestStatus::Pending)
+            .min_by_key(|entry| entry.seq);
+
+        match next {
+            Some(entry) => {
+                entry.status = IngestStatus::InProgress;
+                entry.attempts += 1;
+                entry.updated_at = Utc::now();
+                let entry = entry.clone();
+                self.persist(std::slice::from_ref(&entry))?;
+                Ok(Some(entry.url))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// 记录URL的处理结果；成功时先把文档保存到文档库，再标记为已完成
+    ///
+    /// 文档保存失败时URL标记为失败，可以重新入队重试。
+    pub fn complete(
+        &self,
+        url: &str,
+        result: Result<(Vec<u8>, Option<String>), MonolithError>,
+    ) -> Result<(), MonolithError> {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries
+            .get_mut(url)
+            .ok_or_else(|| MonolithError::new(&format!("Unknown ingest URL: {}", url)))?;
+        entry.updated_at = Utc::now();
+
+        let stored = result.and_then(|(data, title)| {
+            // 从保存的文档重新解析，与重复入库时的计算方式一致
+            let fingerprint =
+                content_fingerprint(&html_to_dom(&data, "UTF-8".to_string()).document);
+            self.library.store_page(url, title.clone(), data)?;
+            Ok((title, fingerprint))
+        });
+
+        match stored {
+            Ok((title, fingerprint)) => {
+                entry.status = IngestStatus::Done;
+                entry.error = None;
+                entry.title = title;
+                entry.fingerprint = Some(fingerprint);
+                let entry = entry.clone();
+                self.persist(std::slice::from_ref(&entry))
+            }
+            Err(error) => {
+                entry.status = IngestStatus::Failed;
+                entry.error = Some(error.to_string());
+                let entry = entry.clone();
+                self.persist(std::slice::from_ref(&entry))
+            }
+        }
+    }
+
+    /// 获取当前进度
+    pub fn progress(&self) -> IngestProgress {
+        let entries = self.entries.lock().unwrap();
+        let mut progress = IngestProgress {
+            total: entries.len(),
+            running: self.worker_running.load(Ordering::SeqCst),
+            ..Default::default()
+        };
+
+        for entry in entries.values() {
+            match entry.status {
+                IngestStatus::Pending => progress.pending += 1,
+                IngestStatus::InProgress => progress.in_progress += 1,
+                IngestStatus::Done => progress.done += 1,
+                IngestStatus::Failed => progress.failed += 1,
+            }
+        }
+
+        progress
+    }
+
+    /// 获取失败的URL列表（按入队顺序）
+    pub fn failed(&self) -> Vec<IngestEntry> {
+        let entries = self.entries.lock().unwrap();
+        let mut failed: Vec<IngestEntry> = entries
+            .values()
+            .filter(|entry| entry.status == IngestStatus::Failed)
+            .cloned()
+            .collect();
+        failed.sort_by_key(|entry| entry.seq);
+        failed
+    }
+
+    /// 在后台依次处理待处理的URL（已在运行时不会重复启动）
+    pub fn start_worker(self: &Arc<Self>, options: MonolithOptions) {
+        if self.worker_running.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let ingest = Arc::clone(self);
+        tokio::spawn(async move {
+            loop {
+                let queue = Arc::clone(&ingest);
+                let next = tokio::task::spawn_blocking(move || queue.take_next())
+                    .await
+                    .unwrap_or_else(|e| Err(MonolithError::new(&format!("任务执行失败: {}", e))));
+                let url = match next {
+                    Ok(Some(url)) => url,
+                    Ok(None) => break,
+                    Err(e) => {
+                        tracing::error!("读取入库队列失败: {}", e);
+                        break;
+                    }
+                };
+
+                let session_options = options.clone();
+                let target = url.clone();
+                let result = tokio::task::spawn_blocking(move || {
+                    let session = Session::new(None, None, session_options);
+                    create_monolithic_document(session, &target)
+                })
+                .await
+                .unwrap_or_else(|e| Err(MonolithError::new(&format!("任务执行失败: {}", e))));
+
+                if let Err(e) = &result {
+                    tracing::warn!("入库失败: {} ({})", url, e);
+                }
+                let queue = Arc::clone(&ingest);
+                let completed = url.clone();
+                let saved = tokio::task::spawn_blocking(move || queue.complete(&completed, result))
+                    .await
+                    .unwrap_or_else(|e| Err(MonolithError::new(&format!("任务执行失败: {}", e))));
+                if let Err(e) = saved {
+                    tracing::error!("保存入库状态失败: {} ({})", url, e);
+                }
+            }
+
+            ingest.worker_running.store(false, Ordering::SeqCst);
+
+            // 退出前有新的URL入队时重新启动
+            if ingest.progress().pending > 0 {
+                ingest.start_worker(options);
+            }
+        });
+    }
+
+    fn persist(&self, entries: &[IngestEntry]) -> Result<(), MonolithError> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        let db = self.db.get_or_create().map_err(storage_error)?;
+        let write_txn = db.begin_write().map_err(storage_error)?;
+        {
+            let mut table = write_txn.open_table(ENTRIES_TABLE).map_err(storage_error)?;
+            for entry in entries {
+                table
+                    .insert(entry.url.as_str(), encode_entry(entry)?.as_slice())
+                    .map_err(storage_error)?;
+            }
+        }
+        write_txn.commit().map_err(storage_error)
+    }
+}
+
+fn encode_entry(entry: &IngestEntry) -> Result<Vec<u8>, MonolithError> {
+    serde_json::to_vec(entry).map_err(storage_error)
+}
+
+fn storage_error<E: std::fmt::Display>(error: E) -> MonolithError {
+    MonolithError::new(&format!("Ingest storage error: {}", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::env::web::LibraryBackendKind;
+    use crate::web::library::DomainStats;
+
+    fn temp_db(name: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "monolith-ingest-{}-{}.redb",
+            name,
+            std::process::id()
+        ));
+        remove(&path);
+        path
+    }
+
+    /// 文档保存在入库状态文件旁边的目录中
+    fn temp_library(path: &Path) -> Arc<LibraryServiceV2> {
+        let dir = path.with_extension("pages");
+        Arc::new(LibraryServiceV2::open(LibraryBackendKind::Filesystem, &dir).unwrap())
+    }
+
+    fn remove(path: &Path) {
+        let _ = std::fs::remove_file(path);
+        let _ = std::fs::remove_dir_all(path.with_extension("pages"));
+    }
+
+    #[test]
+    fn test_enqueue_is_idempotent() {
+        let path = temp_db("enqueue");
+        let ingest = BulkIngest::open(&path, temp_library(&path)).unwrap();
+
+        let summary = ingest
+            .enqueue(&[
+                "https://example.com/a",
+                "https://example.com/b",
+                "not a url",
+            ])
+            .unwrap();
+        assert_eq!(summary.added, 2);
+        assert_eq!(summary.invalid, vec!["not a url".to_string()]);
+
+        let summary = ingest.enqueue(&["https://example.com/a"]).unwrap();
+        assert_eq!(summary.added, 0);
+        assert_eq!(summary.skipped, 1);
+        assert_eq!(ingest.progress().pending, 2);
+
+        drop(ingest);
+        remove(&path);
+    }
+
+    #[test]
+    fn test_completed_pages_are_stored() {
+        let path = temp_db("stats");
+        let library = temp_library(&path);
+        let ingest = BulkIngest::open(&path, Arc::clone(&library)).unwrap();
+
+        // 第一次入队之前不创建数据库文件
+        assert!(!path.exists());
+
+        let urls = [
+            "https://example.com/a",
+            "https://example.com/b",
+            "https://docs.example.org/c",
+        ];
+        ingest.enqueue(&urls).unwrap();
+        for (i, _) in urls.iter().enumerate() {
+            let url = ingest.take_next().unwrap().unwrap();
+            let result = if i == 2 {
+                Err(MonolithError::new("connection refused"))
+            } else {
+                Ok((vec![b'x'; 10 * (i + 1)], None))
+            };
+            ingest.complete(&url, result).unwrap();
+        }
+
+        let stats = library.compute_stats().unwrap();
+        assert_eq!(stats.documents, 2);
+        assert_eq!(stats.total_size_bytes, 30);
+        assert_eq!(
+            stats.domains.get("example.com"),
+            Some(&DomainStats {
+                documents: 2,
+                size_bytes: 30
+            })
+        );
+        assert!(!stats.domains.contains_key("docs.example.org"));
+
+        drop(ingest);
+        remove(&path);
+    }
+
+    #[test]
+    fn test_resume_skips_done_and_retries_interrupted_and_failed() {
+        let path = temp_db("resume");
+        {
+            let ingest = BulkIngest::open(&path, temp_library(&path)).unwrap();
+            ingest
+                .enqueue(&[
+                    "https://example.com/a",
+                    "https://example.com/b",
+                    "https://example.com/c",
+                ])
+                .unwrap();
+
+            let a = ingest.take_next().unwrap().unwrap();
+            assert_eq!(a, "https://example.com/a");
+            ingest
+                .complete(&a, Ok((b"<html></html>".to_vec(), Some("A".to_string()))))
+                .unwrap();
+
+            let b = ingest.take_next().unwrap().unwrap();
+            ingest
+                .complete(&b, Err(MonolithError::new("connection refused")))
+                .unwrap();
+
+            // 模拟崩溃：c 处于处理中时进程退出
+            assert_eq!(
+                ingest.take_next().unwrap().unwrap(),
+                "https://example.com/c"
+            );
+        }
+
+        let ingest = BulkIngest::open(&path, temp_library(&path)).unwrap();
+        let progress = ingest.progress();
+        assert_eq!(
+            (progress.done, progress.failed, progress.pending),
+            (1, 1, 1)
+        );
+        let page = temp_library(&path)
+            .page("https://example.com/a")
+            .unwrap()
+            .unwrap();
+        assert_eq!(page.data, b"<html></html>".to_vec());
+        assert_eq!(page.meta.title.as_deref(), Some("A"));
+
+        let failed = ingest.failed();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].url, "https://example.com/b");
+        assert_eq!(failed[0].attempts, 1);
+        assert_eq!(failed[0].error.as_deref(), Some("connection refused"));
+
+        // 重新入队：已完成的跳过，失败的重试
+        let summary = ingest
+            .enqueue(&["https://example.com/a", "https://example.com/b"])
+            .unwrap();
+        assert_eq!((summary.skipped, summary.retried), (1, 1));
+        assert_eq!(
+            ingest.take_next().unwrap().unwrap(),
+            "https://example.com/c"
+        );
+        assert_eq!(
+            ingest.take_next().unwrap().unwrap(),
+            "https://example.com/b"
+        );
+        assert_eq!(ingest.take_next().unwrap(), None);
+
+        drop(ingest);
+        remove(&path);
+    }
+}
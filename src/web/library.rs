@@ -0,0 +1,549 @@
+//! 文档库
+//!
+//! 批量入库处理完的文档保存在文档库中。[`LibraryStore`] 定义了文档库需要的存储操作（按URL读取、
+//! 保存、删除，按域名列出和统计），[`LibraryServiceV2`] 在其上提供入库服务和 Web 接口使用的
+//! 操作，并缓存最近一次的统计结果。存储方式由 `MONOLITH_WEB_LIBRARY_BACKEND` 选择：
+//!
+//! - [`RedbLibraryStore`]：文档库目录中的 `library.redb`，默认使用；
+//! - [`FsLibraryStore`]：文档库目录中的 `pages/`，每个文档保存为一个 HTML 文件和一个元数据文件，
+//!   便于直接查看或用其他工具备份。
+//!
+//! 两种存储都在保存第一个文档时才创建文件。存储的方法会阻塞，异步环境中应放在阻塞线程中调用。
+
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use redb::{ReadableTable, TableDefinition};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::core::MonolithError;
+use crate::env::web::LibraryBackendKind;
+use crate::utils::storage::LazyDatabase;
+use crate::utils::url::Url;
+
+/// URL -> 文档内容
+const PAGES_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("library_pages");
+/// URL -> JSON 格式的 [`PageMeta`]
+const META_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("library_meta");
+
+/// 已入库文档的元数据
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PageMeta {
+    pub url: String,
+    pub title: Option<String>,
+    pub size_bytes: u64,
+    pub stored_at: DateTime<Utc>,
+}
+
+/// 已入库的文档
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StoredPage {
+    pub meta: PageMeta,
+    pub data: Vec<u8>,
+}
+
+/// 单个域名下的已入库文档
+#[derive(Serialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct DomainStats {
+    pub documents: usize,
+    pub size_bytes: u64,
+}
+
+/// 文档库统计
+#[derive(Serialize, Debug, Clone)]
+pub struct LibraryStats {
+    /// 已入库的文档数量
+    pub documents: usize,
+    /// 已入库文档的总大小
+    pub total_size_bytes: u64,
+    /// 按域名统计，键为主机名
+    pub domains: BTreeMap<String, DomainStats>,
+    /// 本次统计的计算时间
+    pub computed_at: DateTime<Utc>,
+}
+
+/// 文档库的存储后端
+pub trait LibraryStore: Send + Sync {
+    /// 读取文档，不存在时返回 `None`
+    fn get(&self, url: &str) -> Result<Option<StoredPage>, MonolithError>;
+
+    /// 保存文档，覆盖同一URL之前的文档
+    fn put(&self, page: &StoredPage) -> Result<(), MonolithError>;
+
+    /// 按URL顺序列出文档的元数据，指定 `domain`（主机名）时只列出该域名下的文档
+    fn list(&self, domain: Option<&str>) -> Result<Vec<PageMeta>, MonolithError>;
+
+    /// 删除文档，返回文档是否存在
+    fn delete(&self, url: &str) -> Result<bool, MonolithError>;
+
+    /// 按域名统计所有文档，默认由 [`list`](Self::list) 的结果汇总
+    fn stats(&self) -> Result<LibraryStats, MonolithError> {
+        let mut stats = LibraryStats {
+            documents: 0,
+            total_size_bytes: 0,
+            domains: BTreeMap::new(),
+            computed_at: Utc::now(),
+        };
+        for meta in self.list(None)? {
+            stats.documents += 1;
+            stats.total_size_bytes += meta.size_bytes;
+            let domain = stats.domains.entry(page_domain(&meta.url)).or_default();
+            domain.documents += 1;
+            domain.size_bytes += meta.size_bytes;
+        }
+        Ok(stats)
+    }
+}
+
+impl<S: LibraryStore + ?Sized> LibraryStore for Box<S> {
+    fn get(&self, url: &str) -> Result<Option<StoredPage>, MonolithError> {
+        (**self).get(url)
+    }
+
+    fn put(&self, page: &StoredPage) -> Result<(), MonolithError> {
+        (**self).put(page)
+    }
+
+    fn list(&self, domain: Option<&str>) -> Result<Vec<PageMeta>, MonolithError> {
+        (**self).list(domain)
+    }
+
+    fn delete(&self, url: &str) -> Result<bool, MonolithError> {
+        (**self).delete(url)
+    }
+
+    fn stats(&self) -> Result<LibraryStats, MonolithError> {
+        (**self).stats()
+    }
+}
+
+/// 保存在单个 redb 文件中的文档库
+pub struct RedbLibraryStore {
+    db: LazyDatabase,
+}
+
+impl RedbLibraryStore {
+    /// 打开文档库数据库，文件不存在时等到保存第一个文档再创建
+    pub fn open(path: &Path) -> Result<Self, MonolithError> {
+        let db = LazyDatabase::open(path).map_err(storage_error)?;
+        if let Some(existing) = db.get() {
+            // 确保两张表都存在，之后的读事务才能打开它们
+            let write_txn = existing.begin_write().map_err(storage_error)?;
+            write_txn.open_table(PAGES_TABLE).map_err(storage_error)?;
+            write_txn.open_table(META_TABLE).map_err(storage_error)?;
+            write_txn.commit().map_err(storage_error)?;
+        }
+        Ok(Self { db })
+    }
+}
+
+impl LibraryStore for RedbLibraryStore {
+    fn get(&self, url: &str) -> Result<Option<StoredPage>, MonolithError> {
+        let Some(db) = self.db.get() else {
+            return Ok(None);
+        };
+        let read_txn = db.begin_read().map_err(storage_error)?;
+        let meta_table = read_txn.open_table(META_TABLE).map_err(storage_error)?;
+        let meta = match meta_table.get(url).map_err(storage_error)? {
+            Some(meta) => decode_meta(meta.value())?,
+            None => return Ok(None),
+        };
+        let pages = read_txn.open_table(PAGES_TABLE).map_err(storage_error)?;
+        let data = pages.get(url).map_err(storage_error)?;
+        Ok(data.map(|data| StoredPage {
+            meta,
+            data: data.value().to_vec(),
+        }))
+    }
+
+    fn put(&self, page: &StoredPage) -> Result<(), MonolithError> {
+        let db = self.db.get_or_create().map_err(storage_error)?;
+        let write_txn = db.begin_write().map_err(storage_error)?;
+        {
+            let url = page.meta.url.as_str();
+            let mut pages = write_txn.open_table(PAGES_TABLE).map_err(storage_error)?;
+            pages
+                .insert(url, page.data.as_slice())
+                .map_err(storage_error)?;
+            let mut meta = write_txn.open_table(META_TABLE).map_err(storage_error)?;
+            meta.insert(url, encode_meta(&page.meta)?.as_slice())
+                .map_err(storage_error)?;
+        }
+        write_txn.commit().map_err(storage_error)
+    }
+
+    fn list(&self, domain: Option<&str>) -> Result<Vec<PageMeta>, MonolithError> {
+        let Some(db) = self.db.get() else {
+            return Ok(Vec::new());
+        };
+        let read_txn = db.begin_read().map_err(storage_error)?;
+        let table = read_txn.open_table(META_TABLE).map_err(storage_error)?;
+
+        let mut pages = Vec::new();
+        for item in table.iter().map_err(storage_error)? {
+            let (_, value) = item.map_err(storage_error)?;
+            let meta = decode_meta(value.value())?;
+            if in_domain(&meta, domain) {
+                pages.push(meta);
+            }
+        }
+        Ok(pages)
+    }
+
+    fn delete(&self, url: &str) -> Result<bool, MonolithError> {
+        let Some(db) = self.db.get() else {
+            return Ok(false);
+        };
+        let write_txn = db.begin_write().map_err(storage_error)?;
+        let existed = {
+            let mut pages = write_txn.open_table(PAGES_TABLE).map_err(storage_error)?;
+            pages.remove(url).map_err(storage_error)?;
+            let mut meta = write_txn.open_table(META_TABLE).map_err(storage_error)?;
+            let existed = meta.remove(url).map_err(storage_error)?.is_some();
+            existed
+        };
+        write_txn.commit().map_err(storage_error)?;
+        Ok(existed)
+    }
+}
+
+/// 每个文档保存为目录中一个文件的文档库
+///
+/// 文件名为URL的 SHA-256，`<hash>.html` 为文档内容，`<hash>.json` 为 [`PageMeta`]。
+/// 元数据文件最后写入，列出文档时只读取元数据文件，不完整的写入不会出现在列表中。
+pub struct FsLibraryStore {
+    dir: PathBuf,
+}
+
+impl FsLibraryStore {
+    /// 使用指定目录，目录不存在时等到保存第一个文档再创建
+    pub fn open(dir: &Path) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+        }
+    }
+
+    fn file_path(&self, url: &str, extension: &str) -> PathBuf {
+        let hash = format!("{:x}", Sha256::digest(url.as_bytes()));
+        self.dir.join(format!("{}.{}", hash, extension))
+    }
+
+    fn read_meta(&self, path: &Path) -> Result<Option<PageMeta>, MonolithError> {
+        match std::fs::read(path) {
+            Ok(data) => decode_meta(&data).map(Some),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(storage_error(e)),
+        }
+    }
+}
+
+impl LibraryStore for FsLibraryStore {
+    fn get(&self, url: &str) -> Result<Option<StoredPage>, MonolithError> {
+        let meta = match self.read_meta(&self.file_path(url, "json"))? {
+            Some(meta) => meta,
+            None => return Ok(None),
+        };
+        match std::fs::read(self.file_path(url, "html")) {
+            Ok(data) => Ok(Some(StoredPage { meta, data })),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(storage_error(e)),
+        }
+    }
+
+    fn put(&self, page: &StoredPage) -> Result<(), MonolithError> {
+        std::fs::create_dir_all(&self.dir).map_err(storage_error)?;
+        let url = page.meta.url.as_str();
+        write_file(&self.file_path(url, "html"), &page.data)?;
+        write_file(&self.file_path(url, "json"), &encode_meta(&page.meta)?)
+    }
+
+    fn list(&self, domain: Option<&str>) -> Result<Vec<PageMeta>, MonolithError> {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(storage_error(e)),
+        };
+
+        let mut pages = Vec::new();
+        for entry in entries {
+            let path = entry.map_err(storage_error)?.path();
+            if path.extension().and_then(|extension| extension.to_str()) != Some("json") {
+                continue;
+            }
+            match self.read_meta(&path) {
+                Ok(Some(meta)) if in_domain(&meta, domain) => pages.push(meta),
+                Ok(_) => {}
+                Err(e) => tracing::warn!("跳过无法读取的文档元数据 {}: {}", path.display(), e),
+            }
+        }
+        pages.sort_by(|a, b| a.url.cmp(&b.url));
+        Ok(pages)
+    }
+
+    fn delete(&self, url: &str) -> Result<bool, MonolithError> {
+        let existed = match std::fs::remove_file(self.file_path(url, "json")) {
+            Ok(()) => true,
+            Err(e) if e.kind() == ErrorKind::NotFound => false,
+            Err(e) => return Err(storage_error(e)),
+        };
+        match std::fs::remove_file(self.file_path(url, "html")) {
+            Ok(()) => Ok(existed),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(existed),
+            Err(e) => Err(storage_error(e)),
+        }
+    }
+}
+
+/// 文档库服务
+///
+/// 在 [`LibraryStore`] 之上保存入库的文档，并缓存最近一次计算的文档库统计。
+/// 默认的类型参数用于运行时按配置选择的存储，见 [`LibraryServiceV2::open`]。
+pub struct LibraryServiceV2<S: LibraryStore = Box<dyn LibraryStore>> {
+    store: S,
+    /// 最近一次计算的文档库统计
+    stats: Mutex<Option<LibraryStats>>,
+}
+
+impl LibraryServiceV2 {
+    /// 在文档库目录中打开指定方式的存储
+    pub fn open(backend: LibraryBackendKind, library_dir: &Path) -> Result<Self, MonolithError> {
+        let store: Box<dyn LibraryStore> = match backend {
+            LibraryBackendKind::Redb => {
+                Box::new(RedbLibraryStore::open(&library_dir.join("library.redb"))?)
+            }
+            LibraryBackendKind::Filesystem => {
+                Box::new(FsLibraryStore::open(&library_dir.join("pages")))
+            }
+        };
+        Ok(Self::new(store))
+    }
+}
+
+impl<S: LibraryStore> LibraryServiceV2<S> {
+    pub fn new(store: S) -> Self {
+        Self {
+            store,
+            stats: Mutex::new(None),
+        }
+    }
+
+    /// 保存处理完的文档，返回其元数据
+    pub fn store_page(
+        &self,
+        url: &str,
+        title: Option<String>,
+        data: Vec<u8>,
+    ) -> Result<PageMeta, MonolithError> {
+        let page = StoredPage {
+            meta: PageMeta {
+                url: url.to_string(),
+                title,
+                size_bytes: data.len() as u64,
+                stored_at: Utc::now(),
+            },
+            data,
+        };
+        self.store.put(&page)?;
+        Ok(page.meta)
+    }
+
+    /// 读取已入库的文档
+    pub fn page(&self, url: &str) -> Result<Option<StoredPage>, MonolithError> {
+        self.store.get(url)
+    }
+
+    /// 列出已入库的文档，指定 `domain` 时只列出该域名下的文档
+    pub fn pages(&self, domain: Option<&str>) -> Result<Vec<PageMeta>, MonolithError> {
+        self.store.list(domain)
+    }
+
+    /// 删除已入库的文档，返回文档是否存在
+    pub fn delete_page(&self, url: &str) -> Result<bool, MonolithError> {
+        self.store.delete(url)
+    }
+
+    /// 计算文档库统计
+    ///
+    /// 需要读取所有文档的元数据，文档较多时耗时较长。
+    pub fn compute_stats(&self) -> Result<LibraryStats, MonolithError> {
+        self.store.stats()
+    }
+
+    /// 重新计算文档库统计并更新缓存
+    pub fn refresh_stats(&self) -> Result<LibraryStats, MonolithError> {
+        let stats = self.compute_stats()?;
+        *self.stats.lock().unwrap() = Some(stats.clone());
+        Ok(stats)
+    }
+
+    /// 最近一次计算的文档库统计，尚未计算过时返回 `None`
+    pub fn cached_stats(&self) -> Option<LibraryStats> {
+        self.stats.lock().unwrap().clone()
+    }
+}
+
+impl<S: LibraryStore + 'static> LibraryServiceV2<S> {
+    /// 启动定期刷新文档库统计的后台任务
+    pub fn start_stats_refresher(self: &Arc<Self>, interval: Duration) {
+        let library = Arc::clone(self);
+        tokio::spawn(async move {
+            loop {
+                let refresher = Arc::clone(&library);
+                match tokio::task::spawn_blocking(move || refresher.refresh_stats()).await {
+                    Ok(Ok(_)) => {}
+                    Ok(Err(e)) => tracing::warn!("文档库统计失败: {}", e),
+                    Err(e) => tracing::warn!("文档库统计任务执行失败: {}", e),
+                }
+                tokio::time::sleep(interval).await;
+            }
+        });
+    }
+}
+
+/// 文档所在的域名（主机名），无法解析时为空
+fn page_domain(url: &str) -> String {
+    Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(|host| host.to_string()))
+        .unwrap_or_default()
+}
+
+fn in_domain(meta: &PageMeta, domain: Option<&str>) -> bool {
+    domain.is_none_or(|domain| page_domain(&meta.url) == domain)
+}
+
+/// 先写入临时文件再重命名，读取方不会看到写了一半的文件
+fn write_file(path: &Path, data: &[u8]) -> Result<(), MonolithError> {
+    let temp = path.with_extension("tmp");
+    std::fs::write(&temp, data).map_err(storage_error)?;
+    std::fs::rename(&temp, path).map_err(storage_error)
+}
+
+fn encode_meta(meta: &PageMeta) -> Result<Vec<u8>, MonolithError> {
+    serde_json::to_vec(meta).map_err(storage_error)
+}
+
+fn decode_meta(data: &[u8]) -> Result<PageMeta, MonolithError> {
+    serde_json::from_slice(data).map_err(storage_error)
+}
+
+fn storage_error<E: std::fmt::Display>(error: E) -> MonolithError {
+    MonolithError::new(&format!("Library storage error: {}", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("monolith-library-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    /// 两种存储共用的行为
+    fn check_store<S: LibraryStore>(library: LibraryServiceV2<S>, path: &Path) {
+        assert_eq!(library.page("https://example.com/a").unwrap(), None);
+        assert!(library.pages(None).unwrap().is_empty());
+        assert_eq!(library.compute_stats().unwrap().documents, 0);
+        assert!(!library.delete_page("https://example.com/a").unwrap());
+        // 保存第一个文档之前不创建文件
+        assert!(!path.exists());
+
+        library
+            .store_page("https://example.com/b", None, vec![b'x'; 20])
+            .unwrap();
+        library
+            .store_page("https://docs.example.org/c", None, vec![b'x'; 5])
+            .unwrap();
+        library
+            .store_page(
+                "https://example.com/a",
+                Some("Old".to_string()),
+                vec![b'x'; 1],
+            )
+            .unwrap();
+        let meta = library
+            .store_page(
+                "https://example.com/a",
+                Some("A".to_string()),
+                vec![b'x'; 10],
+            )
+            .unwrap();
+        assert!(path.exists());
+
+        let page = library.page("https://example.com/a").unwrap().unwrap();
+        assert_eq!(page.meta, meta);
+        assert_eq!(page.meta.title.as_deref(), Some("A"));
+        assert_eq!(page.data, vec![b'x'; 10]);
+
+        let urls = |pages: Vec<PageMeta>| -> Vec<String> {
+            pages.into_iter().map(|page| page.url).collect()
+        };
+        assert_eq!(
+            urls(library.pages(None).unwrap()),
+            [
+                "https://docs.example.org/c",
+                "https://example.com/a",
+                "https://example.com/b"
+            ]
+        );
+        assert_eq!(
+            urls(library.pages(Some("example.com")).unwrap()),
+            ["https://example.com/a", "https://example.com/b"]
+        );
+
+        let stats = library.refresh_stats().unwrap();
+        assert_eq!((stats.documents, stats.total_size_bytes), (3, 35));
+        assert_eq!(
+            stats.domains.get("example.com"),
+            Some(&DomainStats {
+                documents: 2,
+                size_bytes: 30
+            })
+        );
+        assert_eq!(
+            library.cached_stats().map(|cached| cached.computed_at),
+            Some(stats.computed_at)
+        );
+
+        assert!(library.delete_page("https://example.com/b").unwrap());
+        assert!(!library.delete_page("https://example.com/b").unwrap());
+        assert_eq!(library.page("https://example.com/b").unwrap(), None);
+        assert_eq!(library.compute_stats().unwrap().documents, 2);
+    }
+
+    #[test]
+    fn test_redb_store() {
+        let path = temp_path("redb");
+        check_store(
+            LibraryServiceV2::new(RedbLibraryStore::open(&path).unwrap()),
+            &path,
+        );
+
+        // 重新打开后文档仍然存在
+        let store = RedbLibraryStore::open(&path).unwrap();
+        assert_eq!(store.list(None).unwrap().len(), 2);
+        drop(store);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_filesystem_store() {
+        let path = temp_path("fs");
+        check_store(LibraryServiceV2::new(FsLibraryStore::open(&path)), &path);
+
+        // 写了一半的文档（没有元数据）不出现在列表中
+        std::fs::write(path.join("partial.html"), b"<html>").unwrap();
+        let store = FsLibraryStore::open(&path);
+        assert_eq!(store.list(None).unwrap().len(), 2);
+        let _ = std::fs::remove_dir_all(&path);
+    }
+}
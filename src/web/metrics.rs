@@ -0,0 +1,379 @@
+//! Prometheus 监控指标
+//!
+//! 以 Prometheus 文本格式（`text/plain; version=0.0.4`）导出服务运行指标，
+//! 由 `/metrics` 路由提供：
+//!
+//! - `monolith_http_requests_total` / `monolith_http_request_duration_seconds`：
+//!   按方法、路由模板和状态码统计的请求；
+//! - `monolith_asset_fetch_duration_seconds`：资源下载耗时，按成功与否区分；
+//! - `monolith_translation_*`：翻译批次、字符数以及缓存命中情况（需要 `translation` feature）。
+//!
+//! 路由标签使用匹配到的路由模板（例如 `/preview/*url`），未匹配的请求统一记为 `unmatched`，
+//! 避免标签基数随请求路径无限增长。
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "web")]
+use axum::{
+    extract::{MatchedPath, Request},
+    middleware::Next,
+    response::Response,
+};
+
+/// 耗时直方图的桶上限（秒）
+const DURATION_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0,
+];
+
+/// 固定分桶的耗时直方图
+#[derive(Debug)]
+pub struct Histogram {
+    /// 各桶的计数（非累计），最后一个元素对应 `+Inf`
+    buckets: Vec<AtomicU64>,
+    /// 观测值总和（微秒）
+    sum_micros: AtomicU64,
+    count: AtomicU64,
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self {
+            buckets: (0..=DURATION_BUCKETS.len())
+                .map(|_| AtomicU64::new(0))
+                .collect(),
+            sum_micros: AtomicU64::new(0),
+            count: AtomicU64::new(0),
+        }
+    }
+}
+
+impl Histogram {
+    /// 记录一次耗时
+    pub fn observe(&self, duration: Duration) {
+        let seconds = duration.as_secs_f64();
+        let index = DURATION_BUCKETS
+            .iter()
+            .position(|&bound| seconds <= bound)
+            .unwrap_or(DURATION_BUCKETS.len());
+        self.buckets[index].fetch_add(1, Ordering::Relaxed);
+        self.sum_micros
+            .fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 观测次数
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    /// 按 Prometheus 格式写出 `_bucket`、`_sum` 和 `_count` 样本
+    fn write_samples(&self, out: &mut String, name: &str, labels: &str) {
+        let separator = if labels.is_empty() { "" } else { "," };
+        let mut cumulative = 0;
+        for (i, bucket) in self.buckets.iter().enumerate() {
+            cumulative += bucket.load(Ordering::Relaxed);
+            let bound = DURATION_BUCKETS
+                .get(i)
+                .map(|bound| bound.to_string())
+                .unwrap_or_else(|| "+Inf".to_string());
+            let _ = writeln!(
+                out,
+                "{}_bucket{{{}{}le=\"{}\"}} {}",
+                name, labels, separator, bound, cumulative
+            );
+        }
+        let braces = if labels.is_empty() {
+            String::new()
+        } else {
+            format!("{{{}}}", labels)
+        };
+        let sum = self.sum_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0;
+        let _ = writeln!(out, "{}_sum{} {}", name, braces, sum);
+        let _ = writeln!(out, "{}_count{} {}", name, braces, self.count());
+    }
+}
+
+/// 服务监控指标
+#[derive(Debug, Default)]
+pub struct Metrics {
+    /// (方法, 路由模板, 状态码) -> 请求数
+    http_requests: Mutex<BTreeMap<(String, String, u16), u64>>,
+    /// 路由模板 -> 请求耗时
+    http_durations: Mutex<BTreeMap<String, Histogram>>,
+    asset_fetch_ok: Histogram,
+    asset_fetch_error: Histogram,
+}
+
+/// 进程内共享的指标实例
+pub fn global() -> &'static Metrics {
+    static METRICS: OnceLock<Metrics> = OnceLock::new();
+    METRICS.get_or_init(Metrics::default)
+}
+
+impl Metrics {
+    /// 记录一次 HTTP 请求
+    pub fn observe_request(&self, method: &str, route: &str, status: u16, duration: Duration) {
+        *self
+            .http_requests
+            .lock()
+            .unwrap()
+            .entry((method.to_string(), route.to_string(), status))
+            .or_insert(0) += 1;
+        self.http_durations
+            .lock()
+            .unwrap()
+            .entry(route.to_string())
+            .or_default()
+            .observe(duration);
+    }
+
+    /// 记录一次资源下载
+    pub fn observe_asset_fetch(&self, duration: Duration, success: bool) {
+        if success {
+            self.asset_fetch_ok.observe(duration);
+        } else {
+            self.asset_fetch_error.observe(duration);
+        }
+    }
+
+    /// 按 Prometheus 文本格式导出 HTTP 与资源下载指标
+    pub fn render(&self, out: &mut String) {
+        write_header(
+            out,
+            "monolith_http_requests_total",
+            "counter",
+            "HTTP requests by method, route and status code.",
+        );
+        for ((method, route, status), count) in self.http_requests.lock().unwrap().iter() {
+            let _ = writeln!(
+                out,
+                "monolith_http_requests_total{{method=\"{}\",route=\"{}\",status=\"{}\"}} {}",
+                escape_label(method),
+                escape_label(route),
+                status,
+                count
+            );
+        }
+
+        write_header(
+            out,
+            "monolith_http_request_duration_seconds",
+            "histogram",
+            "HTTP request latency by route.",
+        );
+        for (route, histogram) in self.http_durations.lock().unwrap().iter() {
+            histogram.write_samples(
+                out,
+                "monolith_http_request_duration_seconds",
+                &format!("route=\"{}\"", escape_label(route)),
+            );
+        }
+
+        write_header(
+            out,
+            "monolith_asset_fetch_duration_seconds",
+            "histogram",
+            "Time spent downloading page assets.",
+        );
+        self.asset_fetch_ok.write_samples(
+            out,
+            "monolith_asset_fetch_duration_seconds",
+            "result=\"success\"",
+        );
+        self.asset_fetch_error.write_samples(
+            out,
+            "monolith_asset_fetch_duration_seconds",
+            "result=\"error\"",
+        );
+    }
+}
+
+/// 导出进程内累计的翻译统计和共享缓存的命中情况
+#[cfg(feature = "translation")]
+pub fn render_translation(out: &mut String, cache: &crate::translation::CacheManager) {
+    let stats = crate::translation::global_stats().snapshot();
+    let counters: [(&str, &str, usize); 8] = [
+        (
+            "monolith_translation_texts_total",
+            "Translatable texts collected from documents.",
+            stats.texts_collected,
+        ),
+        (
+            "monolith_translation_batches_total",
+            "Translation batches sent to the backend.",
+            stats.batches_created,
+        ),
+        (
+            "monolith_translation_characters_total",
+            "Characters submitted for translation.",
+            stats.total_chars_processed,
+        ),
+        (
+            "monolith_translation_completed_total",
+            "Texts translated by the backend.",
+            stats.translations_completed,
+        ),
+        (
+            "monolith_translation_errors_total",
+            "Document translations that failed.",
+            stats.errors_encountered,
+        ),
+        (
+            "monolith_translation_failed_items_total",
+            "Texts left untranslated because their batch failed.",
+            stats.items_failed,
+        ),
+        (
+            "monolith_translation_skipped_total",
+            "Documents skipped because nothing needed translating.",
+            stats.skipped_empty,
+        ),
+        (
+            "monolith_translation_skipped_translated_total",
+            "Documents skipped because they were already marked as translated.",
+            stats.skipped_translated,
+        ),
+    ];
+    for (name, help, value) in counters {
+        write_header(out, name, "counter", help);
+        let _ = writeln!(out, "{} {}", name, value);
+    }
+
+    write_header(
+        out,
+        "monolith_translation_duration_seconds_total",
+        "counter",
+        "Total time spent translating documents.",
+    );
+    let _ = writeln!(
+        out,
+        "monolith_translation_duration_seconds_total {}",
+        stats.processing_time.as_secs_f64()
+    );
+
+    let cache_stats = cache.get_stats();
+    let cache_counters: [(&str, &str, u64); 3] = [
+        (
+            "monolith_translation_cache_hits_total",
+            "Translation cache lookups that found an entry.",
+            cache_stats.cache_hits,
+        ),
+        (
+            "monolith_translation_cache_misses_total",
+            "Translation cache lookups that found no entry.",
+            cache_stats.cache_misses,
+        ),
+        (
+            "monolith_translation_cache_evictions_total",
+            "Entries evicted from the translation cache.",
+            cache_stats.evictions,
+        ),
+    ];
+    for (name, help, value) in cache_counters {
+        write_header(out, name, "counter", help);
+        let _ = writeln!(out, "{} {}", name, value);
+    }
+
+    write_header(
+        out,
+        "monolith_translation_cache_entries",
+        "gauge",
+        "Entries currently held in the translation cache.",
+    );
+    let _ = writeln!(
+        out,
+        "monolith_translation_cache_entries {}",
+        cache_stats.total_entries
+    );
+
+    let lookups = cache_stats.cache_hits + cache_stats.cache_misses;
+    let hit_ratio = if lookups == 0 {
+        0.0
+    } else {
+        cache_stats.cache_hits as f64 / lookups as f64
+    };
+    write_header(
+        out,
+        "monolith_translation_cache_hit_ratio",
+        "gauge",
+        "Share of translation cache lookups that were hits.",
+    );
+    let _ = writeln!(out, "monolith_translation_cache_hit_ratio {}", hit_ratio);
+}
+
+/// 记录请求数和耗时的中间件
+#[cfg(feature = "web")]
+pub async fn track_requests(request: Request, next: Next) -> Response {
+    let started = Instant::now();
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+
+    let response = next.run(request).await;
+    global().observe_request(
+        &method,
+        &route,
+        response.status().as_u16(),
+        started.elapsed(),
+    );
+    response
+}
+
+fn write_header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+/// 转义标签值中的反斜杠、双引号和换行
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_buckets_are_cumulative() {
+        let histogram = Histogram::default();
+        histogram.observe(Duration::from_millis(3));
+        histogram.observe(Duration::from_millis(40));
+        histogram.observe(Duration::from_secs(60));
+
+        let mut out = String::new();
+        histogram.write_samples(&mut out, "t", "");
+        assert!(out.contains("t_bucket{le=\"0.005\"} 1\n"));
+        assert!(out.contains("t_bucket{le=\"0.05\"} 2\n"));
+        assert!(out.contains("t_bucket{le=\"30\"} 2\n"));
+        assert!(out.contains("t_bucket{le=\"+Inf\"} 3\n"));
+        assert!(out.contains("t_count 3\n"));
+    }
+
+    #[test]
+    fn test_render_requests() {
+        let metrics = Metrics::default();
+        metrics.observe_request("GET", "/preview/*url", 200, Duration::from_millis(20));
+        metrics.observe_request("GET", "/preview/*url", 200, Duration::from_millis(30));
+        metrics.observe_request("POST", "/api/process", 500, Duration::from_millis(1));
+
+        let mut out = String::new();
+        metrics.render(&mut out);
+        assert!(out.contains("# TYPE monolith_http_requests_total counter\n"));
+        assert!(out.contains(
+            "monolith_http_requests_total{method=\"GET\",route=\"/preview/*url\",status=\"200\"} 2\n"
+        ));
+        assert!(out
+            .contains("monolith_http_request_duration_seconds_count{route=\"/api/process\"} 1\n"));
+        assert!(out.contains("monolith_asset_fetch_duration_seconds_count{result=\"error\"} 0\n"));
+    }
+}
@@ -4,6 +4,10 @@
 
 pub mod config;
 pub mod handlers;
+pub mod ingest;
+pub mod library;
+pub mod metrics;
+pub mod page_cache;
 pub mod routes;
 pub mod theme;
 pub mod types;
@@ -22,6 +26,9 @@ use tower_http::{cors::CorsLayer, services::ServeDir};
 
 use crate::core::MonolithError;
 
+/// 共享资源缓存中小于该大小（字节）的资源只保存在内存中
+const SHARED_CACHE_FILE_SIZE_THRESHOLD: usize = 1024 * 10;
+
 /// Web 服务器
 pub struct WebServer {
     config: WebConfig,
@@ -45,9 +52,87 @@ impl WebServer {
         // 初始化主题管理器
         let theme_manager = std::sync::Mutex::new(theme::ThemeManager::new());
 
+        // 打开文档库和批量入库状态，恢复上次未完成的任务；文档库目录在第一次写入时才创建
+        let library_dir = std::path::Path::new(&self.config.library_dir);
+        let library = Arc::new(library::LibraryServiceV2::open(
+            self.config.library_backend,
+            library_dir,
+        )?);
+        library.start_stats_refresher(self.config.library_stats_interval);
+        let ingest = Arc::new(ingest::BulkIngest::open(
+            &library_dir.join("ingest.redb"),
+            Arc::clone(&library),
+        )?);
+        if ingest.progress().pending > 0 {
+            ingest.start_worker(self.monolith_options.clone());
+        }
+
+        // 所有请求复用同一个 HTTP 客户端；资源缓存的索引只保存在内存中，启动时丢弃上次的缓存文件
+        let cache = if self.config.shared_asset_cache {
+            std::fs::create_dir_all(library_dir).map_err(|e| {
+                MonolithError::new(&format!("Failed to create library dir: {}", e))
+            })?;
+            let cache_file = library_dir.join("asset-cache.redb");
+            let _ = std::fs::remove_file(&cache_file);
+            Some(crate::cache::Cache::new(
+                SHARED_CACHE_FILE_SIZE_THRESHOLD,
+                Some(cache_file.display().to_string()),
+            ))
+        } else {
+            None
+        };
+        // 阻塞的 HTTP 客户端不能在异步上下文中创建，在阻塞线程上建立会话
+        let options = self.monolith_options.clone();
+        let session = tokio::task::spawn_blocking(move || {
+            Arc::new(crate::session::Session::new(cache, None, options))
+        })
+        .await
+        .map_err(|e| MonolithError::new(&format!("Failed to create HTTP session: {}", e)))?;
+
+        // 按需确认翻译后端可用，严格模式下失败时拒绝启动
+        #[cfg(feature = "translation")]
+        check_translation_backend(self.config.translation_self_check).await?;
+
+        // 打开译文修正存储并装入共享缓存
+        #[cfg(feature = "translation")]
+        let (translation_cache, translation_corrections) = {
+            use crate::translation::{CacheConfig, CacheManager, CorrectionStore};
+
+            let cache = CacheManager::new(CacheConfig::default());
+            let store = CorrectionStore::open(&library_dir.join("corrections.redb"))
+                .map_err(|e| MonolithError::new(&e.to_string()))?;
+            let loaded = store
+                .load_into(&cache)
+                .map_err(|e| MonolithError::new(&e.to_string()))?;
+            println!("已加载 {} 条译文修正", loaded);
+            (cache, Arc::new(store))
+        };
+
+        // 按语言分配的并发额度在所有请求之间共享
+        #[cfg(feature = "translation")]
+        let translation_concurrency = {
+            use crate::env::EnvVar;
+            use crate::translation::{load_translation_config, LanguageConcurrency};
+
+            let target_lang = crate::env::translation::TargetLang::get_or_default("zh".to_string());
+            LanguageConcurrency::from_config(&load_translation_config(&target_lang, None))
+        };
+
         let app_state = Arc::new(AppState {
             monolith_options: self.monolith_options.clone(),
+            session,
             theme_manager: Arc::new(theme_manager),
+            library,
+            ingest,
+            page_cache: page_cache::PageCache::default(),
+            #[cfg(feature = "translation")]
+            translation_cache,
+            #[cfg(feature = "translation")]
+            translation_corrections,
+            #[cfg(feature = "translation")]
+            translation_concurrency,
+            #[cfg(feature = "translation")]
+            translation_timeout: self.config.translation_timeout,
         });
 
         let app = create_router(app_state, &self.config);
@@ -78,10 +163,44 @@ impl WebServer {
     }
 }
 
+/// 启动时的翻译后端自检
+#[cfg(all(feature = "web", feature = "translation"))]
+async fn check_translation_backend(
+    mode: crate::env::translation::SelfCheckMode,
+) -> Result<(), MonolithError> {
+    use crate::env::{translation::SelfCheckMode, EnvVar};
+    use crate::translation::{load_translation_config, TranslationService};
+
+    if mode == SelfCheckMode::Off {
+        return Ok(());
+    }
+
+    let target_lang = crate::env::translation::TargetLang::get_or_default("zh".to_string());
+    let result = match TranslationService::new(load_translation_config(&target_lang, None)) {
+        Ok(service) => service.self_check().await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(_) => {
+            println!("翻译服务自检通过");
+            Ok(())
+        }
+        Err(e) if mode == SelfCheckMode::Strict => Err(MonolithError::new(&e.to_string())),
+        Err(e) => {
+            tracing::warn!("{}", e);
+            Ok(())
+        }
+    }
+}
+
 /// 创建路由器
 #[cfg(feature = "web")]
 fn create_router(app_state: Arc<AppState>, config: &WebConfig) -> Router {
-    let mut app = create_routes().with_state(app_state);
+    // 请求指标只记录业务路由，不包括下面的静态文件服务
+    let mut app = create_routes()
+        .with_state(app_state)
+        .layer(axum::middleware::from_fn(metrics::track_requests));
 
     // 添加CORS支持
     app = app.layer(CorsLayer::permissive());
@@ -99,3 +218,45 @@ fn create_router(app_state: Arc<AppState>, config: &WebConfig) -> Router {
 
     app
 }
+
+/// 测试用的应用状态，存储文件放在临时目录中
+#[cfg(all(test, feature = "web"))]
+pub(crate) fn test_state(name: &str) -> Arc<AppState> {
+    let library_dir =
+        std::env::temp_dir().join(format!("monolith-web-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&library_dir);
+
+    let mut monolith_options = crate::core::MonolithOptions::default();
+    monolith_options.silent = true;
+    let library = Arc::new(
+        library::LibraryServiceV2::open(
+            crate::env::web::LibraryBackendKind::Redb,
+            &library_dir,
+        )
+        .unwrap(),
+    );
+    Arc::new(AppState {
+        monolith_options: monolith_options.clone(),
+        session: Arc::new(crate::session::Session::new(None, None, monolith_options)),
+        theme_manager: Arc::new(std::sync::Mutex::new(theme::ThemeManager::new())),
+        ingest: Arc::new(
+            ingest::BulkIngest::open(&library_dir.join("ingest.redb"), Arc::clone(&library))
+                .unwrap(),
+        ),
+        library,
+        page_cache: page_cache::PageCache::default(),
+        #[cfg(feature = "translation")]
+        translation_cache: crate::translation::CacheManager::new(
+            crate::translation::CacheConfig::default(),
+        ),
+        #[cfg(feature = "translation")]
+        translation_corrections: Arc::new(
+            crate::translation::CorrectionStore::open(&library_dir.join("corrections.redb"))
+                .unwrap(),
+        ),
+        #[cfg(feature = "translation")]
+        translation_concurrency: None,
+        #[cfg(feature = "translation")]
+        translation_timeout: None,
+    })
+}
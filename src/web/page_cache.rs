@@ -0,0 +1,151 @@
+//! 已嵌入资源的原文页面缓存
+//!
+//! 同一个页面被请求翻译成多种语言时，每次都重新抓取页面和全部资源是不必要的：资源嵌入的结果
+//! 与目标语言无关。`/api/process` 处理完原文后把嵌入资源、尚未翻译的 HTML 保存在这里，
+//! `/api/translation/retranslate` 直接翻译保存的原文，不再发起任何网络请求（翻译服务除外）。
+//!
+//! 缓存以 URL 为键，保存的是最近一次处理该页面时按请求选项（`no_css`、`no_images` 等）嵌入的结果。
+//! 缓存只保存在内存中，按插入顺序淘汰最早的页面，上限为 [`DEFAULT_CAPACITY`]。
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+
+/// 默认最多保存的页面数
+pub const DEFAULT_CAPACITY: usize = 64;
+
+/// 缓存的页面
+#[derive(Clone, Debug)]
+pub struct CachedHtml {
+    pub url: String,
+    pub title: Option<String>,
+    /// 嵌入资源之后、翻译之前的 HTML
+    pub original_html: Option<Arc<str>>,
+    pub cached_at: DateTime<Utc>,
+}
+
+/// 按 URL 保存原文页面，可在请求之间共享
+#[derive(Clone, Debug)]
+pub struct PageCache {
+    inner: Arc<Mutex<PageCacheInner>>,
+}
+
+#[derive(Debug)]
+struct PageCacheInner {
+    capacity: usize,
+    pages: HashMap<String, CachedHtml>,
+    /// 插入顺序，用于淘汰最早的页面
+    order: VecDeque<String>,
+}
+
+impl Default for PageCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl PageCache {
+    /// 创建最多保存 `capacity` 个页面的缓存，为 0 时不保存任何页面
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(PageCacheInner {
+                capacity,
+                pages: HashMap::new(),
+                order: VecDeque::new(),
+            })),
+        }
+    }
+
+    /// 保存页面的原文，已存在的页面会被替换并视为最新插入
+    pub fn insert_original(&self, url: &str, title: Option<String>, original_html: String) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.capacity == 0 {
+            return;
+        }
+
+        if inner.pages.contains_key(url) {
+            inner.order.retain(|cached| cached != url);
+        }
+        while inner.order.len() >= inner.capacity {
+            match inner.order.pop_front() {
+                Some(oldest) => {
+                    inner.pages.remove(&oldest);
+                }
+                None => break,
+            }
+        }
+
+        inner.order.push_back(url.to_string());
+        inner.pages.insert(
+            url.to_string(),
+            CachedHtml {
+                url: url.to_string(),
+                title,
+                original_html: Some(original_html.into()),
+                cached_at: Utc::now(),
+            },
+        );
+    }
+
+    /// 读取页面，未缓存时返回 `None`
+    pub fn get(&self, url: &str) -> Option<CachedHtml> {
+        self.inner.lock().unwrap().pages.get(url).cloned()
+    }
+
+    /// 删除页面，返回是否存在
+    pub fn remove(&self, url: &str) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        inner.order.retain(|cached| cached != url);
+        inner.pages.remove(url).is_some()
+    }
+
+    /// 清空缓存
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.pages.clear();
+        inner.order.clear();
+    }
+
+    /// 当前保存的页面数
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().pages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oldest_pages_are_evicted() {
+        let cache = PageCache::new(2);
+        cache.insert_original("https://a.example/", None, "a".to_string());
+        cache.insert_original("https://b.example/", None, "b".to_string());
+        // 重新插入的页面变为最新
+        cache.insert_original(
+            "https://a.example/",
+            Some("A".to_string()),
+            "a2".to_string(),
+        );
+        cache.insert_original("https://c.example/", None, "c".to_string());
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("https://b.example/").is_none());
+        let page = cache.get("https://a.example/").unwrap();
+        assert_eq!(page.title.as_deref(), Some("A"));
+        assert_eq!(page.original_html.as_deref(), Some("a2"));
+        assert!(cache.get("https://c.example/").is_some());
+    }
+
+    #[test]
+    fn test_zero_capacity_stores_nothing() {
+        let cache = PageCache::new(0);
+        cache.insert_original("https://a.example/", None, "a".to_string());
+        assert!(cache.is_empty());
+    }
+}
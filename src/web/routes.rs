@@ -22,9 +22,24 @@ pub fn create_routes() -> Router<Arc<AppState>> {
         // 统一的API端点 - 使用process作为主要处理入口
         .route("/api/process", post(process_url))
         .route("/api/bookmarklet", get(generate_bookmarklet))
+        // 译文修正
+        .route("/api/translation/correction", post(submit_translation_correction))
+        // 把已缓存的原文翻译成其他语言，不重新抓取
+        .route("/api/translation/retranslate", post(retranslate_cached_page))
+        // 逐步推送译文
+        .route("/api/translation/stream", get(stream_translation))
+        // 批量入库
+        .route("/api/ingest", post(enqueue_ingest))
+        .route("/api/ingest/progress", get(get_ingest_progress))
+        .route("/api/ingest/failed", get(get_ingest_failed))
+        .route("/api/ingest/stats", get(get_library_stats))
         // 基础管理功能
         .route("/api/cache/clear", post(clear_cache))
         .route("/api/cache/stats", get(get_cache_stats))
+        // 完整流程自检（结果会缓存，不要用作存活探针）
+        .route("/api/health/pipeline", get(get_pipeline_health))
+        // Prometheus 监控指标
+        .route("/metrics", get(get_metrics))
         // 向后兼容的API别名（已弃用，建议使用/api/process）
         .route("/api/translate", post(translate_url_deprecated))
         .route("/api/content", post(get_content_deprecated))
@@ -10,7 +10,26 @@ use serde::{Deserialize, Serialize};
 #[derive(Clone)]
 pub struct AppState {
     pub monolith_options: crate::core::MonolithOptions,
+    /// 所有请求共享的基础会话，持有 HTTP 客户端（及其连接池）和可选的资源缓存；
+    /// 每个请求通过 `Session::with_options` 创建自己的会话
+    pub session: std::sync::Arc<crate::session::Session>,
     pub theme_manager: std::sync::Arc<std::sync::Mutex<crate::web::theme::ThemeManager>>,
+    pub ingest: std::sync::Arc<crate::web::ingest::BulkIngest>,
+    /// 已入库的文档
+    pub library: std::sync::Arc<crate::web::library::LibraryServiceV2>,
+    /// 已嵌入资源的原文页面，用于翻译成其他语言时跳过抓取
+    pub page_cache: crate::web::page_cache::PageCache,
+    /// 所有翻译请求共享的缓存，包含人工修正
+    #[cfg(feature = "translation")]
+    pub translation_cache: crate::translation::CacheManager,
+    #[cfg(feature = "translation")]
+    pub translation_corrections: std::sync::Arc<crate::translation::CorrectionStore>,
+    /// 配置了语言并发权重时，所有翻译请求共享的按语言分配的并发额度
+    #[cfg(feature = "translation")]
+    pub translation_concurrency: Option<crate::translation::LanguageConcurrency>,
+    /// 翻译流的最长翻译时间，超时后返回已完成的部分译文
+    #[cfg(feature = "translation")]
+    pub translation_timeout: Option<std::time::Duration>,
 }
 
 /// 翻译请求
@@ -32,6 +51,71 @@ pub struct TranslateResponse {
     pub url: String,
 }
 
+/// 重新翻译已缓存页面的请求
+#[cfg(feature = "web")]
+#[derive(Deserialize)]
+pub struct RetranslateRequest {
+    pub url: String,
+    pub target_lang: String,
+}
+
+/// 翻译流请求，客户端连接后发送的第一条消息
+#[cfg(feature = "web")]
+#[derive(Deserialize)]
+pub struct TranslationStreamRequest {
+    pub url: String,
+    pub target_lang: Option<String>,
+}
+
+/// 翻译流发送给客户端的消息，以 `type` 字段区分种类
+#[cfg(feature = "web")]
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TranslationStreamMessage {
+    /// 嵌入资源后的原文页面
+    Skeleton { html: String, title: Option<String> },
+    /// 一条写回的译文
+    Update(TranslationStreamUpdate),
+    /// 翻译结束；`cancelled` 表示超时后提前结束，只有部分译文
+    Done {
+        translations_completed: usize,
+        items_failed: usize,
+        cancelled: bool,
+    },
+    /// 抓取或翻译失败
+    Error { error: String },
+}
+
+/// 翻译流中的单条译文更新
+#[cfg(feature = "web")]
+#[derive(Serialize)]
+pub struct TranslationStreamUpdate {
+    /// 节点位置，见 `handlers::api::stream::node_path`
+    pub node_path: String,
+    /// 译文所属的属性，文本节点为空
+    pub attr: Option<String>,
+    pub translated_text: String,
+}
+
+/// 译文修正请求
+#[cfg(feature = "web")]
+#[derive(Deserialize)]
+pub struct TranslationCorrectionRequest {
+    pub source_text: String,
+    pub target_lang: String,
+    pub corrected_text: String,
+}
+
+/// 译文修正响应
+#[cfg(feature = "web")]
+#[derive(Serialize)]
+pub struct TranslationCorrectionResponse {
+    pub status: String,
+    pub source_text: String,
+    pub target_lang: String,
+    pub corrected_text: String,
+}
+
 /// 翻译信息结构（重新导出给其他模块使用）
 #[cfg(feature = "web")]
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -111,6 +195,8 @@ pub struct ProcessOptions {
     pub no_images: Option<bool>,
     pub no_fonts: Option<bool>,
     pub timeout: Option<u64>,
+    /// 翻译与资源嵌入并行执行（仅在请求翻译时生效）
+    pub concurrent_translation: Option<bool>,
 }
 
 /// 统一处理响应
@@ -127,6 +213,62 @@ pub struct ProcessResponse {
     pub target_lang: Option<String>,
 }
 
+/// 批量入库请求
+#[cfg(feature = "web")]
+#[derive(Deserialize)]
+pub struct IngestRequest {
+    pub urls: Vec<String>,
+}
+
+/// 文档库统计查询参数
+#[cfg(feature = "web")]
+#[derive(Deserialize)]
+pub struct LibraryStatsQuery {
+    /// 为 true 时重新计算，而不是返回后台缓存的结果
+    pub fresh: Option<bool>,
+}
+
+/// 流水线自检查询参数
+#[cfg(feature = "web")]
+#[derive(Deserialize)]
+pub struct PipelineHealthQuery {
+    /// 为 true 时立即重新运行，而不是返回最近一次的结果
+    pub fresh: Option<bool>,
+}
+
+/// 流水线自检中单个阶段的结果
+#[cfg(feature = "web")]
+#[derive(Serialize, Clone)]
+pub struct PipelineStageReport {
+    pub name: &'static str,
+    pub ok: bool,
+    /// 未启用对应功能时跳过
+    pub skipped: bool,
+    pub duration_ms: u64,
+    pub error: Option<String>,
+}
+
+/// 流水线自检结果
+#[cfg(feature = "web")]
+#[derive(Serialize, Clone)]
+pub struct PipelineHealthReport {
+    pub healthy: bool,
+    pub stages: Vec<PipelineStageReport>,
+    pub total_ms: u64,
+    /// 运行时间（RFC 3339）
+    pub checked_at: String,
+    /// 是否为缓存的结果
+    pub cached: bool,
+}
+
+/// 批量入库响应
+#[cfg(feature = "web")]
+#[derive(Serialize)]
+pub struct IngestResponse {
+    pub summary: crate::web::ingest::EnqueueSummary,
+    pub progress: crate::web::ingest::IngestProgress,
+}
+
 // 非 web feature 的占位类型
 #[cfg(not(feature = "web"))]
 #[derive(Clone)]
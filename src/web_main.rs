@@ -29,6 +29,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     use monolith::env::{web, EnvVar};
     let mut bind_addr = web::BindAddress::get_or_default("127.0.0.1".to_string());
     let mut port = web::Port::get_or_default(7080);
+    let mut self_check = monolith::env::translation::SelfCheck::get_or_default(
+        monolith::env::translation::SelfCheckMode::Off,
+    );
 
     // 简单的命令行参数解析
     let mut i = 1;
@@ -55,6 +58,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     std::process::exit(1);
                 }
             }
+            "--self-check" => {
+                if i + 1 < args.len() {
+                    self_check = monolith::env::translation::SelfCheck::parse(&args[i + 1])
+                        .unwrap_or_else(|e| {
+                            eprintln!("Error: {}", e);
+                            std::process::exit(1);
+                        });
+                    i += 2;
+                } else {
+                    eprintln!("Error: --self-check requires a mode (off, warn, strict)");
+                    std::process::exit(1);
+                }
+            }
             "--help" | "-h" => {
                 print_help();
                 return Ok(());
@@ -71,6 +87,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut monolith_options = MonolithOptions::default();
     monolith_options.silent = true; // Web 模式下静默运行
     monolith_options.output_format = monolith::core::MonolithOutputFormat::HTML;
+    // 服务器按用户提交的地址抓取，默认拒绝本地文件和内部地址
+    let mut fetch_policy = monolith::fetch_policy::FetchPolicy::server();
+    fetch_policy.deny_internal_addresses = !web::AllowInternalFetch::get_or_default(false);
+    monolith_options.fetch_policy = Some(fetch_policy);
 
     // 创建 Web 配置，优先使用命令行参数覆盖环境变量
     let mut web_config = WebConfig::from_env().unwrap_or_else(|e| {
@@ -79,12 +99,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             bind_addr: "127.0.0.1".to_string(),
             port: 7080,
             static_dir: Some("static".to_string()),
+            library_dir: "library".to_string(),
+            library_backend: monolith::env::web::LibraryBackendKind::Redb,
+            library_stats_interval: std::time::Duration::from_secs(300),
+            translation_self_check: self_check,
+            shared_asset_cache: false,
+            translation_timeout: Some(std::time::Duration::from_secs(300)),
         }
     });
     
     // 命令行参数覆盖环境变量配置
     web_config.bind_addr = bind_addr;
     web_config.port = port;
+    web_config.translation_self_check = self_check;
     
     // 验证最终配置
     if let Err(e) = web_config.validate() {
@@ -109,6 +136,8 @@ fn print_help() {
     println!("OPTIONS:");
     println!("    -b, --bind <ADDRESS>     Bind address [default: 127.0.0.1]");
     println!("    -p, --port <PORT>        Port number [default: 7080]");
+    println!("        --self-check <MODE>  Probe the translation backend at startup:");
+    println!("                             off, warn, strict [default: off]");
     println!("    -h, --help               Print help information");
     println!();
     println!("EXAMPLES:");
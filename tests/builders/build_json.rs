@@ -0,0 +1,94 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::builders::{build_json, JsonDocument};
+    use monolith::html;
+    use monolith::session::EmbeddedResource;
+    use monolith::url::Url;
+
+    #[test]
+    fn title_resources_and_links() {
+        let html = "<html><head><title>Page \"one\"</title></head><body>\
+                    <a href=\"/about\">About</a><a href=\"#top\">Top</a>\
+                    <a href=\"https://other.com/\">Other</a></body></html>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let base_url = Url::parse("https://example.com/index.html").unwrap();
+        let resources = vec![EmbeddedResource {
+            url: "https://example.com/style.css".to_string(),
+            media_type: "text/css".to_string(),
+            size: 42,
+        }];
+
+        assert_eq!(
+            build_json(&dom.document, "UTF-8", &base_url, &resources),
+            "{\n  \"title\": \"Page \\\"one\\\"\",\n  \"charset\": \"UTF-8\",\n  \
+             \"resources\": [\n    {\"url\": \"https://example.com/style.css\", \
+             \"media_type\": \"text/css\", \"size\": 42}\n  ],\n  \
+             \"links\": [\n    \"https://example.com/about\",\n    \"https://other.com/\"\n  ]\n}\n"
+        );
+    }
+
+    #[test]
+    fn social_metadata() {
+        let html = "<html><head><meta property=\"og:title\" content=\"Launch\">\
+                    <meta property=\"og:image\" content=\"/cover.png\">\
+                    <meta name=\"twitter:card\" content=\"summary_large_image\"></head></html>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let base_url = Url::parse("https://example.com/post/").unwrap();
+
+        assert_eq!(
+            build_json(&dom.document, "UTF-8", &base_url, &[]),
+            "{\n  \"title\": null,\n  \"charset\": \"UTF-8\",\n  \
+             \"social\": {\"title\": \"Launch\", \"description\": null, \
+             \"image\": \"https://example.com/cover.png\", \"type\": \"summary_large_image\"},\n  \
+             \"resources\": [],\n  \"links\": []\n}\n"
+        );
+    }
+
+    #[test]
+    fn translations() {
+        let dom = html::html_to_dom(&"<p>你好</p>".as_bytes().to_vec(), "".to_string());
+        let base_url = Url::parse("https://example.com/").unwrap();
+        let document = JsonDocument::new(&dom.document, "UTF-8", &base_url, &[])
+            .with_translations(vec![("Hello\n".to_string(), "你好".to_string())]);
+
+        assert_eq!(
+            document.to_json(),
+            "{\n  \"title\": null,\n  \"charset\": \"UTF-8\",\n  \"resources\": [],\n  \
+             \"links\": [],\n  \"translations\": [\n    \
+             {\"source\": \"Hello\\n\", \"target\": \"你好\"}\n  ]\n}\n"
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::builders::build_json;
+    use monolith::html;
+    use monolith::url::Url;
+
+    #[test]
+    fn links_without_http_base() {
+        let dom = html::html_to_dom(
+            &"<a href=\"page.html\">Page</a>".as_bytes().to_vec(),
+            "".to_string(),
+        );
+        let base_url = Url::parse("data:text/html,").unwrap();
+
+        // Relative links cannot be resolved against a data URL
+        assert!(build_json(&dom.document, "UTF-8", &base_url, &[]).contains("\"links\": []"));
+    }
+}
@@ -0,0 +1,78 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::builders::{build_text, TextBuilder};
+    use monolith::html;
+
+    #[test]
+    fn paragraphs_and_inline_elements() {
+        let html = "<html><head><title>Page</title></head>\
+                    <body><h1>Title</h1><p>Hello <b>bold</b>\n   world</p><p>Second</p></body></html>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+
+        assert_eq!(build_text(&dom.document), "Title\n\nHello bold world\n\nSecond\n");
+    }
+
+    #[test]
+    fn line_breaks_and_list_items() {
+        let html = "<div>one<br>two</div><ul><li>a</li><li>b</li></ul>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+
+        assert_eq!(build_text(&dom.document), "one\ntwo\n\na\nb\n");
+    }
+
+    #[test]
+    fn skip_invisible_elements() {
+        let html = "<body><script>var a = 1;</script><style>p { color: red; }</style>\
+                    <p>Visible</p><p hidden>Hidden</p><template><p>Template</p></template></body>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+
+        assert_eq!(build_text(&dom.document), "Visible\n");
+    }
+
+    #[test]
+    fn table_cells() {
+        let html = "<table><tr><th>Name</th><th>Value</th></tr><tr><td>a</td><td>1</td></tr></table>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+
+        assert_eq!(build_text(&dom.document), "Name Value\na 1\n");
+    }
+
+    #[test]
+    fn canvas_fallback() {
+        let html = "<p>Visible</p><canvas><p>Fallback</p></canvas>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+
+        assert_eq!(build_text(&dom.document), "Visible\n");
+
+        let mut builder = TextBuilder::new().with_canvas_fallback();
+        builder.walk(&dom.document);
+        assert_eq!(builder.finish(), "Visible\n\nFallback\n");
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::builders::build_text;
+    use monolith::html;
+
+    #[test]
+    fn empty_document() {
+        let dom = html::html_to_dom(&"".as_bytes().to_vec(), "".to_string());
+
+        assert_eq!(build_text(&dom.document), "");
+    }
+}
@@ -0,0 +1,2 @@
+mod build_json;
+mod build_text;
@@ -6,7 +6,7 @@ use std::time::Duration;
 use std::sync::Arc;
 
 use monolith::translation::storage::cache::{
-    CacheManager, CacheConfig, CacheKey, CacheEntry
+    CacheManager, CacheConfig, CacheEntry
 };
 
 mod common {
@@ -14,7 +14,7 @@ mod common {
 }
 
 use common::{
-    TestDataGenerator, PerformanceHelper
+    TestDataGenerator, PerformanceHelper, CacheTestHelper
 };
 
 /// 测试基本缓存操作
@@ -25,36 +25,28 @@ async fn test_basic_cache_operations() {
         local_cache_size: 100,
         default_ttl: Duration::from_secs(300),
         enable_warmup: false,
-        cleanup_interval: Duration::from_secs(60),
+        ..CacheConfig::default()
     };
     
     let cache_manager = CacheManager::new(config);
     
     // 测试基本的存储和检索
-    let key = CacheKey::new(
-        "Hello world".to_string(),
-        "en".to_string(),
-        "zh".to_string(),
-    );
-    
     let entry = CacheEntry::new(
         "Hello world".to_string(),
         "你好世界".to_string(),
-        Some(Duration::from_secs(300)),
+        "en".to_string(),
+        "zh".to_string(),
     );
     
     // 初始状态应该为空
-    let initial_result = cache_manager.get(&key).await
-        .expect("Cache get should not error");
+    let initial_result = cache_manager.lookup(&entry.original_text, &entry.target_lang);
     assert!(initial_result.is_none(), "Cache should be empty initially");
     
     // 存储条目
-    cache_manager.put(key.clone(), entry.clone()).await
-        .expect("Cache put should succeed");
+    CacheTestHelper::put(&cache_manager, &entry);
     
     // 检索条目
-    let retrieved = cache_manager.get(&key).await
-        .expect("Cache get should not error")
+    let retrieved = cache_manager.lookup(&entry.original_text, &entry.target_lang)
         .expect("Cache item should be found");
     
     assert_eq!(retrieved.original_text, entry.original_text);
@@ -71,15 +63,15 @@ async fn test_cache_statistics_monitoring() {
         local_cache_size: 50,
         default_ttl: Duration::from_secs(300),
         enable_warmup: false,
-        cleanup_interval: Duration::from_secs(60),
+        ..CacheConfig::default()
     };
     
     let cache_manager = CacheManager::new(config);
     
     // 初始统计
     let initial_stats = cache_manager.get_stats();
-    assert_eq!(initial_stats.local_hits, 0);
-    assert_eq!(initial_stats.misses, 0);
+    assert_eq!(initial_stats.cache_hits, 0);
+    assert_eq!(initial_stats.cache_misses, 0);
     
     // 生成测试数据
     let test_entries = TestDataGenerator::create_test_cache_entries(10);
@@ -87,26 +79,26 @@ async fn test_cache_statistics_monitoring() {
     // 执行一系列操作并监控统计
     for (key, entry) in &test_entries {
         // 首次访问应该是miss
-        let _miss_result = cache_manager.get(key).await.unwrap();
+        let _miss_result = cache_manager.get(key);
         
         // 存储条目
-        cache_manager.put(key.clone(), entry.clone()).await.unwrap();
+        CacheTestHelper::put(&cache_manager, entry);
         
         // 再次访问应该是hit
-        let _hit_result = cache_manager.get(key).await.unwrap();
+        let _hit_result = cache_manager.get(key);
     }
     
     // 验证统计信息
     let final_stats = cache_manager.get_stats();
-    assert_eq!(final_stats.misses, test_entries.len());
-    assert_eq!(final_stats.local_hits, test_entries.len());
+    assert_eq!(final_stats.cache_misses, test_entries.len() as u64);
+    assert_eq!(final_stats.cache_hits, test_entries.len() as u64);
     assert!(final_stats.hit_rate() > 0.0);
     
     // 测试统计重置
     cache_manager.reset_stats();
     let reset_stats = cache_manager.get_stats();
-    assert_eq!(reset_stats.local_hits, 0);
-    assert_eq!(reset_stats.misses, 0);
+    assert_eq!(reset_stats.cache_hits, 0);
+    assert_eq!(reset_stats.cache_misses, 0);
     
     println!("✅ Cache statistics monitoring test passed - tracked {} operations", 
              test_entries.len() * 2);
@@ -121,7 +113,7 @@ async fn test_cache_capacity_and_lru_eviction() {
         local_cache_size: small_capacity,
         default_ttl: Duration::from_secs(300),
         enable_warmup: false,
-        cleanup_interval: Duration::from_secs(60),
+        ..CacheConfig::default()
     };
     
     let cache_manager = CacheManager::new(config);
@@ -130,8 +122,8 @@ async fn test_cache_capacity_and_lru_eviction() {
     let test_entries = TestDataGenerator::create_test_cache_entries(small_capacity * 2);
     
     // 填充缓存超过容量
-    for (key, entry) in &test_entries {
-        cache_manager.put(key.clone(), entry.clone()).await.unwrap();
+    for (_, entry) in &test_entries {
+        CacheTestHelper::put(&cache_manager, entry);
     }
     
     // 验证缓存大小不超过限制
@@ -143,7 +135,7 @@ async fn test_cache_capacity_and_lru_eviction() {
     // 验证最近使用的项目仍在缓存中
     let recent_keys = &test_entries[test_entries.len() - small_capacity..];
     for (key, _) in recent_keys {
-        let result = cache_manager.get(key).await.unwrap();
+        let result = cache_manager.get(key);
         assert!(result.is_some(), "Recently added item should still be in cache");
     }
     
@@ -151,7 +143,7 @@ async fn test_cache_capacity_and_lru_eviction() {
     let early_keys = &test_entries[..test_entries.len() - small_capacity];
     let mut evicted_count = 0;
     for (key, _) in early_keys {
-        let result = cache_manager.get(key).await.unwrap();
+        let result = cache_manager.get(key);
         if result.is_none() {
             evicted_count += 1;
         }
@@ -171,41 +163,43 @@ async fn test_cache_expiration_mechanism() {
         local_cache_size: 100,
         default_ttl: Duration::from_millis(100), // 短过期时间
         enable_warmup: false,
-        cleanup_interval: Duration::from_secs(60),
+        ..CacheConfig::default()
     };
     
     let cache_manager = CacheManager::new(config);
     
     // 创建短过期时间的条目
-    let key = CacheKey::new(
+    let entry = CacheEntry::new(
         "Expiring item".to_string(),
+        "过期项目".to_string(),
         "en".to_string(),
         "zh".to_string(),
     );
-    
-    let entry = CacheEntry::new(
-        "Expiring item".to_string(),
-        "过期项目".to_string(),
-        Some(Duration::from_millis(50)), // 非常短的TTL
+    let untouched = CacheEntry::new(
+        "Untouched item".to_string(),
+        "未访问项目".to_string(),
+        "en".to_string(),
+        "zh".to_string(),
     );
     
     // 存储条目
-    cache_manager.put(key.clone(), entry.clone()).await.unwrap();
+    CacheTestHelper::put(&cache_manager, &entry);
+    CacheTestHelper::put(&cache_manager, &untouched);
     
     // 立即访问应该成功
-    let immediate_result = cache_manager.get(&key).await.unwrap();
+    let immediate_result = cache_manager.get(&entry.original_text);
     assert!(immediate_result.is_some(), "Item should be available immediately");
     
     // 等待过期
-    tokio::time::sleep(Duration::from_millis(100)).await;
+    tokio::time::sleep(Duration::from_millis(150)).await;
     
     // 访问过期项目
-    let expired_result = cache_manager.get(&key).await.unwrap();
+    let expired_result = cache_manager.get(&entry.original_text);
     assert!(expired_result.is_none(), "Expired item should not be available");
     
-    // 测试清理过期项目
-    let cleanup_count = cache_manager.cleanup_expired().await.unwrap();
-    assert!(cleanup_count >= 0, "Cleanup should return non-negative count");
+    // 测试清理过期项目：访问时已移除的条目不再计入
+    let cleanup_count = cache_manager.cleanup_expired();
+    assert_eq!(cleanup_count, 1, "Cleanup should remove the untouched expired item");
     
     println!("✅ Cache expiration mechanism test passed - cleaned up {} expired items", 
              cleanup_count);
@@ -219,7 +213,7 @@ async fn test_high_concurrency_cache_access() {
         local_cache_size: 200,
         default_ttl: Duration::from_secs(300),
         enable_warmup: false,
-        cleanup_interval: Duration::from_secs(60),
+        ..CacheConfig::default()
     };
     
     let cache_manager = Arc::new(CacheManager::new(config));
@@ -238,23 +232,18 @@ async fn test_high_concurrency_cache_access() {
             let mut local_operations = 0;
             
             for op_id in 0..operations_per_task {
-                let key = CacheKey::new(
+                let entry = CacheEntry::new(
                     format!("concurrent_item_{}_{}", task_id, op_id),
+                    format!("项目 {} {}", task_id, op_id),
                     "en".to_string(),
                     "zh".to_string(),
                 );
                 
-                let entry = CacheEntry::new(
-                    format!("Item {} {}", task_id, op_id),
-                    format!("项目 {} {}", task_id, op_id),
-                    Some(Duration::from_secs(300)),
-                );
-                
                 // 并发存储
-                cm.put(key.clone(), entry).await.unwrap();
+                CacheTestHelper::put(&cm, &entry);
                 
                 // 并发检索
-                let _retrieved = cm.get(&key).await.unwrap();
+                let _retrieved = cm.get(&entry.original_text);
                 
                 local_operations += 1;
             }
@@ -311,27 +300,21 @@ async fn test_cache_key_handling() {
     ];
     
     for (text, src_lang, target_lang) in &test_cases {
-        let key = CacheKey::new(
-            text.to_string(),
-            src_lang.to_string(),
-            target_lang.to_string(),
-        );
-        
         let entry = CacheEntry::new(
             text.to_string(),
             format!("translated_{}", text),
-            Some(Duration::from_secs(300)),
+            src_lang.to_string(),
+            target_lang.to_string(),
         );
         
         // 存储和检索应该都成功
-        let store_result = cache_manager.put(key.clone(), entry.clone()).await;
-        assert!(store_result.is_ok(), "Should handle cache key: {:?}", key);
+        CacheTestHelper::put(&cache_manager, &entry);
         
-        let retrieve_result = cache_manager.get(&key).await;
-        assert!(retrieve_result.is_ok(), "Should retrieve cache key: {:?}", key);
+        let retrieve_result = cache_manager.lookup(text, target_lang);
+        assert!(retrieve_result.is_some(), "Should retrieve cache key: {:?}", entry.cache_key());
         
         // 验证键的字符串表示
-        let key_string = key.to_string();
+        let key_string = entry.cache_key();
         assert!(!key_string.is_empty(), "Key string should not be empty");
         assert!(key_string.contains(src_lang), "Key string should contain source language");
         assert!(key_string.contains(target_lang), "Key string should contain target language");
@@ -349,39 +332,29 @@ async fn test_cache_error_handling_and_recovery() {
         local_cache_size: 10,
         default_ttl: Duration::from_secs(300),
         enable_warmup: false,
-        cleanup_interval: Duration::from_secs(60),
+        ..CacheConfig::default()
     };
     
     let cache_manager = CacheManager::new(config);
     
     // 测试大量数据的处理
     let large_text = "A".repeat(100000); // 100KB文本
-    let large_key = CacheKey::new(
-        large_text.clone(),
-        "en".to_string(),
-        "zh".to_string(),
-    );
-    
     let large_entry = CacheEntry::new(
         large_text.clone(),
         format!("translated_{}", &large_text[..100]), // 截断翻译以节省内存
-        Some(Duration::from_secs(300)),
+        "en".to_string(),
+        "zh".to_string(),
     );
     
     // 大文本存储应该成功
-    let large_store_result = cache_manager.put(large_key.clone(), large_entry).await;
-    assert!(large_store_result.is_ok(), "Should handle large text gracefully");
+    CacheTestHelper::put(&cache_manager, &large_entry);
     
     // 大文本检索应该成功
-    let large_retrieve_result = cache_manager.get(&large_key).await;
-    assert!(large_retrieve_result.is_ok(), "Should retrieve large text gracefully");
+    let large_retrieve_result = cache_manager.get(&large_text);
+    assert!(large_retrieve_result.is_some(), "Should retrieve large text gracefully");
     
     // 测试并发访问同一键
-    let shared_key = CacheKey::new(
-        "shared_item".to_string(),
-        "en".to_string(),
-        "zh".to_string(),
-    );
+    let shared_key = "shared_item".to_string();
     
     let mut concurrent_handles = vec![];
     
@@ -391,16 +364,17 @@ async fn test_cache_error_handling_and_recovery() {
         
         let handle = tokio::spawn(async move {
             let entry = CacheEntry::new(
-                "shared_item".to_string(),
+                key.clone(),
                 format!("translation_{}", i),
-                Some(Duration::from_secs(300)),
+                "en".to_string(),
+                "zh".to_string(),
             );
             
             // 并发存储同一键
-            cm.put(key.clone(), entry).await.unwrap();
+            CacheTestHelper::put(&cm, &entry);
             
             // 并发检索
-            let retrieved = cm.get(&key).await.unwrap();
+            let retrieved = cm.get(&key);
             retrieved.is_some()
         });
         
@@ -419,7 +393,7 @@ async fn test_cache_error_handling_and_recovery() {
     assert_eq!(success_count, 10, "All concurrent operations on same key should succeed");
     
     // 验证最终状态一致性
-    let final_result = cache_manager.get(&shared_key).await.unwrap();
+    let final_result = cache_manager.get(&shared_key);
     assert!(final_result.is_some(), "Shared key should have a final consistent value");
     
     println!("✅ Cache error handling test passed - handled edge cases and concurrency gracefully");
@@ -433,7 +407,7 @@ async fn test_cache_performance_stress() {
         local_cache_size: 1000,
         default_ttl: Duration::from_secs(300),
         enable_warmup: false,
-        cleanup_interval: Duration::from_secs(60),
+        ..CacheConfig::default()
     };
     
     let cache_manager = CacheManager::new(config);
@@ -444,8 +418,8 @@ async fn test_cache_performance_stress() {
     
     // 测试批量写入性能
     let (_, write_time) = PerformanceHelper::measure_async_time(|| async {
-        for (key, entry) in &test_entries {
-            cache_manager.put(key.clone(), entry.clone()).await.unwrap();
+        for (_, entry) in &test_entries {
+            CacheTestHelper::put(&cache_manager, entry);
         }
     }).await;
     
@@ -454,7 +428,7 @@ async fn test_cache_performance_stress() {
     // 测试批量读取性能
     let (_, read_time) = PerformanceHelper::measure_async_time(|| async {
         for (key, _) in &test_entries {
-            let _result = cache_manager.get(key).await.unwrap();
+            let _result = cache_manager.get(key);
         }
     }).await;
     
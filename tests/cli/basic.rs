@@ -123,6 +123,34 @@ mod passing {
         // Exit code should be 0
         out.assert().code(0);
     }
+
+    #[test]
+    fn output_file_is_target() {
+        let dir = env::temp_dir().join(format!("monolith-cli-self-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("index.html");
+        fs::write(&path, "<html><body><p>hello</p></body></html>").unwrap();
+
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("-M")
+            .arg(path.as_os_str())
+            .arg("-o")
+            .arg(path.as_os_str())
+            .output()
+            .unwrap();
+
+        // The output file should be replaced only once the target has been read
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "<html><head><meta name=\"robots\" content=\"none\"></meta></head><body><p>hello</p></body></html>\n"
+        );
+
+        // Exit code should be 0
+        out.assert().code(0);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
 
 //  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
@@ -136,6 +164,7 @@ mod passing {
 mod failing {
     use assert_cmd::prelude::*;
     use std::env;
+    use std::fs;
     use std::process::Command;
 
     #[test]
@@ -173,4 +202,30 @@ mod failing {
         // Exit code should be 1
         out.assert().code(1);
     }
+
+    #[test]
+    fn failed_target_keeps_output_file() {
+        let dir = env::temp_dir().join(format!("monolith-cli-keep-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("output.html");
+        fs::write(&path, "previous").unwrap();
+
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("-M")
+            .arg(dir.join("missing.html").as_os_str())
+            .arg("-o")
+            .arg(path.as_os_str())
+            .output()
+            .unwrap();
+
+        // The existing output file should be left as it was
+        assert_eq!(fs::read_to_string(&path).unwrap(), "previous");
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        // Exit code should be 1
+        out.assert().code(1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -0,0 +1,102 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use assert_cmd::prelude::*;
+    use std::env;
+    use std::process::Command;
+
+    #[test]
+    fn append_stylesheet() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("-M")
+            .arg("--extra-css")
+            .arg("tests/_data_/extra_css/style.css")
+            .arg("data:text/html,<style>p{color:red}</style>Hello")
+            .output()
+            .unwrap();
+
+        // STDERR should be empty
+        assert_eq!(String::from_utf8_lossy(&out.stderr), "");
+
+        // STDOUT should contain the page's own styles followed by the custom stylesheet
+        assert_eq!(
+            String::from_utf8_lossy(&out.stdout),
+            r#"<html><head><style>p{color:red}</style><meta name="robots" content="none"></meta><style>body { background: #222; }
+</style></head><body>Hello</body></html>
+"#
+        );
+
+        // Exit code should be 0
+        out.assert().code(0);
+    }
+
+    #[test]
+    fn keep_stylesheet_when_removing_css() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("-M")
+            .arg("-c")
+            .arg("--extra-css")
+            .arg("tests/_data_/extra_css/style.css")
+            .arg("data:text/html,<style>p{color:red}</style>Hello")
+            .output()
+            .unwrap();
+
+        // STDERR should be empty
+        assert_eq!(String::from_utf8_lossy(&out.stderr), "");
+
+        // STDOUT should only allow the custom stylesheet
+        assert_eq!(
+            String::from_utf8_lossy(&out.stdout),
+            r#"<html><head><meta http-equiv="Content-Security-Policy" content="style-src 'sha256-AQjFGEs/engbw8+MCEYt4LY7Udec8bYNG9Yzi+VTKBo=';"></meta><style></style><meta name="robots" content="none"></meta><style>body { background: #222; }
+</style></head><body>Hello</body></html>
+"#
+        );
+
+        // Exit code should be 0
+        out.assert().code(0);
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use assert_cmd::prelude::*;
+    use std::env;
+    use std::process::Command;
+
+    #[test]
+    fn missing_stylesheet_file() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("--extra-css")
+            .arg("tests/_data_/extra_css/missing.css")
+            .arg("data:text/html,Hello")
+            .output()
+            .unwrap();
+
+        // STDERR should contain error description
+        assert!(String::from_utf8_lossy(&out.stderr).contains(
+            "could not read specified stylesheet \"tests/_data_/extra_css/missing.css\""
+        ));
+
+        // STDOUT should be empty
+        assert_eq!(String::from_utf8_lossy(&out.stdout), "");
+
+        // Exit code should be 1
+        out.assert().code(1);
+    }
+}
@@ -345,7 +345,7 @@ document.body.style.color = "red";
             String::from_utf8_lossy(&out.stdout),
             r##"<!DOCTYPE html><html lang="en"><head><meta http-equiv="Content-Security-Policy" content="img-src data:;"></meta>
         <title>Local HTML file</title>
-        <link href="data:text/css;base64,Ym9keSB7CiAgICBiYWNrZ3JvdW5kLWNvbG9yOiAjMDAwOwogICAgY29sb3I6ICNGRkY7Cn0K" rel="stylesheet" type="text/css" crossorigin="anonymous">
+        <link href="data:text/css;base64,Ym9keSB7CiAgICBiYWNrZ3JvdW5kLWNvbG9yOiAjMDAwOwogICAgY29sb3I6ICNGRkY7Cn0K" rel="stylesheet" type="text/css">
         <link href="style.css" rel="stylesheet" type="text/css" crossorigin="anonymous">
     <meta name="robots" content="none"></meta></head>
 
@@ -368,4 +368,26 @@ document.body.style.color = "red";
         // Exit code should be 0
         out.assert().code(0);
     }
+
+    #[test]
+    fn text_output_skips_local_video() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("--text")
+            .arg(format!(
+                "tests{s}_data_{s}basic{s}local-video.html",
+                s = MAIN_SEPARATOR
+            ))
+            .output()
+            .unwrap();
+
+        // STDERR should be empty, the video should not be retrieved
+        assert_eq!(String::from_utf8_lossy(&out.stderr), "");
+
+        // STDOUT should contain the text of the local file
+        assert!(String::from_utf8_lossy(&out.stdout).contains("Watch the clip below."));
+
+        // Exit code should be 0
+        out.assert().code(0);
+    }
 }
@@ -1,6 +1,7 @@
 mod base_url;
 mod basic;
 mod data_url;
+mod extra_css;
 mod local_files;
 mod noscript;
 mod unusual_encodings;
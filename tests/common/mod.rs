@@ -5,7 +5,7 @@
 use std::time::Duration;
 
 use monolith::translation::config::{TranslationConfig, constants};
-use monolith::translation::storage::cache::{CacheConfig, CacheManager, CacheKey, CacheEntry};
+use monolith::translation::storage::cache::{CacheConfig, CacheManager, CacheEntry};
 use monolith::translation::pipeline::collector::{TextCollector, CollectorConfig, TextItem, TextPriority, TextType};
 use monolith::translation::pipeline::batch::{BatchManager, BatchManagerConfig};
 use monolith::translation::pipeline::filters::TextFilter;
@@ -65,7 +65,7 @@ impl TestConfigBuilder {
             local_cache_size: 100,
             default_ttl: Duration::from_secs(300),
             enable_warmup: false,
-            cleanup_interval: Duration::from_secs(60),
+            ..CacheConfig::default()
         };
         self
     }
@@ -74,6 +74,7 @@ impl TestConfigBuilder {
         self.batch_config = BatchManagerConfig {
             max_batch_size: 50,
             min_batch_chars: 5,
+            preferred_batch_chars: None,
             max_effective_size: 100.0,
             min_effective_size: 5.0,
             batch_delay: Duration::from_millis(100),
@@ -95,7 +96,17 @@ impl TestConfigBuilder {
             skip_elements: vec!["script".to_string(), "style".to_string()],
             collect_attributes: vec!["title".to_string(), "alt".to_string()],
             min_text_length: constants::MIN_TEXT_LENGTH,
+            min_text_lengths: Default::default(),
             enable_priority_sorting: true,
+            normalize_whitespace: true,
+            normalize_invisible_chars: true,
+            include_templates: false,
+            include_social_meta: false,
+            target_lang: None,
+            attribute_types: CollectorConfig::default().attribute_types,
+            only_selectors: Vec::new(),
+            translate_preformatted: false,
+            translate_json_paths: Vec::new(),
         };
         self
     }
@@ -349,7 +360,8 @@ impl TestDataGenerator {
                 priority,
                 text_type,
                 depth: i % 10,
-                parent_tag: Some(format!("div")),
+                parent_tag: Some("div".to_string()),
+                duplicates: Vec::new(),
             };
             items.push(item);
         }
@@ -357,24 +369,19 @@ impl TestDataGenerator {
         items
     }
 
-    /// 生成测试用的缓存条目
-    pub fn create_test_cache_entries(count: usize) -> Vec<(CacheKey, CacheEntry)> {
+    /// 生成测试用的缓存条目，键为缓存查询使用的原文
+    pub fn create_test_cache_entries(count: usize) -> Vec<(String, CacheEntry)> {
         let mut entries = Vec::new();
 
         for i in 0..count {
-            let key = CacheKey::new(
-                format!("Test text {}", i + 1),
-                "en".to_string(),
-                "zh".to_string(),
-            );
-
             let entry = CacheEntry::new(
                 format!("Test text {}", i + 1),
                 format!("测试文本 {}", i + 1),
-                Some(Duration::from_secs(3600)),
+                "en".to_string(),
+                "zh".to_string(),
             );
 
-            entries.push((key, entry));
+            entries.push((entry.original_text.clone(), entry));
         }
 
         entries
@@ -394,6 +401,7 @@ impl TestDataGenerator {
                 text_type: TextType::Title,
                 depth: 1,
                 parent_tag: Some("h1".to_string()),
+                duplicates: Vec::new(),
             },
             TextItem {
                 text: "High priority button".to_string(),
@@ -403,6 +411,7 @@ impl TestDataGenerator {
                 text_type: TextType::Button,
                 depth: 2,
                 parent_tag: Some("button".to_string()),
+                duplicates: Vec::new(),
             },
             TextItem {
                 text: "Normal content paragraph with some longer text to test batching".to_string(),
@@ -412,6 +421,7 @@ impl TestDataGenerator {
                 text_type: TextType::Content,
                 depth: 3,
                 parent_tag: Some("p".to_string()),
+                duplicates: Vec::new(),
             },
             TextItem {
                 text: "Low priority attribute".to_string(),
@@ -421,11 +431,27 @@ impl TestDataGenerator {
                 text_type: TextType::Attribute("title".to_string()),
                 depth: 2,
                 parent_tag: Some("div".to_string()),
+                duplicates: Vec::new(),
             },
         ]
     }
 }
 
+/// 缓存测试辅助工具
+pub struct CacheTestHelper;
+
+impl CacheTestHelper {
+    /// 按条目的原文、译文和语言写入缓存
+    pub fn put(cache_manager: &CacheManager, entry: &CacheEntry) {
+        cache_manager.insert_with_lang(
+            entry.original_text.clone(),
+            entry.translated_text.clone(),
+            entry.source_lang.clone(),
+            entry.target_lang.clone(),
+        );
+    }
+}
+
 /// 性能测试辅助工具
 pub struct PerformanceHelper;
 
@@ -0,0 +1,213 @@
+// Local HTTP server for integration tests
+//
+// Each test describes the server it needs as a handler from the received request to the
+// response; connections are served on their own threads, so a slow handler doesn't hold up
+// other requests.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use reqwest::Url;
+
+/// A request received by the server
+pub struct Request {
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    /// Value of the first header with the given name, compared case-insensitively
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// A response to send back, or an instruction to drop the connection without answering
+pub struct Response {
+    status: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+    drop_connection: bool,
+}
+
+impl Response {
+    pub fn new(status: &str, content_type: &str, body: impl Into<Vec<u8>>) -> Response {
+        Response {
+            status: status.to_string(),
+            headers: vec![("Content-Type".to_string(), content_type.to_string())],
+            body: body.into(),
+            drop_connection: false,
+        }
+    }
+
+    pub fn ok(content_type: &str, body: impl Into<Vec<u8>>) -> Response {
+        Response::new("200 OK", content_type, body)
+    }
+
+    pub fn not_found() -> Response {
+        Response::new("404 Not Found", "text/plain", "")
+    }
+
+    /// Closes the connection without sending anything
+    pub fn drop_connection() -> Response {
+        Response {
+            drop_connection: true,
+            ..Response::new("", "", "")
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Response {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+pub struct TestServer {
+    address: SocketAddr,
+    connections: Arc<AtomicUsize>,
+}
+
+impl TestServer {
+    /// Starts a server answering one request per connection with `Connection: close`
+    pub fn start<H>(handler: H) -> TestServer
+    where
+        H: Fn(&Request) -> Response + Send + Sync + 'static,
+    {
+        TestServer::spawn(handler, false)
+    }
+
+    /// Starts a server keeping connections open for further requests
+    pub fn start_keep_alive<H>(handler: H) -> TestServer
+    where
+        H: Fn(&Request) -> Response + Send + Sync + 'static,
+    {
+        TestServer::spawn(handler, true)
+    }
+
+    fn spawn<H>(handler: H, keep_alive: bool) -> TestServer
+    where
+        H: Fn(&Request) -> Response + Send + Sync + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let server = TestServer {
+            address: listener.local_addr().unwrap(),
+            connections: Arc::new(AtomicUsize::new(0)),
+        };
+
+        let handler = Arc::new(handler);
+        let connections = Arc::clone(&server.connections);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let stream = match stream {
+                    Ok(stream) => stream,
+                    Err(_) => break,
+                };
+                connections.fetch_add(1, Ordering::SeqCst);
+                let handler = Arc::clone(&handler);
+                thread::spawn(move || serve_connection(stream, &*handler, keep_alive));
+            }
+        });
+
+        server
+    }
+
+    /// Base URL of the server, ending with `/`
+    pub fn url(&self) -> Url {
+        Url::parse(&format!("http://{}/", self.address)).unwrap()
+    }
+
+    /// Number of connections accepted so far
+    pub fn connections(&self) -> usize {
+        self.connections.load(Ordering::SeqCst)
+    }
+}
+
+/// Serves requests from an already accepted connection (e.g. one wrapped in TLS)
+///  until the client closes it, or after the first response unless `keep_alive` is set
+pub fn serve_connection<S, H>(stream: S, handler: H, keep_alive: bool)
+where
+    S: Read + Write,
+    H: Fn(&Request) -> Response,
+{
+    let mut reader = BufReader::new(stream);
+    while let Some(request) = read_request(&mut reader) {
+        let response = handler(&request);
+        if response.drop_connection {
+            return;
+        }
+
+        let stream = reader.get_mut();
+        let mut head = format!("HTTP/1.1 {}\r\n", response.status);
+        for (name, value) in &response.headers {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        head.push_str(&format!("Content-Length: {}\r\n", response.body.len()));
+        if !keep_alive {
+            head.push_str("Connection: close\r\n");
+        }
+        head.push_str("\r\n");
+        if stream.write_all(head.as_bytes()).is_err() || stream.write_all(&response.body).is_err() {
+            return;
+        }
+        let _ = stream.flush();
+        if !keep_alive {
+            return;
+        }
+    }
+}
+
+fn read_request<S: Read>(reader: &mut BufReader<S>) -> Option<Request> {
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).ok()? == 0 {
+        return None;
+    }
+    let mut parts = request_line.split_whitespace();
+    let path = parts.nth(1)?.to_string();
+
+    let mut headers = Vec::new();
+    let mut line = String::new();
+    loop {
+        line.clear();
+        match reader.read_line(&mut line) {
+            Ok(0) | Err(_) => return None,
+            Ok(_) if line == "\r\n" => break,
+            Ok(_) => {}
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+    }
+
+    let mut request = Request {
+        path,
+        headers,
+        body: Vec::new(),
+    };
+    let content_length = request
+        .header("content-length")
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(0);
+    request.body = vec![0; content_length];
+    reader.read_exact(&mut request.body).ok()?;
+    Some(request)
+}
+
+/// Starts a DeepLX-compatible backend answering every request with the given translation,
+///  returns its endpoint URL
+#[cfg(feature = "translation")]
+pub fn start_translation_backend(translation: &'static str) -> String {
+    let server = TestServer::start(move |_| {
+        Response::ok(
+            "application/json",
+            format!("{{\"code\":200,\"data\":\"{}\"}}", translation),
+        )
+    });
+    format!("{}translate", server.url())
+}
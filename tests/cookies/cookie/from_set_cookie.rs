@@ -0,0 +1,103 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::cookies::Cookie;
+    use monolith::url::Url;
+
+    pub fn parse(header: &str, url: &str) -> Option<Cookie> {
+        Cookie::from_set_cookie(header, &Url::parse(url).unwrap())
+    }
+
+    #[test]
+    fn name_and_value_only() {
+        let cookie = parse("sid=abc123", "http://127.0.0.1/account/login").unwrap();
+
+        assert_eq!(cookie.name, "sid");
+        assert_eq!(cookie.value, "abc123");
+        assert_eq!(cookie.domain, "127.0.0.1");
+        assert!(!cookie.include_subdomains);
+        assert_eq!(cookie.path, "/account");
+        assert!(!cookie.https_only);
+        assert_eq!(cookie.expires, 0);
+    }
+
+    #[test]
+    fn attributes() {
+        let cookie = parse(
+            "sid=\"abc\"; Domain=.Example.com; Path=/app; Secure; HttpOnly; SameSite=Lax",
+            "https://www.example.com/",
+        )
+        .unwrap();
+
+        assert_eq!(cookie.value, "abc");
+        assert_eq!(cookie.domain, ".example.com");
+        assert!(cookie.include_subdomains);
+        assert_eq!(cookie.path, "/app");
+        assert!(cookie.https_only);
+        assert!(cookie.matches_url("https://example.com/app/page"));
+        assert!(!cookie.matches_url("http://example.com/app/page"));
+    }
+
+    #[test]
+    fn expires() {
+        let cookie = parse(
+            "sid=abc; Expires=Wed, 21 Oct 2099 07:28:00 GMT",
+            "https://example.com/",
+        )
+        .unwrap();
+        assert_eq!(cookie.expires, 4096250880);
+        assert!(!cookie.is_expired());
+
+        let cookie = parse(
+            "sid=abc; expires=Thu, 01-Jan-2015 00:00:00 GMT",
+            "https://example.com/",
+        )
+        .unwrap();
+        assert!(cookie.is_expired());
+    }
+
+    #[test]
+    fn max_age_overrides_expires() {
+        let cookie = parse(
+            "sid=abc; Max-Age=3600; Expires=Thu, 01 Jan 2015 00:00:00 GMT",
+            "https://example.com/",
+        )
+        .unwrap();
+        assert!(!cookie.is_expired());
+
+        let cookie = parse("sid=; Max-Age=0", "https://example.com/").unwrap();
+        assert!(cookie.is_expired());
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use super::passing::parse;
+
+    #[test]
+    fn malformed() {
+        assert!(parse("", "https://example.com/").is_none());
+        assert!(parse("no-value", "https://example.com/").is_none());
+        assert!(parse("=value", "https://example.com/").is_none());
+    }
+
+    #[test]
+    fn foreign_domain() {
+        assert!(parse("sid=abc; Domain=example.org", "https://example.com/").is_none());
+        assert!(parse("sid=abc; Domain=ample.com", "https://example.com/").is_none());
+        assert!(parse("sid=abc; Domain=0.0.1", "http://127.0.0.1/").is_none());
+    }
+}
@@ -50,6 +50,20 @@ mod passing {
         };
         assert!(cookie.matches_url("https://cdn.somethingsomething.com/something"));
     }
+
+    #[test]
+    fn parent_domain_of_subdomain_cookie() {
+        let cookie = cookies::Cookie {
+            domain: String::from(".somethingsomething.com"),
+            include_subdomains: true,
+            path: String::from("/"),
+            https_only: true,
+            expires: 0,
+            name: String::from(""),
+            value: String::from(""),
+        };
+        assert!(cookie.matches_url("https://somethingsomething.com/something"));
+    }
 }
 
 //  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
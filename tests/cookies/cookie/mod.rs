@@ -1,2 +1,3 @@
+mod from_set_cookie;
 mod is_expired;
 mod matches_url;
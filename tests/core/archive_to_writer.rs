@@ -0,0 +1,167 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::core::{
+        archive_to_writer, create_monolithic_document, MonolithOptions, MonolithOutputFormat,
+    };
+    use monolith::session::Session;
+
+    use crate::common::server::{Response, TestServer};
+
+    const PAGE: &str = "<html><head><title>Field notes</title>\
+                        <link rel=\"stylesheet\" href=\"style.css\"></head>\
+                        <body><h1>Field notes</h1><img src=\"pixel.gif\" alt=\"A pixel\">\
+                        <p>Archived pages keep working long after the original site is gone.</p>\
+                        </body></html>";
+    const STYLE: &str = "body { background: url(pixel.gif); }";
+    const PIXEL: &[u8] = b"GIF89a\x01\x00\x01\x00\x00\x00\x00;";
+
+    /// Starts an HTTP server serving a page with a stylesheet and an image, returns the page URL
+    pub fn start_server() -> String {
+        let server = TestServer::start(|request| match request.path.as_str() {
+            "/page.html" => Response::ok("text/html", PAGE),
+            "/style.css" => Response::ok("text/css", STYLE),
+            "/pixel.gif" => Response::ok("image/gif", PIXEL),
+            _ => Response::not_found(),
+        });
+
+        format!("{}page.html", server.url())
+    }
+
+    pub fn options() -> MonolithOptions {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.no_metadata = true;
+        options.timeout = 10;
+        options
+    }
+
+    /// Archives `target` in memory and into a writer, returns both outputs and titles
+    fn archive_both_ways(
+        options: MonolithOptions,
+        target: &str,
+    ) -> ((Vec<u8>, Option<String>), (Vec<u8>, Option<String>)) {
+        let in_memory =
+            create_monolithic_document(Session::new(None, None, options.clone()), target).unwrap();
+
+        let mut streamed = Vec::new();
+        let title =
+            archive_to_writer(Session::new(None, None, options), target, &mut streamed).unwrap();
+
+        (in_memory, (streamed, title))
+    }
+
+    #[test]
+    fn matches_in_memory_output() {
+        let target = start_server();
+
+        let (in_memory, streamed) = archive_both_ways(options(), &target);
+
+        let html = String::from_utf8_lossy(&streamed.0).to_string();
+        assert!(html.contains("data:text/css;base64,"));
+        assert!(html.contains("data:image/gif;base64,"));
+        assert!(html.ends_with('\n'));
+        assert_eq!(streamed, in_memory);
+        assert_eq!(streamed.1.as_deref(), Some("Field notes"));
+    }
+
+    #[test]
+    fn matches_in_memory_output_for_every_format() {
+        let target = start_server();
+
+        for output_format in [
+            MonolithOutputFormat::MHTML,
+            MonolithOutputFormat::Text,
+            MonolithOutputFormat::Json,
+        ] {
+            let mut options = options();
+            options.output_format = output_format;
+            let (in_memory, streamed) = archive_both_ways(options, &target);
+            assert_eq!(streamed, in_memory);
+        }
+    }
+
+    #[cfg(feature = "translation")]
+    #[test]
+    fn matches_in_memory_output_when_translating() {
+        use crate::common::server::start_translation_backend;
+
+        let target = start_server();
+
+        let mut options = options();
+        options.enable_translation = true;
+        options.target_language = Some("zh".to_string());
+        options.source_language = Some("en".to_string());
+        options.translation_api_url = Some(start_translation_backend("野外笔记"));
+
+        let (in_memory, streamed) = archive_both_ways(options, &target);
+
+        assert!(String::from_utf8_lossy(&streamed.0).contains("<title>野外笔记</title>"));
+        assert_eq!(streamed, in_memory);
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use std::io::{self, Write};
+
+    use monolith::core::archive_to_writer;
+    use monolith::session::Session;
+
+    use super::passing::{options, start_server};
+
+    /// A writer whose destination is gone, like a closed pipe
+    struct BrokenPipe;
+
+    impl Write for BrokenPipe {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "pipe closed"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn reports_write_errors() {
+        let target = start_server();
+
+        let error = archive_to_writer(
+            Session::new(None, None, options()),
+            &target,
+            &mut BrokenPipe,
+        )
+        .unwrap_err();
+
+        assert!(error.to_string().contains("pipe closed"));
+    }
+
+    #[test]
+    fn reports_fetch_errors_before_writing() {
+        let mut output = Vec::new();
+
+        let error = archive_to_writer(
+            Session::new(None, None, options()),
+            "/nonexistent/page.html",
+            &mut output,
+        )
+        .unwrap_err();
+
+        assert!(error.to_string().contains("File not found"));
+        assert!(output.is_empty());
+    }
+}
@@ -0,0 +1,197 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::core::{create_monolithic_document, MonolithOptions};
+    use monolith::session::Session;
+
+    use crate::common::server::{Response, TestServer};
+
+    /// Starts an HTTP server answering each path with the given HTML, returns its base URL
+    pub fn start_server(pages: Vec<(&'static str, String)>) -> String {
+        let server = TestServer::start(move |request| {
+            match pages.iter().find(|(page, _)| *page == request.path) {
+                Some((_, body)) => Response::ok("text/html", body.as_str()),
+                None => Response::not_found(),
+            }
+        });
+
+        server.url().as_str().trim_end_matches('/').to_string()
+    }
+
+    pub fn archive(target: &str, follow_meta_refresh: bool) -> Result<String, String> {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.no_metadata = true;
+        options.timeout = 10;
+        options.follow_meta_refresh = follow_meta_refresh;
+
+        create_monolithic_document(Session::new(None, None, options), target)
+            .map(|(result, _)| String::from_utf8_lossy(&result).to_string())
+            .map_err(|error| error.to_string())
+    }
+
+    #[test]
+    fn follows_redirect_shim() {
+        let base_url = start_server(vec![
+            (
+                "/",
+                "<meta http-equiv=\"refresh\" content=\"0;url=/intermediate\">".to_string(),
+            ),
+            (
+                "/intermediate",
+                "<meta http-equiv=\"refresh\" content=\"0; URL='article'\">\
+                 <p>Redirecting&hellip;</p>"
+                    .to_string(),
+            ),
+            (
+                "/article",
+                "<title>Article</title><p>The real content</p>".to_string(),
+            ),
+        ]);
+
+        let result = archive(&format!("{}/", base_url), true).unwrap();
+
+        assert!(result.contains("<p>The real content</p>"));
+        assert!(!result.contains("Redirecting"));
+    }
+
+    #[test]
+    fn keeps_pages_with_content() {
+        let reload_page = "<meta http-equiv=\"refresh\" content=\"300\">\
+                    <meta http-equiv=\"refresh\" content=\"5;url=/elsewhere\">"
+            .to_string();
+        let long_page = format!(
+            "<meta http-equiv=\"refresh\" content=\"0;url=/elsewhere\"><p>{}</p>",
+            "Plenty of text. ".repeat(20)
+        );
+        let base_url = start_server(vec![
+            ("/reload", reload_page),
+            ("/long", long_page),
+            ("/elsewhere", "<p>Elsewhere</p>".to_string()),
+        ]);
+
+        let result = archive(&format!("{}/reload", base_url), true).unwrap();
+        assert!(!result.contains("Elsewhere"));
+
+        let result = archive(&format!("{}/long", base_url), true).unwrap();
+        assert!(result.contains("Plenty of text."));
+        assert!(!result.contains("Elsewhere"));
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let base_url = start_server(vec![
+            (
+                "/",
+                "<meta http-equiv=\"refresh\" content=\"0;url=/article\">".to_string(),
+            ),
+            ("/article", "<p>The real content</p>".to_string()),
+        ]);
+
+        let result = archive(&format!("{}/", base_url), false).unwrap();
+
+        assert!(!result.contains("The real content"));
+    }
+
+    #[test]
+    fn adds_archive_metadata() {
+        let base_url = start_server(vec![(
+            "/page",
+            "<html><head><title>Page</title></head><body>Hi</body></html>".to_string(),
+        )]);
+        let target = format!("{}/page", base_url);
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.add_archive_metadata = true;
+        let (result, _) =
+            create_monolithic_document(Session::new(None, None, options.clone()), &target).unwrap();
+        let result = String::from_utf8_lossy(&result).to_string();
+
+        let archived_at = result
+            .split("<meta name=\"monolith-archived-at\" content=\"")
+            .nth(1)
+            .and_then(|rest| rest.split('"').next())
+            .unwrap();
+        assert!(chrono::DateTime::parse_from_rfc3339(archived_at).is_ok());
+        assert!(result.contains(&format!(
+            "<meta name=\"monolith-source-url\" content=\"{}\"></meta>",
+            target
+        )));
+
+        options.no_metadata = true;
+        let (result, _) =
+            create_monolithic_document(Session::new(None, None, options), &target).unwrap();
+        let result = String::from_utf8_lossy(&result).to_string();
+
+        assert!(!result.contains("monolith-archived-at"));
+        assert!(!result.contains("monolith-source-url"));
+    }
+
+    #[cfg(feature = "translation")]
+    #[test]
+    fn uses_translated_title() {
+        use monolith::core::{create_monolithic_document_from_data, format_output_path};
+
+        use crate::common::server::start_translation_backend;
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.no_metadata = true;
+        options.enable_translation = true;
+        options.target_language = Some("zh".to_string());
+        options.source_language = Some("en".to_string());
+        options.translation_api_url = Some(start_translation_backend("季度报告"));
+
+        let (result, title) = create_monolithic_document_from_data(
+            Session::new(None, None, options),
+            b"<html><head><title>Quarterly report</title></head><body></body></html>".to_vec(),
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert!(String::from_utf8_lossy(&result).contains("<title>季度报告</title>"));
+        assert_eq!(title.as_deref(), Some("季度报告"));
+        assert_eq!(
+            format_output_path("%title%.html", title.as_deref(), false),
+            "季度报告.html"
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use super::passing::{archive, start_server};
+
+    #[test]
+    fn stops_redirect_loops() {
+        let base_url = start_server(vec![
+            (
+                "/a",
+                "<meta http-equiv=\"refresh\" content=\"0;url=/b\">".to_string(),
+            ),
+            (
+                "/b",
+                "<meta http-equiv=\"refresh\" content=\"0;url=/a\">".to_string(),
+            ),
+        ]);
+
+        let error = archive(&format!("{}/a", base_url), true).unwrap_err();
+
+        assert!(error.contains("Too many meta refresh redirects"));
+    }
+}
@@ -0,0 +1,112 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use monolith::core::{find_fresh_output, format_output_path};
+
+    fn output_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "monolith-find-fresh-output-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn existing_output() {
+        let dir = output_dir("existing");
+        let template = format!("{}/%title%.html", dir.display());
+        let output = format_output_path(&template, Some("Page: One"), false);
+        fs::write(&output, "<html></html>").unwrap();
+
+        assert_eq!(
+            find_fresh_output(&template, Some("Page: One"), false, Duration::from_secs(60)),
+            Some(PathBuf::from(&output))
+        );
+        assert_eq!(
+            find_fresh_output(&template, Some("Page: Two"), false, Duration::from_secs(60)),
+            None
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn timestamp_template_matches_title() {
+        let dir = output_dir("timestamp");
+        let template = format!("{}/%title% %timestamp%.html", dir.display());
+        fs::write(dir.join("Page 2024-01-31T12_00_00Z.html"), "").unwrap();
+        fs::write(dir.join("Page draft.html"), "").unwrap();
+        fs::write(dir.join("Other 2024-01-31T12_00_00Z.html"), "").unwrap();
+
+        assert_eq!(
+            find_fresh_output(&template, Some("Page"), false, Duration::from_secs(60)),
+            Some(dir.join("Page 2024-01-31T12_00_00Z.html"))
+        );
+        assert_eq!(
+            find_fresh_output(&template, Some("Missing"), false, Duration::from_secs(60)),
+            None
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use std::fs;
+    use std::thread;
+    use std::time::Duration;
+
+    use monolith::core::find_fresh_output;
+
+    #[test]
+    fn stale_output() {
+        let dir = std::env::temp_dir().join(format!(
+            "monolith-find-fresh-output-stale-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let output = dir.join("page.html");
+        fs::write(&output, "").unwrap();
+        thread::sleep(Duration::from_millis(20));
+
+        assert_eq!(
+            find_fresh_output(
+                &output.display().to_string(),
+                None,
+                false,
+                Duration::from_millis(10)
+            ),
+            None
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn stdout() {
+        assert_eq!(find_fresh_output("-", None, false, Duration::MAX), None);
+        assert_eq!(find_fresh_output("", None, false, Duration::MAX), None);
+    }
+}
@@ -0,0 +1,110 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use monolith::core::{create_monolithic_document, MonolithOptions};
+    use monolith::session::Session;
+    use monolith::url::{parse_data_url, Url};
+
+    fn write_pages(name: &str, pages: &[(&str, &str)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "monolith-max-iframe-depth-{}-{}",
+            name,
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        for (file_name, html) in pages {
+            fs::write(dir.join(file_name), html).unwrap();
+        }
+        dir
+    }
+
+    fn archive(path: &PathBuf, options: MonolithOptions) -> String {
+        let session = Session::new(None, None, options);
+        let (data, _) = create_monolithic_document(session, path.to_str().unwrap()).unwrap();
+        String::from_utf8_lossy(&data).to_string()
+    }
+
+    fn frame_sources(html: &str) -> Vec<String> {
+        html.split("<iframe src=\"")
+            .skip(1)
+            .map(|rest| rest[..rest.find('"').unwrap()].to_string())
+            .collect()
+    }
+
+    fn frame_document(data_url: &str) -> String {
+        let (_, _, data) = parse_data_url(&Url::parse(data_url).unwrap());
+        String::from_utf8_lossy(&data).to_string()
+    }
+
+    #[test]
+    fn keeps_frames_below_max_depth_as_urls() {
+        let dir = write_pages(
+            "nested",
+            &[
+                ("index.html", "<iframe src=\"one.html\"></iframe>"),
+                ("one.html", "<p>One</p><iframe src=\"two.html\"></iframe>"),
+                ("two.html", "<p>Two</p><iframe src=\"three.html\"></iframe>"),
+                ("three.html", "<p>Three</p>"),
+            ],
+        );
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.no_metadata = true;
+        options.max_iframe_depth = Some(1);
+        let html = archive(&dir.join("index.html"), options);
+
+        let sources = frame_sources(&html);
+        assert_eq!(sources.len(), 1);
+        assert!(sources[0].starts_with("data:"));
+
+        let first_level = frame_document(&sources[0]);
+        assert!(first_level.contains("<p>One</p>"));
+        // The second level is left as an absolute URL and never fetched
+        assert_eq!(
+            frame_sources(&first_level),
+            vec![Url::from_file_path(dir.join("two.html"))
+                .unwrap()
+                .to_string()]
+        );
+        assert!(!first_level.contains("Two"));
+    }
+
+    #[test]
+    fn stops_inlining_frames_over_total_size() {
+        let dir = write_pages(
+            "size",
+            &[
+                (
+                    "index.html",
+                    "<iframe src=\"first.html\"></iframe><iframe src=\"second.html\"></iframe>",
+                ),
+                ("first.html", "<p>First</p>"),
+                ("second.html", "<p>Second</p>"),
+            ],
+        );
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.no_metadata = true;
+        options.max_inlined_frames_size = Some(100);
+        let html = archive(&dir.join("index.html"), options);
+
+        let sources = frame_sources(&html);
+        assert_eq!(sources.len(), 2);
+        assert!(frame_document(&sources[0]).contains("<p>First</p>"));
+        assert_eq!(
+            sources[1],
+            Url::from_file_path(dir.join("second.html"))
+                .unwrap()
+                .to_string()
+        );
+    }
+}
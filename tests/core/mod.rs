@@ -1,4 +1,12 @@
+mod archive_to_writer;
+mod create_monolithic_document;
 mod detect_media_type;
+mod find_fresh_output;
 mod format_output_path;
+mod max_iframe_depth;
 mod options;
 mod parse_content_type;
+mod process_dom;
+mod process_urls;
+mod root_selector;
+mod save_output;
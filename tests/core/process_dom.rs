@@ -0,0 +1,91 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use html5ever::interface::{Attribute, QualName};
+    use html5ever::tree_builder::{create_element, NodeOrText, TreeSink};
+    use html5ever::{namespace_url, ns, LocalName};
+
+    use monolith::core::{DocumentProcessor, MonolithOptions};
+    use monolith::html::{self, find_nodes};
+    use monolith::session::Session;
+
+    const PAGE: &str = "<html><head><title>Page</title></head><body><p>Hello</p></body></html>";
+
+    fn session() -> Session {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.no_metadata = true;
+        Session::new(None, None, options)
+    }
+
+    #[test]
+    fn matches_bytes_api() {
+        let (from_bytes, title) = DocumentProcessor::new(session())
+            .process_document(PAGE.as_bytes().to_vec(), None, None)
+            .unwrap();
+        let dom = html::html_to_dom(PAGE.as_bytes(), "".to_string());
+        let (from_dom, dom_title) = DocumentProcessor::new(session())
+            .process_dom(dom, None)
+            .unwrap();
+
+        assert_eq!(from_dom, from_bytes);
+        assert_eq!(dom_title, title);
+        assert_eq!(dom_title.as_deref(), Some("Page"));
+    }
+
+    #[test]
+    fn keeps_nodes_added_before_processing() {
+        let dom = html::html_to_dom(PAGE.as_bytes(), "".to_string());
+        let body = find_nodes(&dom.document, vec!["html", "body"])
+            .first()
+            .unwrap()
+            .clone();
+        let aside = create_element(
+            &dom,
+            QualName::new(None, ns!(), LocalName::from("aside")),
+            vec![Attribute {
+                name: QualName::new(None, ns!(), LocalName::from("data-note")),
+                value: "preprocessed".into(),
+            }],
+        );
+        dom.append(&body, NodeOrText::AppendNode(aside));
+
+        let (output, _) = DocumentProcessor::new(session())
+            .process_dom(dom, Some("https://example.com/page".to_string()))
+            .unwrap();
+
+        assert!(String::from_utf8_lossy(&output)
+            .contains("<p>Hello</p><aside data-note=\"preprocessed\"></aside>"));
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::core::{DocumentProcessor, MonolithOptions};
+    use monolith::html;
+    use monolith::session::Session;
+
+    #[test]
+    fn rejects_invalid_document_url() {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        let dom = html::html_to_dom(b"<p>Hello</p>", "".to_string());
+
+        assert!(DocumentProcessor::new(Session::new(None, None, options))
+            .process_dom(dom, Some("not a url".to_string()))
+            .is_err());
+    }
+}
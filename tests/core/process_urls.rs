@@ -0,0 +1,161 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+
+    use monolith::core::{process_urls, process_urls_with_options, BatchOptions, MonolithOptions};
+    use monolith::session::Session;
+
+    use crate::common::server::{Response, TestServer};
+
+    /// Starts an HTTP server that answers `/slow` after two seconds, `/missing` with 404
+    ///  and any other path with a page titled after the path, and returns its base URL
+    ///  along with the highest number of requests it was serving at the same time
+    pub fn start_server() -> (String, Arc<AtomicUsize>) {
+        let active = AtomicUsize::new(0);
+        let max_active = Arc::new(AtomicUsize::new(0));
+
+        let peak = Arc::clone(&max_active);
+        let server = TestServer::start(move |request| {
+            let now_active = active.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now_active, Ordering::SeqCst);
+            let response = match request.path.as_str() {
+                "/slow" => {
+                    thread::sleep(Duration::from_secs(2));
+                    Response::ok("text/html", "<html></html>")
+                }
+                "/missing" => Response::new("404 Not Found", "text/html", ""),
+                path => {
+                    thread::sleep(Duration::from_millis(100));
+                    Response::ok(
+                        "text/html",
+                        format!("<html><head><title>{}</title></head></html>", path),
+                    )
+                }
+            };
+            active.fetch_sub(1, Ordering::SeqCst);
+            response
+        });
+
+        (
+            server.url().as_str().trim_end_matches('/').to_string(),
+            max_active,
+        )
+    }
+
+    pub fn session() -> Session {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        Session::new(None, None, options)
+    }
+
+    #[test]
+    fn results_in_order() {
+        let (base_url, max_active) = start_server();
+        let targets: Vec<String> = (0..6).map(|i| format!("{}/page{}", base_url, i)).collect();
+
+        let results = process_urls(&session(), &targets, 2);
+
+        assert_eq!(results.len(), 6);
+        for (i, result) in results.iter().enumerate() {
+            assert_eq!(result.target, targets[i]);
+            let (_, title) = result.result.as_ref().unwrap();
+            assert_eq!(title.as_deref(), Some(format!("/page{}", i).as_str()));
+        }
+        assert!(max_active.load(Ordering::SeqCst) <= 2);
+    }
+
+    #[test]
+    fn progress_is_reported() {
+        let (base_url, _) = start_server();
+        let targets: Vec<String> = vec![format!("{}/a", base_url), format!("{}/b", base_url)];
+        let reported = Arc::new(Mutex::new(Vec::new()));
+
+        let log = Arc::clone(&reported);
+        let options = BatchOptions {
+            concurrency: 2,
+            progress: Some(Arc::new(move |result, finished, total| {
+                log.lock()
+                    .unwrap()
+                    .push((result.target.clone(), finished, total));
+            })),
+            ..BatchOptions::default()
+        };
+        process_urls_with_options(&session(), &targets, &options);
+
+        let mut reported = reported.lock().unwrap().clone();
+        reported.sort();
+        assert_eq!(
+            reported
+                .iter()
+                .map(|(target, _, total)| (target.as_str(), *total))
+                .collect::<Vec<_>>(),
+            vec![(targets[0].as_str(), 2), (targets[1].as_str(), 2)]
+        );
+        let mut finished: Vec<usize> = reported.iter().map(|(_, finished, _)| *finished).collect();
+        finished.sort();
+        assert_eq!(finished, vec![1, 2]);
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use std::time::{Duration, Instant};
+
+    use monolith::core::{process_urls, process_urls_with_options, BatchOptions};
+
+    use super::passing::{session, start_server};
+
+    #[test]
+    fn failed_target_does_not_stop_batch() {
+        let (base_url, _) = start_server();
+        let targets = vec![
+            format!("{}/missing", base_url),
+            format!("{}/page", base_url),
+        ];
+
+        let results = process_urls(&session(), &targets, 1);
+
+        assert!(results[0].result.is_err());
+        assert!(results[1].result.is_ok());
+    }
+
+    #[test]
+    fn target_timeout() {
+        let (base_url, _) = start_server();
+        let targets = vec![format!("{}/slow", base_url), format!("{}/page", base_url)];
+        let options = BatchOptions {
+            concurrency: 2,
+            target_timeout: Some(Duration::from_millis(500)),
+            ..BatchOptions::default()
+        };
+
+        let started = Instant::now();
+        let results = process_urls_with_options(&session(), &targets, &options);
+
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert!(results[0]
+            .result
+            .as_ref()
+            .unwrap_err()
+            .to_string()
+            .contains("timed out"));
+        assert!(results[1].result.is_ok());
+    }
+}
@@ -0,0 +1,119 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use monolith::core::{create_monolithic_document, MonolithOptions};
+    use monolith::session::Session;
+
+    const PNG: &[u8] = b"\x89PNG\x0D\x0A\x1A\x0A\x00\x00\x00\x0DIHDR";
+
+    fn write_page(name: &str, html: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "monolith-root-selector-{}-{}",
+            name,
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("article.png"), PNG).unwrap();
+        fs::write(dir.join("sidebar.png"), PNG).unwrap();
+        fs::write(dir.join("index.html"), html).unwrap();
+        dir.join("index.html")
+    }
+
+    fn archive(path: &PathBuf, selector: &str) -> Result<String, String> {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.no_metadata = true;
+        options.root_selector = Some(selector.to_string());
+        let session = Session::new(None, None, options);
+        create_monolithic_document(session, path.to_str().unwrap())
+            .map(|(data, _)| String::from_utf8_lossy(&data).to_string())
+            .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn keeps_only_selected_subtree() {
+        let path = write_page(
+            "subtree",
+            "<html><head><title>Page</title><style>p { color: red; }</style></head><body>\
+             <nav>Navigation</nav>\
+             <div id=\"article\" class=\"post\"><p>Article text</p><img src=\"article.png\"></div>\
+             <aside><style>.post { margin: 0; }</style><img src=\"sidebar.png\">Sidebar</aside>\
+             </body></html>",
+        );
+        let html = archive(&path, "#article").unwrap();
+
+        assert!(html.contains("Article text"));
+        assert!(html.contains("data:image/png;base64,"));
+        assert!(!html.contains("Navigation"));
+        assert!(!html.contains("Sidebar"));
+        assert!(!html.contains("sidebar.png"));
+        // Stylesheets inside HEAD and outside the fragment are kept
+        assert!(html.contains("p { color: red; }"));
+        assert!(html.contains(".post { margin: 0; }"));
+        assert_eq!(html.matches("data:image/png;base64,").count(), 1);
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn tag_and_class_selectors() {
+        let path = write_page(
+            "class",
+            "<html><body><div>Other</div><section class=\"a widget\">Widget</section></body></html>",
+        );
+        let html = archive(&path, "section.widget").unwrap();
+
+        assert!(html.contains("<body><section class=\"a widget\">Widget</section></body>"));
+        assert!(!html.contains("Other"));
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use std::fs;
+
+    use monolith::core::{create_monolithic_document, MonolithOptions};
+    use monolith::session::Session;
+
+    #[test]
+    fn no_matching_element() {
+        let dir = std::env::temp_dir().join(format!(
+            "monolith-root-selector-missing-{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("index.html");
+        fs::write(&path, "<html><body><p>Text</p></body></html>").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.root_selector = Some("#missing".to_string());
+        let session = Session::new(None, None, options);
+        let result = create_monolithic_document(session, path.to_str().unwrap());
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "No element matches root selector \"#missing\""
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,117 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::fs;
+    use std::io::Read;
+    use std::path::PathBuf;
+
+    use monolith::core::{save_output, OutputCompression};
+
+    pub fn output_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "monolith-save-output-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    pub fn document() -> Vec<u8> {
+        "<html><head><title>Archive</title></head><body><p>Hello</p></body></html>"
+            .repeat(50)
+            .into_bytes()
+    }
+
+    #[test]
+    fn gzip() {
+        let dir = output_dir("gzip");
+        let data = document();
+
+        let saved = save_output(
+            &data,
+            &format!("{}/page.html", dir.display()),
+            OutputCompression::Gzip,
+        )
+        .unwrap();
+
+        assert_eq!(saved.path, dir.join("page.html.gz"));
+        assert_eq!(saved.uncompressed_size, data.len());
+        assert!(saved.written_size < data.len());
+
+        let mut decompressed = Vec::new();
+        flate2::read::GzDecoder::new(fs::File::open(&saved.path).unwrap())
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, data);
+    }
+
+    #[test]
+    fn brotli_from_extension() {
+        let dir = output_dir("brotli");
+        let data = document();
+        let path = format!("{}/page.html.br", dir.display());
+
+        let saved = save_output(&data, &path, OutputCompression::from_path(&path)).unwrap();
+
+        assert_eq!(saved.path, PathBuf::from(&path));
+        assert_eq!(saved.uncompressed_size, data.len());
+        assert_eq!(
+            saved.written_size,
+            fs::metadata(&path).unwrap().len() as usize
+        );
+
+        let mut decompressed = Vec::new();
+        brotli::Decompressor::new(fs::File::open(&path).unwrap(), 4096)
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, data);
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use std::fs;
+
+    use monolith::core::{save_output, OutputCompression};
+
+    use super::passing::{document, output_dir};
+
+    #[test]
+    fn uncompressed() {
+        let dir = output_dir("uncompressed");
+        let data = document();
+        let path = format!("{}/page.html", dir.display());
+
+        let saved = save_output(&data, &path, OutputCompression::from_path(&path)).unwrap();
+
+        assert_eq!(saved.written_size, data.len());
+        assert_eq!(fs::read(&path).unwrap(), data);
+    }
+
+    #[test]
+    fn missing_directory() {
+        let dir = output_dir("missing");
+
+        assert!(save_output(
+            &document(),
+            &format!("{}/missing/page.html", dir.display()),
+            OutputCompression::Gzip,
+        )
+        .is_err());
+    }
+}
@@ -9,7 +9,7 @@
 mod passing {
     use reqwest::Url;
 
-    use monolith::core::MonolithOptions;
+    use monolith::core::{DataUrlEncoding, MonolithOptions};
     use monolith::css;
     use monolith::session::Session;
     use monolith::url::EMPTY_IMAGE_DATA_URL;
@@ -182,6 +182,65 @@ mod passing {
         );
     }
 
+    #[test]
+    fn import_string_percent_encoded() {
+        let document_url: Url = Url::parse("https://doesntmatter.local/").unwrap();
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.data_url_encoding = DataUrlEncoding::PercentEncode;
+        let mut session: Session = Session::new(None, None, options);
+
+        const CSS: &str = "\
+            @import 'data:text/css;base64,aHRtbHtiYWNrZ3JvdW5kLWNvbG9yOiMwMDB9';\n\
+            ";
+
+        assert_eq!(
+            css::embed_css(&mut session, &document_url, CSS),
+            "\
+            @import \"data:text/css,html{background-color:%23000}\";\n\
+            "
+        );
+    }
+
+    #[test]
+    fn import_with_invalid_utf8() {
+        let document_url: Url = Url::parse("https://doesntmatter.local/").unwrap();
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        let mut session: Session = Session::new(None, None, options);
+
+        const CSS: &str = "\
+            @import 'data:text/css;charset=utf-8,body%7Bcolor:red%7D%FF';\n\
+            ";
+
+        assert_eq!(
+            css::embed_css(&mut session, &document_url, CSS),
+            "\
+            @import \"data:text/css;charset=utf-8;base64,Ym9keXtjb2xvcjpyZWR977+9\";\n\
+            "
+        );
+    }
+
+    #[test]
+    fn import_transcoded_to_utf8() {
+        let document_url: Url = Url::parse("https://doesntmatter.local/").unwrap();
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        let mut session: Session = Session::new(None, None, options);
+
+        // "é" in windows-1252
+        const CSS: &str = "\
+            @import 'data:text/css;charset=windows-1252;base64,YTo6YmVmb3Jle2NvbnRlbnQ6IukifQ==';\n\
+            ";
+
+        assert_eq!(
+            css::embed_css(&mut session, &document_url, CSS),
+            "\
+            @import \"data:text/css;charset=utf-8;base64,YTo6YmVmb3Jle2NvbnRlbnQ6IsOpIn0=\";\n\
+            "
+        );
+    }
+
     #[test]
     fn hash_urls() {
         let document_url: Url = Url::parse("https://doesntmatter.local/").unwrap();
@@ -305,6 +364,29 @@ mod passing {
         assert_eq!(css::embed_css(&mut session, &document_url, CSS), CSS_OUT);
     }
 
+    #[test]
+    fn image_set() {
+        let document_url: Url = Url::parse("data:,").unwrap();
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        let mut session: Session = Session::new(None, None, options);
+
+        const CSS: &str = "\
+            .hero {\n\
+                background: image-set(\"data:image/png,a\" 1x, url(data:image/png,b) 2x);\n\
+                background-image: -webkit-image-set('data:image/png,c' type(\"image/png\") 1x);\n\
+            }\n\
+            ";
+        const CSS_OUT: &str = "\
+            .hero {\n\
+                background: image-set(\"data:image/png;base64,YQ==\" 1x, url(\"data:image/png;base64,Yg==\") 2x);\n\
+                background-image: -webkit-image-set(\"data:image/png;base64,Yw==\" type(\"image/png\") 1x);\n\
+            }\n\
+            ";
+
+        assert_eq!(css::embed_css(&mut session, &document_url, CSS), CSS_OUT);
+    }
+
     #[test]
     fn ie_css_hack() {
         let document_url: Url = Url::parse("data:,").unwrap();
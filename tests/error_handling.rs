@@ -5,17 +5,19 @@
 use std::time::Duration;
 
 use monolith::translation::error::TranslationError;
-use monolith::translation::storage::cache::{CacheKey, CacheEntry};
+use monolith::translation::storage::cache::CacheEntry;
 use monolith::translation::pipeline::collector::TextItem;
 use monolith::translation::pipeline::filters::TextFilter;
 
+// 每个测试目标只用到公共模块的一部分
+#[allow(dead_code)]
 mod common {
     include!("common/mod.rs");
 }
 
 use common::{
     TestEnvironment, TestConfigBuilder, HtmlTestHelper, TestDataGenerator,
-    PerformanceHelper
+    PerformanceHelper, CacheTestHelper
 };
 
 /// 测试无效HTML输入的错误处理
@@ -24,7 +26,7 @@ async fn test_invalid_html_error_handling() {
     let mut env = TestEnvironment::default();
     
     // 测试各种无效HTML输入
-    let invalid_html_cases = vec![
+    let invalid_html_cases = [
         "",                                    // 空HTML
         "<html><head></head><body></body></html>", // 有效但空的HTML
         "<div>Unclosed div",                   // 未闭合标签
@@ -61,27 +63,29 @@ async fn test_cache_system_error_recovery() {
     let env = TestEnvironment::default();
     
     // 测试无效缓存键
-    let invalid_keys = vec![
-        CacheKey::new("".to_string(), "".to_string(), "".to_string()),
-        CacheKey::new("text".to_string(), "".to_string(), "zh".to_string()),
-        CacheKey::new("text".to_string(), "en".to_string(), "".to_string()),
-        CacheKey::new("\0\0\0".to_string(), "en".to_string(), "zh".to_string()), // 空字符
+    let invalid_keys = [
+        ("", "", ""),
+        ("text", "", "zh"),
+        ("text", "en", ""),
+        ("\0\0\0", "en", "zh"), // 空字符
     ];
     
-    for (i, key) in invalid_keys.iter().enumerate() {
+    for (i, (text, source_lang, target_lang)) in invalid_keys.iter().enumerate() {
         // 获取操作应该优雅处理无效键
-        let get_result = env.cache_manager.get(key).await;
-        assert!(get_result.is_ok(), "Get operation should handle invalid key {} gracefully", i);
+        let get_result = env.cache_manager.lookup(text, target_lang);
+        assert!(get_result.is_none(), "Get operation should handle invalid key {} gracefully", i);
         
         // 存储操作也应该处理无效键
         let entry = CacheEntry::new(
-            "test text".to_string(),
+            text.to_string(),
             "测试文本".to_string(),
-            Some(Duration::from_secs(300)),
+            source_lang.to_string(),
+            target_lang.to_string(),
         );
         
-        let put_result = env.cache_manager.put(key.clone(), entry).await;
-        assert!(put_result.is_ok(), "Put operation should handle invalid key {} gracefully", i);
+        CacheTestHelper::put(&env.cache_manager, &entry);
+        let put_result = env.cache_manager.lookup(text, target_lang);
+        assert!(put_result.is_some(), "Put operation should handle invalid key {} gracefully", i);
     }
     
     println!("✅ Cache error recovery test passed - handled {} invalid keys", invalid_keys.len());
@@ -146,15 +150,15 @@ async fn test_text_filter_boundary_cases() {
         
         // 可翻译性评分应该在有效范围内
         let score = env.text_filter.translatability_score(text);
-        assert!(score >= 0.0 && score <= 1.0, 
+        assert!((0.0..=1.0).contains(&score), 
                "Translatability score for case {} should be in [0,1], got {}", i, score);
         
         // 文本分析应该完成
         let analysis = env.text_filter.analyze_text(text);
         assert_eq!(analysis.original_text, *text, "Analysis should preserve original text");
         
-        let display_text = if text.len() > 20 { 
-                format!("{}...", &text[..20]) 
+        let display_text = if text.chars().count() > 20 { 
+                format!("{}...", text.chars().take(20).collect::<String>()) 
             } else { 
                 text.to_string() 
             };
@@ -183,30 +187,24 @@ async fn test_concurrent_error_scenarios() {
             // 每个任务执行多种可能出错的操作
             for op_id in 0..10 {
                 // 测试无效缓存操作
-                let invalid_key = CacheKey::new(
+                let mut entry = CacheEntry::new(
                     format!("task_{}_op_{}", task_id, op_id),
+                    "测试".to_string(),
                     if op_id % 3 == 0 { "" } else { "en" }.to_string(),
                     "zh".to_string(),
                 );
-                
-                let entry = CacheEntry::new(
-                    "test".to_string(),
-                    "测试".to_string(),
-                    if op_id % 4 == 0 { None } else { Some(Duration::from_millis(1)) }, // 有些立即过期
-                );
+                if op_id % 4 != 0 {
+                    entry.ttl = Some(Duration::from_millis(1)); // 有些立即过期
+                }
                 
                 // 并发缓存操作
-                match cache_manager.put(invalid_key.clone(), entry).await {
-                    Ok(_) => {
-                        local_successes += 1;
-                        // 尝试立即获取
-                        if cache_manager.get(&invalid_key).await.is_ok() {
-                            local_successes += 1;
-                        } else {
-                            local_errors += 1;
-                        }
-                    }
-                    Err(_) => local_errors += 1,
+                CacheTestHelper::put(&cache_manager, &entry);
+                local_successes += 1;
+                // 尝试立即获取
+                if cache_manager.get(&entry.original_text).is_some() {
+                    local_successes += 1;
+                } else {
+                    local_errors += 1;
                 }
                 
                 // 测试边界文本过滤
@@ -243,7 +241,7 @@ async fn test_concurrent_error_scenarios() {
     
     // 系统应该能够处理错误而不崩溃
     let final_stats = env.cache_manager.get_stats();
-    assert!(final_stats.total_entries >= 0, "Cache should remain in valid state");
+    assert!(final_stats.total_entries > 0, "Cache should keep the entries written concurrently");
     
     println!("✅ Concurrent error scenarios test passed - {} successes, {} errors handled gracefully", 
              total_successes, total_errors);
@@ -278,10 +276,9 @@ async fn test_resource_exhaustion_handling() {
         let mut successful_operations = 0;
         
         for (key, entry) in large_cache_entries {
-            if env.cache_manager.put(key.clone(), entry).await.is_ok() {
-                if env.cache_manager.get(&key).await.is_ok() {
-                    successful_operations += 1;
-                }
+            CacheTestHelper::put(&env.cache_manager, &entry);
+            if env.cache_manager.get(&key).is_some() {
+                successful_operations += 1;
             }
         }
         
@@ -341,30 +338,18 @@ async fn test_error_propagation_and_recovery() {
         let mut cache_operations = 0;
         for batch in &batches {
             for item in &batch.items {
-                let key = CacheKey::new(
-                    item.text.clone(),
-                    "en".to_string(),
-                    "zh".to_string(),
-                );
-                
                 // 尝试获取缓存
-                match env.cache_manager.get(&key).await {
-                    Ok(Some(_)) => cache_operations += 1,
-                    Ok(None) => {
+                match env.cache_manager.lookup(&item.text, "zh") {
+                    Some(_) => cache_operations += 1,
+                    None => {
                         // 缓存未命中，尝试存储
-                        let entry = CacheEntry::new(
+                        env.cache_manager.insert_with_lang(
                             item.text.clone(),
                             format!("翻译:{}", item.text),
-                            Some(Duration::from_secs(300)),
+                            "en".to_string(),
+                            "zh".to_string(),
                         );
-                        
-                        if env.cache_manager.put(key, entry).await.is_ok() {
-                            cache_operations += 1;
-                        }
-                    }
-                    Err(_) => {
-                        // 缓存错误，继续处理其他项
-                        continue;
+                        cache_operations += 1;
                     }
                 }
             }
@@ -388,11 +373,11 @@ async fn test_error_propagation_and_recovery() {
     }
     
     // 验证系统状态仍然有效
-    let cache_stats = env.cache_manager.get_stats();
-    assert!(cache_stats.total_entries >= 0, "Cache should remain in valid state after errors");
+    // 出错之后仍然可以读取缓存统计
+    let _cache_stats = env.cache_manager.get_stats();
     
     let collector_stats = env.get_text_collector_mut().get_stats();
-    assert!(collector_stats.nodes_visited >= 0, "Collector should remain in valid state");
+    assert!(collector_stats.nodes_visited > 0, "Collector should remain in valid state");
 }
 
 /// 测试长时间运行的稳定性
@@ -432,21 +417,15 @@ async fn test_long_running_stability() {
                         // 模拟缓存操作
                         for batch in batches.iter().take(2) { // 限制操作数量以加速测试
                             for item in batch.items.iter().take(3) {
-                                let key = CacheKey::new(
-                                    item.text.clone(),
-                                    "en".to_string(),
-                                    "zh".to_string(),
-                                );
-                                
                                 let entry = CacheEntry::new(
                                     item.text.clone(),
                                     format!("翻译{}", operation_count),
-                                    Some(Duration::from_secs(60)),
+                                    "en".to_string(),
+                                    "zh".to_string(),
                                 );
                                 
-                                if env.cache_manager.put(key.clone(), entry).await.is_ok() {
-                                    let _ = env.cache_manager.get(&key).await;
-                                }
+                                CacheTestHelper::put(&env.cache_manager, &entry);
+                                let _ = env.cache_manager.get(&item.text);
                             }
                         }
                         operation_count += 1;
@@ -476,7 +455,7 @@ async fn test_long_running_stability() {
     
     // 验证系统最终状态
     let final_cache_stats = env.cache_manager.get_stats();
-    assert!(final_cache_stats.total_entries >= 0, "Cache should remain stable");
+    assert!(final_cache_stats.total_entries > 0, "Cache should keep the entries written during the run");
     
     println!("✅ Long running stability test passed - {} operations, {:.1}% error rate in {:?}", 
              operation_count, error_rate * 100.0, start_time.elapsed());
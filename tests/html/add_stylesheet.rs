@@ -0,0 +1,58 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use html5ever::serialize::{serialize, SerializeOpts};
+    use markup5ever_rcdom::{RcDom, SerializableHandle};
+
+    use monolith::html;
+
+    fn serialize_dom(dom: &RcDom) -> String {
+        let mut buf: Vec<u8> = Vec::new();
+        serialize(
+            &mut buf,
+            &SerializableHandle::from(dom.document.clone()),
+            SerializeOpts::default(),
+        )
+        .unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn basic() {
+        let html = "<title>Page</title><div>text</div>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+
+        let dom = html::add_stylesheet(dom, "body { background: #222; }");
+
+        assert_eq!(
+            serialize_dom(&dom),
+            "<html><head><title>Page</title><style>body { background: #222; }</style></head><body><div>text</div></body></html>"
+        );
+    }
+
+    #[test]
+    fn creates_missing_head() {
+        let html = "<div>text</div>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        for head in html::find_nodes(&dom.document, vec!["html", "head"]) {
+            let html_node = html::get_parent_node(&head);
+            html_node
+                .children
+                .borrow_mut()
+                .retain(|child| !std::rc::Rc::ptr_eq(child, &head));
+        }
+
+        let dom = html::add_stylesheet(dom, "p { margin: 0; }");
+
+        assert_eq!(
+            serialize_dom(&dom),
+            "<html><head><style>p { margin: 0; }</style></head><body><div>text</div></body></html>"
+        );
+    }
+}
@@ -31,6 +31,19 @@ mod passing {
         assert_eq!(csp_content, "style-src 'none';");
     }
 
+    #[test]
+    fn no_css_with_extra_css() {
+        let mut options = MonolithOptions::default();
+        options.no_css = true;
+        options.extra_css = Some("body { background: #222; }\n".to_string());
+        let csp_content = html::compose_csp(&options);
+
+        assert_eq!(
+            csp_content,
+            "style-src 'sha256-AQjFGEs/engbw8+MCEYt4LY7Udec8bYNG9Yzi+VTKBo=';"
+        );
+    }
+
     #[test]
     fn no_fonts() {
         let mut options = MonolithOptions::default();
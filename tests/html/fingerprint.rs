@@ -0,0 +1,88 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::html;
+
+    fn fingerprint(html: &str) -> String {
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        html::content_fingerprint(&dom.document)
+    }
+
+    #[test]
+    fn ignores_embed_payloads_and_volatile_bits() {
+        let first = "<!-- Saved from https://example.com/ at 2024-01-01T00:00:00Z -->\
+                     <html><head>\
+                     <meta name=\"csrf-token\" content=\"abc\">\
+                     <script nonce=\"n1\">var now = 1700000000;</script>\
+                     <style>body { background: url(\"data:image/png;base64,AAAA\"); }</style>\
+                     </head><body>\
+                     <img src=\"data:image/png;base64,AAAA\" alt=\"a\">\
+                     <img src=\"data:image/png;base64,BBBB\" alt=\"b\">\
+                     <p class=\"x\" id=\"intro\">Hello   world</p>\
+                     </body></html>";
+        let second = "<!-- Saved from https://example.com/ at 2025-06-30T12:00:00Z -->\
+                      <html><head>\
+                      <meta name=\"csrf-token\" content=\"xyz\">\
+                      <script nonce=\"n2\">var now = 1800000000;</script>\
+                      <style>body { background: url(\"data:image/png;base64,CCCC\"); }</style>\
+                      </head><body>\
+                      <img alt=\"a\" src=\"data:image/png;base64,BBBB\">\
+                      <img alt=\"b\" src=\"data:image/png;base64,AAAA\">\
+                      <p id=\"intro\" class=\"x\">Hello\n world</p>\
+                      </body></html>";
+
+        assert_eq!(fingerprint(first), fingerprint(second));
+        assert_eq!(fingerprint(first).len(), 64);
+    }
+
+    #[test]
+    fn includes_template_contents() {
+        assert_ne!(
+            fingerprint("<template><p>One</p></template>"),
+            fingerprint("<template><p>Two</p></template>")
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::html;
+
+    fn fingerprint(html: &str) -> String {
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        html::content_fingerprint(&dom.document)
+    }
+
+    #[test]
+    fn detects_text_changes() {
+        assert_ne!(
+            fingerprint("<p>Price: 10</p>"),
+            fingerprint("<p>Price: 12</p>")
+        );
+    }
+
+    #[test]
+    fn detects_structure_and_media_type_changes() {
+        assert_ne!(
+            fingerprint("<p><b>Hello</b></p>"),
+            fingerprint("<p><i>Hello</i></p>")
+        );
+        assert_ne!(
+            fingerprint("<img src=\"data:image/png;base64,AAAA\">"),
+            fingerprint("<img src=\"data:image/gif;base64,AAAA\">")
+        );
+    }
+}
@@ -0,0 +1,85 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::html;
+
+    pub fn refresh_url(content: &str) -> Option<String> {
+        let html = format!("<meta http-equiv=\"refresh\" content=\"{}\">", content);
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        html::get_meta_refresh_url(&dom.document)
+    }
+
+    #[test]
+    fn plain_url() {
+        assert_eq!(
+            refresh_url("0;url=https://example.com/"),
+            Some("https://example.com/".to_string())
+        );
+    }
+
+    #[test]
+    fn spacing_and_case() {
+        assert_eq!(
+            refresh_url("5 ; URL = next.html"),
+            Some("next.html".to_string())
+        );
+        assert_eq!(
+            refresh_url("0,Url=next.html"),
+            Some("next.html".to_string())
+        );
+    }
+
+    #[test]
+    fn quoted_url() {
+        assert_eq!(
+            refresh_url("0; url='next.html'"),
+            Some("next.html".to_string())
+        );
+    }
+
+    #[test]
+    fn without_url_prefix() {
+        assert_eq!(refresh_url("0; next.html"), Some("next.html".to_string()));
+        assert_eq!(refresh_url("0; urls.html"), Some("urls.html".to_string()));
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::html;
+
+    use super::passing::refresh_url;
+
+    #[test]
+    fn reload_only() {
+        assert_eq!(refresh_url("30"), None);
+        assert_eq!(refresh_url("0; url="), None);
+    }
+
+    #[test]
+    fn invalid_delay() {
+        assert_eq!(refresh_url("soon; url=next.html"), None);
+        assert_eq!(refresh_url("; url=next.html"), None);
+    }
+
+    #[test]
+    fn no_refresh_tag() {
+        let html = "<meta http-equiv=\"content-type\" content=\"0; url=next.html\">";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+
+        assert_eq!(html::get_meta_refresh_url(&dom.document), None);
+    }
+}
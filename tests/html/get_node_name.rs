@@ -50,4 +50,22 @@ mod passing {
 
         assert_eq!(count, 7);
     }
+
+    #[test]
+    fn parent_node_lookup_keeps_parent() {
+        let html = "<!doctype html><html><body><div><p></p></div></body></html>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let p = html::find_nodes(&dom.document, vec!["html", "body", "div", "p"])
+            .pop()
+            .unwrap();
+
+        assert_eq!(html::get_node_name(&html::get_parent_node(&p)), Some("div"));
+        assert_eq!(html::get_node_name(&html::get_parent_node(&p)), Some("div"));
+
+        html::remove_node(&p);
+        let div = html::find_nodes(&dom.document, vec!["html", "body", "div"])
+            .pop()
+            .unwrap();
+        assert!(div.children.borrow().is_empty());
+    }
 }
@@ -0,0 +1,64 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::html;
+
+    #[test]
+    fn open_graph() {
+        let html = "<html><head>\
+                    <meta property=\"og:title\" content=\"Launch announcement\">\
+                    <meta property=\"og:description\" content=\" Everything about the launch \">\
+                    <meta property=\"og:image\" content=\"https://example.com/cover.png\">\
+                    <meta property=\"og:type\" content=\"article\">\
+                    <meta name=\"twitter:title\" content=\"Twitter title\">\
+                    </head><body></body></html>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+
+        assert_eq!(
+            html::get_social_metadata(&dom.document),
+            html::SocialMetadata {
+                title: Some("Launch announcement".to_string()),
+                description: Some("Everything about the launch".to_string()),
+                image: Some("https://example.com/cover.png".to_string()),
+                kind: Some("article".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn twitter_card_fallback() {
+        let html = "<html><head>\
+                    <meta name=\"twitter:card\" content=\"summary\">\
+                    <meta name=\"twitter:title\" content=\"Launch\">\
+                    <meta name=\"og:description\" content=\"Written with name instead of property\">\
+                    <meta name=\"twitter:image:src\" content=\"/cover.png\">\
+                    <meta name=\"description\" content=\"Plain description\">\
+                    </head><body></body></html>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+
+        assert_eq!(
+            html::get_social_metadata(&dom.document),
+            html::SocialMetadata {
+                title: Some("Launch".to_string()),
+                description: Some("Written with name instead of property".to_string()),
+                image: Some("/cover.png".to_string()),
+                kind: Some("summary".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn no_social_metadata() {
+        let html = "<html><head><meta property=\"og:title\" content=\"  \">\
+                    <meta name=\"description\" content=\"Plain description\"></head></html>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+
+        assert!(html::get_social_metadata(&dom.document).is_empty());
+    }
+}
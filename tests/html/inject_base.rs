@@ -0,0 +1,93 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::core::{create_monolithic_document_from_data, MonolithOptions};
+    use monolith::html;
+    use monolith::session::Session;
+    use monolith::url::Url;
+
+    fn archive(html: &str, options: MonolithOptions) -> String {
+        let session = Session::new(None, None, options);
+        let (data, _) = create_monolithic_document_from_data(
+            session,
+            html.as_bytes().to_vec(),
+            None,
+            Some("https://example.com:8443/docs/page.html#intro".to_string()),
+        )
+        .unwrap();
+        String::from_utf8(data).unwrap()
+    }
+
+    fn inject_base() -> MonolithOptions {
+        MonolithOptions {
+            inject_base: true,
+            no_images: true,
+            no_metadata: true,
+            silent: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn injects_document_url() {
+        let output = archive(
+            "<html><head><title>Page</title></head><body><a href=\"#top\">Top</a></body></html>",
+            inject_base(),
+        );
+
+        assert!(output.contains("<base href=\"https://example.com:8443/docs/page.html\">"));
+    }
+
+    #[test]
+    fn no_base_when_all_assets_embedded() {
+        let output = archive(
+            "<html><head></head><body></body></html>",
+            MonolithOptions {
+                no_images: false,
+                ..inject_base()
+            },
+        );
+
+        assert!(!output.contains("<base"));
+    }
+
+    #[test]
+    fn keeps_existing_base() {
+        let output = archive(
+            "<html><head><base href=\"https://cdn.example.com/\"></head><body></body></html>",
+            inject_base(),
+        );
+
+        assert!(output.contains("<base href=\"https://cdn.example.com/\">"));
+        assert!(!output.contains("example.com:8443"));
+    }
+
+    #[test]
+    fn no_base_by_default() {
+        let output = archive(
+            "<html><head></head><body></body></html>",
+            MonolithOptions {
+                no_metadata: true,
+                silent: true,
+                ..Default::default()
+            },
+        );
+
+        assert!(!output.contains("<base"));
+    }
+
+    #[test]
+    fn skips_opaque_origins() {
+        let dom = html::html_to_dom(b"<p>text</p>", "".to_string());
+        let dom = html::inject_base(dom, &Url::parse("file:///home/user/page.html").unwrap());
+        let output = html::serialize_document(dom, "".to_string(), &MonolithOptions::default());
+
+        assert!(!String::from_utf8_lossy(&output).contains("<base"));
+    }
+}
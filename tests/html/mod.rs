@@ -1,16 +1,27 @@
 mod add_favicon;
+mod add_stylesheet;
 mod check_integrity;
 mod compose_csp;
 mod create_metadata_tag;
 mod embed_srcset;
+mod fingerprint;
 mod get_base_url;
 mod get_charset;
+mod get_meta_refresh_url;
 mod get_node_attr;
 mod get_node_name;
+mod get_social_metadata;
 mod has_favicon;
+mod inject_base;
 mod is_favicon;
+mod minify;
+mod normalize_head;
 mod parse_link_type;
 mod parse_srcset;
+mod sanitize;
 mod serialize_document;
+mod set_archive_metadata;
+mod serialize_document_to_writer;
 mod set_node_attr;
+mod set_viewport;
 mod walk;
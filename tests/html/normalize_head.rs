@@ -0,0 +1,63 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use html5ever::serialize::{serialize, SerializeOpts};
+    use markup5ever_rcdom::{RcDom, SerializableHandle};
+
+    use monolith::html;
+
+    fn serialize_dom(dom: &RcDom) -> String {
+        let mut buf: Vec<u8> = Vec::new();
+        serialize(
+            &mut buf,
+            &SerializableHandle::from(dom.document.clone()),
+            SerializeOpts::default(),
+        )
+        .unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn keeps_single_charset_declaration() {
+        let html = "<head><title>Page</title><meta charset=\"iso-8859-1\">\
+                    <meta http-equiv=\"Content-Type\" content=\"text/html; charset=windows-1252\">\
+                    <meta charset=\"utf-8\"></head><body></body>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+
+        let dom = html::normalize_head(dom, "utf-8");
+        let output = serialize_dom(&dom);
+
+        assert_eq!(output.matches("charset").count(), 1);
+        assert_eq!(
+            output,
+            "<html><head><meta charset=\"utf-8\"><title>Page</title></head><body></body></html>"
+        );
+    }
+
+    #[test]
+    fn keeps_first_meta_with_same_name() {
+        let html = "<head><meta name=\"viewport\" content=\"width=device-width\">\
+                    <meta property=\"og:image\" content=\"a.png\">\
+                    <meta property=\"og:image\" content=\"b.png\">\
+                    <meta name=\"Viewport\" content=\"width=1024\">\
+                    <meta charset=\"utf-8\"></head><body></body>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+
+        let dom = html::normalize_head(dom, "utf-8");
+
+        assert_eq!(
+            serialize_dom(&dom),
+            "<html><head><meta charset=\"utf-8\">\
+             <meta name=\"viewport\" content=\"width=device-width\">\
+             <meta property=\"og:image\" content=\"a.png\">\
+             <meta property=\"og:image\" content=\"b.png\">\
+             </head><body></body></html>"
+        );
+    }
+}
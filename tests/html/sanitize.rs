@@ -0,0 +1,223 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use html5ever::serialize::{serialize, SerializeOpts};
+    use markup5ever_rcdom::{RcDom, SerializableHandle};
+    use reqwest::Url;
+
+    use monolith::html::{self, SanitizeProfile};
+
+    pub fn sanitize_html(html: &str, profile: &SanitizeProfile) -> String {
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("https://example.com/articles/").unwrap();
+        html::sanitize(&dom.document, &url, profile);
+        serialize_dom(&dom)
+    }
+
+    fn serialize_dom(dom: &RcDom) -> String {
+        let mut buf: Vec<u8> = Vec::new();
+        serialize(
+            &mut buf,
+            &SerializableHandle::from(dom.document.clone()),
+            SerializeOpts::default(),
+        )
+        .unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn removes_scripts_and_event_handlers() {
+        let output = sanitize_html(
+            "<body><script>alert(1)</script>\
+             <img src=\"x.png\" onerror=\"alert(1)\" ONLOAD=\"alert(2)\" onpointerrawupdate=\"alert(3)\">\
+             </body>",
+            &SanitizeProfile::strict(),
+        );
+
+        assert_eq!(
+            output,
+            "<html><head></head><body><img src=\"x.png\"></body></html>"
+        );
+    }
+
+    #[test]
+    fn keeps_non_ascii_attribute_names() {
+        let output = sanitize_html(
+            "<body><div 中x=\"1\" 中=\"2\">text</div></body>",
+            &SanitizeProfile::strict(),
+        );
+
+        assert!(output.contains("中x=\"1\""), "{}", output);
+        assert!(output.contains("text</div>"), "{}", output);
+    }
+
+    #[test]
+    fn removes_obfuscated_script_urls() {
+        let output = sanitize_html(
+            "<body>\
+             <a href=\"javascript:alert(1)\">1</a>\
+             <a href=\"  JaVaScRiPt:alert(2)\">2</a>\
+             <a href=\"&#106;avascript:alert(3)\">3</a>\
+             <a href=\"java&#x09;script:alert(4)\">4</a>\
+             <a href=\"&#x01;javascript:alert(5)\">5</a>\
+             <a href=\"vbscript:msgbox(6)\">6</a>\
+             <form><button formaction=\"javascript:alert(7)\">7</button></form>\
+             <iframe src=\"java\nscript:alert(8)\"></iframe>\
+             </body>",
+            &SanitizeProfile::strict(),
+        );
+
+        assert!(!output.to_lowercase().contains("script:"), "{}", output);
+        assert!(output.contains("<a>1</a>"));
+        assert!(output.contains("<button>7</button>"));
+    }
+
+    #[test]
+    fn removes_svg_animation_script_urls() {
+        let output = sanitize_html(
+            "<body><svg><a><animate attributeName=\"href\" to=\"javascript:alert(1)\"></animate>\
+             <set attributeName=\"href\" values=\"x;javascript:alert(2)\"></set>\
+             <text>Click</text></a></svg></body>",
+            &SanitizeProfile::strict(),
+        );
+
+        assert!(!output.contains("javascript:"), "{}", output);
+        assert!(output.contains("<animate attributeName=\"href\"></animate>"));
+    }
+
+    #[test]
+    fn removes_script_styles_and_refresh() {
+        let output = sanitize_html(
+            "<head><meta http-equiv=\"refresh\" content=\"0; url=javascript:alert(1)\">\
+             <meta http-equiv=\"refresh\" content=\"5; url=/next\"></head>\
+             <body><div style=\"width: expression(alert(1))\">1</div>\
+             <div style=\"background: url('java\\script:alert(2)')\">2</div>\
+             <div style=\"color: red\">3</div></body>",
+            &SanitizeProfile::strict(),
+        );
+
+        assert_eq!(
+            output,
+            "<html><head><meta http-equiv=\"refresh\" content=\"5; url=/next\"></head>\
+             <body><div>1</div><div>2</div><div style=\"color: red\">3</div></body></html>"
+        );
+    }
+
+    #[test]
+    fn removes_html_frames() {
+        let output = sanitize_html(
+            "<body>\
+             <iframe src=\"data:text/html;base64,PHNjcmlwdD5hbGVydCgxKTwvc2NyaXB0Pg==\"></iframe>\
+             <iframe src=\" DATA:image/svg+xml,<svg onload=alert(2)>\"></iframe>\
+             <iframe srcdoc=\"<script>alert(3)</script>\"></iframe>\
+             <iframe src=\"data:image/png;base64,AAAA\"></iframe>\
+             </body>",
+            &SanitizeProfile::strict(),
+        );
+
+        assert_eq!(
+            output,
+            "<html><head></head><body>\
+             <iframe></iframe><iframe></iframe><iframe></iframe>\
+             <iframe src=\"data:image/png;base64,AAAA\"></iframe>\
+             </body></html>"
+        );
+    }
+
+    #[test]
+    fn removes_external_form_actions() {
+        let output = sanitize_html(
+            "<body>\
+             <form action=\"https://evil.example.net/collect\"><input name=\"q\"></form>\
+             <form action=\"//evil.example.net/collect\"></form>\
+             <form action=\"/search\"><button formaction=\"http://example.com/search\">Go</button></form>\
+             <form action=\"search\"></form>\
+             </body>",
+            &SanitizeProfile::strict(),
+        );
+
+        assert_eq!(
+            output,
+            "<html><head></head><body>\
+             <form><input name=\"q\"></form>\
+             <form></form>\
+             <form action=\"/search\"><button>Go</button></form>\
+             <form action=\"search\"></form>\
+             </body></html>"
+        );
+    }
+
+    #[test]
+    fn removes_plugins() {
+        let output = sanitize_html(
+            "<body><object data=\"movie.swf\"><embed src=\"movie.swf\"></object>\
+             <applet code=\"Applet.class\"></applet><p>Text</p></body>",
+            &SanitizeProfile::strict(),
+        );
+
+        assert_eq!(output, "<html><head></head><body><p>Text</p></body></html>");
+    }
+
+    #[test]
+    fn sanitizes_template_contents() {
+        let dom = html::html_to_dom(
+            &"<body><template><img src=\"x.png\" onerror=\"alert(1)\"><script>alert(2)</script></template></body>"
+                .as_bytes()
+                .to_vec(),
+            "".to_string(),
+        );
+        let url: Url = Url::parse("https://example.com/").unwrap();
+        html::sanitize(&dom.document, &url, &SanitizeProfile::strict());
+        html::inline_template_contents(&dom.document);
+
+        assert_eq!(
+            serialize_dom(&dom),
+            "<html><head></head><body><template><img src=\"x.png\"></template></body></html>"
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::html::SanitizeProfile;
+
+    use super::passing::sanitize_html;
+
+    #[test]
+    fn keeps_safe_urls() {
+        let html = "<html><head></head><body>\
+                    <a href=\"https://example.com/javascript:guide\">Guide</a>\
+                    <a href=\"/docs/vbscript.html\">Docs</a>\
+                    <img src=\"data:image/png;base64,AAAA\" alt=\"on\">\
+                    </body></html>";
+
+        assert_eq!(sanitize_html(html, &SanitizeProfile::strict()), html);
+    }
+
+    #[test]
+    fn disabled_rules() {
+        let profile = SanitizeProfile {
+            remove_scripts: false,
+            remove_event_handlers: false,
+            ..SanitizeProfile::strict()
+        };
+        let html = "<html><head></head><body>\
+                    <script>alert(1)</script><img src=\"x.png\" onerror=\"alert(2)\">\
+                    </body></html>";
+
+        assert_eq!(sanitize_html(html, &profile), html);
+    }
+}
@@ -138,4 +138,23 @@ mod passing {
                 </html>"
         );
     }
+
+    #[test]
+    fn keeps_template_contents() {
+        let html = "<div><template><p>Inside</p><template><b>Nested</b></template></template></div>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let options = MonolithOptions::default();
+
+        assert_eq!(
+            String::from_utf8_lossy(&html::serialize_document(dom, "".to_string(), &options)),
+            "<html>\
+                <head></head>\
+                <body>\
+                    <div>\
+                        <template><p>Inside</p><template><b>Nested</b></template></template>\
+                    </div>\
+                </body>\
+            </html>"
+        );
+    }
 }
@@ -0,0 +1,158 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::io::{self, Write};
+
+    use monolith::core::MonolithOptions;
+    use monolith::html;
+
+    /// Records every chunk handed to the writer
+    #[derive(Default)]
+    struct ChunkRecorder {
+        chunks: Vec<Vec<u8>>,
+    }
+
+    impl Write for ChunkRecorder {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.chunks.push(buf.to_vec());
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn compare_with_serialize_document(html: &str, encoding: &str, options: &MonolithOptions) {
+        let buffered = html::serialize_document(
+            html::html_to_dom(&html.as_bytes().to_vec(), "".to_string()),
+            encoding.to_string(),
+            options,
+        );
+
+        let mut recorder = ChunkRecorder::default();
+        html::serialize_document_to_writer(
+            html::html_to_dom(&html.as_bytes().to_vec(), "".to_string()),
+            encoding.to_string(),
+            options,
+            &mut recorder,
+        )
+        .unwrap();
+
+        assert_eq!(recorder.chunks.concat(), buffered);
+    }
+
+    #[test]
+    fn streams_document_in_chunks() {
+        let html = "<title>Streamed</title><p>One</p><p>Two</p>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let mut recorder = ChunkRecorder::default();
+
+        html::serialize_document_to_writer(
+            dom,
+            "".to_string(),
+            &MonolithOptions::default(),
+            &mut recorder,
+        )
+        .unwrap();
+
+        assert!(recorder.chunks.len() > 1);
+        assert_eq!(
+            String::from_utf8(recorder.chunks.concat()).unwrap(),
+            "<html><head><title>Streamed</title></head><body><p>One</p><p>Two</p></body></html>"
+        );
+    }
+
+    #[test]
+    fn same_output_as_serialize_document() {
+        let html = "<title>Document</title><noscript><p>No JS</p></noscript><div>  text  </div>";
+        let mut options = MonolithOptions::default();
+        compare_with_serialize_document(html, "", &options);
+
+        options.no_js = true;
+        options.no_css = true;
+        compare_with_serialize_document(html, "", &options);
+
+        options.minify = true;
+        options.silent = true;
+        compare_with_serialize_document(html, "", &options);
+
+        options.minify = false;
+        options.unwrap_noscript = true;
+        compare_with_serialize_document(html, "", &options);
+    }
+
+    #[test]
+    fn converts_encoding_while_streaming() {
+        let html = "<p>café 日本</p>";
+        compare_with_serialize_document(html, "iso-8859-1", &MonolithOptions::default());
+        compare_with_serialize_document(html, "Shift_JIS", &MonolithOptions::default());
+
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let mut buf: Vec<u8> = Vec::new();
+        html::serialize_document_to_writer(
+            dom,
+            "iso-8859-1".to_string(),
+            &MonolithOptions::default(),
+            &mut buf,
+        )
+        .unwrap();
+
+        assert_eq!(
+            buf,
+            b"<html><head></head><body><p>caf\xe9 &#26085;&#26412;</p></body></html>"
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use std::io::{self, Write};
+
+    use monolith::core::MonolithOptions;
+    use monolith::html;
+
+    struct BrokenWriter;
+
+    impl Write for BrokenWriter {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "connection closed",
+            ))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn reports_write_errors() {
+        let options = MonolithOptions::default();
+        for encoding in ["", "iso-8859-1"] {
+            let dom = html::html_to_dom(&b"<p>text</p>".to_vec(), "".to_string());
+            let result = html::serialize_document_to_writer(
+                dom,
+                encoding.to_string(),
+                &options,
+                &mut BrokenWriter,
+            );
+
+            assert_eq!(result.unwrap_err().kind(), io::ErrorKind::BrokenPipe);
+        }
+    }
+}
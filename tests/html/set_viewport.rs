@@ -0,0 +1,112 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::core::{create_monolithic_document_from_data, MonolithOptions};
+    use monolith::html;
+    use monolith::session::Session;
+
+    fn archive(html: &str, options: MonolithOptions) -> String {
+        let session = Session::new(None, None, options);
+        let (data, _) = create_monolithic_document_from_data(
+            session,
+            html.as_bytes().to_vec(),
+            None,
+            Some("https://example.com/".to_string()),
+        )
+        .unwrap();
+        String::from_utf8(data).unwrap()
+    }
+
+    fn force_viewport() -> MonolithOptions {
+        MonolithOptions {
+            force_viewport: true,
+            no_metadata: true,
+            silent: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn injects_missing_viewport() {
+        let output = archive(
+            "<html><head><title>Page</title></head><body></body></html>",
+            force_viewport(),
+        );
+
+        assert!(output
+            .contains("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">"));
+    }
+
+    #[test]
+    fn overrides_existing_viewport() {
+        let output = archive(
+            "<html><head><meta name=\"Viewport\" content=\"width=1024\"></head><body></body></html>",
+            force_viewport(),
+        );
+
+        assert!(output
+            .contains("<meta name=\"Viewport\" content=\"width=device-width, initial-scale=1\">"));
+        assert!(!output.contains("width=1024"));
+    }
+
+    #[test]
+    fn keeps_viewport_by_default() {
+        let output = archive(
+            "<html><head><meta name=\"viewport\" content=\"width=1024\"></head><body></body></html>",
+            MonolithOptions {
+                no_metadata: true,
+                silent: true,
+                ..Default::default()
+            },
+        );
+
+        assert!(output.contains("content=\"width=1024\""));
+        assert!(!output.contains("initial-scale"));
+    }
+
+    #[test]
+    fn adds_max_width_stylesheet() {
+        let output = archive(
+            "<html><head></head><body><p>Text</p></body></html>",
+            MonolithOptions {
+                max_content_width: Some("48rem".to_string()),
+                ..force_viewport()
+            },
+        );
+
+        assert!(output.contains(
+            "<style>body { max-width: 48rem; margin-left: auto; margin-right: auto; }</style>"
+        ));
+    }
+
+    #[test]
+    fn ignores_invalid_max_width() {
+        let output = archive(
+            "<html><head></head><body><p>Text</p></body></html>",
+            MonolithOptions {
+                max_content_width: Some("1px}</style><script>alert(1)</script>".to_string()),
+                ..force_viewport()
+            },
+        );
+
+        assert!(!output.contains("max-width"));
+        assert!(!output.contains("<script>"));
+    }
+
+    #[test]
+    fn set_viewport_adds_meta_to_head() {
+        let dom = html::html_to_dom(b"<p>text</p>", "".to_string());
+        let dom = html::set_viewport(dom, html::DEFAULT_VIEWPORT);
+        let output = html::serialize_document(dom, "".to_string(), &MonolithOptions::default());
+
+        assert!(String::from_utf8_lossy(&output).contains(
+            "<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\"></meta></head>"
+        ));
+    }
+}
@@ -9,10 +9,14 @@
 mod passing {
     use html5ever::serialize::{serialize, SerializeOpts};
     use markup5ever_rcdom::SerializableHandle;
+    use std::fs;
+    use std::path::Path;
+    use std::sync::Arc;
     use url::Url;
 
-    use monolith::core::MonolithOptions;
+    use monolith::core::{JsonScriptPolicy, MonolithOptions};
     use monolith::html;
+    use monolith::media::Viewport;
     use monolith::session::Session;
     use monolith::url::EMPTY_IMAGE_DATA_URL;
 
@@ -446,6 +450,40 @@ mod passing {
         );
     }
 
+    #[test]
+    fn discards_fetch_attributes_for_inlined_stylesheets() {
+        let html = "\
+            <link rel=\"stylesheet\" href=\"data:text/css,p{color:red}\" crossorigin=\"anonymous\" \
+                referrerpolicy=\"no-referrer\" integrity=\"sha256-p0bF+un5yUb9MBO6xRb8kPHlY2BdpHVtLiFkDrZPF64=\">\
+            <script src=\"data:text/javascript,void(0)\" crossorigin=\"use-credentials\"></script>\
+        ";
+        let url: Url = Url::parse("http://localhost").unwrap();
+        let walked = |keep_fetch_attributes: bool| {
+            let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+            let mut options = MonolithOptions::default();
+            options.silent = true;
+            options.keep_fetch_attributes = keep_fetch_attributes;
+            let mut session: Session = Session::new(None, None, options.clone());
+            html::walk(&mut session, &url, &dom.document);
+            String::from_utf8_lossy(&html::serialize_document(dom, "".to_string(), &options))
+                .to_string()
+        };
+
+        let output = walked(false);
+        assert!(output.contains("href=\"data:text/css;base64,"));
+        assert!(output.contains("<script>void(0)</script>"));
+        assert!(!output.contains("crossorigin"));
+        assert!(!output.contains("referrerpolicy"));
+        assert!(!output.contains("integrity"));
+
+        // Opting out keeps the request attributes, but never the stale integrity hash
+        let output = walked(true);
+        assert!(output.contains("crossorigin=\"anonymous\""));
+        assert!(output.contains("crossorigin=\"use-credentials\""));
+        assert!(output.contains("referrerpolicy=\"no-referrer\""));
+        assert!(!output.contains("integrity"));
+    }
+
     #[test]
     fn removes_unwanted_meta_tags() {
         let html = "\
@@ -574,4 +612,596 @@ mod passing {
             </html>"
         );
     }
+
+    #[test]
+    fn embeds_assets_inside_template() {
+        let path_svg = Path::new("tests/_data_/svg/image.svg");
+        let file_url_svg = Url::from_file_path(fs::canonicalize(path_svg).unwrap()).unwrap();
+        let html = format!("<template><img src=\"{}\"></template>", file_url_svg);
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::from_file_path(fs::canonicalize("tests/_data_/svg").unwrap()).unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options.clone());
+
+        html::walk(&mut session, &url, &dom.document);
+
+        let output = String::from_utf8_lossy(&html::serialize_document(
+            dom,
+            "".to_string(),
+            &options,
+        ))
+        .to_string();
+
+        assert!(output.contains("<template><img src=\"data:image/svg+xml;base64,"));
+        assert!(!output.contains(file_url_svg.as_str()));
+    }
+
+    #[test]
+    fn strips_blocked_hosts() {
+        let html = "            <script async src=\"https://www.google-analytics.com/analytics.js\"></script>\
+            <style>body { background: url(https://stats.tracker.example/p.gif); }</style>\
+            <img src=\"https://ssl.google-analytics.com/collect?v=1\" srcset=\"https://ssl.google-analytics.com/2x.gif 2x\">\
+            <iframe src=\"https://pixel.tracker.example/frame.html\"></iframe>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("http://localhost").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.block_hosts = vec![
+            "google-analytics.com".to_string(),
+            "*.tracker.example".to_string(),
+        ];
+
+        let mut session: Session = Session::new(None, None, options.clone());
+
+        html::walk(&mut session, &url, &dom.document);
+
+        let output = String::from_utf8_lossy(&html::serialize_document(
+            dom,
+            "".to_string(),
+            &options,
+        ))
+        .to_string();
+
+        assert!(!output.contains("google-analytics.com"));
+        assert!(!output.contains("tracker.example"));
+        assert!(output.contains("<script async=\"\"></script>"));
+        assert!(output.contains("<iframe></iframe>"));
+    }
+
+    #[test]
+    fn removes_json_scripts() {
+        let html = "\
+            <script id=\"__NEXT_DATA__\" type=\"application/json\">{\"props\":{\"user\":\"jane\"}}</script>\
+            <script type=\"application/ld+json\">{\"@type\":\"Article\"}</script>\
+            <script>var app = 1;</script>\
+        ";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("http://localhost").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.json_script_policy = JsonScriptPolicy::Remove;
+
+        let mut session: Session = Session::new(None, None, options);
+
+        html::walk(&mut session, &url, &dom.document);
+
+        let mut buf: Vec<u8> = Vec::new();
+        serialize(
+            &mut buf,
+            &SerializableHandle::from(dom.document.clone()),
+            SerializeOpts::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            buf.iter().map(|&c| c as char).collect::<String>(),
+            "\
+            <html>\
+                <head>\
+                    <script type=\"application/ld+json\">{\"@type\":\"Article\"}</script>\
+                    <script>var app = 1;</script>\
+                </head>\
+                <body>\
+                </body>\
+            </html>"
+        );
+    }
+
+    #[test]
+    fn redacts_json_scripts() {
+        let html = "\
+            <script id=\"state\" type=\"Application/JSON; charset=utf-8\">{\"email\": \"jane@example.com\", \"id\": 7}</script>\
+            <script>var app = \"kept\";</script>\
+        ";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("http://localhost").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.json_script_policy = JsonScriptPolicy::Redact(Arc::new(html::redact_json_strings));
+
+        let mut session: Session = Session::new(None, None, options);
+
+        html::walk(&mut session, &url, &dom.document);
+
+        let mut buf: Vec<u8> = Vec::new();
+        serialize(
+            &mut buf,
+            &SerializableHandle::from(dom.document.clone()),
+            SerializeOpts::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            buf.iter().map(|&c| c as char).collect::<String>(),
+            "\
+            <html>\
+                <head>\
+                    <script id=\"state\" type=\"Application/JSON; charset=utf-8\">{\"email\": \"\", \"id\": 7}</script>\
+                    <script>var app = \"kept\";</script>\
+                </head>\
+                <body>\
+                </body>\
+            </html>"
+        );
+    }
+
+    #[test]
+    fn redacts_json_scripts_with_custom_callback() {
+        let html = "<script id=\"__NEXT_DATA__\">{\"token\":\"abc\"}</script>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("http://localhost").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.json_script_policy = JsonScriptPolicy::Redact(Arc::new(|_| "{}".to_string()));
+
+        let mut session: Session = Session::new(None, None, options.clone());
+
+        html::walk(&mut session, &url, &dom.document);
+
+        let output = String::from_utf8_lossy(&html::serialize_document(
+            dom,
+            "".to_string(),
+            &options,
+        ))
+        .to_string();
+
+        assert!(output.contains("<script id=\"__NEXT_DATA__\">{}</script>"));
+        assert!(!output.contains("abc"));
+    }
+
+    #[test]
+    fn drops_picture_sources_outside_target_viewport() {
+        let html = "\
+            <picture>\
+                <source media=\"(max-width: 600px)\" srcset=\"data:image/png;base64,c21hbGw=\">\
+                <source media=\"(min-width: 601px)\" srcset=\"data:image/png;base64,bGFyZ2U=\">\
+                <img src=\"data:image/png;base64,ZmFsbGJhY2s=\">\
+            </picture>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("http://localhost").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.target_viewport = Some(Viewport {
+            width: 1280,
+            height: 800,
+        });
+
+        let mut session: Session = Session::new(None, None, options.clone());
+
+        html::walk(&mut session, &url, &dom.document);
+
+        let output = String::from_utf8_lossy(&html::serialize_document(
+            dom,
+            "".to_string(),
+            &options,
+        ))
+        .to_string();
+
+        assert!(!output.contains("max-width"));
+        assert!(output.contains(
+            "<source media=\"(min-width: 601px)\" srcset=\"data:image/png;base64,bGFyZ2U=\">"
+        ));
+        assert!(output.contains("<img src=\"data:image/png;base64,ZmFsbGJhY2s=\">"));
+    }
+
+    #[test]
+    fn keeps_picture_sources_without_target_viewport() {
+        let html = "<picture><source media=\"print\" srcset=\"data:image/png;base64,cHJpbnQ=\"><img></picture>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("http://localhost").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options.clone());
+
+        html::walk(&mut session, &url, &dom.document);
+
+        let output = String::from_utf8_lossy(&html::serialize_document(
+            dom,
+            "".to_string(),
+            &options,
+        ))
+        .to_string();
+
+        assert!(output.contains("<source media=\"print\""));
+    }
+
+    #[test]
+    fn keeps_preferred_picture_source_format() {
+        let html = "\
+            <picture>\
+                <source type=\"image/jpeg\" srcset=\"data:image/jpeg;base64,anBlZw==\">\
+                <source type=\"image/webp\" srcset=\"data:image/webp;base64,d2VicA==\">\
+                <source type=\"image/avif\" srcset=\"data:image/avif;base64,YXZpZg==\">\
+                <img src=\"data:image/png;base64,ZmFsbGJhY2s=\">\
+            </picture>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("http://localhost").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.preferred_image_formats = vec!["image/avif".to_string(), "image/webp".to_string()];
+
+        let mut session: Session = Session::new(None, None, options.clone());
+
+        html::walk(&mut session, &url, &dom.document);
+
+        let output = String::from_utf8_lossy(&html::serialize_document(
+            dom,
+            "".to_string(),
+            &options,
+        ))
+        .to_string();
+
+        assert_eq!(output.matches("<source").count(), 1);
+        assert!(output
+            .contains("<source type=\"image/avif\" srcset=\"data:image/avif;base64,YXZpZg==\">"));
+        assert!(output.contains("<img src=\"data:image/png;base64,ZmFsbGJhY2s=\">"));
+    }
+
+    #[test]
+    fn keeps_picture_sources_per_media_query() {
+        let html = "\
+            <picture>\
+                <source media=\"(max-width: 600px)\" type=\"image/webp\" srcset=\"data:image/webp;base64,c21hbGw=\">\
+                <source media=\"(max-width: 600px)\" type=\"image/jpeg\" srcset=\"data:image/jpeg;base64,c21hbGw=\">\
+                <source type=\"image/jpeg\" srcset=\"data:image/jpeg;base64,bGFyZ2U=\">\
+                <source srcset=\"data:image/png;base64,dW50eXBlZA==\">\
+                <img>\
+            </picture>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("http://localhost").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.preferred_image_formats = vec!["image/webp".to_string()];
+
+        let mut session: Session = Session::new(None, None, options.clone());
+
+        html::walk(&mut session, &url, &dom.document);
+
+        let output = String::from_utf8_lossy(&html::serialize_document(
+            dom,
+            "".to_string(),
+            &options,
+        ))
+        .to_string();
+
+        // Each media query is negotiated on its own; unlisted and untyped sources are kept
+        assert!(output.contains("data:image/webp;base64,c21hbGw="));
+        assert!(!output.contains("data:image/jpeg;base64,c21hbGw="));
+        assert!(output.contains("data:image/jpeg;base64,bGFyZ2U="));
+        assert!(output.contains("data:image/png;base64,dW50eXBlZA=="));
+    }
+
+    #[test]
+    fn strips_hyperlink_auditing() {
+        let html =
+            "<a href=\"/docs\" ping=\"https://tracker.example/ping\" rel=\"external\">Docs</a>\
+                    <map><area href=\"https://example.com/\" ping=\"/beacon\"></map>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("http://localhost").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.strip_hyperlink_auditing = true;
+
+        let mut session: Session = Session::new(None, None, options.clone());
+
+        html::walk(&mut session, &url, &dom.document);
+
+        let output = String::from_utf8_lossy(&html::serialize_document(
+            dom,
+            "".to_string(),
+            &options,
+        ))
+        .to_string();
+
+        assert!(!output.contains("ping"));
+        assert!(output.contains(
+            "<a href=\"http://localhost/docs\" rel=\"external noopener noreferrer\" referrerpolicy=\"no-referrer\">Docs</a>"
+        ));
+        assert!(output.contains(
+            "<area href=\"https://example.com/\" referrerpolicy=\"no-referrer\" rel=\"noopener noreferrer\">"
+        ));
+    }
+
+    #[test]
+    fn keeps_ping_without_stripping_hyperlink_auditing() {
+        let html = "<a href=\"https://example.com/\" ping=\"/beacon\">Link</a>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("http://localhost").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options.clone());
+
+        html::walk(&mut session, &url, &dom.document);
+
+        let output = String::from_utf8_lossy(&html::serialize_document(
+            dom,
+            "".to_string(),
+            &options,
+        ))
+        .to_string();
+
+        assert!(output.contains("<a href=\"https://example.com/\" ping=\"/beacon\">Link</a>"));
+    }
+
+    #[test]
+    fn strips_inline_event_handlers_but_keeps_scripts() {
+        let html = "<body onload=\"init()\"><script>function init() {}</script>\
+                    <button onclick=\"alert(1)\" class=\"primary\">Click</button></body>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("http://localhost").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.strip_inline_event_handlers = true;
+
+        let mut session: Session = Session::new(None, None, options.clone());
+
+        html::walk(&mut session, &url, &dom.document);
+
+        let output = String::from_utf8_lossy(&html::serialize_document(
+            dom,
+            "".to_string(),
+            &options,
+        ))
+        .to_string();
+
+        assert!(!output.contains("onload"));
+        assert!(!output.contains("onclick"));
+        assert!(output.contains("<script>function init() {}</script>"));
+        assert!(output.contains("<button class=\"primary\">Click</button>"));
+    }
+
+    #[test]
+    fn strips_listed_attributes() {
+        let html = "<body><div data-analytics-id=\"1\" data-analytics-event=\"view\" data-id=\"2\">\
+                    <a href=\"#top\" data-analytics-link=\"top\" nonce=\"abc\">Top</a></div></body>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("http://localhost").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.strip_attributes = vec!["data-analytics-*".to_string(), "NONCE".to_string()];
+
+        let mut session: Session = Session::new(None, None, options.clone());
+
+        html::walk(&mut session, &url, &dom.document);
+
+        let output = String::from_utf8_lossy(&html::serialize_document(
+            dom,
+            "".to_string(),
+            &options,
+        ))
+        .to_string();
+
+        assert!(!output.contains("data-analytics-"));
+        assert!(!output.contains("nonce"));
+        assert!(output.contains("<div data-id=\"2\"><a href=\"#top\">Top</a></div>"));
+    }
+
+    #[test]
+    fn strip_attributes_glob_keeps_src_and_href() {
+        let html = "<body><a href=\"#top\" hreflang=\"en\">Top</a></body>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("http://localhost").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.strip_attributes = vec!["h*".to_string()];
+
+        let mut session: Session = Session::new(None, None, options.clone());
+
+        html::walk(&mut session, &url, &dom.document);
+
+        let output = String::from_utf8_lossy(&html::serialize_document(
+            dom,
+            "".to_string(),
+            &options,
+        ))
+        .to_string();
+
+        assert!(output.contains("<a href=\"#top\">Top</a>"));
+    }
+
+    #[test]
+    fn eager_load_images() {
+        let html = "<body>\
+                    <img src=\"photo.png\" loading=\"lazy\" decoding=\"async\" fetchpriority=\"low\">\
+                    <img src=\"logo.png\">\
+                    </body>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("http://localhost").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.no_images = true;
+        options.silent = true;
+        options.eager_load_images = true;
+
+        let mut session: Session = Session::new(None, None, options.clone());
+
+        html::walk(&mut session, &url, &dom.document);
+
+        let output = String::from_utf8_lossy(&html::serialize_document(
+            dom,
+            "".to_string(),
+            &options,
+        ))
+        .to_string();
+
+        assert!(output.contains("loading=\"eager\""));
+        assert!(!output.contains("lazy"));
+        assert!(!output.contains("decoding"));
+        assert!(!output.contains("fetchpriority"));
+        assert_eq!(output.matches("loading=").count(), 1);
+    }
+
+    #[test]
+    fn captures_preloaded_font() {
+        let html = fs::read_to_string("tests/_data_/preload/index.html").unwrap();
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url =
+            Url::from_file_path(fs::canonicalize("tests/_data_/preload/index.html").unwrap())
+                .unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.capture_preloads = true;
+
+        let mut session: Session = Session::new(None, None, options.clone());
+
+        html::walk(&mut session, &url, &dom.document);
+
+        let output = String::from_utf8_lossy(&html::serialize_document(
+            dom,
+            "".to_string(),
+            &options,
+        ))
+        .to_string();
+
+        assert!(!output.contains("rel=\"preload\""));
+        assert!(output.contains("<link href=\"data:"));
+        assert!(output.contains(";base64,d09GMgABAAAAAAAwAAo=\""));
+        assert!(output.contains("as=\"font\""));
+    }
+
+    #[test]
+    fn embeds_canvas_fallback_image() {
+        let html = fs::read_to_string("tests/_data_/canvas/index.html").unwrap();
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url =
+            Url::from_file_path(fs::canonicalize("tests/_data_/canvas/index.html").unwrap())
+                .unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options.clone());
+
+        html::walk(&mut session, &url, &dom.document);
+
+        let output = String::from_utf8_lossy(&html::serialize_document(
+            dom,
+            "".to_string(),
+            &options,
+        ))
+        .to_string();
+
+        assert!(output.contains(
+            "<img src=\"data:image/svg+xml;base64,PHN2ZyB4bWxucz0iaHR0cDovL3d3dy53My5vcmcvMjAwMC9zdmciIHdpZHRoPSI0IiBoZWlnaHQ9IjMiPjxyZWN0IHdpZHRoPSI0IiBoZWlnaHQ9IjMiLz48L3N2Zz4K\" alt=\"Visitors per month\">"
+        ));
+        assert!(output.contains("<canvas id=\"chart\" width=\"400\" height=\"300\">"));
+    }
+
+    #[test]
+    fn drops_preload_hint_without_capturing() {
+        let html = fs::read_to_string("tests/_data_/preload/index.html").unwrap();
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url =
+            Url::from_file_path(fs::canonicalize("tests/_data_/preload/index.html").unwrap())
+                .unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options.clone());
+
+        html::walk(&mut session, &url, &dom.document);
+
+        let output = String::from_utf8_lossy(&html::serialize_document(
+            dom,
+            "".to_string(),
+            &options,
+        ))
+        .to_string();
+
+        assert!(!output.contains("rel=\"preload\""));
+        assert!(output.contains("href=\"font.woff2\""));
+    }
+
+    #[test]
+    fn inlines_external_svg_use_symbol() {
+        let html = "<svg><use href=\"icons.svg#icon-2\"></use><use href=\"#local\"></use></svg>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url =
+            Url::from_file_path(fs::canonicalize("tests/_data_/svg/svg.html").unwrap()).unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options.clone());
+
+        html::walk(&mut session, &url, &dom.document);
+
+        let output = String::from_utf8_lossy(&html::serialize_document(
+            dom,
+            "".to_string(),
+            &options,
+        ))
+        .to_string();
+
+        assert!(output.contains("<use href=\"#icon-2\"><symbol id=\"icon-2\">"));
+        assert!(output.contains("<path fill-rule=\"evenodd\""));
+        assert!(output.contains("<use href=\"#local\"></use>"));
+    }
+
+    #[test]
+    fn embeds_svg_image_xlink_href() {
+        let html = "<svg><image xlink:href=\"image.svg\"></image>\
+                    <image xlink:href=\"missing.svg\" href=\"image.svg\"></image></svg>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url =
+            Url::from_file_path(fs::canonicalize("tests/_data_/svg/image.html").unwrap()).unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options.clone());
+
+        html::walk(&mut session, &url, &dom.document);
+
+        let output = String::from_utf8_lossy(&html::serialize_document(
+            dom,
+            "".to_string(),
+            &options,
+        ))
+        .to_string();
+
+        assert!(output.contains("<image xlink:href=\"data:image/svg+xml;base64,"));
+        assert!(!output.contains("image.svg"));
+        assert!(!output.contains("missing.svg"));
+    }
 }
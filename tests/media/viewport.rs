@@ -0,0 +1,92 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::media::Viewport;
+
+    const DESKTOP: Viewport = Viewport {
+        width: 1280,
+        height: 800,
+    };
+
+    #[test]
+    fn parse() {
+        assert_eq!(Viewport::parse("1280x800"), Some(DESKTOP));
+        assert_eq!(Viewport::parse(" 1280X800 "), Some(DESKTOP));
+    }
+
+    #[test]
+    fn empty_query_matches() {
+        assert!(DESKTOP.matches_media(""));
+        assert!(DESKTOP.matches_media("all"));
+        assert!(DESKTOP.matches_media("only screen"));
+    }
+
+    #[test]
+    fn min_and_max_width() {
+        assert!(DESKTOP.matches_media("(min-width: 1024px)"));
+        assert!(DESKTOP.matches_media("screen and (max-width: 80em)"));
+        assert!(!DESKTOP.matches_media("(max-width: 600px)"));
+        assert!(!DESKTOP.matches_media("screen and (min-width: 601px) and (max-width: 900px)"));
+    }
+
+    #[test]
+    fn range_syntax() {
+        assert!(DESKTOP.matches_media("(width >= 1280px)"));
+        assert!(DESKTOP.matches_media("(600px < width <= 1440px)"));
+        assert!(!DESKTOP.matches_media("(width < 600px)"));
+    }
+
+    #[test]
+    fn orientation() {
+        assert!(DESKTOP.matches_media("(orientation: landscape)"));
+        assert!(!DESKTOP.matches_media("(orientation: portrait)"));
+    }
+
+    #[test]
+    fn query_lists_and_negation() {
+        assert!(DESKTOP.matches_media("(max-width: 600px), (min-width: 1200px)"));
+        assert!(DESKTOP.matches_media("not print"));
+        assert!(!DESKTOP.matches_media("not all and (min-width: 1000px)"));
+    }
+
+    #[test]
+    fn unknown_features_match() {
+        assert!(DESKTOP.matches_media("(prefers-color-scheme: dark)"));
+        assert!(DESKTOP.matches_media("(min-resolution: 2dppx)"));
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::media::Viewport;
+
+    const DESKTOP: Viewport = Viewport {
+        width: 1280,
+        height: 800,
+    };
+
+    #[test]
+    fn parse_invalid() {
+        assert_eq!(Viewport::parse("1280"), None);
+        assert_eq!(Viewport::parse("wide x tall"), None);
+    }
+
+    #[test]
+    fn other_media_types() {
+        assert!(!DESKTOP.matches_media("print"));
+        assert!(!DESKTOP.matches_media("print and (min-width: 100px)"));
+    }
+}
@@ -1,8 +1,13 @@
+mod builders;
 mod cli;
+mod common {
+    pub mod server;
+}
 mod cookies;
 mod core;
 mod css;
 mod html;
 mod js;
+mod media;
 mod session;
 mod url;
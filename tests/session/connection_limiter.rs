@@ -0,0 +1,126 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::collections::HashMap;
+    use std::sync::{Arc, Barrier, Mutex};
+    use std::thread;
+    use std::time::Duration;
+
+    use monolith::core::MonolithOptions;
+    use monolith::limiter::ConnectionLimiter;
+    use monolith::session::Session;
+
+    /// Runs one simulated fetch per host on its own thread and returns
+    ///  the highest number of simultaneous fetches per host and overall
+    pub fn max_concurrency(
+        limiter: Arc<ConnectionLimiter>,
+        hosts: &[&str],
+    ) -> (HashMap<String, usize>, usize) {
+        let barrier = Arc::new(Barrier::new(hosts.len()));
+        let state = Arc::new(Mutex::new((HashMap::new(), HashMap::new(), 0, 0)));
+
+        let handles: Vec<_> = hosts
+            .iter()
+            .map(|host| {
+                let host = host.to_string();
+                let limiter = Arc::clone(&limiter);
+                let barrier = Arc::clone(&barrier);
+                let state = Arc::clone(&state);
+                thread::spawn(move || {
+                    barrier.wait();
+                    let _permit = limiter.acquire(&host);
+                    {
+                        let mut state = state.lock().unwrap();
+                        let (active, max, global, max_global) = &mut *state;
+                        let count = active.entry(host.clone()).or_insert(0);
+                        *count += 1;
+                        let host_max = max.entry(host.clone()).or_insert(0);
+                        *host_max = (*host_max).max(*count);
+                        *global += 1;
+                        *max_global = (*max_global).max(*global);
+                    }
+                    thread::sleep(Duration::from_millis(100));
+                    let mut state = state.lock().unwrap();
+                    *state.0.get_mut(&host).unwrap() -= 1;
+                    state.2 -= 1;
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let state = state.lock().unwrap();
+        (state.1.clone(), state.3)
+    }
+
+    #[test]
+    fn serialize_per_host_but_parallelize_across_hosts() {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.max_concurrent_fetches = 8;
+        options.max_connections_per_host = 1;
+
+        let session: Session = Session::new(None, None, options);
+
+        let (per_host, global) = max_concurrency(
+            session.connection_limiter(),
+            &[
+                "a.example.com",
+                "a.example.com",
+                "b.example.com",
+                "b.example.com",
+            ],
+        );
+
+        assert_eq!(per_host["a.example.com"], 1);
+        assert_eq!(per_host["b.example.com"], 1);
+        assert_eq!(global, 2);
+    }
+
+    #[test]
+    fn unlimited_by_default() {
+        let (per_host, global) = max_concurrency(
+            Arc::new(ConnectionLimiter::default()),
+            &["a.example.com", "a.example.com", "a.example.com"],
+        );
+
+        assert_eq!(per_host["a.example.com"], 3);
+        assert_eq!(global, 3);
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use std::sync::Arc;
+
+    use monolith::limiter::ConnectionLimiter;
+
+    use super::passing::max_concurrency;
+
+    #[test]
+    fn global_limit_serializes_different_hosts() {
+        let (per_host, global) = max_concurrency(
+            Arc::new(ConnectionLimiter::new(1, 0)),
+            &["a.example.com", "b.example.com"],
+        );
+
+        assert_eq!(per_host["a.example.com"], 1);
+        assert_eq!(per_host["b.example.com"], 1);
+        assert_eq!(global, 1);
+    }
+}
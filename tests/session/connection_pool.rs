@@ -0,0 +1,156 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::Url;
+
+    use monolith::cache::Cache;
+    use monolith::core::MonolithOptions;
+    use monolith::session::Session;
+
+    use crate::common::server::{Response, TestServer};
+
+    /// Starts a keep-alive HTTP server that answers every request with a small
+    ///  stylesheet, and returns the server, which counts the accepted connections
+    pub fn start_server() -> TestServer {
+        TestServer::start_keep_alive(|_| Response::ok("text/css", "body{}"))
+    }
+
+    pub fn fetch_assets(options: MonolithOptions, base_url: &Url, count: usize) {
+        let mut session: Session = Session::new(None, None, options);
+        for i in 0..count {
+            let (data, _, media_type, _) = session
+                .retrieve_asset(
+                    base_url,
+                    &base_url.join(&format!("style{}.css", i)).unwrap(),
+                )
+                .unwrap();
+            assert_eq!(data, b"body{}");
+            assert_eq!(media_type, "text/css");
+        }
+    }
+
+    #[test]
+    fn reuses_connections_by_default() {
+        let server = start_server();
+        let base_url = server.url();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.timeout = 10;
+        fetch_assets(options, &base_url, 5);
+
+        assert_eq!(server.connections(), 1);
+    }
+
+    #[test]
+    fn applies_pool_settings() {
+        let server = start_server();
+        let base_url = server.url();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.timeout = 10;
+        options.pool_max_idle_per_host = Some(4);
+        options.pool_idle_timeout = Some(30);
+        options.tcp_keepalive = Some(60);
+        fetch_assets(options, &base_url, 5);
+
+        assert_eq!(server.connections(), 1);
+    }
+
+    #[test]
+    fn sessions_created_with_options_share_connections() {
+        let server = start_server();
+        let base_url = server.url();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.timeout = 10;
+        let base_session = Session::new(None, None, options.clone());
+
+        // Two requests with different options, as a server would handle them
+        for no_images in [false, true] {
+            let mut request_options = options.clone();
+            request_options.no_images = no_images;
+            let mut session = base_session.with_options(request_options);
+            let (data, _, _, _) = session
+                .retrieve_asset(&base_url, &base_url.join("style.css").unwrap())
+                .unwrap();
+            assert_eq!(data, b"body{}");
+        }
+
+        assert_eq!(server.connections(), 1);
+    }
+
+    #[test]
+    fn sessions_created_with_options_share_cache() {
+        let server = start_server();
+        let base_url = server.url();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.timeout = 10;
+        // Every request opens a new connection, so connections count requests
+        options.pool_max_idle_per_host = Some(0);
+        let base_session = Session::new(Some(Cache::new(0, None)), None, options.clone());
+
+        for _ in 0..2 {
+            let mut session = base_session.with_options(options.clone());
+            let (data, _, media_type, _) = session
+                .retrieve_asset(&base_url, &base_url.join("style.css").unwrap())
+                .unwrap();
+            assert_eq!(data, b"body{}");
+            assert_eq!(media_type, "text/css");
+        }
+
+        assert_eq!(server.connections(), 1);
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::core::MonolithOptions;
+
+    use super::passing::{fetch_assets, start_server};
+
+    #[test]
+    fn disabling_idle_pool_opens_new_connections() {
+        let server = start_server();
+        let base_url = server.url();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.timeout = 10;
+        options.pool_max_idle_per_host = Some(0);
+        fetch_assets(options, &base_url, 3);
+
+        assert_eq!(server.connections(), 3);
+    }
+
+    #[test]
+    fn separate_sessions_open_their_own_connections() {
+        let server = start_server();
+        let base_url = server.url();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.timeout = 10;
+        fetch_assets(options.clone(), &base_url, 1);
+        fetch_assets(options, &base_url, 1);
+
+        assert_eq!(server.connections(), 2);
+    }
+}
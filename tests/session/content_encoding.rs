@@ -0,0 +1,100 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    use flate2::write::{GzEncoder, ZlibEncoder};
+    use flate2::Compression;
+    use reqwest::Url;
+
+    use monolith::core::MonolithOptions;
+    use monolith::session::Session;
+
+    use crate::common::server::{Response, TestServer};
+
+    const CSS: &str = "body { background-color: #fafafa; font-family: sans-serif; }";
+
+    fn compress(encoding: &str, data: &[u8]) -> Vec<u8> {
+        match encoding {
+            "gzip" => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(data).unwrap();
+                encoder.finish().unwrap()
+            }
+            "deflate" => {
+                let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(data).unwrap();
+                encoder.finish().unwrap()
+            }
+            "br" => {
+                let mut output = Vec::new();
+                {
+                    let mut encoder = brotli::CompressorWriter::new(&mut output, 4096, 5, 22);
+                    encoder.write_all(data).unwrap();
+                }
+                output
+            }
+            _ => data.to_vec(),
+        }
+    }
+
+    /// Starts an HTTP server that answers `/<encoding>.css` with the stylesheet compressed
+    ///  using that content coding, and returns its base URL along with the Accept-Encoding
+    ///  headers it received
+    pub fn start_server() -> (Url, Arc<Mutex<Vec<String>>>) {
+        let accept_encodings = Arc::new(Mutex::new(Vec::new()));
+
+        let received = Arc::clone(&accept_encodings);
+        let server = TestServer::start(move |request| {
+            if let Some(value) = request.header("accept-encoding") {
+                received.lock().unwrap().push(value.to_string());
+            }
+            let encoding = request
+                .path
+                .trim_start_matches('/')
+                .trim_end_matches(".css");
+            Response::ok("text/css", compress(encoding, CSS.as_bytes()))
+                .header("Content-Encoding", encoding)
+        });
+
+        (server.url(), accept_encodings)
+    }
+
+    #[test]
+    fn decodes_compressed_responses() {
+        let (base_url, accept_encodings) = start_server();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.timeout = 10;
+        let mut session: Session = Session::new(None, None, options);
+
+        for encoding in ["gzip", "deflate", "br"] {
+            let (data, _, media_type, _) = session
+                .retrieve_asset(
+                    &base_url,
+                    &base_url.join(&format!("{}.css", encoding)).unwrap(),
+                )
+                .unwrap();
+            assert_eq!(String::from_utf8_lossy(&data), CSS, "{}", encoding);
+            assert_eq!(media_type, "text/css");
+        }
+
+        let accept_encodings = accept_encodings.lock().unwrap();
+        assert_eq!(accept_encodings.len(), 3);
+        for coding in ["gzip", "deflate", "br"] {
+            assert!(
+                accept_encodings[0].contains(coding),
+                "{}",
+                accept_encodings[0]
+            );
+        }
+    }
+}
@@ -0,0 +1,103 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::Url;
+
+    use monolith::core::MonolithOptions;
+    use monolith::session::Session;
+
+    use crate::common::server::{Response, TestServer};
+
+    /// Starts an HTTP server with a login page issuing a session cookie, a logout page
+    /// deleting it, and a protected page that only answers when the cookie is sent
+    pub fn start_server() -> Url {
+        TestServer::start(|request| {
+            let cookie_header = request.header("cookie").unwrap_or("");
+            match request.path.as_str() {
+                "/login" => Response::ok("text/html", "ok")
+                    .header("Set-Cookie", "sid=s3cr3t; Path=/; HttpOnly"),
+                "/logout" => {
+                    Response::ok("text/html", "ok").header("Set-Cookie", "sid=; Path=/; Max-Age=0")
+                }
+                "/members/page"
+                    if cookie_header
+                        .split("; ")
+                        .any(|cookie| cookie == "sid=s3cr3t") =>
+                {
+                    Response::ok("text/html", "ok")
+                }
+                _ => Response::new("403 Forbidden", "text/html", "ok"),
+            }
+        })
+        .url()
+    }
+
+    pub fn new_session() -> Session {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.timeout = 10;
+        Session::new(None, None, options)
+    }
+
+    #[test]
+    fn sends_cookies_set_by_earlier_responses() {
+        let base_url = start_server();
+        let mut session = new_session();
+
+        session
+            .retrieve_asset(&base_url, &base_url.join("login").unwrap())
+            .unwrap();
+        assert_eq!(session.cookies().len(), 1);
+
+        let (data, _, _, _) = session
+            .retrieve_asset(&base_url, &base_url.join("members/page").unwrap())
+            .unwrap();
+        assert_eq!(data, b"ok");
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use super::passing::{new_session, start_server};
+
+    #[test]
+    fn no_cookie_without_login() {
+        let base_url = start_server();
+        let mut session = new_session();
+
+        assert!(session
+            .retrieve_asset(&base_url, &base_url.join("members/page").unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn deleted_cookie_is_not_sent() {
+        let base_url = start_server();
+        let mut session = new_session();
+
+        session
+            .retrieve_asset(&base_url, &base_url.join("login").unwrap())
+            .unwrap();
+        session
+            .retrieve_asset(&base_url, &base_url.join("logout").unwrap())
+            .unwrap();
+        assert!(session.cookies().is_empty());
+
+        assert!(session
+            .retrieve_asset(&base_url, &base_url.join("members/page").unwrap())
+            .is_err());
+    }
+}
@@ -0,0 +1,94 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use monolith::core::MonolithOptions;
+    use monolith::session::Session;
+
+    use crate::common::server::{Response, TestServer};
+
+    /// Starts an HTTP server that drops the first `drops` connections without
+    /// answering, then responds to every request with the given status line,
+    /// and returns the server, which counts the accepted connections
+    pub fn start_flaky_server(drops: usize, status: &'static str) -> TestServer {
+        let connections = AtomicUsize::new(0);
+        TestServer::start(move |_| {
+            if connections.fetch_add(1, Ordering::SeqCst) < drops {
+                return Response::drop_connection();
+            }
+            Response::new(status, "text/html", "<html></html>")
+        })
+    }
+
+    pub fn options_with_retries(retries: usize) -> MonolithOptions {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.timeout = 10;
+        options.document_fetch_retries = retries;
+        options
+    }
+
+    #[test]
+    fn retries_dropped_connections() {
+        let server = start_flaky_server(2, "200 OK");
+        let url = server.url();
+
+        let mut session: Session = Session::new(None, None, options_with_retries(2));
+        let (data, _, media_type, _) = session.retrieve_document(&url).unwrap();
+
+        assert_eq!(data, b"<html></html>");
+        assert_eq!(media_type, "text/html");
+        assert_eq!(server.connections(), 3);
+    }
+
+    #[test]
+    fn does_not_retry_not_found() {
+        let server = start_flaky_server(0, "404 Not Found");
+        let url = server.url();
+
+        let mut session: Session = Session::new(None, None, options_with_retries(3));
+        assert!(session.retrieve_document(&url).is_err());
+        assert_eq!(server.connections(), 1);
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::session::Session;
+
+    use super::passing::{options_with_retries, start_flaky_server};
+
+    #[test]
+    fn gives_up_after_configured_retries() {
+        let server = start_flaky_server(5, "200 OK");
+        let url = server.url();
+
+        let mut session: Session = Session::new(None, None, options_with_retries(1));
+        assert!(session.retrieve_document(&url).is_err());
+        assert_eq!(server.connections(), 2);
+    }
+
+    #[test]
+    fn no_retries_by_default() {
+        let server = start_flaky_server(1, "200 OK");
+        let url = server.url();
+
+        let mut session: Session = Session::new(None, None, options_with_retries(0));
+        assert!(session.retrieve_document(&url).is_err());
+        assert_eq!(server.connections(), 1);
+    }
+}
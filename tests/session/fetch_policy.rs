@@ -0,0 +1,111 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::net::IpAddr;
+
+    use reqwest::Url;
+
+    use monolith::core::{create_monolithic_document, MonolithOptions};
+    use monolith::fetch_policy::{is_internal_address, FetchPolicy};
+    use monolith::session::Session;
+
+    use crate::common::server::{Response, TestServer};
+
+    /// Starts an HTTP server on the loopback interface that answers every request with
+    ///  a small stylesheet, and returns its port
+    pub fn start_local_server() -> u16 {
+        TestServer::start(|_| Response::ok("text/css", "body{}"))
+            .url()
+            .port()
+            .unwrap()
+    }
+
+    fn session(fetch_policy: Option<FetchPolicy>) -> Session {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.timeout = 5;
+        options.fetch_policy = fetch_policy;
+        Session::new(None, None, options)
+    }
+
+    #[test]
+    fn rejects_metadata_address() {
+        let url = Url::parse("http://169.254.169.254/latest/meta-data/").unwrap();
+        let mut session = session(Some(FetchPolicy::server()));
+        assert!(session.retrieve_asset(&url, &url).is_err());
+
+        let result = create_monolithic_document(
+            self::session(Some(FetchPolicy::server())),
+            "http://169.254.169.254/",
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn rejects_host_names_resolving_to_internal_addresses() {
+        let port = start_local_server();
+        let url = Url::parse(&format!("http://localhost:{}/style.css", port)).unwrap();
+
+        let mut session = session(Some(FetchPolicy::server()));
+        assert!(session.retrieve_asset(&url, &url).is_err());
+
+        let mut session = self::session(None);
+        let (data, ..) = session.retrieve_asset(&url, &url).unwrap();
+        assert_eq!(data, b"body{}");
+
+        // Internal addresses can be allowed while still restricting schemes
+        let mut session = self::session(Some(FetchPolicy {
+            deny_internal_addresses: false,
+            ..FetchPolicy::server()
+        }));
+        assert!(session.retrieve_asset(&url, &url).is_ok());
+    }
+
+    #[test]
+    fn rejects_local_files_and_data_url_targets() {
+        for target in [
+            "Cargo.toml",
+            "file:///etc/passwd",
+            "data:text/html,<p>Hello</p>",
+        ] {
+            let result = create_monolithic_document(session(Some(FetchPolicy::server())), target);
+            assert!(result.is_err(), "{}", target);
+        }
+
+        let result = create_monolithic_document(session(None), "data:text/html,<p>Hello</p>");
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn classifies_internal_addresses() {
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.100.100.200",
+            "0.0.0.0",
+            "::1",
+            "fd00:ec2::254",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "64:ff9b::a9fe:a9fe",
+        ] {
+            assert!(is_internal_address(ip.parse::<IpAddr>().unwrap()), "{}", ip);
+        }
+        for ip in ["93.184.216.34", "8.8.8.8", "2606:4700::1111"] {
+            assert!(
+                !is_internal_address(ip.parse::<IpAddr>().unwrap()),
+                "{}",
+                ip
+            );
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::net::TcpListener;
+    use std::sync::Arc;
+    use std::thread;
+
+    use openssl::asn1::Asn1Time;
+    use openssl::hash::MessageDigest;
+    use openssl::pkey::PKey;
+    use openssl::rsa::Rsa;
+    use openssl::ssl::{SslAcceptor, SslMethod};
+    use openssl::x509::{X509NameBuilder, X509};
+    use reqwest::Url;
+
+    use monolith::core::MonolithOptions;
+    use monolith::session::Session;
+
+    use crate::common::server::{serve_connection, Response};
+
+    /// Starts an HTTPS server with a freshly generated self-signed certificate that answers
+    ///  every request with a small stylesheet, and returns its base URL
+    pub fn start_self_signed_server() -> Url {
+        let key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+        let mut name = X509NameBuilder::new().unwrap();
+        name.append_entry_by_text("CN", "127.0.0.1").unwrap();
+        let name = name.build();
+        let mut certificate = X509::builder().unwrap();
+        certificate.set_version(2).unwrap();
+        certificate.set_subject_name(&name).unwrap();
+        certificate.set_issuer_name(&name).unwrap();
+        certificate.set_pubkey(&key).unwrap();
+        certificate
+            .set_not_before(&Asn1Time::days_from_now(0).unwrap())
+            .unwrap();
+        certificate
+            .set_not_after(&Asn1Time::days_from_now(1).unwrap())
+            .unwrap();
+        certificate.sign(&key, MessageDigest::sha256()).unwrap();
+
+        let mut acceptor = SslAcceptor::mozilla_intermediate(SslMethod::tls()).unwrap();
+        acceptor.set_private_key(&key).unwrap();
+        acceptor.set_certificate(&certificate.build()).unwrap();
+        let acceptor = Arc::new(acceptor.build());
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let stream = match stream {
+                    Ok(stream) => stream,
+                    Err(_) => break,
+                };
+                let acceptor = Arc::clone(&acceptor);
+                thread::spawn(move || {
+                    // Clients that verify the certificate abort the handshake
+                    if let Ok(stream) = acceptor.accept(stream) {
+                        serve_connection(stream, |_| Response::ok("text/css", "body{}"), false);
+                    }
+                });
+            }
+        });
+
+        Url::parse(&format!("https://{}/", address)).unwrap()
+    }
+
+    pub fn options(insecure_hosts: &[&str]) -> MonolithOptions {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.insecure_hosts = insecure_hosts.iter().map(|host| host.to_string()).collect();
+        options
+    }
+
+    pub fn fetch_with(mut session: Session, url: &Url) -> bool {
+        session
+            .retrieve_asset(url, &url.join("style.css").unwrap())
+            .is_ok_and(|(data, _, _, _)| data == b"body{}")
+    }
+
+    pub fn fetch(insecure_hosts: &[&str], url: &Url) -> bool {
+        fetch_with(Session::new(None, None, options(insecure_hosts)), url)
+    }
+
+    #[test]
+    fn listed_host() {
+        let url = start_self_signed_server();
+
+        assert!(fetch(&["127.0.0.1"], &url));
+    }
+
+    #[test]
+    fn wildcard_pattern() {
+        let url = start_self_signed_server();
+
+        assert!(fetch(&["internal.example", "127.0.0.*"], &url));
+    }
+
+    #[test]
+    fn derived_session_keeps_base_hosts() {
+        let url = start_self_signed_server();
+        let base = Session::new(None, None, options(&["127.0.0.1"]));
+
+        assert!(fetch_with(base.with_options(options(&[])), &url));
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::session::Session;
+
+    use super::passing::{fetch, fetch_with, options, start_self_signed_server};
+
+    #[test]
+    fn unlisted_host() {
+        let url = start_self_signed_server();
+
+        assert!(!fetch(&["internal.example"], &url));
+    }
+
+    #[test]
+    fn no_insecure_hosts() {
+        let url = start_self_signed_server();
+
+        assert!(!fetch(&[], &url));
+    }
+
+    #[test]
+    fn derived_session_ignores_new_hosts() {
+        let url = start_self_signed_server();
+        let base = Session::new(None, None, options(&["internal.example"]));
+        let session = base.with_options(options(&["127.0.0.1"]));
+
+        assert!(!fetch_with(session, &url));
+    }
+}
@@ -0,0 +1,87 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::Url;
+
+    use monolith::core::MonolithOptions;
+    use monolith::html;
+    use monolith::session::Session;
+
+    use crate::common::server::{Response, TestServer};
+
+    /// Starts an HTTP server that answers every request with a tiny GIF image
+    pub fn start_server() -> Url {
+        TestServer::start_keep_alive(|_| Response::ok("image/gif", "GIF89a")).url()
+    }
+
+    /// Walks a page with `count` images and returns the output along with the session
+    pub fn walk_images(options: MonolithOptions, count: usize) -> (String, Session, Url) {
+        let base_url = start_server();
+        let images: String = (0..count)
+            .map(|i| format!("<img src=\"image{}.gif\">", i))
+            .collect();
+        let html = format!("<body>{}</body>", images);
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+
+        let mut session: Session = Session::new(None, None, options.clone());
+        html::walk(&mut session, &base_url, &dom.document);
+
+        let output =
+            String::from_utf8_lossy(&html::serialize_document(dom, "".to_string(), &options))
+                .to_string();
+
+        (output, session, base_url)
+    }
+
+    #[test]
+    fn stops_embedding_at_limit() {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.timeout = 10;
+        options.max_embedded_assets = Some(10);
+
+        let (output, session, base_url) = walk_images(options, 100);
+
+        assert_eq!(output.matches("src=\"data:image/gif;base64,").count(), 10);
+        assert_eq!(
+            output
+                .matches(&format!("src=\"{}image", base_url.as_str()))
+                .count(),
+            90
+        );
+        assert!(output.contains(&format!("src=\"{}image99.gif\"", base_url.as_str())));
+        assert_eq!(session.skipped_assets_count(), 90);
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::core::MonolithOptions;
+
+    use super::passing::walk_images;
+
+    #[test]
+    fn no_limit_by_default() {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.timeout = 10;
+
+        let (output, session, _) = walk_images(options, 20);
+
+        assert_eq!(output.matches("src=\"data:image/gif;base64,").count(), 20);
+        assert_eq!(session.skipped_assets_count(), 0);
+    }
+}
@@ -0,0 +1,93 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::Url;
+
+    use monolith::core::MonolithOptions;
+    use monolith::html;
+    use monolith::session::Session;
+
+    use crate::common::server::{Response, TestServer};
+
+    /// Starts an HTTP server that sends every response as `application/octet-stream`
+    pub fn start_server() -> Url {
+        TestServer::start_keep_alive(|_| Response::ok("application/octet-stream", "wOF2")).url()
+    }
+
+    /// Walks a page whose stylesheet loads a web font and returns the output
+    pub fn walk_font_page(options: MonolithOptions) -> String {
+        let base_url = start_server();
+        let html = "<style>@font-face { font-family: F; src: url(fonts/f.woff2?v=3); }</style>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+
+        let mut session: Session = Session::new(None, None, options.clone());
+        html::walk(&mut session, &base_url, &dom.document);
+
+        String::from_utf8_lossy(&html::serialize_document(dom, "".to_string(), &options))
+            .to_string()
+    }
+
+    fn options() -> MonolithOptions {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.timeout = 10;
+        options
+    }
+
+    #[test]
+    fn forces_media_type_of_matching_assets() {
+        let mut options = options();
+        options.media_type_overrides = vec![
+            ("*.svg".to_string(), "image/svg+xml".to_string()),
+            (".woff2".to_string(), "font/woff2".to_string()),
+        ];
+
+        let output = walk_font_page(options);
+
+        assert!(output.contains("url(\"data:font/woff2;base64,d09GMg==\")"));
+    }
+
+    #[test]
+    fn data_urls_keep_their_media_type() {
+        let mut options = options();
+        options.media_type_overrides = vec![("*".to_string(), "font/woff2".to_string())];
+        let url = Url::parse("data:image/gif;base64,R0lGODlh").unwrap();
+        let mut session: Session = Session::new(None, None, options);
+
+        let (_, _, media_type, _) = session.retrieve_asset(&url, &url).unwrap();
+
+        assert_eq!(media_type, "image/gif");
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::core::MonolithOptions;
+
+    use super::passing::walk_font_page;
+
+    #[test]
+    fn served_media_type_is_kept_without_overrides() {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.timeout = 10;
+        options.media_type_overrides = vec![(".woff".to_string(), "font/woff".to_string())];
+
+        let output = walk_font_page(options);
+
+        assert!(output.contains("url(\"data:application/octet-stream;base64,d09GMg==\")"));
+    }
+}
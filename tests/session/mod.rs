@@ -1 +1,12 @@
+mod connection_limiter;
+mod connection_pool;
+mod content_encoding;
+mod cookie_store;
+mod document_retries;
+mod fetch_policy;
+mod insecure_hosts;
+mod max_embedded_assets;
+mod media_type_overrides;
+mod referer_policy;
 mod retrieve_asset;
+mod timeouts;
@@ -0,0 +1,117 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::Url;
+
+    use monolith::core::{MonolithOptions, RefererPolicy};
+    use monolith::session::Session;
+
+    use crate::common::server::{Response, TestServer};
+
+    /// Starts an HTTP server that answers every request with the Referer it received
+    pub fn start_server() -> Url {
+        TestServer::start(|request| {
+            Response::ok("text/plain", request.header("referer").unwrap_or(""))
+        })
+        .url()
+    }
+
+    /// Fetches an asset from the server as referenced by `parent_url`
+    ///  and returns the Referer the server received
+    pub fn referer_sent(policy: RefererPolicy, base_url: &Url, parent_url: &Url) -> String {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.timeout = 10;
+        options.referer_policy = policy;
+        let mut session = Session::new(None, None, options);
+
+        let (data, _, _, _) = session
+            .retrieve_asset(parent_url, &base_url.join("image.png").unwrap())
+            .unwrap();
+        String::from_utf8(data).unwrap()
+    }
+
+    #[test]
+    fn full_url_by_default() {
+        let base_url = start_server();
+        let mut parent_url = base_url.join("articles/page.html?id=1#comments").unwrap();
+        parent_url.set_username("user").unwrap();
+        parent_url.set_password(Some("pass")).unwrap();
+
+        assert_eq!(
+            referer_sent(RefererPolicy::default(), &base_url, &parent_url),
+            base_url.join("articles/page.html?id=1").unwrap().as_str()
+        );
+    }
+
+    #[test]
+    fn origin() {
+        let base_url = start_server();
+        let parent_url = base_url.join("articles/page.html").unwrap();
+
+        assert_eq!(
+            referer_sent(RefererPolicy::Origin, &base_url, &parent_url),
+            base_url.as_str()
+        );
+    }
+
+    #[test]
+    fn fixed() {
+        let base_url = start_server();
+        let parent_url = base_url.join("articles/page.html").unwrap();
+        let referer = Url::parse("https://example.com/gallery/").unwrap();
+
+        assert_eq!(
+            referer_sent(RefererPolicy::Fixed(referer), &base_url, &parent_url),
+            "https://example.com/gallery/"
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use reqwest::Url;
+
+    use monolith::core::RefererPolicy;
+
+    use super::passing::{referer_sent, start_server};
+
+    #[test]
+    fn none() {
+        let base_url = start_server();
+        let parent_url = base_url.join("articles/page.html").unwrap();
+
+        assert_eq!(
+            referer_sent(RefererPolicy::None, &base_url, &parent_url),
+            ""
+        );
+    }
+
+    #[test]
+    fn not_from_non_http_pages() {
+        let base_url = start_server();
+        let parent_url = Url::parse("file:///home/monolith/page.html").unwrap();
+
+        assert_eq!(
+            referer_sent(RefererPolicy::FullUrl, &base_url, &parent_url),
+            ""
+        );
+        assert_eq!(
+            referer_sent(RefererPolicy::Origin, &base_url, &parent_url),
+            ""
+        );
+    }
+}
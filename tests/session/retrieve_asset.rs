@@ -85,6 +85,45 @@ mod passing {
             .unwrap()
         );
     }
+    #[test]
+    fn record_retrieved_resources() {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options);
+
+        let file_url_protocol: &str = if cfg!(windows) { "file:///" } else { "file://" };
+        let cwd = env::current_dir().unwrap();
+        let parent_url = Url::parse(&format!(
+            "{file}{cwd}/tests/_data_/basic/local-file.html",
+            file = file_url_protocol,
+            cwd = cwd.to_str().unwrap()
+        ))
+        .unwrap();
+        let script_url = Url::parse(&format!(
+            "{file}{cwd}/tests/_data_/basic/local-script.js",
+            file = file_url_protocol,
+            cwd = cwd.to_str().unwrap()
+        ))
+        .unwrap();
+
+        // Data URLs are already part of the document and should not be recorded,
+        //  retrieving the same asset twice should only record it once
+        session
+            .retrieve_asset(&parent_url, &Url::parse("data:text/plain,abc").unwrap())
+            .unwrap();
+        let (_, _, media_type, _) = session.retrieve_asset(&parent_url, &script_url).unwrap();
+        session.retrieve_asset(&parent_url, &script_url).unwrap();
+
+        let resources = session.resources();
+        assert_eq!(resources.len(), 1);
+        assert_eq!(resources[0].url, script_url.as_str());
+        assert_eq!(resources[0].media_type, media_type);
+        assert_eq!(resources[0].size, 82);
+
+        session.clear_resources();
+        assert!(session.resources().is_empty());
+    }
 }
 
 //  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
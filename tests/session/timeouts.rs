@@ -0,0 +1,89 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::thread;
+    use std::time::Duration;
+
+    use reqwest::Url;
+
+    use monolith::core::MonolithOptions;
+    use monolith::session::Session;
+
+    use crate::common::server::{Response, TestServer};
+
+    /// Starts an HTTP server that waits for the given delay before answering
+    /// every request with a small HTML document, and returns its base URL
+    pub fn start_slow_server(delay: Duration) -> Url {
+        TestServer::start(move |_| {
+            thread::sleep(delay);
+            Response::ok("text/html", "<html></html>")
+        })
+        .url()
+    }
+
+    #[test]
+    fn document_uses_document_timeout() {
+        let url = start_slow_server(Duration::from_secs(2));
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.timeout = 1;
+        options.document_timeout = Some(10);
+        let mut session: Session = Session::new(None, None, options);
+
+        let (data, _, media_type, _) = session.retrieve_document(&url).unwrap();
+        assert_eq!(data, b"<html></html>");
+        assert_eq!(media_type, "text/html");
+    }
+
+    #[test]
+    fn asset_timeout_falls_back_to_timeout() {
+        let url = start_slow_server(Duration::from_secs(2));
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.timeout = 10;
+        let mut session: Session = Session::new(None, None, options);
+
+        assert!(session.retrieve_asset(&url, &url).is_ok());
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use std::time::{Duration, Instant};
+
+    use monolith::core::MonolithOptions;
+    use monolith::session::Session;
+
+    use super::passing::start_slow_server;
+
+    #[test]
+    fn assets_use_asset_timeout() {
+        let url = start_slow_server(Duration::from_secs(3));
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.timeout = 10;
+        options.document_timeout = Some(10);
+        options.asset_timeout = Some(1);
+        let mut session: Session = Session::new(None, None, options);
+
+        let started = Instant::now();
+        assert!(session.retrieve_asset(&url, &url).is_err());
+        assert!(started.elapsed() < Duration::from_secs(3));
+    }
+}
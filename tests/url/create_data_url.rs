@@ -9,6 +9,7 @@
 mod passing {
     use reqwest::Url;
 
+    use monolith::core::DataUrlEncoding;
     use monolith::url;
 
     #[test]
@@ -106,4 +107,42 @@ mod passing {
 
         assert_eq!(data_url.as_str(), "data:text/plain;charset=utf8;base64,");
     }
+
+    #[test]
+    fn create_data_url_percent_encoded() {
+        let data = "p { content: \"#1\" }\n";
+        let data_url = url::create_data_url_with_encoding(
+            "text/css",
+            "utf-8",
+            data.as_bytes(),
+            &Url::parse("data:,").unwrap(),
+            DataUrlEncoding::PercentEncode,
+        );
+
+        assert_eq!(
+            data_url.as_str(),
+            "data:text/css;charset=utf-8,p%20{%20content:%20%22%231%22%20}%0A"
+        );
+    }
+
+    #[test]
+    fn create_data_url_auto_picks_shorter_encoding() {
+        let text = url::create_data_url_with_encoding(
+            "text/css",
+            "",
+            b"a{color:red}",
+            &Url::parse("data:,").unwrap(),
+            DataUrlEncoding::Auto,
+        );
+        assert_eq!(text.as_str(), "data:text/css,a{color:red}");
+
+        let binary = url::create_data_url_with_encoding(
+            "image/gif",
+            "",
+            &[0xFF; 12],
+            &Url::parse("data:,").unwrap(),
+            DataUrlEncoding::Auto,
+        );
+        assert_eq!(binary.as_str(), "data:image/gif;base64,////////////////");
+    }
 }
@@ -0,0 +1,86 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::url::host_matches_pattern;
+
+    #[test]
+    fn plain_pattern_matches_host() {
+        assert!(host_matches_pattern(
+            "google-analytics.com",
+            "google-analytics.com"
+        ));
+    }
+
+    #[test]
+    fn plain_pattern_matches_sub_domains() {
+        assert!(host_matches_pattern(
+            "www.google-analytics.com",
+            "google-analytics.com"
+        ));
+    }
+
+    #[test]
+    fn leading_wildcard() {
+        assert!(host_matches_pattern(
+            "stats.g.doubleclick.net",
+            "*.doubleclick.net"
+        ));
+    }
+
+    #[test]
+    fn trailing_wildcard() {
+        assert!(host_matches_pattern("ads.example.org", "ads.*"));
+    }
+
+    #[test]
+    fn ignores_case_and_trailing_dot() {
+        assert!(host_matches_pattern(
+            "Pixel.Example.COM.",
+            "pixel.example.com"
+        ));
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::url::host_matches_pattern;
+
+    #[test]
+    fn plain_pattern_does_not_match_suffix_of_label() {
+        assert!(!host_matches_pattern(
+            "notgoogle-analytics.com",
+            "google-analytics.com"
+        ));
+    }
+
+    #[test]
+    fn sub_domain_wildcard_requires_sub_domain() {
+        assert!(!host_matches_pattern(
+            "doubleclick.net",
+            "*.doubleclick.net"
+        ));
+    }
+
+    #[test]
+    fn wildcard_does_not_match_other_hosts() {
+        assert!(!host_matches_pattern("example.com", "ads.*"));
+    }
+
+    #[test]
+    fn empty_pattern() {
+        assert!(!host_matches_pattern("example.com", ""));
+    }
+}
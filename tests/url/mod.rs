@@ -2,6 +2,8 @@ mod clean_url;
 mod create_data_url;
 mod domain_is_within_domain;
 mod get_referer_url;
+mod host_matches_pattern;
 mod is_url_and_has_protocol;
 mod parse_data_url;
 mod resolve_url;
+mod url_matches_pattern;
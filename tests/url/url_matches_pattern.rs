@@ -0,0 +1,71 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::url::{url_matches_pattern, Url};
+
+    fn url(url: &str) -> Url {
+        Url::parse(url).unwrap()
+    }
+
+    #[test]
+    fn extension_ignores_query_and_case() {
+        assert!(url_matches_pattern(
+            &url("https://cdn.example.com/fonts/Icons.WOFF2?v=3#x"),
+            ".woff2"
+        ));
+    }
+
+    #[test]
+    fn glob_matches_host_and_path() {
+        assert!(url_matches_pattern(
+            &url("https://cdn.example.com/fonts/a/b.ttf"),
+            "cdn.example.com/fonts/*"
+        ));
+        assert!(url_matches_pattern(
+            &url("https://cdn.example.com/img/logo.svg"),
+            "*.svg"
+        ));
+    }
+
+    #[test]
+    fn pattern_with_scheme_matches_whole_url() {
+        assert!(url_matches_pattern(
+            &url("https://cdn.example.com/icons/logo.svg?v=1"),
+            "https://*.example.com/icons/*"
+        ));
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::url::{url_matches_pattern, Url};
+
+    #[test]
+    fn extension_must_end_the_path() {
+        assert!(!url_matches_pattern(
+            &Url::parse("https://example.com/f.woff2.map").unwrap(),
+            ".woff2"
+        ));
+    }
+
+    #[test]
+    fn pattern_without_scheme_does_not_see_scheme() {
+        assert!(!url_matches_pattern(
+            &Url::parse("http://example.com/a.svg").unwrap(),
+            "http*"
+        ));
+    }
+}